@@ -7,7 +7,7 @@ use crate::attester_cache::{AttesterCache, AttesterCacheKey};
 use crate::beacon_block_streamer::{BeaconBlockStreamer, CheckCaches};
 use crate::beacon_proposer_cache::compute_proposer_duties_from_head;
 use crate::beacon_proposer_cache::BeaconProposerCache;
-use crate::blob_verification::{GossipBlobError, GossipVerifiedBlob};
+use crate::blob_verification::{GossipBlobError, GossipVerifiedBlob, KzgVerifiedBlobList};
 use crate::block_times_cache::BlockTimesCache;
 use crate::block_verification::POS_PANDA_BANNER;
 use crate::block_verification::{
@@ -123,6 +123,7 @@ use task_executor::{ShutdownReason, TaskExecutor};
 use tokio_stream::Stream;
 use tree_hash::TreeHash;
 use types::blob_sidecar::FixedBlobSidecarList;
+use types::light_client_update::MAX_REQUEST_LIGHT_CLIENT_UPDATES;
 use types::payload::BlockProductionVersion;
 use types::*;
 
@@ -1228,6 +1229,18 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
+    /// Returns the data column at the given root and index, if any.
+    ///
+    /// ## Errors
+    /// May return a database error.
+    pub fn get_data_column(
+        &self,
+        block_root: &Hash256,
+        column_index: &ColumnIndex,
+    ) -> Result<Option<Arc<DataColumnSidecar<T::EthSpec>>>, Error> {
+        Ok(self.store.get_data_column(block_root, column_index)?)
+    }
+
     pub fn get_blinded_block(
         &self,
         block_root: &Hash256,
@@ -2835,13 +2848,20 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                                 imported_blocks += 1;
                             }
                             AvailabilityProcessingStatus::MissingComponents(slot, block_root) => {
-                                warn!(self.log, "Blobs missing in response to range request";
-                                    "block_root" => ?block_root, "slot" => slot);
+                                let error = if self.spec.is_peer_das_enabled_for_epoch(
+                                    slot.epoch(T::EthSpec::slots_per_epoch()),
+                                ) {
+                                    warn!(self.log, "Custody columns missing in response to range request";
+                                        "block_root" => ?block_root, "slot" => slot);
+                                    AvailabilityCheckError::MissingCustodyColumns
+                                } else {
+                                    warn!(self.log, "Blobs missing in response to range request";
+                                        "block_root" => ?block_root, "slot" => slot);
+                                    AvailabilityCheckError::MissingBlobs
+                                };
                                 return ChainSegmentResult::Failed {
                                     imported_blocks,
-                                    error: BlockError::AvailabilityCheck(
-                                        AvailabilityCheckError::MissingBlobs,
-                                    ),
+                                    error: BlockError::AvailabilityCheck(error),
                                 };
                             }
                         }
@@ -3018,6 +3038,40 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self.remove_notified(&block_root, r)
     }
 
+    /// Cache the blobs fetched from the execution layer, process them, then evict them from the
+    /// processing cache if the block was imported or errors.
+    pub async fn process_engine_blobs(
+        self: &Arc<Self>,
+        slot: Slot,
+        block_root: Hash256,
+        blobs: KzgVerifiedBlobList<T::EthSpec>,
+    ) -> Result<AvailabilityProcessingStatus, BlockError<T::EthSpec>> {
+        // If this block has already been imported to forkchoice it must have been available, so
+        // we don't need to process its blobs again.
+        if self
+            .canonical_head
+            .fork_choice_read_lock()
+            .contains_block(&block_root)
+        {
+            return Err(BlockError::BlockIsAlreadyKnown(block_root));
+        }
+
+        if let Some(event_handler) = self.event_handler.as_ref() {
+            if event_handler.has_blob_sidecar_subscribers() {
+                for blob in blobs.iter() {
+                    event_handler.register(EventKind::BlobSidecar(
+                        SseBlobSidecar::from_blob_sidecar(blob.as_blob()),
+                    ));
+                }
+            }
+        }
+
+        let r = self
+            .check_engine_blob_availability_and_import(slot, block_root, blobs)
+            .await;
+        self.remove_notified(&block_root, r)
+    }
+
     /// Remove any block components from the *processing cache* if we no longer require them. If the
     /// block was imported full or erred, we no longer require them.
     fn remove_notified(
@@ -3356,6 +3410,23 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self.process_availability(slot, availability).await
     }
 
+    /// Checks if the provided blobs fetched from the execution layer can make any cached blocks
+    /// available, and imports immediately if so, otherwise caches the blobs in the data
+    /// availability checker.
+    async fn check_engine_blob_availability_and_import(
+        self: &Arc<Self>,
+        slot: Slot,
+        block_root: Hash256,
+        blobs: KzgVerifiedBlobList<T::EthSpec>,
+    ) -> Result<AvailabilityProcessingStatus, BlockError<T::EthSpec>> {
+        let epoch = slot.epoch(T::EthSpec::slots_per_epoch());
+        let availability = self
+            .data_availability_checker
+            .put_engine_blobs(block_root, epoch, blobs)?;
+
+        self.process_availability(slot, availability).await
+    }
+
     /// Imports a fully available block. Otherwise, returns `AvailabilityProcessingStatus::MissingComponents`
     ///
     /// An error is returned if the block was unable to be imported. It may be partially imported
@@ -3627,7 +3698,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         // If the write fails, revert fork choice to the version from disk, else we can
         // end up with blocks in fork choice that are missing from disk.
         // See https://github.com/sigp/lighthouse/issues/2028
-        let (_, signed_block, blobs) = signed_block.deconstruct();
+        let (_, signed_block, blobs, data_columns) = signed_block.deconstruct();
         let block = signed_block.message();
         ops.extend(
             confirmed_state_roots
@@ -3648,6 +3719,17 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
         }
 
+        if let Some(data_columns) = data_columns {
+            if !data_columns.is_empty() {
+                debug!(
+                    self.log, "Writing data columns to store";
+                    "block_root" => %block_root,
+                    "count" => data_columns.len(),
+                );
+                ops.push(StoreOp::PutDataColumns(block_root, data_columns));
+            }
+        }
+
         let txn_lock = self.store.hot_db.begin_rw_transaction();
 
         if let Err(e) = self.store.do_atomically_with_block_and_blobs_cache(ops) {
@@ -6797,6 +6879,26 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
+    /// Gets the best `LightClientUpdate` of each period in `start_period..start_period + count`.
+    ///
+    /// The result is truncated at the first period without a stored update, so that it only
+    /// contains consecutive periods.
+    pub fn get_light_client_updates(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Vec<LightClientUpdate<T::EthSpec>>, Error> {
+        let count = std::cmp::min(count, MAX_REQUEST_LIGHT_CLIENT_UPDATES);
+        let mut updates = vec![];
+        for period in start_period..start_period.saturating_add(count) {
+            let Some(update) = self.store.get_light_client_update(period)? else {
+                break;
+            };
+            updates.push(update);
+        }
+        Ok(updates)
+    }
+
     pub fn metrics(&self) -> BeaconChainMetrics {
         BeaconChainMetrics {
             reqresp_pre_import_cache_len: self.reqresp_pre_import_cache.read().len(),
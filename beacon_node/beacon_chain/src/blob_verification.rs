@@ -353,6 +353,12 @@ impl<E: EthSpec> KzgVerifiedBlobList<E> {
     }
 }
 
+impl<E: EthSpec> KzgVerifiedBlobList<E> {
+    pub fn iter(&self) -> impl Iterator<Item = &KzgVerifiedBlob<E>> {
+        self.verified_blobs.iter()
+    }
+}
+
 impl<E: EthSpec> IntoIterator for KzgVerifiedBlobList<E> {
     type Item = KzgVerifiedBlob<E>;
     type IntoIter = std::vec::IntoIter<Self::Item>;
@@ -11,18 +11,24 @@ use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 use types::blob_sidecar::{BlobIdentifier, BlobSidecarError, FixedBlobSidecarList};
 use types::{
-    BeaconBlockRef, BeaconState, BlindedPayload, BlobSidecarList, Epoch, EthSpec, Hash256,
-    SignedBeaconBlock, SignedBeaconBlockHeader, Slot,
+    BeaconBlockRef, BeaconState, BlindedPayload, BlobSidecarList, ColumnIndex,
+    DataColumnSidecarList, Epoch, EthSpec, Hash256, SignedBeaconBlock, SignedBeaconBlockHeader,
+    Slot,
 };
 
-/// A block that has been received over RPC. It has 2 internal variants:
+/// A block that has been received over RPC. It has 3 internal variants:
 ///
 /// 1. `BlockAndBlobs`: A fully available post deneb block with all the blobs available. This variant
 ///    is only constructed after making consistency checks between blocks and blobs.
 ///    Hence, it is fully self contained w.r.t verification. i.e. this block has all the required
 ///    data to get verified and imported into fork choice.
 ///
-/// 2. `Block`: This can be a fully available pre-deneb block **or** a post-deneb block that may or may
+/// 2. `BlockAndCustodyColumns`: A post PeerDAS block with the data columns this node custodies,
+///    and the indices of those custody columns. The block and columns are checked for consistency
+///    on construction, but it is up to the `DataAvailabilityChecker` to determine whether exactly
+///    the custody columns are present.
+///
+/// 3. `Block`: This can be a fully available pre-deneb block **or** a post-deneb block that may or may
 ///    not require blobs to be considered fully available.
 ///
 /// Note: We make a distinction over blocks received over gossip because
@@ -50,6 +56,7 @@ impl<E: EthSpec> RpcBlock<E> {
         match &self.block {
             RpcBlockInner::Block(block) => block,
             RpcBlockInner::BlockAndBlobs(block, _) => block,
+            RpcBlockInner::BlockAndCustodyColumns(block, _, _) => block,
         }
     }
 
@@ -57,6 +64,7 @@ impl<E: EthSpec> RpcBlock<E> {
         match &self.block {
             RpcBlockInner::Block(block) => block.clone(),
             RpcBlockInner::BlockAndBlobs(block, _) => block.clone(),
+            RpcBlockInner::BlockAndCustodyColumns(block, _, _) => block.clone(),
         }
     }
 
@@ -64,6 +72,15 @@ impl<E: EthSpec> RpcBlock<E> {
         match &self.block {
             RpcBlockInner::Block(_) => None,
             RpcBlockInner::BlockAndBlobs(_, blobs) => Some(blobs),
+            RpcBlockInner::BlockAndCustodyColumns(_, _, _) => None,
+        }
+    }
+
+    pub fn custody_columns(&self) -> Option<&DataColumnSidecarList<E>> {
+        match &self.block {
+            RpcBlockInner::Block(_) => None,
+            RpcBlockInner::BlockAndBlobs(_, _) => None,
+            RpcBlockInner::BlockAndCustodyColumns(_, data_columns, _) => Some(data_columns),
         }
     }
 }
@@ -79,6 +96,14 @@ enum RpcBlockInner<E: EthSpec> {
     /// This variant is used with parent lookups and by-range responses. It should have all blobs
     /// ordered, all block roots matching, and the correct number of blobs for this block.
     BlockAndBlobs(Arc<SignedBeaconBlock<E>>, BlobSidecarList<E>),
+    /// This variant is used with by-range responses post PeerDAS. It should have all custody
+    /// columns of this node ordered by index, with all block roots and commitments matching, along
+    /// with the sorted indices of this node's custody columns.
+    BlockAndCustodyColumns(
+        Arc<SignedBeaconBlock<E>>,
+        DataColumnSidecarList<E>,
+        Vec<ColumnIndex>,
+    ),
 }
 
 impl<E: EthSpec> RpcBlock<E> {
@@ -136,6 +161,48 @@ impl<E: EthSpec> RpcBlock<E> {
         })
     }
 
+    /// Constructs a new `BlockAndCustodyColumns` variant after making consistency checks between
+    /// the provided block and data columns. As with `new`, this makes no guarantees about which
+    /// columns are present: the `DataAvailabilityChecker` checks them against
+    /// `custody_column_indices`, which should be the custody columns of this node.
+    pub fn new_with_custody_columns(
+        block_root: Option<Hash256>,
+        block: Arc<SignedBeaconBlock<E>>,
+        mut custody_columns: DataColumnSidecarList<E>,
+        mut custody_column_indices: Vec<ColumnIndex>,
+    ) -> Result<Self, AvailabilityCheckError> {
+        let block_root = block_root.unwrap_or_else(|| get_block_root(&block));
+
+        if let Ok(block_commitments) = block.message().body().blob_kzg_commitments() {
+            // The block has no data, so no columns are expected.
+            if block_commitments.is_empty() {
+                custody_columns.clear();
+            }
+            for data_column in custody_columns.iter() {
+                if data_column.block_root() != block_root
+                    || data_column.kzg_commitments != *block_commitments
+                {
+                    return Err(AvailabilityCheckError::InconsistentDataColumn(
+                        data_column.index,
+                    ));
+                }
+            }
+        }
+        custody_columns.sort_by_key(|data_column| data_column.index);
+        custody_column_indices.sort_unstable();
+        custody_column_indices.dedup();
+
+        let inner = if custody_columns.is_empty() {
+            RpcBlockInner::Block(block)
+        } else {
+            RpcBlockInner::BlockAndCustodyColumns(block, custody_columns, custody_column_indices)
+        };
+        Ok(Self {
+            block_root,
+            block: inner,
+        })
+    }
+
     pub fn new_from_fixed(
         block_root: Hash256,
         block: Arc<SignedBeaconBlock<E>>,
@@ -153,25 +220,41 @@ impl<E: EthSpec> RpcBlock<E> {
         Self::new(Some(block_root), block, blobs)
     }
 
+    /// Returns the block root, block, blobs and custody columns along with their expected
+    /// indices.
+    #[allow(clippy::type_complexity)]
     pub fn deconstruct(
         self,
     ) -> (
         Hash256,
         Arc<SignedBeaconBlock<E>>,
         Option<BlobSidecarList<E>>,
+        Option<(DataColumnSidecarList<E>, Vec<ColumnIndex>)>,
     ) {
         let block_root = self.block_root();
         match self.block {
-            RpcBlockInner::Block(block) => (block_root, block, None),
-            RpcBlockInner::BlockAndBlobs(block, blobs) => (block_root, block, Some(blobs)),
+            RpcBlockInner::Block(block) => (block_root, block, None, None),
+            RpcBlockInner::BlockAndBlobs(block, blobs) => (block_root, block, Some(blobs), None),
+            RpcBlockInner::BlockAndCustodyColumns(block, data_columns, custody_column_indices) => (
+                block_root,
+                block,
+                None,
+                Some((data_columns, custody_column_indices)),
+            ),
         }
     }
     pub fn n_blobs(&self) -> usize {
         match &self.block {
-            RpcBlockInner::Block(_) => 0,
+            RpcBlockInner::Block(_) | RpcBlockInner::BlockAndCustodyColumns(_, _, _) => 0,
             RpcBlockInner::BlockAndBlobs(_, blobs) => blobs.len(),
         }
     }
+    pub fn n_data_columns(&self) -> usize {
+        match &self.block {
+            RpcBlockInner::Block(_) | RpcBlockInner::BlockAndBlobs(_, _) => 0,
+            RpcBlockInner::BlockAndCustodyColumns(_, data_columns, _) => data_columns.len(),
+        }
+    }
 }
 
 /// A block that has gone through all pre-deneb block processing checks including block processing
@@ -517,12 +600,16 @@ impl<E: EthSpec> AsBlock<E> for AvailableBlock<E> {
     }
 
     fn into_rpc_block(self) -> RpcBlock<E> {
-        let (block_root, block, blobs_opt) = self.deconstruct();
+        let (block_root, block, blobs_opt, data_columns_opt) = self.deconstruct();
         // Circumvent the constructor here, because an Available block will have already had
         // consistency checks performed.
-        let inner = match blobs_opt {
-            None => RpcBlockInner::Block(block),
-            Some(blobs) => RpcBlockInner::BlockAndBlobs(block, blobs),
+        let inner = match (blobs_opt, data_columns_opt) {
+            (None, None) => RpcBlockInner::Block(block),
+            (Some(blobs), _) => RpcBlockInner::BlockAndBlobs(block, blobs),
+            (None, Some(data_columns)) => {
+                let custody_column_indices = data_columns.iter().map(|c| c.index).collect();
+                RpcBlockInner::BlockAndCustodyColumns(block, data_columns, custody_column_indices)
+            }
         };
         RpcBlock {
             block_root,
@@ -554,12 +641,14 @@ impl<E: EthSpec> AsBlock<E> for RpcBlock<E> {
         match &self.block {
             RpcBlockInner::Block(block) => block,
             RpcBlockInner::BlockAndBlobs(block, _) => block,
+            RpcBlockInner::BlockAndCustodyColumns(block, _, _) => block,
         }
     }
     fn block_cloned(&self) -> Arc<SignedBeaconBlock<E>> {
         match &self.block {
             RpcBlockInner::Block(block) => block.clone(),
             RpcBlockInner::BlockAndBlobs(block, _) => block.clone(),
+            RpcBlockInner::BlockAndCustodyColumns(block, _, _) => block.clone(),
         }
     }
     fn canonical_root(&self) -> Hash256 {
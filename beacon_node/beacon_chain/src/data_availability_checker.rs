@@ -3,6 +3,7 @@ use crate::block_verification_types::{
     AvailabilityPendingExecutedBlock, AvailableExecutedBlock, RpcBlock,
 };
 use crate::data_availability_checker::overflow_lru_cache::DataAvailabilityCheckerInner;
+use crate::data_column_verification::verify_kzg_for_data_column_list;
 use crate::{BeaconChain, BeaconChainTypes, BeaconStore};
 use kzg::Kzg;
 use slog::{debug, error, Logger};
@@ -14,7 +15,10 @@ use std::sync::Arc;
 use std::time::Duration;
 use task_executor::TaskExecutor;
 use types::blob_sidecar::{BlobIdentifier, BlobSidecar, FixedBlobSidecarList};
-use types::{BlobSidecarList, ChainSpec, Epoch, EthSpec, Hash256, SignedBeaconBlock};
+use types::{
+    BlobSidecarList, ChainSpec, ColumnIndex, DataColumnIdentifier, DataColumnSidecar,
+    DataColumnSidecarList, Epoch, EthSpec, Hash256, SignedBeaconBlock,
+};
 
 mod error;
 mod overflow_lru_cache;
@@ -60,6 +64,8 @@ pub const STATE_LRU_CAPACITY: usize = STATE_LRU_CAPACITY_NON_ZERO.get();
 /// data during moments of unstable network conditions.
 pub struct DataAvailabilityChecker<T: BeaconChainTypes> {
     availability_cache: Arc<DataAvailabilityCheckerInner<T>>,
+    /// The number of data columns the node is custodying.
+    custody_column_count: usize,
     slot_clock: T::SlotClock,
     kzg: Option<Arc<Kzg>>,
     log: Logger,
@@ -92,11 +98,16 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
         slot_clock: T::SlotClock,
         kzg: Option<Arc<Kzg>>,
         store: BeaconStore<T>,
+        import_all_data_columns: bool,
         log: &Logger,
         spec: ChainSpec,
     ) -> Result<Self, AvailabilityCheckError> {
-        // TODO(das): support supernode or custom custody requirement
-        let custody_subnet_count = spec.custody_requirement as usize;
+        // This must match the custody subnet count advertised in our ENR.
+        let custody_subnet_count = if import_all_data_columns {
+            spec.data_column_sidecar_subnet_count as usize
+        } else {
+            spec.custody_requirement as usize
+        };
         let custody_column_count =
             custody_subnet_count.saturating_mul(spec.data_columns_per_subnet());
 
@@ -108,6 +119,7 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
         )?;
         Ok(Self {
             availability_cache: Arc::new(overflow_cache),
+            custody_column_count,
             slot_clock,
             log: log.clone(),
             kzg,
@@ -148,6 +160,14 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
         self.availability_cache.peek_blob(blob_id)
     }
 
+    /// Get a data column from the availability cache.
+    pub fn get_data_column(
+        &self,
+        data_column_id: &DataColumnIdentifier,
+    ) -> Result<Option<Arc<DataColumnSidecar<T::EthSpec>>>, AvailabilityCheckError> {
+        self.availability_cache.peek_data_column(data_column_id)
+    }
+
     /// Put a list of blobs received via RPC into the availability cache. This performs KZG
     /// verification on the blobs in the list.
     pub fn put_rpc_blobs(
@@ -173,6 +193,18 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
             .put_kzg_verified_blobs(block_root, epoch, verified_blobs)
     }
 
+    /// Put a list of blobs fetched from the execution layer into the availability cache. The blobs
+    /// must have already been KZG verified.
+    pub fn put_engine_blobs(
+        &self,
+        block_root: Hash256,
+        epoch: Epoch,
+        blobs: KzgVerifiedBlobList<T::EthSpec>,
+    ) -> Result<Availability<T::EthSpec>, AvailabilityCheckError> {
+        self.availability_cache
+            .put_kzg_verified_blobs(block_root, epoch, blobs)
+    }
+
     /// Check if we've cached other blobs for this block. If it completes a set and we also
     /// have a block cached, return the `Availability` variant triggering block import.
     /// Otherwise cache the blob sidecar.
@@ -221,7 +253,27 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
         &self,
         block: RpcBlock<T::EthSpec>,
     ) -> Result<MaybeAvailableBlock<T::EthSpec>, AvailabilityCheckError> {
-        let (block_root, block, blobs) = block.deconstruct();
+        let (block_root, block, blobs, data_columns) = block.deconstruct();
+        if self.data_columns_required_for_block(&block) {
+            return match data_columns {
+                Some((data_column_list, custody_column_indices)) => {
+                    let kzg = self
+                        .kzg
+                        .as_ref()
+                        .ok_or(AvailabilityCheckError::KzgNotInitialized)?;
+                    verify_kzg_for_data_column_list(data_column_list.iter(), kzg)
+                        .map_err(AvailabilityCheckError::Kzg)?;
+                    Ok(self.make_available_with_custody_columns(
+                        block_root,
+                        block,
+                        data_column_list,
+                        &custody_column_indices,
+                    ))
+                }
+                None => Ok(MaybeAvailableBlock::AvailabilityPending { block_root, block }),
+            };
+        }
+
         match blobs {
             None => {
                 if self.blobs_required_for_block(&block) {
@@ -231,6 +283,7 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
                         block_root,
                         block,
                         blobs: None,
+                        data_columns: None,
                         blobs_available_timestamp: None,
                     }))
                 }
@@ -251,6 +304,7 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
                     block_root,
                     block,
                     blobs: verified_blobs,
+                    data_columns: None,
                     blobs_available_timestamp: None,
                 }))
             }
@@ -286,8 +340,40 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
             verify_kzg_for_blob_list(all_blobs.iter(), kzg)?;
         }
 
+        let all_data_columns: DataColumnSidecarList<T::EthSpec> = blocks
+            .iter()
+            .filter(|block| self.data_columns_required_for_block(block.as_block()))
+            // this clone is cheap as it's cloning an Arc
+            .filter_map(|block| block.custody_columns().cloned())
+            .flatten()
+            .collect::<Vec<_>>();
+
+        // verify kzg for all data columns at once
+        if !all_data_columns.is_empty() {
+            let kzg = self
+                .kzg
+                .as_ref()
+                .ok_or(AvailabilityCheckError::KzgNotInitialized)?;
+            verify_kzg_for_data_column_list(all_data_columns.iter(), kzg)?;
+        }
+
         for block in blocks {
-            let (block_root, block, blobs) = block.deconstruct();
+            let (block_root, block, blobs, data_columns) = block.deconstruct();
+            if self.data_columns_required_for_block(&block) {
+                // already verified kzg for all data columns
+                results.push(match data_columns {
+                    Some((data_column_list, custody_column_indices)) => self
+                        .make_available_with_custody_columns(
+                            block_root,
+                            block,
+                            data_column_list,
+                            &custody_column_indices,
+                        ),
+                    None => MaybeAvailableBlock::AvailabilityPending { block_root, block },
+                });
+                continue;
+            }
+
             match blobs {
                 None => {
                     if self.blobs_required_for_block(&block) {
@@ -297,6 +383,7 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
                             block_root,
                             block,
                             blobs: None,
+                            data_columns: None,
                             blobs_available_timestamp: None,
                         }))
                     }
@@ -312,6 +399,7 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
                         block_root,
                         block,
                         blobs: verified_blobs,
+                        data_columns: None,
                         blobs_available_timestamp: None,
                     }))
                 }
@@ -323,8 +411,49 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
 
     /// Determines the blob requirements for a block. If the block is pre-deneb, no blobs are required.
     /// If the block's epoch is from prior to the data availability boundary, no blobs are required.
+    /// Post PeerDAS, custody columns are required instead of blobs.
     fn blobs_required_for_block(&self, block: &SignedBeaconBlock<T::EthSpec>) -> bool {
-        block.num_expected_blobs() > 0 && self.da_check_required_for_epoch(block.epoch())
+        block.num_expected_blobs() > 0
+            && self.da_check_required_for_epoch(block.epoch())
+            && !self.spec.is_peer_das_enabled_for_epoch(block.epoch())
+    }
+
+    /// Determines the data column requirements for a block. Custody columns are only required for
+    /// blocks with blobs, from the PeerDAS fork onwards and within the data availability boundary.
+    fn data_columns_required_for_block(&self, block: &SignedBeaconBlock<T::EthSpec>) -> bool {
+        block.num_expected_blobs() > 0
+            && self.da_check_required_for_epoch(block.epoch())
+            && self.spec.is_peer_das_enabled_for_epoch(block.epoch())
+    }
+
+    /// Returns an `Available` block if `data_columns` are exactly the columns this node custodies,
+    /// and `AvailabilityPending` otherwise. The columns MUST already be KZG verified.
+    ///
+    /// `custody_column_indices` are the custody columns of this node, as advertised in its ENR.
+    fn make_available_with_custody_columns(
+        &self,
+        block_root: Hash256,
+        block: Arc<SignedBeaconBlock<T::EthSpec>>,
+        data_columns: DataColumnSidecarList<T::EthSpec>,
+        custody_column_indices: &[ColumnIndex],
+    ) -> MaybeAvailableBlock<T::EthSpec> {
+        // `RpcBlock` guarantees that both the columns and the custody indices are sorted, so this
+        // also rejects duplicate and non-custody columns.
+        let is_custody_columns = custody_column_indices.len() == self.custody_column_count
+            && data_columns
+                .iter()
+                .map(|data_column| data_column.index)
+                .eq(custody_column_indices.iter().copied());
+        if !is_custody_columns {
+            return MaybeAvailableBlock::AvailabilityPending { block_root, block };
+        }
+        MaybeAvailableBlock::Available(AvailableBlock {
+            block_root,
+            block,
+            blobs: None,
+            data_columns: Some(data_columns),
+            blobs_available_timestamp: None,
+        })
     }
 
     /// The epoch at which we require a data availability check in block processing.
@@ -477,6 +606,7 @@ pub struct AvailableBlock<E: EthSpec> {
     block_root: Hash256,
     block: Arc<SignedBeaconBlock<E>>,
     blobs: Option<BlobSidecarList<E>>,
+    data_columns: Option<DataColumnSidecarList<E>>,
     /// Timestamp at which this block first became available (UNIX timestamp, time since 1970).
     blobs_available_timestamp: Option<Duration>,
 }
@@ -491,6 +621,7 @@ impl<E: EthSpec> AvailableBlock<E> {
             block_root,
             block,
             blobs,
+            data_columns: None,
             blobs_available_timestamp: None,
         }
     }
@@ -506,6 +637,10 @@ impl<E: EthSpec> AvailableBlock<E> {
         self.blobs.as_ref()
     }
 
+    pub fn data_columns(&self) -> Option<&DataColumnSidecarList<E>> {
+        self.data_columns.as_ref()
+    }
+
     pub fn blobs_available_timestamp(&self) -> Option<Duration> {
         self.blobs_available_timestamp
     }
@@ -516,14 +651,16 @@ impl<E: EthSpec> AvailableBlock<E> {
         Hash256,
         Arc<SignedBeaconBlock<E>>,
         Option<BlobSidecarList<E>>,
+        Option<DataColumnSidecarList<E>>,
     ) {
         let AvailableBlock {
             block_root,
             block,
             blobs,
+            data_columns,
             blobs_available_timestamp: _,
         } = self;
-        (block_root, block, blobs)
+        (block_root, block, blobs, data_columns)
     }
 }
 
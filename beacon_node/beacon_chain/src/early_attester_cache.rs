@@ -69,7 +69,7 @@ impl<E: EthSpec> EarlyAttesterCache<E> {
             },
         };
 
-        let (_, block, blobs) = block.deconstruct();
+        let (_, block, blobs, _data_columns) = block.deconstruct();
         let item = CacheItem {
             epoch,
             committee_lengths,
@@ -0,0 +1,162 @@
+//! This module implements an optimisation to fetch blobs via JSON-RPC from the EL.
+//!
+//! If a blob has already been seen in the public mempool, then it is often unnecessary to wait for
+//! it to arrive on P2P gossip. This module uses `engine_getBlobsV1` to fetch the blobs from the
+//! EL's blob pool, builds the `BlobSidecar`s locally using the KZG commitment inclusion proofs
+//! computed from the block body, and imports them once they have been KZG verified. Blobs that we
+//! have not yet seen on gossip are published so that they propagate across the network faster.
+use crate::blob_verification::KzgVerifiedBlobList;
+use crate::observed_blob_sidecars::Error as ObservedBlobSidecarsError;
+use crate::{AvailabilityProcessingStatus, BeaconChain, BeaconChainTypes, BlockError};
+use kzg::Error as KzgError;
+use slog::{debug, error};
+use slot_clock::SlotClock;
+use state_processing::per_block_processing::deneb::kzg_commitment_to_versioned_hash;
+use std::sync::Arc;
+use types::blob_sidecar::{BlobSidecarError, FixedBlobSidecarList};
+use types::{BlobSidecar, EthSpec, Hash256, SignedBeaconBlock};
+
+#[derive(Debug)]
+pub enum FetchEngineBlobError<E: EthSpec> {
+    BlobProcessingError(BlockError<E>),
+    BlobSidecarError(BlobSidecarError),
+    ExecutionLayerMissing,
+    InternalError(String),
+    KzgError(KzgError),
+    KzgNotInitialized,
+    ObservedBlobSidecarsError(ObservedBlobSidecarsError),
+    RequestFailed(execution_layer::Error),
+    SlotClockError,
+}
+
+/// Fetches blobs from the EL mempool and processes them. It also broadcasts unseen blobs via
+/// gossip using the provided `publish_fn`.
+///
+/// Returns `Ok(None)` if the block has no blobs or if the EL did not return any of them.
+pub async fn fetch_and_process_engine_blobs<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    block_root: Hash256,
+    block: Arc<SignedBeaconBlock<T::EthSpec>>,
+    publish_fn: impl FnOnce(Vec<Arc<BlobSidecar<T::EthSpec>>>) + Send + 'static,
+) -> Result<Option<AvailabilityProcessingStatus>, FetchEngineBlobError<T::EthSpec>> {
+    // Once PeerDAS is enabled, blobs are distributed as data columns rather than blob sidecars.
+    if chain.spec.is_peer_das_enabled_for_epoch(block.epoch()) {
+        return Ok(None);
+    }
+
+    let versioned_hashes = match block.message().body().blob_kzg_commitments() {
+        Ok(kzg_commitments) => kzg_commitments
+            .iter()
+            .map(kzg_commitment_to_versioned_hash)
+            .collect::<Vec<_>>(),
+        // Pre-Deneb blocks have no blobs to fetch.
+        Err(_) => return Ok(None),
+    };
+    let num_expected_blobs = versioned_hashes.len();
+    if num_expected_blobs == 0 {
+        return Ok(None);
+    }
+
+    let execution_layer = chain
+        .execution_layer
+        .as_ref()
+        .ok_or(FetchEngineBlobError::ExecutionLayerMissing)?;
+
+    debug!(
+        chain.log,
+        "Fetching blobs from the EL";
+        "num_expected_blobs" => num_expected_blobs,
+        "block_root" => %block_root,
+    );
+    let response = execution_layer
+        .get_blobs(versioned_hashes)
+        .await
+        .map_err(FetchEngineBlobError::RequestFailed)?;
+
+    let num_fetched_blobs = response.iter().filter(|opt| opt.is_some()).count();
+    if num_fetched_blobs == 0 {
+        debug!(
+            chain.log,
+            "No blobs fetched from the EL";
+            "num_expected_blobs" => num_expected_blobs,
+            "block_root" => %block_root,
+        );
+        return Ok(None);
+    }
+
+    let mut fixed_blob_sidecar_list = FixedBlobSidecarList::default();
+    for (index, blob_and_proof) in response
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, opt_blob)| Some((index, opt_blob?)))
+    {
+        // The inclusion proof is computed from the block body, so the sidecar is valid with
+        // respect to the block as long as the KZG proof is.
+        let blob_sidecar =
+            BlobSidecar::new(index, blob_and_proof.blob, &block, blob_and_proof.proof)
+                .map_err(FetchEngineBlobError::BlobSidecarError)?;
+        let Some(blob_mut) = fixed_blob_sidecar_list.get_mut(index) else {
+            return Err(FetchEngineBlobError::InternalError(format!(
+                "Blob index {index} out of bounds"
+            )));
+        };
+        *blob_mut = Some(Arc::new(blob_sidecar));
+    }
+
+    let kzg = chain
+        .kzg
+        .as_ref()
+        .ok_or(FetchEngineBlobError::KzgNotInitialized)?;
+    let seen_timestamp = chain
+        .slot_clock
+        .now_duration()
+        .ok_or(FetchEngineBlobError::SlotClockError)?;
+    let verified_blobs = KzgVerifiedBlobList::new(
+        Vec::from(fixed_blob_sidecar_list).into_iter().flatten(),
+        kzg,
+        seen_timestamp,
+    )
+    .map_err(FetchEngineBlobError::KzgError)?;
+
+    // Publish the blobs that we have not yet seen on gossip. Observing them here also ensures
+    // that copies arriving later over gossip are ignored rather than re-processed. The block has
+    // passed proposer signature verification, so the sidecar headers are safe to observe.
+    let mut blobs_to_publish = vec![];
+    {
+        let mut observed_blob_sidecars = chain.observed_blob_sidecars.write();
+        for blob in verified_blobs.iter() {
+            let already_seen = observed_blob_sidecars
+                .observe_sidecar(blob.as_blob())
+                .map_err(FetchEngineBlobError::ObservedBlobSidecarsError)?;
+            if !already_seen {
+                blobs_to_publish.push(blob.clone_blob());
+            }
+        }
+    }
+    if !blobs_to_publish.is_empty() {
+        publish_fn(blobs_to_publish);
+    }
+
+    debug!(
+        chain.log,
+        "Processing engine blobs";
+        "num_fetched_blobs" => num_fetched_blobs,
+        "num_expected_blobs" => num_expected_blobs,
+        "block_root" => %block_root,
+    );
+
+    let availability_processing_status = chain
+        .process_engine_blobs(block.slot(), block_root, verified_blobs)
+        .await
+        .map_err(|e| {
+            error!(
+                chain.log,
+                "Failed to process engine blobs";
+                "error" => ?e,
+                "block_root" => %block_root,
+            );
+            FetchEngineBlobError::BlobProcessingError(e)
+        })?;
+
+    Ok(Some(availability_processing_status))
+}
@@ -1,5 +1,7 @@
-use kzg::{Blob as KzgBlob, Error as KzgError, Kzg};
-use types::{Blob, EthSpec, Hash256, KzgCommitment, KzgProof};
+use kzg::{Blob as KzgBlob, Bytes48, Error as KzgError, Kzg};
+use std::sync::Arc;
+use types::data_column_sidecar::ssz_cell_to_crypto_cell;
+use types::{Blob, DataColumnSidecar, EthSpec, Hash256, KzgCommitment, KzgProof};
 
 /// Converts a blob ssz List object to an array to be used with the kzg
 /// crypto library.
@@ -35,6 +37,51 @@ pub fn validate_blobs<E: EthSpec>(
     kzg.verify_blob_kzg_proof_batch(&blobs, expected_kzg_commitments, kzg_proofs)
 }
 
+/// Validate a batch of `DataColumnSidecar`s.
+///
+/// Every cell in every column is verified against the commitment of the blob (row) it belongs to.
+pub fn validate_data_columns<'a, E: EthSpec, I>(
+    kzg: &Kzg,
+    data_column_iter: I,
+) -> Result<(), KzgError>
+where
+    I: Iterator<Item = &'a Arc<DataColumnSidecar<E>>>,
+{
+    let mut cells = vec![];
+    let mut proofs = vec![];
+    let mut column_indices = vec![];
+    let mut commitments = vec![];
+
+    for data_column in data_column_iter {
+        let num_cells = data_column.column.len();
+        if data_column.kzg_proofs.len() != num_cells
+            || data_column.kzg_commitments.len() != num_cells
+        {
+            return Err(KzgError::InconsistentArrayLength(format!(
+                "Data column {} has {} cells, {} proofs and {} commitments",
+                data_column.index,
+                num_cells,
+                data_column.kzg_proofs.len(),
+                data_column.kzg_commitments.len()
+            )));
+        }
+
+        for ((cell, proof), commitment) in data_column
+            .column
+            .iter()
+            .zip(data_column.kzg_proofs.iter())
+            .zip(data_column.kzg_commitments.iter())
+        {
+            cells.push(ssz_cell_to_crypto_cell::<E>(cell)?);
+            proofs.push(Bytes48::from(*proof));
+            column_indices.push(data_column.index);
+            commitments.push(Bytes48::from(*commitment));
+        }
+    }
+
+    kzg.verify_cell_proof_batch(&cells, &proofs, column_indices, &commitments)
+}
+
 /// Compute the kzg proof given an ssz blob and its kzg commitment.
 pub fn compute_blob_kzg_proof<E: EthSpec>(
     kzg: &Kzg,
@@ -76,3 +123,52 @@ pub fn verify_kzg_proof<E: EthSpec>(
 ) -> Result<bool, KzgError> {
     kzg.verify_kzg_proof(kzg_commitment, &z.0.into(), &y.0.into(), kzg_proof)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::{generate_rand_block_and_blobs, NumBlobs, KZG};
+    use rand::thread_rng;
+    use types::{BlobsList, ForkName, MainnetEthSpec};
+
+    type E = MainnetEthSpec;
+
+    #[test]
+    fn build_verify_and_reconstruct_data_columns() {
+        let spec = E::default_spec();
+        let (block, blob_sidecars) = generate_rand_block_and_blobs::<E>(
+            ForkName::Deneb,
+            NumBlobs::Number(2),
+            &mut thread_rng(),
+        );
+        let blobs = BlobsList::<E>::new(
+            blob_sidecars
+                .into_iter()
+                .map(|sidecar| sidecar.blob)
+                .collect(),
+        )
+        .unwrap();
+
+        let column_sidecars =
+            DataColumnSidecar::build_sidecars(&blobs, &block, &KZG, &spec).unwrap();
+        assert_eq!(column_sidecars.len(), spec.number_of_columns);
+        validate_data_columns(&KZG, column_sidecars.iter()).expect("columns should be valid");
+
+        // Reconstructing from any half of the columns recovers the full set.
+        let reconstructed = DataColumnSidecar::reconstruct(
+            &KZG,
+            &column_sidecars[spec.number_of_columns / 2..],
+            &spec,
+        )
+        .unwrap();
+        assert_eq!(reconstructed, column_sidecars);
+
+        // A single corrupted cell fails verification.
+        let mut invalid_column = (*column_sidecars[0]).clone();
+        invalid_column.column[0] = invalid_column.column[1].clone();
+        assert!(matches!(
+            validate_data_columns(&KZG, [Arc::new(invalid_column)].iter()),
+            Err(KzgError::KzgVerificationFailed)
+        ));
+    }
+}
@@ -4,16 +4,22 @@ use parking_lot::{Mutex, RwLock};
 use slog::{debug, Logger};
 use ssz_types::FixedVector;
 use std::num::NonZeroUsize;
-use types::light_client_update::{FinalizedRootProofLen, FINALIZED_ROOT_INDEX};
+use std::sync::Arc;
+use types::light_client_update::{
+    FinalizedRootProofLen, NextSyncCommitteeProofLen, FINALIZED_ROOT_INDEX,
+    NEXT_SYNC_COMMITTEE_INDEX,
+};
 use types::non_zero_usize::new_non_zero_usize;
 use types::{
     BeaconBlockRef, BeaconState, ChainSpec, EthSpec, ForkName, Hash256, LightClientFinalityUpdate,
-    LightClientOptimisticUpdate, Slot, SyncAggregate,
+    LightClientOptimisticUpdate, LightClientUpdate, SignedBeaconBlock, Slot, SyncAggregate,
+    SyncCommittee,
 };
 
 /// A prev block cache miss requires to re-generate the state of the post-parent block. Items in the
-/// prev block cache are very small 32 * (6 + 1) = 224 bytes. 32 is an arbitrary number that
-/// represents unlikely re-orgs, while keeping the cache very small.
+/// prev block cache are small, 32 * (6 + 5 + 1) = 384 bytes plus a reference to the next sync
+/// committee which is shared with the state. 32 is an arbitrary number that represents unlikely
+/// re-orgs, while keeping the cache very small.
 const PREV_BLOCK_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(32);
 
 /// This cache computes light client messages ahead of time, required to satisfy p2p and API
@@ -31,7 +37,10 @@ pub struct LightClientServerCache<T: BeaconChainTypes> {
     /// Tracks a single global latest optimistic update out of all imported blocks.
     latest_optimistic_update: RwLock<Option<LightClientOptimisticUpdate<T::EthSpec>>>,
     /// Caches state proofs by block root
-    prev_block_cache: Mutex<lru::LruCache<Hash256, LightClientCachedData>>,
+    prev_block_cache: Mutex<lru::LruCache<Hash256, LightClientCachedData<T::EthSpec>>>,
+    /// Serializes the read-compare-write of the best update of each period, which happens both
+    /// during block import and backfill.
+    best_update_lock: Mutex<()>,
 }
 
 impl<T: BeaconChainTypes> LightClientServerCache<T> {
@@ -40,6 +49,7 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
             latest_finality_update: None.into(),
             latest_optimistic_update: None.into(),
             prev_block_cache: lru::LruCache::new(PREV_BLOCK_CACHE_SIZE).into(),
+            best_update_lock: Mutex::new(()),
         }
     }
 
@@ -99,6 +109,12 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
             attested_block.slot(),
         )?;
 
+        let finalized_block = if cached_parts.finalized_block_root.is_zero() {
+            None
+        } else {
+            store.get_full_block(&cached_parts.finalized_block_root)?
+        };
+
         let attested_slot = attested_block.slot();
 
         // Spec: Full nodes SHOULD provide the LightClientOptimisticUpdate with the highest
@@ -129,12 +145,10 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
         };
         if is_latest_finality & !cached_parts.finalized_block_root.is_zero() {
             // Immediately after checkpoint sync the finalized block may not be available yet.
-            if let Some(finalized_block) =
-                store.get_full_block(&cached_parts.finalized_block_root)?
-            {
+            if let Some(finalized_block) = &finalized_block {
                 *self.latest_finality_update.write() = Some(LightClientFinalityUpdate::new(
                     &attested_block,
-                    &finalized_block,
+                    finalized_block,
                     cached_parts.finality_branch.clone(),
                     sync_aggregate.clone(),
                     signature_slot,
@@ -149,9 +163,54 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
             }
         }
 
+        // Spec: Full nodes SHOULD provide the best derivable LightClientUpdate for each sync
+        // committee period
+        if let Some(finalized_block) = &finalized_block {
+            let attested_period = attested_slot
+                .epoch(T::EthSpec::slots_per_epoch())
+                .sync_committee_period(chain_spec)?;
+            let signature_period = signature_slot
+                .epoch(T::EthSpec::slots_per_epoch())
+                .sync_committee_period(chain_spec)?;
+            let enough_participants = sync_aggregate.num_set_bits()
+                >= chain_spec.min_sync_committee_participants as usize;
+            if attested_period == signature_period && enough_participants {
+                let update = cached_parts.to_light_client_update(
+                    sync_aggregate,
+                    signature_slot,
+                    &attested_block,
+                    finalized_block,
+                    chain_spec,
+                )?;
+                self.persist_if_better_update(&store, attested_period, &update, chain_spec)?;
+            }
+        }
+
         Ok(())
     }
 
+    /// Store `update` as the best update of `sync_committee_period` if it is better than the
+    /// currently stored update, as defined by the spec's `is_better_update`.
+    ///
+    /// Returns `true` if `update` was stored.
+    pub fn persist_if_better_update(
+        &self,
+        store: &BeaconStore<T>,
+        sync_committee_period: u64,
+        update: &LightClientUpdate<T::EthSpec>,
+        chain_spec: &ChainSpec,
+    ) -> Result<bool, BeaconChainError> {
+        let _guard = self.best_update_lock.lock();
+        if let Some(prev_update) = store.get_light_client_update(sync_committee_period)? {
+            if !update.is_better_update(&prev_update, chain_spec)? {
+                return Ok(false);
+            }
+        }
+        store.put_light_client_update(sync_committee_period, update)?;
+        metrics::inc_counter(&metrics::LIGHT_CLIENT_SERVER_CACHE_BEST_UPDATES_STORED);
+        Ok(true)
+    }
+
     /// Retrieves prev block cached data from cache. If not present re-computes by retrieving the
     /// parent state, and inserts an entry to the cache.
     ///
@@ -162,7 +221,7 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
         block_root: &Hash256,
         block_state_root: &Hash256,
         block_slot: Slot,
-    ) -> Result<LightClientCachedData, BeaconChainError> {
+    ) -> Result<LightClientCachedData<T::EthSpec>, BeaconChainError> {
         // Attempt to get the value from the cache first.
         if let Some(cached_parts) = self.prev_block_cache.lock().get(block_root) {
             return Ok(cached_parts.clone());
@@ -200,20 +259,55 @@ impl<T: BeaconChainTypes> Default for LightClientServerCache<T> {
 }
 
 type FinalityBranch = FixedVector<Hash256, FinalizedRootProofLen>;
+type NextSyncCommitteeBranch = FixedVector<Hash256, NextSyncCommitteeProofLen>;
 
+/// The parts of a block's post-state which are required to produce light client messages that
+/// attest to that block.
 #[derive(Clone)]
-struct LightClientCachedData {
+pub struct LightClientCachedData<E: EthSpec> {
     finality_branch: FinalityBranch,
+    next_sync_committee_branch: NextSyncCommitteeBranch,
+    next_sync_committee: Arc<SyncCommittee<E>>,
     finalized_block_root: Hash256,
 }
 
-impl LightClientCachedData {
-    fn from_state<E: EthSpec>(state: &mut BeaconState<E>) -> Result<Self, BeaconChainError> {
+impl<E: EthSpec> LightClientCachedData<E> {
+    pub fn from_state(state: &mut BeaconState<E>) -> Result<Self, BeaconChainError> {
         Ok(Self {
             finality_branch: state.compute_merkle_proof(FINALIZED_ROOT_INDEX)?.into(),
+            next_sync_committee_branch: state
+                .compute_merkle_proof(NEXT_SYNC_COMMITTEE_INDEX)?
+                .into(),
+            next_sync_committee: state.next_sync_committee()?.clone(),
             finalized_block_root: state.finalized_checkpoint().root,
         })
     }
+
+    pub fn finalized_block_root(&self) -> Hash256 {
+        self.finalized_block_root
+    }
+
+    /// Build a `LightClientUpdate` attesting to `attested_block`, the block these parts were
+    /// computed from.
+    pub fn to_light_client_update(
+        &self,
+        sync_aggregate: &SyncAggregate<E>,
+        signature_slot: Slot,
+        attested_block: &SignedBeaconBlock<E>,
+        finalized_block: &SignedBeaconBlock<E>,
+        chain_spec: &ChainSpec,
+    ) -> Result<LightClientUpdate<E>, BeaconChainError> {
+        Ok(LightClientUpdate::new(
+            sync_aggregate,
+            signature_slot,
+            self.next_sync_committee.clone(),
+            self.next_sync_committee_branch.clone(),
+            self.finality_branch.clone(),
+            attested_block,
+            finalized_block,
+            chain_spec,
+        )?)
+    }
 }
 
 // Implements spec prioritization rules:
@@ -0,0 +1,221 @@
+//! Computes the best `LightClientUpdate` of sync committee periods which were not observed during
+//! block import, e.g. because the light client server was enabled later or the node was checkpoint
+//! synced.
+use crate::light_client_server_cache::LightClientCachedData;
+use crate::{errors::BeaconChainError as Error, metrics, BeaconChain, BeaconChainTypes};
+use slog::{debug, info};
+use std::cmp::{max, min, Reverse};
+use types::{EthSpec, Hash256, LightClientUpdate, SignedBeaconBlock, Slot, SyncAggregate};
+
+/// The number of blocks with the highest sync committee participation evaluated for each period.
+///
+/// Evaluating a candidate requires loading the post-state of its parent, which is expensive for
+/// finalized periods. Candidates are ranked by participation alone, so if all of them lack a
+/// relevant sync committee or finality the stored update may not be the best update of the period
+/// according to `is_better_update`.
+pub const BACKFILL_CANDIDATES_PER_PERIOD: usize = 4;
+
+/// A block whose sync aggregate may sign the best update of its period.
+struct Candidate<E: EthSpec> {
+    signature_slot: Slot,
+    attested_block_root: Hash256,
+    sync_aggregate: SyncAggregate<E>,
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Compute and store the best `LightClientUpdate` of each period which light clients may
+    /// request and which has no stored update.
+    ///
+    /// Periods are visited from the current period backwards. Backfill stops at the first period
+    /// whose blocks or states are unavailable, e.g. prior to the checkpoint sync anchor.
+    ///
+    /// Returns the number of periods for which an update was stored.
+    pub fn backfill_light_client_updates(&self) -> Result<usize, Error> {
+        let Some(altair_fork_epoch) = self.spec.altair_fork_epoch else {
+            return Ok(0);
+        };
+        let current_epoch = self.epoch()?;
+        let current_period = current_epoch.sync_committee_period(&self.spec)?;
+        let oldest_epoch = max(
+            altair_fork_epoch,
+            current_epoch.saturating_sub(self.spec.min_epochs_for_block_requests),
+        );
+        let oldest_period = oldest_epoch.sync_committee_period(&self.spec)?;
+
+        let mut stored = 0;
+        for period in (oldest_period..=current_period).rev() {
+            // The current period is still in progress, so blocks imported prior to startup may
+            // provide a better update than the stored one.
+            if period != current_period && self.store.get_light_client_update(period)?.is_some() {
+                continue;
+            }
+
+            let timer = metrics::start_timer(&metrics::LIGHT_CLIENT_UPDATES_BACKFILL_TIMES);
+            let update = match self.compute_best_light_client_update(period) {
+                Ok(Some(update)) => update,
+                Ok(None) => {
+                    debug!(
+                        self.log,
+                        "No light client update derivable for period";
+                        "period" => period,
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    debug!(
+                        self.log,
+                        "Stopping light client update backfill";
+                        "period" => period,
+                        "reason" => ?e,
+                    );
+                    break;
+                }
+            };
+            drop(timer);
+
+            if self.light_client_server_cache.persist_if_better_update(
+                &self.store,
+                period,
+                &update,
+                &self.spec,
+            )? {
+                stored += 1;
+            }
+        }
+
+        info!(
+            self.log,
+            "Light client update backfill complete";
+            "stored_periods" => stored,
+            "oldest_period" => oldest_period,
+            "current_period" => current_period,
+        );
+        Ok(stored)
+    }
+
+    /// Compute the best update of `period` from the canonical blocks of that period.
+    ///
+    /// Only the `BACKFILL_CANDIDATES_PER_PERIOD` blocks with the highest sync committee
+    /// participation are evaluated.
+    pub fn compute_best_light_client_update(
+        &self,
+        period: u64,
+    ) -> Result<Option<LightClientUpdate<T::EthSpec>>, Error> {
+        let slots_per_epoch = T::EthSpec::slots_per_epoch();
+        let period_start_slot =
+            (self.spec.epochs_per_sync_committee_period * period).start_slot(slots_per_epoch);
+        let next_period_start_slot = (self.spec.epochs_per_sync_committee_period
+            * period.saturating_add(1))
+        .start_slot(slots_per_epoch);
+        let end_slot = min(
+            next_period_start_slot.saturating_sub(1u64),
+            self.canonical_head.cached_head().head_slot(),
+        );
+        if end_slot < period_start_slot {
+            return Ok(None);
+        }
+
+        let mut candidates = vec![];
+        let mut prev_block_root = None;
+        for result in self.forwards_iter_block_roots_until(period_start_slot, end_slot)? {
+            let (block_root, _) = result?;
+            // Skipped slots repeat the root of the previous block.
+            if prev_block_root == Some(block_root) {
+                continue;
+            }
+            prev_block_root = Some(block_root);
+
+            let block = self
+                .get_blinded_block(&block_root)?
+                .ok_or(Error::MissingBeaconBlock(block_root))?;
+            let Ok(sync_aggregate) = block.message().body().sync_aggregate() else {
+                continue;
+            };
+            if sync_aggregate.num_set_bits() < self.spec.min_sync_committee_participants as usize {
+                continue;
+            }
+            candidates.push(Candidate {
+                signature_slot: block.slot(),
+                attested_block_root: block.parent_root(),
+                sync_aggregate: sync_aggregate.clone(),
+            });
+        }
+
+        // Prefer the highest participation, then the oldest data.
+        candidates.sort_by_key(|candidate| {
+            (
+                Reverse(candidate.sync_aggregate.num_set_bits()),
+                candidate.signature_slot,
+            )
+        });
+
+        let mut best_update: Option<LightClientUpdate<T::EthSpec>> = None;
+        for candidate in candidates.into_iter().take(BACKFILL_CANDIDATES_PER_PERIOD) {
+            let Some(update) = self.light_client_update_for_candidate(period, &candidate)? else {
+                continue;
+            };
+            let is_better = match &best_update {
+                Some(best_update) => update.is_better_update(best_update, &self.spec)?,
+                None => true,
+            };
+            if is_better {
+                best_update = Some(update);
+            }
+        }
+
+        Ok(best_update)
+    }
+
+    /// Build the update signed by `candidate`, if its attested block belongs to `period` and its
+    /// finalized block is available.
+    fn light_client_update_for_candidate(
+        &self,
+        period: u64,
+        candidate: &Candidate<T::EthSpec>,
+    ) -> Result<Option<LightClientUpdate<T::EthSpec>>, Error> {
+        let Some(attested_block) = self.get_full_block_blocking(&candidate.attested_block_root)?
+        else {
+            return Err(Error::MissingBeaconBlock(candidate.attested_block_root));
+        };
+        let attested_period = attested_block
+            .slot()
+            .epoch(T::EthSpec::slots_per_epoch())
+            .sync_committee_period(&self.spec)?;
+        if attested_period != period {
+            return Ok(None);
+        }
+
+        let attested_state_root = attested_block.state_root();
+        let mut attested_state = self
+            .get_state(&attested_state_root, Some(attested_block.slot()))?
+            .ok_or(Error::MissingBeaconState(attested_state_root))?;
+        let cached_parts = LightClientCachedData::from_state(&mut attested_state)?;
+
+        let finalized_block_root = cached_parts.finalized_block_root();
+        if finalized_block_root.is_zero() {
+            return Ok(None);
+        }
+        let Some(finalized_block) = self.get_full_block_blocking(&finalized_block_root)? else {
+            return Ok(None);
+        };
+
+        cached_parts
+            .to_light_client_update(
+                &candidate.sync_aggregate,
+                candidate.signature_slot,
+                &attested_block,
+                &finalized_block,
+                &self.spec,
+            )
+            .map(Some)
+    }
+
+    /// Load a full block, reconstructing its payload from the execution layer if it was pruned.
+    fn get_full_block_blocking(
+        &self,
+        block_root: &Hash256,
+    ) -> Result<Option<SignedBeaconBlock<T::EthSpec>>, Error> {
+        let handle = self.task_executor.handle().ok_or(Error::RuntimeShutdown)?;
+        handle.block_on(self.get_block(block_root))
+    }
+}
@@ -1148,6 +1148,10 @@ lazy_static! {
         try_create_histogram("kzg_verification_single_seconds", "Runtime of single kzg verification");
     pub static ref KZG_VERIFICATION_BATCH_TIMES: Result<Histogram> =
         try_create_histogram("kzg_verification_batch_seconds", "Runtime of batched kzg verification");
+    pub static ref KZG_VERIFICATION_DATA_COLUMN_SINGLE_TIMES: Result<Histogram> =
+        try_create_histogram("kzg_verification_data_column_single_seconds", "Runtime of single data column kzg verification");
+    pub static ref KZG_VERIFICATION_DATA_COLUMN_BATCH_TIMES: Result<Histogram> =
+        try_create_histogram("kzg_verification_data_column_batch_seconds", "Runtime of batched data column kzg verification");
 
     pub static ref BLOCK_PRODUCTION_BLOBS_VERIFICATION_TIMES: Result<Histogram> = try_create_histogram(
             "beacon_block_production_blobs_verification_seconds",
@@ -1195,6 +1199,14 @@ lazy_static! {
         "beacon_light_client_server_cache_prev_block_cache_miss",
         "Count of prev block cache misses",
     );
+    pub static ref LIGHT_CLIENT_SERVER_CACHE_BEST_UPDATES_STORED: Result<IntCounter> = try_create_int_counter(
+        "beacon_light_client_server_cache_best_updates_stored_total",
+        "Count of light client updates stored as the best update of their period",
+    );
+    pub static ref LIGHT_CLIENT_UPDATES_BACKFILL_TIMES: Result<Histogram> = try_create_histogram(
+        "beacon_light_client_updates_backfill_period_seconds",
+        "Time taken to compute the best light client update of a past period",
+    );
 }
 
 /// Scrape the `beacon_chain` for metrics that are not constantly updated (e.g., the present slot,
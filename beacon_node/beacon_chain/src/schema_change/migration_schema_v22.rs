@@ -0,0 +1,261 @@
+use crate::beacon_chain::BeaconChainTypes;
+use slog::{info, Logger};
+use ssz::{Decode, Encode};
+use std::cmp::min;
+use std::sync::Arc;
+use store::metadata::{CONFIG_KEY, FREEZER_MIGRATION_PROGRESS_KEY, STATE_UPPER_LIMIT_NO_RETAIN};
+use store::{
+    get_key_for_col, AnchorInfo, DBColumn, Error, HotColdDB, KeyValueStore, KeyValueStoreOp,
+    StoreItem,
+};
+use types::{BeaconState, EthSpec, Slot};
+
+/// Convert the freezer database from restore points to the hierarchical diff layout.
+///
+/// The restore points at the start of each range of retained states (genesis, and the state upper
+/// limit after checkpoint sync) are re-stored as snapshots, and the remainder of each range is
+/// re-created by replaying blocks. The slot of the latest re-stored state is persisted as the
+/// migration progresses, so an interrupted migration resumes from that state rather than from the
+/// start of its range. The legacy restore points are only deleted once all states have been
+/// re-stored.
+pub fn upgrade_to_v22<T: BeaconChainTypes>(
+    db: Arc<HotColdDB<T::EthSpec, T::HotStore, T::ColdStore>>,
+    log: Logger,
+) -> Result<Vec<KeyValueStoreOp>, Error> {
+    info!(log, "Upgrading from v21 to v22");
+
+    let slots_per_restore_point = load_slots_per_restore_point::<T>(&db)?;
+    let split = db.get_split_info();
+    let anchor = db.get_anchor_info();
+    let progress_slot = load_migration_progress::<T>(&db)?;
+
+    // Replace the on-disk config, which previously held `slots_per_restore_point`, and clear the
+    // migration progress once complete.
+    let ops = vec![
+        db.get_config().as_disk_config().as_kv_store_op(CONFIG_KEY),
+        KeyValueStoreOp::DeleteKey(get_key_for_col(
+            DBColumn::BeaconMeta.into(),
+            FREEZER_MIGRATION_PROGRESS_KEY.as_bytes(),
+        )),
+    ];
+
+    let Some((genesis_state_root, genesis_state)) = db.load_legacy_restore_point(0)? else {
+        // The legacy restore points have already been deleted by a previous attempt, or the
+        // freezer was never initialised.
+        info!(log, "No restore points to migrate");
+        return Ok(ops);
+    };
+
+    info!(
+        log,
+        "Migrating freezer to hierarchical state diffs";
+        "hierarchy" => %db.get_config().hierarchy_config,
+        "slots_per_restore_point" => slots_per_restore_point,
+        "split_slot" => split.slot,
+        "resume_slot" => ?progress_slot,
+    );
+
+    // Re-store the states from genesis to the state lower limit (inclusive), which will be all
+    // states prior to the split for a node synced from genesis.
+    let lower_range_end_slot = match &anchor {
+        None => split.slot,
+        Some(anchor) if anchor.state_upper_limit == STATE_UPPER_LIMIT_NO_RETAIN => Slot::new(0),
+        Some(anchor) => min(anchor.state_lower_limit + 1, split.slot),
+    };
+    let upper_range_start_slot = anchor
+        .map(|anchor| anchor.state_upper_limit)
+        .filter(|upper_limit| *upper_limit < split.slot);
+
+    match progress_slot {
+        // The lower range was completed by a previous attempt.
+        Some(slot) if slot + 1 >= lower_range_end_slot => (),
+        Some(slot) => {
+            let state = load_migrated_state::<T>(&db, slot)?;
+            replay_range::<T>(&db, state, lower_range_end_slot, &log)?;
+        }
+        None => {
+            let mut cold_ops = vec![];
+            db.store_cold_state(&genesis_state_root, &genesis_state, &mut cold_ops)?;
+            db.cold_db.do_atomically(cold_ops)?;
+            replay_range::<T>(&db, genesis_state, lower_range_end_slot, &log)?;
+        }
+    }
+
+    // Re-store the states from the state upper limit to the split, if any.
+    if let Some(upper_limit_slot) = upper_range_start_slot {
+        let state = match progress_slot {
+            Some(slot) if slot >= upper_limit_slot => load_migrated_state::<T>(&db, slot)?,
+            _ => {
+                let restore_point_index = upper_limit_slot.as_u64() / slots_per_restore_point;
+                let (state_root, state) = db
+                    .load_legacy_restore_point(restore_point_index)?
+                    .ok_or(Error::DBError {
+                        message: format!(
+                            "Missing restore point at state upper limit {upper_limit_slot}"
+                        ),
+                    })?;
+                let mut cold_ops = vec![];
+                db.store_cold_state(&state_root, &state, &mut cold_ops)?;
+                db.cold_db.do_atomically(cold_ops)?;
+                state
+            }
+        };
+        replay_range::<T>(&db, state, split.slot, &log)?;
+    }
+
+    info!(log, "Deleting legacy restore points");
+    db.delete_legacy_restore_points()?;
+    db.cold_db.compact()?;
+
+    info!(log, "Freezer migration complete");
+
+    Ok(ops)
+}
+
+/// Convert the freezer database from the hierarchical diff layout back to restore points.
+///
+/// A restore point is stored every `slots_per_historical_root` slots for which a frozen state is
+/// available. As v21 can only load states lying between two restore points, the state limits of
+/// a partially reconstructed freezer are aligned to restore points, which may make some states
+/// unavailable until they are reconstructed again.
+pub fn downgrade_from_v22<T: BeaconChainTypes>(
+    db: Arc<HotColdDB<T::EthSpec, T::HotStore, T::ColdStore>>,
+    log: Logger,
+) -> Result<Vec<KeyValueStoreOp>, Error> {
+    info!(log, "Downgrading from v22 to v21");
+
+    let slots_per_restore_point = T::EthSpec::slots_per_historical_root() as u64;
+    let split = db.get_split_info();
+
+    info!(
+        log,
+        "Migrating freezer to restore points";
+        "slots_per_restore_point" => slots_per_restore_point,
+        "split_slot" => split.slot,
+    );
+
+    for slot in (0..split.slot.as_u64()).step_by(slots_per_restore_point as usize) {
+        let Some(mut state) = db.load_cold_state_by_slot(Slot::new(slot))? else {
+            continue;
+        };
+        let state_root = state.update_tree_hash_cache()?;
+        let mut cold_ops = vec![];
+        db.store_legacy_restore_point(
+            slot / slots_per_restore_point,
+            state_root,
+            &state,
+            &mut cold_ops,
+        );
+        db.cold_db.do_atomically(cold_ops)?;
+        info!(
+            log,
+            "Freezer migration in progress";
+            "slot" => slot,
+            "end_slot" => split.slot,
+        );
+    }
+
+    info!(log, "Deleting hierarchical state diffs");
+    db.delete_hdiff_states()?;
+    db.cold_db.compact()?;
+
+    info!(log, "Freezer migration complete");
+
+    let mut ops = vec![KeyValueStoreOp::PutKeyValue(
+        get_key_for_col(DBColumn::BeaconMeta.into(), CONFIG_KEY.as_bytes()),
+        slots_per_restore_point.as_ssz_bytes(),
+    )];
+
+    if let Some(anchor) = db
+        .get_anchor_info()
+        .filter(|anchor| anchor.state_lower_limit < anchor.state_upper_limit)
+    {
+        let state_upper_limit = if anchor.state_upper_limit == STATE_UPPER_LIMIT_NO_RETAIN {
+            STATE_UPPER_LIMIT_NO_RETAIN
+        } else {
+            Slot::new(
+                anchor
+                    .state_upper_limit
+                    .as_u64()
+                    .div_ceil(slots_per_restore_point)
+                    * slots_per_restore_point,
+            )
+        };
+        let state_lower_limit = Slot::new(
+            anchor.state_lower_limit.as_u64() / slots_per_restore_point * slots_per_restore_point,
+        );
+        let new_anchor = AnchorInfo {
+            state_upper_limit,
+            state_lower_limit,
+            ..anchor.clone()
+        };
+        ops.push(db.compare_and_set_anchor_info(Some(anchor), Some(new_anchor))?);
+    }
+
+    Ok(ops)
+}
+
+/// Re-store the frozen states from the slot of `state` up to `end_slot` (exclusive).
+fn replay_range<T: BeaconChainTypes>(
+    db: &Arc<HotColdDB<T::EthSpec, T::HotStore, T::ColdStore>>,
+    state: BeaconState<T::EthSpec>,
+    end_slot: Slot,
+    log: &Logger,
+) -> Result<(), Error> {
+    let start_slot = state.slot();
+    let log_interval = T::EthSpec::slots_per_historical_root() as u64;
+    db.store_cold_states_by_replay(state, end_slot, |slot| {
+        db.hot_db.put_bytes(
+            DBColumn::BeaconMeta.into(),
+            FREEZER_MIGRATION_PROGRESS_KEY.as_bytes(),
+            &slot.as_ssz_bytes(),
+        )?;
+        if slot % log_interval == 0 {
+            info!(
+                log,
+                "Freezer migration in progress";
+                "slot" => slot,
+                "start_slot" => start_slot,
+                "end_slot" => end_slot,
+            );
+        }
+        Ok(())
+    })
+}
+
+/// Read the `slots_per_restore_point` from the legacy on-disk store config.
+fn load_slots_per_restore_point<T: BeaconChainTypes>(
+    db: &HotColdDB<T::EthSpec, T::HotStore, T::ColdStore>,
+) -> Result<u64, Error> {
+    let bytes = db
+        .hot_db
+        .get_bytes(DBColumn::BeaconMeta.into(), CONFIG_KEY.as_bytes())?
+        .ok_or(Error::DBError {
+            message: "Missing on-disk store config".to_string(),
+        })?;
+    Ok(u64::from_ssz_bytes(&bytes)?)
+}
+
+/// Read the slot of the latest state re-stored by an interrupted migration, if any.
+fn load_migration_progress<T: BeaconChainTypes>(
+    db: &HotColdDB<T::EthSpec, T::HotStore, T::ColdStore>,
+) -> Result<Option<Slot>, Error> {
+    db.hot_db
+        .get_bytes(
+            DBColumn::BeaconMeta.into(),
+            FREEZER_MIGRATION_PROGRESS_KEY.as_bytes(),
+        )?
+        .map(|bytes| Slot::from_ssz_bytes(&bytes))
+        .transpose()
+        .map_err(Into::into)
+}
+
+/// Load a state which has already been re-stored in the hierarchical layout.
+fn load_migrated_state<T: BeaconChainTypes>(
+    db: &HotColdDB<T::EthSpec, T::HotStore, T::ColdStore>,
+    slot: Slot,
+) -> Result<BeaconState<T::EthSpec>, Error> {
+    db.load_cold_state_by_slot(slot)?.ok_or(Error::DBError {
+        message: format!("Missing migrated state at slot {slot}"),
+    })
+}
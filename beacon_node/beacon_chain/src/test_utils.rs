@@ -54,7 +54,7 @@ use std::str::FromStr;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use store::{config::StoreConfig, HotColdDB, ItemStore, LevelDB, MemoryStore};
+use store::{config::StoreConfig, BeaconNodeBackend, HotColdDB, ItemStore, MemoryStore};
 use task_executor::TaskExecutor;
 use task_executor::{test_utils::TestRuntime, ShutdownReason};
 use tree_hash::TreeHash;
@@ -88,7 +88,7 @@ lazy_static! {
 pub type BaseHarnessType<E, THotStore, TColdStore> =
     Witness<TestingSlotClock, CachingEth1Backend<E>, E, THotStore, TColdStore>;
 
-pub type DiskHarnessType<E> = BaseHarnessType<E, LevelDB<E>, LevelDB<E>>;
+pub type DiskHarnessType<E> = BaseHarnessType<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>;
 pub type EphemeralHarnessType<E> = BaseHarnessType<E, MemoryStore<E>, MemoryStore<E>>;
 
 pub type BoxedMutator<E, Hot, Cold> = Box<
@@ -265,7 +265,10 @@ impl<E: EthSpec> Builder<EphemeralHarnessType<E>> {
 
 impl<E: EthSpec> Builder<DiskHarnessType<E>> {
     /// Disk store, start from genesis.
-    pub fn fresh_disk_store(mut self, store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>) -> Self {
+    pub fn fresh_disk_store(
+        mut self,
+        store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
+    ) -> Self {
         let validator_keypairs = self
             .validator_keypairs
             .clone()
@@ -290,7 +293,10 @@ impl<E: EthSpec> Builder<DiskHarnessType<E>> {
     }
 
     /// Disk store, resume.
-    pub fn resumed_disk_store(mut self, store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>) -> Self {
+    pub fn resumed_disk_store(
+        mut self,
+        store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
+    ) -> Self {
         let mutator = move |builder: BeaconChainBuilder<_>| {
             builder
                 .resume_from_db()
@@ -692,17 +698,46 @@ where
         self.mock_builder = Some(Arc::new(mock_builder));
 
         // Sanity check.
-        let el_builder = self
-            .chain
-            .execution_layer
+        let el_builders = self.chain.execution_layer.as_ref().unwrap().builders();
+        let mock_el_builders = mock_el.el.builders();
+        assert!(Arc::ptr_eq(&el_builders, &mock_el_builders));
+
+        mock_builder_server
+    }
+
+    /// Connect an additional mock builder relay alongside any existing ones.
+    ///
+    /// The returned builder is not stored in the harness, so callers are responsible for keeping
+    /// it around to control its bids.
+    pub fn add_mock_builder(
+        &self,
+        beacon_url: SensitiveUrl,
+    ) -> (Arc<MockBuilder<E>>, impl futures::Future<Output = ()>) {
+        let mock_el = self
+            .mock_execution_layer
             .as_ref()
-            .unwrap()
-            .builder()
+            .expect("harness was not built with mock execution layer");
+
+        let mock_el_url = SensitiveUrl::parse(mock_el.server.url().as_str()).unwrap();
+
+        let (mock_builder, (addr, mock_builder_server)) = MockBuilder::new_for_testing(
+            mock_el_url,
+            beacon_url,
+            self.spec.clone(),
+            self.runtime.task_executor.clone(),
+        );
+
+        let port = addr.port();
+        mock_el
+            .el
+            .add_builder_url(
+                SensitiveUrl::parse(format!("http://127.0.0.1:{port}").as_str()).unwrap(),
+                None,
+                None,
+            )
             .unwrap();
-        let mock_el_builder = mock_el.el.builder().unwrap();
-        assert!(Arc::ptr_eq(&el_builder, &mock_el_builder));
 
-        mock_builder_server
+        (Arc::new(mock_builder), mock_builder_server)
     }
 
     pub fn get_head_block(&self) -> RpcBlock<E> {
@@ -1,9 +1,11 @@
 #![cfg(not(debug_assertions))]
 
 use beacon_chain::block_verification_types::{AsBlock, ExecutedBlock, RpcBlock};
+use beacon_chain::data_availability_checker::MaybeAvailableBlock;
 use beacon_chain::{
     test_utils::{
-        test_spec, AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
+        generate_rand_block_and_blobs, test_spec, AttestationStrategy, BeaconChainHarness,
+        BlockStrategy, EphemeralHarnessType, NumBlobs, KZG,
     },
     AvailabilityProcessingStatus, BeaconChain, BeaconChainTypes, ExecutionPendingBlock,
 };
@@ -13,6 +15,7 @@ use beacon_chain::{
 };
 use lazy_static::lazy_static;
 use logging::test_logger;
+use rand::thread_rng;
 use slasher::{Config as SlasherConfig, Slasher};
 use state_processing::{
     common::{attesting_indices_base, attesting_indices_electra},
@@ -1717,6 +1720,81 @@ async fn import_duplicate_block_unrealized_justification() {
     );
 }
 
+/// A supernode custodies every data column subnet, so a block must not become available until all
+/// columns have been received.
+#[tokio::test]
+async fn supernode_requires_all_data_columns() {
+    let mut spec = ForkName::Deneb.make_genesis_spec(E::default_spec());
+    spec.eip7594_fork_epoch = Some(Epoch::new(0));
+
+    let harness = BeaconChainHarness::builder(MainnetEthSpec)
+        .spec(spec.clone())
+        .keypairs(KEYPAIRS[..].to_vec())
+        .fresh_ephemeral_store()
+        .initial_mutator(Box::new(|builder| builder.import_all_data_columns(true)))
+        .mock_execution_layer()
+        .build();
+    let da_checker = &harness.chain.data_availability_checker;
+
+    let (block, blob_sidecars) =
+        generate_rand_block_and_blobs::<E>(ForkName::Deneb, NumBlobs::Number(1), &mut thread_rng());
+    let block = Arc::new(block);
+    let block_root = block.canonical_root();
+    let blobs = BlobsList::<E>::new(
+        blob_sidecars
+            .into_iter()
+            .map(|sidecar| sidecar.blob)
+            .collect(),
+    )
+    .unwrap();
+    let data_columns = DataColumnSidecar::build_sidecars(&blobs, &block, &KZG, &spec).unwrap();
+    assert_eq!(data_columns.len(), spec.number_of_columns);
+
+    // The number of columns a regular node custodies is not enough for a supernode.
+    let regular_custody_column_count =
+        spec.custody_requirement as usize * spec.data_columns_per_subnet();
+    let regular_custody_columns = data_columns[..regular_custody_column_count].to_vec();
+    let rpc_block = RpcBlock::new_with_custody_columns(
+        Some(block_root),
+        block.clone(),
+        regular_custody_columns.clone(),
+        regular_custody_columns.iter().map(|c| c.index).collect(),
+    )
+    .unwrap();
+    assert!(matches!(
+        da_checker.verify_kzg_for_rpc_block(rpc_block).unwrap(),
+        MaybeAvailableBlock::AvailabilityPending { .. }
+    ));
+
+    // A duplicated column is not accepted in place of a missing custody column.
+    let all_column_indices = (0..spec.number_of_columns as ColumnIndex).collect::<Vec<_>>();
+    let mut duplicated_columns = data_columns.clone();
+    duplicated_columns[1] = duplicated_columns[0].clone();
+    let rpc_block = RpcBlock::new_with_custody_columns(
+        Some(block_root),
+        block.clone(),
+        duplicated_columns,
+        all_column_indices.clone(),
+    )
+    .unwrap();
+    assert!(matches!(
+        da_checker.verify_kzg_for_rpc_block(rpc_block).unwrap(),
+        MaybeAvailableBlock::AvailabilityPending { .. }
+    ));
+
+    let rpc_block = RpcBlock::new_with_custody_columns(
+        Some(block_root),
+        block,
+        data_columns,
+        all_column_indices,
+    )
+    .unwrap();
+    assert!(matches!(
+        da_checker.verify_kzg_for_rpc_block(rpc_block).unwrap(),
+        MaybeAvailableBlock::Available(_)
+    ));
+}
+
 async fn import_execution_pending_block<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
     execution_pending_block: ExecutionPendingBlock<T>,
@@ -0,0 +1,125 @@
+use beacon_chain::block_verification_types::RpcBlock;
+use beacon_chain::fetch_blobs::fetch_and_process_engine_blobs;
+use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
+use beacon_chain::{AvailabilityProcessingStatus, NotifyExecutionLayer};
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::time::Duration;
+use types::{BlobSidecar, BlockImportSource, EthSpec, ForkName, MinimalEthSpec, SignedBeaconBlock};
+
+type E = MinimalEthSpec;
+
+const VALIDATOR_COUNT: usize = 8;
+
+fn get_harness() -> BeaconChainHarness<EphemeralHarnessType<E>> {
+    let spec = ForkName::Deneb.make_genesis_spec(E::default_spec());
+    let harness = BeaconChainHarness::builder(E::default())
+        .spec(spec)
+        .deterministic_keypairs(VALIDATOR_COUNT)
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+    harness.advance_slot();
+    harness
+}
+
+/// Produces a block at the next slot which commits to at least one blob. The blobs remain in the
+/// mock EL's bundles, which it serves from `engine_getBlobsV1`.
+async fn make_block_with_blobs(
+    harness: &BeaconChainHarness<EphemeralHarnessType<E>>,
+) -> Arc<SignedBeaconBlock<E>> {
+    for _ in 0..16 {
+        let state = harness.get_current_state();
+        let slot = harness.get_current_slot();
+        let ((block, _), _) = harness.make_block(state, slot).await;
+        if block.num_expected_blobs() > 0 {
+            return block;
+        }
+    }
+    panic!("mock EL did not produce a block with blobs");
+}
+
+/// Blobs fetched from the EL should complete a block that arrived without its blobs, and every
+/// blob not yet seen on gossip should be published.
+#[tokio::test]
+async fn fetch_engine_blobs_completes_block() {
+    let harness = get_harness();
+    let block = make_block_with_blobs(&harness).await;
+    let block_root = block.canonical_root();
+    let num_expected_blobs = block.num_expected_blobs();
+
+    let status = harness
+        .chain
+        .process_block(
+            block_root,
+            RpcBlock::new_without_blobs(Some(block_root), block.clone()),
+            NotifyExecutionLayer::Yes,
+            BlockImportSource::Gossip,
+            || Ok(()),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(
+        status,
+        AvailabilityProcessingStatus::MissingComponents(_, _)
+    ));
+
+    let published = Arc::new(Mutex::new(vec![]));
+    let published_clone = published.clone();
+    let publish_fn = move |blobs: Vec<Arc<BlobSidecar<E>>>| {
+        published_clone.lock().extend(blobs);
+    };
+
+    let status = fetch_and_process_engine_blobs(
+        harness.chain.clone(),
+        block_root,
+        block.clone(),
+        publish_fn,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        status,
+        Some(AvailabilityProcessingStatus::Imported(block_root))
+    );
+
+    let published = published.lock();
+    assert_eq!(published.len(), num_expected_blobs);
+    for blob in published.iter() {
+        assert_eq!(blob.block_root(), block_root);
+        assert!(blob.verify_blob_sidecar_inclusion_proof());
+    }
+}
+
+/// If the EL does not support `engine_getBlobsV1` nothing is fetched or published.
+#[tokio::test]
+async fn fetch_engine_blobs_unsupported_by_el() {
+    let harness = get_harness();
+    harness
+        .mock_execution_layer
+        .as_ref()
+        .unwrap()
+        .server
+        .set_engine_capabilities(execution_layer::EngineCapabilities {
+            get_blobs_v1: false,
+            ..execution_layer::test_utils::DEFAULT_ENGINE_CAPABILITIES
+        });
+    // refresh capabilities cache
+    harness
+        .chain
+        .execution_layer
+        .as_ref()
+        .unwrap()
+        .get_engine_capabilities(Some(Duration::ZERO))
+        .await
+        .unwrap();
+    let block = make_block_with_blobs(&harness).await;
+    let block_root = block.canonical_root();
+
+    let status = fetch_and_process_engine_blobs(harness.chain.clone(), block_root, block, |_| {
+        panic!("no blobs should be published")
+    })
+    .await
+    .unwrap();
+    assert_eq!(status, None);
+}
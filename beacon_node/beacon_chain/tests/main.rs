@@ -4,6 +4,7 @@ mod bellatrix;
 mod block_verification;
 mod capella;
 mod events;
+mod fetch_blobs;
 mod op_verification;
 mod payload_invalidation;
 mod rewards;
@@ -19,6 +19,7 @@ use logging::test_logger;
 use maplit::hashset;
 use rand::Rng;
 use slot_clock::{SlotClock, TestingSlotClock};
+use ssz::Encode;
 use state_processing::{state_advance::complete_state_advance, BlockReplayer};
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -26,16 +27,20 @@ use std::convert::TryInto;
 use std::sync::Arc;
 use std::time::Duration;
 use store::chunked_vector::Chunk;
-use store::metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION, STATE_UPPER_LIMIT_NO_RETAIN};
+use store::hdiff::HierarchyConfig;
+use store::metadata::{
+    SchemaVersion, CURRENT_SCHEMA_VERSION, FREEZER_MIGRATION_PROGRESS_KEY,
+    STATE_UPPER_LIMIT_NO_RETAIN,
+};
 use store::{
     chunked_vector::{chunk_key, Field},
     get_key_for_col,
     iter::{BlockRootsIterator, StateRootsIterator},
-    BlobInfo, DBColumn, HotColdDB, KeyValueStore, KeyValueStoreOp, LevelDB, StoreConfig,
+    BeaconNodeBackend, BlobInfo, DBColumn, HotColdDB, KeyValueStore, KeyValueStoreOp, StoreConfig,
 };
 use tempfile::{tempdir, TempDir};
 use tokio::time::sleep;
-use types::test_utils::{SeedableRng, XorShiftRng};
+use types::test_utils::{SeedableRng, TestRandom, XorShiftRng};
 use types::*;
 
 // Should ideally be divisible by 3.
@@ -50,7 +55,7 @@ lazy_static! {
 type E = MinimalEthSpec;
 type TestHarness = BeaconChainHarness<DiskHarnessType<E>>;
 
-fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>> {
     get_store_generic(db_path, StoreConfig::default(), test_spec::<E>())
 }
 
@@ -58,7 +63,7 @@ fn get_store_generic(
     db_path: &TempDir,
     config: StoreConfig,
     spec: ChainSpec,
-) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>> {
     let hot_path = db_path.path().join("hot_db");
     let cold_path = db_path.path().join("cold_db");
     let blobs_path = db_path.path().join("blobs_db");
@@ -77,7 +82,7 @@ fn get_store_generic(
 }
 
 fn get_harness(
-    store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
     validator_count: usize,
 ) -> TestHarness {
     // Most tests expect to retain historic states, so we use this as the default.
@@ -89,7 +94,7 @@ fn get_harness(
 }
 
 fn get_harness_generic(
-    store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
     validator_count: usize,
     chain_config: ChainConfig,
 ) -> TestHarness {
@@ -105,7 +110,7 @@ fn get_harness_generic(
     harness
 }
 
-/// Tests that `store.heal_freezer_block_roots_at_split` inserts block roots between last restore point
+/// Tests that `store.heal_freezer_block_roots_at_split` inserts block roots between last cold state
 /// slot and the split slot.
 #[tokio::test]
 async fn heal_freezer_block_roots_at_split() {
@@ -115,7 +120,9 @@ async fn heal_freezer_block_roots_at_split() {
     let store = get_store_generic(
         &db_path,
         StoreConfig {
-            slots_per_restore_point: 2 * E::slots_per_epoch(),
+            hierarchy_config: HierarchyConfig {
+                exponents: vec![4, 5, 7],
+            },
             ..Default::default()
         },
         test_spec::<E>(),
@@ -134,13 +141,13 @@ async fn heal_freezer_block_roots_at_split() {
     assert_eq!(split_slot, 18 * E::slots_per_epoch());
 
     // Do a heal before deleting to make sure that it doesn't break.
-    let last_restore_point_slot = Slot::new(16 * E::slots_per_epoch());
+    let last_cold_state_slot = Slot::new(16 * E::slots_per_epoch());
     store.heal_freezer_block_roots_at_split().unwrap();
-    check_freezer_block_roots(&harness, last_restore_point_slot, split_slot);
+    check_freezer_block_roots(&harness, last_cold_state_slot, split_slot);
 
-    // Delete block roots between `last_restore_point_slot` and `split_slot`.
+    // Delete block roots between `last_cold_state_slot` and `split_slot`.
     let chunk_index = <store::chunked_vector::BlockRoots as Field<E>>::chunk_index(
-        last_restore_point_slot.as_usize(),
+        last_cold_state_slot.as_usize(),
     );
     let key_chunk = get_key_for_col(DBColumn::BeaconBlockRoots.as_str(), &chunk_key(chunk_index));
     store
@@ -150,8 +157,8 @@ async fn heal_freezer_block_roots_at_split() {
 
     let block_root_err = store
         .forwards_block_roots_iterator_until(
-            last_restore_point_slot,
-            last_restore_point_slot + 1,
+            last_cold_state_slot,
+            last_cold_state_slot + 1,
             || unreachable!(),
             &harness.chain.spec,
         )
@@ -164,7 +171,7 @@ async fn heal_freezer_block_roots_at_split() {
 
     // Re-insert block roots
     store.heal_freezer_block_roots_at_split().unwrap();
-    check_freezer_block_roots(&harness, last_restore_point_slot, split_slot);
+    check_freezer_block_roots(&harness, last_cold_state_slot, split_slot);
 
     // Run for another two epochs to check that the invariant is maintained.
     let additional_blocks_produced = 2 * E::slots_per_epoch();
@@ -181,7 +188,7 @@ async fn heal_freezer_block_roots_at_split() {
     check_iterators(&harness);
 }
 
-/// Tests that `store.heal_freezer_block_roots` inserts block roots between last restore point
+/// Tests that `store.heal_freezer_block_roots` inserts block roots between last cold state
 /// slot and the split slot.
 #[tokio::test]
 async fn heal_freezer_block_roots_with_skip_slots() {
@@ -191,7 +198,9 @@ async fn heal_freezer_block_roots_with_skip_slots() {
     let store = get_store_generic(
         &db_path,
         StoreConfig {
-            slots_per_restore_point: 2 * E::slots_per_epoch(),
+            hierarchy_config: HierarchyConfig {
+                exponents: vec![4, 5, 7],
+            },
             ..Default::default()
         },
         test_spec::<E>(),
@@ -217,9 +226,9 @@ async fn heal_freezer_block_roots_with_skip_slots() {
     let split_slot = store.get_split_slot();
     assert_eq!(split_slot, 18 * E::slots_per_epoch());
 
-    let last_restore_point_slot = Slot::new(16 * E::slots_per_epoch());
+    let last_cold_state_slot = Slot::new(16 * E::slots_per_epoch());
     let chunk_index = <store::chunked_vector::BlockRoots as Field<E>>::chunk_index(
-        last_restore_point_slot.as_usize(),
+        last_cold_state_slot.as_usize(),
     );
     let key_chunk = get_key_for_col(DBColumn::BeaconBlockRoots.as_str(), &chunk_key(chunk_index));
     store
@@ -229,8 +238,8 @@ async fn heal_freezer_block_roots_with_skip_slots() {
 
     let block_root_err = store
         .forwards_block_roots_iterator_until(
-            last_restore_point_slot,
-            last_restore_point_slot + 1,
+            last_cold_state_slot,
+            last_cold_state_slot + 1,
             || unreachable!(),
             &harness.chain.spec,
         )
@@ -243,7 +252,7 @@ async fn heal_freezer_block_roots_with_skip_slots() {
 
     // heal function
     store.heal_freezer_block_roots_at_split().unwrap();
-    check_freezer_block_roots(&harness, last_restore_point_slot, split_slot);
+    check_freezer_block_roots(&harness, last_cold_state_slot, split_slot);
 
     // Run for another two epochs to check that the invariant is maintained.
     let additional_blocks_produced = 2 * E::slots_per_epoch();
@@ -680,10 +689,10 @@ async fn forwards_iter_block_and_state_roots_until() {
     check_finalization(&harness, num_blocks_produced);
     check_split_slot(&harness, store.clone());
 
-    // The last restore point slot is the point at which the hybrid forwards iterator behaviour
+    // The last cold state slot is the point at which the hybrid forwards iterator behaviour
     // changes.
-    let last_restore_point_slot = store.get_latest_restore_point_slot().unwrap();
-    assert!(last_restore_point_slot > 0);
+    let last_cold_state_slot = store.get_latest_cold_state_slot().unwrap();
+    assert!(last_cold_state_slot > 0);
 
     let chain = &harness.chain;
     let head_state = harness.get_current_state();
@@ -708,14 +717,14 @@ async fn forwards_iter_block_and_state_roots_until() {
     };
 
     let split_slot = store.get_split_slot();
-    assert!(split_slot > last_restore_point_slot);
+    assert!(split_slot > last_cold_state_slot);
 
-    test_range(Slot::new(0), last_restore_point_slot);
-    test_range(last_restore_point_slot, last_restore_point_slot);
-    test_range(last_restore_point_slot - 1, last_restore_point_slot);
-    test_range(Slot::new(0), last_restore_point_slot - 1);
+    test_range(Slot::new(0), last_cold_state_slot);
+    test_range(last_cold_state_slot, last_cold_state_slot);
+    test_range(last_cold_state_slot - 1, last_cold_state_slot);
+    test_range(Slot::new(0), last_cold_state_slot - 1);
     test_range(Slot::new(0), split_slot);
-    test_range(last_restore_point_slot - 1, split_slot);
+    test_range(last_cold_state_slot - 1, split_slot);
     test_range(Slot::new(0), head_state.slot());
 }
 
@@ -2544,7 +2553,7 @@ async fn weak_subjectivity_sync_test(slots: Vec<Slot>, checkpoint_slot: Slot) {
     // signatures correctly. Regression test for https://github.com/sigp/lighthouse/pull/5120.
     let mut batch_with_invalid_first_block = available_blocks.clone();
     batch_with_invalid_first_block[0] = {
-        let (block_root, block, blobs) = available_blocks[0].clone().deconstruct();
+        let (block_root, block, blobs, _data_columns) = available_blocks[0].clone().deconstruct();
         let mut corrupt_block = (*block).clone();
         *corrupt_block.signature_mut() = Signature::empty();
         AvailableBlock::__new_for_testing(block_root, Arc::new(corrupt_block), blobs)
@@ -2615,6 +2624,17 @@ async fn weak_subjectivity_sync_test(slots: Vec<Slot>, checkpoint_slot: Slot) {
     // Reconstruct states.
     store.clone().reconstruct_historic_states().unwrap();
     assert_eq!(store.get_anchor_slot(), None);
+
+    // All states can be loaded after reconstruction, including those stored prior to it.
+    for (state_root, slot) in beacon_chain
+        .forwards_iter_state_roots(Slot::new(0))
+        .unwrap()
+        .map(Result::unwrap)
+    {
+        let mut state = store.get_state(&state_root, Some(slot)).unwrap().unwrap();
+        assert_eq!(state.slot(), slot);
+        assert_eq!(state.canonical_root().unwrap(), state_root);
+    }
 }
 
 /// Test that blocks and attestations that refer to states around an unaligned split state are
@@ -3113,6 +3133,123 @@ async fn schema_downgrade_to_min_version() {
     .expect_err("should not downgrade below minimum version");
 }
 
+// This test checks that the freezer is converted to restore points by the v22 downgrade, and back
+// to hierarchical diffs by the upgrade, which resumes from the progress of an interrupted attempt.
+#[tokio::test]
+async fn schema_v22_freezer_migration_round_trip() {
+    let num_blocks_produced = E::slots_per_epoch() * 12;
+    let slots_per_restore_point = E::slots_per_historical_root() as u64;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+    let spec = &harness.chain.spec.clone();
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let split_slot = store.get_split_slot();
+    assert!(split_slot.as_u64() > slots_per_restore_point);
+    let state_roots = (0..split_slot.as_u64())
+        .map(|slot| {
+            harness
+                .chain
+                .state_root_at_slot(Slot::new(slot))
+                .unwrap()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let first_block_root = harness
+        .chain
+        .block_root_at_slot(Slot::new(1), WhenSlotSkipped::None)
+        .unwrap()
+        .unwrap();
+
+    // Close the database to ensure everything is written to disk.
+    drop(store);
+    drop(harness);
+    let store = get_store(&db_path);
+
+    // Downgrade to restore points.
+    migrate_schema::<DiskHarnessType<E>>(
+        store.clone(),
+        0,
+        CURRENT_SCHEMA_VERSION,
+        SchemaVersion(21),
+        store.logger().clone(),
+        spec,
+    )
+    .expect("schema downgrade to v21 should work");
+
+    for slot in (0..split_slot.as_u64()).step_by(slots_per_restore_point as usize) {
+        let (state_root, state) = store
+            .load_legacy_restore_point(slot / slots_per_restore_point)
+            .unwrap()
+            .expect("restore point should be stored");
+        assert_eq!(state.slot(), Slot::new(slot));
+        assert_eq!(state_root, state_roots[slot as usize]);
+    }
+
+    // Simulate an upgrade which was interrupted after re-storing the states up to the second
+    // restore point.
+    let resume_slot = Slot::new(slots_per_restore_point);
+    let (genesis_state_root, genesis_state) = store.load_legacy_restore_point(0).unwrap().unwrap();
+    let mut ops = vec![];
+    store
+        .store_cold_state(&genesis_state_root, &genesis_state, &mut ops)
+        .unwrap();
+    store.cold_db.do_atomically(ops).unwrap();
+    store
+        .store_cold_states_by_replay(genesis_state, resume_slot + 1, |_| Ok(()))
+        .unwrap();
+    store
+        .hot_db
+        .put_bytes(
+            DBColumn::BeaconMeta.into(),
+            FREEZER_MIGRATION_PROGRESS_KEY.as_bytes(),
+            &resume_slot.as_ssz_bytes(),
+        )
+        .unwrap();
+
+    // Blocks prior to the resume slot should not be replayed again.
+    store.delete_block(&first_block_root).unwrap();
+
+    migrate_schema::<DiskHarnessType<E>>(
+        store.clone(),
+        0,
+        SchemaVersion(21),
+        CURRENT_SCHEMA_VERSION,
+        store.logger().clone(),
+        spec,
+    )
+    .expect("schema upgrade to v22 should resume");
+
+    assert!(store.load_legacy_restore_point(0).unwrap().is_none());
+    assert!(store
+        .hot_db
+        .get_bytes(
+            DBColumn::BeaconMeta.into(),
+            FREEZER_MIGRATION_PROGRESS_KEY.as_bytes(),
+        )
+        .unwrap()
+        .is_none());
+
+    for slot in resume_slot.as_u64()..split_slot.as_u64() {
+        let mut state = store
+            .load_cold_state_by_slot(Slot::new(slot))
+            .unwrap()
+            .expect("state should be available");
+        assert_eq!(
+            state.update_tree_hash_cache().unwrap(),
+            state_roots[slot as usize]
+        );
+    }
+}
+
 /// Check that blob pruning prunes blobs older than the data availability boundary.
 #[tokio::test]
 async fn deneb_prune_blobs_happy_case() {
@@ -3160,6 +3297,67 @@ async fn deneb_prune_blobs_happy_case() {
     check_blob_existence(&harness, oldest_blob_slot, harness.head_slot(), true);
 }
 
+/// Check that data columns are pruned along with blobs.
+#[tokio::test]
+async fn deneb_prune_data_columns() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+
+    if store.get_chain_spec().deneb_fork_epoch.is_none() {
+        // No-op prior to Deneb.
+        return;
+    }
+
+    let num_blocks_produced = E::slots_per_epoch() * 8;
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    // Store a data column for every block.
+    let rng = &mut XorShiftRng::from_seed([42; 16]);
+    let block_roots = harness
+        .chain
+        .forwards_iter_block_roots_until(Slot::new(1), harness.head_slot())
+        .unwrap()
+        .map(Result::unwrap)
+        .collect::<Vec<_>>();
+    for (block_root, _) in &block_roots {
+        let data_column = Arc::new(DataColumnSidecar::<E>::random_for_test(rng));
+        store
+            .put_data_columns(block_root, vec![data_column])
+            .unwrap();
+    }
+
+    // Trigger pruning of blobs and data columns older than epoch 2.
+    let data_availability_boundary = Epoch::new(2);
+    store
+        .try_prune_blobs(true, data_availability_boundary)
+        .unwrap();
+
+    let oldest_blob_slot = store.get_blob_info().oldest_blob_slot.unwrap();
+    for (block_root, slot) in &block_roots {
+        let data_column_indices = store.get_data_column_keys(*block_root).unwrap();
+        if *slot < oldest_blob_slot {
+            assert!(
+                data_column_indices.is_empty(),
+                "data columns at slot {slot} exist but should not"
+            );
+        } else {
+            assert_eq!(
+                data_column_indices.len(),
+                1,
+                "missing data column at slot {slot}"
+            );
+        }
+    }
+}
+
 /// Check that blob pruning does not prune without finalization.
 #[tokio::test]
 async fn deneb_prune_blobs_no_finalization() {
@@ -3563,7 +3761,10 @@ fn check_finalization(harness: &TestHarness, expected_slot: u64) {
 }
 
 /// Check that the HotColdDB's split_slot is equal to the start slot of the last finalized epoch.
-fn check_split_slot(harness: &TestHarness, store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>) {
+fn check_split_slot(
+    harness: &TestHarness,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
+) {
     let split_slot = store.get_split_slot();
     assert_eq!(
         harness
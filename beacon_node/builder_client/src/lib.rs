@@ -71,6 +71,11 @@ impl BuilderHttpClient {
         &self.user_agent
     }
 
+    /// The URL of the relay this client talks to.
+    pub fn server(&self) -> &SensitiveUrl {
+        &self.server
+    }
+
     async fn get_with_timeout<T: DeserializeOwned, U: IntoUrl>(
         &self,
         url: U,
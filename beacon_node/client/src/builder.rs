@@ -16,7 +16,7 @@ use beacon_chain::{
     eth1_chain::{CachingEth1Backend, Eth1Chain},
     slot_clock::{SlotClock, SystemTimeSlotClock},
     state_advance_timer::spawn_state_advance_timer,
-    store::{HotColdDB, ItemStore, LevelDB, StoreConfig},
+    store::{BeaconNodeBackend, HotColdDB, ItemStore, StoreConfig},
     BeaconChain, BeaconChainTypes, Eth1ChainBackend, MigratorConfig, ServerSentEventHandler,
 };
 use beacon_processor::{BeaconProcessor, BeaconProcessorChannels};
@@ -207,6 +207,7 @@ where
             .beacon_graffiti(beacon_graffiti)
             .event_handler(event_handler)
             .execution_layer(execution_layer)
+            .import_all_data_columns(config.network.subscribe_all_data_column_subnets)
             .validator_monitor_config(config.validator_monitor.clone());
 
         let builder = if let Some(slasher) = self.slasher.clone() {
@@ -968,6 +969,23 @@ where
                     },
                     "lc_update",
                 );
+
+                // Compute the best updates of past periods, which were not observed during block
+                // import.
+                let backfill_chain = beacon_chain.clone();
+                let log = light_client_update_context.log().clone();
+                light_client_update_context.executor.spawn_blocking(
+                    move || {
+                        if let Err(e) = backfill_chain.backfill_light_client_updates() {
+                            warn!(
+                                log,
+                                "Failed to backfill light client updates";
+                                "error" => ?e
+                            );
+                        }
+                    },
+                    "lc_update_backfill",
+                );
             }
 
             start_proposer_prep_service(runtime_context.executor.clone(), beacon_chain.clone());
@@ -1033,7 +1051,7 @@ where
 }
 
 impl<TSlotClock, TEth1Backend, E>
-    ClientBuilder<Witness<TSlotClock, TEth1Backend, E, LevelDB<E>, LevelDB<E>>>
+    ClientBuilder<Witness<TSlotClock, TEth1Backend, E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>
 where
     TSlotClock: SlotClock + 'static,
     TEth1Backend: Eth1ChainBackend<E> + 'static,
@@ -2,6 +2,7 @@
 
 use super::*;
 use crate::auth::Auth;
+use crate::ipc::IpcClient;
 use crate::json_structures::*;
 use lazy_static::lazy_static;
 use lighthouse_version::{COMMIT_PREFIX, VERSION};
@@ -52,6 +53,9 @@ pub const ENGINE_GET_PAYLOAD_BODIES_BY_HASH_V1: &str = "engine_getPayloadBodiesB
 pub const ENGINE_GET_PAYLOAD_BODIES_BY_RANGE_V1: &str = "engine_getPayloadBodiesByRangeV1";
 pub const ENGINE_GET_PAYLOAD_BODIES_TIMEOUT: Duration = Duration::from_secs(10);
 
+pub const ENGINE_GET_BLOBS_V1: &str = "engine_getBlobsV1";
+pub const ENGINE_GET_BLOBS_TIMEOUT: Duration = Duration::from_secs(1);
+
 pub const ENGINE_EXCHANGE_CAPABILITIES: &str = "engine_exchangeCapabilities";
 pub const ENGINE_EXCHANGE_CAPABILITIES_TIMEOUT: Duration = Duration::from_secs(1);
 
@@ -79,6 +83,7 @@ pub static LIGHTHOUSE_CAPABILITIES: &[&str] = &[
     ENGINE_GET_PAYLOAD_BODIES_BY_HASH_V1,
     ENGINE_GET_PAYLOAD_BODIES_BY_RANGE_V1,
     ENGINE_GET_CLIENT_VERSION_V1,
+    ENGINE_GET_BLOBS_V1,
 ];
 
 lazy_static! {
@@ -600,6 +605,9 @@ pub struct HttpJsonRpc {
     pub engine_capabilities_cache: Mutex<Option<CachedResponse<EngineCapabilities>>>,
     pub engine_version_cache: Mutex<Option<CachedResponse<Vec<ClientVersionV1>>>>,
     auth: Option<Auth>,
+    /// Set if `url` uses the IPC scheme, in which case requests are sent over the socket instead
+    /// of HTTP.
+    ipc: Option<IpcClient>,
 }
 
 impl HttpJsonRpc {
@@ -609,11 +617,12 @@ impl HttpJsonRpc {
     ) -> Result<Self, Error> {
         Ok(Self {
             client: Client::builder().build()?,
-            url,
             execution_timeout_multiplier: execution_timeout_multiplier.unwrap_or(1),
             engine_capabilities_cache: Mutex::new(None),
             engine_version_cache: Mutex::new(None),
             auth: None,
+            ipc: IpcClient::from_url(&url),
+            url,
         })
     }
 
@@ -624,11 +633,12 @@ impl HttpJsonRpc {
     ) -> Result<Self, Error> {
         Ok(Self {
             client: Client::builder().build()?,
-            url,
             execution_timeout_multiplier: execution_timeout_multiplier.unwrap_or(1),
             engine_capabilities_cache: Mutex::new(None),
             engine_version_cache: Mutex::new(None),
             auth: Some(auth),
+            ipc: IpcClient::from_url(&url),
+            url,
         })
     }
 
@@ -645,20 +655,24 @@ impl HttpJsonRpc {
             id: json!(STATIC_ID),
         };
 
-        let mut request = self
-            .client
-            .post(self.url.full.clone())
-            .timeout(timeout)
-            .header(CONTENT_TYPE, "application/json")
-            .json(&body);
+        let body: JsonResponseBody = if let Some(ipc) = &self.ipc {
+            ipc.request(&body, timeout).await?
+        } else {
+            let mut request = self
+                .client
+                .post(self.url.full.clone())
+                .timeout(timeout)
+                .header(CONTENT_TYPE, "application/json")
+                .json(&body);
+
+            // Generate and add a jwt token to the header if auth is defined.
+            if let Some(auth) = &self.auth {
+                request = request.bearer_auth(auth.generate_token()?);
+            };
 
-        // Generate and add a jwt token to the header if auth is defined.
-        if let Some(auth) = &self.auth {
-            request = request.bearer_auth(auth.generate_token()?);
+            request.send().await?.error_for_status()?.json().await?
         };
 
-        let body: JsonResponseBody = request.send().await?.error_for_status()?.json().await?;
-
         match (body.result, body.error) {
             (result, None) => serde_json::from_value(result).map_err(Into::into),
             (_, Some(error)) => {
@@ -1077,6 +1091,20 @@ impl HttpJsonRpc {
             .collect())
     }
 
+    pub async fn get_blobs<E: EthSpec>(
+        &self,
+        versioned_hashes: Vec<Hash256>,
+    ) -> Result<Vec<Option<BlobAndProofV1<E>>>, Error> {
+        let params = json!([versioned_hashes]);
+
+        self.rpc_request(
+            ENGINE_GET_BLOBS_V1,
+            params,
+            ENGINE_GET_BLOBS_TIMEOUT * self.execution_timeout_multiplier,
+        )
+        .await
+    }
+
     pub async fn exchange_capabilities(&self) -> Result<EngineCapabilities, Error> {
         let params = json!([LIGHTHOUSE_CAPABILITIES]);
 
@@ -1105,6 +1133,7 @@ impl HttpJsonRpc {
             get_payload_v3: capabilities.contains(ENGINE_GET_PAYLOAD_V3),
             get_payload_v4: capabilities.contains(ENGINE_GET_PAYLOAD_V4),
             get_client_version_v1: capabilities.contains(ENGINE_GET_CLIENT_VERSION_V1),
+            get_blobs_v1: capabilities.contains(ENGINE_GET_BLOBS_V1),
         })
     }
 
@@ -1687,6 +1716,36 @@ mod test {
             .await;
     }
 
+    #[tokio::test]
+    async fn get_blobs_request() {
+        Tester::new(true)
+            .assert_request_equals(
+                |client| async move {
+                    let _ = client
+                        .get_blobs::<MainnetEthSpec>(vec![
+                            Hash256::repeat_byte(0),
+                            Hash256::repeat_byte(1),
+                        ])
+                        .await;
+                },
+                json!({
+                    "id": STATIC_ID,
+                    "jsonrpc": JSONRPC_VERSION,
+                    "method": ENGINE_GET_BLOBS_V1,
+                    "params": [[HASH_00, HASH_01]]
+                }),
+            )
+            .await;
+
+        Tester::new(false)
+            .assert_auth_failure(|client| async move {
+                client
+                    .get_blobs::<MainnetEthSpec>(vec![Hash256::repeat_byte(1)])
+                    .await
+            })
+            .await;
+    }
+
     #[tokio::test]
     async fn new_payload_v1_request() {
         Tester::new(true)
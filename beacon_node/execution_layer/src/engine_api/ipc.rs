@@ -0,0 +1,350 @@
+//! Provides a JSON-RPC transport over a Unix domain socket, for use when the execution engine runs
+//! on the same host as the beacon node.
+//!
+//! IPC endpoints are selected by the `ipc` URL scheme, e.g. `ipc:///var/run/geth/geth.ipc`.
+//! Requests are written as JSON values to the socket and responses are read back as a stream of
+//! JSON values, the same framing used by the IPC servers of the execution clients. Access to the
+//! socket implies access to the host, so no JWT authentication takes place.
+use super::Error;
+use sensitive_url::SensitiveUrl;
+use serde::{de::DeserializeOwned, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// The URL scheme which selects the IPC transport.
+pub const IPC_URL_SCHEME: &str = "ipc";
+
+/// Read responses in chunks of this size.
+#[cfg(unix)]
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The maximum number of idle connections kept open for reuse.
+#[cfg(unix)]
+const MAX_IDLE_CONNECTIONS: usize = 8;
+
+/// Returns `true` if `url` should be served by the IPC transport.
+pub fn is_ipc_url(url: &SensitiveUrl) -> bool {
+    url.full.scheme() == IPC_URL_SCHEME
+}
+
+pub struct IpcClient {
+    path: PathBuf,
+    #[cfg(unix)]
+    idle_connections: tokio::sync::Mutex<Vec<tokio::net::UnixStream>>,
+}
+
+impl IpcClient {
+    /// Returns an `IpcClient` for the socket at the path of `url`, or `None` if `url` does not use
+    /// the IPC scheme.
+    pub fn from_url(url: &SensitiveUrl) -> Option<Self> {
+        is_ipc_url(url).then(|| Self {
+            path: PathBuf::from(url.full.path()),
+            #[cfg(unix)]
+            idle_connections: <_>::default(),
+        })
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Sends `body` to the socket and returns the deserialized response.
+    ///
+    /// Connections are reused across requests, but each connection only ever has a single request
+    /// in flight so responses never need to be matched to requests by id.
+    #[cfg(unix)]
+    pub async fn request<B: Serialize, D: DeserializeOwned>(
+        &self,
+        body: &B,
+        timeout: Duration,
+    ) -> Result<D, Error> {
+        let mut bytes = serde_json::to_vec(body)?;
+        bytes.push(b'\n');
+
+        let request = async {
+            let idle_connection = self.idle_connections.lock().await.pop();
+            match idle_connection {
+                Some(stream) => match self.send(stream, &bytes).await {
+                    // The server may have closed the pooled connection since it was last used,
+                    // e.g. because the execution client restarted. Drop it and retry once on a
+                    // new connection.
+                    Err(Error::Ipc(_)) => self.send(self.connect().await?, &bytes).await,
+                    result => result,
+                },
+                None => self.send(self.connect().await?, &bytes).await,
+            }
+        };
+
+        // Dropping the request future on timeout also drops its connection, so a late response
+        // can never be read by a subsequent request.
+        tokio::time::timeout(timeout, request)
+            .await
+            .map_err(|_| Error::Ipc(format!("Request timed out after {timeout:?}")))?
+    }
+
+    #[cfg(unix)]
+    async fn connect(&self) -> Result<tokio::net::UnixStream, Error> {
+        tokio::net::UnixStream::connect(&self.path)
+            .await
+            .map_err(|e| Error::Ipc(format!("Failed to connect to socket: {e}")))
+    }
+
+    /// Writes `bytes` to `stream` and reads back the response, returning `stream` to the pool of
+    /// idle connections if the exchange succeeds.
+    #[cfg(unix)]
+    async fn send<D: DeserializeOwned>(
+        &self,
+        mut stream: tokio::net::UnixStream,
+        bytes: &[u8],
+    ) -> Result<D, Error> {
+        use tokio::io::AsyncWriteExt;
+
+        stream
+            .write_all(bytes)
+            .await
+            .map_err(|e| Error::Ipc(format!("Failed to write request: {e}")))?;
+        let response = read_json_value(&mut stream)
+            .await?
+            .ok_or_else(|| Error::Ipc("Socket closed before response".to_string()))?;
+
+        let mut idle_connections = self.idle_connections.lock().await;
+        if idle_connections.len() < MAX_IDLE_CONNECTIONS {
+            idle_connections.push(stream);
+        }
+        Ok(response)
+    }
+
+    #[cfg(not(unix))]
+    pub async fn request<B: Serialize, D: DeserializeOwned>(
+        &self,
+        _body: &B,
+        _timeout: Duration,
+    ) -> Result<D, Error> {
+        Err(Error::Ipc(
+            "IPC endpoints are only supported on unix platforms".to_string(),
+        ))
+    }
+}
+
+/// Reads a single JSON value from `reader`.
+///
+/// Returns `Ok(None)` if the stream is closed before any data is read.
+#[cfg(unix)]
+pub async fn read_json_value<R, D>(reader: &mut R) -> Result<Option<D>, Error>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    D: DeserializeOwned,
+{
+    use tokio::io::AsyncReadExt;
+
+    let mut buf = Vec::with_capacity(READ_CHUNK_SIZE);
+    loop {
+        buf.reserve(READ_CHUNK_SIZE);
+        let bytes_read = reader
+            .read_buf(&mut buf)
+            .await
+            .map_err(|e| Error::Ipc(format!("Failed to read response: {e}")))?;
+
+        let last_byte = buf.iter().rev().find(|byte| !byte.is_ascii_whitespace());
+        if bytes_read == 0 {
+            return if last_byte.is_none() {
+                Ok(None)
+            } else {
+                Err(Error::Ipc("Socket closed mid-response".to_string()))
+            };
+        }
+
+        // Only attempt to parse once the buffer could hold a complete JSON object, avoiding
+        // repeatedly parsing large responses which arrive over many reads.
+        if last_byte != Some(&b'}') {
+            continue;
+        }
+        match serde_json::from_slice(&buf) {
+            Ok(value) => return Ok(Some(value)),
+            Err(e) if e.is_eof() => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::*;
+    use crate::engine_api::http::{HttpJsonRpc, ETH_GET_BLOCK_BY_NUMBER, ETH_SYNCING};
+    use crate::engine_api::{BlockByNumberQuery, LATEST_TAG};
+    use crate::test_utils::MockServer;
+    use futures::future::join_all;
+    use serde_json::json;
+    use tempfile::TempDir;
+    use tokio::io::AsyncWriteExt;
+    use types::MainnetEthSpec;
+
+    struct Tester {
+        server: MockServer<MainnetEthSpec>,
+        client: HttpJsonRpc,
+        _dir: TempDir,
+    }
+
+    impl Tester {
+        fn new() -> Self {
+            let server = MockServer::unit_testing();
+            let dir = TempDir::new().unwrap();
+            let url = server.start_ipc_server(&dir.path().join("engine.ipc"));
+            let client = HttpJsonRpc::new(SensitiveUrl::parse(&url).unwrap(), None).unwrap();
+            Self {
+                server,
+                client,
+                _dir: dir,
+            }
+        }
+    }
+
+    #[test]
+    fn ipc_url_scheme() {
+        assert!(is_ipc_url(
+            &SensitiveUrl::parse("ipc:///tmp/geth.ipc").unwrap()
+        ));
+        assert!(!is_ipc_url(
+            &SensitiveUrl::parse("http://localhost:8551").unwrap()
+        ));
+
+        let url = SensitiveUrl::parse("ipc:///tmp/geth.ipc").unwrap();
+        let client = IpcClient::from_url(&url).unwrap();
+        assert_eq!(client.path(), &PathBuf::from("/tmp/geth.ipc"));
+    }
+
+    #[tokio::test]
+    async fn requests_served_over_ipc() {
+        let tester = Tester::new();
+
+        tester.client.upcheck().await.unwrap();
+        let request = tester.server.take_previous_request().unwrap();
+        assert_eq!(request["method"], ETH_SYNCING);
+
+        let block = tester
+            .client
+            .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
+            .await
+            .unwrap()
+            .unwrap();
+        let request = tester.server.take_previous_request().unwrap();
+        assert_eq!(request["method"], ETH_GET_BLOCK_BY_NUMBER);
+        assert_eq!(
+            block.block_hash,
+            tester
+                .server
+                .execution_block_generator()
+                .latest_block()
+                .unwrap()
+                .block_hash()
+        );
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_over_ipc() {
+        let tester = Tester::new();
+        let results = join_all((0..32).map(|_| tester.client.upcheck())).await;
+        assert!(results.into_iter().all(|result| result.is_ok()));
+    }
+
+    #[tokio::test]
+    async fn server_error_over_ipc() {
+        let tester = Tester::new();
+        tester.server.push_preloaded_response(json!({
+            "id": 1,
+            "jsonrpc": "2.0",
+            "error": {
+                "code": -32000,
+                "message": "internal error"
+            }
+        }));
+        let result = tester.client.upcheck().await;
+        assert!(
+            matches!(result, Err(Error::ServerMessage { code: -32000, .. })),
+            "{result:?}"
+        );
+
+        // The connection remains usable after an error response.
+        tester.client.upcheck().await.unwrap();
+    }
+
+    /// Accepts a single connection on `listener` and answers its requests until the client closes
+    /// it, or until `max_requests` have been answered.
+    async fn serve_connection(listener: &tokio::net::UnixListener, max_requests: Option<usize>) {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut served = 0;
+        while max_requests.map_or(true, |max| served < max) {
+            let Some(body) = read_json_value::<_, serde_json::Value>(&mut stream)
+                .await
+                .unwrap()
+            else {
+                break;
+            };
+            let mut bytes =
+                serde_json::to_vec(&json!({ "id": body["id"], "result": served })).unwrap();
+            bytes.push(b'\n');
+            stream.write_all(&bytes).await.unwrap();
+            served += 1;
+        }
+    }
+
+    #[tokio::test]
+    async fn reconnect_after_server_restart() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("engine.ipc");
+        let url = SensitiveUrl::parse(&format!("ipc://{}", path.display())).unwrap();
+        let client = IpcClient::from_url(&url).unwrap();
+        let timeout = Duration::from_secs(5);
+
+        // The first server answers a single request, after which it shuts down and closes the
+        // connection which the client has pooled.
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        let server = tokio::spawn(async move { serve_connection(&listener, Some(1)).await });
+        let response: serde_json::Value =
+            client.request(&json!({ "id": 1 }), timeout).await.unwrap();
+        assert_eq!(response["result"], 0);
+        server.await.unwrap();
+
+        // The restarted server is reached on a new connection.
+        std::fs::remove_file(&path).unwrap();
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        tokio::spawn(async move { serve_connection(&listener, None).await });
+        let response: serde_json::Value =
+            client.request(&json!({ "id": 2 }), timeout).await.unwrap();
+        assert_eq!(response["id"], 2);
+        assert_eq!(response["result"], 0);
+    }
+
+    #[tokio::test]
+    async fn missing_socket() {
+        let dir = TempDir::new().unwrap();
+        let url = format!("ipc://{}", dir.path().join("missing.ipc").display());
+        let client = HttpJsonRpc::new(SensitiveUrl::parse(&url).unwrap(), None).unwrap();
+        let result = client.upcheck().await;
+        assert!(matches!(result, Err(Error::Ipc(_))), "{result:?}");
+    }
+
+    #[tokio::test]
+    async fn read_response_across_many_chunks() {
+        let large_string = "a".repeat(4 * READ_CHUNK_SIZE + 1);
+        let value = json!({ "result": { "data": large_string } });
+        let bytes = serde_json::to_vec(&value).unwrap();
+
+        let (mut reader, mut writer) = tokio::io::duplex(1024);
+        let write = async move {
+            for chunk in bytes.chunks(1000) {
+                writer.write_all(chunk).await.unwrap();
+            }
+        };
+        let (read, ()) = tokio::join!(read_json_value::<_, serde_json::Value>(&mut reader), write);
+        assert_eq!(read.unwrap(), Some(value));
+
+        // The stream was closed after a complete response.
+        assert_eq!(
+            read_json_value::<_, serde_json::Value>(&mut reader)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+}
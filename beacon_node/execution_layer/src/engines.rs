@@ -245,6 +245,14 @@ impl Engine {
         EngineState::from(**self.state.read().await) == EngineState::Offline
     }
 
+    /// Mark the engine as offline until its next successful upcheck.
+    pub async fn set_offline(&self) {
+        self.state
+            .write()
+            .await
+            .update(EngineStateInternal::Offline);
+    }
+
     /// Run the `EngineApi::upcheck` function if the node's last known state is not synced. This
     /// might be used to recover the node if offline.
     pub async fn upcheck(&self) {
@@ -5,20 +5,23 @@
 //! deposit-contract functionality that the `beacon_node/eth1` crate already provides.
 
 use crate::payload_cache::PayloadCache;
-use arc_swap::ArcSwapOption;
+use arc_swap::ArcSwap;
 use auth::{strip_prefix, Auth, JwtKey};
 pub use block_hash::calculate_execution_block_hash;
 use builder_client::BuilderHttpClient;
+use engine_api::ipc::is_ipc_url;
 pub use engine_api::EngineCapabilities;
 use engine_api::Error as ApiError;
 pub use engine_api::*;
 pub use engine_api::{http, http::deposit_methods, http::HttpJsonRpc};
 use engines::{Engine, EngineError};
 pub use engines::{EngineState, ForkchoiceState};
-use eth2::types::FullPayloadContents;
 use eth2::types::{builder_bid::SignedBuilderBid, BlobsBundle, ForkVersionedResponse};
+use eth2::types::{FullPayloadContents, SignedValidatorRegistrationData};
 use ethers_core::types::Transaction as EthersTransaction;
 use fork_choice::ForkchoiceUpdateParameters;
+use futures::future::{join_all, select_ok};
+use futures::stream::{select_all, BoxStream, StreamExt};
 use lru::LruCache;
 use payload_status::process_payload_status;
 pub use payload_status::PayloadStatus;
@@ -31,7 +34,7 @@ use std::fmt;
 use std::future::Future;
 use std::io::Write;
 use std::num::NonZeroUsize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use strum::AsRefStr;
@@ -40,7 +43,6 @@ use tokio::{
     sync::{Mutex, MutexGuard, RwLock},
     time::sleep,
 };
-use tokio_stream::wrappers::WatchStream;
 use tree_hash::TreeHash;
 use types::beacon_block_body::KzgCommitments;
 use types::builder_bid::BuilderBid;
@@ -75,6 +77,10 @@ pub const DEFAULT_JWT_FILE: &str = "jwt.hex";
 /// in an LRU cache to avoid redundant lookups. This is the size of that cache.
 const EXECUTION_BLOCKS_LRU_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(128);
 
+/// The relay which provided each winning builder bid is remembered so that the signed blinded
+/// block is only revealed to that relay. This is the size of that cache.
+const WINNING_RELAYS_LRU_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(64);
+
 /// A fee recipient address for use during block production. Only used as a very last resort if
 /// there is no address provided by the user.
 ///
@@ -346,9 +352,17 @@ pub enum FailedCondition {
 
 type PayloadContentsRefTuple<'a, E> = (ExecutionPayloadRef<'a, E>, Option<&'a BlobsBundle<E>>);
 
+type RelayBidResult<E> =
+    Result<Option<ForkVersionedResponse<SignedBuilderBid<E>>>, builder_client::Error>;
+
 struct Inner<E: EthSpec> {
     engine: Arc<Engine>,
-    builder: ArcSwapOption<BuilderHttpClient>,
+    /// Engines to fail over to if the primary `engine` is offline or syncing, in order of
+    /// priority.
+    backup_engines: Vec<Arc<Engine>>,
+    builders: ArcSwap<Vec<Arc<BuilderHttpClient>>>,
+    /// The relay that provided the winning bid for each builder payload, keyed by block hash.
+    winning_relays: Mutex<LruCache<ExecutionBlockHash, Arc<BuilderHttpClient>>>,
     execution_engine_forkchoice_lock: Mutex<()>,
     suggested_fee_recipient: Option<Address>,
     proposer_preparation_data: Mutex<HashMap<u64, ProposerPreparationDataEntry>>,
@@ -367,16 +381,25 @@ struct Inner<E: EthSpec> {
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Config {
     /// Endpoint url for EL nodes that are running the engine api.
+    ///
+    /// Urls with the `ipc` scheme, e.g. `ipc:///path/to/geth.ipc`, connect to the EL over a Unix
+    /// domain socket without JWT authentication.
     pub execution_endpoint: Option<SensitiveUrl>,
+    /// Endpoint urls for backup EL nodes, used when the primary `execution_endpoint` is offline
+    /// or syncing.
+    pub backup_execution_endpoints: Vec<SensitiveUrl>,
     /// Endpoint urls for services providing the builder api.
-    pub builder_url: Option<SensitiveUrl>,
+    pub builder_urls: Vec<SensitiveUrl>,
     /// The timeout value used when making a request to fetch a block header
-    /// from the builder api.
+    /// from each builder api.
     pub builder_header_timeout: Option<Duration>,
     /// User agent to send with requests to the builder API.
     pub builder_user_agent: Option<String>,
     /// JWT secret for the above endpoint running the engine api.
     pub secret_file: Option<PathBuf>,
+    /// JWT secrets for each of the `backup_execution_endpoints`. If empty, the JWT secret of the
+    /// primary endpoint is used for all backups.
+    pub backup_secret_files: Vec<PathBuf>,
     /// The default fee recipient to use on the beacon node if none if provided from
     /// the validator client during block preparation.
     pub suggested_fee_recipient: Option<Address>,
@@ -397,14 +420,16 @@ pub struct ExecutionLayer<E: EthSpec> {
 }
 
 impl<E: EthSpec> ExecutionLayer<E> {
-    /// Instantiate `Self` with an Execution engine specified in `Config`, using JSON-RPC via HTTP.
+    /// Instantiate `Self` with an Execution engine specified in `Config`, using JSON-RPC via HTTP or IPC.
     pub fn from_config(config: Config, executor: TaskExecutor, log: Logger) -> Result<Self, Error> {
         let Config {
             execution_endpoint: url,
-            builder_url,
+            backup_execution_endpoints,
+            builder_urls,
             builder_user_agent,
             builder_header_timeout,
             secret_file,
+            backup_secret_files,
             suggested_fee_recipient,
             jwt_id,
             jwt_version,
@@ -417,46 +442,47 @@ impl<E: EthSpec> ExecutionLayer<E> {
         // Use the default jwt secret path if not provided via cli.
         let secret_file = secret_file.unwrap_or_else(|| default_datadir.join(DEFAULT_JWT_FILE));
 
-        let jwt_key = if secret_file.exists() {
-            // Read secret from file if it already exists
-            std::fs::read_to_string(&secret_file)
-                .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))
-                .and_then(|ref s| {
-                    let secret = JwtKey::from_slice(
-                        &hex::decode(strip_prefix(s.trim_end()))
-                            .map_err(|e| format!("Invalid hex string: {:?}", e))?,
-                    )?;
-                    Ok(secret)
-                })
-                .map_err(Error::InvalidJWTSecret)
+        // IPC endpoints are not authenticated, so a JWT secret is only loaded for HTTP endpoints.
+        let new_engine = |url: SensitiveUrl, secret_file: &Path| {
+            let api = if is_ipc_url(&url) {
+                debug!(log, "Loaded IPC execution endpoint"; "endpoint" => %url);
+                HttpJsonRpc::new(url, execution_timeout_multiplier)
+            } else {
+                let jwt_key = load_jwt_key(secret_file, &log)?;
+                let auth = Auth::new(jwt_key, jwt_id.clone(), jwt_version.clone());
+                debug!(log, "Loaded execution endpoint"; "endpoint" => %url, "jwt_path" => ?secret_file);
+                HttpJsonRpc::new_with_auth(url, auth, execution_timeout_multiplier)
+            }
+            .map_err(Error::ApiError)?;
+            Ok::<_, Error>(Engine::new(api, executor.clone(), &log))
+        };
+
+        let engine = new_engine(execution_url, &secret_file)?;
+
+        let backup_engines = if backup_secret_files.is_empty() {
+            backup_execution_endpoints
+                .into_iter()
+                .map(|url| new_engine(url, &secret_file).map(Arc::new))
+                .collect::<Result<Vec<_>, _>>()?
+        } else if backup_secret_files.len() == backup_execution_endpoints.len() {
+            backup_execution_endpoints
+                .into_iter()
+                .zip(backup_secret_files)
+                .map(|(url, secret_file)| new_engine(url, &secret_file).map(Arc::new))
+                .collect::<Result<Vec<_>, _>>()?
         } else {
-            // Create a new file and write a randomly generated secret to it if file does not exist
-            warn!(log, "No JWT found on disk. Generating"; "path" => %secret_file.display());
-            std::fs::File::options()
-                .write(true)
-                .create_new(true)
-                .open(&secret_file)
-                .map_err(|e| format!("Failed to open JWT secret file. Error: {:?}", e))
-                .and_then(|mut f| {
-                    let secret = auth::JwtKey::random();
-                    f.write_all(secret.hex_string().as_bytes())
-                        .map_err(|e| format!("Failed to write to JWT secret file: {:?}", e))?;
-                    Ok(secret)
-                })
-                .map_err(Error::InvalidJWTSecret)
-        }?;
-
-        let engine: Engine = {
-            let auth = Auth::new(jwt_key, jwt_id, jwt_version);
-            debug!(log, "Loaded execution endpoint"; "endpoint" => %execution_url, "jwt_path" => ?secret_file.as_path());
-            let api = HttpJsonRpc::new_with_auth(execution_url, auth, execution_timeout_multiplier)
-                .map_err(Error::ApiError)?;
-            Engine::new(api, executor.clone(), &log)
+            return Err(Error::InvalidJWTSecret(format!(
+                "{} JWT secrets provided for {} backup execution endpoints",
+                backup_secret_files.len(),
+                backup_execution_endpoints.len()
+            )));
         };
 
         let inner = Inner {
             engine: Arc::new(engine),
-            builder: ArcSwapOption::empty(),
+            backup_engines,
+            builders: ArcSwap::from_pointee(vec![]),
+            winning_relays: Mutex::new(LruCache::new(WINNING_RELAYS_LRU_CACHE_SIZE)),
             execution_engine_forkchoice_lock: <_>::default(),
             suggested_fee_recipient,
             proposer_preparation_data: Mutex::new(HashMap::new()),
@@ -472,22 +498,119 @@ impl<E: EthSpec> ExecutionLayer<E> {
             inner: Arc::new(inner),
         };
 
-        if let Some(builder_url) = builder_url {
-            el.set_builder_url(builder_url, builder_user_agent, builder_header_timeout)?;
+        if !builder_urls.is_empty() {
+            el.set_builder_urls(builder_urls, builder_user_agent, builder_header_timeout)?;
         }
 
         Ok(el)
     }
 
-    fn engine(&self) -> &Arc<Engine> {
+    /// Returns the engine that requests should be sent to: the first synced engine in order of
+    /// priority, or the primary engine if no engine is synced.
+    async fn engine(&self) -> &Arc<Engine> {
+        for engine in self.engines() {
+            if engine.is_synced().await {
+                return engine;
+            }
+        }
         &self.inner.engine
     }
 
-    pub fn builder(&self) -> Option<Arc<BuilderHttpClient>> {
-        self.inner.builder.load_full()
+    /// Returns all engines in order of priority, starting with the primary engine.
+    fn engines(&self) -> impl Iterator<Item = &Arc<Engine>> {
+        std::iter::once(&self.inner.engine).chain(self.inner.backup_engines.iter())
+    }
+
+    /// Returns the number of connected execution engines, including backups.
+    pub fn num_engines(&self) -> usize {
+        1 + self.inner.backup_engines.len()
+    }
+
+    /// Run `func` on the first synced engine, failing over to the next synced engine in order of
+    /// priority if it returns an error. If no engine is synced, only the primary engine is tried.
+    async fn request_with_failover<'a, F, G, H>(&'a self, func: F) -> Result<H, EngineError>
+    where
+        F: Fn(&'a Engine) -> G,
+        G: Future<Output = Result<H, ApiError>>,
+    {
+        let mut synced_engines = vec![];
+        for engine in self.engines() {
+            if engine.is_synced().await {
+                synced_engines.push(engine);
+            }
+        }
+        if synced_engines.is_empty() {
+            return self.inner.engine.request(func).await;
+        }
+
+        let num_synced_engines = synced_engines.len();
+        let mut result = Err(EngineError::Offline);
+        for (i, engine) in synced_engines.into_iter().enumerate() {
+            result = engine.request(&func).await;
+            match &result {
+                Ok(_) => break,
+                Err(e) if i + 1 < num_synced_engines => warn!(
+                    self.log(),
+                    "Failing over to backup execution engine";
+                    "failed_endpoint" => %engine.api.url,
+                    "error" => ?e,
+                ),
+                Err(_) => (),
+            }
+        }
+        result
+    }
+
+    /// Run `func` on every engine and return the result of the active engine.
+    ///
+    /// If the active engine returns an error it is marked offline, so that subsequent requests
+    /// fail over too, and only the result of the next engine in order of priority that responded
+    /// is returned.
+    async fn request_all_with_failover<'a, F, G, H>(&'a self, func: F) -> Result<H, EngineError>
+    where
+        F: Fn(&'a Engine) -> G,
+        G: Future<Output = Result<H, ApiError>>,
+    {
+        let active_engine = self.engine().await;
+        let results = join_all(self.engines().map(|engine| engine.request(&func))).await;
+
+        let mut active_result = Err(EngineError::Offline);
+        let mut failover = None;
+        for (engine, result) in self.engines().zip(results) {
+            if Arc::ptr_eq(engine, active_engine) {
+                active_result = result;
+            } else if failover.is_none() && result.is_ok() {
+                failover = Some((engine, result));
+            }
+        }
+
+        match (active_result, failover) {
+            (Err(e), Some((engine, result))) => {
+                active_engine.set_offline().await;
+                warn!(
+                    self.log(),
+                    "Failing over to backup execution engine";
+                    "failed_endpoint" => %active_engine.api.url,
+                    "endpoint" => %engine.api.url,
+                    "error" => ?e,
+                );
+                result
+            }
+            (active_result, _) => active_result,
+        }
+    }
+
+    /// Returns the clients for all connected builder relays.
+    pub fn builders(&self) -> Arc<Vec<Arc<BuilderHttpClient>>> {
+        self.inner.builders.load_full()
+    }
+
+    /// Returns `true` if at least one builder relay is connected.
+    pub fn has_builder(&self) -> bool {
+        !self.inner.builders.load().is_empty()
     }
 
-    /// Set the builder URL after initialization.
+    /// Set a single builder URL after initialization, replacing any existing relays.
     ///
     /// This is useful for breaking circular dependencies between mock ELs and mock builders in
     /// tests.
@@ -497,6 +620,61 @@ impl<E: EthSpec> ExecutionLayer<E> {
         builder_user_agent: Option<String>,
         builder_header_timeout: Option<Duration>,
     ) -> Result<(), Error> {
+        self.set_builder_urls(
+            vec![builder_url],
+            builder_user_agent,
+            builder_header_timeout,
+        )
+    }
+
+    /// Set the builder URLs, replacing any existing relays.
+    pub fn set_builder_urls(
+        &self,
+        builder_urls: Vec<SensitiveUrl>,
+        builder_user_agent: Option<String>,
+        builder_header_timeout: Option<Duration>,
+    ) -> Result<(), Error> {
+        let builders = builder_urls
+            .into_iter()
+            .map(|builder_url| {
+                self.new_builder_client(
+                    builder_url,
+                    builder_user_agent.clone(),
+                    builder_header_timeout,
+                )
+                .map(Arc::new)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.inner.builders.store(Arc::new(builders));
+        Ok(())
+    }
+
+    /// Add another builder relay after initialization, in addition to any existing relays.
+    pub fn add_builder_url(
+        &self,
+        builder_url: SensitiveUrl,
+        builder_user_agent: Option<String>,
+        builder_header_timeout: Option<Duration>,
+    ) -> Result<(), Error> {
+        let builder = Arc::new(self.new_builder_client(
+            builder_url,
+            builder_user_agent,
+            builder_header_timeout,
+        )?);
+        self.inner.builders.rcu(|builders| {
+            let mut builders = Vec::clone(builders);
+            builders.push(builder.clone());
+            builders
+        });
+        Ok(())
+    }
+
+    fn new_builder_client(
+        &self,
+        builder_url: SensitiveUrl,
+        builder_user_agent: Option<String>,
+        builder_header_timeout: Option<Duration>,
+    ) -> Result<BuilderHttpClient, Error> {
         let builder_client = BuilderHttpClient::new(
             builder_url.clone(),
             builder_user_agent,
@@ -509,8 +687,63 @@ impl<E: EthSpec> ExecutionLayer<E> {
             "builder_url" => ?builder_url,
             "local_user_agent" => builder_client.get_user_agent(),
         );
-        self.inner.builder.swap(Some(Arc::new(builder_client)));
-        Ok(())
+        Ok(builder_client)
+    }
+
+    /// Forward validator registrations to every connected builder relay concurrently.
+    ///
+    /// Succeeds if at least one relay accepts the registrations, otherwise the error from the
+    /// first relay is returned.
+    pub async fn post_builder_validators(
+        &self,
+        registrations: &[SignedValidatorRegistrationData],
+    ) -> Result<(), Error> {
+        let builders = self.builders();
+        if builders.is_empty() {
+            return Err(Error::NoPayloadBuilder);
+        }
+
+        let results = join_all(builders.iter().map(|builder| {
+            timed_relay_future(
+                builder,
+                metrics::POST_BUILDER_VALIDATORS,
+                builder.post_builder_validators(registrations),
+            )
+        }))
+        .await;
+
+        let mut num_accepted = 0;
+        let mut first_error = None;
+        for (builder, (result, _)) in builders.iter().zip(results) {
+            match result {
+                Ok(()) => {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_BUILDER_REGISTRATION_OUTCOME,
+                        &[builder.server().as_ref(), metrics::SUCCESS],
+                    );
+                    num_accepted += 1;
+                }
+                Err(e) => {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_BUILDER_REGISTRATION_OUTCOME,
+                        &[builder.server().as_ref(), metrics::FAILURE],
+                    );
+                    warn!(
+                        self.log(),
+                        "Relay error when registering validator(s)";
+                        "relay" => %builder.server(),
+                        "num_registrations" => registrations.len(),
+                        "error" => ?e,
+                    );
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) if num_accepted == 0 => Err(Error::Builder(e)),
+            _ => Ok(()),
+        }
     }
 
     /// Cache a full payload, keyed on the `tree_hash_root` of the payload
@@ -547,6 +780,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
     pub async fn get_current_difficulty(&self) -> Result<Uint256, ApiError> {
         let block = self
             .engine()
+            .await
             .api
             .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
             .await?
@@ -562,9 +796,33 @@ impl<E: EthSpec> ExecutionLayer<E> {
 
     /// Gives access to a channel containing if the last engine state is online or not.
     ///
+    /// When backup engines are configured the execution layer is considered online as long as any
+    /// one of the engines is online.
+    ///
     /// This can be called several times.
-    pub async fn get_responsiveness_watch(&self) -> WatchStream<EngineState> {
-        self.engine().watch_state().await
+    pub async fn get_responsiveness_watch(&self) -> BoxStream<'static, EngineState> {
+        if self.inner.backup_engines.is_empty() {
+            return self.inner.engine.watch_state().await.boxed();
+        }
+
+        let mut watches = vec![];
+        for engine in self.engines() {
+            watches.push(engine.watch_state().await);
+        }
+        let engines = self.engines().cloned().collect::<Vec<_>>();
+        select_all(watches)
+            .then(move |_| {
+                let engines = engines.clone();
+                async move {
+                    for engine in &engines {
+                        if !engine.is_offline().await {
+                            return EngineState::Online;
+                        }
+                    }
+                    EngineState::Offline
+                }
+            })
+            .boxed()
     }
 
     /// Note: this function returns a mutex guard, be careful to avoid deadlocks.
@@ -616,7 +874,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
 
     /// Performs a single execution of the watchdog routine.
     pub async fn watchdog_task(&self) {
-        self.engine().upcheck().await;
+        self.upcheck().await;
     }
 
     /// Spawns a routine which cleans the cached proposer data periodically.
@@ -658,9 +916,9 @@ impl<E: EthSpec> ExecutionLayer<E> {
         self.spawn(preparation_cleaner, "exec_preparation_cleanup");
     }
 
-    /// Returns `true` if the execution engine is synced and reachable.
+    /// Returns `true` if any execution engine is synced and reachable.
     pub async fn is_synced(&self) -> bool {
-        self.engine().is_synced().await
+        self.engine().await.is_synced().await
     }
 
     /// Execution nodes return a "SYNCED" response when they do not have any peers.
@@ -674,6 +932,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
         if synced {
             if let Ok(Some(block)) = self
                 .engine()
+                .await
                 .api
                 .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
                 .await
@@ -686,13 +945,22 @@ impl<E: EthSpec> ExecutionLayer<E> {
         synced
     }
 
-    /// Return `true` if the execution layer is offline or returning errors on `newPayload`.
+    /// Return `true` if all execution engines are offline or the last `newPayload` returned an
+    /// error.
     ///
     /// This function should never be used to prevent any operation in the beacon node, but can
     /// be used to give an indication on the HTTP API that the node's execution layer is struggling,
     /// which can in turn be used by the VC.
     pub async fn is_offline_or_erroring(&self) -> bool {
-        self.engine().is_offline().await || *self.inner.last_new_payload_errored.read().await
+        if *self.inner.last_new_payload_errored.read().await {
+            return true;
+        }
+        for engine in self.engines() {
+            if !engine.is_offline().await {
+                return false;
+            }
+        }
+        true
     }
 
     /// Updates the proposer preparation data provided by validators
@@ -904,16 +1172,19 @@ impl<E: EthSpec> ExecutionLayer<E> {
     }
 
     /// Fetches local and builder paylaods concurrently, Logs and returns results.
+    ///
+    /// Every relay is queried in parallel and is subject to its own timeout, so a slow or failing
+    /// relay does not affect the responses from the others.
     async fn fetch_builder_and_local_payloads(
         &self,
-        builder: &BuilderHttpClient,
+        builders: &[Arc<BuilderHttpClient>],
         parent_hash: ExecutionBlockHash,
         builder_params: &BuilderParams,
         payload_attributes: &PayloadAttributes,
         forkchoice_update_params: ForkchoiceUpdateParameters,
         current_fork: ForkName,
     ) -> (
-        Result<Option<ForkVersionedResponse<SignedBuilderBid<E>>>, builder_client::Error>,
+        Vec<(Arc<BuilderHttpClient>, RelayBidResult<E>)>,
         Result<GetPayloadResponse<E>, Error>,
     ) {
         let slot = builder_params.slot;
@@ -921,19 +1192,43 @@ impl<E: EthSpec> ExecutionLayer<E> {
 
         info!(
             self.log(),
-            "Requesting blinded header from connected builder";
+            "Requesting blinded header from connected builders";
             "slot" => ?slot,
             "pubkey" => ?pubkey,
             "parent_hash" => ?parent_hash,
+            "relay_count" => builders.len(),
         );
 
-        // Wait for the builder *and* local EL to produce a payload (or return an error).
-        let ((relay_result, relay_duration), (local_result, local_duration)) = tokio::join!(
-            timed_future(metrics::GET_BLINDED_PAYLOAD_BUILDER, async {
-                builder
-                    .get_builder_header::<E>(slot, parent_hash, pubkey)
-                    .await
-            }),
+        let relay_futures = builders.iter().map(|builder| async move {
+            let (result, duration) = timed_relay_future(
+                builder,
+                metrics::GET_BLINDED_PAYLOAD_BUILDER,
+                builder.get_builder_header::<E>(slot, parent_hash, pubkey),
+            )
+            .await;
+
+            debug!(
+                self.log(),
+                "Received response from builder";
+                "relay" => %builder.server(),
+                "relay_fee_recipient" => match &result {
+                    Ok(Some(r)) => format!("{:?}", r.data.message.header().fee_recipient()),
+                    Ok(None) => "empty response".to_string(),
+                    Err(_) => "request failed".to_string(),
+                },
+                "relay_response_ms" => duration.as_millis(),
+                "parent_hash" => ?parent_hash,
+            );
+
+            (builder.clone(), result)
+        });
+
+        // Wait for the builders *and* local EL to produce a payload (or return an error).
+        let ((relay_results, relay_duration), (local_result, local_duration)) = tokio::join!(
+            timed_future(
+                metrics::GET_BLINDED_PAYLOAD_BUILDER,
+                join_all(relay_futures)
+            ),
             timed_future(metrics::GET_BLINDED_PAYLOAD_LOCAL, async {
                 self.get_full_payload_caching(
                     parent_hash,
@@ -952,11 +1247,10 @@ impl<E: EthSpec> ExecutionLayer<E> {
         info!(
             self.log(),
             "Requested blinded execution payload";
-            "relay_fee_recipient" => match &relay_result {
-                Ok(Some(r)) => format!("{:?}", r.data.message.header().fee_recipient()),
-                Ok(None) => "empty response".to_string(),
-                Err(_) => "request failed".to_string(),
-            },
+            "relay_bids" => relay_results
+                .iter()
+                .filter(|(_, result)| matches!(result, Ok(Some(_))))
+                .count(),
             "relay_response_ms" => relay_duration.as_millis(),
             "local_fee_recipient" => match &local_result {
                 Ok(get_payload_response) => format!("{:?}", get_payload_response.fee_recipient()),
@@ -966,7 +1260,134 @@ impl<E: EthSpec> ExecutionLayer<E> {
             "parent_hash" => ?parent_hash,
         );
 
-        (relay_result, local_result)
+        (relay_results, local_result)
+    }
+
+    /// Verify the bid returned by each relay and return the most valuable valid bid, along with
+    /// the relay that provided it.
+    ///
+    /// If several relays return bids of equal value, the relay configured first is preferred.
+    #[allow(clippy::type_complexity)]
+    fn select_best_relay_bid(
+        &self,
+        relay_results: Vec<(Arc<BuilderHttpClient>, RelayBidResult<E>)>,
+        parent_hash: ExecutionBlockHash,
+        payload_attributes: &PayloadAttributes,
+        block_number: Option<u64>,
+        current_fork: ForkName,
+        spec: &ChainSpec,
+    ) -> Option<(
+        Arc<BuilderHttpClient>,
+        ForkVersionedResponse<SignedBuilderBid<E>>,
+    )> {
+        let mut best_bid: Option<(
+            Arc<BuilderHttpClient>,
+            ForkVersionedResponse<SignedBuilderBid<E>>,
+        )> = None;
+
+        for (builder, relay_result) in relay_results {
+            let relay = builder.server().as_ref();
+            let relay_bid = match relay_result {
+                Ok(Some(relay_bid)) => relay_bid,
+                Ok(None) => {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_BUILDER_GET_HEADER_OUTCOME,
+                        &[relay, metrics::NO_BID],
+                    );
+                    info!(
+                        self.log(),
+                        "Builder did not return a payload";
+                        "relay" => relay,
+                        "parent_hash" => ?parent_hash,
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_BUILDER_GET_HEADER_OUTCOME,
+                        &[relay, metrics::FAILURE],
+                    );
+                    warn!(
+                        self.log(),
+                        "Builder error when requesting payload";
+                        "relay" => relay,
+                        "relay_error" => ?e,
+                        "parent_hash" => ?parent_hash,
+                    );
+                    continue;
+                }
+            };
+
+            if let Err(reason) = verify_builder_bid(
+                &relay_bid,
+                parent_hash,
+                payload_attributes,
+                block_number,
+                current_fork,
+                spec,
+            ) {
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
+                    &[reason.as_ref().as_ref()],
+                );
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_BUILDER_GET_HEADER_OUTCOME,
+                    &[relay, metrics::INVALID],
+                );
+                warn!(
+                    self.log(),
+                    "Builder returned invalid payload";
+                    "relay" => relay,
+                    "reason" => %reason,
+                    "relay_block_hash" => ?relay_bid.data.message.header().block_hash(),
+                    "parent_hash" => ?parent_hash,
+                );
+                continue;
+            }
+
+            metrics::inc_counter_vec(
+                &metrics::EXECUTION_LAYER_BUILDER_GET_HEADER_OUTCOME,
+                &[relay, metrics::SUCCESS],
+            );
+            let relay_value = *relay_bid.data.message.value();
+            // Avoid logging values that we can't represent with our Prometheus library.
+            let relay_value_gwei = relay_value / 1_000_000_000;
+            if relay_value_gwei <= Uint256::from(i64::MAX) {
+                metrics::set_gauge_vec(
+                    &metrics::EXECUTION_LAYER_BUILDER_BID_VALUES,
+                    &[relay],
+                    relay_value_gwei.low_u64() as i64,
+                );
+            }
+
+            let is_best = best_bid
+                .as_ref()
+                .map_or(true, |(_, best)| relay_value > *best.data.message.value());
+            if is_best {
+                best_bid = Some((builder, relay_bid));
+            }
+        }
+
+        best_bid
+    }
+
+    /// Remember which relay provided the builder payload that we're about to propose, so that the
+    /// signed blinded block is only sent back to that relay.
+    async fn record_winning_relay(
+        &self,
+        builder: Arc<BuilderHttpClient>,
+        relay_bid: &ForkVersionedResponse<SignedBuilderBid<E>>,
+    ) {
+        metrics::inc_counter_vec(
+            &metrics::EXECUTION_LAYER_BUILDER_WINNING_BIDS,
+            &[builder.server().as_ref()],
+        );
+        let block_hash = relay_bid.data.message.header().block_hash();
+        self.inner
+            .winning_relays
+            .lock()
+            .await
+            .put(block_hash, builder);
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -980,7 +1401,8 @@ impl<E: EthSpec> ExecutionLayer<E> {
         builder_boost_factor: Option<u64>,
         spec: &ChainSpec,
     ) -> Result<ProvenancedPayload<BlockProposalContentsType<E>>, Error> {
-        let Some(builder) = self.builder() else {
+        let builders = self.builders();
+        if builders.is_empty() {
             // no builder.. return local payload
             return self
                 .get_full_payload_caching(
@@ -992,7 +1414,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
                 .await
                 .and_then(GetPayloadResponseType::try_into)
                 .map(ProvenancedPayload::Local);
-        };
+        }
 
         // check chain health
         if builder_params.chain_health != ChainHealth::Healthy {
@@ -1030,9 +1452,9 @@ impl<E: EthSpec> ExecutionLayer<E> {
                 .map(ProvenancedPayload::Local);
         }
 
-        let (relay_result, local_result) = self
+        let (relay_results, local_result) = self
             .fetch_builder_and_local_payloads(
-                builder.as_ref(),
+                &builders,
                 parent_hash,
                 &builder_params,
                 payload_attributes,
@@ -1041,24 +1463,20 @@ impl<E: EthSpec> ExecutionLayer<E> {
             )
             .await;
 
-        match (relay_result, local_result) {
-            (Err(e), Ok(local)) => {
-                warn!(
-                    self.log(),
-                    "Builder error when requesting payload";
-                    "info" => "falling back to local execution client",
-                    "relay_error" => ?e,
-                    "local_block_hash" => ?local.block_hash(),
-                    "parent_hash" => ?parent_hash,
-                );
-                Ok(ProvenancedPayload::Local(BlockProposalContentsType::Full(
-                    local.try_into()?,
-                )))
-            }
-            (Ok(None), Ok(local)) => {
+        let best_relay_bid = self.select_best_relay_bid(
+            relay_results,
+            parent_hash,
+            payload_attributes,
+            local_result.as_ref().ok().map(|local| local.block_number()),
+            current_fork,
+            spec,
+        );
+
+        match (best_relay_bid, local_result) {
+            (None, Ok(local)) => {
                 info!(
                     self.log(),
-                    "Builder did not return a payload";
+                    "No valid payload from any builder";
                     "info" => "falling back to local execution client",
                     "local_block_hash" => ?local.block_hash(),
                     "parent_hash" => ?parent_hash,
@@ -1067,23 +1485,11 @@ impl<E: EthSpec> ExecutionLayer<E> {
                     local.try_into()?,
                 )))
             }
-            (Err(relay_error), Err(local_error)) => {
+            (None, Err(local_error)) => {
                 crit!(
                     self.log(),
                     "Unable to produce execution payload";
-                    "info" => "the local EL and builder both failed - unable to propose block",
-                    "relay_error" => ?relay_error,
-                    "local_error" => ?local_error,
-                    "parent_hash" => ?parent_hash,
-                );
-
-                Err(Error::CannotProduceHeader)
-            }
-            (Ok(None), Err(local_error)) => {
-                crit!(
-                    self.log(),
-                    "Unable to produce execution payload";
-                    "info" => "the local EL failed and the builder returned nothing - \
+                    "info" => "the local EL failed and no builder returned a valid payload - \
                         the block proposal will be missed",
                     "local_error" => ?local_error,
                     "parent_hash" => ?parent_hash,
@@ -1091,44 +1497,18 @@ impl<E: EthSpec> ExecutionLayer<E> {
 
                 Err(Error::CannotProduceHeader)
             }
-            (Ok(Some(relay)), Ok(local)) => {
+            (Some((builder, relay)), Ok(local)) => {
                 let header = &relay.data.message.header();
 
                 info!(
                     self.log(),
                     "Received local and builder payloads";
+                    "relay" => %builder.server(),
                     "relay_block_hash" => ?header.block_hash(),
                     "local_block_hash" => ?local.block_hash(),
                     "parent_hash" => ?parent_hash,
                 );
 
-                // check relay payload validity
-                if let Err(reason) = verify_builder_bid(
-                    &relay,
-                    parent_hash,
-                    payload_attributes,
-                    Some(local.block_number()),
-                    current_fork,
-                    spec,
-                ) {
-                    // relay payload invalid -> return local
-                    metrics::inc_counter_vec(
-                        &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
-                        &[reason.as_ref().as_ref()],
-                    );
-                    warn!(
-                        self.log(),
-                        "Builder returned invalid payload";
-                        "info" => "using local payload",
-                        "reason" => %reason,
-                        "relay_block_hash" => ?header.block_hash(),
-                        "parent_hash" => ?parent_hash,
-                    );
-                    return Ok(ProvenancedPayload::Local(BlockProposalContentsType::Full(
-                        local.try_into()?,
-                    )));
-                }
-
                 let relay_value = *relay.data.message.value();
 
                 let boosted_relay_value = match builder_boost_factor {
@@ -1169,50 +1549,30 @@ impl<E: EthSpec> ExecutionLayer<E> {
                 info!(
                     self.log(),
                     "Relay block is more profitable than local block";
+                    "relay" => %builder.server(),
                     "local_block_value" => %local_value,
                     "relay_value" => %relay_value,
                     "boosted_relay_value" => %boosted_relay_value,
                     "builder_boost_factor" => ?builder_boost_factor
                 );
 
+                self.record_winning_relay(builder, &relay).await;
                 Ok(ProvenancedPayload::try_from(relay.data.message)?)
             }
-            (Ok(Some(relay)), Err(local_error)) => {
+            (Some((builder, relay)), Err(local_error)) => {
                 let header = &relay.data.message.header();
 
                 info!(
                     self.log(),
                     "Received builder payload with local error";
+                    "relay" => %builder.server(),
                     "relay_block_hash" => ?header.block_hash(),
                     "local_error" => ?local_error,
                     "parent_hash" => ?parent_hash,
                 );
 
-                match verify_builder_bid(
-                    &relay,
-                    parent_hash,
-                    payload_attributes,
-                    None,
-                    current_fork,
-                    spec,
-                ) {
-                    Ok(()) => Ok(ProvenancedPayload::try_from(relay.data.message)?),
-                    Err(reason) => {
-                        metrics::inc_counter_vec(
-                            &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
-                            &[reason.as_ref().as_ref()],
-                        );
-                        crit!(
-                            self.log(),
-                            "Builder returned invalid payload";
-                            "info" => "no local payload either - unable to propose block",
-                            "reason" => %reason,
-                            "relay_block_hash" => ?header.block_hash(),
-                            "parent_hash" => ?parent_hash,
-                        );
-                        Err(Error::CannotProduceHeader)
-                    }
-                }
+                self.record_winning_relay(builder, &relay).await;
+                Ok(ProvenancedPayload::try_from(relay.data.message)?)
             }
         }
     }
@@ -1246,103 +1606,117 @@ impl<E: EthSpec> ExecutionLayer<E> {
             PayloadContentsRefTuple<E>,
         ) -> Option<FullPayloadContents<E>>,
     ) -> Result<GetPayloadResponseType<E>, Error> {
-        self.engine()
-            .request(move |engine| async move {
-                let payload_id = if let Some(id) = engine
-                    .get_payload_id(&parent_hash, payload_attributes)
-                    .await
-                {
-                    // The payload id has been cached for this engine.
-                    metrics::inc_counter_vec(
-                        &metrics::EXECUTION_LAYER_PRE_PREPARED_PAYLOAD_ID,
-                        &[metrics::HIT],
-                    );
-                    id
-                } else {
-                    // The payload id has *not* been cached. Trigger an artificial
-                    // fork choice update to retrieve a payload ID.
-                    metrics::inc_counter_vec(
-                        &metrics::EXECUTION_LAYER_PRE_PREPARED_PAYLOAD_ID,
-                        &[metrics::MISS],
-                    );
-                    let fork_choice_state = ForkchoiceState {
-                        head_block_hash: parent_hash,
-                        safe_block_hash: forkchoice_update_params
-                            .justified_hash
-                            .unwrap_or_else(ExecutionBlockHash::zero),
-                        finalized_block_hash: forkchoice_update_params
-                            .finalized_hash
-                            .unwrap_or_else(ExecutionBlockHash::zero),
-                    };
-
-                    let response = engine
-                        .notify_forkchoice_updated(
-                            fork_choice_state,
-                            Some(payload_attributes.clone()),
-                            self.log(),
-                        )
-                        .await?;
-
-                    match response.payload_id {
-                        Some(payload_id) => payload_id,
-                        None => {
-                            error!(
-                                self.log(),
-                                "Exec engine unable to produce payload";
-                                "msg" => "No payload ID, the engine is likely syncing. \
-                                          This has the potential to cause a missed block proposal.",
-                                "status" => ?response.payload_status
-                            );
-                            return Err(ApiError::PayloadIdUnavailable);
-                        }
-                    }
+        // Payload building fails over to a synced backup engine if the active engine errors.
+        self.request_with_failover(move |engine| async move {
+            let payload_id = if let Some(id) = engine
+                .get_payload_id(&parent_hash, payload_attributes)
+                .await
+            {
+                // The payload id has been cached for this engine.
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_PRE_PREPARED_PAYLOAD_ID,
+                    &[metrics::HIT],
+                );
+                id
+            } else {
+                // The payload id has *not* been cached. Trigger an artificial
+                // fork choice update to retrieve a payload ID.
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_PRE_PREPARED_PAYLOAD_ID,
+                    &[metrics::MISS],
+                );
+                let fork_choice_state = ForkchoiceState {
+                    head_block_hash: parent_hash,
+                    safe_block_hash: forkchoice_update_params
+                        .justified_hash
+                        .unwrap_or_else(ExecutionBlockHash::zero),
+                    finalized_block_hash: forkchoice_update_params
+                        .finalized_hash
+                        .unwrap_or_else(ExecutionBlockHash::zero),
                 };
 
-                let payload_response = async {
-                    debug!(
+                let response = engine
+                    .notify_forkchoice_updated(
+                        fork_choice_state,
+                        Some(payload_attributes.clone()),
                         self.log(),
-                        "Issuing engine_getPayload";
-                        "suggested_fee_recipient" => ?payload_attributes.suggested_fee_recipient(),
-                        "prev_randao" => ?payload_attributes.prev_randao(),
-                        "timestamp" => payload_attributes.timestamp(),
-                        "parent_hash" => ?parent_hash,
-                    );
-                    let _timer = metrics::start_timer_vec(
-                        &metrics::EXECUTION_LAYER_REQUEST_TIMES,
-                        &[metrics::GET_PAYLOAD],
-                    );
-                    engine.api.get_payload::<E>(current_fork, payload_id).await
-                }.await?;
+                    )
+                    .await?;
 
-                if payload_response.execution_payload_ref().fee_recipient() != payload_attributes.suggested_fee_recipient() {
-                    error!(
-                        self.log(),
-                        "Inconsistent fee recipient";
-                        "msg" => "The fee recipient returned from the Execution Engine differs \
-                        from the suggested_fee_recipient set on the beacon node. This could \
-                        indicate that fees are being diverted to another address. Please \
-                        ensure that the value of suggested_fee_recipient is set correctly and \
-                        that the Execution Engine is trusted.",
-                        "fee_recipient" => ?payload_response.execution_payload_ref().fee_recipient(),
-                        "suggested_fee_recipient" => ?payload_attributes.suggested_fee_recipient(),
-                    );
+                match response.payload_id {
+                    Some(payload_id) => payload_id,
+                    None => {
+                        error!(
+                            self.log(),
+                            "Exec engine unable to produce payload";
+                            "msg" => "No payload ID, the engine is likely syncing. \
+                                      This has the potential to cause a missed block proposal.",
+                            "status" => ?response.payload_status
+                        );
+                        return Err(ApiError::PayloadIdUnavailable);
+                    }
                 }
-                if cache_fn(self, (payload_response.execution_payload_ref(), payload_response.blobs_bundle().ok())).is_some() {
-                    warn!(
-                        self.log(),
-                        "Duplicate payload cached, this might indicate redundant proposal \
+            };
+
+            let payload_response = async {
+                debug!(
+                    self.log(),
+                    "Issuing engine_getPayload";
+                    "suggested_fee_recipient" => ?payload_attributes.suggested_fee_recipient(),
+                    "prev_randao" => ?payload_attributes.prev_randao(),
+                    "timestamp" => payload_attributes.timestamp(),
+                    "parent_hash" => ?parent_hash,
+                );
+                let _timer = metrics::start_timer_vec(
+                    &metrics::EXECUTION_LAYER_REQUEST_TIMES,
+                    &[metrics::GET_PAYLOAD],
+                );
+                engine.api.get_payload::<E>(current_fork, payload_id).await
+            }
+            .await?;
+
+            if payload_response.execution_payload_ref().fee_recipient()
+                != payload_attributes.suggested_fee_recipient()
+            {
+                error!(
+                    self.log(),
+                    "Inconsistent fee recipient";
+                    "msg" => "The fee recipient returned from the Execution Engine differs \
+                    from the suggested_fee_recipient set on the beacon node. This could \
+                    indicate that fees are being diverted to another address. Please \
+                    ensure that the value of suggested_fee_recipient is set correctly and \
+                    that the Execution Engine is trusted.",
+                    "fee_recipient" => ?payload_response.execution_payload_ref().fee_recipient(),
+                    "suggested_fee_recipient" => ?payload_attributes.suggested_fee_recipient(),
+                );
+            }
+            if cache_fn(
+                self,
+                (
+                    payload_response.execution_payload_ref(),
+                    payload_response.blobs_bundle().ok(),
+                ),
+            )
+            .is_some()
+            {
+                warn!(
+                    self.log(),
+                    "Duplicate payload cached, this might indicate redundant proposal \
                              attempts."
-                    );
-                }
+                );
+            }
 
-                Ok(GetPayloadResponseType::Full(payload_response))
-            })
-            .await
-            .map_err(Box::new)
-            .map_err(Error::EngineError)
+            Ok(GetPayloadResponseType::Full(payload_response))
+        })
+        .await
+        .map_err(Box::new)
+        .map_err(Error::EngineError)
     }
 
     /// Maps to the `engine_newPayload` JSON-RPC call.
+    ///
+    /// The payload is sent to every engine so that the backups are ready to take over. Only the
+    /// status of the active engine is returned.
     pub async fn notify_new_payload(
         &self,
         new_payload_request: NewPayloadRequest<'_, E>,
@@ -1358,8 +1732,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
         let parent_hash = new_payload_request.parent_hash();
 
         let result = self
-            .engine()
-            .request(|engine| engine.api.new_payload(new_payload_request))
+            .request_all_with_failover(|engine| engine.api.new_payload(new_payload_request.clone()))
             .await;
 
         if let Ok(status) = &result {
@@ -1385,9 +1758,9 @@ impl<E: EthSpec> ExecutionLayer<E> {
             .map_err(Error::EngineError)
     }
 
-    /// Update engine sync status.
+    /// Update the sync status of all engines.
     pub async fn upcheck(&self) {
-        self.engine().upcheck().await;
+        join_all(self.engines().map(|engine| engine.upcheck())).await;
     }
 
     /// Register that the given `validator_index` is going to produce a block at `slot`.
@@ -1501,16 +1874,20 @@ impl<E: EthSpec> ExecutionLayer<E> {
             finalized_block_hash,
         };
 
-        self.engine()
-            .set_latest_forkchoice_state(forkchoice_state)
-            .await;
+        for engine in self.engines() {
+            engine.set_latest_forkchoice_state(forkchoice_state).await;
+        }
 
+        // Mirror the forkchoice update to every engine so that the backups are ready to take over
+        // payload building.
         let result = self
-            .engine()
-            .request(|engine| async move {
-                engine
-                    .notify_forkchoice_updated(forkchoice_state, payload_attributes, self.log())
-                    .await
+            .request_all_with_failover(|engine| {
+                let payload_attributes = payload_attributes.clone();
+                async move {
+                    engine
+                        .notify_forkchoice_updated(forkchoice_state, payload_attributes, self.log())
+                        .await
+                }
             })
             .await;
 
@@ -1544,6 +1921,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
         age_limit: Option<Duration>,
     ) -> Result<EngineCapabilities, Error> {
         self.engine()
+            .await
             .request(|engine| engine.get_engine_capabilities(age_limit))
             .await
             .map_err(Into::into)
@@ -1563,6 +1941,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
         age_limit: Option<Duration>,
     ) -> Result<Vec<ClientVersionV1>, Error> {
         self.engine()
+            .await
             .request(|engine| engine.get_engine_version(age_limit))
             .await
             .map_err(Into::into)
@@ -1587,6 +1966,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
 
         let hash_opt = self
             .engine()
+            .await
             .request(|engine| async move {
                 let terminal_block_hash = spec.terminal_block_hash;
                 if terminal_block_hash != ExecutionBlockHash::zero() {
@@ -1714,6 +2094,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
         );
 
         self.engine()
+            .await
             .request(|engine| async move {
                 if let Some(pow_block) = self.get_pow_block(engine, block_hash).await? {
                     if let Some(pow_parent) =
@@ -1773,6 +2154,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
         hashes: Vec<ExecutionBlockHash>,
     ) -> Result<Vec<Option<ExecutionPayloadBodyV1<E>>>, Error> {
         self.engine()
+            .await
             .request(|engine: &Engine| async move {
                 engine.api.get_payload_bodies_by_hash_v1(hashes).await
             })
@@ -1781,6 +2163,27 @@ impl<E: EthSpec> ExecutionLayer<E> {
             .map_err(Error::EngineError)
     }
 
+    /// Requests the blobs matching `versioned_hashes` from the execution engine's blob pool.
+    ///
+    /// Returns `None` for every blob if the engine does not support `engine_getBlobsV1`.
+    pub async fn get_blobs(
+        &self,
+        versioned_hashes: Vec<Hash256>,
+    ) -> Result<Vec<Option<BlobAndProofV1<E>>>, Error> {
+        let capabilities = self.get_engine_capabilities(None).await?;
+
+        if !capabilities.get_blobs_v1 {
+            return Ok(vec![None; versioned_hashes.len()]);
+        }
+
+        self.engine()
+            .await
+            .request(|engine: &Engine| async move { engine.api.get_blobs(versioned_hashes).await })
+            .await
+            .map_err(Box::new)
+            .map_err(Error::EngineError)
+    }
+
     pub async fn get_payload_bodies_by_range(
         &self,
         start: u64,
@@ -1788,6 +2191,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
     ) -> Result<Vec<Option<ExecutionPayloadBodyV1<E>>>, Error> {
         let _timer = metrics::start_timer(&metrics::EXECUTION_LAYER_GET_PAYLOAD_BODIES_BY_RANGE);
         self.engine()
+            .await
             .request(|engine: &Engine| async move {
                 engine
                     .api
@@ -1851,6 +2255,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
         query: BlockByNumberQuery<'_>,
     ) -> Result<Option<ExecutionBlock>, Error> {
         self.engine()
+            .await
             .request(|engine| async move { engine.api.get_block_by_number(query).await })
             .await
             .map_err(Box::new)
@@ -1863,6 +2268,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
         fork: ForkName,
     ) -> Result<Option<ExecutionPayload<E>>, Error> {
         self.engine()
+            .await
             .request(|engine| async move {
                 self.get_payload_by_hash_from_engine(engine, hash, fork)
                     .await
@@ -2037,6 +2443,12 @@ impl<E: EthSpec> ExecutionLayer<E> {
         Ok(Some(payload))
     }
 
+    /// Send a signed blinded block to the relay that provided its payload, and return the
+    /// revealed payload.
+    ///
+    /// If the winning relay is unknown (e.g. the block was produced by a different node or before
+    /// a restart), the block is sent to every connected relay and the first payload returned is
+    /// used.
     pub async fn propose_blinded_beacon_block(
         &self,
         block_root: Hash256,
@@ -2048,58 +2460,102 @@ impl<E: EthSpec> ExecutionLayer<E> {
             "root" => ?block_root,
         );
 
-        if let Some(builder) = self.builder() {
-            let (payload_result, duration) =
-                timed_future(metrics::POST_BLINDED_PAYLOAD_BUILDER, async {
-                    builder
-                        .post_builder_blinded_blocks(block)
-                        .await
-                        .map_err(Error::Builder)
-                        .map(|d| d.data)
-                })
-                .await;
+        let builders = self.builders();
+        if builders.is_empty() {
+            return Err(Error::NoPayloadBuilder);
+        }
 
-            match &payload_result {
-                Ok(unblinded_response) => {
-                    metrics::inc_counter_vec(
-                        &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
-                        &[metrics::SUCCESS],
-                    );
-                    let payload = unblinded_response.payload_ref();
-                    info!(
-                        self.log(),
-                        "Builder successfully revealed payload";
-                        "relay_response_ms" => duration.as_millis(),
-                        "block_root" => ?block_root,
-                        "fee_recipient" => ?payload.fee_recipient(),
-                        "block_hash" => ?payload.block_hash(),
-                        "parent_hash" => ?payload.parent_hash()
-                    )
-                }
-                Err(e) => {
-                    metrics::inc_counter_vec(
-                        &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
-                        &[metrics::FAILURE],
-                    );
+        let block_hash = block
+            .message()
+            .execution_payload()
+            .map(|payload| payload.block_hash())
+            .ok();
+        let winning_relay = match block_hash {
+            Some(block_hash) => self
+                .inner
+                .winning_relays
+                .lock()
+                .await
+                .get(&block_hash)
+                .cloned(),
+            None => None,
+        };
+        let relays = match winning_relay {
+            Some(builder) => vec![builder],
+            None => {
+                if builders.len() > 1 {
                     warn!(
                         self.log(),
-                        "Builder failed to reveal payload";
-                        "info" => "this is common behaviour for some builders and may not indicate an issue",
-                        "error" => ?e,
-                        "relay_response_ms" => duration.as_millis(),
+                        "Unknown relay for blinded block";
+                        "info" => "sending the block to all connected relays",
                         "block_root" => ?block_root,
-                        "parent_hash" => ?block
-                            .message()
-                            .execution_payload()
-                            .map(|payload| format!("{}", payload.parent_hash()))
-                            .unwrap_or_else(|_| "unknown".to_string())
-                    )
+                        "block_hash" => ?block_hash,
+                    );
                 }
+                builders.to_vec()
             }
+        };
 
-            payload_result
-        } else {
-            Err(Error::NoPayloadBuilder)
+        let (payload_result, duration) =
+            timed_future(metrics::POST_BLINDED_PAYLOAD_BUILDER, async {
+                select_ok(relays.iter().map(|builder| {
+                    Box::pin(async move {
+                        let (result, _) = timed_relay_future(
+                            builder,
+                            metrics::POST_BLINDED_PAYLOAD_BUILDER,
+                            builder.post_builder_blinded_blocks(block),
+                        )
+                        .await;
+                        result
+                            .map(|d| (builder.clone(), d.data))
+                            .map_err(|e| (builder.clone(), e))
+                    })
+                }))
+                .await
+                .map(|(response, _)| response)
+            })
+            .await;
+
+        match payload_result {
+            Ok((builder, unblinded_response)) => {
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
+                    &[metrics::SUCCESS],
+                );
+                let payload = unblinded_response.payload_ref();
+                info!(
+                    self.log(),
+                    "Builder successfully revealed payload";
+                    "relay" => %builder.server(),
+                    "relay_response_ms" => duration.as_millis(),
+                    "block_root" => ?block_root,
+                    "fee_recipient" => ?payload.fee_recipient(),
+                    "block_hash" => ?payload.block_hash(),
+                    "parent_hash" => ?payload.parent_hash()
+                );
+                Ok(unblinded_response)
+            }
+            Err((builder, e)) => {
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
+                    &[metrics::FAILURE],
+                );
+                warn!(
+                    self.log(),
+                    "Builder failed to reveal payload";
+                    "info" => "this is common behaviour for some builders and may not indicate an issue",
+                    "relay" => %builder.server(),
+                    "error" => ?e,
+                    "relay_response_ms" => duration.as_millis(),
+                    "block_root" => ?block_root,
+                    "parent_hash" => ?block
+                        .message()
+                        .execution_payload()
+                        .map(|payload| format!("{}", payload.parent_hash()))
+                        .unwrap_or_else(|_| "unknown".to_string())
+                );
+                Err(Error::Builder(e))
+            }
         }
     }
 }
@@ -2255,6 +2711,56 @@ async fn timed_future<F: Future<Output = T>, T>(metric: &str, future: F) -> (T,
     (result, duration)
 }
 
+/// A helper function to record the time it takes an individual builder relay to respond.
+async fn timed_relay_future<F: Future<Output = T>, T>(
+    builder: &BuilderHttpClient,
+    metric: &str,
+    future: F,
+) -> (T, Duration) {
+    let start = Instant::now();
+    let result = future.await;
+    let duration = start.elapsed();
+    metrics::observe_timer_vec(
+        &metrics::EXECUTION_LAYER_BUILDER_REQUEST_TIMES,
+        &[builder.server().as_ref(), metric],
+        duration,
+    );
+    (result, duration)
+}
+
+/// Reads the JWT secret from `secret_file`, or generates a random secret and writes it to
+/// `secret_file` if the file does not exist.
+fn load_jwt_key(secret_file: &Path, log: &Logger) -> Result<JwtKey, Error> {
+    if secret_file.exists() {
+        // Read secret from file if it already exists
+        std::fs::read_to_string(secret_file)
+            .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))
+            .and_then(|ref s| {
+                let secret = JwtKey::from_slice(
+                    &hex::decode(strip_prefix(s.trim_end()))
+                        .map_err(|e| format!("Invalid hex string: {:?}", e))?,
+                )?;
+                Ok(secret)
+            })
+            .map_err(Error::InvalidJWTSecret)
+    } else {
+        // Create a new file and write a randomly generated secret to it if file does not exist
+        warn!(log, "No JWT found on disk. Generating"; "path" => %secret_file.display());
+        std::fs::File::options()
+            .write(true)
+            .create_new(true)
+            .open(secret_file)
+            .map_err(|e| format!("Failed to open JWT secret file. Error: {:?}", e))
+            .and_then(|mut f| {
+                let secret = auth::JwtKey::random();
+                f.write_all(secret.hex_string().as_bytes())
+                    .map_err(|e| format!("Failed to write to JWT secret file: {:?}", e))?;
+                Ok(secret)
+            })
+            .map_err(Error::InvalidJWTSecret)
+    }
+}
+
 #[cfg(test)]
 /// Returns the duration since the unix epoch.
 fn timestamp_now() -> u64 {
@@ -2293,6 +2799,135 @@ mod test {
             .await;
     }
 
+    #[tokio::test]
+    async fn forkchoice_updated_is_mirrored_to_backup_engines() {
+        let runtime = TestRuntime::default();
+        let mock = MockExecutionLayer::default_params_with_backup_engines(
+            runtime.task_executor.clone(),
+            2,
+        )
+        .move_to_terminal_block();
+        mock.el.upcheck().await;
+
+        let head_block_hash = mock
+            .server
+            .execution_block_generator()
+            .latest_block()
+            .unwrap()
+            .block_hash();
+        mock.el
+            .notify_forkchoice_updated(
+                head_block_hash,
+                ExecutionBlockHash::zero(),
+                ExecutionBlockHash::zero(),
+                Slot::new(0),
+                Hash256::repeat_byte(42),
+            )
+            .await
+            .unwrap();
+
+        for server in mock.servers() {
+            let request = server.take_previous_request().unwrap();
+            assert_eq!(request["method"], http::ENGINE_FORKCHOICE_UPDATED_V3);
+        }
+    }
+
+    #[tokio::test]
+    async fn forkchoice_updated_fails_over_to_backup_engine_status() {
+        let runtime = TestRuntime::default();
+        let mock = MockExecutionLayer::default_params_with_backup_engines(
+            runtime.task_executor.clone(),
+            1,
+        )
+        .move_to_terminal_block();
+        mock.el.upcheck().await;
+
+        let head_block_hash = mock
+            .server
+            .execution_block_generator()
+            .latest_block()
+            .unwrap()
+            .block_hash();
+        mock.server
+            .set_fcu_payload_error(head_block_hash, "primary failed".to_string());
+        mock.backup_servers[0].set_fcu_payload_status(
+            head_block_hash,
+            PayloadStatusV1 {
+                status: PayloadStatusV1Status::Syncing,
+                latest_valid_hash: None,
+                validation_error: None,
+            },
+        );
+
+        let status = mock
+            .el
+            .notify_forkchoice_updated(
+                head_block_hash,
+                ExecutionBlockHash::zero(),
+                ExecutionBlockHash::zero(),
+                Slot::new(0),
+                Hash256::repeat_byte(42),
+            )
+            .await
+            .unwrap();
+
+        // The backup's status is reported rather than the primary's error.
+        assert_eq!(status, PayloadStatus::Syncing);
+    }
+
+    #[tokio::test]
+    async fn payload_building_fails_over_to_synced_backup_engine() {
+        let runtime = TestRuntime::default();
+        let mock = MockExecutionLayer::default_params_with_backup_engines(
+            runtime.task_executor.clone(),
+            1,
+        )
+        .move_to_terminal_block();
+        mock.server.set_syncing_response(Ok(true));
+        mock.el.upcheck().await;
+        assert!(mock.el.is_synced().await);
+
+        let latest_block = mock
+            .server
+            .execution_block_generator()
+            .latest_block()
+            .unwrap();
+        let parent_hash = latest_block.block_hash();
+        let timestamp = latest_block.block_number() + 1;
+        let prev_randao = Hash256::from_low_u64_be(timestamp);
+        let forkchoice_update_params = ForkchoiceUpdateParameters {
+            head_root: Hash256::repeat_byte(42),
+            head_hash: Some(parent_hash),
+            justified_hash: None,
+            finalized_hash: None,
+        };
+        let payload_attributes =
+            PayloadAttributes::new(timestamp, prev_randao, Address::repeat_byte(42), None, None);
+        let builder_params = BuilderParams {
+            pubkey: PublicKeyBytes::empty(),
+            slot: Slot::new(0),
+            chain_health: ChainHealth::Healthy,
+        };
+
+        mock.el
+            .get_payload(
+                parent_hash,
+                &payload_attributes,
+                forkchoice_update_params,
+                builder_params,
+                ForkName::Bellatrix,
+                &mock.spec,
+                None,
+                BlockProductionVersion::FullV2,
+            )
+            .await
+            .unwrap();
+
+        // The payload must have been built by the backup since the primary is syncing.
+        let request = mock.backup_servers[0].take_previous_request().unwrap();
+        assert_eq!(request["method"], http::ENGINE_GET_PAYLOAD_V1);
+    }
+
     #[tokio::test]
     async fn test_forked_terminal_block() {
         let runtime = TestRuntime::default();
@@ -2313,7 +2948,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_block_prior_to_terminal_block()
             .with_terminal_block(|spec, el, _| async move {
-                el.engine().upcheck().await;
+                el.upcheck().await;
                 assert_eq!(
                     el.get_terminal_pow_block_hash(&spec, timestamp_now())
                         .await
@@ -2340,7 +2975,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_block_prior_to_terminal_block()
             .with_terminal_block(|spec, el, _| async move {
-                el.engine().upcheck().await;
+                el.upcheck().await;
                 assert_eq!(
                     el.get_terminal_pow_block_hash(&spec, timestamp_now())
                         .await
@@ -2368,7 +3003,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_terminal_block()
             .with_terminal_block(|spec, el, terminal_block| async move {
-                el.engine().upcheck().await;
+                el.upcheck().await;
                 assert_eq!(
                     el.is_valid_terminal_pow_block_hash(terminal_block.unwrap().block_hash, &spec)
                         .await
@@ -2385,7 +3020,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_terminal_block()
             .with_terminal_block(|spec, el, terminal_block| async move {
-                el.engine().upcheck().await;
+                el.upcheck().await;
                 let invalid_terminal_block = terminal_block.unwrap().parent_hash;
 
                 assert_eq!(
@@ -2404,7 +3039,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_terminal_block()
             .with_terminal_block(|spec, el, _| async move {
-                el.engine().upcheck().await;
+                el.upcheck().await;
                 let missing_terminal_block = ExecutionBlockHash::repeat_byte(42);
 
                 assert_eq!(
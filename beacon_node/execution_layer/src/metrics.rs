@@ -7,6 +7,7 @@ pub const GET_BLINDED_PAYLOAD: &str = "get_blinded_payload";
 pub const GET_BLINDED_PAYLOAD_LOCAL: &str = "get_blinded_payload_local";
 pub const GET_BLINDED_PAYLOAD_BUILDER: &str = "get_blinded_payload_builder";
 pub const POST_BLINDED_PAYLOAD_BUILDER: &str = "post_blinded_payload_builder";
+pub const POST_BUILDER_VALIDATORS: &str = "post_builder_validators";
 pub const NEW_PAYLOAD: &str = "new_payload";
 pub const FORKCHOICE_UPDATED: &str = "forkchoice_updated";
 pub const GET_TERMINAL_POW_BLOCK_HASH: &str = "get_terminal_pow_block_hash";
@@ -15,6 +16,8 @@ pub const LOCAL: &str = "local";
 pub const BUILDER: &str = "builder";
 pub const SUCCESS: &str = "success";
 pub const FAILURE: &str = "failure";
+pub const NO_BID: &str = "no_bid";
+pub const INVALID: &str = "invalid";
 
 lazy_static::lazy_static! {
     pub static ref EXECUTION_LAYER_PROPOSER_INSERTED: Result<IntCounter> = try_create_int_counter(
@@ -84,4 +87,31 @@ lazy_static::lazy_static! {
         "The gwei bid value of payloads received by local EEs or builders. Only shows values up to i64::MAX.",
         &["source"]
     );
+    pub static ref EXECUTION_LAYER_BUILDER_REQUEST_TIMES: Result<HistogramVec> =
+        try_create_histogram_vec_with_buckets(
+        "execution_layer_builder_request_times",
+        "Duration of calls to each builder relay",
+        decimal_buckets(-2, 1),
+        &["relay", "method"]
+    );
+    pub static ref EXECUTION_LAYER_BUILDER_GET_HEADER_OUTCOME: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_builder_get_header_outcome",
+        "The success/no_bid/invalid/failure outcomes from requesting a header from each builder relay",
+        &["relay", "outcome"]
+    );
+    pub static ref EXECUTION_LAYER_BUILDER_BID_VALUES: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "execution_layer_builder_bid_values",
+        "The gwei value of the last valid bid received from each builder relay. Only shows values up to i64::MAX.",
+        &["relay"]
+    );
+    pub static ref EXECUTION_LAYER_BUILDER_WINNING_BIDS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_builder_winning_bids",
+        "Count of times each builder relay provided the payload used for a block proposal",
+        &["relay"]
+    );
+    pub static ref EXECUTION_LAYER_BUILDER_REGISTRATION_OUTCOME: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_builder_registration_outcome",
+        "The success/failure outcomes from forwarding validator registrations to each builder relay",
+        &["relay", "outcome"]
+    );
 }
@@ -3,7 +3,8 @@ use crate::EthersTransaction;
 use crate::{
     engine_api::{
         json_structures::{
-            JsonForkchoiceUpdatedV1Response, JsonPayloadStatusV1, JsonPayloadStatusV1Status,
+            BlobAndProofV1, JsonForkchoiceUpdatedV1Response, JsonPayloadStatusV1,
+            JsonPayloadStatusV1Status,
         },
         ExecutionBlock, PayloadAttributes, PayloadId, PayloadStatusV1, PayloadStatusV1Status,
     },
@@ -16,6 +17,7 @@ use rand::{rngs::StdRng, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 use ssz::Decode;
 use ssz_types::VariableList;
+use state_processing::per_block_processing::deneb::kzg_commitment_to_versioned_hash;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tree_hash::TreeHash;
@@ -451,6 +453,25 @@ impl<E: EthSpec> ExecutionBlockGenerator<E> {
         self.blobs_bundles.get(id).cloned()
     }
 
+    /// Looks up a blob and its proof by versioned hash in the bundles of the payloads produced by
+    /// this generator. This stands in for the blob pool of a real execution client.
+    pub fn get_blob_and_proof(&self, versioned_hash: &Hash256) -> Option<BlobAndProofV1<E>> {
+        self.blobs_bundles.values().find_map(|bundle| {
+            bundle
+                .commitments
+                .iter()
+                .zip(bundle.proofs.iter())
+                .zip(bundle.blobs.iter())
+                .find(|((commitment, _), _)| {
+                    kzg_commitment_to_versioned_hash(commitment) == *versioned_hash
+                })
+                .map(|((_, proof), blob)| BlobAndProofV1 {
+                    blob: blob.clone(),
+                    proof: *proof,
+                })
+        })
+    }
+
     pub fn new_payload(&mut self, payload: ExecutionPayload<E>) -> PayloadStatusV1 {
         let Some(parent) = self.blocks.get(&payload.parent_hash()) else {
             return PayloadStatusV1 {
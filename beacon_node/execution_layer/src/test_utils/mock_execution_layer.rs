@@ -11,6 +11,8 @@ use types::MainnetEthSpec;
 
 pub struct MockExecutionLayer<E: EthSpec> {
     pub server: MockServer<E>,
+    /// Servers for the backup execution engines, in order of priority.
+    pub backup_servers: Vec<MockServer<E>>,
     pub el: ExecutionLayer<E>,
     pub executor: TaskExecutor,
     pub spec: ChainSpec,
@@ -18,11 +20,20 @@ pub struct MockExecutionLayer<E: EthSpec> {
 
 impl<E: EthSpec> MockExecutionLayer<E> {
     pub fn default_params(executor: TaskExecutor) -> Self {
+        Self::default_params_with_backup_engines(executor, 0)
+    }
+
+    /// Instantiate with a primary engine and `num_backup_engines` backup engines, each served by
+    /// its own `MockServer`.
+    pub fn default_params_with_backup_engines(
+        executor: TaskExecutor,
+        num_backup_engines: usize,
+    ) -> Self {
         let mut spec = MainnetEthSpec::default_spec();
         spec.terminal_total_difficulty = DEFAULT_TERMINAL_DIFFICULTY.into();
         spec.terminal_block_hash = ExecutionBlockHash::zero();
         spec.terminal_block_hash_activation_epoch = Epoch::new(0);
-        Self::new(
+        Self::new_with_backup_engines(
             executor,
             DEFAULT_TERMINAL_BLOCK,
             None,
@@ -31,6 +42,7 @@ impl<E: EthSpec> MockExecutionLayer<E> {
             Some(JwtKey::from_slice(&DEFAULT_JWT_SECRET).unwrap()),
             spec,
             None,
+            num_backup_engines,
         )
     }
 
@@ -45,22 +57,57 @@ impl<E: EthSpec> MockExecutionLayer<E> {
         spec: ChainSpec,
         kzg: Option<Arc<Kzg>>,
     ) -> Self {
-        let handle = executor.handle().unwrap();
-
-        let jwt_key = jwt_key.unwrap_or_else(JwtKey::random);
-        let server = MockServer::new(
-            &handle,
-            jwt_key,
-            spec.terminal_total_difficulty,
+        Self::new_with_backup_engines(
+            executor,
             terminal_block,
-            spec.terminal_block_hash,
             shanghai_time,
             cancun_time,
             prague_time,
+            jwt_key,
+            spec,
             kzg,
-        );
+            0,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_backup_engines(
+        executor: TaskExecutor,
+        terminal_block: u64,
+        shanghai_time: Option<u64>,
+        cancun_time: Option<u64>,
+        prague_time: Option<u64>,
+        jwt_key: Option<JwtKey>,
+        spec: ChainSpec,
+        kzg: Option<Arc<Kzg>>,
+        num_backup_engines: usize,
+    ) -> Self {
+        let handle = executor.handle().unwrap();
+
+        let jwt_key = jwt_key.unwrap_or_else(JwtKey::random);
+        let new_server = || {
+            MockServer::new(
+                &handle,
+                jwt_key.clone(),
+                spec.terminal_total_difficulty,
+                terminal_block,
+                spec.terminal_block_hash,
+                shanghai_time,
+                cancun_time,
+                prague_time,
+                kzg.clone(),
+            )
+        };
+        let server = new_server();
+        let backup_servers = (0..num_backup_engines)
+            .map(|_| new_server())
+            .collect::<Vec<_>>();
 
         let url = SensitiveUrl::parse(&server.url()).unwrap();
+        let backup_urls = backup_servers
+            .iter()
+            .map(|server| SensitiveUrl::parse(&server.url()).unwrap())
+            .collect();
         let file = NamedTempFile::new().unwrap();
 
         let path = file.path().into();
@@ -68,6 +115,7 @@ impl<E: EthSpec> MockExecutionLayer<E> {
 
         let config = Config {
             execution_endpoint: Some(url),
+            backup_execution_endpoints: backup_urls,
             secret_file: Some(path),
             suggested_fee_recipient: Some(Address::repeat_byte(42)),
             ..Default::default()
@@ -77,6 +125,7 @@ impl<E: EthSpec> MockExecutionLayer<E> {
 
         Self {
             server,
+            backup_servers,
             el,
             executor,
             spec,
@@ -274,19 +323,28 @@ impl<E: EthSpec> MockExecutionLayer<E> {
         assert_eq!(head_execution_block.parent_hash(), parent_hash);
     }
 
+    /// Returns the primary server followed by all backup servers.
+    pub fn servers(&self) -> impl Iterator<Item = &MockServer<E>> {
+        std::iter::once(&self.server).chain(self.backup_servers.iter())
+    }
+
     pub fn move_to_block_prior_to_terminal_block(self) -> Self {
-        self.server
-            .execution_block_generator()
-            .move_to_block_prior_to_terminal_block()
-            .unwrap();
+        for server in self.servers() {
+            server
+                .execution_block_generator()
+                .move_to_block_prior_to_terminal_block()
+                .unwrap();
+        }
         self
     }
 
     pub fn move_to_terminal_block(self) -> Self {
-        self.server
-            .execution_block_generator()
-            .move_to_terminal_block()
-            .unwrap();
+        for server in self.servers() {
+            server
+                .execution_block_generator()
+                .move_to_terminal_block()
+                .unwrap();
+        }
         self
     }
 
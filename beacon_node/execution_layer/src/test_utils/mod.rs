@@ -1,6 +1,8 @@
 //! Provides a mock execution engine HTTP JSON-RPC API for use in testing.
 
 use crate::engine_api::auth::JwtKey;
+#[cfg(unix)]
+use crate::engine_api::ipc::{read_json_value, IPC_URL_SCHEME};
 use crate::engine_api::{
     auth::Auth, http::JSONRPC_VERSION, ExecutionBlock, PayloadStatusV1, PayloadStatusV1Status,
 };
@@ -20,6 +22,8 @@ use std::convert::Infallible;
 use std::future::Future;
 use std::marker::PhantomData;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+#[cfg(unix)]
+use std::path::Path;
 use std::sync::Arc;
 use tokio::{runtime, sync::oneshot};
 use types::{EthSpec, ExecutionBlockHash, Uint256};
@@ -54,6 +58,7 @@ pub const DEFAULT_ENGINE_CAPABILITIES: EngineCapabilities = EngineCapabilities {
     get_payload_v3: true,
     get_payload_v4: true,
     get_client_version_v1: true,
+    get_blobs_v1: true,
 };
 
 lazy_static! {
@@ -230,6 +235,16 @@ impl<E: EthSpec> MockServer<E> {
         self.ctx.execution_block_generator.write()
     }
 
+    /// Starts serving the engine API over a Unix domain socket at `path`, in addition to HTTP.
+    ///
+    /// Returns the `ipc` URL of the socket.
+    #[cfg(unix)]
+    pub fn start_ipc_server(&self, path: &Path) -> String {
+        let server = serve_ipc(self.ctx.clone(), path).expect("should bind IPC socket");
+        tokio::spawn(server);
+        format!("{}://{}", IPC_URL_SCHEME, path.display())
+    }
+
     pub fn url(&self) -> String {
         format!(
             "http://{}:{}",
@@ -475,6 +490,13 @@ impl<E: EthSpec> MockServer<E> {
             .insert(block_hash, Err(error));
     }
 
+    pub fn set_fcu_payload_error(&self, block_hash: ExecutionBlockHash, error: String) {
+        self.ctx
+            .fcu_payload_statuses
+            .lock()
+            .insert(block_hash, Err(error));
+    }
+
     pub fn set_syncing_response(&self, res: Result<bool, String>) {
         *self.ctx.syncing_response.lock() = res;
     }
@@ -637,6 +659,78 @@ async fn handle_rejection(err: Rejection) -> Result<impl warp::Reply, Infallible
     Ok(warp::reply::with_status(json, code))
 }
 
+/// Returns the JSON-RPC response to the request `body`, either from the preloaded responses in
+/// `ctx` or by handling the request with `handle_rpc`.
+async fn rpc_response<E: EthSpec>(
+    id: serde_json::Value,
+    body: serde_json::Value,
+    ctx: Arc<Context<E>>,
+) -> serde_json::Value {
+    let preloaded_response = {
+        let mut preloaded_responses = ctx.preloaded_responses.lock();
+        if !preloaded_responses.is_empty() {
+            Some(preloaded_responses.remove(0))
+        } else {
+            None
+        }
+    };
+
+    if let Some(preloaded_response) = preloaded_response {
+        preloaded_response
+    } else {
+        match handle_rpc(body, ctx).await {
+            Ok(result) => json!({
+                "id": id,
+                "jsonrpc": JSONRPC_VERSION,
+                "result": result
+            }),
+            Err((message, code)) => json!({
+                "id": id,
+                "jsonrpc": JSONRPC_VERSION,
+                "error": {
+                    "code": code,
+                    "message": message
+                }
+            }),
+        }
+    }
+}
+
+/// Creates a server that will serve JSON-RPC requests over the Unix domain socket at `path`,
+/// using information from `ctx`.
+///
+/// Returns the server future, which will need to be awaited or spawned.
+#[cfg(unix)]
+pub fn serve_ipc<E: EthSpec>(
+    ctx: Arc<Context<E>>,
+    path: &Path,
+) -> Result<impl Future<Output = ()>, Error> {
+    use tokio::io::AsyncWriteExt;
+
+    let listener = tokio::net::UnixListener::bind(path)
+        .map_err(|e| format!("Failed to bind IPC socket: {:?}", e))?;
+
+    Ok(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let ctx = ctx.clone();
+            tokio::spawn(async move {
+                while let Ok(Some(body)) =
+                    read_json_value::<_, serde_json::Value>(&mut stream).await
+                {
+                    let id = body.get("id").cloned().unwrap_or_default();
+                    let response = rpc_response(id, body, ctx.clone()).await;
+                    let mut bytes =
+                        serde_json::to_vec(&response).expect("response must be valid JSON");
+                    bytes.push(b'\n');
+                    if stream.write_all(&bytes).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    })
+}
+
 /// Creates a server that will serve requests using information from `ctx`.
 ///
 /// The server will shut down gracefully when the `shutdown` future resolves.
@@ -673,34 +767,7 @@ pub fn serve<E: EthSpec>(
                 .get("id")
                 .and_then(serde_json::Value::as_u64)
                 .ok_or_else(|| warp::reject::custom(MissingIdField))?;
-            let preloaded_response = {
-                let mut preloaded_responses = ctx.preloaded_responses.lock();
-                if !preloaded_responses.is_empty() {
-                    Some(preloaded_responses.remove(0))
-                } else {
-                    None
-                }
-            };
-
-            let response = if let Some(preloaded_response) = preloaded_response {
-                preloaded_response
-            } else {
-                match handle_rpc(body, ctx).await {
-                    Ok(result) => json!({
-                        "id": id,
-                        "jsonrpc": JSONRPC_VERSION,
-                        "result": result
-                    }),
-                    Err((message, code)) => json!({
-                        "id": id,
-                        "jsonrpc": JSONRPC_VERSION,
-                        "error": {
-                            "code": code,
-                            "message": message
-                        }
-                    }),
-                }
-            };
+            let response = rpc_response(json!(id), body, ctx).await;
 
             Ok::<_, warp::reject::Rejection>(
                 warp::http::Response::builder()
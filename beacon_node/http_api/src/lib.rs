@@ -15,9 +15,11 @@ mod builder_states;
 mod database;
 mod metrics;
 mod produce_block;
+mod proofs;
 mod proposer_duties;
 mod publish_attestations;
 mod publish_blocks;
+mod simulate_block;
 mod standard_block_rewards;
 mod state_id;
 mod sync_committee_rewards;
@@ -26,6 +28,7 @@ mod task_spawner;
 pub mod test_utils;
 mod ui;
 mod validator;
+mod validator_history;
 mod validator_inclusion;
 mod validators;
 mod version;
@@ -90,14 +93,19 @@ use validator::pubkey_to_validator_index;
 use version::{
     add_consensus_version_header, add_ssz_content_type_header,
     execution_optimistic_finalized_fork_versioned_response, inconsistent_fork_rejection,
-    unsupported_version_rejection, V1, V2, V3,
+    unsupported_version_rejection, V0, V1, V2, V3,
 };
 use warp::http::StatusCode;
 use warp::hyper::Body;
 use warp::sse::Event;
 use warp::Reply;
 use warp::{http::Response, Filter, Rejection};
-use warp_utils::{query::multi_key_query, reject::convert_rejection, uor::UnifyingOrFilter};
+use warp_utils::{
+    negotiate::{json_only_response, ssz_or_json_response},
+    query::multi_key_query,
+    reject::convert_rejection,
+    uor::UnifyingOrFilter,
+};
 
 const API_PREFIX: &str = "eth";
 
@@ -367,6 +375,7 @@ pub fn serve<T: BeaconChainTypes>(
             .untuple_one()
     };
 
+    let eth_v0 = single_version(V0);
     let eth_v1 = single_version(V1);
     let eth_v2 = single_version(V2);
 
@@ -553,15 +562,19 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
-            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let genesis_data = api_types::GenesisData {
                         genesis_time: chain.genesis_time,
                         genesis_validators_root: chain.genesis_validators_root,
                         genesis_fork_version: chain.spec.genesis_fork_version,
                     };
-                    Ok(api_types::GenericResponse::from(genesis_data))
+                    let res = api_types::GenericResponse::from(genesis_data);
+                    ssz_or_json_response(accept_header, &res.data, &res)
                 })
             },
         );
@@ -586,18 +599,17 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("root"))
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
-             chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let (root, execution_optimistic, finalized) = state_id.root(&chain)?;
-                    Ok(api_types::GenericResponse::from(api_types::RootData::from(
-                        root,
-                    )))
-                    .map(|resp| {
-                        resp.add_execution_optimistic_finalized(execution_optimistic, finalized)
-                    })
+                    let res = api_types::GenericResponse::from(api_types::RootData::from(root))
+                        .add_execution_optimistic_finalized(execution_optimistic, finalized);
+                    ssz_or_json_response(accept_header, &res.data, &res)
                 })
             },
         );
@@ -607,18 +619,21 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("fork"))
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
-             chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let (fork, execution_optimistic, finalized) =
                         state_id.fork_and_execution_optimistic_and_finalized(&chain)?;
-                    Ok(api_types::ExecutionOptimisticFinalizedResponse {
+                    let res = api_types::ExecutionOptimisticFinalizedResponse {
                         data: fork,
                         execution_optimistic: Some(execution_optimistic),
                         finalized: Some(finalized),
-                    })
+                    };
+                    ssz_or_json_response(accept_header, &res.data, &res)
                 })
             },
         );
@@ -628,11 +643,13 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("finality_checkpoints"))
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
-             chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let (data, execution_optimistic, finalized) = state_id
                         .map_state_and_execution_optimistic_and_finalized(
                             &chain,
@@ -649,11 +666,12 @@ pub fn serve<T: BeaconChainTypes>(
                             },
                         )?;
 
-                    Ok(api_types::ExecutionOptimisticFinalizedResponse {
+                    let res = api_types::ExecutionOptimisticFinalizedResponse {
                         data,
                         execution_optimistic: Some(execution_optimistic),
                         finalized: Some(finalized),
-                    })
+                    };
+                    ssz_or_json_response(accept_header, &res.data, &res)
                 })
             },
         );
@@ -664,18 +682,21 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("validator_balances"))
         .and(warp::path::end())
         .and(multi_key_query::<api_types::ValidatorBalancesQuery>())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             query_res: Result<api_types::ValidatorBalancesQuery, warp::Rejection>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             query_res: Result<api_types::ValidatorBalancesQuery, warp::Rejection>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let query = query_res?;
-                    crate::validators::get_beacon_state_validator_balances(
+                    let res = crate::validators::get_beacon_state_validator_balances(
                         state_id,
                         chain,
                         query.id.as_deref(),
-                    )
+                    )?;
+                    ssz_or_json_response(accept_header, &res.data, &res)
                 })
             },
         );
@@ -686,17 +707,20 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("validator_balances"))
         .and(warp::path::end())
         .and(warp_utils::json::json())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             query: ValidatorBalancesRequestBody| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
-                    crate::validators::get_beacon_state_validator_balances(
+             query: ValidatorBalancesRequestBody,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    let res = crate::validators::get_beacon_state_validator_balances(
                         state_id,
                         chain,
                         Some(&query.ids),
-                    )
+                    )?;
+                    ssz_or_json_response(accept_header, &res.data, &res)
                 })
             },
         );
@@ -707,19 +731,22 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("validators"))
         .and(warp::path::end())
         .and(multi_key_query::<api_types::ValidatorsQuery>())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             query_res: Result<api_types::ValidatorsQuery, warp::Rejection>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             query_res: Result<api_types::ValidatorsQuery, warp::Rejection>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let query = query_res?;
-                    crate::validators::get_beacon_state_validators(
+                    let res = crate::validators::get_beacon_state_validators(
                         state_id,
                         chain,
                         &query.id,
                         &query.status,
-                    )
+                    )?;
+                    ssz_or_json_response(accept_header, &res.data, &res)
                 })
             },
         );
@@ -730,18 +757,21 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("validators"))
         .and(warp::path::end())
         .and(warp_utils::json::json())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             query: ValidatorsRequestBody| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
-                    crate::validators::get_beacon_state_validators(
+             query: ValidatorsRequestBody,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    let res = crate::validators::get_beacon_state_validators(
                         state_id,
                         chain,
                         &query.ids,
                         &query.statuses,
-                    )
+                    )?;
+                    ssz_or_json_response(accept_header, &res.data, &res)
                 })
             },
         );
@@ -756,12 +786,14 @@ pub fn serve<T: BeaconChainTypes>(
             ))
         }))
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             validator_id: ValidatorId| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             validator_id: ValidatorId,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let (data, execution_optimistic, finalized) = state_id
                         .map_state_and_execution_optimistic_and_finalized(
                             &chain,
@@ -809,11 +841,12 @@ pub fn serve<T: BeaconChainTypes>(
                             },
                         )?;
 
-                    Ok(api_types::ExecutionOptimisticFinalizedResponse {
+                    let res = api_types::ExecutionOptimisticFinalizedResponse {
                         data,
                         execution_optimistic: Some(execution_optimistic),
                         finalized: Some(finalized),
-                    })
+                    };
+                    ssz_or_json_response(accept_header, &res.data, &res)
                 })
             },
         );
@@ -824,12 +857,14 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("committees"))
         .and(warp::query::<api_types::CommitteesQuery>())
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             query: api_types::CommitteesQuery| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             query: api_types::CommitteesQuery,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let (data, execution_optimistic, finalized) = state_id
                         .map_state_and_execution_optimistic_and_finalized(
                             &chain,
@@ -989,11 +1024,12 @@ pub fn serve<T: BeaconChainTypes>(
                                 Ok((response, execution_optimistic, finalized))
                             },
                         )?;
-                    Ok(api_types::ExecutionOptimisticFinalizedResponse {
+                    let res = api_types::ExecutionOptimisticFinalizedResponse {
                         data,
                         execution_optimistic: Some(execution_optimistic),
                         finalized: Some(finalized),
-                    })
+                    };
+                    ssz_or_json_response(accept_header, &res.data, &res)
                 })
             },
         );
@@ -1004,12 +1040,14 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("sync_committees"))
         .and(warp::query::<api_types::SyncCommitteesQuery>())
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             query: api_types::SyncCommitteesQuery| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             query: api_types::SyncCommitteesQuery,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let (sync_committee, execution_optimistic, finalized) = state_id
                         .map_state_and_execution_optimistic_and_finalized(
                             &chain,
@@ -1058,8 +1096,9 @@ pub fn serve<T: BeaconChainTypes>(
                         validator_aggregates,
                     };
 
-                    Ok(api_types::GenericResponse::from(response)
-                        .add_execution_optimistic_finalized(execution_optimistic, finalized))
+                    let res = api_types::GenericResponse::from(response)
+                        .add_execution_optimistic_finalized(execution_optimistic, finalized);
+                    ssz_or_json_response(accept_header, &res.data, &res)
                 })
             },
         );
@@ -1070,12 +1109,14 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("randao"))
         .and(warp::query::<api_types::RandaoQuery>())
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             query: api_types::RandaoQuery| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             query: api_types::RandaoQuery,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let (randao, execution_optimistic, finalized) = state_id
                         .map_state_and_execution_optimistic_and_finalized(
                             &chain,
@@ -1090,10 +1131,9 @@ pub fn serve<T: BeaconChainTypes>(
                             },
                         )?;
 
-                    Ok(
-                        api_types::GenericResponse::from(api_types::RandaoMix { randao })
-                            .add_execution_optimistic_finalized(execution_optimistic, finalized),
-                    )
+                    let res = api_types::GenericResponse::from(api_types::RandaoMix { randao })
+                        .add_execution_optimistic_finalized(execution_optimistic, finalized);
+                    ssz_or_json_response(accept_header, &res.data, &res)
                 })
             },
         );
@@ -1112,11 +1152,13 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |query: api_types::HeadersQuery,
              task_spawner: TaskSpawner<T::EthSpec>,
-             chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let (root, block, execution_optimistic, finalized) =
                         match (query.slot, query.parent_root) {
                             // No query parameters, return the canonical head block.
@@ -1194,8 +1236,9 @@ pub fn serve<T: BeaconChainTypes>(
                         },
                     };
 
-                    Ok(api_types::GenericResponse::from(vec![data])
-                        .add_execution_optimistic_finalized(execution_optimistic, finalized))
+                    let res = api_types::GenericResponse::from(vec![data])
+                        .add_execution_optimistic_finalized(execution_optimistic, finalized);
+                    ssz_or_json_response(accept_header, &res.data, &res)
                 })
             },
         );
@@ -1212,11 +1255,13 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |block_id: BlockId,
              task_spawner: TaskSpawner<T::EthSpec>,
-             chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let (root, execution_optimistic, finalized) = block_id.root(&chain)?;
                     // Ignore the second `execution_optimistic` since the first one has more
                     // information about the original request.
@@ -1237,11 +1282,12 @@ pub fn serve<T: BeaconChainTypes>(
                         },
                     };
 
-                    Ok(api_types::ExecutionOptimisticFinalizedResponse {
+                    let res = api_types::ExecutionOptimisticFinalizedResponse {
                         execution_optimistic: Some(execution_optimistic),
                         finalized: Some(finalized),
                         data,
-                    })
+                    };
+                    ssz_or_json_response(accept_header, &res.data, &res)
                 })
             },
         );
@@ -1605,10 +1651,12 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("root"))
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |block_id: BlockId,
              task_spawner: TaskSpawner<T::EthSpec>,
-             chain: Arc<BeaconChain<T>>| {
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
                 // Prioritise requests for the head block root, as it is used by some VCs (including
                 // the Lighthouse VC) to create sync committee messages.
                 let priority = if let BlockId(eth2::types::BlockId::Head) = block_id {
@@ -1616,12 +1664,12 @@ pub fn serve<T: BeaconChainTypes>(
                 } else {
                     Priority::P1
                 };
-                task_spawner.blocking_json_task(priority, move || {
+                task_spawner.blocking_response_task(priority, move || {
                     let (block_root, execution_optimistic, finalized) = block_id.root(&chain)?;
-                    Ok(
+                    let res =
                         api_types::GenericResponse::from(api_types::RootData::from(block_root))
-                            .add_execution_optimistic_finalized(execution_optimistic, finalized),
-                    )
+                            .add_execution_optimistic_finalized(execution_optimistic, finalized);
+                    ssz_or_json_response(accept_header, &res.data, &res)
                 })
             },
         );
@@ -1631,11 +1679,13 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("attestations"))
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |endpoint_version: EndpointVersion,
              block_id: BlockId,
              task_spawner: TaskSpawner<T::EthSpec>,
-             chain: Arc<BeaconChain<T>>| {
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
                 task_spawner.blocking_response_task(Priority::P1, move || {
                     let (block, execution_optimistic, finalized) =
                         block_id.blinded_block(&chain)?;
@@ -1656,7 +1706,7 @@ pub fn serve<T: BeaconChainTypes>(
                         &atts,
                     )?;
                     Ok(add_consensus_version_header(
-                        warp::reply::json(&res).into_response(),
+                        ssz_or_json_response(accept_header, &atts, &res)?,
                         fork_name,
                     ))
                 })
@@ -1756,6 +1806,69 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    /*
+     * beacon/{states,blocks}/{id}/proof
+     */
+
+    // GET beacon/states/{state_id}/proof
+    let proof_cache = Arc::new(proofs::ProofCache::default());
+    let get_beacon_state_proof = eth_v0
+        .and(warp::path("beacon"))
+        .and(warp::path("states"))
+        .and(warp::path::param::<StateId>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid state ID".to_string(),
+            ))
+        }))
+        .and(warp::path("proof"))
+        .and(warp::path::end())
+        .and(multi_key_query::<api_types::ProofQuery>())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .and(warp::any().map(move || proof_cache.clone()))
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .then(
+            |state_id: StateId,
+             query_res: Result<api_types::ProofQuery, warp::Rejection>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             proof_cache: Arc<proofs::ProofCache>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    let query = query_res?;
+                    let (proof, execution_optimistic, finalized) =
+                        proofs::state_proof(state_id, query, &chain, &proof_cache)?;
+                    proofs::proof_response(proof, execution_optimistic, finalized, accept_header)
+                })
+            },
+        );
+
+    // GET beacon/blocks/{block_id}/proof
+    let get_beacon_block_proof = eth_v0
+        .and(warp::path("beacon"))
+        .and(warp::path("blocks"))
+        .and(block_id_or_err)
+        .and(warp::path("proof"))
+        .and(warp::path::end())
+        .and(multi_key_query::<api_types::ProofQuery>())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .then(
+            |block_id: BlockId,
+             query_res: Result<api_types::ProofQuery, warp::Rejection>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    let query = query_res?;
+                    let (proof, execution_optimistic, finalized) =
+                        proofs::block_proof(block_id, query, &chain).await?;
+                    proofs::proof_response(proof, execution_optimistic, finalized, accept_header)
+                })
+            },
+        );
+
     /*
      * beacon/pool
      */
@@ -1812,11 +1925,13 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("attestations"))
         .and(warp::path::end())
         .and(warp::query::<api_types::AttestationPoolQuery>())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |endpoint_version: EndpointVersion,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             query: api_types::AttestationPoolQuery| {
+             query: api_types::AttestationPoolQuery,
+             accept_header: Option<api_types::Accept>| {
                 task_spawner.blocking_response_task(Priority::P1, move || {
                     let query_filter = |data: &AttestationData| {
                         query.slot.map_or(true, |slot| slot == data.slot)
@@ -1856,7 +1971,7 @@ pub fn serve<T: BeaconChainTypes>(
 
                     let res = fork_versioned_response(endpoint_version, fork_name, &attestations)?;
                     Ok(add_consensus_version_header(
-                        warp::reply::json(&res).into_response(),
+                        ssz_or_json_response(accept_header, &attestations, &res)?,
                         fork_name,
                     ))
                 })
@@ -1917,10 +2032,12 @@ pub fn serve<T: BeaconChainTypes>(
             .clone()
             .and(warp::path("attester_slashings"))
             .and(warp::path::end())
+            .and(warp::header::optional::<api_types::Accept>("accept"))
             .then(
                 |endpoint_version: EndpointVersion,
                  task_spawner: TaskSpawner<T::EthSpec>,
-                 chain: Arc<BeaconChain<T>>| {
+                 chain: Arc<BeaconChain<T>>,
+                 accept_header: Option<api_types::Accept>| {
                     task_spawner.blocking_response_task(Priority::P1, move || {
                         let slashings = chain.op_pool.get_all_attester_slashings();
 
@@ -1945,7 +2062,7 @@ pub fn serve<T: BeaconChainTypes>(
 
                         let res = fork_versioned_response(endpoint_version, fork_name, &slashings)?;
                         Ok(add_consensus_version_header(
-                            warp::reply::json(&res).into_response(),
+                            ssz_or_json_response(accept_header, &slashings, &res)?,
                             fork_name,
                         ))
                     })
@@ -2001,11 +2118,15 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("proposer_slashings"))
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
-            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let attestations = chain.op_pool.get_all_proposer_slashings();
-                    Ok(api_types::GenericResponse::from(attestations))
+                    let res = api_types::GenericResponse::from(attestations);
+                    ssz_or_json_response(accept_header, &res.data, &res)
                 })
             },
         );
@@ -2057,11 +2178,15 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("voluntary_exits"))
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
-            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let attestations = chain.op_pool.get_all_voluntary_exits();
-                    Ok(api_types::GenericResponse::from(attestations))
+                    let res = api_types::GenericResponse::from(attestations);
+                    ssz_or_json_response(accept_header, &res.data, &res)
                 })
             },
         );
@@ -2094,11 +2219,15 @@ pub fn serve<T: BeaconChainTypes>(
         .clone()
         .and(warp::path("bls_to_execution_changes"))
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
-            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let address_changes = chain.op_pool.get_all_bls_to_execution_changes();
-                    Ok(api_types::GenericResponse::from(address_changes))
+                    let res = api_types::GenericResponse::from(address_changes);
+                    ssz_or_json_response(accept_header, &res.data, &res)
                 })
             },
         );
@@ -2257,16 +2386,18 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("blocks"))
         .and(block_id_or_err)
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             block_id: BlockId| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             block_id: BlockId,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let (rewards, execution_optimistic, finalized) =
                         standard_block_rewards::compute_beacon_block_rewards(chain, block_id)?;
-                    Ok(api_types::GenericResponse::from(rewards)).map(|resp| {
-                        resp.add_execution_optimistic_finalized(execution_optimistic, finalized)
-                    })
+                    let res = api_types::GenericResponse::from(rewards)
+                        .add_execution_optimistic_finalized(execution_optimistic, finalized);
+                    json_only_response(accept_header, &res)
                 })
             },
         );
@@ -2484,6 +2615,73 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET beacon/light_client/updates
+    let get_beacon_light_client_updates = beacon_light_client_path
+        .clone()
+        .and(task_spawner_filter.clone())
+        .and(warp::path("updates"))
+        .and(warp::path::end())
+        .and(warp::query::<api_types::LightClientUpdatesQuery>())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .then(
+            |chain: Arc<BeaconChain<T>>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             query: api_types::LightClientUpdatesQuery,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    let updates = chain
+                        .get_light_client_updates(query.start_period, query.count)
+                        .map_err(|e| {
+                            warp_utils::reject::custom_server_error(format!(
+                                "Unable to obtain LightClientUpdates: {e:?}"
+                            ))
+                        })?;
+
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => {
+                            // Each chunk is the length of the context and payload as a
+                            // little-endian `u64`, the fork digest and the SSZ payload.
+                            let mut bytes = vec![];
+                            for update in &updates {
+                                let fork_digest = chain.spec.fork_digest::<T::EthSpec>(
+                                    update.attested_header_slot(),
+                                    chain.genesis_validators_root,
+                                );
+                                let payload = update.as_ssz_bytes();
+                                let chunk_len = (fork_digest.len() + payload.len()) as u64;
+                                bytes.extend_from_slice(&chunk_len.to_le_bytes());
+                                bytes.extend_from_slice(&fork_digest);
+                                bytes.extend_from_slice(&payload);
+                            }
+                            Response::builder()
+                                .status(200)
+                                .body(bytes.into())
+                                .map(|res: Response<Body>| add_ssz_content_type_header(res))
+                                .map_err(|e| {
+                                    warp_utils::reject::custom_server_error(format!(
+                                        "failed to create response: {}",
+                                        e
+                                    ))
+                                })
+                        }
+                        _ => {
+                            let updates = updates
+                                .into_iter()
+                                .map(|update| ForkVersionedResponse {
+                                    version: Some(chain.spec.fork_name_at_slot::<T::EthSpec>(
+                                        update.attested_header_slot(),
+                                    )),
+                                    metadata: EmptyMetadata {},
+                                    data: update,
+                                })
+                                .collect::<Vec<_>>();
+                            Ok(warp::reply::json(&updates).into_response())
+                        }
+                    }
+                })
+            },
+        );
+
     /*
      * beacon/rewards
      */
@@ -2501,12 +2699,14 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path::param::<Epoch>())
         .and(warp::path::end())
         .and(warp_utils::json::json())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
              epoch: Epoch,
-             validators: Vec<ValidatorId>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             validators: Vec<ValidatorId>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let attestation_rewards = chain
                         .compute_attestation_rewards(epoch, validators)
                         .map_err(|e| match e {
@@ -2538,8 +2738,9 @@ pub fn serve<T: BeaconChainTypes>(
                     let execution_optimistic =
                         chain.is_optimistic_or_invalid_head().unwrap_or_default();
 
-                    Ok(api_types::GenericResponse::from(attestation_rewards))
-                        .map(|resp| resp.add_execution_optimistic(execution_optimistic))
+                    let res = api_types::GenericResponse::from(attestation_rewards)
+                        .add_execution_optimistic(execution_optimistic);
+                    json_only_response(accept_header, &res)
                 })
             },
         );
@@ -2552,21 +2753,23 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path::end())
         .and(warp_utils::json::json())
         .and(log_filter.clone())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
              block_id: BlockId,
              validators: Vec<ValidatorId>,
-             log: Logger| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             log: Logger,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let (rewards, execution_optimistic, finalized) =
                         sync_committee_rewards::compute_sync_committee_rewards(
                             chain, block_id, validators, log,
                         )?;
 
-                    Ok(api_types::GenericResponse::from(rewards)).map(|resp| {
-                        resp.add_execution_optimistic_finalized(execution_optimistic, finalized)
-                    })
+                    let res = api_types::GenericResponse::from(rewards)
+                        .add_execution_optimistic_finalized(execution_optimistic, finalized);
+                    json_only_response(accept_header, &res)
                 })
             },
         );
@@ -3768,17 +3971,19 @@ pub fn serve<T: BeaconChainTypes>(
                         // send the response back to our original HTTP request
                         // task via a channel.
                         let builder_future = async move {
-                            let arc_builder = chain
+                            let execution_layer = chain
                                 .execution_layer
                                 .as_ref()
                                 .ok_or(BeaconChainError::ExecutionLayerMissing)
-                                .map_err(warp_utils::reject::beacon_chain_error)?
-                                .builder();
-                            let builder = arc_builder
-                                .as_ref()
-                                .ok_or(BeaconChainError::BuilderMissing)
                                 .map_err(warp_utils::reject::beacon_chain_error)?;
-                            builder
+                            if !execution_layer.has_builder() {
+                                return Err(warp_utils::reject::beacon_chain_error(
+                                    BeaconChainError::BuilderMissing,
+                                ));
+                            }
+                            // Registrations are forwarded to every connected relay, and only
+                            // fail if no relay accepted them.
+                            execution_layer
                                 .post_builder_validators(&filtered_registration_data)
                                 .await
                                 .map(|resp| warp::reply::json(&resp).into_response())
@@ -3791,7 +3996,10 @@ pub fn serve<T: BeaconChainTypes>(
                                     );
                                     // Forward the HTTP status code if we are able to, otherwise fall back
                                     // to a server error.
-                                    if let eth2::Error::ServerMessage(message) = e {
+                                    if let execution_layer::Error::Builder(
+                                        eth2::Error::ServerMessage(message),
+                                    ) = e
+                                    {
                                         if message.code == StatusCode::BAD_REQUEST.as_u16() {
                                             return warp_utils::reject::custom_bad_request(
                                                 message.message,
@@ -4360,6 +4568,28 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/analysis/simulate_block
+    let post_lighthouse_simulate_block = warp::path("lighthouse")
+        .and(warp::path("analysis"))
+        .and(warp::path("simulate_block"))
+        .and(warp::query::<eth2::lighthouse::SimulateBlockQuery>())
+        .and(warp::path::end())
+        .and(warp::body::bytes())
+        .and(consensus_version_header_filter)
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |query,
+             block_bytes: Bytes,
+             consensus_version: ForkName,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    simulate_block::simulate_block(block_bytes, consensus_version, query, chain)
+                })
+            },
+        );
+
     // GET lighthouse/analysis/attestation_performance/{index}
     let get_lighthouse_attestation_performance = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4377,6 +4607,33 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/validators/{validator_id}/history
+    let validator_history_cache = Arc::new(validator_history::ValidatorHistoryCache::default());
+    let get_lighthouse_validator_history = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<ValidatorId>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid validator ID".to_string(),
+            ))
+        }))
+        .and(warp::path("history"))
+        .and(warp::query::<eth2::lighthouse::ValidatorHistoryQuery>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            move |validator_id: ValidatorId,
+                  query,
+                  task_spawner: TaskSpawner<T::EthSpec>,
+                  chain: Arc<BeaconChain<T>>| {
+                let cache = validator_history_cache.clone();
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    validator_history::get_validator_history(validator_id, query, chain, &cache)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     // GET lighthouse/analysis/block_packing_efficiency
     let get_lighthouse_block_packing_efficiency = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4583,6 +4840,8 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_beacon_blinded_block)
                 .uor(get_beacon_block_root)
                 .uor(get_blobs)
+                .uor(get_beacon_state_proof)
+                .uor(get_beacon_block_proof)
                 .uor(get_beacon_pool_attestations)
                 .uor(get_beacon_pool_attester_slashings)
                 .uor(get_beacon_pool_proposer_slashings)
@@ -4626,6 +4885,7 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_lighthouse_database_info)
                 .uor(get_lighthouse_block_rewards)
                 .uor(get_lighthouse_attestation_performance)
+                .uor(get_lighthouse_validator_history)
                 .uor(
                     enable(ctx.config.enable_light_client_server)
                         .and(get_beacon_light_client_optimistic_update),
@@ -4638,6 +4898,10 @@ pub fn serve<T: BeaconChainTypes>(
                     enable(ctx.config.enable_light_client_server)
                         .and(get_beacon_light_client_bootstrap),
                 )
+                .uor(
+                    enable(ctx.config.enable_light_client_server)
+                        .and(get_beacon_light_client_updates),
+                )
                 .uor(get_lighthouse_block_packing_efficiency)
                 .uor(get_lighthouse_merge_readiness)
                 .uor(get_events)
@@ -4682,6 +4946,7 @@ pub fn serve<T: BeaconChainTypes>(
                     .uor(post_lighthouse_liveness)
                     .uor(post_lighthouse_database_reconstruct)
                     .uor(post_lighthouse_block_rewards)
+                    .uor(post_lighthouse_simulate_block)
                     .uor(post_lighthouse_ui_validator_metrics)
                     .uor(post_lighthouse_ui_validator_info)
                     .recover(warp_utils::reject::handle_rejection),
@@ -0,0 +1,135 @@
+//! Merkle multiproofs of arbitrary nodes of beacon states and blocks.
+use crate::block_id::BlockId;
+use crate::state_id::StateId;
+use crate::version::add_ssz_content_type_header;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::types::{Accept, GenericResponse, ProofQuery, SszPath};
+use lru::LruCache;
+use parking_lot::Mutex;
+use ssz::Encode;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use types::merkle_multiproof::{generalized_index, ChunkLayers, LayerCache};
+use types::non_zero_usize::new_non_zero_usize;
+use types::{Hash256, MerkleNode, Multiproof};
+use warp::http::Response;
+use warp::hyper::Body;
+use warp::Reply;
+use warp_utils::reject::{beacon_state_error, custom_bad_request, custom_server_error};
+
+/// The maximum number of nodes which may be proven by a single request.
+pub const MAX_PROOF_NODES: usize = 1024;
+/// The number of large lists whose layers are cached. The layers of a registry of one million
+/// validators take 64MB.
+const LAYER_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(4);
+
+/// The layers of large lists of recently proven states, e.g. the validator registry.
+///
+/// The lock is only held to look up or insert layers, which are computed outside of it. Concurrent
+/// proofs of a list which is not yet cached may therefore compute its layers more than once.
+pub struct ProofCache {
+    layers: Mutex<LruCache<Hash256, Arc<ChunkLayers>>>,
+}
+
+impl Default for ProofCache {
+    fn default() -> Self {
+        Self {
+            layers: Mutex::new(LruCache::new(LAYER_CACHE_SIZE)),
+        }
+    }
+}
+
+impl LayerCache for &ProofCache {
+    fn get(&mut self, root: &Hash256) -> Option<Arc<ChunkLayers>> {
+        self.layers.lock().get(root).cloned()
+    }
+
+    fn insert(&mut self, root: Hash256, layers: Arc<ChunkLayers>) {
+        self.layers.lock().put(root, layers);
+    }
+}
+
+/// Compute a multiproof of the nodes of a state selected by `query`.
+///
+/// Returns the proof along with the `execution_optimistic` and `finalized` flags of the state.
+pub fn state_proof<T: BeaconChainTypes>(
+    state_id: StateId,
+    query: ProofQuery,
+    chain: &BeaconChain<T>,
+    cache: &ProofCache,
+) -> Result<(Multiproof, bool, bool), warp::Rejection> {
+    let (mut state, execution_optimistic, finalized) = state_id.state(chain)?;
+    state
+        .apply_pending_mutations()
+        .map_err(beacon_state_error)?;
+    let mut layer_cache = cache;
+    let proof = multiproof(&state, query, &mut layer_cache)?;
+    Ok((proof, execution_optimistic, finalized))
+}
+
+/// Compute a multiproof of the nodes of a block selected by `query`.
+///
+/// Returns the proof along with the `execution_optimistic` and `finalized` flags of the block.
+pub async fn block_proof<T: BeaconChainTypes>(
+    block_id: BlockId,
+    query: ProofQuery,
+    chain: &BeaconChain<T>,
+) -> Result<(Multiproof, bool, bool), warp::Rejection> {
+    let (block, execution_optimistic, finalized) = block_id.full_block(chain).await?;
+    let proof = multiproof(&block.message(), query, &mut ())?;
+    Ok((proof, execution_optimistic, finalized))
+}
+
+/// Respond with `proof` as SSZ bytes or as JSON, depending on the `Accept` header.
+pub fn proof_response(
+    proof: Multiproof,
+    execution_optimistic: bool,
+    finalized: bool,
+    accept_header: Option<Accept>,
+) -> Result<Response<Body>, warp::Rejection> {
+    match accept_header {
+        Some(Accept::Ssz) => Response::builder()
+            .status(200)
+            .body(proof.as_ssz_bytes().into())
+            .map(|res: Response<Body>| add_ssz_content_type_header(res))
+            .map_err(|e| custom_server_error(format!("failed to create response: {}", e))),
+        _ => Ok(warp::reply::json(
+            &GenericResponse::from(proof)
+                .add_execution_optimistic_finalized(execution_optimistic, finalized),
+        )
+        .into_response()),
+    }
+}
+
+/// Prove the generalized indices of `query`, followed by the generalized indices of its paths.
+fn multiproof(
+    node: &dyn MerkleNode,
+    query: ProofQuery,
+    cache: &mut dyn LayerCache,
+) -> Result<Multiproof, warp::Rejection> {
+    let mut gindices = query.gindices.unwrap_or_default();
+    for path in query.paths.unwrap_or_default() {
+        gindices.push(resolve_path(node, &path)?);
+    }
+
+    if gindices.is_empty() {
+        return Err(custom_bad_request(
+            "at least one gindex or path is required".to_string(),
+        ));
+    }
+    if gindices.len() > MAX_PROOF_NODES {
+        return Err(custom_bad_request(format!(
+            "too many nodes requested: {}, maximum: {}",
+            gindices.len(),
+            MAX_PROOF_NODES
+        )));
+    }
+
+    Multiproof::new_with_cache(node, gindices, cache)
+        .map_err(|e| custom_bad_request(format!("unable to compute proof: {:?}", e)))
+}
+
+fn resolve_path(node: &dyn MerkleNode, path: &SszPath) -> Result<u64, warp::Rejection> {
+    generalized_index(node, &path.0)
+        .map_err(|e| custom_bad_request(format!("invalid path {}: {:?}", path, e)))
+}
@@ -0,0 +1,501 @@
+//! Dry-run of a block against an arbitrary state, without importing or publishing the block.
+//!
+//! The validity of the block and its post-state are determined by `per_block_processing`, exactly
+//! as on import. If the block is invalid, it is replayed one operation at a time, reporting and
+//! skipping each operation which fails, so that all problems with a block are found in a single
+//! request.
+use crate::state_id::StateId;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use bytes::Bytes;
+use eth2::lighthouse::{
+    SimulateBlockQuery, SimulatedBlock, SimulatedOperation, SimulatedOperationError,
+    SimulatedValidatorChange,
+};
+use execution_layer::NewPayloadRequest;
+use state_processing::per_block_processing::{
+    is_execution_enabled, process_block_header, process_eth1_data, process_execution_payload,
+    process_operations::{
+        altair_deneb, base, process_attester_slashings, process_bls_to_execution_changes,
+        process_consolidations, process_deposit_requests, process_deposits,
+        process_execution_layer_withdrawal_requests, process_exits, process_proposer_slashings,
+    },
+    process_randao, process_sync_aggregate, process_withdrawals, verify_block_signature,
+};
+use state_processing::{
+    per_block_processing, signature_sets::get_pubkey_from_state,
+    state_advance::complete_state_advance, AllCaches, BlockProcessingError, BlockSignatureStrategy,
+    BlockSignatureVerifier, ConsensusContext, VerifyBlockRoot, VerifySignatures,
+};
+use std::borrow::Cow;
+use std::str::FromStr;
+use std::sync::Arc;
+use types::{
+    BeaconBlock, BeaconBlockBodyRef, BeaconState, ChainSpec, EthSpec, ExecPayload, ForkName,
+    FullPayload, Signature, SignedBeaconBlock,
+};
+use warp_utils::reject::{
+    beacon_chain_error, beacon_state_error, custom_bad_request, custom_server_error,
+};
+
+/// The maximum number of epochs through which the state is advanced to reach the slot of the
+/// block.
+///
+/// Advancing the state requires an epoch transition per epoch, so this bounds the work done for a
+/// single request.
+const MAX_ADVANCE_EPOCHS: u64 = 4;
+
+/// Apply the SSZ encoded block in `block_bytes` to the state identified by `query.state_id`,
+/// reporting the outcome of each step of block processing.
+pub fn simulate_block<T: BeaconChainTypes>(
+    block_bytes: Bytes,
+    fork_name: ForkName,
+    query: SimulateBlockQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<SimulatedBlock, warp::Rejection> {
+    let spec = &chain.spec;
+
+    let block = if query.unsigned {
+        BeaconBlock::from_ssz_bytes_for_fork(&block_bytes, fork_name)
+            .map(|block| SignedBeaconBlock::from_block(block, Signature::empty()))
+    } else {
+        SignedBeaconBlock::from_ssz_bytes_for_fork(&block_bytes, fork_name)
+    }
+    .map_err(|e| custom_bad_request(format!("invalid SSZ: {e:?}")))?;
+    block
+        .fork_name(spec)
+        .map_err(|e| custom_bad_request(format!("inconsistent fork: {e:?}")))?;
+
+    // Blocks for the next slot may be simulated ahead of their proposal.
+    let current_slot = chain.slot().map_err(beacon_chain_error)?;
+    if block.slot() > current_slot + 1 {
+        return Err(custom_bad_request(format!(
+            "block slot {} is later than the next slot {}",
+            block.slot(),
+            current_slot + 1
+        )));
+    }
+
+    let state_id = StateId::from_str(&query.state_id).map_err(custom_bad_request)?;
+    let (mut state, _, _) = state_id.state(&chain)?;
+    if state.slot() > block.slot() {
+        return Err(custom_bad_request(format!(
+            "state slot {} is later than block slot {}",
+            state.slot(),
+            block.slot()
+        )));
+    }
+    let max_advance_slots = MAX_ADVANCE_EPOCHS * T::EthSpec::slots_per_epoch();
+    if block.slot() > state.slot() + max_advance_slots {
+        return Err(custom_bad_request(format!(
+            "block slot {} is more than {} epochs after state slot {}",
+            block.slot(),
+            MAX_ADVANCE_EPOCHS,
+            state.slot()
+        )));
+    }
+    complete_state_advance(&mut state, None, block.slot(), spec)
+        .map_err(|e| custom_server_error(format!("unable to advance state: {e:?}")))?;
+    state
+        .build_all_caches(spec)
+        .map_err(|e| custom_server_error(format!("unable to build caches: {e:?}")))?;
+    state.build_slashings_cache().map_err(beacon_state_error)?;
+
+    let block_root = block.canonical_root();
+    let rewards = chain
+        .compute_block_reward(
+            block.message(),
+            block_root,
+            &state,
+            &mut Default::default(),
+            false,
+        )
+        .ok();
+
+    let mut errors = vec![];
+    let payload_verified =
+        if query.verify_payload && is_execution_enabled(&state, block.message().body()) {
+            Some(verify_payload(&block, &mut errors))
+        } else {
+            None
+        };
+
+    let mut post_state = state.clone();
+    let result = apply_block(
+        &mut post_state,
+        &block,
+        !query.skip_signature_verification,
+        query.unsigned,
+        spec,
+    );
+    let post_state_root = match result {
+        Ok(()) => Some(
+            post_state
+                .update_tree_hash_cache()
+                .map_err(beacon_state_error)?,
+        ),
+        Err(e) => {
+            // Replay the block one step at a time to find every step which fails.
+            let verify_signatures = if query.skip_signature_verification {
+                VerifySignatures::False
+            } else {
+                VerifySignatures::True
+            };
+            let num_errors = errors.len();
+            post_state = state.clone();
+            process_block(
+                &mut post_state,
+                &block,
+                verify_signatures,
+                !query.unsigned,
+                &mut errors,
+                spec,
+            );
+            if errors.len() == num_errors {
+                errors.push(SimulatedOperationError {
+                    operation: SimulatedOperation::Block,
+                    index: None,
+                    reason: format!("{e:?}"),
+                });
+            }
+            None
+        }
+    };
+
+    Ok(SimulatedBlock {
+        slot: block.slot(),
+        block_root,
+        valid: errors.is_empty(),
+        errors,
+        post_state_root,
+        state_root_matches: post_state_root == Some(block.state_root()),
+        payload_verified,
+        rewards,
+        changed_validators: changed_validators(&state, &post_state),
+    })
+}
+
+/// Apply `block` to `state` with `per_block_processing`, as on import.
+///
+/// The proposal signature of an unsigned block is not verified, but its other signatures are.
+fn apply_block<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    block: &SignedBeaconBlock<E>,
+    verify_signatures: bool,
+    unsigned: bool,
+    spec: &ChainSpec,
+) -> Result<(), BlockProcessingError> {
+    let mut ctxt = ConsensusContext::new(block.slot());
+    let block_signature_strategy = if !verify_signatures {
+        BlockSignatureStrategy::NoVerification
+    } else if unsigned {
+        let mut verifier = BlockSignatureVerifier::new(
+            state,
+            |i| get_pubkey_from_state(state, i),
+            |pk_bytes| pk_bytes.decompress().ok().map(Cow::Owned),
+            spec,
+        );
+        verifier
+            .include_all_signatures_except_proposal(block, &mut ctxt)
+            .and_then(|()| verifier.verify())
+            .map_err(|_| BlockProcessingError::BulkSignatureVerificationFailed)?;
+        BlockSignatureStrategy::NoVerification
+    } else {
+        BlockSignatureStrategy::VerifyBulk
+    };
+    per_block_processing(
+        state,
+        block,
+        block_signature_strategy,
+        VerifyBlockRoot::True,
+        &mut ctxt,
+        spec,
+    )
+}
+
+/// Verify the block hash of the execution payload of `block` and the versioned hashes of its
+/// blob transactions, returning `true` if both are valid.
+///
+/// These are the checks made on a payload during optimistic sync. The payload is not sent to the
+/// execution engine, which would store it.
+fn verify_payload<E: EthSpec>(
+    block: &SignedBeaconBlock<E>,
+    errors: &mut Vec<SimulatedOperationError>,
+) -> bool {
+    let result = NewPayloadRequest::try_from(block.message())
+        .map_err(|e| format!("{e:?}"))
+        .and_then(|request| {
+            request
+                .perform_optimistic_sync_verifications()
+                .map_err(|e| format!("{e:?}"))
+        });
+    match result {
+        Ok(()) => true,
+        Err(reason) => {
+            errors.push(SimulatedOperationError {
+                operation: SimulatedOperation::PayloadHashes,
+                index: None,
+                reason,
+            });
+            false
+        }
+    }
+}
+
+/// Apply `signed_block` to `state` in the same order as `per_block_processing`, recording and
+/// skipping each step which fails.
+///
+/// This is only used to diagnose blocks rejected by `per_block_processing`; the resulting state is
+/// not the post-state of any valid block.
+///
+/// Deposits, execution requests and consolidations are processed as lists since their validity
+/// depends on the other elements of the list.
+fn process_block<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    signed_block: &SignedBeaconBlock<E>,
+    verify_signatures: VerifySignatures,
+    verify_proposal: bool,
+    errors: &mut Vec<SimulatedOperationError>,
+    spec: &ChainSpec,
+) {
+    let block = signed_block.message();
+    let body = block.body();
+    let mut ctxt = ConsensusContext::new(block.slot());
+
+    record(
+        errors,
+        SimulatedOperation::BlockHeader,
+        None,
+        process_block_header(
+            state,
+            block.temporary_block_header(),
+            VerifyBlockRoot::True,
+            &mut ctxt,
+            spec,
+        ),
+    );
+    if verify_signatures.is_true() && verify_proposal {
+        record(
+            errors,
+            SimulatedOperation::ProposalSignature,
+            None,
+            verify_block_signature(state, signed_block, &mut ctxt, spec),
+        );
+    }
+
+    if is_execution_enabled(state, body) {
+        if let Some(payload) = record(
+            errors,
+            SimulatedOperation::ExecutionPayload,
+            None,
+            body.execution_payload(),
+        ) {
+            record(
+                errors,
+                SimulatedOperation::Withdrawals,
+                None,
+                process_withdrawals::<E, FullPayload<E>>(state, payload, spec),
+            );
+            record(
+                errors,
+                SimulatedOperation::ExecutionPayload,
+                None,
+                process_execution_payload::<E, FullPayload<E>>(state, body, spec),
+            );
+        }
+    }
+
+    record(
+        errors,
+        SimulatedOperation::Randao,
+        None,
+        process_randao(state, block, verify_signatures, &mut ctxt, spec),
+    );
+    record(
+        errors,
+        SimulatedOperation::Eth1Data,
+        None,
+        process_eth1_data(state, body.eth1_data()),
+    );
+
+    for (i, proposer_slashing) in body.proposer_slashings().iter().enumerate() {
+        record(
+            errors,
+            SimulatedOperation::ProposerSlashing,
+            Some(i),
+            process_proposer_slashings(
+                state,
+                std::slice::from_ref(proposer_slashing),
+                verify_signatures,
+                &mut ctxt,
+                spec,
+            ),
+        );
+    }
+    for (i, attester_slashing) in body.attester_slashings().enumerate() {
+        record(
+            errors,
+            SimulatedOperation::AttesterSlashing,
+            Some(i),
+            process_attester_slashings(
+                state,
+                std::iter::once(attester_slashing),
+                verify_signatures,
+                &mut ctxt,
+                spec,
+            ),
+        );
+    }
+    for (i, attestation) in body.attestations().enumerate() {
+        let result = match body {
+            BeaconBlockBodyRef::Base(_) => base::process_attestations(
+                state,
+                std::iter::once(attestation),
+                verify_signatures,
+                &mut ctxt,
+                spec,
+            ),
+            _ => altair_deneb::process_attestation(
+                state,
+                attestation,
+                i,
+                &mut ctxt,
+                verify_signatures,
+                spec,
+            ),
+        };
+        record(errors, SimulatedOperation::Attestation, Some(i), result);
+    }
+    record(
+        errors,
+        SimulatedOperation::Deposits,
+        None,
+        process_deposits(state, body.deposits(), spec),
+    );
+    for (i, exit) in body.voluntary_exits().iter().enumerate() {
+        record(
+            errors,
+            SimulatedOperation::VoluntaryExit,
+            Some(i),
+            process_exits(state, std::slice::from_ref(exit), verify_signatures, spec),
+        );
+    }
+    if let Ok(bls_to_execution_changes) = body.bls_to_execution_changes() {
+        for (i, change) in bls_to_execution_changes.iter().enumerate() {
+            record(
+                errors,
+                SimulatedOperation::BlsToExecutionChange,
+                Some(i),
+                process_bls_to_execution_changes(
+                    state,
+                    std::slice::from_ref(change),
+                    verify_signatures,
+                    spec,
+                ),
+            );
+        }
+    }
+
+    if state.fork_name_unchecked().electra_enabled() {
+        if let Ok(payload) = body.execution_payload() {
+            if let Ok(Some(requests)) = payload.withdrawal_requests() {
+                record(
+                    errors,
+                    SimulatedOperation::WithdrawalRequests,
+                    None,
+                    process_execution_layer_withdrawal_requests(state, &requests, spec),
+                );
+            }
+            if let Ok(Some(requests)) = payload.deposit_requests() {
+                record(
+                    errors,
+                    SimulatedOperation::DepositRequests,
+                    None,
+                    process_deposit_requests(state, &requests, spec),
+                );
+            }
+        }
+        if let Ok(consolidations) = body.consolidations() {
+            record(
+                errors,
+                SimulatedOperation::Consolidations,
+                None,
+                process_consolidations(state, consolidations, verify_signatures, spec),
+            );
+        }
+    }
+
+    if let Ok(sync_aggregate) = body.sync_aggregate() {
+        record(
+            errors,
+            SimulatedOperation::SyncAggregate,
+            None,
+            process_sync_aggregate(
+                state,
+                sync_aggregate,
+                block.proposer_index(),
+                verify_signatures,
+                spec,
+            ),
+        );
+    }
+}
+
+/// Record the error of a step of block processing, returning its output if it succeeded.
+fn record<T, E: Into<BlockProcessingError>>(
+    errors: &mut Vec<SimulatedOperationError>,
+    operation: SimulatedOperation,
+    index: Option<usize>,
+    result: Result<T, E>,
+) -> Option<T> {
+    match result {
+        Ok(output) => Some(output),
+        Err(e) => {
+            errors.push(SimulatedOperationError {
+                operation,
+                index,
+                reason: error_reason(e.into()),
+            });
+            None
+        }
+    }
+}
+
+/// Describe `error` without the index of the operation, which is always zero for operations
+/// processed one at a time.
+fn error_reason(error: BlockProcessingError) -> String {
+    match error {
+        BlockProcessingError::ProposerSlashingInvalid { reason, .. } => format!("{reason:?}"),
+        BlockProcessingError::AttesterSlashingInvalid { reason, .. } => format!("{reason:?}"),
+        BlockProcessingError::IndexedAttestationInvalid { reason, .. } => format!("{reason:?}"),
+        BlockProcessingError::AttestationInvalid { reason, .. } => format!("{reason:?}"),
+        BlockProcessingError::ExitInvalid { reason, .. } => format!("{reason:?}"),
+        BlockProcessingError::BlsExecutionChangeInvalid { reason, .. } => format!("{reason:?}"),
+        other => format!("{other:?}"),
+    }
+}
+
+/// Return the validators whose record or balance differs between `pre_state` and `post_state`.
+fn changed_validators<E: EthSpec>(
+    pre_state: &BeaconState<E>,
+    post_state: &BeaconState<E>,
+) -> Vec<SimulatedValidatorChange> {
+    post_state
+        .validators()
+        .iter()
+        .zip(post_state.balances().iter())
+        .enumerate()
+        .filter_map(|(index, (post_validator, &post_balance))| {
+            let pre_validator = pre_state.validators().get(index);
+            let pre_balance = pre_state.balances().get(index).copied().unwrap_or(0);
+            (pre_validator != Some(post_validator) || pre_balance != post_balance).then(|| {
+                SimulatedValidatorChange {
+                    index: index as u64,
+                    pre_validator: pre_validator.cloned(),
+                    post_validator: post_validator.clone(),
+                    pre_balance,
+                    post_balance,
+                }
+            })
+        })
+        .collect()
+}
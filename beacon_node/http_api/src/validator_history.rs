@@ -0,0 +1,276 @@
+//! Time series of a validator's balance and status, sampled at the start of each epoch.
+//!
+//! Samples are computed by replaying blocks atop a single state rather than loading the state of
+//! every epoch, which is prohibitively expensive for epochs in the freezer database.
+//!
+//! The response is built in full rather than streamed, so requests are limited to
+//! `MAX_VALIDATOR_HISTORY_EPOCHS` epochs.
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::{
+    ValidatorHistory, ValidatorHistoryQuery, ValidatorHistorySample, MAX_VALIDATOR_HISTORY_EPOCHS,
+};
+use eth2::types::{ValidatorId, ValidatorStatus};
+use lru::LruCache;
+use parking_lot::Mutex;
+use state_processing::{
+    per_epoch_processing::EpochProcessingSummary, BlockReplayError, BlockReplayer,
+};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use types::non_zero_usize::new_non_zero_usize;
+use types::{BeaconState, Epoch, EthSpec, Hash256, SignedBlindedBeaconBlock};
+use warp_utils::reject::{beacon_chain_error, custom_bad_request, custom_server_error};
+
+const BLOCK_ROOT_CHUNK_SIZE: usize = 100;
+/// The number of validators whose finalized samples are cached.
+const CACHE_SIZE: NonZeroUsize = new_non_zero_usize(16);
+
+/// The sample of a validator at an epoch, or `None` if it was not yet part of the registry.
+type Sample = Option<ValidatorHistorySample>;
+
+/// Finalized samples of recently requested validators.
+///
+/// Samples of finalized epochs never change, so repeated and overlapping requests only replay
+/// the blocks of epochs which have not been sampled before.
+pub struct ValidatorHistoryCache {
+    samples: Mutex<LruCache<u64, BTreeMap<Epoch, Sample>>>,
+}
+
+impl Default for ValidatorHistoryCache {
+    fn default() -> Self {
+        Self {
+            samples: Mutex::new(LruCache::new(CACHE_SIZE)),
+        }
+    }
+}
+
+impl ValidatorHistoryCache {
+    /// Return the cached samples of `index` within `start_epoch..=end_epoch`, and the epochs
+    /// within that range which are not cached.
+    fn get(
+        &self,
+        index: u64,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+    ) -> (BTreeMap<Epoch, Sample>, Vec<Epoch>) {
+        let mut samples = self.samples.lock();
+        let cached = samples.get(&index);
+        let mut found = BTreeMap::new();
+        let mut missing = vec![];
+        for epoch in (start_epoch.as_u64()..=end_epoch.as_u64()).map(Epoch::new) {
+            match cached.and_then(|cached| cached.get(&epoch)) {
+                Some(sample) => {
+                    found.insert(epoch, sample.clone());
+                }
+                None => missing.push(epoch),
+            }
+        }
+        (found, missing)
+    }
+
+    /// Cache the samples of `index` which are not later than `finalized_epoch`.
+    fn insert(&self, index: u64, samples: &BTreeMap<Epoch, Sample>, finalized_epoch: Epoch) {
+        let mut cache = self.samples.lock();
+        let cached = cache.get_or_insert_mut(index, BTreeMap::new);
+        for (epoch, sample) in samples.range(..=finalized_epoch) {
+            cached.insert(*epoch, sample.clone());
+        }
+    }
+}
+
+#[derive(Debug)]
+// We don't use the inner values directly, but they're used in the Debug impl.
+enum ValidatorHistoryError {
+    BlockReplay(#[allow(dead_code)] BlockReplayError),
+}
+
+impl From<BlockReplayError> for ValidatorHistoryError {
+    fn from(e: BlockReplayError) -> Self {
+        Self::BlockReplay(e)
+    }
+}
+
+/// Fetch the balance, effective balance and status of a validator in the state at the start of
+/// each epoch of the query.
+///
+/// Epochs at which the validator is not yet part of the registry are omitted.
+pub fn get_validator_history<T: BeaconChainTypes>(
+    validator_id: ValidatorId,
+    query: ValidatorHistoryQuery,
+    chain: Arc<BeaconChain<T>>,
+    cache: &ValidatorHistoryCache,
+) -> Result<ValidatorHistory, warp::Rejection> {
+    let ValidatorHistoryQuery {
+        start_epoch,
+        end_epoch,
+    } = query;
+
+    if start_epoch > end_epoch {
+        return Err(custom_bad_request(format!(
+            "start_epoch must not be larger than end_epoch. start: {}, end: {}",
+            start_epoch, end_epoch
+        )));
+    }
+    if (end_epoch - start_epoch).as_u64() >= MAX_VALIDATOR_HISTORY_EPOCHS {
+        return Err(custom_bad_request(format!(
+            "end_epoch must not exceed start_epoch by {} epochs or more. start: {}, end: {}",
+            MAX_VALIDATOR_HISTORY_EPOCHS, start_epoch, end_epoch
+        )));
+    }
+
+    let cached_head = chain.canonical_head.cached_head();
+    let head_slot = cached_head.head_slot();
+    let finalized_epoch = cached_head.finalized_checkpoint().epoch;
+    if end_epoch.start_slot(T::EthSpec::slots_per_epoch()) > head_slot {
+        return Err(custom_bad_request(format!(
+            "end_epoch must not be later than the epoch of the head. head slot: {}, end: {}",
+            head_slot, end_epoch
+        )));
+    }
+
+    let index = match validator_id {
+        ValidatorId::Index(index) => index,
+        ValidatorId::PublicKey(pubkey) => chain
+            .validator_index(&pubkey)
+            .map_err(beacon_chain_error)?
+            .ok_or_else(|| custom_bad_request(format!("unknown validator: {:?}", pubkey)))?
+            as u64,
+    };
+
+    let (mut samples, missing) = cache.get(index, start_epoch, end_epoch);
+    if let (Some(&first_missing), Some(&last_missing)) = (missing.first(), missing.last()) {
+        let replayed = replay_samples(&chain, index, first_missing, last_missing)?;
+        cache.insert(index, &replayed, finalized_epoch);
+        for (epoch, sample) in replayed {
+            samples.entry(epoch).or_insert(sample);
+        }
+    }
+
+    Ok(ValidatorHistory {
+        index,
+        samples: samples.into_values().flatten().collect(),
+    })
+}
+
+/// Sample the validator at `index` in each epoch of `start_epoch..=end_epoch` by replaying the
+/// blocks of that range atop the state at the start of `start_epoch`.
+fn replay_samples<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    index: u64,
+    start_epoch: Epoch,
+    end_epoch: Epoch,
+) -> Result<BTreeMap<Epoch, Sample>, warp::Rejection> {
+    let spec = &chain.spec;
+    let slots_per_epoch = T::EthSpec::slots_per_epoch();
+    let start_slot = start_epoch.start_slot(slots_per_epoch);
+    let end_slot = end_epoch.start_slot(slots_per_epoch);
+
+    // The first root is that of the latest block at or prior to `start_slot`.
+    let mut block_roots: Vec<Hash256> = chain
+        .forwards_iter_block_roots_until(start_slot, end_slot)
+        .map_err(beacon_chain_error)?
+        .map(|res| res.map(|(root, _)| root))
+        .collect::<Result<Vec<Hash256>, _>>()
+        .map_err(beacon_chain_error)?;
+    block_roots.dedup();
+
+    let first_block_root = block_roots.first().ok_or_else(|| {
+        custom_server_error(
+            "No blocks roots could be loaded. Ensure the beacon node is synced.".to_string(),
+        )
+    })?;
+    let first_block = load_block(chain, first_block_root)?;
+    let state_root = first_block.state_root();
+    let state = chain
+        .get_state(&state_root, Some(first_block.slot()))
+        .and_then(|maybe_state| maybe_state.ok_or(BeaconChainError::MissingBeaconState(state_root)))
+        .map_err(beacon_chain_error)?;
+
+    let mut samples = BTreeMap::new();
+    let in_range = |state: &BeaconState<T::EthSpec>| {
+        state.slot() % slots_per_epoch == 0
+            && state.current_epoch() >= start_epoch
+            && state.current_epoch() <= end_epoch
+    };
+    let sample_state = |state: &BeaconState<T::EthSpec>| {
+        let epoch = state.current_epoch();
+        let validator = state.validators().get(index as usize);
+        let balance = state.balances().get(index as usize);
+        let sample = validator
+            .zip(balance)
+            .map(|(validator, balance)| ValidatorHistorySample {
+                epoch,
+                balance: *balance,
+                effective_balance: validator.effective_balance,
+                status: ValidatorStatus::from_validator(validator, epoch, spec.far_future_epoch),
+            });
+        (epoch, sample)
+    };
+
+    // If a block exists at `start_slot` then the state is the sample of the first epoch.
+    if first_block.slot() == start_slot {
+        let (epoch, sample) = sample_state(&state);
+        samples.insert(epoch, sample);
+    }
+
+    // Sample the state at the start of each epoch after the block at that slot, if any, has been
+    // applied.
+    let samples = RefCell::new(samples);
+    let post_slot_hook = |state: &mut BeaconState<T::EthSpec>,
+                          _summary: Option<EpochProcessingSummary<T::EthSpec>>,
+                          is_skip_slot: bool|
+     -> Result<(), ValidatorHistoryError> {
+        if is_skip_slot && in_range(state) {
+            let (epoch, sample) = sample_state(state);
+            samples.borrow_mut().insert(epoch, sample);
+        }
+        Ok(())
+    };
+    let post_block_hook = |state: &mut BeaconState<T::EthSpec>,
+                           _block: &SignedBlindedBeaconBlock<T::EthSpec>|
+     -> Result<(), ValidatorHistoryError> {
+        if in_range(state) {
+            let (epoch, sample) = sample_state(state);
+            samples.borrow_mut().insert(epoch, sample);
+        }
+        Ok(())
+    };
+
+    let mut replayer = BlockReplayer::new(state, spec)
+        .no_state_root_iter()
+        .no_signature_verification()
+        .minimal_block_root_verification()
+        .post_slot_hook(Box::new(post_slot_hook))
+        .post_block_hook(Box::new(post_block_hook));
+
+    // Iterate through block roots in chunks to reduce load on memory.
+    let num_chunks = block_roots.len().div_ceil(BLOCK_ROOT_CHUNK_SIZE);
+    for (i, block_root_chunk) in block_roots.chunks(BLOCK_ROOT_CHUNK_SIZE).enumerate() {
+        let blocks = block_root_chunk
+            .iter()
+            .map(|root| load_block(chain, root))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Advance through any skipped slots at the end of the range.
+        let target_slot = (i + 1 == num_chunks).then_some(end_slot);
+        replayer = replayer
+            .apply_blocks(blocks, target_slot)
+            .map_err(|e| custom_server_error(format!("{:?}", e)))?;
+    }
+
+    drop(replayer);
+
+    Ok(samples.into_inner())
+}
+
+fn load_block<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    root: &Hash256,
+) -> Result<SignedBlindedBeaconBlock<T::EthSpec>, warp::Rejection> {
+    chain
+        .get_blinded_block(root)
+        .and_then(|maybe_block| maybe_block.ok_or(BeaconChainError::MissingBeaconBlock(*root)))
+        .map_err(beacon_chain_error)
+}
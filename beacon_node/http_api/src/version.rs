@@ -12,6 +12,8 @@ use types::{
 };
 use warp::reply::{self, Reply, Response};
 
+/// Version of endpoints which are not (yet) part of the standard beacon API.
+pub const V0: EndpointVersion = EndpointVersion(0);
 pub const V1: EndpointVersion = EndpointVersion(1);
 pub const V2: EndpointVersion = EndpointVersion(2);
 pub const V3: EndpointVersion = EndpointVersion(3);
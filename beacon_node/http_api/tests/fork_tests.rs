@@ -10,7 +10,7 @@ use http_api::test_utils::*;
 use std::collections::HashSet;
 use types::{
     test_utils::{generate_deterministic_keypair, generate_deterministic_keypairs},
-    Address, ChainSpec, Epoch, EthSpec, Hash256, MinimalEthSpec, Slot,
+    Address, Attestation, ChainSpec, Epoch, EthSpec, ForkName, Hash256, MinimalEthSpec, Slot,
 };
 
 type E = MinimalEthSpec;
@@ -29,6 +29,16 @@ fn capella_spec(capella_fork_epoch: Epoch) -> ChainSpec {
     spec
 }
 
+fn electra_spec(electra_fork_epoch: Epoch) -> ChainSpec {
+    let mut spec = E::default_spec();
+    spec.altair_fork_epoch = Some(Epoch::new(0));
+    spec.bellatrix_fork_epoch = Some(Epoch::new(0));
+    spec.capella_fork_epoch = Some(Epoch::new(0));
+    spec.deneb_fork_epoch = Some(Epoch::new(0));
+    spec.electra_fork_epoch = Some(electra_fork_epoch);
+    spec
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn sync_committee_duties_across_fork() {
     let validator_count = E::sync_committee_size();
@@ -321,11 +331,17 @@ async fn sync_committee_indices_across_fork() {
         Some(next_period_epoch),
         Some(next_period_epoch + 1),
     ] {
-        let committee = client
+        let response = client
             .get_beacon_states_sync_committees(StateId::Head, epoch)
             .await
-            .unwrap()
-            .data;
+            .unwrap();
+        let response_ssz = client
+            .get_beacon_states_sync_committees_ssz(StateId::Head, epoch)
+            .await
+            .unwrap();
+        assert_eq!(response_ssz.as_ref(), Some(&response));
+
+        let committee = response.data;
         assert_eq!(committee.validators.len(), E::sync_committee_size());
 
         assert_eq!(
@@ -548,3 +564,70 @@ async fn bls_to_execution_changes_update_all_around_capella_fork() {
         assert!(validator.has_eth1_withdrawal_credential(&spec));
     }
 }
+
+/// The attestation pool holds attestations of both formats around the Electra fork. Only those of
+/// the current fork are served, so that the SSZ list can be decoded using `Eth-Consensus-Version`.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pool_attestations_ssz_across_electra_fork() {
+    let validator_count = 32;
+    let fork_epoch = Epoch::new(2);
+    let spec = electra_spec(fork_epoch);
+    let tester = InteractiveTester::<E>::new(Some(spec), validator_count).await;
+    let harness = &tester.harness;
+    let client = &tester.client;
+
+    let all_validators = harness.get_all_validators();
+    let fork_slot = fork_epoch.start_slot(E::slots_per_epoch());
+
+    // Fill the op pool with pre-Electra attestations.
+    harness.extend_to_slot(fork_slot - 1).await;
+    assert!(harness
+        .chain
+        .op_pool
+        .get_all_attestations()
+        .iter()
+        .any(|att| matches!(att, Attestation::Base(_))));
+
+    // Add Electra attestations at the fork slot.
+    let mut fork_state = harness
+        .chain
+        .state_at_slot(fork_slot, StateSkipConfig::WithStateRoots)
+        .unwrap();
+    let fork_state_root = fork_state.update_tree_hash_cache().unwrap();
+
+    harness.set_current_slot(fork_slot);
+
+    let unaggregated_attestations = harness
+        .make_attestations(
+            &all_validators,
+            &fork_state,
+            fork_state_root,
+            (*fork_state.get_block_root(fork_slot - 1).unwrap()).into(),
+            fork_slot,
+        )
+        .into_iter()
+        .flat_map(|(atts, _)| atts.into_iter().map(|(att, _)| att))
+        .collect::<Vec<_>>();
+    assert!(!unaggregated_attestations.is_empty());
+    client
+        .post_beacon_pool_attestations_v2(&unaggregated_attestations, ForkName::Electra)
+        .await
+        .unwrap();
+
+    let response = client
+        .get_beacon_pool_attestations_v2::<E>(None, None)
+        .await
+        .unwrap();
+    assert_eq!(response.version, Some(ForkName::Electra));
+    assert!(!response.data.is_empty());
+    assert!(response
+        .data
+        .iter()
+        .all(|att| matches!(att, Attestation::Electra(_))));
+
+    let response_ssz = client
+        .get_beacon_pool_attestations_v2_ssz::<E>(None, None)
+        .await
+        .unwrap();
+    assert_eq!(response_ssz, response);
+}
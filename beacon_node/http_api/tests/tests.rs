@@ -5,6 +5,7 @@ use beacon_chain::{
 };
 use environment::null_logger;
 use eth2::{
+    lighthouse::{SimulateBlockQuery, SimulatedOperation, MAX_VALIDATOR_HISTORY_EPOCHS},
     mixin::{RequestAccept, ResponseForkName, ResponseOptional},
     reqwest::RequestBuilder,
     types::{
@@ -28,9 +29,13 @@ use network::NetworkReceivers;
 use proto_array::ExecutionStatus;
 use sensitive_url::SensitiveUrl;
 use slot_clock::SlotClock;
+use ssz::Encode;
 use state_processing::per_block_processing::get_expected_withdrawals;
 use state_processing::per_slot_processing;
 use state_processing::state_advance::partial_state_advance;
+use state_processing::{
+    per_block_processing, BlockSignatureStrategy, ConsensusContext, VerifyBlockRoot,
+};
 use std::convert::TryInto;
 use std::sync::Arc;
 use tokio::time::Duration;
@@ -78,6 +83,7 @@ struct ApiTester {
     local_enr: Enr,
     external_peer_id: PeerId,
     mock_builder: Option<Arc<MockBuilder<E>>>,
+    extra_mock_builders: Vec<Arc<MockBuilder<E>>>,
 }
 
 struct ApiTesterConfig {
@@ -301,6 +307,7 @@ impl ApiTester {
             local_enr,
             external_peer_id,
             mock_builder,
+            extra_mock_builders: vec![],
         }
     }
 
@@ -389,6 +396,7 @@ impl ApiTester {
             local_enr,
             external_peer_id,
             mock_builder: None,
+            extra_mock_builders: vec![],
         }
     }
 
@@ -427,6 +435,18 @@ impl ApiTester {
         tester
     }
 
+    /// Connect another mock builder relay alongside the default one.
+    pub fn with_additional_mock_builder(mut self) -> Self {
+        let beacon_url = SensitiveUrl::parse(self.client.as_ref()).unwrap();
+        let (mock_builder, mock_builder_server) = self.harness.add_mock_builder(beacon_url);
+        self.harness.runtime.task_executor.spawn(
+            async move { mock_builder_server.await },
+            "mock_builder_server",
+        );
+        self.extra_mock_builders.push(mock_builder);
+        self
+    }
+
     fn skip_slots(self, count: u64) -> Self {
         for _ in 0..count {
             self.chain
@@ -478,6 +498,7 @@ impl ApiTester {
     }
     pub async fn test_beacon_genesis(self) -> Self {
         let result = self.client.get_beacon_genesis().await.unwrap().data;
+        let result_ssz = self.client.get_beacon_genesis_ssz().await.unwrap();
 
         let state = &self.chain.head_snapshot().beacon_state;
         let expected = GenesisData {
@@ -487,6 +508,7 @@ impl ApiTester {
         };
 
         assert_eq!(result, expected);
+        assert_eq!(result_ssz, Some(expected));
 
         self
     }
@@ -754,12 +776,18 @@ impl ApiTester {
 
     pub async fn test_beacon_states_root(self) -> Self {
         for state_id in self.interesting_state_ids() {
-            let result = self
+            let response = self
                 .client
                 .get_beacon_states_root(state_id.0)
                 .await
-                .unwrap()
-                .map(|res| res.data.root);
+                .unwrap();
+            let response_ssz = self
+                .client
+                .get_beacon_states_root_ssz(state_id.0)
+                .await
+                .unwrap();
+            assert_eq!(response_ssz, response, "{:?}", state_id);
+            let result = response.map(|res| res.data.root);
 
             let expected = state_id
                 .root(&self.chain)
@@ -774,12 +802,18 @@ impl ApiTester {
 
     pub async fn test_beacon_states_fork(self) -> Self {
         for state_id in self.interesting_state_ids() {
-            let result = self
+            let response = self
                 .client
                 .get_beacon_states_fork(state_id.0)
                 .await
-                .unwrap()
-                .map(|res| res.data);
+                .unwrap();
+            let response_ssz = self
+                .client
+                .get_beacon_states_fork_ssz(state_id.0)
+                .await
+                .unwrap();
+            assert_eq!(response_ssz, response, "{:?}", state_id);
+            let result = response.map(|res| res.data);
 
             let expected = state_id.fork(&self.chain).ok();
 
@@ -791,12 +825,18 @@ impl ApiTester {
 
     pub async fn test_beacon_states_finality_checkpoints(self) -> Self {
         for state_id in self.interesting_state_ids() {
-            let result = self
+            let response = self
                 .client
                 .get_beacon_states_finality_checkpoints(state_id.0)
                 .await
-                .unwrap()
-                .map(|res| res.data);
+                .unwrap();
+            let response_ssz = self
+                .client
+                .get_beacon_states_finality_checkpoints_ssz(state_id.0)
+                .await
+                .unwrap();
+            assert_eq!(response_ssz, response, "{:?}", state_id);
+            let result = response.map(|res| res.data);
 
             let expected = state_id.state(&self.chain).ok().map(
                 |(state, _execution_optimistic, _finalized)| FinalityCheckpointsData {
@@ -872,15 +912,14 @@ impl ApiTester {
                     })
                     .collect::<Vec<ValidatorId>>();
 
-                let result_index_ids = self
+                let response_index_ids = self
                     .client
                     .get_beacon_states_validator_balances(
                         state_id.0,
                         Some(validator_index_ids.as_slice()),
                     )
                     .await
-                    .unwrap()
-                    .map(|res| res.data);
+                    .unwrap();
                 let result_pubkey_ids = self
                     .client
                     .get_beacon_states_validator_balances(
@@ -890,18 +929,42 @@ impl ApiTester {
                     .await
                     .unwrap()
                     .map(|res| res.data);
+                let response_ssz_index_ids = self
+                    .client
+                    .get_beacon_states_validator_balances_ssz(
+                        state_id.0,
+                        Some(validator_index_ids.as_slice()),
+                    )
+                    .await
+                    .unwrap();
+                let response_post_ssz_pubkey_ids = self
+                    .client
+                    .post_beacon_states_validator_balances_ssz(
+                        state_id.0,
+                        validator_pubkey_ids.clone(),
+                    )
+                    .await
+                    .unwrap();
                 let result_post_index_ids = self
                     .client
                     .post_beacon_states_validator_balances(state_id.0, validator_index_ids)
                     .await
                     .unwrap()
                     .map(|res| res.data);
-                let result_post_pubkey_ids = self
+                let response_post_pubkey_ids = self
                     .client
                     .post_beacon_states_validator_balances(state_id.0, validator_pubkey_ids)
                     .await
-                    .unwrap()
-                    .map(|res| res.data);
+                    .unwrap();
+
+                assert_eq!(response_ssz_index_ids, response_index_ids, "{:?}", state_id);
+                assert_eq!(
+                    response_post_ssz_pubkey_ids, response_post_pubkey_ids,
+                    "{:?}",
+                    state_id
+                );
+                let result_index_ids = response_index_ids.map(|res| res.data);
+                let result_post_pubkey_ids = response_post_pubkey_ids.map(|res| res.data);
 
                 let expected = state_opt.map(|(state, _execution_optimistic, _finalized)| {
                     let mut validators = Vec::with_capacity(validator_indices.len());
@@ -957,7 +1020,7 @@ impl ApiTester {
                         })
                         .collect::<Vec<ValidatorId>>();
 
-                    let result_index_ids = self
+                    let response_index_ids = self
                         .client
                         .get_beacon_states_validators(
                             state_id.0,
@@ -965,8 +1028,7 @@ impl ApiTester {
                             None,
                         )
                         .await
-                        .unwrap()
-                        .map(|res| res.data);
+                        .unwrap();
                     let result_pubkey_ids = self
                         .client
                         .get_beacon_states_validators(
@@ -977,18 +1039,44 @@ impl ApiTester {
                         .await
                         .unwrap()
                         .map(|res| res.data);
+                    let response_ssz_index_ids = self
+                        .client
+                        .get_beacon_states_validators_ssz(
+                            state_id.0,
+                            Some(validator_index_ids.as_slice()),
+                            None,
+                        )
+                        .await
+                        .unwrap();
+                    let post_response_ssz_pubkey_ids = self
+                        .client
+                        .post_beacon_states_validators_ssz(
+                            state_id.0,
+                            Some(validator_pubkey_ids.clone()),
+                            None,
+                        )
+                        .await
+                        .unwrap();
                     let post_result_index_ids = self
                         .client
                         .post_beacon_states_validators(state_id.0, Some(validator_index_ids), None)
                         .await
                         .unwrap()
                         .map(|res| res.data);
-                    let post_result_pubkey_ids = self
+                    let post_response_pubkey_ids = self
                         .client
                         .post_beacon_states_validators(state_id.0, Some(validator_pubkey_ids), None)
                         .await
-                        .unwrap()
-                        .map(|res| res.data);
+                        .unwrap();
+
+                    assert_eq!(response_ssz_index_ids, response_index_ids, "{:?}", state_id);
+                    assert_eq!(
+                        post_response_ssz_pubkey_ids, post_response_pubkey_ids,
+                        "{:?}",
+                        state_id
+                    );
+                    let result_index_ids = response_index_ids.map(|res| res.data);
+                    let post_result_pubkey_ids = post_response_pubkey_ids.map(|res| res.data);
 
                     let expected = state_opt.map(|state| {
                         let epoch = state.current_epoch();
@@ -1051,12 +1139,18 @@ impl ApiTester {
                 ];
 
                 for validator_id in validator_ids {
-                    let result = self
+                    let response = self
                         .client
                         .get_beacon_states_validator_id(state_id.0, validator_id)
                         .await
-                        .unwrap()
-                        .map(|res| res.data);
+                        .unwrap();
+                    let response_ssz = self
+                        .client
+                        .get_beacon_states_validator_id_ssz(state_id.0, validator_id)
+                        .await
+                        .unwrap();
+                    assert_eq!(response_ssz, response, "{:?}, {:?}", state_id, validator_id);
+                    let result = response.map(|res| res.data);
 
                     if result.is_none() && state_opt.is_none() {
                         continue;
@@ -1096,12 +1190,18 @@ impl ApiTester {
                 .map(|(state, _execution_optimistic, _finalized)| state);
 
             let epoch_opt = state_opt.as_ref().map(|state| state.current_epoch());
-            let results = self
+            let response = self
                 .client
                 .get_beacon_states_committees(state_id.0, None, None, epoch_opt)
                 .await
-                .unwrap()
-                .map(|res| res.data);
+                .unwrap();
+            let response_ssz = self
+                .client
+                .get_beacon_states_committees_ssz(state_id.0, None, None, epoch_opt)
+                .await
+                .unwrap();
+            assert_eq!(response_ssz, response, "{}", state_id);
+            let results = response.map(|res| res.data);
 
             if results.is_none() && state_opt.is_none() {
                 continue;
@@ -1143,12 +1243,18 @@ impl ApiTester {
                 .map(|(state, _execution_optimistic, _finalized)| state);
 
             let epoch_opt = state_opt.as_ref().map(|state| state.current_epoch());
-            let result = self
+            let response = self
                 .client
                 .get_beacon_states_randao(state_id.0, epoch_opt)
                 .await
-                .unwrap()
-                .map(|res| res.data);
+                .unwrap();
+            let response_ssz = self
+                .client
+                .get_beacon_states_randao_ssz(state_id.0, epoch_opt)
+                .await
+                .unwrap();
+            assert_eq!(response_ssz, response, "{}", state_id);
+            let result = response.map(|res| res.data);
 
             if result.is_none() && state_opt.is_none() {
                 continue;
@@ -1165,16 +1271,94 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_beacon_states_proof(self) -> Self {
+        let paths = [
+            "slot",
+            "finalized_checkpoint.root",
+            "validators.1.effective_balance",
+            "validators.__len__",
+            "balances.5",
+        ]
+        .iter()
+        .map(|path| path.parse::<SszPath>().unwrap())
+        .collect::<Vec<_>>();
+        // The `fork` field, which is the fourth of the 32 leaves of states prior to Electra.
+        let gindices = [35];
+
+        for state_id in self.interesting_state_ids() {
+            let state_opt = state_id
+                .state(&self.chain)
+                .ok()
+                .map(|(state, _execution_optimistic, _finalized)| state);
+
+            let json_result = self
+                .client
+                .get_beacon_states_proof(state_id.0, &gindices, &paths)
+                .await
+                .unwrap()
+                .map(|res| res.data);
+            let ssz_result = self
+                .client
+                .get_beacon_states_proof_ssz(state_id.0, &gindices, &paths)
+                .await
+                .unwrap();
+
+            let Some(mut state) = state_opt else {
+                assert_eq!(json_result, None);
+                assert_eq!(ssz_result, None);
+                continue;
+            };
+            let proof = json_result.unwrap();
+            assert_eq!(ssz_result, Some(proof.clone()));
+            assert!(proof.verify(state.canonical_root().unwrap()).unwrap());
+
+            // Check the leaves against roots computed from the fields themselves.
+            let validator = state.validators().get(1).unwrap();
+            let balances_chunk = Hash256::from_slice(
+                &(4..8)
+                    .flat_map(|i| state.balances().get(i).unwrap().to_le_bytes())
+                    .collect::<Vec<_>>(),
+            );
+            assert_eq!(
+                proof.leaves,
+                vec![
+                    state.fork().tree_hash_root(),
+                    state.slot().tree_hash_root(),
+                    state.finalized_checkpoint().root,
+                    validator.effective_balance.tree_hash_root(),
+                    (state.validators().len() as u64).tree_hash_root(),
+                    balances_chunk,
+                ]
+            );
+        }
+
+        // Nodes within a leaf cannot be proven.
+        let path = "validators.0.pubkey.0".parse::<SszPath>().unwrap();
+        let result = self
+            .client
+            .get_beacon_states_proof(CoreStateId::Head, &[], &[path])
+            .await;
+        assert_eq!(result.unwrap_err().status().unwrap(), 400);
+
+        self
+    }
+
     pub async fn test_beacon_headers_all_slots(self) -> Self {
         for slot in 0..CHAIN_LENGTH {
             let slot = Slot::from(slot);
 
-            let result = self
+            let response = self
                 .client
                 .get_beacon_headers(Some(slot), None)
                 .await
-                .unwrap()
-                .map(|res| res.data);
+                .unwrap();
+            let response_ssz = self
+                .client
+                .get_beacon_headers_ssz(Some(slot), None)
+                .await
+                .unwrap();
+            assert_eq!(response_ssz, response, "slot {:?}", slot);
+            let result = response.map(|res| res.data);
 
             let root = self
                 .chain
@@ -1240,12 +1424,18 @@ impl ApiTester {
 
     pub async fn test_beacon_headers_block_id(self) -> Self {
         for block_id in self.interesting_block_ids() {
-            let result = self
+            let response = self
                 .client
                 .get_beacon_headers_block_id(block_id.0)
                 .await
-                .unwrap()
-                .map(|res| res.data);
+                .unwrap();
+            let response_ssz = self
+                .client
+                .get_beacon_headers_block_id_ssz(block_id.0)
+                .await
+                .unwrap();
+            assert_eq!(response_ssz, response, "{:?}", block_id);
+            let result = response.map(|res| res.data);
 
             let block_root_opt = block_id
                 .root(&self.chain)
@@ -1300,12 +1490,18 @@ impl ApiTester {
 
     pub async fn test_beacon_blocks_root(self) -> Self {
         for block_id in self.interesting_block_ids() {
-            let result = self
+            let response = self
                 .client
                 .get_beacon_blocks_root(block_id.0)
                 .await
-                .unwrap()
-                .map(|res| res.data.root);
+                .unwrap();
+            let response_ssz = self
+                .client
+                .get_beacon_blocks_root_ssz(block_id.0)
+                .await
+                .unwrap();
+            assert_eq!(response_ssz, response, "{:?}", block_id);
+            let result = response.map(|res| res.data.root);
 
             let expected = block_id
                 .root(&self.chain)
@@ -1667,14 +1863,79 @@ impl ApiTester {
         self
     }
 
-    pub async fn test_beacon_blocks_attestations(self) -> Self {
+    pub async fn test_beacon_blocks_proof(self) -> Self {
+        let paths = [
+            "parent_root",
+            "body.eth1_data.deposit_root",
+            "body.attestations.__len__",
+            "body.attestations.0.data.target.epoch",
+        ]
+        .iter()
+        .map(|path| path.parse::<SszPath>().unwrap())
+        .collect::<Vec<_>>();
+
         for block_id in self.interesting_block_ids() {
-            let result = self
+            let block_opt = block_id
+                .full_block(&self.chain)
+                .await
+                .ok()
+                .map(|(block, _execution_optimistic, _finalized)| block);
+
+            // Only blocks with attestations have a node at the last path.
+            let paths = match &block_opt {
+                Some(block) if block.message().body().attestations_len() == 0 => &paths[..3],
+                _ => &paths[..],
+            };
+            let json_result = self
                 .client
-                .get_beacon_blocks_attestations_v2(block_id.0)
+                .get_beacon_blocks_proof(block_id.0, &[], paths)
                 .await
                 .unwrap()
                 .map(|res| res.data);
+            let ssz_result = self
+                .client
+                .get_beacon_blocks_proof_ssz(block_id.0, &[], paths)
+                .await
+                .unwrap();
+
+            let Some(block) = block_opt else {
+                assert_eq!(json_result, None);
+                assert_eq!(ssz_result, None);
+                continue;
+            };
+            let proof = json_result.unwrap();
+            assert_eq!(ssz_result, Some(proof.clone()));
+            assert!(proof.verify(block.canonical_root()).unwrap());
+
+            let body = block.message().body();
+            let mut expected = vec![
+                block.parent_root(),
+                body.eth1_data().deposit_root,
+                (body.attestations_len() as u64).tree_hash_root(),
+            ];
+            if let Some(attestation) = body.attestations().next() {
+                expected.push(attestation.data().target.epoch.tree_hash_root());
+            }
+            assert_eq!(proof.leaves, expected);
+        }
+
+        self
+    }
+
+    pub async fn test_beacon_blocks_attestations(self) -> Self {
+        for block_id in self.interesting_block_ids() {
+            let response = self
+                .client
+                .get_beacon_blocks_attestations_v2(block_id.0)
+                .await
+                .unwrap();
+            let response_ssz = self
+                .client
+                .get_beacon_blocks_attestations_v2_ssz::<E>(block_id.0)
+                .await
+                .unwrap();
+            assert_eq!(response_ssz, response, "{:?}", block_id);
+            let result = response.map(|res| res.data);
 
             let expected = block_id.full_block(&self.chain).await.ok().map(
                 |(block, _execution_optimistic, _finalized)| {
@@ -1872,6 +2133,43 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_beacon_light_client_updates(self) -> Self {
+        let chain = self.chain.clone();
+        tokio::task::spawn_blocking(move || chain.backfill_light_client_updates())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let current_period = self
+            .chain
+            .epoch()
+            .unwrap()
+            .sync_committee_period(&self.chain.spec)
+            .unwrap();
+
+        let result = match self
+            .client
+            .get_beacon_light_client_updates::<E>(0, current_period + 1)
+            .await
+        {
+            Ok(result) => result
+                .unwrap()
+                .into_iter()
+                .map(|res| res.data)
+                .collect::<Vec<_>>(),
+            Err(e) => panic!("query failed incorrectly: {e:?}"),
+        };
+
+        let expected = self
+            .chain
+            .get_light_client_updates(0, current_period + 1)
+            .unwrap();
+        assert_eq!(result, expected);
+        assert!(!result.is_empty());
+
+        self
+    }
+
     pub async fn test_get_beacon_pool_attestations(self) -> Self {
         let result = self
             .client
@@ -1885,13 +2183,19 @@ impl ApiTester {
 
         assert_eq!(result, expected);
 
-        let result = self
+        let response = self
             .client
             .get_beacon_pool_attestations_v2(None, None)
             .await
-            .unwrap()
-            .data;
-        assert_eq!(result, expected);
+            .unwrap();
+        assert_eq!(response.data, expected);
+
+        let response_ssz = self
+            .client
+            .get_beacon_pool_attestations_v2_ssz::<E>(None, None)
+            .await
+            .unwrap();
+        assert_eq!(response_ssz, response);
 
         self
     }
@@ -1992,13 +2296,19 @@ impl ApiTester {
 
         assert_eq!(result, expected);
 
-        let result = self
+        let response = self
             .client
             .get_beacon_pool_attester_slashings_v2()
             .await
-            .unwrap()
-            .data;
-        assert_eq!(result, expected);
+            .unwrap();
+        assert_eq!(response.data, expected);
+
+        let response_ssz = self
+            .client
+            .get_beacon_pool_attester_slashings_v2_ssz::<E>()
+            .await
+            .unwrap();
+        assert_eq!(response_ssz, response);
 
         self
     }
@@ -2046,6 +2356,13 @@ impl ApiTester {
 
         assert_eq!(result, expected);
 
+        let result = self
+            .client
+            .get_beacon_pool_proposer_slashings_ssz()
+            .await
+            .unwrap();
+        assert_eq!(result, expected);
+
         self
     }
 
@@ -2092,6 +2409,85 @@ impl ApiTester {
 
         assert_eq!(result, expected);
 
+        let result = self
+            .client
+            .get_beacon_pool_voluntary_exits_ssz()
+            .await
+            .unwrap();
+        assert_eq!(result, expected);
+
+        self
+    }
+
+    pub async fn test_get_beacon_pool_bls_to_execution_changes(self) -> Self {
+        let result = self
+            .client
+            .get_beacon_pool_bls_to_execution_changes()
+            .await
+            .unwrap()
+            .data;
+
+        let expected = self.chain.op_pool.get_all_bls_to_execution_changes();
+
+        assert_eq!(result, expected);
+
+        let result = self
+            .client
+            .get_beacon_pool_bls_to_execution_changes_ssz()
+            .await
+            .unwrap();
+        assert_eq!(result, expected);
+
+        self
+    }
+
+    pub async fn test_beacon_rewards(self) -> Self {
+        let head = self.chain.head_snapshot();
+        let block_id = CoreBlockId::Root(head.beacon_block_root);
+
+        let block_rewards = self
+            .client
+            .get_beacon_rewards_blocks(block_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        assert_eq!(
+            block_rewards.proposer_index,
+            head.beacon_block.message().proposer_index()
+        );
+
+        // The rewards have no standard SSZ encoding, so an SSZ-only request is not acceptable.
+        match self
+            .client
+            .get_beacon_rewards_blocks_with_ssz_accept_header(block_id)
+            .await
+        {
+            Err(ServerMessage(error)) => assert_eq!(error.code, 406),
+            other => panic!("should error with not acceptable response: {:?}", other),
+        }
+
+        let validators = vec![ValidatorId::Index(0), ValidatorId::Index(1)];
+        let epoch = head.beacon_state.current_epoch() - 1;
+        let attestation_rewards = self
+            .client
+            .post_beacon_rewards_attestations(epoch, &validators)
+            .await
+            .unwrap()
+            .data;
+        assert!(!attestation_rewards.total_rewards.is_empty());
+
+        if head.beacon_state.fork_name_unchecked().altair_enabled() {
+            let sync_committee_rewards = self
+                .client
+                .post_beacon_rewards_sync_committee(block_id, &[])
+                .await
+                .unwrap()
+                .unwrap()
+                .data;
+            assert!(!sync_committee_rewards.is_empty());
+        }
+
         self
     }
 
@@ -5056,6 +5452,90 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_builder_highest_relay_bid_chosen(self) -> Self {
+        let low_bid_fee_recipient = Address::repeat_byte(0x11);
+        let high_bid_fee_recipient = Address::repeat_byte(0x22);
+
+        let first_builder = self.mock_builder.as_ref().unwrap();
+        first_builder.add_operation(Operation::Value(Uint256::from(
+            DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI + 1,
+        )));
+        first_builder.add_operation(Operation::FeeRecipient(low_bid_fee_recipient));
+
+        let second_builder = &self.extra_mock_builders[0];
+        second_builder.add_operation(Operation::Value(Uint256::from(
+            DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI + 2,
+        )));
+        second_builder.add_operation(Operation::FeeRecipient(high_bid_fee_recipient));
+
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+
+        let (_, randao_reveal) = self.get_test_randao(slot, epoch).await;
+
+        let payload: BlindedPayload<E> = self
+            .client
+            .get_validator_blinded_blocks::<E>(slot, &randao_reveal, None)
+            .await
+            .unwrap()
+            .data
+            .body()
+            .execution_payload()
+            .unwrap()
+            .into();
+
+        // The most valuable bid should have been chosen.
+        assert_eq!(payload.fee_recipient(), high_bid_fee_recipient);
+        self
+    }
+
+    pub async fn test_builder_valid_relay_bid_chosen_when_other_relay_invalid(self) -> Self {
+        let valid_bid_fee_recipient = Address::repeat_byte(0x33);
+        let invalid_bid_fee_recipient = Address::repeat_byte(0x44);
+
+        let first_builder = self.mock_builder.as_ref().unwrap();
+        first_builder.add_operation(Operation::Value(Uint256::from(
+            DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI + 1,
+        )));
+        first_builder.add_operation(Operation::FeeRecipient(valid_bid_fee_recipient));
+
+        // The second relay bids more, but with an invalid signature.
+        let second_builder = &self.extra_mock_builders[0];
+        second_builder.add_operation(Operation::Value(Uint256::from(
+            DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI + 2,
+        )));
+        second_builder.add_operation(Operation::FeeRecipient(invalid_bid_fee_recipient));
+        second_builder.invalid_signatures();
+
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+
+        let (_, randao_reveal) = self.get_test_randao(slot, epoch).await;
+
+        let payload: BlindedPayload<E> = self
+            .client
+            .get_validator_blinded_blocks::<E>(slot, &randao_reveal, None)
+            .await
+            .unwrap()
+            .data
+            .body()
+            .execution_payload()
+            .unwrap()
+            .into();
+
+        assert_eq!(payload.fee_recipient(), valid_bid_fee_recipient);
+
+        // This cache should not be populated because fallback should not have been used.
+        assert!(self
+            .chain
+            .execution_layer
+            .as_ref()
+            .unwrap()
+            .get_payload_by_root(&payload.tree_hash_root())
+            .is_none());
+        self
+    }
+
     pub async fn test_local_payload_chosen_when_equally_profitable(self) -> Self {
         // Mutate value.
         self.mock_builder
@@ -5368,6 +5848,318 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_lighthouse_analysis_simulate_block(self) -> Self {
+        let block = self.chain.head_beacon_block();
+        let fork_name = block.fork_name_unchecked();
+        let parent_state_root = self
+            .chain
+            .get_blinded_block(&block.parent_root())
+            .unwrap()
+            .unwrap()
+            .state_root();
+        let query = |unsigned, skip_signature_verification| SimulateBlockQuery {
+            state_id: format!("{:?}", parent_state_root),
+            unsigned,
+            skip_signature_verification,
+            verify_payload: false,
+        };
+
+        // The canonical head is valid atop the state of its parent, whether or not it is signed.
+        let simulated = self
+            .client
+            .post_lighthouse_analysis_simulate_block(
+                block.as_ssz_bytes(),
+                fork_name,
+                &query(false, false),
+            )
+            .await
+            .unwrap();
+        assert!(simulated.valid, "{:?}", simulated.errors);
+        assert_eq!(simulated.slot, block.slot());
+        assert_eq!(simulated.block_root, block.canonical_root());
+        assert_eq!(simulated.post_state_root, Some(block.state_root()));
+        assert!(simulated.state_root_matches);
+        assert!(simulated.rewards.is_some());
+
+        let unsigned = self
+            .client
+            .post_lighthouse_analysis_simulate_block(
+                block.message().as_ssz_bytes(),
+                fork_name,
+                &query(true, false),
+            )
+            .await
+            .unwrap();
+        assert_eq!(unsigned, simulated);
+
+        // An invalid proposal signature is reported unless signatures are not verified.
+        let invalid_signature =
+            SignedBeaconBlock::from_block(block.message().clone(), Signature::empty());
+        let simulated = self
+            .client
+            .post_lighthouse_analysis_simulate_block(
+                invalid_signature.as_ssz_bytes(),
+                fork_name,
+                &query(false, false),
+            )
+            .await
+            .unwrap();
+        assert!(!simulated.valid);
+        assert_eq!(simulated.errors.len(), 1);
+        assert_eq!(
+            simulated.errors[0].operation,
+            SimulatedOperation::ProposalSignature
+        );
+        assert_eq!(simulated.post_state_root, None);
+        assert!(!simulated.state_root_matches);
+
+        let simulated = self
+            .client
+            .post_lighthouse_analysis_simulate_block(
+                invalid_signature.as_ssz_bytes(),
+                fork_name,
+                &query(false, true),
+            )
+            .await
+            .unwrap();
+        assert!(simulated.valid);
+
+        // The block cannot be applied to its own post-state.
+        let result = self
+            .client
+            .post_lighthouse_analysis_simulate_block(
+                block.as_ssz_bytes(),
+                fork_name,
+                &SimulateBlockQuery {
+                    state_id: format!("{:?}", block.state_root()),
+                    ..query(false, false)
+                },
+            )
+            .await
+            .unwrap();
+        assert!(result
+            .errors
+            .iter()
+            .any(|error| error.operation == SimulatedOperation::BlockHeader));
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_analysis_simulate_block_with_operations(self) -> Self {
+        let head = self.chain.head_snapshot();
+        let spec = &self.chain.spec;
+        let slot = head.beacon_state.slot() + 1;
+        let query = SimulateBlockQuery {
+            state_id: format!("{:?}", head.beacon_block.state_root()),
+            unsigned: false,
+            skip_signature_verification: false,
+            verify_payload: false,
+        };
+
+        // Use the last validator so that the slashing does not conflict with any in the op pool.
+        let slashed_index = head
+            .beacon_state
+            .validators()
+            .iter()
+            .rposition(|validator| !validator.slashed)
+            .unwrap() as u64;
+        let proposer_slashing = self.harness.make_proposer_slashing(slashed_index);
+
+        let ((block, _), pre_state) = self
+            .harness
+            .make_block_with_modifier(head.beacon_state.clone(), slot, |block| {
+                block
+                    .body_mut()
+                    .proposer_slashings_mut()
+                    .push(proposer_slashing.clone())
+                    .unwrap();
+            })
+            .await;
+
+        // The post-state is the same as that of `per_block_processing`.
+        let mut expected_state = pre_state.clone();
+        expected_state.build_all_caches(spec).unwrap();
+        per_block_processing(
+            &mut expected_state,
+            &*block,
+            BlockSignatureStrategy::VerifyIndividual,
+            VerifyBlockRoot::True,
+            &mut ConsensusContext::new(slot),
+            spec,
+        )
+        .unwrap();
+        let expected_state_root = expected_state.update_tree_hash_cache().unwrap();
+
+        let simulated = self
+            .client
+            .post_lighthouse_analysis_simulate_block(
+                block.as_ssz_bytes(),
+                block.fork_name_unchecked(),
+                &query,
+            )
+            .await
+            .unwrap();
+        assert!(simulated.valid, "{:?}", simulated.errors);
+        assert_eq!(simulated.post_state_root, Some(expected_state_root));
+
+        let slashed_change = simulated
+            .changed_validators
+            .iter()
+            .find(|change| change.index == slashed_index)
+            .expect("slashed validator should be changed");
+        assert!(!slashed_change.pre_validator.as_ref().unwrap().slashed);
+        assert!(slashed_change.post_validator.slashed);
+        assert!(slashed_change.post_balance < slashed_change.pre_balance);
+        for change in &simulated.changed_validators {
+            let index = change.index as usize;
+            assert_eq!(
+                change.pre_validator.as_ref(),
+                pre_state.validators().get(index)
+            );
+            assert_eq!(change.post_validator, expected_state.validators()[index]);
+            assert_eq!(change.post_balance, expected_state.balances()[index]);
+        }
+
+        // A repeated slashing is reported at its index and skipped, while the first is applied.
+        let ((block, _), _) = self
+            .harness
+            .make_block_with_modifier(head.beacon_state.clone(), slot, |block| {
+                let proposer_slashings = block.body_mut().proposer_slashings_mut();
+                proposer_slashings.push(proposer_slashing.clone()).unwrap();
+                proposer_slashings.push(proposer_slashing).unwrap();
+            })
+            .await;
+        let duplicate_index = block.message().body().proposer_slashings().len() - 1;
+
+        let simulated = self
+            .client
+            .post_lighthouse_analysis_simulate_block(
+                block.as_ssz_bytes(),
+                block.fork_name_unchecked(),
+                &query,
+            )
+            .await
+            .unwrap();
+        assert!(!simulated.valid);
+        assert_eq!(simulated.errors.len(), 1, "{:?}", simulated.errors);
+        assert_eq!(
+            simulated.errors[0].operation,
+            SimulatedOperation::ProposerSlashing
+        );
+        assert_eq!(simulated.errors[0].index, Some(duplicate_index));
+        assert_eq!(simulated.post_state_root, None);
+        assert!(simulated
+            .changed_validators
+            .iter()
+            .any(|change| change.index == slashed_index && change.post_validator.slashed));
+
+        // Blocks which require advancing the state through too many epochs are rejected.
+        let genesis_query = SimulateBlockQuery {
+            state_id: "genesis".to_string(),
+            ..query.clone()
+        };
+        let result = self
+            .client
+            .post_lighthouse_analysis_simulate_block(
+                block.as_ssz_bytes(),
+                block.fork_name_unchecked(),
+                &genesis_query,
+            )
+            .await;
+        assert_eq!(result.unwrap_err().status().map(Into::into), Some(400));
+
+        // As are blocks later than the next slot.
+        let mut future_block = block.message().clone();
+        *future_block.slot_mut() = self.chain.slot().unwrap() + 2;
+        let unsigned_query = SimulateBlockQuery {
+            unsigned: true,
+            ..query
+        };
+        let result = self
+            .client
+            .post_lighthouse_analysis_simulate_block(
+                future_block.as_ssz_bytes(),
+                block.fork_name_unchecked(),
+                &unsigned_query,
+            )
+            .await;
+        assert_eq!(result.unwrap_err().status().map(Into::into), Some(400));
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_validator_history(self) -> Self {
+        let end_epoch = self
+            .chain
+            .head_beacon_block()
+            .slot()
+            .epoch(E::slots_per_epoch());
+        let validator_ids = [
+            ValidatorId::Index(0),
+            ValidatorId::PublicKey(self.validator_keypairs()[1].pk.compress()),
+        ];
+
+        for (index, validator_id) in validator_ids.into_iter().enumerate() {
+            let history = self
+                .client
+                .get_lighthouse_validator_history(validator_id.clone(), Epoch::new(0), end_epoch)
+                .await
+                .unwrap()
+                .data;
+
+            assert_eq!(history.index, index as u64);
+            assert_eq!(history.samples.len() as u64, end_epoch.as_u64() + 1);
+            for sample in &history.samples {
+                let state = self
+                    .chain
+                    .state_at_slot(
+                        sample.epoch.start_slot(E::slots_per_epoch()),
+                        StateSkipConfig::WithStateRoots,
+                    )
+                    .unwrap();
+                let validator = state.validators().get(index).unwrap();
+                assert_eq!(sample.balance, *state.balances().get(index).unwrap());
+                assert_eq!(sample.effective_balance, validator.effective_balance);
+                assert_eq!(
+                    sample.status,
+                    ValidatorStatus::from_validator(
+                        validator,
+                        sample.epoch,
+                        self.chain.spec.far_future_epoch
+                    )
+                );
+            }
+
+            // Samples of finalized epochs are now cached, the others are replayed again.
+            let overlapping = self
+                .client
+                .get_lighthouse_validator_history(validator_id, Epoch::new(1), end_epoch)
+                .await
+                .unwrap()
+                .data;
+            assert_eq!(overlapping.samples, history.samples[1..]);
+        }
+
+        let result = self
+            .client
+            .get_lighthouse_validator_history(ValidatorId::Index(0), end_epoch, Epoch::new(0))
+            .await;
+        assert_eq!(result.unwrap_err().status().unwrap(), 400);
+
+        // Ranges longer than the cap are rejected before any blocks are replayed.
+        let result = self
+            .client
+            .get_lighthouse_validator_history(
+                ValidatorId::Index(0),
+                Epoch::new(0),
+                Epoch::new(MAX_VALIDATOR_HISTORY_EPOCHS),
+            )
+            .await;
+        assert_eq!(result.unwrap_err().status().unwrap(), 400);
+
+        self
+    }
+
     pub async fn test_get_lighthouse_eth1_syncing(self) -> Self {
         self.client.get_lighthouse_eth1_syncing().await.unwrap();
 
@@ -5968,6 +6760,8 @@ async fn beacon_get() {
         .await
         .test_beacon_states_randao()
         .await
+        .test_beacon_states_proof()
+        .await
         .test_beacon_headers_all_slots()
         .await
         .test_beacon_headers_all_parents()
@@ -5980,6 +6774,8 @@ async fn beacon_get() {
         .await
         .test_beacon_blocks_attestations()
         .await
+        .test_beacon_blocks_proof()
+        .await
         .test_beacon_blocks_root()
         .await
         .test_get_beacon_pool_attestations()
@@ -5989,6 +6785,23 @@ async fn beacon_get() {
         .test_get_beacon_pool_proposer_slashings()
         .await
         .test_get_beacon_pool_voluntary_exits()
+        .await
+        .test_get_beacon_pool_bls_to_execution_changes()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn beacon_rewards() {
+    ApiTester::new().await.test_beacon_rewards().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn beacon_rewards_altair() {
+    let mut config = ApiTesterConfig::default();
+    config.spec.altair_fork_epoch = Some(Epoch::new(0));
+    ApiTester::new_from_config(config)
+        .await
+        .test_beacon_rewards()
         .await;
 }
 
@@ -6207,6 +7020,18 @@ async fn get_light_client_finality_update() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_light_client_updates() {
+    let config = ApiTesterConfig {
+        spec: ForkName::Altair.make_genesis_spec(E::default_spec()),
+        ..<_>::default()
+    };
+    ApiTester::new_from_config(config)
+        .await
+        .test_get_beacon_light_client_updates()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn get_validator_duties_early() {
     ApiTester::new()
@@ -6742,6 +7567,20 @@ async fn builder_payload_chosen_by_profit_v3() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_multiple_relays() {
+    ApiTester::new_mev_tester_default_payload_value()
+        .await
+        .with_additional_mock_builder()
+        // Register again so that the registrations are forwarded to both relays.
+        .test_post_validator_register_validator()
+        .await
+        .test_builder_highest_relay_bid_chosen()
+        .await
+        .test_builder_valid_relay_bid_chosen_when_other_relay_invalid()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn builder_works_post_capella() {
     let mut config = ApiTesterConfig {
@@ -6826,6 +7665,12 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_validator_inclusion_global()
         .await
+        .test_get_lighthouse_validator_history()
+        .await
+        .test_post_lighthouse_analysis_simulate_block()
+        .await
+        .test_post_lighthouse_analysis_simulate_block_with_operations()
+        .await
         .test_get_lighthouse_eth1_syncing()
         .await
         .test_get_lighthouse_eth1_block_cache()
@@ -103,6 +103,10 @@ pub struct Config {
     /// Subscribe to all subnets for the duration of the runtime.
     pub subscribe_all_subnets: bool,
 
+    /// Subscribe to and custody all data column subnets for the duration of the runtime
+    /// (supernode mode).
+    pub subscribe_all_data_column_subnets: bool,
+
     /// Import/aggregate all attestations received on subscribed subnets for the duration of the
     /// runtime.
     pub import_all_attestations: bool,
@@ -339,6 +343,7 @@ impl Default for Config {
             network_load: 4,
             private: false,
             subscribe_all_subnets: false,
+            subscribe_all_data_column_subnets: false,
             import_all_attestations: false,
             shutdown_after_sync: false,
             topics: Vec::new(),
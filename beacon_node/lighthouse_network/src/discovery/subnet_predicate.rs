@@ -29,8 +29,7 @@ where
         let sync_committee_bitfield: Result<EnrSyncCommitteeBitfield<E>, _> =
             enr.sync_committee_bitfield::<E>();
 
-        // TODO(das): compute from enr
-        let custody_subnet_count = spec.custody_requirement;
+        let custody_subnet_count = enr.custody_subnet_count::<E>(&spec);
 
         let predicate = subnets.iter().any(|subnet| match subnet {
             Subnet::Attestation(s) => attestation_bitfield
@@ -1,6 +1,7 @@
 //! Implementation of Lighthouse's peer management system.
 
 use crate::discovery::enr_ext::EnrExt;
+use crate::discovery::{peer_id_to_node_id, Eth2Enr};
 use crate::rpc::{GoodbyeReason, MetaData, Protocol, RPCError, RPCResponseErrorCode};
 use crate::service::TARGET_SUBNET_PEERS;
 use crate::{error, metrics, Gossipsub};
@@ -18,7 +19,7 @@ use std::{
     sync::Arc,
     time::{Duration, Instant},
 };
-use types::{EthSpec, SyncSubnetId};
+use types::{DataColumnSubnetId, EthSpec, SyncSubnetId};
 
 pub use libp2p::core::Multiaddr;
 pub use libp2p::identity::Keypair;
@@ -51,6 +52,9 @@ pub const PEER_RECONNECTION_TIMEOUT: Duration = Duration::from_secs(600);
 /// lower our peer count below this number. Instead we favour a non-uniform distribution of subnet
 /// peers.
 pub const MIN_SYNC_COMMITTEE_PEERS: u64 = 2;
+/// This is used in the pruning logic. We avoid pruning peers that custody one of our data column
+/// subnets if doing so would lower the number of peers on that subnet below this number.
+pub const MIN_DATA_COLUMN_SUBNET_PEERS: u64 = 2;
 /// A fraction of `PeerManager::target_peers` that we allow to connect to us in excess of
 /// `PeerManager::target_peers`. For clarity, if `PeerManager::target_peers` is 50 and
 /// PEER_EXCESS_FACTOR = 0.1 we allow 10% more nodes, i.e 55.
@@ -520,7 +524,10 @@ impl<E: EthSpec> PeerManager<E> {
                 RPCResponseErrorCode::Unknown => PeerAction::HighToleranceError,
                 RPCResponseErrorCode::ResourceUnavailable => {
                     // Don't ban on this because we want to retry with a block by root request.
-                    if matches!(protocol, Protocol::BlobsByRoot) {
+                    if matches!(
+                        protocol,
+                        Protocol::BlobsByRoot | Protocol::DataColumnsByRoot
+                    ) {
                         return;
                     }
 
@@ -558,7 +565,10 @@ impl<E: EthSpec> PeerManager<E> {
                     Protocol::LightClientBootstrap => return,
                     Protocol::LightClientOptimisticUpdate => return,
                     Protocol::LightClientFinalityUpdate => return,
+                    Protocol::LightClientUpdatesByRange => return,
                     Protocol::BlobsByRoot => PeerAction::MidToleranceError,
+                    Protocol::DataColumnsByRoot => PeerAction::MidToleranceError,
+                    Protocol::DataColumnsByRange => PeerAction::MidToleranceError,
                     Protocol::Goodbye => PeerAction::LowToleranceError,
                     Protocol::MetaData => PeerAction::LowToleranceError,
                     Protocol::Status => PeerAction::LowToleranceError,
@@ -577,10 +587,13 @@ impl<E: EthSpec> PeerManager<E> {
                     Protocol::BlocksByRoot => return,
                     Protocol::BlobsByRange => return,
                     Protocol::BlobsByRoot => return,
+                    Protocol::DataColumnsByRoot => return,
+                    Protocol::DataColumnsByRange => return,
                     Protocol::Goodbye => return,
                     Protocol::LightClientBootstrap => return,
                     Protocol::LightClientOptimisticUpdate => return,
                     Protocol::LightClientFinalityUpdate => return,
+                    Protocol::LightClientUpdatesByRange => return,
                     Protocol::MetaData => PeerAction::Fatal,
                     Protocol::Status => PeerAction::Fatal,
                 }
@@ -597,9 +610,12 @@ impl<E: EthSpec> PeerManager<E> {
                     Protocol::BlocksByRoot => PeerAction::MidToleranceError,
                     Protocol::BlobsByRange => PeerAction::MidToleranceError,
                     Protocol::BlobsByRoot => PeerAction::MidToleranceError,
+                    Protocol::DataColumnsByRoot => PeerAction::MidToleranceError,
+                    Protocol::DataColumnsByRange => PeerAction::MidToleranceError,
                     Protocol::LightClientBootstrap => return,
                     Protocol::LightClientOptimisticUpdate => return,
                     Protocol::LightClientFinalityUpdate => return,
+                    Protocol::LightClientUpdatesByRange => return,
                     Protocol::Goodbye => return,
                     Protocol::MetaData => return,
                     Protocol::Status => return,
@@ -701,7 +717,10 @@ impl<E: EthSpec> PeerManager<E> {
         } else {
             error!(self.log, "Received METADATA from an unknown peer";
                 "peer_id" => %peer_id);
+            return;
         }
+        // The metadata may advertise a new custody subnet count.
+        self.update_peer_custody_subnets(peer_id);
     }
 
     /// Updates the gossipsub scores for all known peers in gossipsub.
@@ -811,9 +830,62 @@ impl<E: EthSpec> PeerManager<E> {
         // start a ping and status timer for the peer
         self.status_peers.insert(*peer_id);
 
+        // compute the data column subnets the peer is expected to custody
+        self.update_peer_custody_subnets(peer_id);
+
         true
     }
 
+    /// Computes the data column subnets a peer is required to custody and records them in the
+    /// `PeerDB`.
+    ///
+    /// The custody subnet count is read from the peer's metadata if it is known, otherwise from
+    /// its ENR. Peers that advertise neither are assumed to custody the minimum
+    /// `custody_requirement`.
+    fn update_peer_custody_subnets(&mut self, peer_id: &PeerId) {
+        let spec = &self.network_globals.spec;
+        if !spec.is_peer_das_scheduled() {
+            return;
+        }
+
+        let mut peerdb = self.network_globals.peers.write();
+        let Some(peer_info) = peerdb.peer_info_mut(peer_id) else {
+            return;
+        };
+
+        let node_id = match peer_info.enr() {
+            Some(enr) => enr.node_id(),
+            None => match peer_id_to_node_id(peer_id) {
+                Ok(node_id) => node_id,
+                Err(e) => {
+                    debug!(self.log, "Unable to compute peer custody subnets"; "peer_id" => %peer_id, "error" => e);
+                    return;
+                }
+            },
+        };
+
+        let custody_subnet_count = peer_info
+            .meta_data()
+            .and_then(|meta_data| meta_data.custody_subnet_count().ok().copied())
+            .filter(|count| {
+                (spec.custody_requirement..=spec.data_column_sidecar_subnet_count).contains(count)
+            })
+            .or_else(|| {
+                peer_info
+                    .enr()
+                    .map(|enr| enr.custody_subnet_count::<E>(spec))
+            })
+            .unwrap_or(spec.custody_requirement);
+
+        let custody_subnets = DataColumnSubnetId::compute_custody_subnets::<E>(
+            node_id.raw().into(),
+            custody_subnet_count,
+            spec,
+        )
+        .collect();
+        peer_info.set_custody_subnets(custody_subnets);
+    }
+
     // Gracefully disconnects a peer without banning them.
     fn disconnect_peer(&mut self, peer_id: PeerId, reason: GoodbyeReason) {
         self.events
@@ -864,6 +936,39 @@ impl<E: EthSpec> PeerManager<E> {
         }
     }
 
+    /// Run discovery query for additional peers on our custody data column subnets if we fall
+    /// below `TARGET_SUBNET_PEERS`.
+    fn maintain_custody_peers(&mut self) {
+        if !self.network_globals.spec.is_peer_das_scheduled() {
+            return;
+        }
+
+        let subnets_to_discover: Vec<SubnetDiscovery> = {
+            let peerdb = self.network_globals.peers.read();
+            self.network_globals
+                .custody_subnets()
+                .filter(|subnet| {
+                    peerdb.good_custody_subnet_peer(*subnet).count() < TARGET_SUBNET_PEERS
+                })
+                .map(|subnet| SubnetDiscovery {
+                    subnet: Subnet::DataColumn(subnet),
+                    min_ttl: None,
+                })
+                .collect()
+        };
+
+        // request the subnet query from discovery
+        if !subnets_to_discover.is_empty() {
+            debug!(
+                self.log,
+                "Making subnet queries for maintaining custody peers";
+                "subnets" => ?subnets_to_discover.iter().map(|s| s.subnet).collect::<Vec<_>>()
+            );
+            self.events
+                .push(PeerManagerEvent::DiscoverSubnetPeers(subnets_to_discover));
+        }
+    }
+
     /// This function checks the status of our current peers and optionally requests a discovery
     /// query if we need to find more peers to maintain the current number of peers
     fn maintain_peer_count(&mut self, dialing_peers: usize) {
@@ -917,6 +1022,8 @@ impl<E: EthSpec> PeerManager<E> {
     ///     MIN_SYNC_COMMITTEE_PEERS
     ///     number should be set low as an absolute lower bound to maintain peers on the sync
     ///     committees.
+    /// - In the same way, do not prune a peer that would lower us below MIN_DATA_COLUMN_SUBNET_PEERS
+    ///     peers on any of the data column subnets we custody.
     /// - Do not prune trusted peers. NOTE: This means if a user has more trusted peers than the
     ///     excess peer limit, all of the following logic is subverted as we will not prune any peers.
     ///     Also, the more trusted peers a user has, the less room Lighthouse has to efficiently manage
@@ -1001,6 +1108,19 @@ impl<E: EthSpec> PeerManager<E> {
                 PeerId,
                 std::collections::HashSet<SyncSubnetId>,
             > = HashMap::new();
+            // Similarly, track peers on the data column subnets we custody so we don't prune
+            // below MIN_DATA_COLUMN_SUBNET_PEERS on any of them.
+            let our_custody_subnets: std::collections::HashSet<DataColumnSubnetId> =
+                if self.network_globals.spec.is_peer_das_scheduled() {
+                    self.network_globals.custody_subnets().collect()
+                } else {
+                    std::collections::HashSet::new()
+                };
+            let mut custody_subnet_peer_count: HashMap<DataColumnSubnetId, u64> = HashMap::new();
+            let mut peer_to_custody_subnets: HashMap<
+                PeerId,
+                std::collections::HashSet<DataColumnSubnetId>,
+            > = HashMap::new();
 
             for (peer_id, info) in self.network_globals.peers.read().connected_peers() {
                 // Ignore peers we trust or that we are already pruning
@@ -1027,12 +1147,22 @@ impl<E: EthSpec> PeerManager<E> {
                                 .or_default()
                                 .insert(id);
                         }
-                        // TODO(das) to be implemented. We're not pruning data column peers yet
-                        // because data column topics are subscribed as core topics until we
-                        // implement recomputing data column subnets.
+                        // Data column subnets are not advertised as long-lived subnets, they
+                        // are tracked below from the peer's custody assignment.
                         Subnet::DataColumn(_) => {}
                     }
                 }
+
+                for subnet in info
+                    .custody_subnets()
+                    .filter(|subnet| our_custody_subnets.contains(subnet))
+                {
+                    *custody_subnet_peer_count.entry(*subnet).or_default() += 1;
+                    peer_to_custody_subnets
+                        .entry(*peer_id)
+                        .or_default()
+                        .insert(*subnet);
+                }
             }
 
             // Add to the peers to prune mapping
@@ -1089,6 +1219,20 @@ impl<E: EthSpec> PeerManager<E> {
                                 }
                             }
 
+                            // Check the data column subnets we custody
+                            if let Some(subnets) = peer_to_custody_subnets.get(candidate_peer) {
+                                if let Some(min_subnet_count) = subnets
+                                    .iter()
+                                    .filter_map(|v| custody_subnet_peer_count.get(v).copied())
+                                    .min()
+                                {
+                                    if min_subnet_count <= MIN_DATA_COLUMN_SUBNET_PEERS {
+                                        // Do not drop this peer in this pruning interval
+                                        continue;
+                                    }
+                                }
+                            }
+
                             if info.is_outbound_only() {
                                 outbound_peers_pruned += 1;
                             }
@@ -1120,6 +1264,16 @@ impl<E: EthSpec> PeerManager<E> {
                                     }
                                 }
                             }
+                            // Remove pruned peers from all custody subnet counts
+                            if let Some(custody_subnets) =
+                                peer_to_custody_subnets.get(&candidate_peer)
+                            {
+                                for subnet in custody_subnets {
+                                    if let Some(count) = custody_subnet_peer_count.get_mut(subnet) {
+                                        *count = count.saturating_sub(1);
+                                    }
+                                }
+                            }
                             peers_to_prune.insert(candidate_peer);
                         } else {
                             peers_on_subnet.clear();
@@ -1175,6 +1329,9 @@ impl<E: EthSpec> PeerManager<E> {
         // Maintain minimum count for sync committee peers.
         self.maintain_sync_committee_peers();
 
+        // Maintain minimum count for peers on our custody data column subnets.
+        self.maintain_custody_peers();
+
         // Prune any excess peers back to our target in such a way that incentivises good scores and
         // a uniform distribution of subnets.
         self.prune_excess_peers();
@@ -1369,7 +1526,20 @@ mod tests {
             ..Default::default()
         };
         let log = build_log(slog::Level::Debug, false);
-        let globals = NetworkGlobals::new_test_globals(vec![], &log);
+        let globals = NetworkGlobals::new_test_globals(vec![], &log, Arc::new(E::default_spec()));
+        PeerManager::new(config, Arc::new(globals), &log).unwrap()
+    }
+
+    async fn build_peer_manager_with_peer_das(target_peer_count: usize) -> PeerManager<E> {
+        let config = config::Config {
+            target_peer_count,
+            discovery_enabled: false,
+            ..Default::default()
+        };
+        let mut spec = E::default_spec();
+        spec.eip7594_fork_epoch = Some(types::Epoch::new(0));
+        let log = build_log(slog::Level::Debug, false);
+        let globals = NetworkGlobals::new_test_globals(vec![], &log, Arc::new(spec));
         PeerManager::new(config, Arc::new(globals), &log).unwrap()
     }
 
@@ -1383,7 +1553,8 @@ mod tests {
             ..Default::default()
         };
         let log = build_log(slog::Level::Debug, false);
-        let globals = NetworkGlobals::new_test_globals(trusted_peers, &log);
+        let globals =
+            NetworkGlobals::new_test_globals(trusted_peers, &log, Arc::new(E::default_spec()));
         PeerManager::new(config, Arc::new(globals), &log).unwrap()
     }
 
@@ -2048,6 +2219,90 @@ mod tests {
         assert!(!connected_peers.contains(&peers[2]));
     }
 
+    /// Test the pruning logic to prioritise peers with the most subnets, but not at the expense of
+    /// dropping below `MIN_DATA_COLUMN_SUBNET_PEERS` on a data column subnet we custody.
+    ///
+    /// Create 6 peers.
+    /// Peer0 : Subnet 1,2
+    /// Peer1 : Subnet 1,2
+    /// Peer2 : Subnet 1,2
+    /// Peer3 : Subnet 1,2
+    /// Peer4 : Subnet 1,  Custody-column-subnet
+    /// Peer5 : Subnet 1,  Custody-column-subnet
+    ///
+    /// Prune 3 peers: Should be three of Peer0-3 because 4 and 5 are the last custodians of one
+    /// of our column subnets, even though they are on the fewest attestation subnets.
+    #[tokio::test]
+    async fn test_peer_manager_prune_data_column_subnet_peers() {
+        let target = 3;
+        let mut peer_manager = build_peer_manager_with_peer_das(target).await;
+        let custody_subnet = peer_manager
+            .network_globals
+            .custody_subnets()
+            .next()
+            .expect("we custody at least one column subnet");
+
+        // Create 6 peers to connect to.
+        let mut peers = Vec::new();
+        for x in 0..6 {
+            let peer = PeerId::random();
+            peer_manager.inject_connect_ingoing(&peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+
+            let mut attnets = crate::types::EnrAttestationBitfield::<E>::new();
+            let mut custody_subnets = std::collections::HashSet::new();
+
+            attnets.set(1, true).unwrap();
+            match x {
+                0..=3 => {
+                    attnets.set(2, true).unwrap();
+                }
+                4 | 5 => {
+                    custody_subnets.insert(custody_subnet);
+                }
+                _ => unreachable!(),
+            }
+
+            let metadata = crate::rpc::MetaDataV2 {
+                seq_number: 0,
+                attnets,
+                syncnets: Default::default(),
+            };
+            let mut peers_db = peer_manager.network_globals.peers.write();
+            let peer_info = peers_db.peer_info_mut(&peer).unwrap();
+            peer_info.set_meta_data(MetaData::V2(metadata));
+            // Override the custody assignment derived from the peer id on connection.
+            peer_info.set_custody_subnets(custody_subnets);
+            let long_lived_subnets = peer_info.long_lived_subnets();
+            for subnet in long_lived_subnets {
+                peers_db.add_subscription(&peer, subnet);
+            }
+            drop(peers_db);
+            peers.push(peer);
+        }
+
+        // Perform the heartbeat.
+        peer_manager.heartbeat();
+
+        // Tests that when we are over the target peer limit, after disconnecting an unhealthy peer,
+        // the number of connected peers updates and we will not remove too many peers.
+        assert_eq!(
+            peer_manager.network_globals.connected_or_dialing_peers(),
+            target
+        );
+
+        // Check that we kept both custodians of the column subnet
+        let connected_peers: std::collections::HashSet<_> = peer_manager
+            .network_globals
+            .peers
+            .read()
+            .connected_or_dialing_peers()
+            .cloned()
+            .collect();
+
+        assert!(connected_peers.contains(&peers[4]));
+        assert!(connected_peers.contains(&peers[5]));
+    }
+
     /// This test is for reproducing the issue:
     /// https://github.com/sigp/lighthouse/pull/3236#issue-1256432659
     ///
@@ -12,7 +12,7 @@ use std::{
     fmt::Formatter,
 };
 use sync_status::SyncStatus;
-use types::EthSpec;
+use types::{DataColumnSubnetId, EthSpec};
 
 pub mod client;
 pub mod peer_info;
@@ -246,6 +246,19 @@ impl<E: EthSpec> PeerDB<E> {
             .map(|(peer_id, _)| peer_id)
     }
 
+    /// Gives an iterator of all connected peers that custody the given data column subnet.
+    pub fn good_custody_subnet_peer(
+        &self,
+        subnet: DataColumnSubnetId,
+    ) -> impl Iterator<Item = &PeerId> {
+        self.peers
+            .iter()
+            .filter(move |(_, info)| {
+                info.is_connected() && info.is_assigned_to_custody_subnet(&subnet)
+            })
+            .map(|(peer_id, _)| peer_id)
+    }
+
     /// Gives the ids of all known disconnected peers.
     pub fn disconnected_peers(&self) -> impl Iterator<Item = &PeerId> {
         self.peers
@@ -686,6 +699,20 @@ impl<E: EthSpec> PeerDB<E> {
         )
     }
 
+    /// Updates the connection state and sets the data column subnets the peer custodies. MUST ONLY
+    /// BE USED IN TESTS.
+    pub fn __add_connected_custody_peer_testing_only(
+        &mut self,
+        peer_id: &PeerId,
+        custody_subnets: HashSet<DataColumnSubnetId>,
+    ) -> Option<BanOperation> {
+        let ban_operation = self.__add_connected_peer_testing_only(peer_id);
+        if let Some(info) = self.peers.get_mut(peer_id) {
+            info.set_custody_subnets(custody_subnets);
+        }
+        ban_operation
+    }
+
     /// The connection state of the peer has been changed. Modify the peer in the db to ensure all
     /// variables are in sync with libp2p.
     /// Updating the state can lead to a `BanOperation` which needs to be processed via the peer
@@ -13,7 +13,7 @@ use std::collections::HashSet;
 use std::net::IpAddr;
 use std::time::Instant;
 use strum::AsRefStr;
-use types::EthSpec;
+use types::{DataColumnSubnetId, EthSpec};
 use PeerConnectionStatus::*;
 
 /// Information about a given connected peer.
@@ -40,6 +40,9 @@ pub struct PeerInfo<E: EthSpec> {
     meta_data: Option<MetaData<E>>,
     /// Subnets the peer is connected to.
     subnets: HashSet<Subnet>,
+    /// The data column subnets the peer is required to custody, derived from its node id and
+    /// advertised custody subnet count.
+    custody_subnets: HashSet<DataColumnSubnetId>,
     /// The time we would like to retain this peer. After this time, the peer is no longer
     /// necessary.
     #[serde(skip)]
@@ -62,6 +65,7 @@ impl<E: EthSpec> Default for PeerInfo<E> {
             listening_addresses: Vec::new(),
             seen_multiaddrs: HashSet::new(),
             subnets: HashSet::new(),
+            custody_subnets: HashSet::new(),
             sync_status: SyncStatus::Unknown,
             meta_data: None,
             min_ttl: None,
@@ -83,7 +87,12 @@ impl<E: EthSpec> PeerInfo<E> {
     }
 
     /// Returns if the peer is subscribed to a given `Subnet` from the metadata attnets/syncnets field.
+    ///
+    /// Data column subnets are checked against the peer's computed custody subnets.
     pub fn on_subnet_metadata(&self, subnet: &Subnet) -> bool {
+        if let Subnet::DataColumn(id) = subnet {
+            return self.is_assigned_to_custody_subnet(id);
+        }
         if let Some(meta_data) = &self.meta_data {
             match subnet {
                 Subnet::Attestation(id) => {
@@ -94,15 +103,7 @@ impl<E: EthSpec> PeerInfo<E> {
                         .syncnets()
                         .map_or(false, |s| s.get(**id as usize).unwrap_or(false))
                 }
-                Subnet::DataColumn(_) => {
-                    // TODO(das): Pending spec PR https://github.com/ethereum/consensus-specs/pull/3821
-                    // We should use MetaDataV3 for peer selection rather than
-                    // looking at subscribed peers (current behavior). Until MetaDataV3 is
-                    // implemented, this is the perhaps the only viable option on the current devnet
-                    // as the peer count is low and it's important to identify supernodes to get a
-                    // good distribution of peers across subnets.
-                    return true;
-                }
+                Subnet::DataColumn(_) => {}
             }
         }
         false
@@ -154,6 +155,16 @@ impl<E: EthSpec> PeerInfo<E> {
         self.subnets.iter()
     }
 
+    /// Returns if the peer is required to custody the given data column subnet.
+    pub fn is_assigned_to_custody_subnet(&self, subnet: &DataColumnSubnetId) -> bool {
+        self.custody_subnets.contains(subnet)
+    }
+
+    /// An iterator over the data column subnets the peer is required to custody.
+    pub fn custody_subnets(&self) -> impl Iterator<Item = &DataColumnSubnetId> {
+        self.custody_subnets.iter()
+    }
+
     /// Returns the number of long lived subnets a peer is subscribed to.
     // NOTE: This currently excludes sync committee subnets
     pub fn long_lived_subnet_count(&self) -> usize {
@@ -357,6 +368,15 @@ impl<E: EthSpec> PeerInfo<E> {
         self.meta_data = Some(meta_data)
     }
 
+    /// Sets the data column subnets the peer is required to custody.
+    // VISIBILITY: The peer manager computes the custody subnets from the peer's ENR and metadata
+    pub(in crate::peer_manager) fn set_custody_subnets(
+        &mut self,
+        custody_subnets: HashSet<DataColumnSubnetId>,
+    ) {
+        self.custody_subnets = custody_subnets
+    }
+
     /// Sets the connection status of the peer.
     pub(super) fn set_connection_status(&mut self, connection_status: PeerConnectionStatus) {
         self.connection_status = connection_status
@@ -16,10 +16,11 @@ use std::marker::PhantomData;
 use std::sync::Arc;
 use tokio_util::codec::{Decoder, Encoder};
 use types::{
-    BlobSidecar, ChainSpec, EthSpec, ForkContext, ForkName, Hash256, LightClientBootstrap,
-    LightClientFinalityUpdate, LightClientOptimisticUpdate, RuntimeVariableList, SignedBeaconBlock,
-    SignedBeaconBlockAltair, SignedBeaconBlockBase, SignedBeaconBlockBellatrix,
-    SignedBeaconBlockCapella, SignedBeaconBlockDeneb, SignedBeaconBlockElectra,
+    BlobSidecar, ChainSpec, DataColumnSidecar, EthSpec, ForkContext, ForkName, Hash256,
+    LightClientBootstrap, LightClientFinalityUpdate, LightClientOptimisticUpdate,
+    LightClientUpdate, RuntimeVariableList, SignedBeaconBlock, SignedBeaconBlockAltair,
+    SignedBeaconBlockBase, SignedBeaconBlockBellatrix, SignedBeaconBlockCapella,
+    SignedBeaconBlockDeneb, SignedBeaconBlockElectra,
 };
 use unsigned_varint::codec::Uvi;
 
@@ -70,9 +71,12 @@ impl<E: EthSpec> Encoder<RPCCodedResponse<E>> for SSZSnappyInboundCodec<E> {
                 RPCResponse::BlocksByRoot(res) => res.as_ssz_bytes(),
                 RPCResponse::BlobsByRange(res) => res.as_ssz_bytes(),
                 RPCResponse::BlobsByRoot(res) => res.as_ssz_bytes(),
+                RPCResponse::DataColumnsByRoot(res) => res.as_ssz_bytes(),
+                RPCResponse::DataColumnsByRange(res) => res.as_ssz_bytes(),
                 RPCResponse::LightClientBootstrap(res) => res.as_ssz_bytes(),
                 RPCResponse::LightClientOptimisticUpdate(res) => res.as_ssz_bytes(),
                 RPCResponse::LightClientFinalityUpdate(res) => res.as_ssz_bytes(),
+                RPCResponse::LightClientUpdatesByRange(res) => res.as_ssz_bytes(),
                 RPCResponse::Pong(res) => res.data.as_ssz_bytes(),
                 RPCResponse::MetaData(res) =>
                 // Encode the correct version of the MetaData response based on the negotiated version.
@@ -82,6 +86,9 @@ impl<E: EthSpec> Encoder<RPCCodedResponse<E>> for SSZSnappyInboundCodec<E> {
                         // We always send V2 metadata responses from the behaviour
                         // No change required.
                         SupportedProtocol::MetaDataV2 => res.metadata_v2().as_ssz_bytes(),
+                        SupportedProtocol::MetaDataV3 => {
+                            res.metadata_v3(&self.fork_context.spec).as_ssz_bytes()
+                        }
                         _ => unreachable!(
                             "We only send metadata responses on negotiating metadata requests"
                         ),
@@ -133,6 +140,9 @@ impl<E: EthSpec> Decoder for SSZSnappyInboundCodec<E> {
         if self.protocol.versioned_protocol == SupportedProtocol::MetaDataV2 {
             return Ok(Some(InboundRequest::MetaData(MetadataRequest::new_v2())));
         }
+        if self.protocol.versioned_protocol == SupportedProtocol::MetaDataV3 {
+            return Ok(Some(InboundRequest::MetaData(MetadataRequest::new_v3())));
+        }
         let Some(length) = handle_length(&mut self.inner, &mut self.len, src)? else {
             return Ok(None);
         };
@@ -224,6 +234,8 @@ impl<E: EthSpec> Encoder<OutboundRequest<E>> for SSZSnappyOutboundCodec<E> {
             },
             OutboundRequest::BlobsByRange(req) => req.as_ssz_bytes(),
             OutboundRequest::BlobsByRoot(req) => req.blob_ids.as_ssz_bytes(),
+            OutboundRequest::DataColumnsByRoot(req) => req.data_column_ids.as_ssz_bytes(),
+            OutboundRequest::DataColumnsByRange(req) => req.as_ssz_bytes(),
             OutboundRequest::Ping(req) => req.as_ssz_bytes(),
             OutboundRequest::MetaData(_) => return Ok(()), // no metadata to encode
         };
@@ -417,6 +429,11 @@ fn context_bytes<E: EthSpec>(
                 RPCResponse::BlobsByRange(_) | RPCResponse::BlobsByRoot(_) => {
                     return fork_context.to_context_bytes(ForkName::Deneb);
                 }
+                RPCResponse::DataColumnsByRoot(sidecar)
+                | RPCResponse::DataColumnsByRange(sidecar) => {
+                    let fork_name = fork_context.spec.fork_name_at_slot::<E>(sidecar.slot());
+                    return fork_context.to_context_bytes(fork_name);
+                }
                 RPCResponse::LightClientBootstrap(lc_bootstrap) => {
                     return lc_bootstrap
                         .map_with_fork_name(|fork_name| fork_context.to_context_bytes(fork_name));
@@ -429,6 +446,13 @@ fn context_bytes<E: EthSpec>(
                     return lc_finality_update
                         .map_with_fork_name(|fork_name| fork_context.to_context_bytes(fork_name));
                 }
+                RPCResponse::LightClientUpdatesByRange(lc_update) => {
+                    // Updates are served with the fork digest of their attested header.
+                    let fork_name = fork_context
+                        .spec
+                        .fork_name_at_slot::<E>(lc_update.attested_header_slot());
+                    return fork_context.to_context_bytes(fork_name);
+                }
                 // These will not pass the has_context_bytes() check
                 RPCResponse::Status(_) | RPCResponse::Pong(_) | RPCResponse::MetaData(_) => {
                     return None;
@@ -512,6 +536,17 @@ fn handle_rpc_request<E: EthSpec>(
                 )?,
             })))
         }
+        SupportedProtocol::DataColumnsByRootV1 => Ok(Some(InboundRequest::DataColumnsByRoot(
+            DataColumnsByRootRequest {
+                data_column_ids: RuntimeVariableList::from_ssz_bytes(
+                    decoded_buffer,
+                    spec.max_request_data_column_sidecars as usize,
+                )?,
+            },
+        ))),
+        SupportedProtocol::DataColumnsByRangeV1 => Ok(Some(InboundRequest::DataColumnsByRange(
+            DataColumnsByRangeRequest::from_ssz_bytes(decoded_buffer)?,
+        ))),
         SupportedProtocol::PingV1 => Ok(Some(InboundRequest::Ping(Ping {
             data: u64::from_ssz_bytes(decoded_buffer)?,
         }))),
@@ -526,8 +561,22 @@ fn handle_rpc_request<E: EthSpec>(
         SupportedProtocol::LightClientFinalityUpdateV1 => {
             Ok(Some(InboundRequest::LightClientFinalityUpdate))
         }
+        SupportedProtocol::LightClientUpdatesByRangeV1 => {
+            Ok(Some(InboundRequest::LightClientUpdatesByRange(
+                LightClientUpdatesByRangeRequest::from_ssz_bytes(decoded_buffer)?,
+            )))
+        }
         // MetaData requests return early from InboundUpgrade and do not reach the decoder.
         // Handle this case just for completeness.
+        SupportedProtocol::MetaDataV3 => {
+            if !decoded_buffer.is_empty() {
+                Err(RPCError::InternalError(
+                    "Metadata requests shouldn't reach decoder",
+                ))
+            } else {
+                Ok(Some(InboundRequest::MetaData(MetadataRequest::new_v3())))
+            }
+        }
         SupportedProtocol::MetaDataV2 => {
             if !decoded_buffer.is_empty() {
                 Err(RPCError::InternalError(
@@ -604,6 +653,42 @@ fn handle_rpc_response<E: EthSpec>(
                 ),
             )),
         },
+        SupportedProtocol::DataColumnsByRootV1 => match fork_name {
+            Some(fork_name) if fork_name.deneb_enabled() => {
+                Ok(Some(RPCResponse::DataColumnsByRoot(Arc::new(
+                    DataColumnSidecar::from_ssz_bytes(decoded_buffer)?,
+                ))))
+            }
+            Some(_) => Err(RPCError::ErrorResponse(
+                RPCResponseErrorCode::InvalidRequest,
+                "Invalid fork name for data columns by root".to_string(),
+            )),
+            None => Err(RPCError::ErrorResponse(
+                RPCResponseErrorCode::InvalidRequest,
+                format!(
+                    "No context bytes provided for {:?} response",
+                    versioned_protocol
+                ),
+            )),
+        },
+        SupportedProtocol::DataColumnsByRangeV1 => match fork_name {
+            Some(fork_name) if fork_name.deneb_enabled() => {
+                Ok(Some(RPCResponse::DataColumnsByRange(Arc::new(
+                    DataColumnSidecar::from_ssz_bytes(decoded_buffer)?,
+                ))))
+            }
+            Some(_) => Err(RPCError::ErrorResponse(
+                RPCResponseErrorCode::InvalidRequest,
+                "Invalid fork name for data columns by range".to_string(),
+            )),
+            None => Err(RPCError::ErrorResponse(
+                RPCResponseErrorCode::InvalidRequest,
+                format!(
+                    "No context bytes provided for {:?} response",
+                    versioned_protocol
+                ),
+            )),
+        },
         SupportedProtocol::PingV1 => Ok(Some(RPCResponse::Pong(Ping {
             data: u64::from_ssz_bytes(decoded_buffer)?,
         }))),
@@ -646,10 +731,25 @@ fn handle_rpc_response<E: EthSpec>(
                 ),
             )),
         },
+        SupportedProtocol::LightClientUpdatesByRangeV1 => match fork_name {
+            Some(fork_name) => Ok(Some(RPCResponse::LightClientUpdatesByRange(Arc::new(
+                LightClientUpdate::from_ssz_bytes(decoded_buffer, fork_name)?,
+            )))),
+            None => Err(RPCError::ErrorResponse(
+                RPCResponseErrorCode::InvalidRequest,
+                format!(
+                    "No context bytes provided for {:?} response",
+                    versioned_protocol
+                ),
+            )),
+        },
         // MetaData V2 responses have no context bytes, so behave similarly to V1 responses
         SupportedProtocol::MetaDataV2 => Ok(Some(RPCResponse::MetaData(MetaData::V2(
             MetaDataV2::from_ssz_bytes(decoded_buffer)?,
         )))),
+        SupportedProtocol::MetaDataV3 => Ok(Some(RPCResponse::MetaData(MetaData::V3(
+            MetaDataV3::from_ssz_bytes(decoded_buffer)?,
+        )))),
         SupportedProtocol::BlocksByRangeV2 => match fork_name {
             Some(ForkName::Altair) => Ok(Some(RPCResponse::BlocksByRange(Arc::new(
                 SignedBeaconBlock::Altair(SignedBeaconBlockAltair::from_ssz_bytes(decoded_buffer)?),
@@ -747,7 +847,8 @@ mod tests {
     use crate::types::{EnrAttestationBitfield, EnrSyncCommitteeBitfield};
     use types::{
         blob_sidecar::BlobIdentifier, BeaconBlock, BeaconBlockAltair, BeaconBlockBase,
-        BeaconBlockBellatrix, EmptyBlock, Epoch, FullPayload, Signature, Slot,
+        BeaconBlockBellatrix, DataColumnIdentifier, EmptyBlock, Epoch, FullPayload, KzgCommitment,
+        KzgProof, Signature, Slot,
     };
 
     type Spec = types::MainnetEthSpec;
@@ -794,6 +895,33 @@ mod tests {
         Arc::new(BlobSidecar::empty())
     }
 
+    /// Data column sidecar at the first slot of the Deneb fork, so that it is encoded with Deneb
+    /// context bytes.
+    fn empty_data_column_sidecar() -> Arc<DataColumnSidecar<Spec>> {
+        let mut sidecar = DataColumnSidecar::empty();
+        sidecar.signed_block_header.message.slot = fork_context(ForkName::Deneb)
+            .spec
+            .deneb_fork_epoch
+            .unwrap()
+            .start_slot(Spec::slots_per_epoch());
+        Arc::new(sidecar)
+    }
+
+    /// Data column sidecar with two cells at the first slot of the Electra fork.
+    fn electra_data_column_sidecar() -> Arc<DataColumnSidecar<Spec>> {
+        let mut sidecar = DataColumnSidecar::empty();
+        sidecar.index = 7;
+        sidecar.column = VariableList::from(vec![<_>::default(); 2]);
+        sidecar.kzg_commitments = VariableList::from(vec![KzgCommitment::empty_for_testing(); 2]);
+        sidecar.kzg_proofs = VariableList::from(vec![KzgProof::empty(); 2]);
+        sidecar.signed_block_header.message.slot = fork_context(ForkName::Electra)
+            .spec
+            .electra_fork_epoch
+            .unwrap()
+            .start_slot(Spec::slots_per_epoch());
+        Arc::new(sidecar)
+    }
+
     /// Bellatrix block with length < max_rpc_size.
     fn bellatrix_block_small(
         fork_context: &ForkContext,
@@ -855,6 +983,14 @@ mod tests {
         }
     }
 
+    fn dcbrange_request() -> DataColumnsByRangeRequest {
+        DataColumnsByRangeRequest {
+            start_slot: 0,
+            count: 10,
+            columns: vec![1, 2, 3],
+        }
+    }
+
     fn bbroot_request_v1(spec: &ChainSpec) -> BlocksByRootRequest {
         BlocksByRootRequest::new_v1(vec![Hash256::zero()], spec)
     }
@@ -873,6 +1009,16 @@ mod tests {
         )
     }
 
+    fn dcbroot_request(spec: &ChainSpec) -> DataColumnsByRootRequest {
+        DataColumnsByRootRequest::new(
+            vec![DataColumnIdentifier {
+                block_root: Hash256::zero(),
+                index: 0,
+            }],
+            spec,
+        )
+    }
+
     fn ping_message() -> Ping {
         Ping { data: 1 }
     }
@@ -892,6 +1038,15 @@ mod tests {
         })
     }
 
+    fn metadata_v3(custody_subnet_count: u64) -> MetaData<Spec> {
+        MetaData::V3(MetaDataV3 {
+            seq_number: 1,
+            attnets: EnrAttestationBitfield::<Spec>::default(),
+            syncnets: EnrSyncCommitteeBitfield::<Spec>::default(),
+            custody_subnet_count,
+        })
+    }
+
     /// Encodes the given protocol response as bytes.
     fn encode_response(
         protocol: SupportedProtocol,
@@ -1012,6 +1167,12 @@ mod tests {
             OutboundRequest::BlobsByRoot(bbroot) => {
                 assert_eq!(decoded, InboundRequest::BlobsByRoot(bbroot))
             }
+            OutboundRequest::DataColumnsByRoot(dcbroot) => {
+                assert_eq!(decoded, InboundRequest::DataColumnsByRoot(dcbroot))
+            }
+            OutboundRequest::DataColumnsByRange(dcbrange) => {
+                assert_eq!(decoded, InboundRequest::DataColumnsByRange(dcbrange))
+            }
             OutboundRequest::Ping(ping) => {
                 assert_eq!(decoded, InboundRequest::Ping(ping))
             }
@@ -1138,6 +1299,34 @@ mod tests {
             ),
             Ok(Some(RPCResponse::BlobsByRoot(empty_blob_sidecar()))),
         );
+
+        assert_eq!(
+            encode_then_decode_response(
+                SupportedProtocol::DataColumnsByRangeV1,
+                RPCCodedResponse::Success(RPCResponse::DataColumnsByRange(
+                    empty_data_column_sidecar()
+                )),
+                ForkName::Deneb,
+                &chain_spec
+            ),
+            Ok(Some(RPCResponse::DataColumnsByRange(
+                empty_data_column_sidecar()
+            ))),
+        );
+
+        assert_eq!(
+            encode_then_decode_response(
+                SupportedProtocol::DataColumnsByRootV1,
+                RPCCodedResponse::Success(RPCResponse::DataColumnsByRoot(
+                    empty_data_column_sidecar()
+                )),
+                ForkName::Deneb,
+                &chain_spec
+            ),
+            Ok(Some(RPCResponse::DataColumnsByRoot(
+                empty_data_column_sidecar()
+            ))),
+        );
     }
 
     // Test RPCResponse encoding/decoding for V1 messages
@@ -1308,6 +1497,33 @@ mod tests {
             ),
             Ok(Some(RPCResponse::MetaData(metadata_v2())))
         );
+
+        // A MetaDataV2 encodes as a MetaDataV3 with the minimum custody requirement
+        assert_eq!(
+            encode_then_decode_response(
+                SupportedProtocol::MetaDataV3,
+                RPCCodedResponse::Success(RPCResponse::MetaData(metadata_v2())),
+                ForkName::Deneb,
+                &chain_spec,
+            ),
+            Ok(Some(RPCResponse::MetaData(metadata_v3(
+                chain_spec.custody_requirement
+            ))))
+        );
+
+        assert_eq!(
+            encode_then_decode_response(
+                SupportedProtocol::MetaDataV3,
+                RPCCodedResponse::Success(RPCResponse::MetaData(metadata_v3(
+                    chain_spec.data_column_sidecar_subnet_count
+                ))),
+                ForkName::Deneb,
+                &chain_spec,
+            ),
+            Ok(Some(RPCResponse::MetaData(metadata_v3(
+                chain_spec.data_column_sidecar_subnet_count
+            ))))
+        );
     }
 
     // Test RPCResponse encoding/decoding for V2 messages
@@ -1476,6 +1692,57 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_encode_then_decode_data_columns() {
+        let chain_spec = Spec::default_spec();
+
+        assert_eq!(
+            encode_then_decode_response(
+                SupportedProtocol::DataColumnsByRangeV1,
+                RPCCodedResponse::Success(RPCResponse::DataColumnsByRange(
+                    electra_data_column_sidecar()
+                )),
+                ForkName::Electra,
+                &chain_spec
+            ),
+            Ok(Some(RPCResponse::DataColumnsByRange(
+                electra_data_column_sidecar()
+            ))),
+        );
+
+        assert_eq!(
+            encode_then_decode_response(
+                SupportedProtocol::DataColumnsByRootV1,
+                RPCCodedResponse::Success(RPCResponse::DataColumnsByRoot(
+                    electra_data_column_sidecar()
+                )),
+                ForkName::Electra,
+                &chain_spec
+            ),
+            Ok(Some(RPCResponse::DataColumnsByRoot(
+                electra_data_column_sidecar()
+            ))),
+        );
+
+        // Data columns with pre-Deneb context bytes should be rejected
+        for protocol in [
+            SupportedProtocol::DataColumnsByRangeV1,
+            SupportedProtocol::DataColumnsByRootV1,
+        ] {
+            let mut encoded = encode_without_length_checks(
+                empty_data_column_sidecar().as_ssz_bytes(),
+                ForkName::Capella,
+            )
+            .unwrap();
+
+            assert!(matches!(
+                decode_response(protocol, &mut encoded, ForkName::Capella, &chain_spec)
+                    .unwrap_err(),
+                RPCError::ErrorResponse(RPCResponseErrorCode::InvalidRequest, _),
+            ));
+        }
+    }
+
     #[test]
     fn test_encode_then_decode_request() {
         let chain_spec = Spec::default_spec();
@@ -1491,6 +1758,8 @@ mod tests {
             OutboundRequest::MetaData(MetadataRequest::new_v1()),
             OutboundRequest::BlobsByRange(blbrange_request()),
             OutboundRequest::BlobsByRoot(blbroot_request(&chain_spec)),
+            OutboundRequest::DataColumnsByRange(dcbrange_request()),
+            OutboundRequest::DataColumnsByRoot(dcbroot_request(&chain_spec)),
             OutboundRequest::MetaData(MetadataRequest::new_v2()),
         ];
 
@@ -36,6 +36,8 @@ pub enum OutboundRequest<E: EthSpec> {
     BlocksByRoot(BlocksByRootRequest),
     BlobsByRange(BlobsByRangeRequest),
     BlobsByRoot(BlobsByRootRequest),
+    DataColumnsByRoot(DataColumnsByRootRequest),
+    DataColumnsByRange(DataColumnsByRangeRequest),
     Ping(Ping),
     MetaData(MetadataRequest<E>),
 }
@@ -79,10 +81,24 @@ impl<E: EthSpec> OutboundRequest<E> {
                 SupportedProtocol::BlobsByRootV1,
                 Encoding::SSZSnappy,
             )],
+            OutboundRequest::DataColumnsByRoot(_) => vec![ProtocolId::new(
+                SupportedProtocol::DataColumnsByRootV1,
+                Encoding::SSZSnappy,
+            )],
+            OutboundRequest::DataColumnsByRange(_) => vec![ProtocolId::new(
+                SupportedProtocol::DataColumnsByRangeV1,
+                Encoding::SSZSnappy,
+            )],
             OutboundRequest::Ping(_) => vec![ProtocolId::new(
                 SupportedProtocol::PingV1,
                 Encoding::SSZSnappy,
             )],
+            // V3 is only offered once PeerDAS is scheduled, which is signalled by a V3 request
+            OutboundRequest::MetaData(MetadataRequest::V3(_)) => vec![
+                ProtocolId::new(SupportedProtocol::MetaDataV3, Encoding::SSZSnappy),
+                ProtocolId::new(SupportedProtocol::MetaDataV2, Encoding::SSZSnappy),
+                ProtocolId::new(SupportedProtocol::MetaDataV1, Encoding::SSZSnappy),
+            ],
             OutboundRequest::MetaData(_) => vec![
                 ProtocolId::new(SupportedProtocol::MetaDataV2, Encoding::SSZSnappy),
                 ProtocolId::new(SupportedProtocol::MetaDataV1, Encoding::SSZSnappy),
@@ -100,6 +116,8 @@ impl<E: EthSpec> OutboundRequest<E> {
             OutboundRequest::BlocksByRoot(req) => req.block_roots().len() as u64,
             OutboundRequest::BlobsByRange(req) => req.max_blobs_requested::<E>(),
             OutboundRequest::BlobsByRoot(req) => req.blob_ids.len() as u64,
+            OutboundRequest::DataColumnsByRoot(req) => req.data_column_ids.len() as u64,
+            OutboundRequest::DataColumnsByRange(req) => req.max_requested(),
             OutboundRequest::Ping(_) => 1,
             OutboundRequest::MetaData(_) => 1,
         }
@@ -113,6 +131,8 @@ impl<E: EthSpec> OutboundRequest<E> {
             OutboundRequest::BlocksByRoot(_) => false,
             OutboundRequest::BlobsByRange(_) => false,
             OutboundRequest::BlobsByRoot(_) => false,
+            OutboundRequest::DataColumnsByRoot(_) => false,
+            OutboundRequest::DataColumnsByRange(_) => false,
             OutboundRequest::Ping(_) => true,
             OutboundRequest::MetaData(_) => true,
         }
@@ -133,10 +153,13 @@ impl<E: EthSpec> OutboundRequest<E> {
             },
             OutboundRequest::BlobsByRange(_) => SupportedProtocol::BlobsByRangeV1,
             OutboundRequest::BlobsByRoot(_) => SupportedProtocol::BlobsByRootV1,
+            OutboundRequest::DataColumnsByRoot(_) => SupportedProtocol::DataColumnsByRootV1,
+            OutboundRequest::DataColumnsByRange(_) => SupportedProtocol::DataColumnsByRangeV1,
             OutboundRequest::Ping(_) => SupportedProtocol::PingV1,
             OutboundRequest::MetaData(req) => match req {
                 MetadataRequest::V1(_) => SupportedProtocol::MetaDataV1,
                 MetadataRequest::V2(_) => SupportedProtocol::MetaDataV2,
+                MetadataRequest::V3(_) => SupportedProtocol::MetaDataV3,
             },
         }
     }
@@ -151,6 +174,8 @@ impl<E: EthSpec> OutboundRequest<E> {
             OutboundRequest::BlocksByRoot(_) => ResponseTermination::BlocksByRoot,
             OutboundRequest::BlobsByRange(_) => ResponseTermination::BlobsByRange,
             OutboundRequest::BlobsByRoot(_) => ResponseTermination::BlobsByRoot,
+            OutboundRequest::DataColumnsByRoot(_) => ResponseTermination::DataColumnsByRoot,
+            OutboundRequest::DataColumnsByRange(_) => ResponseTermination::DataColumnsByRange,
             OutboundRequest::Status(_) => unreachable!(),
             OutboundRequest::Goodbye(_) => unreachable!(),
             OutboundRequest::Ping(_) => unreachable!(),
@@ -208,6 +233,10 @@ impl<E: EthSpec> std::fmt::Display for OutboundRequest<E> {
             OutboundRequest::BlocksByRoot(req) => write!(f, "Blocks by root: {:?}", req),
             OutboundRequest::BlobsByRange(req) => write!(f, "Blobs by range: {:?}", req),
             OutboundRequest::BlobsByRoot(req) => write!(f, "Blobs by root: {:?}", req),
+            OutboundRequest::DataColumnsByRoot(req) => write!(f, "Data columns by root: {:?}", req),
+            OutboundRequest::DataColumnsByRange(req) => {
+                write!(f, "Data columns by range: {:?}", req)
+            }
             OutboundRequest::Ping(ping) => write!(f, "Ping: {}", ping.data),
             OutboundRequest::MetaData(_) => write!(f, "MetaData request"),
         }
@@ -2,16 +2,19 @@ use std::sync::Arc;
 
 use libp2p::swarm::ConnectionId;
 use types::{
-    BlobSidecar, EthSpec, LightClientBootstrap, LightClientFinalityUpdate,
-    LightClientOptimisticUpdate, SignedBeaconBlock,
+    BlobSidecar, DataColumnSidecar, EthSpec, LightClientBootstrap, LightClientFinalityUpdate,
+    LightClientOptimisticUpdate, LightClientUpdate, SignedBeaconBlock,
 };
 
-use crate::rpc::methods::{BlobsByRangeRequest, BlobsByRootRequest};
+use crate::rpc::methods::{
+    BlobsByRangeRequest, BlobsByRootRequest, DataColumnsByRangeRequest, DataColumnsByRootRequest,
+};
 use crate::rpc::{
     methods::{
         BlocksByRangeRequest, BlocksByRootRequest, LightClientBootstrapRequest,
-        OldBlocksByRangeRequest, OldBlocksByRangeRequestV1, OldBlocksByRangeRequestV2,
-        RPCCodedResponse, RPCResponse, ResponseTermination, StatusMessage,
+        LightClientUpdatesByRangeRequest, OldBlocksByRangeRequest, OldBlocksByRangeRequestV1,
+        OldBlocksByRangeRequestV2, RPCCodedResponse, RPCResponse, ResponseTermination,
+        StatusMessage,
     },
     OutboundRequest, SubstreamId,
 };
@@ -73,8 +76,14 @@ pub enum Request {
     LightClientOptimisticUpdate,
     // light client finality update request
     LightClientFinalityUpdate,
+    // light client updates by range request
+    LightClientUpdatesByRange(LightClientUpdatesByRangeRequest),
     /// A request blobs root request.
     BlobsByRoot(BlobsByRootRequest),
+    /// A request data columns root request.
+    DataColumnsByRoot(DataColumnsByRootRequest),
+    /// A data columns by range request.
+    DataColumnsByRange(DataColumnsByRangeRequest),
 }
 
 impl<E: EthSpec> std::convert::From<Request> for OutboundRequest<E> {
@@ -99,11 +108,14 @@ impl<E: EthSpec> std::convert::From<Request> for OutboundRequest<E> {
             },
             Request::LightClientBootstrap(_)
             | Request::LightClientOptimisticUpdate
-            | Request::LightClientFinalityUpdate => {
+            | Request::LightClientFinalityUpdate
+            | Request::LightClientUpdatesByRange(_) => {
                 unreachable!("Lighthouse never makes an outbound light client request")
             }
             Request::BlobsByRange(r) => OutboundRequest::BlobsByRange(r),
             Request::BlobsByRoot(r) => OutboundRequest::BlobsByRoot(r),
+            Request::DataColumnsByRoot(r) => OutboundRequest::DataColumnsByRoot(r),
+            Request::DataColumnsByRange(r) => OutboundRequest::DataColumnsByRange(r),
             Request::Status(s) => OutboundRequest::Status(s),
         }
     }
@@ -127,12 +139,20 @@ pub enum Response<E: EthSpec> {
     BlocksByRoot(Option<Arc<SignedBeaconBlock<E>>>),
     /// A response to a get BLOBS_BY_ROOT request.
     BlobsByRoot(Option<Arc<BlobSidecar<E>>>),
+    /// A response to a get DATA_COLUMN_SIDECARS_BY_ROOT request.
+    DataColumnsByRoot(Option<Arc<DataColumnSidecar<E>>>),
+    /// A response to a get DATA_COLUMN_SIDECARS_BY_RANGE request. A None response signals the end
+    /// of the batch.
+    DataColumnsByRange(Option<Arc<DataColumnSidecar<E>>>),
     /// A response to a LightClientUpdate request.
     LightClientBootstrap(Arc<LightClientBootstrap<E>>),
     /// A response to a LightClientOptimisticUpdate request.
     LightClientOptimisticUpdate(Arc<LightClientOptimisticUpdate<E>>),
     /// A response to a LightClientFinalityUpdate request.
     LightClientFinalityUpdate(Arc<LightClientFinalityUpdate<E>>),
+    /// A response to a LightClientUpdatesByRange request. A None response signals the end of the
+    /// batch.
+    LightClientUpdatesByRange(Option<Arc<LightClientUpdate<E>>>),
 }
 
 impl<E: EthSpec> std::convert::From<Response<E>> for RPCCodedResponse<E> {
@@ -154,6 +174,16 @@ impl<E: EthSpec> std::convert::From<Response<E>> for RPCCodedResponse<E> {
                 Some(b) => RPCCodedResponse::Success(RPCResponse::BlobsByRange(b)),
                 None => RPCCodedResponse::StreamTermination(ResponseTermination::BlobsByRange),
             },
+            Response::DataColumnsByRoot(r) => match r {
+                Some(d) => RPCCodedResponse::Success(RPCResponse::DataColumnsByRoot(d)),
+                None => RPCCodedResponse::StreamTermination(ResponseTermination::DataColumnsByRoot),
+            },
+            Response::DataColumnsByRange(r) => match r {
+                Some(d) => RPCCodedResponse::Success(RPCResponse::DataColumnsByRange(d)),
+                None => {
+                    RPCCodedResponse::StreamTermination(ResponseTermination::DataColumnsByRange)
+                }
+            },
             Response::Status(s) => RPCCodedResponse::Success(RPCResponse::Status(s)),
             Response::LightClientBootstrap(b) => {
                 RPCCodedResponse::Success(RPCResponse::LightClientBootstrap(b))
@@ -164,6 +194,12 @@ impl<E: EthSpec> std::convert::From<Response<E>> for RPCCodedResponse<E> {
             Response::LightClientFinalityUpdate(f) => {
                 RPCCodedResponse::Success(RPCResponse::LightClientFinalityUpdate(f))
             }
+            Response::LightClientUpdatesByRange(r) => match r {
+                Some(u) => RPCCodedResponse::Success(RPCResponse::LightClientUpdatesByRange(u)),
+                None => RPCCodedResponse::StreamTermination(
+                    ResponseTermination::LightClientUpdatesByRange,
+                ),
+            },
         }
     }
 }
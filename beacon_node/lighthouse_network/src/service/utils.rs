@@ -1,5 +1,5 @@
 use crate::multiaddr::Protocol;
-use crate::rpc::{MetaData, MetaDataV1, MetaDataV2};
+use crate::rpc::{MetaData, MetaDataV1, MetaDataV2, MetaDataV3};
 use crate::types::{
     error, EnrAttestationBitfield, EnrSyncCommitteeBitfield, GossipEncoding, GossipKind,
 };
@@ -165,8 +165,11 @@ pub fn strip_peer_id(addr: &mut Multiaddr) {
 }
 
 /// Load metadata from persisted file. Return default metadata if loading fails.
+///
+/// A V3 metadata carrying `custody_subnet_count` is built if PeerDAS is scheduled, otherwise a V2.
 pub fn load_or_build_metadata<E: EthSpec>(
     network_dir: &std::path::Path,
+    custody_subnet_count: Option<u64>,
     log: &slog::Logger,
 ) -> MetaData<E> {
     // We load a V2 metadata version by default (regardless of current fork)
@@ -182,42 +185,48 @@ pub fn load_or_build_metadata<E: EthSpec>(
     if let Ok(mut metadata_file) = File::open(metadata_path) {
         let mut metadata_ssz = Vec::new();
         if metadata_file.read_to_end(&mut metadata_ssz).is_ok() {
-            // Attempt to read a MetaDataV2 version from the persisted file,
-            // if that fails, read MetaDataV1
-            match MetaDataV2::<E>::from_ssz_bytes(&metadata_ssz) {
+            // Attempt to read a MetaDataV3 version from the persisted file, if that fails, read
+            // MetaDataV2 and then MetaDataV1. The versions have distinct fixed lengths.
+            let persisted_metadata = MetaDataV3::<E>::from_ssz_bytes(&metadata_ssz)
+                .map(MetaData::V3)
+                .or_else(|_| MetaDataV2::<E>::from_ssz_bytes(&metadata_ssz).map(MetaData::V2))
+                .or_else(|_| MetaDataV1::<E>::from_ssz_bytes(&metadata_ssz).map(MetaData::V1));
+            match persisted_metadata {
                 Ok(persisted_metadata) => {
-                    meta_data.seq_number = persisted_metadata.seq_number;
-                    // Increment seq number if persisted attnet is not default
-                    if persisted_metadata.attnets != meta_data.attnets
-                        || persisted_metadata.syncnets != meta_data.syncnets
+                    meta_data.seq_number = *persisted_metadata.seq_number();
+                    // Increment seq number if persisted metadata differs from the default or its
+                    // version is updated
+                    if *persisted_metadata.attnets() != meta_data.attnets
+                        || persisted_metadata.syncnets().ok() != Some(&meta_data.syncnets)
+                        || persisted_metadata.custody_subnet_count().ok().copied()
+                            != custody_subnet_count
                     {
                         meta_data.seq_number += 1;
                     }
                     debug!(log, "Loaded metadata from disk");
                 }
-                Err(_) => {
-                    match MetaDataV1::<E>::from_ssz_bytes(&metadata_ssz) {
-                        Ok(persisted_metadata) => {
-                            let persisted_metadata = MetaData::V1(persisted_metadata);
-                            // Increment seq number as the persisted metadata version is updated
-                            meta_data.seq_number = *persisted_metadata.seq_number() + 1;
-                            debug!(log, "Loaded metadata from disk");
-                        }
-                        Err(e) => {
-                            debug!(
-                                log,
-                                "Metadata from file could not be decoded";
-                                "error" => ?e,
-                            );
-                        }
-                    }
+                Err(e) => {
+                    debug!(
+                        log,
+                        "Metadata from file could not be decoded";
+                        "error" => ?e,
+                    );
                 }
             }
         }
     };
 
     // Wrap the MetaData
-    let meta_data = MetaData::V2(meta_data);
+    let meta_data = if let Some(custody_subnet_count) = custody_subnet_count {
+        MetaData::V3(MetaDataV3 {
+            seq_number: meta_data.seq_number,
+            attnets: meta_data.attnets,
+            syncnets: meta_data.syncnets,
+            custody_subnet_count,
+        })
+    } else {
+        MetaData::V2(meta_data)
+    };
 
     debug!(log, "Metadata sequence number"; "seq_num" => meta_data.seq_number());
     save_metadata_to_disk(network_dir, meta_data.clone(), log);
@@ -273,6 +282,7 @@ pub(crate) fn save_metadata_to_disk<E: EthSpec>(
     let metadata_bytes = match metadata {
         MetaData::V1(md) => md.as_ssz_bytes(),
         MetaData::V2(md) => md.as_ssz_bytes(),
+        MetaData::V3(md) => md.as_ssz_bytes(),
     };
     match File::create(dir.join(METADATA_FILENAME)).and_then(|mut f| f.write_all(&metadata_bytes)) {
         Ok(_) => {
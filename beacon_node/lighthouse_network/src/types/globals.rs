@@ -4,10 +4,12 @@ use crate::rpc::{MetaData, MetaDataV2};
 use crate::types::{BackFillState, SyncState};
 use crate::Client;
 use crate::EnrExt;
+use crate::Eth2Enr;
 use crate::{Enr, GossipTopic, Multiaddr, PeerId};
 use parking_lot::RwLock;
 use std::collections::HashSet;
-use types::EthSpec;
+use std::sync::Arc;
+use types::{ChainSpec, ColumnIndex, DataColumnSubnetId, EthSpec};
 
 pub struct NetworkGlobals<E: EthSpec> {
     /// The current local ENR.
@@ -26,6 +28,8 @@ pub struct NetworkGlobals<E: EthSpec> {
     pub sync_state: RwLock<SyncState>,
     /// The current state of the backfill sync.
     pub backfill_state: RwLock<BackFillState>,
+    /// The chain spec, used to compute data column custody assignments.
+    pub spec: Arc<ChainSpec>,
 }
 
 impl<E: EthSpec> NetworkGlobals<E> {
@@ -35,6 +39,7 @@ impl<E: EthSpec> NetworkGlobals<E> {
         trusted_peers: Vec<PeerId>,
         disable_peer_scoring: bool,
         log: &slog::Logger,
+        spec: Arc<ChainSpec>,
     ) -> Self {
         NetworkGlobals {
             local_enr: RwLock::new(enr.clone()),
@@ -45,6 +50,7 @@ impl<E: EthSpec> NetworkGlobals<E> {
             gossipsub_subscriptions: RwLock::new(HashSet::new()),
             sync_state: RwLock::new(SyncState::Stalled),
             backfill_state: RwLock::new(BackFillState::NotRequired),
+            spec,
         }
     }
 
@@ -103,6 +109,31 @@ impl<E: EthSpec> NetworkGlobals<E> {
             .unwrap_or_default()
     }
 
+    /// Compute custody data column subnets the node is assigned to custody, as advertised in the
+    /// local ENR.
+    pub fn custody_subnets(&self) -> impl Iterator<Item = DataColumnSubnetId> {
+        let enr = self.local_enr();
+        let custody_subnet_count = enr.custody_subnet_count::<E>(&self.spec);
+        DataColumnSubnetId::compute_custody_subnets::<E>(
+            enr.node_id().raw().into(),
+            custody_subnet_count,
+            &self.spec,
+        )
+    }
+
+    /// Compute custody data columns the node is assigned to custody, as advertised in the local
+    /// ENR.
+    pub fn custody_columns(&self) -> Vec<ColumnIndex> {
+        let enr = self.local_enr();
+        let custody_subnet_count = enr.custody_subnet_count::<E>(&self.spec);
+        DataColumnSubnetId::compute_custody_columns::<E>(
+            enr.node_id().raw().into(),
+            custody_subnet_count,
+            &self.spec,
+        )
+        .collect()
+    }
+
     /// Updates the syncing state of the node.
     ///
     /// The old state is returned
@@ -111,7 +142,11 @@ impl<E: EthSpec> NetworkGlobals<E> {
     }
 
     /// TESTING ONLY. Build a dummy NetworkGlobals instance.
-    pub fn new_test_globals(trusted_peers: Vec<PeerId>, log: &slog::Logger) -> NetworkGlobals<E> {
+    pub fn new_test_globals(
+        trusted_peers: Vec<PeerId>,
+        log: &slog::Logger,
+        spec: Arc<ChainSpec>,
+    ) -> NetworkGlobals<E> {
         use crate::CombinedKeyExt;
         let keypair = libp2p::identity::secp256k1::Keypair::generate();
         let enr_key: discv5::enr::CombinedKey = discv5::enr::CombinedKey::from_secp256k1(&keypair);
@@ -126,6 +161,7 @@ impl<E: EthSpec> NetworkGlobals<E> {
             trusted_peers,
             false,
             log,
+            spec,
         )
     }
 }
@@ -1340,6 +1340,21 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         let block = verified_block.block.block_cloned();
         let block_root = verified_block.block_root;
 
+        // Try to fetch the block's blobs from the execution layer's blob pool concurrently with
+        // block processing, rather than waiting for them to arrive over gossip.
+        if block.num_expected_blobs() > 0 {
+            let self_clone = self.clone();
+            let block = block.clone();
+            self.executor.spawn(
+                async move {
+                    self_clone
+                        .fetch_engine_blobs_and_publish(block, block_root)
+                        .await
+                },
+                "fetch_blobs_gossip",
+            );
+        }
+
         // TODO(block source)
 
         let result = self
@@ -1,19 +1,27 @@
 use crate::sync::manager::BlockProcessType;
 use crate::{service::NetworkMessage, sync::manager::SyncMessage};
 use beacon_chain::block_verification_types::RpcBlock;
+use beacon_chain::fetch_blobs::{fetch_and_process_engine_blobs, FetchEngineBlobError};
 use beacon_chain::{builder::Witness, eth1_chain::CachingEth1Backend, BeaconChain};
-use beacon_chain::{BeaconChainTypes, NotifyExecutionLayer};
+use beacon_chain::{
+    AvailabilityProcessingStatus, BeaconChainTypes, BlockError, NotifyExecutionLayer,
+};
 use beacon_processor::{
     work_reprocessing_queue::ReprocessQueueMessage, BeaconProcessorChannels, BeaconProcessorSend,
     DuplicateCache, GossipAggregatePackage, GossipAttestationPackage, Work,
     WorkEvent as BeaconWorkEvent,
 };
-use lighthouse_network::rpc::methods::{BlobsByRangeRequest, BlobsByRootRequest};
+use lighthouse_network::rpc::methods::{
+    BlobsByRangeRequest, BlobsByRootRequest, DataColumnsByRangeRequest, DataColumnsByRootRequest,
+};
 use lighthouse_network::{
-    rpc::{BlocksByRangeRequest, BlocksByRootRequest, LightClientBootstrapRequest, StatusMessage},
-    Client, MessageId, NetworkGlobals, PeerId, PeerRequestId,
+    rpc::{
+        BlocksByRangeRequest, BlocksByRootRequest, LightClientBootstrapRequest,
+        LightClientUpdatesByRangeRequest, StatusMessage,
+    },
+    Client, MessageId, NetworkGlobals, PeerId, PeerRequestId, PubsubMessage,
 };
-use slog::{debug, Logger};
+use slog::{debug, error, Logger};
 use slot_clock::ManualSlotClock;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -602,6 +610,40 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         })
     }
 
+    /// Create a new work event to process `DataColumnsByRootRequest`s from the RPC network.
+    pub fn send_data_columns_by_roots_request(
+        self: &Arc<Self>,
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        request: DataColumnsByRootRequest,
+    ) -> Result<(), Error<T::EthSpec>> {
+        let processor = self.clone();
+        let process_fn =
+            move || processor.handle_data_columns_by_root_request(peer_id, request_id, request);
+
+        self.try_send(BeaconWorkEvent {
+            drop_during_sync: false,
+            work: Work::DataColumnsByRootsRequest(Box::new(process_fn)),
+        })
+    }
+
+    /// Create a new work event to process `DataColumnsByRangeRequest`s from the RPC network.
+    pub fn send_data_columns_by_range_request(
+        self: &Arc<Self>,
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        request: DataColumnsByRangeRequest,
+    ) -> Result<(), Error<T::EthSpec>> {
+        let processor = self.clone();
+        let process_fn =
+            move || processor.handle_data_columns_by_range_request(peer_id, request_id, request);
+
+        self.try_send(BeaconWorkEvent {
+            drop_during_sync: false,
+            work: Work::DataColumnsByRangeRequest(Box::new(process_fn)),
+        })
+    }
+
     /// Create a new work event to process `LightClientBootstrap`s from the RPC network.
     pub fn send_light_client_bootstrap_request(
         self: &Arc<Self>,
@@ -650,6 +692,23 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         })
     }
 
+    /// Create a new work event to process a `LightClientUpdatesByRange` request from the RPC network.
+    pub fn send_light_client_updates_by_range_request(
+        self: &Arc<Self>,
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        request: LightClientUpdatesByRangeRequest,
+    ) -> Result<(), Error<T::EthSpec>> {
+        let processor = self.clone();
+        let process_fn =
+            move || processor.handle_light_client_updates_by_range(peer_id, request_id, request);
+
+        self.try_send(BeaconWorkEvent {
+            drop_during_sync: true,
+            work: Work::LightClientUpdatesByRangeRequest(Box::new(process_fn)),
+        })
+    }
+
     /// Send a message to `sync_tx`.
     ///
     /// Creates a log if there is an internal error.
@@ -660,6 +719,67 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         });
     }
 
+    /// Fetches the blobs for `block` from the execution layer's blob pool and imports them. Blobs
+    /// that we have not yet seen on gossip are published to the network.
+    pub async fn fetch_engine_blobs_and_publish(
+        self: &Arc<Self>,
+        block: Arc<SignedBeaconBlock<T::EthSpec>>,
+        block_root: Hash256,
+    ) {
+        let self_cloned = self.clone();
+        let publish_fn = move |blobs: Vec<Arc<BlobSidecar<T::EthSpec>>>| {
+            self_cloned.send_network_message(NetworkMessage::Publish {
+                messages: blobs
+                    .into_iter()
+                    .map(|blob| PubsubMessage::BlobSidecar(Box::new((blob.index, blob))))
+                    .collect(),
+            });
+        };
+
+        match fetch_and_process_engine_blobs(self.chain.clone(), block_root, block, publish_fn)
+            .await
+        {
+            Ok(Some(AvailabilityProcessingStatus::Imported(_))) => {
+                debug!(
+                    self.log,
+                    "Block components retrieved from EL";
+                    "result" => "imported block and blobs",
+                    "block_root" => %block_root,
+                );
+                self.chain.recompute_head_at_current_slot().await;
+            }
+            Ok(Some(AvailabilityProcessingStatus::MissingComponents(_, _))) => {
+                debug!(
+                    self.log,
+                    "Still missing blobs after engine blobs processed";
+                    "block_root" => %block_root,
+                );
+            }
+            Ok(None) => {
+                debug!(
+                    self.log,
+                    "Fetch blobs completed without import";
+                    "block_root" => %block_root,
+                );
+            }
+            Err(FetchEngineBlobError::BlobProcessingError(BlockError::BlockIsAlreadyKnown(_))) => {
+                debug!(
+                    self.log,
+                    "Ignoring engine blobs for already imported block";
+                    "block_root" => %block_root,
+                );
+            }
+            Err(e) => {
+                error!(
+                    self.log,
+                    "Error fetching or processing blobs from EL";
+                    "error" => ?e,
+                    "block_root" => %block_root,
+                );
+            }
+        }
+    }
+
     /// Send a message to `network_tx`.
     ///
     /// Creates a log if there is an internal error.
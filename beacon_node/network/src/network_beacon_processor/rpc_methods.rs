@@ -4,7 +4,10 @@ use crate::status::ToStatusMessage;
 use crate::sync::SyncMessage;
 use beacon_chain::{BeaconChainError, BeaconChainTypes, HistoricalBlockError, WhenSlotSkipped};
 use itertools::process_results;
-use lighthouse_network::rpc::methods::{BlobsByRangeRequest, BlobsByRootRequest};
+use lighthouse_network::rpc::methods::{
+    BlobsByRangeRequest, BlobsByRootRequest, DataColumnsByRangeRequest, DataColumnsByRootRequest,
+    LightClientUpdatesByRangeRequest,
+};
 use lighthouse_network::rpc::*;
 use lighthouse_network::{PeerId, PeerRequestId, ReportSource, Response, SyncInfo};
 use slog::{debug, error, warn};
@@ -314,6 +317,78 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         Ok(())
     }
 
+    /// Handle a `DataColumnsByRoot` request from the peer.
+    pub fn handle_data_columns_by_root_request(
+        self: Arc<Self>,
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        request: DataColumnsByRootRequest,
+    ) {
+        self.terminate_response_stream(
+            peer_id,
+            request_id,
+            self.handle_data_columns_by_root_request_inner(peer_id, request_id, request),
+            Response::DataColumnsByRoot,
+        );
+    }
+
+    /// Handle a `DataColumnsByRoot` request from the peer.
+    pub fn handle_data_columns_by_root_request_inner(
+        &self,
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        request: DataColumnsByRootRequest,
+    ) -> Result<(), (RPCResponseErrorCode, &'static str)> {
+        let mut send_data_column_count = 0;
+
+        for data_column_id in request.data_column_ids.as_slice() {
+            // First attempt to get the data column from the RPC cache, then from the store.
+            let data_column = match self
+                .chain
+                .data_availability_checker
+                .get_data_column(data_column_id)
+            {
+                Ok(Some(data_column)) => Some(data_column),
+                _ => match self
+                    .chain
+                    .get_data_column(&data_column_id.block_root, &data_column_id.index)
+                {
+                    Ok(data_column) => data_column,
+                    Err(e) => {
+                        debug!(
+                            self.log,
+                            "Error fetching data column for peer";
+                            "peer" => %peer_id,
+                            "request_root" => ?data_column_id.block_root,
+                            "column_index" => data_column_id.index,
+                            "error" => ?e,
+                        );
+                        None
+                    }
+                },
+            };
+
+            if let Some(data_column) = data_column {
+                self.send_response(
+                    peer_id,
+                    Response::DataColumnsByRoot(Some(data_column)),
+                    request_id,
+                );
+                send_data_column_count += 1;
+            }
+        }
+
+        debug!(
+            self.log,
+            "DataColumnsByRoot outgoing response processed";
+            "peer" => %peer_id,
+            "request_count" => request.data_column_ids.len(),
+            "returned" => send_data_column_count
+        );
+
+        Ok(())
+    }
+
     /// Handle a `LightClientBootstrap` request from the peer.
     pub fn handle_light_client_bootstrap(
         self: &Arc<Self>,
@@ -394,6 +469,68 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         );
     }
 
+    /// Handle a `LightClientUpdatesByRange` request from the peer.
+    pub fn handle_light_client_updates_by_range(
+        self: &Arc<Self>,
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        request: LightClientUpdatesByRangeRequest,
+    ) {
+        self.terminate_response_stream(
+            peer_id,
+            request_id,
+            self.handle_light_client_updates_by_range_inner(peer_id, request_id, request),
+            Response::LightClientUpdatesByRange,
+        );
+    }
+
+    /// Handle a `LightClientUpdatesByRange` request from the peer.
+    fn handle_light_client_updates_by_range_inner(
+        &self,
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        req: LightClientUpdatesByRangeRequest,
+    ) -> Result<(), (RPCResponseErrorCode, &'static str)> {
+        debug!(self.log, "Received LightClientUpdatesByRange Request";
+            "peer_id" => %peer_id,
+            "start_period" => req.start_period,
+            "count" => req.count,
+        );
+
+        let updates = self
+            .chain
+            .get_light_client_updates(req.start_period, req.max_requested())
+            .map_err(|e| {
+                error!(self.log, "Error getting light client updates";
+                    "start_period" => req.start_period,
+                    "count" => req.count,
+                    "peer" => %peer_id,
+                    "error" => ?e
+                );
+                (RPCResponseErrorCode::ServerError, "Database error")
+            })?;
+
+        let sent_updates = updates.len();
+        for update in updates {
+            self.send_response(
+                peer_id,
+                Response::LightClientUpdatesByRange(Some(Arc::new(update))),
+                request_id,
+            );
+        }
+
+        debug!(
+            self.log,
+            "LightClientUpdatesByRange outgoing response processed";
+            "peer" => %peer_id,
+            "start_period" => req.start_period,
+            "requested" => req.count,
+            "returned" => sent_updates
+        );
+
+        Ok(())
+    }
+
     /// Handle a `BlocksByRange` request from the peer.
     pub async fn handle_blocks_by_range_request(
         self: Arc<Self>,
@@ -697,6 +834,215 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             };
         }
 
+        let block_roots = self.get_block_roots_for_slot_range(
+            peer_id,
+            req.start_slot,
+            req.count,
+            "BlobsByRange",
+        )?;
+
+        let current_slot = self
+            .chain
+            .slot()
+            .unwrap_or_else(|_| self.chain.slot_clock.genesis_slot());
+
+        let log_results = |peer_id, req: BlobsByRangeRequest, blobs_sent| {
+            debug!(
+                self.log,
+                "BlobsByRange outgoing response processed";
+                "peer" => %peer_id,
+                "start_slot" => req.start_slot,
+                "current_slot" => current_slot,
+                "requested" => req.count,
+                "returned" => blobs_sent
+            );
+        };
+
+        let mut blobs_sent = 0;
+
+        for root in block_roots {
+            match self.chain.get_blobs(&root) {
+                Ok(blob_sidecar_list) => {
+                    for blob_sidecar in blob_sidecar_list.iter() {
+                        blobs_sent += 1;
+                        self.send_network_message(NetworkMessage::SendResponse {
+                            peer_id,
+                            response: Response::BlobsByRange(Some(blob_sidecar.clone())),
+                            id: request_id,
+                        });
+                    }
+                }
+                Err(e) => {
+                    error!(
+                        self.log,
+                        "Error fetching blobs block root";
+                        "request" => ?req,
+                        "peer" => %peer_id,
+                        "block_root" => ?root,
+                        "error" => ?e
+                    );
+                    log_results(peer_id, req, blobs_sent);
+
+                    return Err((
+                        RPCResponseErrorCode::ServerError,
+                        "No blobs and failed fetching corresponding block",
+                    ));
+                }
+            }
+        }
+        log_results(peer_id, req, blobs_sent);
+
+        Ok(())
+    }
+
+    /// Handle a `DataColumnsByRange` request from the peer.
+    pub fn handle_data_columns_by_range_request(
+        self: Arc<Self>,
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        req: DataColumnsByRangeRequest,
+    ) {
+        self.terminate_response_stream(
+            peer_id,
+            request_id,
+            self.handle_data_columns_by_range_request_inner(peer_id, request_id, req),
+            Response::DataColumnsByRange,
+        );
+    }
+
+    /// Handle a `DataColumnsByRange` request from the peer.
+    fn handle_data_columns_by_range_request_inner(
+        &self,
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        req: DataColumnsByRangeRequest,
+    ) -> Result<(), (RPCResponseErrorCode, &'static str)> {
+        debug!(self.log, "Received DataColumnsByRange Request";
+            "peer_id" => %peer_id,
+            "count" => req.count,
+            "start_slot" => req.start_slot,
+            "columns" => ?req.columns,
+        );
+
+        // Should not send more than max request data columns
+        if req.max_requested() > self.chain.spec.max_request_data_column_sidecars {
+            return Err((
+                RPCResponseErrorCode::InvalidRequest,
+                "Request exceeded `MAX_REQUEST_DATA_COLUMN_SIDECARS`",
+            ));
+        }
+
+        let request_start_slot = Slot::from(req.start_slot);
+
+        let data_availability_boundary_slot = match self.chain.data_availability_boundary() {
+            Some(boundary) => boundary.start_slot(T::EthSpec::slots_per_epoch()),
+            None => {
+                debug!(self.log, "Deneb fork is disabled");
+                return Err((
+                    RPCResponseErrorCode::InvalidRequest,
+                    "Deneb fork is disabled",
+                ));
+            }
+        };
+
+        // Data columns are stored in the blobs database and pruned along with blobs by
+        // `try_prune_blobs`, so the oldest blob slot is also the oldest data column slot.
+        let oldest_data_column_slot = self
+            .chain
+            .store
+            .get_blob_info()
+            .oldest_blob_slot
+            .unwrap_or(data_availability_boundary_slot);
+        if request_start_slot < oldest_data_column_slot {
+            debug!(
+                self.log,
+                "Range request start slot is older than data availability boundary.";
+                "requested_slot" => request_start_slot,
+                "oldest_data_column_slot" => oldest_data_column_slot,
+                "data_availability_boundary" => data_availability_boundary_slot
+            );
+
+            return if data_availability_boundary_slot < oldest_data_column_slot {
+                Err((
+                    RPCResponseErrorCode::ResourceUnavailable,
+                    "data columns pruned within boundary",
+                ))
+            } else {
+                Err((
+                    RPCResponseErrorCode::InvalidRequest,
+                    "Req outside availability period",
+                ))
+            };
+        }
+
+        let block_roots = self.get_block_roots_for_slot_range(
+            peer_id,
+            req.start_slot,
+            req.count,
+            "DataColumnsByRange",
+        )?;
+
+        let mut data_columns_sent = 0;
+
+        for root in block_roots {
+            for index in &req.columns {
+                match self.chain.get_data_column(&root, index) {
+                    Ok(Some(data_column_sidecar)) => {
+                        data_columns_sent += 1;
+                        self.send_network_message(NetworkMessage::SendResponse {
+                            peer_id,
+                            response: Response::DataColumnsByRange(Some(data_column_sidecar)),
+                            id: request_id,
+                        });
+                    }
+                    Ok(None) => {} // no-op
+                    Err(e) => {
+                        error!(
+                            self.log,
+                            "Error fetching data columns block root";
+                            "request" => ?req,
+                            "peer" => %peer_id,
+                            "block_root" => ?root,
+                            "error" => ?e
+                        );
+                        return Err((
+                            RPCResponseErrorCode::ServerError,
+                            "No data columns and failed fetching corresponding block",
+                        ));
+                    }
+                }
+            }
+        }
+
+        let current_slot = self
+            .chain
+            .slot()
+            .unwrap_or_else(|_| self.chain.slot_clock.genesis_slot());
+
+        debug!(
+            self.log,
+            "DataColumnsByRange outgoing response processed";
+            "peer" => %peer_id,
+            "start_slot" => req.start_slot,
+            "current_slot" => current_slot,
+            "requested" => req.count,
+            "returned" => data_columns_sent
+        );
+
+        Ok(())
+    }
+
+    /// Returns the roots of the canonical blocks in the slot range requested by a `*ByRange`
+    /// request, with skip slots removed.
+    fn get_block_roots_for_slot_range(
+        &self,
+        peer_id: PeerId,
+        req_start_slot: u64,
+        req_count: u64,
+        req_type: &str,
+    ) -> Result<Vec<Hash256>, (RPCResponseErrorCode, &'static str)> {
+        let request_start_slot = Slot::from(req_start_slot);
+
         let forwards_block_root_iter =
             match self.chain.forwards_iter_block_roots(request_start_slot) {
                 Ok(iter) => iter,
@@ -714,7 +1060,9 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 }
                 Err(e) => {
                     error!(self.log, "Unable to obtain root iter";
-                        "request" => ?req,
+                        "req_type" => req_type,
+                        "start_slot" => req_start_slot,
+                        "count" => req_count,
                         "peer" => %peer_id,
                         "error" => ?e
                     );
@@ -724,7 +1072,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
 
         // Use `WhenSlotSkipped::Prev` to get the most recent block root prior to
         // `request_start_slot` in order to check whether the `request_start_slot` is a skip.
-        let mut last_block_root = req.start_slot.checked_sub(1).and_then(|prev_slot| {
+        let mut last_block_root = req_start_slot.checked_sub(1).and_then(|prev_slot| {
             self.chain
                 .block_root_at_slot(Slot::new(prev_slot), WhenSlotSkipped::Prev)
                 .ok()
@@ -733,7 +1081,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
 
         // Pick out the required blocks, ignoring skip-slots.
         let maybe_block_roots = process_results(forwards_block_root_iter, |iter| {
-            iter.take_while(|(_, slot)| slot.as_u64() < req.start_slot.saturating_add(req.count))
+            iter.take_while(|(_, slot)| slot.as_u64() < req_start_slot.saturating_add(req_count))
                 // map skip slots to None
                 .map(|(root, _)| {
                     let result = if Some(root) == last_block_root {
@@ -747,72 +1095,20 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 .collect::<Vec<Option<Hash256>>>()
         });
 
-        let block_roots = match maybe_block_roots {
-            Ok(block_roots) => block_roots,
+        match maybe_block_roots {
+            // remove all skip slots
+            Ok(block_roots) => Ok(block_roots.into_iter().flatten().collect()),
             Err(e) => {
                 error!(self.log, "Error during iteration over blocks";
-                    "request" => ?req,
+                    "req_type" => req_type,
+                    "start_slot" => req_start_slot,
+                    "count" => req_count,
                     "peer" => %peer_id,
                     "error" => ?e
                 );
-                return Err((RPCResponseErrorCode::ServerError, "Database error"));
-            }
-        };
-
-        let current_slot = self
-            .chain
-            .slot()
-            .unwrap_or_else(|_| self.chain.slot_clock.genesis_slot());
-
-        let log_results = |peer_id, req: BlobsByRangeRequest, blobs_sent| {
-            debug!(
-                self.log,
-                "BlobsByRange outgoing response processed";
-                "peer" => %peer_id,
-                "start_slot" => req.start_slot,
-                "current_slot" => current_slot,
-                "requested" => req.count,
-                "returned" => blobs_sent
-            );
-        };
-
-        // remove all skip slots
-        let block_roots = block_roots.into_iter().flatten();
-        let mut blobs_sent = 0;
-
-        for root in block_roots {
-            match self.chain.get_blobs(&root) {
-                Ok(blob_sidecar_list) => {
-                    for blob_sidecar in blob_sidecar_list.iter() {
-                        blobs_sent += 1;
-                        self.send_network_message(NetworkMessage::SendResponse {
-                            peer_id,
-                            response: Response::BlobsByRange(Some(blob_sidecar.clone())),
-                            id: request_id,
-                        });
-                    }
-                }
-                Err(e) => {
-                    error!(
-                        self.log,
-                        "Error fetching blobs block root";
-                        "request" => ?req,
-                        "peer" => %peer_id,
-                        "block_root" => ?root,
-                        "error" => ?e
-                    );
-                    log_results(peer_id, req, blobs_sent);
-
-                    return Err((
-                        RPCResponseErrorCode::ServerError,
-                        "No blobs and failed fetching corresponding block",
-                    ));
-                }
+                Err((RPCResponseErrorCode::ServerError, "Database error"))
             }
         }
-        log_results(peer_id, req, blobs_sent);
-
-        Ok(())
     }
 
     /// Helper function to ensure single item protocol always end with either a single chunk or an
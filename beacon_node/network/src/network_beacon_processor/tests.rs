@@ -15,7 +15,9 @@ use beacon_chain::test_utils::{
 use beacon_chain::{BeaconChain, WhenSlotSkipped};
 use beacon_processor::{work_reprocessing_queue::*, *};
 use lighthouse_network::discovery::ConnectionId;
-use lighthouse_network::rpc::methods::BlobsByRangeRequest;
+use lighthouse_network::rpc::methods::{
+    BlobsByRangeRequest, DataColumnsByRangeRequest, DataColumnsByRootRequest,
+};
 use lighthouse_network::rpc::SubstreamId;
 use lighthouse_network::{
     discv5::enr::{self, CombinedKey},
@@ -30,9 +32,9 @@ use std::time::Duration;
 use tokio::sync::mpsc;
 use types::blob_sidecar::FixedBlobSidecarList;
 use types::{
-    Attestation, AttesterSlashing, BlobSidecar, BlobSidecarList, Epoch, Hash256, MainnetEthSpec,
-    ProposerSlashing, SignedAggregateAndProof, SignedBeaconBlock, SignedVoluntaryExit, Slot,
-    SubnetId,
+    Attestation, AttesterSlashing, BlobSidecar, BlobSidecarList, ColumnIndex, DataColumnIdentifier,
+    DataColumnSidecar, Epoch, Hash256, MainnetEthSpec, ProposerSlashing, SignedAggregateAndProof,
+    SignedBeaconBlock, SignedVoluntaryExit, Slot, SubnetId,
 };
 
 type E = MainnetEthSpec;
@@ -204,7 +206,14 @@ impl TestRig {
         });
         let enr_key = CombinedKey::generate_secp256k1();
         let enr = enr::Enr::builder().build(&enr_key).unwrap();
-        let network_globals = Arc::new(NetworkGlobals::new(enr, meta_data, vec![], false, &log));
+        let network_globals = Arc::new(NetworkGlobals::new(
+            enr,
+            meta_data,
+            vec![],
+            false,
+            &log,
+            Arc::new(chain.spec.clone()),
+        ));
 
         let executor = harness.runtime.task_executor.clone();
 
@@ -359,6 +368,78 @@ impl TestRig {
             .unwrap();
     }
 
+    pub fn enqueue_data_columns_by_range_request(&self, count: u64, columns: Vec<ColumnIndex>) {
+        self.network_beacon_processor
+            .send_data_columns_by_range_request(
+                PeerId::random(),
+                (ConnectionId::new_unchecked(42), SubstreamId::new(24)),
+                DataColumnsByRangeRequest {
+                    start_slot: 0,
+                    count,
+                    columns,
+                },
+            )
+            .unwrap();
+    }
+
+    pub fn enqueue_data_columns_by_root_request(&self, data_column_ids: Vec<DataColumnIdentifier>) {
+        self.network_beacon_processor
+            .send_data_columns_by_roots_request(
+                PeerId::random(),
+                (ConnectionId::new_unchecked(42), SubstreamId::new(24)),
+                DataColumnsByRootRequest::new(data_column_ids, &self.chain.spec),
+            )
+            .unwrap();
+    }
+
+    /// Store the `columns` of every post-Deneb block in the first `slot_count` slots, returning
+    /// the ids of the stored columns.
+    ///
+    /// The columns carry no cells, since serving them does not verify their contents.
+    pub fn store_data_columns(
+        &self,
+        slot_count: u64,
+        columns: &[ColumnIndex],
+    ) -> Vec<DataColumnIdentifier> {
+        let mut data_column_ids = vec![];
+        for slot in 0..slot_count {
+            let Some(block_root) = self
+                .chain
+                .block_root_at_slot(Slot::new(slot), WhenSlotSkipped::None)
+                .unwrap()
+            else {
+                continue;
+            };
+            let block = self.chain.get_blinded_block(&block_root).unwrap().unwrap();
+            let Ok(kzg_commitments) = block.message().body().blob_kzg_commitments() else {
+                continue;
+            };
+            let data_columns = columns
+                .iter()
+                .map(|&index| {
+                    Arc::new(DataColumnSidecar {
+                        index,
+                        column: <_>::default(),
+                        kzg_commitments: kzg_commitments.clone(),
+                        kzg_proofs: <_>::default(),
+                        signed_block_header: block.signed_block_header(),
+                        kzg_commitments_inclusion_proof: block
+                            .message()
+                            .body()
+                            .kzg_commitments_merkle_proof()
+                            .unwrap(),
+                    })
+                })
+                .collect::<Vec<_>>();
+            data_column_ids.extend(data_columns.iter().map(|data_column| data_column.id()));
+            self.chain
+                .store
+                .put_data_columns(&block_root, data_columns)
+                .unwrap();
+        }
+        data_column_ids
+    }
+
     pub fn enqueue_backfill_batch(&self) {
         self.network_beacon_processor
             .send_chain_segment(
@@ -1115,3 +1196,71 @@ async fn test_blobs_by_range() {
     }
     assert_eq!(blob_count, actual_count);
 }
+
+#[tokio::test]
+async fn test_data_columns_by_range() {
+    if test_spec::<E>().deneb_fork_epoch.is_none() {
+        return;
+    };
+    let mut rig = TestRig::new(64).await;
+    let slot_count = 32;
+    let columns = vec![0, 5, 17];
+    let data_column_count = rig.store_data_columns(slot_count, &columns).len();
+    rig.enqueue_data_columns_by_range_request(slot_count, columns.clone());
+
+    let mut actual_count = 0;
+    while let Some(next) = rig._network_rx.recv().await {
+        if let NetworkMessage::SendResponse {
+            peer_id: _,
+            response: Response::DataColumnsByRange(data_column),
+            id: _,
+        } = next
+        {
+            if let Some(data_column) = data_column {
+                assert!(columns.contains(&data_column.index));
+                actual_count += 1;
+            } else {
+                break;
+            }
+        } else {
+            panic!("unexpected message {:?}", next);
+        }
+    }
+    assert_eq!(data_column_count, actual_count);
+}
+
+#[tokio::test]
+async fn test_data_columns_by_root() {
+    if test_spec::<E>().deneb_fork_epoch.is_none() {
+        return;
+    };
+    let mut rig = TestRig::new(64).await;
+    let data_column_ids = rig.store_data_columns(32, &[3, 9]);
+    // Columns which were never stored are skipped rather than failing the request.
+    let unknown_id = DataColumnIdentifier {
+        block_root: Hash256::repeat_byte(0xff),
+        index: 3,
+    };
+    let mut request_ids = data_column_ids.clone();
+    request_ids.push(unknown_id);
+    rig.enqueue_data_columns_by_root_request(request_ids);
+
+    let mut received_ids = vec![];
+    while let Some(next) = rig._network_rx.recv().await {
+        if let NetworkMessage::SendResponse {
+            peer_id: _,
+            response: Response::DataColumnsByRoot(data_column),
+            id: _,
+        } = next
+        {
+            if let Some(data_column) = data_column {
+                received_ids.push(data_column.id());
+            } else {
+                break;
+            }
+        } else {
+            panic!("unexpected message {:?}", next);
+        }
+    }
+    assert_eq!(data_column_ids, received_ids);
+}
@@ -733,6 +733,26 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                     }
                 }
 
+                // Subscribe to the data column subnets we custody. Supernodes advertise a custody
+                // subnet count that covers every data column subnet.
+                if self.fork_context.spec.is_peer_das_scheduled() {
+                    for subnet_id in self.network_globals.custody_subnets() {
+                        let subnet = Subnet::DataColumn(subnet_id);
+                        for fork_digest in self.required_gossip_fork_digests() {
+                            let topic = GossipTopic::new(
+                                subnet.into(),
+                                GossipEncoding::default(),
+                                fork_digest,
+                            );
+                            if self.libp2p.subscribe(topic.clone()) {
+                                subscribed_topics.push(topic);
+                            } else {
+                                warn!(self.log, "Could not subscribe to topic"; "topic" => %topic);
+                            }
+                        }
+                    }
+                }
+
                 // If we are to subscribe to all subnets we do it here
                 if self.subscribe_all_subnets {
                     for subnet_id in 0..<<T as BeaconChainTypes>::EthSpec as EthSpec>::SubnetBitfieldLength::to_u64() {
@@ -1042,6 +1042,17 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
             return Ok(());
         }
 
+        // Batches that include custody columns can only be completed if some connected peer
+        // custodies each of our columns. Wait for such peers before requesting more batches.
+        if !network.good_peers_on_custody_subnets(self.to_be_downloaded) {
+            debug!(
+                self.log,
+                "Waiting for peers to be available on custody column subnets";
+                "epoch" => self.to_be_downloaded
+            );
+            return Ok(());
+        }
+
         // find the next pending batch and request it from the peer
 
         // randomize the peers for load balancing
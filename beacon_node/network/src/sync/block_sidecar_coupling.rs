@@ -1,8 +1,13 @@
 use beacon_chain::block_verification_types::RpcBlock;
+use beacon_chain::get_block_root;
+use lighthouse_network::rpc::methods::DataColumnsByRangeRequest;
 use lighthouse_network::PeerId;
 use ssz_types::VariableList;
-use std::{collections::VecDeque, sync::Arc};
-use types::{BlobSidecar, EthSpec, SignedBeaconBlock};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+use types::{BlobSidecar, ColumnIndex, DataColumnSidecar, EthSpec, Hash256, SignedBeaconBlock};
 
 use super::range_sync::ByRangeRequestType;
 
@@ -18,8 +23,24 @@ pub struct BlocksAndBlobsRequestInfo<E: EthSpec> {
     is_sidecars_stream_terminated: bool,
     /// Used to determine if this accumulator should wait for a sidecars stream termination
     request_type: ByRangeRequestType,
+    /// The custody column indices expected for every block with data, if columns are requested.
+    expects_custody_columns: Option<Vec<ColumnIndex>>,
     /// The peer the request was made to.
     pub(crate) peer_id: PeerId,
+    /// The active `DataColumnsByRange` request to each peer custody columns are requested from.
+    /// Each peer serves a single request.
+    column_requests: HashMap<PeerId, ColumnsByRangeRequest<E>>,
+    /// Peers whose column request failed. Their remaining responses are ignored.
+    failed_column_peers: Vec<PeerId>,
+}
+
+/// A `DataColumnsByRange` request to a single peer for some of the custody columns.
+#[derive(Debug)]
+struct ColumnsByRangeRequest<E: EthSpec> {
+    request: DataColumnsByRangeRequest,
+    /// Columns we have received awaiting for their corresponding block.
+    accumulated_columns: Vec<Arc<DataColumnSidecar<E>>>,
+    is_stream_terminated: bool,
 }
 
 impl<E: EthSpec> BlocksAndBlobsRequestInfo<E> {
@@ -30,8 +51,60 @@ impl<E: EthSpec> BlocksAndBlobsRequestInfo<E> {
             is_blocks_stream_terminated: <_>::default(),
             is_sidecars_stream_terminated: <_>::default(),
             request_type,
+            expects_custody_columns: None,
             peer_id,
+            column_requests: <_>::default(),
+            failed_column_peers: vec![],
+        }
+    }
+
+    /// Expect the `custody_columns` of every block with data to be served by the
+    /// `DataColumnsByRange` requests in `column_requests`.
+    pub fn with_custody_columns(
+        mut self,
+        custody_columns: Vec<ColumnIndex>,
+        column_requests: Vec<(PeerId, DataColumnsByRangeRequest)>,
+    ) -> Self {
+        self.expects_custody_columns = Some(custody_columns);
+        for (peer_id, request) in column_requests {
+            self.add_column_request(peer_id, request);
         }
+        self
+    }
+
+    /// Register a `DataColumnsByRange` request to `peer_id`.
+    pub fn add_column_request(&mut self, peer_id: PeerId, request: DataColumnsByRangeRequest) {
+        self.column_requests.insert(
+            peer_id,
+            ColumnsByRangeRequest {
+                request,
+                accumulated_columns: vec![],
+                is_stream_terminated: false,
+            },
+        );
+    }
+
+    /// Remove the column request to `peer_id`, dropping the columns it served so far, and return
+    /// the request so that its columns can be requested from other peers.
+    pub fn remove_column_request(&mut self, peer_id: &PeerId) -> Option<DataColumnsByRangeRequest> {
+        let request = self.column_requests.remove(peer_id)?;
+        self.failed_column_peers.push(*peer_id);
+        Some(request.request)
+    }
+
+    /// Returns true if a column request to `peer_id` is active.
+    pub fn is_column_peer(&self, peer_id: &PeerId) -> bool {
+        self.column_requests.contains_key(peer_id)
+    }
+
+    /// The peers which must not be sent another column request: those with an active request and
+    /// those whose request failed.
+    pub fn excluded_column_peers(&self) -> Vec<PeerId> {
+        self.column_requests
+            .keys()
+            .chain(&self.failed_column_peers)
+            .copied()
+            .collect()
     }
 
     pub fn get_request_type(&self) -> ByRangeRequestType {
@@ -52,7 +125,86 @@ impl<E: EthSpec> BlocksAndBlobsRequestInfo<E> {
         }
     }
 
+    /// Add a column served by `peer_id`, returning an error if the peer served a column it was
+    /// not asked for.
+    pub fn add_custody_column_response(
+        &mut self,
+        peer_id: PeerId,
+        data_column_opt: Option<Arc<DataColumnSidecar<E>>>,
+    ) -> Result<(), String> {
+        // Responses from a peer whose request has failed are ignored.
+        let Some(request) = self.column_requests.get_mut(&peer_id) else {
+            return Ok(());
+        };
+        match data_column_opt {
+            Some(data_column) => {
+                if !request.request.columns.contains(&data_column.index) {
+                    return Err(format!(
+                        "Received column {} which was not requested",
+                        data_column.index
+                    ));
+                }
+                request.accumulated_columns.push(data_column);
+            }
+            None => request.is_stream_terminated = true,
+        }
+        Ok(())
+    }
+
+    /// Check the columns served by each column peer against the received blocks, returning the
+    /// first peer which did not serve exactly its requested columns for every block with data.
+    ///
+    /// Must only be called once the request is finished.
+    pub fn find_faulty_column_peer(&self) -> Option<(PeerId, String)> {
+        let blocks_with_data = self
+            .accumulated_blocks
+            .iter()
+            .filter(|block| block.num_expected_blobs() > 0)
+            .map(|block| get_block_root(block))
+            .collect::<Vec<_>>();
+
+        self.column_requests.iter().find_map(|(peer_id, request)| {
+            let mut served = request
+                .accumulated_columns
+                .iter()
+                .map(|column| (column.block_root(), column.index))
+                .collect::<Vec<_>>();
+            served.sort();
+            if served.windows(2).any(|pair| pair[0] == pair[1]) {
+                return Some((*peer_id, "Repeat column".to_string()));
+            }
+            if let Some((block_root, _)) = served
+                .iter()
+                .find(|(block_root, _)| !blocks_with_data.contains(block_root))
+            {
+                return Some((
+                    *peer_id,
+                    format!("Received column for block {block_root:?} without data"),
+                ));
+            }
+            if served.len() != blocks_with_data.len() * request.request.columns.len() {
+                return Some((
+                    *peer_id,
+                    format!(
+                        "Received {} columns, expected {}",
+                        served.len(),
+                        blocks_with_data.len() * request.request.columns.len()
+                    ),
+                ));
+            }
+            None
+        })
+    }
+
     pub fn into_responses(self) -> Result<Vec<RpcBlock<E>>, String> {
+        if let Some(expects_custody_columns) = self.expects_custody_columns.clone() {
+            self.into_responses_with_custody_columns(expects_custody_columns)
+        } else {
+            self.into_responses_with_blobs()
+        }
+    }
+
+    fn into_responses_with_blobs(self) -> Result<Vec<RpcBlock<E>>, String> {
         let BlocksAndBlobsRequestInfo {
             accumulated_blocks,
             accumulated_sidecars,
@@ -99,12 +251,94 @@ impl<E: EthSpec> BlocksAndBlobsRequestInfo<E> {
         Ok(responses)
     }
 
+    fn into_responses_with_custody_columns(
+        self,
+        expects_custody_columns: Vec<ColumnIndex>,
+    ) -> Result<Vec<RpcBlock<E>>, String> {
+        let BlocksAndBlobsRequestInfo {
+            accumulated_blocks,
+            column_requests,
+            ..
+        } = self;
+
+        // Group the received columns by block root. Columns for a block are not guaranteed to be
+        // contiguous as they are served by different peers.
+        let mut custody_columns_by_block =
+            HashMap::<Hash256, Vec<Arc<DataColumnSidecar<E>>>>::new();
+        for column in column_requests
+            .into_values()
+            .flat_map(|request| request.accumulated_columns)
+        {
+            if !expects_custody_columns.contains(&column.index) {
+                return Err(format!(
+                    "Received column not in expected custody indices {}",
+                    column.index
+                ));
+            }
+            custody_columns_by_block
+                .entry(column.block_root())
+                .or_default()
+                .push(column);
+        }
+
+        let mut responses = Vec::with_capacity(accumulated_blocks.len());
+        for block in accumulated_blocks {
+            let block_root = get_block_root(&block);
+            let mut custody_columns = custody_columns_by_block
+                .remove(&block_root)
+                .unwrap_or_default();
+
+            if block.num_expected_blobs() > 0 {
+                custody_columns.sort_by_key(|column| column.index);
+                if custody_columns
+                    .windows(2)
+                    .any(|pair| pair[0].index == pair[1].index)
+                {
+                    return Err(format!("Repeat column for block {block_root:?}"));
+                }
+                if let Some(missing_index) = expects_custody_columns
+                    .iter()
+                    .find(|index| !custody_columns.iter().any(|c| c.index == **index))
+                {
+                    return Err(format!(
+                        "Missing custody column {missing_index} for block {block_root:?}"
+                    ));
+                }
+            } else if !custody_columns.is_empty() {
+                return Err(format!(
+                    "Received columns for block {block_root:?} without data"
+                ));
+            }
+
+            responses.push(
+                RpcBlock::new_with_custody_columns(
+                    Some(block_root),
+                    block,
+                    custody_columns,
+                    expects_custody_columns.clone(),
+                )
+                .map_err(|e| format!("{e:?}"))?,
+            )
+        }
+
+        // if accumulated columns is not empty, throw an error.
+        if !custody_columns_by_block.is_empty() {
+            return Err("Received columns that don't pair with any block".to_string());
+        }
+
+        Ok(responses)
+    }
+
     pub fn is_finished(&self) -> bool {
-        let blobs_requested = match self.request_type {
-            ByRangeRequestType::Blocks => false,
-            ByRangeRequestType::BlocksAndBlobs => true,
+        let sidecars_finished = match self.request_type {
+            ByRangeRequestType::Blocks => true,
+            ByRangeRequestType::BlocksAndBlobs => self.is_sidecars_stream_terminated,
+            ByRangeRequestType::BlocksAndColumns => self
+                .column_requests
+                .values()
+                .all(|request| request.is_stream_terminated),
         };
-        self.is_blocks_stream_terminated && (!blobs_requested || self.is_sidecars_stream_terminated)
+        self.is_blocks_stream_terminated && sidecars_finished
     }
 }
 
@@ -113,9 +347,46 @@ mod tests {
     use super::BlocksAndBlobsRequestInfo;
     use crate::sync::range_sync::ByRangeRequestType;
     use beacon_chain::test_utils::{generate_rand_block_and_blobs, NumBlobs};
+    use lighthouse_network::rpc::methods::DataColumnsByRangeRequest;
     use lighthouse_network::PeerId;
     use rand::SeedableRng;
-    use types::{test_utils::XorShiftRng, ForkName, MinimalEthSpec as E};
+    use std::sync::Arc;
+    use types::{
+        test_utils::XorShiftRng, ColumnIndex, DataColumnSidecar, ForkName, MinimalEthSpec as E,
+        SignedBeaconBlock,
+    };
+
+    /// Builds a column sidecar for `block` without cells, enough to exercise the coupling logic.
+    fn column_for_block(
+        block: &SignedBeaconBlock<E>,
+        index: ColumnIndex,
+    ) -> Arc<DataColumnSidecar<E>> {
+        Arc::new(DataColumnSidecar {
+            index,
+            column: <_>::default(),
+            kzg_commitments: block
+                .message()
+                .body()
+                .blob_kzg_commitments()
+                .unwrap()
+                .clone(),
+            kzg_proofs: <_>::default(),
+            signed_block_header: block.signed_block_header(),
+            kzg_commitments_inclusion_proof: block
+                .message()
+                .body()
+                .kzg_commitments_merkle_proof()
+                .unwrap(),
+        })
+    }
+
+    fn columns_request(columns: Vec<ColumnIndex>) -> DataColumnsByRangeRequest {
+        DataColumnsByRangeRequest {
+            start_slot: 0,
+            count: 4,
+            columns,
+        }
+    }
 
     #[test]
     fn no_blobs_into_responses() {
@@ -164,4 +435,106 @@ mod tests {
         assert!(info.is_finished());
         info.into_responses().unwrap();
     }
+
+    #[test]
+    fn custody_columns_into_responses() {
+        let peer_id = PeerId::random();
+        let column_peers = [PeerId::random(), PeerId::random()];
+        let custody_columns = vec![0, 1];
+        let column_requests = column_peers
+            .iter()
+            .zip(&custody_columns)
+            .map(|(peer, index)| (*peer, columns_request(vec![*index])))
+            .collect();
+        let mut info =
+            BlocksAndBlobsRequestInfo::<E>::new(ByRangeRequestType::BlocksAndColumns, peer_id)
+                .with_custody_columns(custody_columns.clone(), column_requests);
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let blocks = (0..4)
+            .map(|_| {
+                generate_rand_block_and_blobs::<E>(ForkName::Deneb, NumBlobs::Number(1), &mut rng).0
+            })
+            .collect::<Vec<_>>();
+
+        // Send blocks and complete terminate response
+        for block in &blocks {
+            info.add_block_response(Some(block.clone().into()));
+        }
+        info.add_block_response(None);
+
+        // Each peer serves one custody column for every block
+        for (column_peer, index) in column_peers.iter().zip(&custody_columns) {
+            for block in &blocks {
+                info.add_custody_column_response(
+                    *column_peer,
+                    Some(column_for_block(block, *index)),
+                )
+                .unwrap();
+            }
+            assert!(!info.is_finished());
+            info.add_custody_column_response(*column_peer, None)
+                .unwrap();
+        }
+
+        // Assert response is finished and RpcBlocks can be constructed with all custody columns
+        assert!(info.is_finished());
+        assert_eq!(info.find_faulty_column_peer(), None);
+        let rpc_blocks = info.into_responses().unwrap();
+        assert_eq!(rpc_blocks.len(), blocks.len());
+        for rpc_block in rpc_blocks {
+            assert_eq!(rpc_block.n_data_columns(), custody_columns.len());
+        }
+    }
+
+    #[test]
+    fn missing_custody_columns_into_responses() {
+        let peer_id = PeerId::random();
+        let column_peer = PeerId::random();
+        let mut info =
+            BlocksAndBlobsRequestInfo::<E>::new(ByRangeRequestType::BlocksAndColumns, peer_id)
+                .with_custody_columns(vec![0, 1], vec![(column_peer, columns_request(vec![0, 1]))]);
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let block =
+            generate_rand_block_and_blobs::<E>(ForkName::Deneb, NumBlobs::Number(1), &mut rng).0;
+
+        info.add_block_response(Some(block.clone().into()));
+        info.add_block_response(None);
+        // Only one of the two custody columns is served
+        info.add_custody_column_response(column_peer, Some(column_for_block(&block, 0)))
+            .unwrap();
+        info.add_custody_column_response(column_peer, None).unwrap();
+
+        assert!(info.is_finished());
+        assert_eq!(
+            info.find_faulty_column_peer().map(|(peer, _)| peer),
+            Some(column_peer)
+        );
+        assert!(info.into_responses().is_err());
+    }
+
+    #[test]
+    fn unrequested_custody_column_is_rejected() {
+        let peer_id = PeerId::random();
+        let column_peer = PeerId::random();
+        let mut info =
+            BlocksAndBlobsRequestInfo::<E>::new(ByRangeRequestType::BlocksAndColumns, peer_id)
+                .with_custody_columns(vec![0], vec![(column_peer, columns_request(vec![0]))]);
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let block =
+            generate_rand_block_and_blobs::<E>(ForkName::Deneb, NumBlobs::Number(1), &mut rng).0;
+
+        assert!(info
+            .add_custody_column_response(column_peer, Some(column_for_block(&block, 1)))
+            .is_err());
+
+        // Once its request is removed, the remaining responses of the peer are ignored
+        assert_eq!(
+            info.remove_column_request(&column_peer),
+            Some(columns_request(vec![0]))
+        );
+        assert!(info
+            .add_custody_column_response(column_peer, Some(column_for_block(&block, 1)))
+            .is_ok());
+        assert_eq!(info.excluded_column_peers(), vec![column_peer]);
+    }
 }
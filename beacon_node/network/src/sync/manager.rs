@@ -101,6 +101,14 @@ pub enum SyncMessage<E: EthSpec> {
         seen_timestamp: Duration,
     },
 
+    /// A data column has been received from the RPC.
+    RpcDataColumn {
+        request_id: SyncRequestId,
+        peer_id: PeerId,
+        data_column: Option<Arc<DataColumnSidecar<E>>>,
+        seen_timestamp: Duration,
+    },
+
     /// A block with an unknown parent has been received.
     UnknownParentBlock(PeerId, RpcBlock<E>, Hash256),
 
@@ -338,7 +346,10 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                 self.on_single_blob_response(id, peer_id, RpcEvent::RPCError(error))
             }
             SyncRequestId::RangeBlockAndBlobs { id } => {
-                if let Some(sender_id) = self.network.range_request_failed(id) {
+                // The failed request may be a custody column request to a peer other than the
+                // batch peer, which is retried with other custody peers. Otherwise the batch is
+                // failed on behalf of the batch peer.
+                if let Some((sender_id, peer_id)) = self.network.range_request_failed(id, peer_id) {
                     match sender_id {
                         RangeRequestId::RangeSync { chain_id, batch_id } => {
                             self.range_sync.inject_error(
@@ -361,7 +372,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                 } else {
                     debug!(
                         self.log,
-                        "RPC error for range request has no associated entry in network context, ungraceful disconnect";
+                        "RPC error for range request was retried or has no associated entry in network context";
                         "peer_id" => %peer_id,
                         "request_id" => %id,
                         "error" => ?error,
@@ -614,6 +625,12 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                 blob_sidecar,
                 seen_timestamp,
             } => self.rpc_blob_received(request_id, peer_id, blob_sidecar, seen_timestamp),
+            SyncMessage::RpcDataColumn {
+                request_id,
+                peer_id,
+                data_column,
+                seen_timestamp,
+            } => self.rpc_data_column_received(request_id, peer_id, data_column, seen_timestamp),
             SyncMessage::UnknownParentBlock(peer_id, block, block_root) => {
                 let block_slot = block.slot();
                 let parent_root = block.parent_root();
@@ -894,6 +911,27 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         }
     }
 
+    fn rpc_data_column_received(
+        &mut self,
+        request_id: SyncRequestId,
+        peer_id: PeerId,
+        data_column: Option<Arc<DataColumnSidecar<T::EthSpec>>>,
+        _seen_timestamp: Duration,
+    ) {
+        match request_id {
+            SyncRequestId::SingleBlock { .. } | SyncRequestId::SingleBlob { .. } => {
+                crit!(self.log, "Data column received during block or blob request"; "peer_id" => %peer_id);
+            }
+            SyncRequestId::RangeBlockAndBlobs { id } => {
+                self.range_block_and_blobs_response(
+                    id,
+                    peer_id,
+                    BlockOrBlob::CustodyColumns(data_column),
+                );
+            }
+        }
+    }
+
     fn on_single_blob_response(
         &mut self,
         id: SingleLookupReqId,
@@ -921,8 +959,11 @@ impl<T: BeaconChainTypes> SyncManager<T> {
     ) {
         if let Some(resp) = self
             .network
-            .range_block_and_blob_response(id, block_or_blob)
+            .range_block_and_blob_response(id, peer_id, block_or_blob)
         {
+            // Custody columns may be served by peers other than the one the batch was requested
+            // from. The coupled response is attributed to the batch peer.
+            let peer_id = resp.peer_id;
             match resp.responses {
                 Ok(blocks) => {
                     match resp.sender_id {
@@ -956,7 +997,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                         }
                     }
                 }
-                Err(e) => {
+                Err((e, faulty_peer)) => {
                     // Re-insert the request so we can retry
                     self.network.insert_range_blocks_and_blobs_request(
                         id,
@@ -969,12 +1010,15 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                         self.log,
                         "Blocks and blobs request for range received invalid data";
                         "peer_id" => %peer_id,
+                        "faulty_peer" => %faulty_peer,
                         "sender_id" => ?resp.sender_id,
                         "error" => e.clone()
                     );
                     let id = SyncRequestId::RangeBlockAndBlobs { id };
+                    // Only the peer which served the invalid data is penalized, which may be a
+                    // custody column peer rather than the batch peer.
                     self.network.report_peer(
-                        peer_id,
+                        faulty_peer,
                         PeerAction::MidToleranceError,
                         "block_blob_faulty_batch",
                     );
@@ -14,24 +14,31 @@ use crate::sync::manager::BlockProcessType;
 use beacon_chain::block_verification_types::RpcBlock;
 use beacon_chain::{BeaconChain, BeaconChainTypes, BlockProcessStatus, EngineState};
 use fnv::FnvHashMap;
-use lighthouse_network::rpc::methods::BlobsByRangeRequest;
+use lighthouse_network::rpc::methods::{BlobsByRangeRequest, DataColumnsByRangeRequest};
 use lighthouse_network::rpc::{BlocksByRangeRequest, GoodbyeReason, RPCError};
 use lighthouse_network::service::api_types::{AppRequestId, Id, SingleLookupReqId, SyncRequestId};
 use lighthouse_network::{Client, NetworkGlobals, PeerAction, PeerId, ReportSource, Request};
 pub use requests::LookupVerifyError;
-use slog::{debug, error, trace, warn};
+use slog::{debug, error, warn};
 use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc;
 use types::blob_sidecar::FixedBlobSidecarList;
-use types::{BlobSidecar, EthSpec, Hash256, SignedBeaconBlock};
+use types::{
+    BlobSidecar, ColumnIndex, DataColumnSidecar, DataColumnSubnetId, Epoch, EthSpec, Hash256,
+    SignedBeaconBlock,
+};
 
 mod requests;
 
 pub struct BlocksAndBlobsByRangeResponse<E: EthSpec> {
     pub sender_id: RangeRequestId,
-    pub responses: Result<Vec<RpcBlock<E>>, String>,
+    /// The peer the batch was requested from.
+    pub peer_id: PeerId,
+    /// The coupled responses, or an error and the peer which served the invalid data.
+    pub responses: Result<Vec<RpcBlock<E>>, (String, PeerId)>,
     pub request_type: ByRangeRequestType,
 }
 
@@ -64,6 +71,8 @@ pub enum RpcResponseError {
 pub enum RpcRequestSendError {
     /// Network channel send failed
     NetworkSendError,
+    /// No connected peer custodies one of the requested data columns
+    NoCustodyPeers(ColumnIndex),
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -144,6 +153,7 @@ pub struct SyncNetworkContext<T: BeaconChainTypes> {
 pub enum BlockOrBlob<E: EthSpec> {
     Block(Option<Arc<SignedBeaconBlock<E>>>),
     Blob(Option<Arc<BlobSidecar<E>>>),
+    CustodyColumns(Option<Arc<DataColumnSidecar<E>>>),
 }
 
 impl<E: EthSpec> From<Option<Arc<SignedBeaconBlock<E>>>> for BlockOrBlob<E> {
@@ -184,7 +194,7 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             self.range_blocks_and_blobs_requests
                 .iter()
                 .filter_map(|(id, request)| {
-                    if request.1.peer_id == *peer_id {
+                    if request.1.peer_id == *peer_id || request.1.is_column_peer(peer_id) {
                         Some(SyncRequestId::RangeBlockAndBlobs { id: *id })
                     } else {
                         None
@@ -256,97 +266,261 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         }
     }
 
-    /// A blocks by range request for the range sync algorithm.
-    pub fn blocks_by_range_request(
+    /// A blocks by range request sent by the range sync algorithm, along with the blobs or
+    /// custody columns of the range as required by `batch_type`.
+    pub fn blocks_and_blobs_by_range_request(
         &mut self,
         peer_id: PeerId,
         batch_type: ByRangeRequestType,
         request: BlocksByRangeRequest,
+        sender_id: RangeRequestId,
     ) -> Result<Id, RpcRequestSendError> {
-        let id = self.next_id();
-        trace!(
-            self.log,
-            "Sending BlocksByRange request";
-            "method" => "BlocksByRange",
-            "count" => request.count(),
-            "peer" => %peer_id,
-        );
-        self.network_send
-            .send(NetworkMessage::SendRequest {
-                peer_id,
-                request: Request::BlocksByRange(request.clone()),
-                request_id: AppRequestId::Sync(SyncRequestId::RangeBlockAndBlobs { id }),
-            })
-            .map_err(|_| RpcRequestSendError::NetworkSendError)?;
+        // Prepare every request before sending any, so that a batch is never partially requested
+        // if some column can't be served.
+        let mut info = BlocksAndBlobsRequestInfo::new(batch_type, peer_id);
+        let mut requests = vec![(peer_id, Request::BlocksByRange(request.clone()))];
+        match batch_type {
+            ByRangeRequestType::Blocks => {}
+            ByRangeRequestType::BlocksAndBlobs => {
+                // Create the blob request based on the blocks request.
+                requests.push((
+                    peer_id,
+                    Request::BlobsByRange(BlobsByRangeRequest {
+                        start_slot: *request.start_slot(),
+                        count: *request.count(),
+                    }),
+                ));
+            }
+            ByRangeRequestType::BlocksAndColumns => {
+                let custody_columns = self.network_globals().custody_columns();
+                let column_requests = self.select_columns_by_range_peers(
+                    peer_id,
+                    &custody_columns,
+                    &[],
+                    *request.start_slot(),
+                    *request.count(),
+                )?;
+                for (column_peer, column_request) in &column_requests {
+                    requests.push((
+                        *column_peer,
+                        Request::DataColumnsByRange(column_request.clone()),
+                    ));
+                }
+                info = info.with_custody_columns(custody_columns, column_requests);
+            }
+        }
 
-        if matches!(batch_type, ByRangeRequestType::BlocksAndBlobs) {
+        let id = self.next_id();
+        for (peer_id, request) in requests {
             debug!(
                 self.log,
-                "Sending BlobsByRange requests";
-                "method" => "BlobsByRange",
-                "count" => request.count(),
+                "Sending by range request";
+                "request" => ?request,
                 "peer" => %peer_id,
             );
-
-            // Create the blob request based on the blocks request.
+            // The network channel only fails if the network service has shut down, in which case
+            // any requests already sent will never be responded to.
             self.network_send
                 .send(NetworkMessage::SendRequest {
                     peer_id,
-                    request: Request::BlobsByRange(BlobsByRangeRequest {
-                        start_slot: *request.start_slot(),
-                        count: *request.count(),
-                    }),
+                    request,
                     request_id: AppRequestId::Sync(SyncRequestId::RangeBlockAndBlobs { id }),
                 })
                 .map_err(|_| RpcRequestSendError::NetworkSendError)?;
         }
 
+        self.range_blocks_and_blobs_requests
+            .insert(id, (sender_id, info));
         Ok(id)
     }
 
-    /// A blocks by range request sent by the range sync algorithm
-    pub fn blocks_and_blobs_by_range_request(
+    /// Assigns each of the `custody_columns` to a connected peer that custodies it, other than
+    /// the `excluded_peers`, and returns the `DataColumnsByRange` request for the slot range to
+    /// each peer. The batch peer is preferred if it custodies the column, otherwise the column
+    /// goes to the custody peer with the fewest columns assigned so far.
+    fn select_columns_by_range_peers(
+        &self,
+        batch_peer: PeerId,
+        custody_columns: &[ColumnIndex],
+        excluded_peers: &[PeerId],
+        start_slot: u64,
+        count: u64,
+    ) -> Result<Vec<(PeerId, DataColumnsByRangeRequest)>, RpcRequestSendError> {
+        let mut peers_to_columns = HashMap::<PeerId, Vec<ColumnIndex>>::new();
+        let peers = self.network_globals().peers.read();
+
+        for column_index in custody_columns {
+            let subnet = DataColumnSubnetId::from_column_index::<T::EthSpec>(
+                *column_index as usize,
+                &self.chain.spec,
+            );
+            let custody_peers = peers
+                .good_custody_subnet_peer(subnet)
+                .filter(|peer| !excluded_peers.contains(peer))
+                .collect::<Vec<_>>();
+
+            let column_peer = if custody_peers.contains(&&batch_peer) {
+                batch_peer
+            } else {
+                **custody_peers
+                    .iter()
+                    .min_by_key(|peer| peers_to_columns.get(**peer).map_or(0, Vec::len))
+                    .ok_or(RpcRequestSendError::NoCustodyPeers(*column_index))?
+            };
+            peers_to_columns
+                .entry(column_peer)
+                .or_default()
+                .push(*column_index);
+        }
+
+        Ok(peers_to_columns
+            .into_iter()
+            .map(|(peer_id, columns)| {
+                (
+                    peer_id,
+                    DataColumnsByRangeRequest {
+                        start_slot,
+                        count,
+                        columns,
+                    },
+                )
+            })
+            .collect())
+    }
+
+    /// Requests the custody columns of the failed column request of `request_id` to `failed_peer`
+    /// from other custody peers, so that the batch does not fail because of a peer other than the
+    /// batch peer.
+    ///
+    /// Returns `false` if `failed_peer` is the batch peer, it has no active column request or no
+    /// other peer custodies its columns.
+    fn retry_custody_columns(&mut self, request_id: Id, failed_peer: PeerId) -> bool {
+        let Some((_, info)) = self.range_blocks_and_blobs_requests.get(&request_id) else {
+            return false;
+        };
+        if info.peer_id == failed_peer || !info.is_column_peer(&failed_peer) {
+            return false;
+        }
+        let batch_peer = info.peer_id;
+        let mut excluded_peers = info.excluded_column_peers();
+        excluded_peers.push(failed_peer);
+
+        let Some((_, info)) = self.range_blocks_and_blobs_requests.get_mut(&request_id) else {
+            return false;
+        };
+        let Some(failed_request) = info.remove_column_request(&failed_peer) else {
+            return false;
+        };
+        let column_requests = match self.select_columns_by_range_peers(
+            batch_peer,
+            &failed_request.columns,
+            &excluded_peers,
+            failed_request.start_slot,
+            failed_request.count,
+        ) {
+            Ok(column_requests) => column_requests,
+            Err(e) => {
+                debug!(
+                    self.log,
+                    "Unable to retry custody columns of range request";
+                    "request_id" => request_id,
+                    "failed_peer" => %failed_peer,
+                    "error" => ?e,
+                );
+                return false;
+            }
+        };
+
+        for (column_peer, column_request) in column_requests {
+            debug!(
+                self.log,
+                "Retrying custody columns of range request";
+                "request_id" => request_id,
+                "failed_peer" => %failed_peer,
+                "peer" => %column_peer,
+                "columns" => ?column_request.columns,
+            );
+            if self
+                .network_send
+                .send(NetworkMessage::SendRequest {
+                    peer_id: column_peer,
+                    request: Request::DataColumnsByRange(column_request.clone()),
+                    request_id: AppRequestId::Sync(SyncRequestId::RangeBlockAndBlobs {
+                        id: request_id,
+                    }),
+                })
+                .is_err()
+            {
+                return false;
+            }
+            if let Some((_, info)) = self.range_blocks_and_blobs_requests.get_mut(&request_id) {
+                info.add_column_request(column_peer, column_request);
+            }
+        }
+        true
+    }
+
+    /// Returns true if every custody column of the node is custodied by at least one connected
+    /// peer, so that a batch for `epoch` can be fully downloaded.
+    pub fn good_peers_on_custody_subnets(&self, epoch: Epoch) -> bool {
+        if !matches!(self.batch_type(epoch), ByRangeRequestType::BlocksAndColumns) {
+            return true;
+        }
+
+        let peers = self.network_globals().peers.read();
+        self.network_globals()
+            .custody_columns()
+            .into_iter()
+            .map(|column_index| {
+                DataColumnSubnetId::from_column_index::<T::EthSpec>(
+                    column_index as usize,
+                    &self.chain.spec,
+                )
+            })
+            .all(|subnet| peers.good_custody_subnet_peer(subnet).next().is_some())
+    }
+
+    /// Handles the failure of the request of the range request `request_id` to `peer_id`.
+    ///
+    /// If `peer_id` was only asked for custody columns, its columns are requested from other
+    /// custody peers and `None` is returned. Otherwise the range request is removed, returning its
+    /// sender and the peer the batch was requested from.
+    pub fn range_request_failed(
         &mut self,
+        request_id: Id,
         peer_id: PeerId,
-        batch_type: ByRangeRequestType,
-        request: BlocksByRangeRequest,
-        sender_id: RangeRequestId,
-    ) -> Result<Id, RpcRequestSendError> {
-        let id = self.blocks_by_range_request(peer_id, batch_type, request)?;
-        self.range_blocks_and_blobs_requests.insert(
-            id,
-            (
-                sender_id,
-                BlocksAndBlobsRequestInfo::new(batch_type, peer_id),
-            ),
-        );
-        Ok(id)
-    }
+    ) -> Option<(RangeRequestId, PeerId)> {
+        if self.retry_custody_columns(request_id, peer_id) {
+            return None;
+        }
 
-    pub fn range_request_failed(&mut self, request_id: Id) -> Option<RangeRequestId> {
-        let sender_id = self
+        let request = self
             .range_blocks_and_blobs_requests
             .remove(&request_id)
-            .map(|(sender_id, _info)| sender_id);
-        if let Some(sender_id) = sender_id {
+            .map(|(sender_id, info)| (sender_id, info.peer_id));
+        if let Some((sender_id, peer_id)) = request {
             debug!(
                 self.log,
                 "Sync range request failed";
                 "request_id" => request_id,
                 "sender_id" => ?sender_id
             );
-            Some(sender_id)
+            Some((sender_id, peer_id))
         } else {
             debug!(self.log, "Sync range request failed"; "request_id" => request_id);
             None
         }
     }
 
-    /// Received a blocks by range or blobs by range response for a request that couples blocks '
-    /// and blobs.
+    /// Received a blocks by range, blobs by range or data columns by range response from `peer_id`
+    /// for a request that couples blocks and their data.
+    ///
+    /// A column peer which serves invalid columns is penalized and its columns are requested from
+    /// other custody peers.
     pub fn range_block_and_blob_response(
         &mut self,
         request_id: Id,
+        peer_id: PeerId,
         block_or_blob: BlockOrBlob<T::EthSpec>,
     ) -> Option<BlocksAndBlobsByRangeResponse<T::EthSpec>> {
         let Entry::Occupied(mut entry) = self.range_blocks_and_blobs_requests.entry(request_id)
@@ -356,18 +530,63 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         };
 
         let (_, info) = entry.get_mut();
-        match block_or_blob {
-            BlockOrBlob::Block(maybe_block) => info.add_block_response(maybe_block),
-            BlockOrBlob::Blob(maybe_sidecar) => info.add_sidecar_response(maybe_sidecar),
+        let faulty_column_peer = match block_or_blob {
+            BlockOrBlob::Block(maybe_block) => {
+                info.add_block_response(maybe_block);
+                None
+            }
+            BlockOrBlob::Blob(maybe_sidecar) => {
+                info.add_sidecar_response(maybe_sidecar);
+                None
+            }
+            BlockOrBlob::CustodyColumns(maybe_column) => info
+                .add_custody_column_response(peer_id, maybe_column)
+                .err()
+                .map(|e| (peer_id, e)),
+        }
+        .or_else(|| {
+            info.is_finished()
+                .then(|| info.find_faulty_column_peer())
+                .flatten()
+        });
+
+        if let Some((column_peer, e)) = faulty_column_peer {
+            debug!(
+                self.log,
+                "Custody column peer served invalid columns";
+                "request_id" => request_id,
+                "peer" => %column_peer,
+                "error" => &e,
+            );
+            if self.retry_custody_columns(request_id, column_peer) {
+                self.report_peer(
+                    column_peer,
+                    PeerAction::MidToleranceError,
+                    "faulty_custody_columns",
+                );
+                return None;
+            }
+            // The columns can't be requested from another peer, so the batch fails.
+            let (sender_id, info) = self.range_blocks_and_blobs_requests.remove(&request_id)?;
+            return Some(BlocksAndBlobsByRangeResponse {
+                sender_id,
+                peer_id: info.peer_id,
+                request_type: info.get_request_type(),
+                responses: Err((e, column_peer)),
+            });
         }
+
+        let (_, info) = self.range_blocks_and_blobs_requests.get(&request_id)?;
         if info.is_finished() {
             // If the request is finished, dequeue everything
-            let (sender_id, info) = entry.remove();
+            let (sender_id, info) = self.range_blocks_and_blobs_requests.remove(&request_id)?;
             let request_type = info.get_request_type();
+            let peer_id = info.peer_id;
             Some(BlocksAndBlobsByRangeResponse {
                 sender_id,
+                peer_id,
                 request_type,
-                responses: info.into_responses(),
+                responses: info.into_responses().map_err(|e| (e, peer_id)),
             })
         } else {
             None
@@ -592,8 +811,8 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         id
     }
 
-    /// Check whether a batch for this epoch (and only this epoch) should request just blocks or
-    /// blocks and blobs.
+    /// Check whether a batch for this epoch (and only this epoch) should request just blocks,
+    /// blocks and blobs, or blocks and custody columns.
     pub fn batch_type(&self, epoch: types::Epoch) -> ByRangeRequestType {
         // Induces a compile time panic if this doesn't hold true.
         #[allow(clippy::assertions_on_constants)]
@@ -604,10 +823,12 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         );
 
         if let Some(data_availability_boundary) = self.chain.data_availability_boundary() {
-            if epoch >= data_availability_boundary {
-                ByRangeRequestType::BlocksAndBlobs
-            } else {
+            if epoch < data_availability_boundary {
                 ByRangeRequestType::Blocks
+            } else if self.chain.spec.is_peer_das_enabled_for_epoch(epoch) {
+                ByRangeRequestType::BlocksAndColumns
+            } else {
+                ByRangeRequestType::BlocksAndBlobs
             }
         } else {
             ByRangeRequestType::Blocks
@@ -1037,6 +1037,18 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             return Ok(KeepChain);
         }
 
+        // Batches that include custody columns can only be completed if some connected peer
+        // custodies each of our columns. Wait for such peers before requesting more batches.
+        let next_epoch = self.optimistic_start.unwrap_or(self.to_be_downloaded);
+        if !network.good_peers_on_custody_subnets(next_epoch) {
+            debug!(
+                self.log,
+                "Waiting for peers to be available on custody column subnets";
+                "epoch" => next_epoch
+            );
+            return Ok(KeepChain);
+        }
+
         // find the next pending batch and request it from the peer
 
         // randomize the peers for load balancing
@@ -391,22 +391,28 @@ mod tests {
 
     use super::*;
     use crate::sync::network_context::{BlockOrBlob, RangeRequestId};
+    use crate::sync::range_sync::ByRangeRequestType;
     use beacon_chain::builder::Witness;
     use beacon_chain::eth1_chain::CachingEth1Backend;
     use beacon_chain::parking_lot::RwLock;
     use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
     use beacon_chain::EngineState;
     use beacon_processor::WorkEvent as BeaconWorkEvent;
+    use lighthouse_network::rpc::BlocksByRangeRequest;
     use lighthouse_network::service::api_types::SyncRequestId;
     use lighthouse_network::{
-        rpc::StatusMessage, service::api_types::AppRequestId, NetworkGlobals,
+        rpc::StatusMessage, service::api_types::AppRequestId, NetworkGlobals, Request,
     };
+    use rand::SeedableRng;
     use slog::{o, Drain};
     use slot_clock::TestingSlotClock;
     use std::collections::HashSet;
     use store::MemoryStore;
     use tokio::sync::mpsc;
-    use types::{ForkName, MinimalEthSpec as E};
+    use types::test_utils::{TestRandom, XorShiftRng};
+    use types::{
+        ColumnIndex, DataColumnSidecar, DataColumnSubnetId, ForkName, MinimalEthSpec as E,
+    };
 
     #[derive(Debug)]
     struct FakeStorage {
@@ -559,18 +565,21 @@ mod tests {
 
         fn complete_range_block_and_blobs_response(
             &mut self,
+            peer_id: PeerId,
             block_req: AppRequestId,
             blob_req_opt: Option<AppRequestId>,
         ) -> (ChainId, BatchId, Id) {
             if blob_req_opt.is_some() {
                 match block_req {
                     AppRequestId::Sync(SyncRequestId::RangeBlockAndBlobs { id }) => {
-                        let _ = self
-                            .cx
-                            .range_block_and_blob_response(id, BlockOrBlob::Block(None));
+                        let _ = self.cx.range_block_and_blob_response(
+                            id,
+                            peer_id,
+                            BlockOrBlob::Block(None),
+                        );
                         let response = self
                             .cx
-                            .range_block_and_blob_response(id, BlockOrBlob::Blob(None))
+                            .range_block_and_blob_response(id, peer_id, BlockOrBlob::Blob(None))
                             .unwrap();
                         let (chain_id, batch_id) =
                             TestRig::unwrap_range_request_id(response.sender_id);
@@ -583,7 +592,7 @@ mod tests {
                     AppRequestId::Sync(SyncRequestId::RangeBlockAndBlobs { id }) => {
                         let response = self
                             .cx
-                            .range_block_and_blob_response(id, BlockOrBlob::Block(None))
+                            .range_block_and_blob_response(id, peer_id, BlockOrBlob::Block(None))
                             .unwrap();
                         let (chain_id, batch_id) =
                             TestRig::unwrap_range_request_id(response.sender_id);
@@ -594,6 +603,36 @@ mod tests {
             }
         }
 
+        /// Reads a `DataColumnsByRange` request to a given peer from the network receiver channel,
+        /// returning the requested columns.
+        #[track_caller]
+        fn grab_columns_request(&mut self, expected_peer: &PeerId) -> Vec<ColumnIndex> {
+            match self.network_rx.try_recv() {
+                Ok(NetworkMessage::SendRequest {
+                    peer_id,
+                    request: Request::DataColumnsByRange(request),
+                    request_id: _,
+                }) => {
+                    assert_eq!(&peer_id, expected_peer);
+                    request.columns
+                }
+                other => panic!("Expected a columns request, found {:?}", other),
+            }
+        }
+
+        /// Drains the network receiver channel, checking that `peer_id` was not reported.
+        #[track_caller]
+        fn expect_peer_not_reported(&mut self, peer_id: &PeerId) {
+            while let Ok(message) = self.network_rx.try_recv() {
+                if let NetworkMessage::ReportPeer {
+                    peer_id: reported, ..
+                } = message
+                {
+                    assert_ne!(&reported, peer_id, "peer should not be reported");
+                }
+            }
+        }
+
         fn unwrap_range_request_id(sender_id: RangeRequestId) -> (ChainId, BatchId) {
             if let RangeRequestId::RangeSync { chain_id, batch_id } = sender_id {
                 (chain_id, batch_id)
@@ -689,7 +728,11 @@ mod tests {
             log.new(o!("component" => "range")),
         );
         let (network_tx, network_rx) = mpsc::unbounded_channel();
-        let globals = Arc::new(NetworkGlobals::new_test_globals(Vec::new(), &log));
+        let globals = Arc::new(NetworkGlobals::new_test_globals(
+            Vec::new(),
+            &log,
+            Arc::new(chain.spec.clone()),
+        ));
         let (network_beacon_processor, beacon_processor_rx) =
             NetworkBeaconProcessor::null_for_testing(
                 globals.clone(),
@@ -799,7 +842,7 @@ mod tests {
         let (block_req, blob_req_opt) = rig.grab_request(&peer1, fork);
 
         let (chain1, batch1, id1) =
-            rig.complete_range_block_and_blobs_response(block_req, blob_req_opt);
+            rig.complete_range_block_and_blobs_response(peer1, block_req, blob_req_opt);
 
         // make the ee offline
         rig.cx.update_execution_engine_state(EngineState::Offline);
@@ -816,7 +859,7 @@ mod tests {
         let (block_req, blob_req_opt) = rig.grab_request(&peer2, fork);
 
         let (chain2, batch2, id2) =
-            rig.complete_range_block_and_blobs_response(block_req, blob_req_opt);
+            rig.complete_range_block_and_blobs_response(peer2, block_req, blob_req_opt);
 
         // send the response to the request
         range.blocks_by_range_response(&mut rig.cx, peer2, chain2, batch2, id2, vec![]);
@@ -833,4 +876,68 @@ mod tests {
         rig.expect_chain_segment();
         rig.expect_chain_segment();
     }
+
+    #[test]
+    fn faulty_custody_column_peer_does_not_penalize_batch_peer() {
+        let (mut rig, _range) = range(false);
+        let all_subnets = (0..rig.cx.chain.spec.data_column_sidecar_subnet_count)
+            .map(DataColumnSubnetId::new)
+            .collect::<HashSet<_>>();
+
+        // The batch peer custodies no columns, so every custody column is requested from the
+        // column peer.
+        let batch_peer = PeerId::random();
+        let column_peer = PeerId::random();
+        rig.globals
+            .peers
+            .write()
+            .__add_connected_custody_peer_testing_only(&column_peer, all_subnets.clone());
+        let id = rig
+            .cx
+            .blocks_and_blobs_by_range_request(
+                batch_peer,
+                ByRangeRequestType::BlocksAndColumns,
+                BlocksByRangeRequest::new(0, E::slots_per_epoch()),
+                RangeRequestId::RangeSync {
+                    chain_id: 1,
+                    batch_id: Epoch::new(0),
+                },
+            )
+            .unwrap();
+        let _ = rig.grab_request(&batch_peer, ForkName::Base);
+        let columns = rig.grab_columns_request(&column_peer);
+
+        // Another custody peer connects before the column peer serves a column it was not asked
+        // for. The column peer is penalized and its columns are requested from the new peer.
+        let retry_peer = PeerId::random();
+        rig.globals
+            .peers
+            .write()
+            .__add_connected_custody_peer_testing_only(&retry_peer, all_subnets);
+        let mut column =
+            DataColumnSidecar::<E>::random_for_test(&mut XorShiftRng::from_seed([42; 16]));
+        column.index = (0..rig.cx.chain.spec.number_of_columns as ColumnIndex)
+            .find(|index| !columns.contains(index))
+            .unwrap();
+        let response = rig.cx.range_block_and_blob_response(
+            id,
+            column_peer,
+            BlockOrBlob::CustodyColumns(Some(Arc::new(column))),
+        );
+        assert!(response.is_none(), "batch should not fail");
+        assert_eq!(rig.grab_columns_request(&retry_peer), columns);
+        match rig.network_rx.try_recv() {
+            Ok(NetworkMessage::ReportPeer { peer_id, .. }) => assert_eq!(peer_id, column_peer),
+            other => panic!("Expected the column peer to be reported, found {:?}", other),
+        }
+
+        // The retry peer fails its request and no other peer custodies the columns, so the batch
+        // fails on behalf of the batch peer, which is never penalized.
+        let (_, failed_peer) = rig
+            .cx
+            .range_request_failed(id, retry_peer)
+            .expect("batch should fail");
+        assert_eq!(failed_peer, batch_peer);
+        rig.expect_peer_not_reported(&batch_peer);
+    }
 }
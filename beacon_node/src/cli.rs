@@ -63,6 +63,16 @@ pub fn cli_app() -> Command {
                        This will also advertise the beacon node as being long-lived subscribed to all subnets.")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("subscribe-all-data-column-subnets")
+                .long("subscribe-all-data-column-subnets")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .help("Subscribe to and custody all data column subnets (supernode mode). \
+                       This will also advertise the beacon node as custodying all data columns. \
+                       Only has an effect once PeerDAS is scheduled.")
+                .display_order(0)
+        )
         .arg(
             Arg::new("import-all-attestations")
                 .long("import-all-attestations")
@@ -723,13 +733,38 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("beacon-node-backend")
+                .long("beacon-node-backend")
+                .value_name("DATABASE")
+                .help("Set the database backend to be used by the beacon node. A database \
+                       created with one backend cannot be opened with another, see \
+                       `lighthouse db convert-backend`.")
+                .action(ArgAction::Set)
+                .value_parser(store::DatabaseBackend::VARIANTS.to_vec())
+                .default_value("leveldb")
+                .display_order(0)
+        )
         .arg(
             Arg::new("slots-per-restore-point")
                 .long("slots-per-restore-point")
                 .value_name("SLOT_COUNT")
-                .help("Specifies how often a freezer DB restore point should be stored. \
-                       Cannot be changed after initialization. \
-                       [default: 8192 (mainnet) or 64 (minimal)]")
+                .help("This flag is deprecated and has no effect.")
+                .hide(true)
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("hierarchy-exponents")
+                .long("hierarchy-exponents")
+                .value_name("EXPONENTS")
+                .help("Specifies the layers of the freezer DB's hierarchy of state snapshots \
+                       and diffs, as a comma-separated list of strictly increasing powers of 2. \
+                       Snapshots are stored every 2^N slots for the last exponent N, and each \
+                       other layer stores diffs against the next coarser layer. States between \
+                       the points of the finest layer are computed by replaying blocks. \
+                       Cannot be changed after initialization.")
+                .default_value("5,9,11,13,16,18,21")
                 .action(ArgAction::Set)
                 .display_order(0)
         )
@@ -762,6 +797,16 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("hdiff-buffer-cache-size")
+                .long("hdiff-buffer-cache-size")
+                .value_name("SIZE")
+                .help("Specifies how many decompressed snapshots and diffs from the freezer \
+                       database should be cached in memory")
+                .default_value("8")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("state-cache-size")
                 .long("state-cache-size")
@@ -781,7 +826,12 @@ pub fn cli_app() -> Command {
                 .alias("execution-endpoints")
                 .help("Server endpoint for an execution layer JWT-authenticated HTTP \
                        JSON-RPC connection. Uses the same endpoint to populate the \
-                       deposit cache.")
+                       deposit cache. Additional comma-separated endpoints are used as \
+                       backups: forkchoice updates are sent to all endpoints and payload \
+                       building fails over to a synced backup if the first endpoint is \
+                       offline or syncing. Endpoints of the form ipc:///path/to/socket \
+                       connect to a local execution layer over IPC, which does not \
+                       require a JWT secret.")
                 .action(ArgAction::Set)
                 .display_order(0)
         )
@@ -791,7 +841,9 @@ pub fn cli_app() -> Command {
                 .value_name("EXECUTION-JWT")
                 .alias("jwt-secrets")
                 .help("File path which contains the hex-encoded JWT secret for the \
-                       execution endpoint provided in the --execution-endpoint flag.")
+                       execution endpoint provided in the --execution-endpoint flag. \
+                       Either a single secret shared by all execution endpoints, or a \
+                       comma-separated list with one secret per endpoint.")
                 .requires("execution-endpoint")
                 .action(ArgAction::Set)
                 .display_order(0)
@@ -848,7 +900,9 @@ pub fn cli_app() -> Command {
                 .long("builder")
                 .alias("payload-builder")
                 .alias("payload-builders")
-                .help("The URL of a service compatible with the MEV-boost API.")
+                .help("One or more comma-delimited URLs of services compatible with the \
+                       MEV-boost API. All relays are queried in parallel and the most \
+                       valuable valid bid is used.")
                 .requires("execution-endpoint")
                 .action(ArgAction::Set)
                 .display_order(0)
@@ -922,7 +976,9 @@ pub fn cli_app() -> Command {
         .arg(
             Arg::new("auto-compact-db")
                 .long("auto-compact-db")
-                .help("Enable or disable automatic compaction of the database on finalization.")
+                .help("Enable or disable automatic compaction of the database on finalization. \
+                       Automatic compaction is disabled by default for the redb backend, because \
+                       it blocks all other database access while it runs.")
                 .action(ArgAction::Set)
                 .default_value("true")
                 .display_order(0)
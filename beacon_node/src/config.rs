@@ -11,7 +11,7 @@ use clap_utils::{parse_flag, parse_required};
 use client::{ClientConfig, ClientGenesis};
 use directory::{DEFAULT_BEACON_NODE_DIR, DEFAULT_NETWORK_DIR, DEFAULT_ROOT_DIR};
 use environment::RuntimeContext;
-use execution_layer::DEFAULT_JWT_FILE;
+use execution_layer::{ipc::is_ipc_url, DEFAULT_JWT_FILE};
 use genesis::Eth1Endpoint;
 use http_api::TlsConfig;
 use lighthouse_network::ListenAddress;
@@ -287,18 +287,30 @@ pub fn get_config<E: EthSpec>(
         // stakers. The merge is already complicated enough.
         client_config.sync_eth1_chain = true;
 
-        // Parse a single execution endpoint, logging warnings if multiple endpoints are supplied.
-        let execution_endpoint =
-            parse_only_one_value(endpoints, SensitiveUrl::parse, "--execution-endpoint", log)?;
+        // The first execution endpoint is the primary, any further endpoints are backups.
+        let mut execution_endpoints =
+            parse_comma_separated_values(endpoints, SensitiveUrl::parse, "--execution-endpoint")?;
+        let execution_endpoint = execution_endpoints.remove(0);
 
-        // JWTs are required if `--execution-endpoint` is supplied. They can be either passed via
-        // file_path or directly as string.
+        // JWTs are required if `--execution-endpoint` is supplied, unless it is an IPC endpoint.
+        // They can be either passed via file_path or directly as string.
 
-        let secret_file: PathBuf;
-        // Parse a single JWT secret from a given file_path, logging warnings if multiple are supplied.
+        let secret_file: Option<PathBuf>;
+        // Parse the JWT secrets from the given file paths. Either a single secret is shared by all
+        // execution endpoints, or one secret is provided per endpoint.
         if let Some(secret_files) = cli_args.get_one::<String>("execution-jwt") {
-            secret_file =
-                parse_only_one_value(secret_files, PathBuf::from_str, "--execution-jwt", log)?;
+            let mut secret_files =
+                parse_comma_separated_values(secret_files, PathBuf::from_str, "--execution-jwt")?;
+            secret_file = Some(secret_files.remove(0));
+            if !secret_files.is_empty() && secret_files.len() != execution_endpoints.len() {
+                return Err(format!(
+                    "--execution-jwt must provide either one JWT secret or one per execution \
+                     endpoint, got {} for {} endpoints",
+                    secret_files.len() + 1,
+                    execution_endpoints.len() + 1
+                ));
+            }
+            el_config.backup_secret_files = secret_files;
 
         // Check if the JWT secret key is passed directly via cli flag and persist it to the default
         // file location.
@@ -306,8 +318,8 @@ pub fn get_config<E: EthSpec>(
         {
             use std::fs::File;
             use std::io::Write;
-            secret_file = client_config.data_dir().join(DEFAULT_JWT_FILE);
-            let mut jwt_secret_key_file = File::create(secret_file.clone())
+            let jwt_secret_file = client_config.data_dir().join(DEFAULT_JWT_FILE);
+            let mut jwt_secret_key_file = File::create(&jwt_secret_file)
                 .map_err(|e| format!("Error while creating jwt_secret_key file: {:?}", e))?;
             jwt_secret_key_file
                 .write_all(jwt_secret_key.as_bytes())
@@ -317,15 +329,20 @@ pub fn get_config<E: EthSpec>(
                         e
                     )
                 })?;
+            secret_file = Some(jwt_secret_file);
+        } else if is_ipc_url(&execution_endpoint) {
+            secret_file = None;
         } else {
             return Err("Error! Please set either --execution-jwt file_path or --execution-jwt-secret-key directly via cli when using --execution-endpoint".to_string());
         }
 
-        // Parse and set the payload builder, if any.
-        if let Some(endpoint) = cli_args.get_one::<String>("builder") {
-            let payload_builder =
-                parse_only_one_value(endpoint, SensitiveUrl::parse, "--builder", log)?;
-            el_config.builder_url = Some(payload_builder);
+        // Parse and set the payload builders, if any.
+        if let Some(endpoints) = cli_args.get_one::<String>("builder") {
+            el_config.builder_urls = endpoints
+                .split(',')
+                .map(SensitiveUrl::parse)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("--builder contains an invalid value {:?}", e))?;
 
             el_config.builder_user_agent =
                 clap_utils::parse_optional(cli_args, "builder-user-agent")?;
@@ -344,8 +361,9 @@ pub fn get_config<E: EthSpec>(
         }
 
         // Set config values from parse values.
-        el_config.secret_file = Some(secret_file.clone());
+        el_config.secret_file.clone_from(&secret_file);
         el_config.execution_endpoint = Some(execution_endpoint.clone());
+        el_config.backup_execution_endpoints = execution_endpoints;
         el_config.suggested_fee_recipient =
             clap_utils::parse_optional(cli_args, "suggested-fee-recipient")?;
         el_config.jwt_id = clap_utils::parse_optional(cli_args, "execution-jwt-id")?;
@@ -357,11 +375,14 @@ pub fn get_config<E: EthSpec>(
             clap_utils::parse_required(cli_args, "execution-timeout-multiplier")?;
         el_config.execution_timeout_multiplier = Some(execution_timeout_multiplier);
 
-        client_config.eth1.endpoint = Eth1Endpoint::Auth {
-            endpoint: execution_endpoint,
-            jwt_path: secret_file,
-            jwt_id: el_config.jwt_id.clone(),
-            jwt_version: el_config.jwt_version.clone(),
+        client_config.eth1.endpoint = match secret_file {
+            Some(jwt_path) if !is_ipc_url(&execution_endpoint) => Eth1Endpoint::Auth {
+                endpoint: execution_endpoint,
+                jwt_path,
+                jwt_id: el_config.jwt_id.clone(),
+                jwt_version: el_config.jwt_version.clone(),
+            },
+            _ => Eth1Endpoint::NoAuth(execution_endpoint),
         };
 
         // Store the EL config in the client config.
@@ -395,12 +416,24 @@ pub fn get_config<E: EthSpec>(
         client_config.blobs_db_path = Some(PathBuf::from(blobs_db_dir));
     }
 
-    let (sprp, sprp_explicit) = get_slots_per_restore_point::<E>(clap_utils::parse_optional(
-        cli_args,
-        "slots-per-restore-point",
-    )?)?;
-    client_config.store.slots_per_restore_point = sprp;
-    client_config.store.slots_per_restore_point_set_explicitly = sprp_explicit;
+    if cli_args
+        .get_one::<String>("slots-per-restore-point")
+        .is_some()
+    {
+        warn!(
+            log,
+            "Ignoring --slots-per-restore-point";
+            "info" => "this flag is deprecated and will be removed, see --hierarchy-exponents"
+        );
+    }
+
+    if let Some(backend) = clap_utils::parse_optional(cli_args, "beacon-node-backend")? {
+        client_config.store.backend = backend;
+    }
+
+    if let Some(hierarchy_config) = clap_utils::parse_optional(cli_args, "hierarchy-exponents")? {
+        client_config.store.hierarchy_config = hierarchy_config;
+    }
 
     if let Some(block_cache_size) = cli_args.get_one::<String>("block-cache-size") {
         client_config.store.block_cache_size = block_cache_size
@@ -421,12 +454,22 @@ pub fn get_config<E: EthSpec>(
             .map_err(|_| "historic-state-cache-size is not a valid integer".to_string())?;
     }
 
+    if let Some(hdiff_buffer_cache_size) =
+        clap_utils::parse_optional(cli_args, "hdiff-buffer-cache-size")?
+    {
+        client_config.store.hdiff_buffer_cache_size = hdiff_buffer_cache_size;
+    }
+
     client_config.store.compact_on_init = cli_args.get_flag("compact-db");
     if let Some(compact_on_prune) = cli_args.get_one::<String>("auto-compact-db") {
         client_config.store.compact_on_prune = compact_on_prune
             .parse()
             .map_err(|_| "auto-compact-db takes a boolean".to_string())?;
     }
+    if cli_args.value_source("auto-compact-db") != Some(ValueSource::CommandLine) {
+        client_config.store.compact_on_prune =
+            client_config.store.backend.compact_on_prune_by_default();
+    }
 
     if let Some(prune_payloads) = clap_utils::parse_optional(cli_args, "prune-payloads")? {
         client_config.store.prune_payloads = prune_payloads;
@@ -1134,6 +1177,10 @@ pub fn set_network_config(
         config.subscribe_all_subnets = true;
     }
 
+    if parse_flag(cli_args, "subscribe-all-data-column-subnets") {
+        config.subscribe_all_data_column_subnets = true;
+    }
+
     if parse_flag(cli_args, "import-all-attestations") {
         config.import_all_attestations = true;
     }
@@ -1471,32 +1518,14 @@ pub fn get_data_dir(cli_args: &ArgMatches) -> PathBuf {
         .unwrap_or_else(|| PathBuf::from("."))
 }
 
-/// Get the `slots_per_restore_point` value to use for the database.
-///
-/// Return `(sprp, set_explicitly)` where `set_explicitly` is `true` if the user provided the value.
-pub fn get_slots_per_restore_point<E: EthSpec>(
-    slots_per_restore_point: Option<u64>,
-) -> Result<(u64, bool), String> {
-    if let Some(slots_per_restore_point) = slots_per_restore_point {
-        Ok((slots_per_restore_point, true))
-    } else {
-        let default = std::cmp::min(
-            E::slots_per_historical_root() as u64,
-            store::config::DEFAULT_SLOTS_PER_RESTORE_POINT,
-        );
-        Ok((default, false))
-    }
-}
-
 /// Parses the `cli_value` as a comma-separated string of values to be parsed with `parser`.
 ///
-/// If there is more than one value, log a warning. If there are no values, return an error.
-pub fn parse_only_one_value<F, T, U>(
+/// If there are no values, return an error.
+pub fn parse_comma_separated_values<F, T, U>(
     cli_value: &str,
     parser: F,
     flag_name: &str,
-    log: &Logger,
-) -> Result<T, String>
+) -> Result<Vec<T>, String>
 where
     F: Fn(&str) -> Result<T, U>,
     U: Debug,
@@ -1507,18 +1536,8 @@ where
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| format!("{} contains an invalid value {:?}", flag_name, e))?;
 
-    if values.len() > 1 {
-        warn!(
-            log,
-            "Multiple values provided";
-            "info" => "multiple values are deprecated, only the first value will be used",
-            "count" => values.len(),
-            "flag" => flag_name
-        );
+    if values.is_empty() {
+        return Err(format!("Must provide at least one value to {}", flag_name));
     }
-
-    values
-        .into_iter()
-        .next()
-        .ok_or(format!("Must provide at least one value to {}", flag_name))
+    Ok(values)
 }
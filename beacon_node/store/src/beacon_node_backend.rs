@@ -0,0 +1,191 @@
+use crate::config::{DatabaseBackend, StoreConfigError};
+#[cfg(feature = "redb")]
+use crate::redb_store::{Redb, REDB_DATA_FILENAME};
+use crate::{
+    ColumnIter, ColumnKeyIter, DBColumn, Error, ItemStore, Key, KeyValueStore, KeyValueStoreOp,
+    LevelDB, RawEntryIter, RawKeyIter,
+};
+use parking_lot::MutexGuard;
+use std::path::Path;
+use types::EthSpec;
+
+/// File which is present in every LevelDB database directory.
+const LEVELDB_CURRENT_FILENAME: &str = "CURRENT";
+
+/// An on-disk key-value store using the database backend selected at runtime.
+pub enum BeaconNodeBackend<E: EthSpec> {
+    LevelDb(LevelDB<E>),
+    #[cfg(feature = "redb")]
+    Redb(Redb<E>),
+}
+
+impl<E: EthSpec> BeaconNodeBackend<E> {
+    /// Open a database at `path` using `backend`, creating a new database if one does not already
+    /// exist.
+    ///
+    /// Returns an error if `path` already holds a database of a different backend.
+    pub fn open(backend: DatabaseBackend, path: &Path) -> Result<Self, Error> {
+        if let Some(on_disk) = DatabaseBackend::detect(path) {
+            if on_disk != backend {
+                return Err(StoreConfigError::MismatchedDatabaseBackend {
+                    config: backend,
+                    on_disk,
+                }
+                .into());
+            }
+        }
+
+        match backend {
+            DatabaseBackend::LevelDb => LevelDB::open(path).map(Self::LevelDb),
+            #[cfg(feature = "redb")]
+            DatabaseBackend::Redb => Redb::open(path).map(Self::Redb),
+        }
+    }
+
+    pub fn backend(&self) -> DatabaseBackend {
+        match self {
+            Self::LevelDb(_) => DatabaseBackend::LevelDb,
+            #[cfg(feature = "redb")]
+            Self::Redb(_) => DatabaseBackend::Redb,
+        }
+    }
+
+    /// Iterate through every key and value in the database, across all columns.
+    pub fn iter_all(&self) -> RawEntryIter {
+        match self {
+            Self::LevelDb(db) => db.iter_all(),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.iter_all(),
+        }
+    }
+}
+
+impl DatabaseBackend {
+    /// Determine the backend of the database in the directory at `path`, if any.
+    pub fn detect(path: &Path) -> Option<Self> {
+        #[cfg(feature = "redb")]
+        if path.join(REDB_DATA_FILENAME).exists() {
+            return Some(Self::Redb);
+        }
+        if path.join(LEVELDB_CURRENT_FILENAME).exists() {
+            return Some(Self::LevelDb);
+        }
+        None
+    }
+}
+
+impl<E: EthSpec> KeyValueStore<E> for BeaconNodeBackend<E> {
+    fn get_bytes(&self, column: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        match self {
+            Self::LevelDb(db) => db.get_bytes(column, key),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.get_bytes(column, key),
+        }
+    }
+
+    fn put_bytes(&self, column: &str, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => db.put_bytes(column, key, value),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.put_bytes(column, key, value),
+        }
+    }
+
+    fn put_bytes_sync(&self, column: &str, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => db.put_bytes_sync(column, key, value),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.put_bytes_sync(column, key, value),
+        }
+    }
+
+    fn sync(&self) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => db.sync(),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.sync(),
+        }
+    }
+
+    fn key_exists(&self, column: &str, key: &[u8]) -> Result<bool, Error> {
+        match self {
+            Self::LevelDb(db) => db.key_exists(column, key),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.key_exists(column, key),
+        }
+    }
+
+    fn key_delete(&self, column: &str, key: &[u8]) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => db.key_delete(column, key),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.key_delete(column, key),
+        }
+    }
+
+    fn do_atomically(&self, batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => db.do_atomically(batch),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.do_atomically(batch),
+        }
+    }
+
+    fn begin_rw_transaction(&self) -> MutexGuard<()> {
+        match self {
+            Self::LevelDb(db) => db.begin_rw_transaction(),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.begin_rw_transaction(),
+        }
+    }
+
+    fn compact_column(&self, column: DBColumn) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => db.compact_column(column),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.compact_column(column),
+        }
+    }
+
+    fn compact(&self) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => db.compact(),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.compact(),
+        }
+    }
+
+    fn iter_column_from<K: Key>(&self, column: DBColumn, from: &[u8]) -> ColumnIter<K> {
+        match self {
+            Self::LevelDb(db) => db.iter_column_from(column, from),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.iter_column_from(column, from),
+        }
+    }
+
+    fn iter_raw_entries(&self, column: DBColumn, prefix: &[u8]) -> RawEntryIter {
+        match self {
+            Self::LevelDb(db) => db.iter_raw_entries(column, prefix),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.iter_raw_entries(column, prefix),
+        }
+    }
+
+    fn iter_raw_keys(&self, column: DBColumn, prefix: &[u8]) -> RawKeyIter {
+        match self {
+            Self::LevelDb(db) => db.iter_raw_keys(column, prefix),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.iter_raw_keys(column, prefix),
+        }
+    }
+
+    fn iter_column_keys<K: Key>(&self, column: DBColumn) -> ColumnKeyIter<K> {
+        match self {
+            Self::LevelDb(db) => db.iter_column_keys(column),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.iter_column_keys(column),
+        }
+    }
+}
+
+impl<E: EthSpec> ItemStore<E> for BeaconNodeBackend<E> {}
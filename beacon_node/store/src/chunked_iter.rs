@@ -28,9 +28,9 @@ where
     Cold: ItemStore<E>,
 {
     /// Create a new iterator which can yield elements from `start_vindex` up to the last
-    /// index stored by the restore point at `last_restore_point_slot`.
+    /// index stored by the frozen state at `freezer_upper_limit`.
     ///
-    /// The `freezer_upper_limit` slot should be the slot of a recent restore point as obtained from
+    /// The `freezer_upper_limit` slot should be the slot of a recent frozen state as obtained from
     /// `Root::freezer_upper_limit`. We pass it as a parameter so that the caller can
     /// maintain a stable view of the database (see `HybridForwardsBlockRootsIterator`).
     pub fn new(
@@ -1,32 +1,35 @@
+use crate::hdiff::HierarchyConfig;
 use crate::{DBColumn, Error, StoreItem};
 use serde::{Deserialize, Serialize};
 use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
 use std::num::NonZeroUsize;
+use strum::{Display, EnumString, EnumVariantNames};
 use types::non_zero_usize::new_non_zero_usize;
-use types::{EthSpec, MinimalEthSpec};
 
-pub const PREV_DEFAULT_SLOTS_PER_RESTORE_POINT: u64 = 2048;
-pub const DEFAULT_SLOTS_PER_RESTORE_POINT: u64 = 8192;
 pub const DEFAULT_BLOCK_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(5);
 pub const DEFAULT_STATE_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(128);
 pub const DEFAULT_HISTORIC_STATE_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(1);
+pub const DEFAULT_HDIFF_BUFFER_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(8);
 pub const DEFAULT_EPOCHS_PER_BLOB_PRUNE: u64 = 1;
 pub const DEFAULT_BLOB_PUNE_MARGIN_EPOCHS: u64 = 0;
+pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::LevelDb;
 
 /// Database configuration parameters.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StoreConfig {
-    /// Number of slots to wait between storing restore points in the freezer database.
-    pub slots_per_restore_point: u64,
-    /// Flag indicating whether the `slots_per_restore_point` was set explicitly by the user.
-    pub slots_per_restore_point_set_explicitly: bool,
+    /// Database backend used for the hot, freezer and blobs databases.
+    pub backend: DatabaseBackend,
+    /// Layers of the diff hierarchy used to store states in the freezer database.
+    pub hierarchy_config: HierarchyConfig,
     /// Maximum number of blocks to store in the in-memory block cache.
     pub block_cache_size: NonZeroUsize,
     /// Maximum number of states to store in the in-memory state cache.
     pub state_cache_size: NonZeroUsize,
     /// Maximum number of states from freezer database to store in the in-memory state cache.
     pub historic_state_cache_size: NonZeroUsize,
+    /// Maximum number of diff buffers from the freezer database to store in memory.
+    pub hdiff_buffer_cache_size: NonZeroUsize,
     /// Whether to compact the database on initialization.
     pub compact_on_init: bool,
     /// Whether to compact the database during database pruning.
@@ -43,25 +46,60 @@ pub struct StoreConfig {
 }
 
 /// Variant of `StoreConfig` that gets written to disk. Contains immutable configuration params.
+///
+/// Prior to schema v22 this was the freezer's `slots_per_restore_point` as a single `u64`.
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub struct OnDiskStoreConfig {
-    pub slots_per_restore_point: u64,
+    pub hierarchy_config: HierarchyConfig,
 }
 
 #[derive(Debug, Clone)]
 pub enum StoreConfigError {
-    MismatchedSlotsPerRestorePoint { config: u64, on_disk: u64 },
+    MismatchedHierarchyConfig {
+        config: HierarchyConfig,
+        on_disk: HierarchyConfig,
+    },
+    MismatchedDatabaseBackend {
+        config: DatabaseBackend,
+        on_disk: DatabaseBackend,
+    },
+}
+
+/// Key-value database implementations which may back the beacon node's databases.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString, EnumVariantNames,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum DatabaseBackend {
+    LevelDb,
+    #[cfg(feature = "redb")]
+    Redb,
+}
+
+impl DatabaseBackend {
+    /// Whether the database is compacted after pruning, unless configured otherwise.
+    ///
+    /// Compacting redb blocks all other access to the database, and is unnecessary to reclaim the
+    /// space of deleted items because redb reuses freed pages.
+    pub fn compact_on_prune_by_default(&self) -> bool {
+        match self {
+            Self::LevelDb => true,
+            #[cfg(feature = "redb")]
+            Self::Redb => false,
+        }
+    }
 }
 
 impl Default for StoreConfig {
     fn default() -> Self {
         Self {
-            // Safe default for tests, shouldn't ever be read by a CLI node.
-            slots_per_restore_point: MinimalEthSpec::slots_per_historical_root() as u64,
-            slots_per_restore_point_set_explicitly: false,
+            backend: DEFAULT_BACKEND,
+            hierarchy_config: HierarchyConfig::default(),
             block_cache_size: DEFAULT_BLOCK_CACHE_SIZE,
             state_cache_size: DEFAULT_STATE_CACHE_SIZE,
             historic_state_cache_size: DEFAULT_HISTORIC_STATE_CACHE_SIZE,
+            hdiff_buffer_cache_size: DEFAULT_HDIFF_BUFFER_CACHE_SIZE,
             compact_on_init: false,
             compact_on_prune: true,
             prune_payloads: true,
@@ -75,7 +113,7 @@ impl Default for StoreConfig {
 impl StoreConfig {
     pub fn as_disk_config(&self) -> OnDiskStoreConfig {
         OnDiskStoreConfig {
-            slots_per_restore_point: self.slots_per_restore_point,
+            hierarchy_config: self.hierarchy_config.clone(),
         }
     }
 
@@ -83,10 +121,10 @@ impl StoreConfig {
         &self,
         on_disk_config: &OnDiskStoreConfig,
     ) -> Result<(), StoreConfigError> {
-        if self.slots_per_restore_point != on_disk_config.slots_per_restore_point {
-            return Err(StoreConfigError::MismatchedSlotsPerRestorePoint {
-                config: self.slots_per_restore_point,
-                on_disk: on_disk_config.slots_per_restore_point,
+        if self.hierarchy_config != on_disk_config.hierarchy_config {
+            return Err(StoreConfigError::MismatchedHierarchyConfig {
+                config: self.hierarchy_config.clone(),
+                on_disk: on_disk_config.hierarchy_config.clone(),
             });
         }
         Ok(())
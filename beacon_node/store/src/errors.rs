@@ -1,5 +1,6 @@
 use crate::chunked_vector::ChunkError;
 use crate::config::StoreConfigError;
+use crate::hdiff;
 use crate::hot_cold_store::HotColdDBError;
 use ssz::DecodeError;
 use state_processing::BlockReplayError;
@@ -57,6 +58,7 @@ pub enum Error {
         state_root: Hash256,
         slot: Slot,
     },
+    Hdiff(hdiff::Error),
 }
 
 pub trait HandleUnavailable<T> {
@@ -127,6 +129,12 @@ impl From<EpochCacheError> for Error {
     }
 }
 
+impl From<hdiff::Error> for Error {
+    fn from(e: hdiff::Error) -> Error {
+        Error::Hdiff(e)
+    }
+}
+
 #[derive(Debug)]
 pub struct DBError {
     pub message: String,
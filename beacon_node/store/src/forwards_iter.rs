@@ -78,10 +78,10 @@ impl<E: EthSpec> Root<E> for StateRoots {
     fn freezer_upper_limit<Hot: ItemStore<E>, Cold: ItemStore<E>>(
         store: &HotColdDB<E, Hot, Cold>,
     ) -> Option<Slot> {
-        // State roots are stored for all slots up to the latest restore point (exclusive).
-        // There may not be a latest restore point if state pruning is enabled, in which
-        // case this function will return `None`.
-        store.get_latest_restore_point_slot()
+        // State roots are stored for all slots up to the latest state on the finest layer of the
+        // diff hierarchy (exclusive). There may not be such a state if state pruning is enabled,
+        // in which case this function will return `None`.
+        store.get_latest_cold_state_slot()
     }
 }
 
@@ -97,16 +97,11 @@ impl<'a, E: EthSpec, F: Root<E>, Hot: ItemStore<E>, Cold: ItemStore<E>>
     pub fn new(
         store: &'a HotColdDB<E, Hot, Cold>,
         start_slot: Slot,
-        last_restore_point_slot: Slot,
+        last_cold_state_slot: Slot,
         spec: &ChainSpec,
     ) -> Self {
         Self {
-            inner: ChunkedVectorIter::new(
-                store,
-                start_slot.as_usize(),
-                last_restore_point_slot,
-                spec,
-            ),
+            inner: ChunkedVectorIter::new(store, start_slot.as_usize(), last_cold_state_slot, spec),
         }
     }
 }
@@ -0,0 +1,555 @@
+//! Hierarchical state diffs, used to store historic states compactly in the freezer database.
+//!
+//! States are stored in layers. Every layer is defined by a power of two: a state belongs to a
+//! layer if its slot is a multiple of the layer's spacing. States in the coarsest layer are stored
+//! as full snapshots. States in each finer layer are stored as a diff against the most recent
+//! state of the next coarser layer. States in between the points of the finest layer are not
+//! stored at all, and are reconstructed by replaying blocks on top of the most recent state of the
+//! finest layer.
+//!
+//! Loading any state therefore requires at most one snapshot, one diff application per layer and a
+//! block replay shorter than the spacing of the finest layer.
+use crate::PartialBeaconState;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use types::{milhouse, BeaconState, ChainSpec, EthSpec, List, Slot, Validator};
+
+/// The zstd compression level used for snapshots and diffs.
+pub const COMPRESSION_LEVEL: i32 = 1;
+
+#[derive(Debug)]
+pub enum Error {
+    InvalidHierarchy(String),
+    SlotBeforeStart { slot: Slot, start_slot: Slot },
+    InvalidValidatorIndex { index: u64, num_validators: usize },
+    InvalidBaseSlot { slot: Slot, base_slot: Slot },
+    Compression(std::io::Error),
+    SszDecode(ssz::DecodeError),
+    Milhouse(milhouse::Error),
+}
+
+impl From<ssz::DecodeError> for Error {
+    fn from(e: ssz::DecodeError) -> Self {
+        Error::SszDecode(e)
+    }
+}
+
+impl From<milhouse::Error> for Error {
+    fn from(e: milhouse::Error) -> Self {
+        Error::Milhouse(e)
+    }
+}
+
+/// Configuration of the layers of the diff hierarchy.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+pub struct HierarchyConfig {
+    /// The base-2 exponents of the slot spacing of each layer, from finest to coarsest.
+    ///
+    /// States at multiples of `2 ** exponents[i]` slots are stored as part of layer `i`.
+    pub exponents: Vec<u8>,
+}
+
+impl Default for HierarchyConfig {
+    fn default() -> Self {
+        Self {
+            exponents: vec![5, 9, 11, 13, 16, 18, 21],
+        }
+    }
+}
+
+impl FromStr for HierarchyConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let exponents = s
+            .split(',')
+            .map(|exponent| {
+                exponent
+                    .trim()
+                    .parse::<u8>()
+                    .map_err(|e| format!("invalid hierarchy exponent {exponent:?}: {e}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let config = Self { exponents };
+        config.validate().map_err(|e| format!("{e:?}"))?;
+        Ok(config)
+    }
+}
+
+impl fmt::Display for HierarchyConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.exponents.iter().join(","))
+    }
+}
+
+impl HierarchyConfig {
+    /// Check that there is at least one layer and that the layers are strictly increasing.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.exponents.is_empty() {
+            return Err(Error::InvalidHierarchy(
+                "at least one layer is required".to_string(),
+            ));
+        }
+        if !self.exponents.iter().tuple_windows().all(|(a, b)| a < b) {
+            return Err(Error::InvalidHierarchy(
+                "exponents must be strictly increasing".to_string(),
+            ));
+        }
+        if self
+            .exponents
+            .iter()
+            .any(|exponent| *exponent >= u64::BITS as u8)
+        {
+            return Err(Error::InvalidHierarchy(format!(
+                "exponents must be less than {}",
+                u64::BITS
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn to_moduli(&self) -> Result<HierarchyModuli, Error> {
+        self.validate()?;
+        Ok(HierarchyModuli {
+            moduli: self
+                .exponents
+                .iter()
+                .map(|exponent| 1 << exponent)
+                .collect(),
+        })
+    }
+}
+
+/// The slot spacing of each layer of a validated `HierarchyConfig`, from finest to coarsest.
+#[derive(Debug, Clone)]
+pub struct HierarchyModuli {
+    moduli: Vec<u64>,
+}
+
+/// How the state at a particular slot is stored in the freezer database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageStrategy {
+    /// Reconstruct the state by replaying blocks on top of the state at the given slot.
+    ReplayFrom(Slot),
+    /// Store a diff against the state at the given slot.
+    DiffFrom(Slot),
+    /// Store the full state.
+    Snapshot,
+}
+
+impl HierarchyModuli {
+    /// Return the storage strategy for the state at `slot`.
+    ///
+    /// States prior to `start_slot` are not available, so the state at `start_slot` is always
+    /// stored as a snapshot and states which would otherwise be based on an earlier state are
+    /// based on it instead.
+    pub fn storage_strategy(&self, slot: Slot, start_slot: Slot) -> Result<StorageStrategy, Error> {
+        match slot.cmp(&start_slot) {
+            Ordering::Less => return Err(Error::SlotBeforeStart { slot, start_slot }),
+            Ordering::Equal => return Ok(StorageStrategy::Snapshot),
+            Ordering::Greater => (),
+        }
+
+        if slot % self.snapshot_modulus() == 0 {
+            return Ok(StorageStrategy::Snapshot);
+        }
+
+        // Find the coarsest layer that `slot` belongs to and diff against the previous state of
+        // the layer above it.
+        let strategy = self
+            .moduli
+            .iter()
+            .rev()
+            .tuple_windows()
+            .find_map(|(&coarser, &finer)| {
+                (slot % finer == 0).then(|| StorageStrategy::DiffFrom(slot / coarser * coarser))
+            })
+            .unwrap_or_else(|| {
+                let finest = self.finest_modulus();
+                StorageStrategy::ReplayFrom(slot / finest * finest)
+            });
+
+        Ok(match strategy {
+            StorageStrategy::DiffFrom(base_slot) if base_slot < start_slot => {
+                StorageStrategy::DiffFrom(start_slot)
+            }
+            StorageStrategy::ReplayFrom(base_slot) if base_slot < start_slot => {
+                StorageStrategy::ReplayFrom(start_slot)
+            }
+            strategy => strategy,
+        })
+    }
+
+    /// The spacing of the finest layer, i.e. the maximum number of slots replayed to load a state.
+    pub fn finest_modulus(&self) -> u64 {
+        self.moduli[0]
+    }
+
+    /// The spacing of the coarsest layer, at which full snapshots are stored.
+    pub fn snapshot_modulus(&self) -> u64 {
+        self.moduli[self.moduli.len() - 1]
+    }
+}
+
+/// Compress `state` as a snapshot, without the vector fields stored separately in the freezer.
+pub fn compress_snapshot<E: EthSpec>(state: &BeaconState<E>) -> Result<Vec<u8>, Error> {
+    let partial_state = PartialBeaconState::from_state_forgetful(state);
+    compress(&partial_state.as_ssz_bytes())
+}
+
+/// Decompress a snapshot stored by `compress_snapshot`.
+pub fn decompress_snapshot<E: EthSpec>(
+    bytes: &[u8],
+    spec: &ChainSpec,
+) -> Result<PartialBeaconState<E>, Error> {
+    Ok(PartialBeaconState::from_ssz_bytes(
+        &decompress(bytes)?,
+        spec,
+    )?)
+}
+
+fn compress(bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    zstd::encode_all(bytes, COMPRESSION_LEVEL).map_err(Error::Compression)
+}
+
+fn decompress(bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    zstd::decode_all(bytes).map_err(Error::Compression)
+}
+
+/// A state in a form suitable for computing and applying diffs.
+///
+/// The lists which grow with the validator set are kept separately from the SSZ bytes of the rest
+/// of the state, so that they can be diffed element-wise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HDiffBuffer {
+    state: Vec<u8>,
+    balances: Vec<u64>,
+    inactivity_scores: Vec<u64>,
+    validators: Vec<Validator>,
+}
+
+impl HDiffBuffer {
+    pub fn from_state<E: EthSpec>(state: &BeaconState<E>) -> Self {
+        Self::from_partial_state(PartialBeaconState::from_state_forgetful(state))
+    }
+
+    pub fn from_partial_state<E: EthSpec>(mut partial_state: PartialBeaconState<E>) -> Self {
+        let balances = std::mem::take(partial_state.balances_mut())
+            .iter()
+            .copied()
+            .collect();
+        let validators = std::mem::take(partial_state.validators_mut())
+            .iter()
+            .cloned()
+            .collect();
+        let inactivity_scores = partial_state
+            .inactivity_scores_mut()
+            .map(|scores| std::mem::take(scores).iter().copied().collect())
+            .unwrap_or_default();
+
+        Self {
+            state: partial_state.as_ssz_bytes(),
+            balances,
+            inactivity_scores,
+            validators,
+        }
+    }
+
+    /// Convert the buffer back into a partial state, without its vector fields.
+    pub fn into_partial_state<E: EthSpec>(
+        self,
+        spec: &ChainSpec,
+    ) -> Result<PartialBeaconState<E>, Error> {
+        let mut partial_state = PartialBeaconState::from_ssz_bytes(&self.state, spec)?;
+        *partial_state.balances_mut() = List::new(self.balances)?;
+        *partial_state.validators_mut() = List::new(self.validators)?;
+        if let Ok(inactivity_scores) = partial_state.inactivity_scores_mut() {
+            *inactivity_scores = List::new(self.inactivity_scores)?;
+        }
+        Ok(partial_state)
+    }
+}
+
+/// A diff between two `HDiffBuffer`s.
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct HDiff {
+    /// The slot of the state that this diff applies to.
+    base_slot: Slot,
+    state: BytesDiff,
+    balances: U64Diff,
+    inactivity_scores: U64Diff,
+    validators: ValidatorsDiff,
+}
+
+impl HDiff {
+    pub fn compute(base_slot: Slot, source: &HDiffBuffer, target: &HDiffBuffer) -> Self {
+        Self {
+            base_slot,
+            state: BytesDiff::compute(&source.state, &target.state),
+            balances: U64Diff::compute(&source.balances, &target.balances),
+            inactivity_scores: U64Diff::compute(
+                &source.inactivity_scores,
+                &target.inactivity_scores,
+            ),
+            validators: ValidatorsDiff::compute(&source.validators, &target.validators),
+        }
+    }
+
+    /// Apply the diff to `buffer`, which must hold the state at `self.base_slot()`.
+    pub fn apply(&self, buffer: &mut HDiffBuffer) -> Result<(), Error> {
+        self.state.apply(&mut buffer.state);
+        self.balances.apply(&mut buffer.balances);
+        self.inactivity_scores.apply(&mut buffer.inactivity_scores);
+        self.validators.apply(&mut buffer.validators)
+    }
+
+    pub fn base_slot(&self) -> Slot {
+        self.base_slot
+    }
+
+    pub fn as_compressed_bytes(&self) -> Result<Vec<u8>, Error> {
+        compress(&self.as_ssz_bytes())
+    }
+
+    /// Decompress a diff stored for the state at `slot`.
+    pub fn from_compressed_bytes(slot: Slot, bytes: &[u8]) -> Result<Self, Error> {
+        let diff = Self::from_ssz_bytes(&decompress(bytes)?)?;
+        // Guard against infinite recursion when loading a corrupt database.
+        if diff.base_slot >= slot {
+            return Err(Error::InvalidBaseSlot {
+                slot,
+                base_slot: diff.base_slot,
+            });
+        }
+        Ok(diff)
+    }
+}
+
+/// Byte-wise XOR of the target against the source, which is mostly zeroes for similar inputs.
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct BytesDiff {
+    bytes: Vec<u8>,
+}
+
+impl BytesDiff {
+    fn compute(source: &[u8], target: &[u8]) -> Self {
+        Self {
+            bytes: xor_with_source(source, target),
+        }
+    }
+
+    fn apply(&self, source: &mut Vec<u8>) {
+        *source = xor_with_source(source, &self.bytes);
+    }
+}
+
+/// XOR each byte of `bytes` with the byte at the same position of `source`, if any.
+fn xor_with_source(source: &[u8], bytes: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .enumerate()
+        .map(|(i, byte)| byte ^ source.get(i).copied().unwrap_or(0))
+        .collect()
+}
+
+/// Element-wise wrapping difference of the target from the source.
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct U64Diff {
+    deltas: Vec<u64>,
+}
+
+impl U64Diff {
+    fn compute(source: &[u64], target: &[u64]) -> Self {
+        let deltas = target
+            .iter()
+            .enumerate()
+            .map(|(i, value)| value.wrapping_sub(source.get(i).copied().unwrap_or(0)))
+            .collect();
+        Self { deltas }
+    }
+
+    fn apply(&self, source: &mut Vec<u64>) {
+        let values = self
+            .deltas
+            .iter()
+            .enumerate()
+            .map(|(i, delta)| delta.wrapping_add(source.get(i).copied().unwrap_or(0)))
+            .collect();
+        *source = values;
+    }
+}
+
+/// The validators which were modified or appended to the registry.
+///
+/// Validators are never removed from the registry, so the target is always at least as long as
+/// the source.
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct ValidatorsDiff {
+    indices: Vec<u64>,
+    validators: Vec<Validator>,
+}
+
+impl ValidatorsDiff {
+    fn compute(source: &[Validator], target: &[Validator]) -> Self {
+        let (indices, validators) = target
+            .iter()
+            .enumerate()
+            .filter(|(i, validator)| source.get(*i) != Some(validator))
+            .map(|(i, validator)| (i as u64, validator.clone()))
+            .unzip();
+        Self {
+            indices,
+            validators,
+        }
+    }
+
+    fn apply(&self, source: &mut Vec<Validator>) -> Result<(), Error> {
+        for (&index, validator) in self.indices.iter().zip(&self.validators) {
+            let num_validators = source.len();
+            match source.get_mut(index as usize) {
+                Some(existing) => *existing = validator.clone(),
+                None if index as usize == num_validators => source.push(validator.clone()),
+                None => {
+                    return Err(Error::InvalidValidatorIndex {
+                        index,
+                        num_validators,
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+    use types::{Epoch, MainnetEthSpec};
+
+    fn moduli(exponents: &[u8]) -> HierarchyModuli {
+        HierarchyConfig {
+            exponents: exponents.to_vec(),
+        }
+        .to_moduli()
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_hierarchy_config() {
+        let config = HierarchyConfig::from_str("5, 9,11").unwrap();
+        assert_eq!(config.exponents, vec![5, 9, 11]);
+        assert_eq!(config.to_string(), "5,9,11");
+        assert_eq!(
+            HierarchyConfig::from_str(&HierarchyConfig::default().to_string()).unwrap(),
+            HierarchyConfig::default()
+        );
+
+        assert!(HierarchyConfig::from_str("").is_err());
+        assert!(HierarchyConfig::from_str("9,5").is_err());
+        assert!(HierarchyConfig::from_str("5,5").is_err());
+        assert!(HierarchyConfig::from_str("5,64").is_err());
+    }
+
+    #[test]
+    fn storage_strategy() {
+        let moduli = moduli(&[1, 3, 5]);
+        let start = Slot::new(0);
+        let strategy = |slot| moduli.storage_strategy(Slot::new(slot), start).unwrap();
+
+        assert_eq!(strategy(0), StorageStrategy::Snapshot);
+        assert_eq!(strategy(32), StorageStrategy::Snapshot);
+        assert_eq!(strategy(8), StorageStrategy::DiffFrom(Slot::new(0)));
+        assert_eq!(strategy(40), StorageStrategy::DiffFrom(Slot::new(32)));
+        assert_eq!(strategy(42), StorageStrategy::DiffFrom(Slot::new(40)));
+        assert_eq!(strategy(2), StorageStrategy::DiffFrom(Slot::new(0)));
+        assert_eq!(strategy(43), StorageStrategy::ReplayFrom(Slot::new(42)));
+        assert_eq!(strategy(1), StorageStrategy::ReplayFrom(Slot::new(0)));
+    }
+
+    #[test]
+    fn storage_strategy_with_start_slot() {
+        let moduli = moduli(&[1, 3, 5]);
+        let start = Slot::new(20);
+        let strategy = |slot| moduli.storage_strategy(Slot::new(slot), start).unwrap();
+
+        assert!(moduli.storage_strategy(Slot::new(19), start).is_err());
+        assert_eq!(strategy(20), StorageStrategy::Snapshot);
+        assert_eq!(strategy(21), StorageStrategy::ReplayFrom(Slot::new(20)));
+        assert_eq!(strategy(22), StorageStrategy::DiffFrom(Slot::new(20)));
+        assert_eq!(strategy(24), StorageStrategy::DiffFrom(Slot::new(20)));
+        assert_eq!(strategy(26), StorageStrategy::DiffFrom(Slot::new(24)));
+        assert_eq!(strategy(32), StorageStrategy::Snapshot);
+        assert_eq!(strategy(34), StorageStrategy::DiffFrom(Slot::new(32)));
+    }
+
+    #[test]
+    fn diff_round_trip() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let validators = (0..16)
+            .map(|_| Validator::random_for_test(&mut rng))
+            .collect::<Vec<_>>();
+        let source = HDiffBuffer {
+            state: vec![1, 2, 3, 4, 5],
+            balances: vec![32, 31, 33, u64::MAX],
+            inactivity_scores: vec![0, 0, 1, 2],
+            validators: validators[..4].to_vec(),
+        };
+        let mut modified_validator = validators[1].clone();
+        modified_validator.exit_epoch = Epoch::new(7);
+        let target = HDiffBuffer {
+            state: vec![1, 2, 7, 4, 5, 6, 7],
+            balances: vec![33, 30, 33, 0, 32, 32],
+            inactivity_scores: vec![1, 0, 0, 2, 0, 0],
+            validators: [
+                vec![validators[0].clone(), modified_validator],
+                validators[2..6].to_vec(),
+            ]
+            .concat(),
+        };
+
+        let diff = HDiff::compute(Slot::new(8), &source, &target);
+        assert_eq!(diff.validators.indices, vec![1, 4, 5]);
+
+        let bytes = diff.as_compressed_bytes().unwrap();
+        let decoded = HDiff::from_compressed_bytes(Slot::new(16), &bytes).unwrap();
+        assert_eq!(decoded, diff);
+        assert!(HDiff::from_compressed_bytes(Slot::new(8), &bytes).is_err());
+
+        let mut buffer = source.clone();
+        decoded.apply(&mut buffer).unwrap();
+        assert_eq!(buffer, target);
+    }
+
+    #[test]
+    fn invalid_validator_index() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let diff = ValidatorsDiff {
+            indices: vec![2],
+            validators: vec![Validator::random_for_test(&mut rng)],
+        };
+        let mut validators = vec![Validator::random_for_test(&mut rng)];
+        assert!(diff.apply(&mut validators).is_err());
+    }
+
+    #[test]
+    fn state_round_trip() {
+        let spec = MainnetEthSpec::default_spec();
+        let state = BeaconState::<MainnetEthSpec>::new(0, <_>::default(), &spec);
+        let buffer = HDiffBuffer::from_state(&state);
+        let partial_state = buffer
+            .clone()
+            .into_partial_state::<MainnetEthSpec>(&spec)
+            .unwrap();
+        assert_eq!(HDiffBuffer::from_partial_state(partial_state), buffer);
+
+        let snapshot = compress_snapshot(&state).unwrap();
+        let partial_state = decompress_snapshot::<MainnetEthSpec>(&snapshot, &spec).unwrap();
+        assert_eq!(HDiffBuffer::from_partial_state(partial_state), buffer);
+    }
+}
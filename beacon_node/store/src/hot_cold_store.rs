@@ -1,15 +1,13 @@
+use crate::beacon_node_backend::BeaconNodeBackend;
 use crate::chunked_vector::{
     store_updated_vector, BlockRoots, HistoricalRoots, HistoricalSummaries, RandaoMixes, StateRoots,
 };
-use crate::config::{
-    OnDiskStoreConfig, StoreConfig, DEFAULT_SLOTS_PER_RESTORE_POINT,
-    PREV_DEFAULT_SLOTS_PER_RESTORE_POINT,
-};
+use crate::config::{OnDiskStoreConfig, StoreConfig};
 use crate::forwards_iter::{HybridForwardsBlockRootsIterator, HybridForwardsStateRootsIterator};
+use crate::hdiff::{self, HDiff, HDiffBuffer, HierarchyModuli, StorageStrategy};
 use crate::impls::beacon_state::{get_full_state, store_full_state};
 use crate::iter::{BlockRootsIterator, ParentRootBlockIterator, RootsIterator};
 use crate::leveldb_store::BytesKey;
-use crate::leveldb_store::LevelDB;
 use crate::memory_store::MemoryStore;
 use crate::metadata::{
     AnchorInfo, BlobInfo, CompactionTimestamp, PruningCheckpoint, SchemaVersion, ANCHOR_INFO_KEY,
@@ -19,11 +17,10 @@ use crate::metadata::{
 use crate::metrics;
 use crate::state_cache::{PutStateOutcome, StateCache};
 use crate::{
-    get_key_for_col, ChunkWriter, DBColumn, DatabaseBlock, Error, ItemStore, KeyValueStoreOp,
-    PartialBeaconState, StoreItem, StoreOp,
+    get_data_column_key, get_key_for_col, ChunkWriter, DBColumn, DatabaseBlock, Error, ItemStore,
+    KeyValueStore, KeyValueStoreOp, PartialBeaconState, StoreItem, StoreOp,
 };
 use itertools::process_results;
-use leveldb::iterator::LevelDBIterator;
 use lru::LruCache;
 use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
@@ -44,8 +41,8 @@ use types::*;
 
 /// On-disk database that stores finalized states efficiently.
 ///
-/// Stores vector fields like the `block_roots` and `state_roots` separately, and only stores
-/// intermittent "restore point" states pre-finalization.
+/// Stores vector fields like the `block_roots` and `state_roots` separately, and stores
+/// pre-finalization states as a hierarchy of snapshots and diffs (see `hdiff`).
 #[derive(Debug)]
 pub struct HotColdDB<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> {
     /// The slot and state root at the point where the database is split between hot and cold.
@@ -74,6 +71,10 @@ pub struct HotColdDB<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> {
     state_cache: Mutex<StateCache<E>>,
     /// LRU cache of replayed states.
     historic_state_cache: Mutex<LruCache<Slot, BeaconState<E>>>,
+    /// LRU cache of diff buffers of frozen states, used as the bases of other states.
+    hdiff_buffer_cache: Mutex<LruCache<Slot, HDiffBuffer>>,
+    /// The slot spacing of each layer of the freezer's diff hierarchy.
+    hierarchy: HierarchyModuli,
     /// Chain spec.
     pub(crate) spec: ChainSpec,
     /// Logger.
@@ -133,8 +134,9 @@ pub enum HotColdDBError {
         proposed_split_slot: Slot,
     },
     MissingStateToFreeze(Hash256),
-    MissingRestorePointHash(u64),
     MissingRestorePoint(Hash256),
+    MissingSnapshotOrDiff(Slot),
+    MissingFrozenBlockRoot(Slot),
     MissingColdStateSummary(Hash256),
     MissingHotStateSummary(Hash256),
     MissingEpochBoundaryState(Hash256),
@@ -149,8 +151,9 @@ pub enum HotColdDBError {
     BlockReplaySlotError(SlotProcessingError),
     BlockReplayBlockError(BlockProcessingError),
     MissingLowerLimitState(Slot),
-    InvalidSlotsPerRestorePoint {
-        slots_per_restore_point: u64,
+    InvalidHierarchy(String),
+    InvalidHierarchyFinestLayer {
+        finest_modulus: u64,
         slots_per_historical_root: u64,
         slots_per_epoch: u64,
     },
@@ -174,7 +177,7 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
         spec: ChainSpec,
         log: Logger,
     ) -> Result<HotColdDB<E, MemoryStore<E>, MemoryStore<E>>, Error> {
-        Self::verify_config(&config)?;
+        let hierarchy = Self::verify_config(&config)?;
 
         let db = HotColdDB {
             split: RwLock::new(Split::default()),
@@ -186,6 +189,8 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
             block_cache: Mutex::new(BlockCache::new(config.block_cache_size)),
             state_cache: Mutex::new(StateCache::new(config.state_cache_size)),
             historic_state_cache: Mutex::new(LruCache::new(config.historic_state_cache_size)),
+            hdiff_buffer_cache: Mutex::new(LruCache::new(config.hdiff_buffer_cache_size)),
+            hierarchy,
             config,
             spec,
             log,
@@ -196,7 +201,7 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
     }
 }
 
-impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
+impl<E: EthSpec> HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>> {
     /// Open a new or existing database, with the given paths to the hot and cold DBs.
     ///
     /// The `migrate_schema` function is passed in so that the parent `BeaconChain` can provide
@@ -210,44 +215,26 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
         spec: ChainSpec,
         log: Logger,
     ) -> Result<Arc<Self>, Error> {
-        Self::verify_slots_per_restore_point(config.slots_per_restore_point)?;
+        let hierarchy = Self::verify_hierarchy_config(&config)?;
 
-        let mut db = HotColdDB {
+        let db = HotColdDB {
             split: RwLock::new(Split::default()),
             anchor_info: RwLock::new(None),
             blob_info: RwLock::new(BlobInfo::default()),
-            cold_db: LevelDB::open(cold_path)?,
-            blobs_db: LevelDB::open(blobs_db_path)?,
-            hot_db: LevelDB::open(hot_path)?,
+            cold_db: BeaconNodeBackend::open(config.backend, cold_path)?,
+            blobs_db: BeaconNodeBackend::open(config.backend, blobs_db_path)?,
+            hot_db: BeaconNodeBackend::open(config.backend, hot_path)?,
             block_cache: Mutex::new(BlockCache::new(config.block_cache_size)),
             state_cache: Mutex::new(StateCache::new(config.state_cache_size)),
             historic_state_cache: Mutex::new(LruCache::new(config.historic_state_cache_size)),
+            hdiff_buffer_cache: Mutex::new(LruCache::new(config.hdiff_buffer_cache_size)),
+            hierarchy,
             config,
             spec,
             log,
             _phantom: PhantomData,
         };
 
-        // Allow the slots-per-restore-point value to stay at the previous default if the config
-        // uses the new default. Don't error on a failed read because the config itself may need
-        // migrating.
-        if let Ok(Some(disk_config)) = db.load_config() {
-            if !db.config.slots_per_restore_point_set_explicitly
-                && disk_config.slots_per_restore_point == PREV_DEFAULT_SLOTS_PER_RESTORE_POINT
-                && db.config.slots_per_restore_point == DEFAULT_SLOTS_PER_RESTORE_POINT
-            {
-                debug!(
-                    db.log,
-                    "Ignoring slots-per-restore-point config in favour of on-disk value";
-                    "config" => db.config.slots_per_restore_point,
-                    "on_disk" => disk_config.slots_per_restore_point,
-                );
-
-                // Mutate the in-memory config so that it's compatible.
-                db.config.slots_per_restore_point = PREV_DEFAULT_SLOTS_PER_RESTORE_POINT;
-            }
-        }
-
         // Load the previous split slot from the database (if any). This ensures we can
         // stop and restart correctly. This needs to occur *before* running any migrations
         // because some migrations load states and depend on the split.
@@ -316,11 +303,15 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
             db.store_schema_version(CURRENT_SCHEMA_VERSION)?;
         }
 
-        // Ensure that any on-disk config is compatible with the supplied config.
-        if let Some(disk_config) = db.load_config()? {
-            db.config.check_compatibility(&disk_config)?;
+        // Ensure that any on-disk config is compatible with the supplied config. If the schema is yet
+        // to be migrated (e.g. by `database_manager migrate`) the on-disk config may be in a legacy
+        // format, and is left as-is.
+        if db.load_schema_version()? == Some(CURRENT_SCHEMA_VERSION) {
+            if let Some(disk_config) = db.load_config()? {
+                db.config.check_compatibility(&disk_config)?;
+            }
+            db.store_config()?;
         }
-        db.store_config()?;
 
         // Run a garbage collection pass.
         db.remove_garbage()?;
@@ -337,23 +328,8 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
 
     /// Return an iterator over the state roots of all temporary states.
     pub fn iter_temporary_state_roots(&self) -> impl Iterator<Item = Result<Hash256, Error>> + '_ {
-        let column = DBColumn::BeaconStateTemporary;
-        let start_key =
-            BytesKey::from_vec(get_key_for_col(column.into(), Hash256::zero().as_bytes()));
-
-        let keys_iter = self.hot_db.keys_iter();
-        keys_iter.seek(&start_key);
-
-        keys_iter
-            .take_while(move |key| key.matches_column(column))
-            .map(move |bytes_key| {
-                bytes_key.remove_column(column).ok_or_else(|| {
-                    HotColdDBError::IterationError {
-                        unexpected_key: bytes_key,
-                    }
-                    .into()
-                })
-            })
+        self.hot_db
+            .iter_column_keys::<Hash256>(DBColumn::BeaconStateTemporary)
     }
 }
 
@@ -606,6 +582,49 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             .key_delete(DBColumn::BeaconBlob.into(), block_root.as_bytes())
     }
 
+    /// Load the best `LightClientUpdate` stored for `sync_committee_period`.
+    ///
+    /// Updates are prefixed with the slot of their attested header, which determines the fork
+    /// they are decoded with.
+    pub fn get_light_client_update(
+        &self,
+        sync_committee_period: u64,
+    ) -> Result<Option<LightClientUpdate<E>>, Error> {
+        let Some(bytes) = self.hot_db.get_bytes(
+            DBColumn::LightClientUpdate.into(),
+            &sync_committee_period.to_be_bytes(),
+        )?
+        else {
+            return Ok(None);
+        };
+        if bytes.len() < 8 {
+            return Err(Error::InvalidBytes);
+        }
+        let (slot_bytes, update_bytes) = bytes.split_at(8);
+        let attested_slot = Slot::from_ssz_bytes(slot_bytes)?;
+        let fork_name = self.spec.fork_name_at_slot::<E>(attested_slot);
+        Ok(Some(LightClientUpdate::from_ssz_bytes(
+            update_bytes,
+            fork_name,
+        )?))
+    }
+
+    /// Store `update` as the best `LightClientUpdate` for `sync_committee_period`, replacing any
+    /// existing update.
+    pub fn put_light_client_update(
+        &self,
+        sync_committee_period: u64,
+        update: &LightClientUpdate<E>,
+    ) -> Result<(), Error> {
+        let mut bytes = update.attested_header_slot().as_ssz_bytes();
+        bytes.extend_from_slice(&update.as_ssz_bytes());
+        self.hot_db.put_bytes(
+            DBColumn::LightClientUpdate.into(),
+            &sync_committee_period.to_be_bytes(),
+            &bytes,
+        )
+    }
+
     pub fn put_blobs(&self, block_root: &Hash256, blobs: BlobSidecarList<E>) -> Result<(), Error> {
         self.blobs_db.put_bytes(
             DBColumn::BeaconBlob.into(),
@@ -626,6 +645,34 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         ops.push(KeyValueStoreOp::PutKeyValue(db_key, blobs.as_ssz_bytes()));
     }
 
+    pub fn put_data_columns(
+        &self,
+        block_root: &Hash256,
+        data_columns: DataColumnSidecarList<E>,
+    ) -> Result<(), Error> {
+        let mut ops = Vec::with_capacity(data_columns.len());
+        self.data_columns_as_kv_store_ops(block_root, data_columns, &mut ops);
+        self.blobs_db.do_atomically(ops)
+    }
+
+    pub fn data_columns_as_kv_store_ops(
+        &self,
+        block_root: &Hash256,
+        data_columns: DataColumnSidecarList<E>,
+        ops: &mut Vec<KeyValueStoreOp>,
+    ) {
+        for data_column in data_columns {
+            let db_key = get_key_for_col(
+                DBColumn::BeaconDataColumn.into(),
+                &get_data_column_key(block_root, &data_column.index),
+            );
+            ops.push(KeyValueStoreOp::PutKeyValue(
+                db_key,
+                data_column.as_ssz_bytes(),
+            ));
+        }
+    }
+
     pub fn put_state_summary(
         &self,
         state_root: &Hash256,
@@ -650,8 +697,12 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     ) -> Result<(), Error> {
         let mut ops: Vec<KeyValueStoreOp> = Vec::new();
         if state.slot() < self.get_split_slot() {
-            self.store_cold_state(state_root, state, &mut ops)?;
-            self.cold_db.do_atomically(ops)
+            let hdiff_buffer = self.store_cold_state(state_root, state, &mut ops)?;
+            self.cold_db.do_atomically(ops)?;
+            if let Some(buffer) = hdiff_buffer {
+                self.cache_hdiff_buffer(state.slot(), buffer);
+            }
+            Ok(())
         } else {
             if temporary {
                 ops.push(TemporaryFlag.as_kv_store_op(*state_root));
@@ -909,6 +960,14 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                     self.blobs_as_kv_store_ops(&block_root, blobs, &mut key_value_batch);
                 }
 
+                StoreOp::PutDataColumns(block_root, data_columns) => {
+                    self.data_columns_as_kv_store_ops(
+                        &block_root,
+                        data_columns,
+                        &mut key_value_batch,
+                    );
+                }
+
                 StoreOp::PutStateSummary(state_root, summary) => {
                     key_value_batch.push(summary.as_kv_store_op(state_root));
                 }
@@ -933,6 +992,16 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                     key_value_batch.push(KeyValueStoreOp::DeleteKey(key));
                 }
 
+                StoreOp::DeleteDataColumns(block_root, column_indices) => {
+                    for index in column_indices {
+                        let key = get_key_for_col(
+                            DBColumn::BeaconDataColumn.into(),
+                            &get_data_column_key(&block_root, &index),
+                        );
+                        key_value_batch.push(KeyValueStoreOp::DeleteKey(key));
+                    }
+                }
+
                 StoreOp::DeleteState(state_root, slot) => {
                     let state_summary_key =
                         get_key_for_col(DBColumn::BeaconStateSummary.into(), state_root.as_bytes());
@@ -963,9 +1032,10 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         batch: Vec<StoreOp<E>>,
     ) -> Result<(), Error> {
         let mut blobs_to_delete = Vec::new();
+        let mut data_columns_to_delete = Vec::new();
         let (blobs_ops, hot_db_ops): (Vec<StoreOp<E>>, Vec<StoreOp<E>>) =
             batch.into_iter().partition(|store_op| match store_op {
-                StoreOp::PutBlobs(_, _) => true,
+                StoreOp::PutBlobs(_, _) | StoreOp::PutDataColumns(_, _) => true,
                 StoreOp::DeleteBlobs(block_root) => {
                     match self.get_blobs(block_root) {
                         Ok(Some(blob_sidecar_list)) => {
@@ -982,6 +1052,25 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                     }
                     true
                 }
+                StoreOp::DeleteDataColumns(block_root, column_indices) => {
+                    let data_columns = column_indices
+                        .iter()
+                        .filter_map(|index| match self.get_data_column(block_root, index) {
+                            Ok(data_column) => data_column,
+                            Err(e) => {
+                                error!(
+                                    self.log, "Error getting data column";
+                                    "block_root" => %block_root,
+                                    "column_index" => index,
+                                    "error" => ?e
+                                );
+                                None
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    data_columns_to_delete.push((*block_root, data_columns));
+                    true
+                }
                 StoreOp::PutBlock(_, _) | StoreOp::DeleteBlock(_) => false,
                 _ => false,
             });
@@ -1017,6 +1106,18 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                         Some((block_root, blobs)) => StoreOp::PutBlobs(block_root, blobs),
                         None => return Err(HotColdDBError::Rollback.into()),
                     },
+                    StoreOp::PutDataColumns(block_root, data_columns) => {
+                        StoreOp::DeleteDataColumns(
+                            *block_root,
+                            data_columns.iter().map(|c| c.index).collect(),
+                        )
+                    }
+                    StoreOp::DeleteDataColumns(_, _) => match data_columns_to_delete.pop() {
+                        Some((block_root, data_columns)) => {
+                            StoreOp::PutDataColumns(block_root, data_columns)
+                        }
+                        None => return Err(HotColdDBError::Rollback.into()),
+                    },
                     _ => return Err(HotColdDBError::Rollback.into()),
                 };
                 *op = reverse_op;
@@ -1034,6 +1135,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
                 StoreOp::PutBlobs(_, _) => (),
 
+                StoreOp::PutDataColumns(_, _) => (),
+
                 StoreOp::PutState(_, _) => (),
 
                 StoreOp::PutStateSummary(_, _) => (),
@@ -1053,6 +1156,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
                 StoreOp::DeleteBlobs(_) => (),
 
+                StoreOp::DeleteDataColumns(_, _) => (),
+
                 StoreOp::DeleteExecutionPayload(_) => (),
 
                 StoreOp::KeyValueOp(_) => (),
@@ -1249,32 +1354,57 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
     /// Store a pre-finalization state in the freezer database.
     ///
-    /// If the state doesn't lie on a restore point boundary then just its summary will be stored.
+    /// If the state doesn't lie on a layer of the diff hierarchy then just its summary will be
+    /// stored. States must be stored in slot-ascending order, and the ops for each state committed
+    /// before the next is stored, as states are diffed against previously stored states.
+    ///
+    /// If the state is stored as a snapshot or diff, its diff buffer is returned. The caller may
+    /// pass it to `cache_hdiff_buffer` once `ops` have been committed, but not before, as the cache
+    /// must not hold the buffer of a state which failed to be written.
     pub fn store_cold_state(
         &self,
         state_root: &Hash256,
         state: &BeaconState<E>,
         ops: &mut Vec<KeyValueStoreOp>,
-    ) -> Result<(), Error> {
+    ) -> Result<Option<HDiffBuffer>, Error> {
         ops.push(ColdStateSummary { slot: state.slot() }.as_kv_store_op(*state_root));
 
-        if state.slot() % self.config.slots_per_restore_point != 0 {
-            return Ok(());
-        }
-
-        trace!(
-            self.log,
-            "Creating restore point";
-            "slot" => state.slot(),
-            "state_root" => format!("{:?}", state_root)
-        );
-
-        // 1. Convert to PartialBeaconState and store that in the DB.
-        let partial_state = PartialBeaconState::from_state_forgetful(state);
-        let op = partial_state.as_kv_store_op(*state_root);
-        ops.push(op);
+        let slot = state.slot();
+        let hdiff_buffer = match self.cold_storage_strategy(slot)? {
+            StorageStrategy::ReplayFrom(_) => return Ok(None),
+            StorageStrategy::Snapshot => {
+                trace!(
+                    self.log,
+                    "Storing cold state snapshot";
+                    "slot" => slot,
+                    "state_root" => ?state_root,
+                );
+                let bytes = hdiff::compress_snapshot(state)?;
+                let db_key = get_key_for_col(DBColumn::BeaconStateSnapshot.into(), &slot_key(slot));
+                ops.push(KeyValueStoreOp::PutKeyValue(db_key, bytes));
+                HDiffBuffer::from_state(state)
+            }
+            StorageStrategy::DiffFrom(base_slot) => {
+                trace!(
+                    self.log,
+                    "Storing cold state diff";
+                    "slot" => slot,
+                    "base_slot" => base_slot,
+                    "state_root" => ?state_root,
+                );
+                let base_buffer = self.load_hdiff_buffer_for_slot(base_slot)?;
+                let target_buffer = HDiffBuffer::from_state(state);
+                let diff = HDiff::compute(base_slot, &base_buffer, &target_buffer);
+                let db_key = get_key_for_col(DBColumn::BeaconStateDiff.into(), &slot_key(slot));
+                ops.push(KeyValueStoreOp::PutKeyValue(
+                    db_key,
+                    diff.as_compressed_bytes()?,
+                ));
+                target_buffer
+            }
+        };
 
-        // 2. Store updated vector entries.
+        // Store updated vector entries.
         // Block roots need to be written here as well as by the `ChunkWriter` in `migrate_db`
         // because states may require older block roots, and the writer only stores block roots
         // between the previous split point and the new split point.
@@ -1285,11 +1415,28 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         store_updated_vector(RandaoMixes, db, state, &self.spec, ops)?;
         store_updated_vector(HistoricalSummaries, db, state, &self.spec, ops)?;
 
-        // 3. Store restore point.
-        let restore_point_index = state.slot().as_u64() / self.config.slots_per_restore_point;
-        self.store_restore_point_hash(restore_point_index, *state_root, ops);
+        Ok(Some(hdiff_buffer))
+    }
 
-        Ok(())
+    /// Cache the diff buffer of the frozen state at `slot`, which must already be committed.
+    pub(crate) fn cache_hdiff_buffer(&self, slot: Slot, buffer: HDiffBuffer) {
+        self.hdiff_buffer_cache.lock().put(slot, buffer);
+    }
+
+    /// Return the storage strategy of the frozen state at `slot`.
+    ///
+    /// After checkpoint sync the states stored from the state upper limit onwards are based on
+    /// the state at the upper limit, as the states prior to it are not yet available. States
+    /// stored by reconstruction prior to the upper limit use the full hierarchy.
+    pub fn cold_storage_strategy(&self, slot: Slot) -> Result<StorageStrategy, Error> {
+        let start_slot = self
+            .get_anchor_info()
+            .map(|anchor| anchor.state_upper_limit)
+            .filter(|upper_limit| {
+                *upper_limit != STATE_UPPER_LIMIT_NO_RETAIN && slot >= *upper_limit
+            })
+            .unwrap_or(self.spec.genesis_slot);
+        Ok(self.hierarchy.storage_strategy(slot, start_slot)?)
     }
 
     /// Try to load a pre-finalization state from the freezer database.
@@ -1304,7 +1451,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
     /// Load a pre-finalization state from the freezer database.
     ///
-    /// Will reconstruct the state if it lies between restore points.
+    /// Will reconstruct the state from its snapshot and diffs, replaying blocks if it lies between
+    /// the states of the finest layer of the hierarchy.
     pub fn load_cold_state_by_slot(&self, slot: Slot) -> Result<Option<BeaconState<E>>, Error> {
         // Guard against fetching states that do not exist due to gaps in the historic state
         // database, which can occur due to checkpoint sync or re-indexing.
@@ -1312,28 +1460,71 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         let (lower_limit, upper_limit) = self.get_historic_state_limits();
 
         if slot <= lower_limit || slot >= upper_limit {
-            if slot % self.config.slots_per_restore_point == 0 {
-                let restore_point_idx = slot.as_u64() / self.config.slots_per_restore_point;
-                self.load_restore_point_by_index(restore_point_idx)
-            } else {
-                self.load_cold_intermediate_state(slot)
-            }
-            .map(Some)
+            self.load_hierarchical_cold_state(slot).map(Some)
         } else {
             Ok(None)
         }
     }
 
-    /// Load a restore point state by its `state_root`.
-    fn load_restore_point(&self, state_root: &Hash256) -> Result<BeaconState<E>, Error> {
-        let partial_state_bytes = self
+    /// Load the frozen state at `slot`, which must lie within the historic state limits.
+    fn load_hierarchical_cold_state(&self, slot: Slot) -> Result<BeaconState<E>, Error> {
+        if let Some(state) = self.historic_state_cache.lock().get(&slot) {
+            return Ok(state.clone());
+        }
+
+        let state = match self.cold_storage_strategy(slot)? {
+            StorageStrategy::Snapshot | StorageStrategy::DiffFrom(_) => {
+                let buffer = self.load_hdiff_buffer_for_slot(slot)?;
+                self.load_cold_state_from_partial(buffer.into_partial_state(&self.spec)?)?
+            }
+            StorageStrategy::ReplayFrom(base_slot) => {
+                self.load_cold_state_by_replay(base_slot, slot)?
+            }
+        };
+
+        self.historic_state_cache.lock().put(slot, state.clone());
+
+        Ok(state)
+    }
+
+    /// Load the diff buffer of the frozen state at `slot`, which must be stored as a snapshot or a
+    /// diff.
+    ///
+    /// Diffs record the slot of the state they are based on, so the hierarchy can be traversed
+    /// without knowledge of the anchor at the time the state was stored.
+    fn load_hdiff_buffer_for_slot(&self, slot: Slot) -> Result<HDiffBuffer, Error> {
+        if let Some(buffer) = self.hdiff_buffer_cache.lock().get(&slot) {
+            return Ok(buffer.clone());
+        }
+
+        let key = slot_key(slot);
+        let buffer = if let Some(bytes) = self
             .cold_db
-            .get_bytes(DBColumn::BeaconState.into(), state_root.as_bytes())?
-            .ok_or(HotColdDBError::MissingRestorePoint(*state_root))?;
-        let mut partial_state: PartialBeaconState<E> =
-            PartialBeaconState::from_ssz_bytes(&partial_state_bytes, &self.spec)?;
+            .get_bytes(DBColumn::BeaconStateDiff.into(), &key)?
+        {
+            let diff = HDiff::from_compressed_bytes(slot, &bytes)?;
+            let mut buffer = self.load_hdiff_buffer_for_slot(diff.base_slot())?;
+            diff.apply(&mut buffer)?;
+            buffer
+        } else if let Some(bytes) = self
+            .cold_db
+            .get_bytes(DBColumn::BeaconStateSnapshot.into(), &key)?
+        {
+            HDiffBuffer::from_partial_state(hdiff::decompress_snapshot::<E>(&bytes, &self.spec)?)
+        } else {
+            return Err(HotColdDBError::MissingSnapshotOrDiff(slot).into());
+        };
+
+        self.hdiff_buffer_cache.lock().put(slot, buffer.clone());
+
+        Ok(buffer)
+    }
 
-        // Fill in the fields of the partial state.
+    /// Fill in the vector fields of a partial state from the freezer database.
+    fn load_cold_state_from_partial(
+        &self,
+        mut partial_state: PartialBeaconState<E>,
+    ) -> Result<BeaconState<E>, Error> {
         partial_state.load_block_roots(&self.cold_db, &self.spec)?;
         partial_state.load_state_roots(&self.cold_db, &self.spec)?;
         partial_state.load_historical_roots(&self.cold_db, &self.spec)?;
@@ -1345,109 +1536,75 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         Ok(state)
     }
 
-    /// Load a restore point state by its `restore_point_index`.
-    fn load_restore_point_by_index(
+    /// Load a frozen state that lies between the states of the finest layer of the hierarchy, by
+    /// replaying blocks on top of the state at `base_slot`.
+    fn load_cold_state_by_replay(
         &self,
-        restore_point_index: u64,
+        base_slot: Slot,
+        slot: Slot,
     ) -> Result<BeaconState<E>, Error> {
-        let state_root = self.load_restore_point_hash(restore_point_index)?;
-        self.load_restore_point(&state_root)
-    }
-
-    /// Load a frozen state that lies between restore points.
-    fn load_cold_intermediate_state(&self, slot: Slot) -> Result<BeaconState<E>, Error> {
-        if let Some(state) = self.historic_state_cache.lock().get(&slot) {
-            return Ok(state.clone());
-        }
-
-        // 1. Load the restore points either side of the intermediate state.
-        let low_restore_point_idx = slot.as_u64() / self.config.slots_per_restore_point;
-        let high_restore_point_idx = low_restore_point_idx + 1;
-
-        // Use low restore point as the base state.
-        let mut low_slot: Slot =
-            Slot::new(low_restore_point_idx * self.config.slots_per_restore_point);
-        let mut low_state: Option<BeaconState<E>> = None;
-
         // Try to get a more recent state from the cache to avoid massive blocks replay.
-        for (s, state) in self.historic_state_cache.lock().iter() {
-            if s.as_u64() / self.config.slots_per_restore_point == low_restore_point_idx
-                && *s < slot
-                && low_slot < *s
-            {
-                low_slot = *s;
-                low_state = Some(state.clone());
-            }
-        }
-
-        // If low_state is still None, use load_restore_point_by_index to load the state.
-        let low_state = match low_state {
-            Some(state) => state,
-            None => self.load_restore_point_by_index(low_restore_point_idx)?,
+        let cached_state = self
+            .historic_state_cache
+            .lock()
+            .iter()
+            .filter(|(s, _)| base_slot < **s && **s < slot)
+            .max_by_key(|(s, _)| **s)
+            .map(|(s, state)| (*s, state.clone()));
+
+        let (low_slot, low_state) = match cached_state {
+            Some(cached) => cached,
+            None => (base_slot, self.load_hierarchical_cold_state(base_slot)?),
         };
 
         // Acquire the read lock, so that the split can't change while this is happening.
         let split = self.split.read_recursive();
 
-        let high_restore_point = self.get_restore_point(high_restore_point_idx, &split)?;
-
-        // 2. Load the blocks from the high restore point back to the low point.
-        let blocks = self.load_blocks_to_replay(
-            low_slot,
-            slot,
-            self.get_high_restore_point_block_root(&high_restore_point, slot)?,
-        )?;
+        let blocks =
+            self.load_blocks_to_replay(low_slot, slot, self.get_frozen_block_root(slot, &split)?)?;
 
-        // 3. Replay the blocks on top of the low point.
         // Use a forwards state root iterator to avoid doing any tree hashing.
-        // The state root of the high restore point should never be used, so is safely set to 0.
         let state_root_iter = self.forwards_state_roots_iterator_until(
             low_slot,
             slot,
-            || Ok((high_restore_point, Hash256::zero())),
+            || Ok((self.get_split_state(&split)?, split.state_root)),
             &self.spec,
         )?;
 
         let mut state = self.replay_blocks(low_state, blocks, slot, Some(state_root_iter), None)?;
         state.apply_pending_mutations()?;
 
-        // If state is not error, put it in the cache.
-        self.historic_state_cache.lock().put(slot, state.clone());
-
         Ok(state)
     }
 
-    /// Get the restore point with the given index, or if it is out of bounds, the split state.
-    pub(crate) fn get_restore_point(
-        &self,
-        restore_point_idx: u64,
-        split: &Split,
-    ) -> Result<BeaconState<E>, Error> {
-        if restore_point_idx * self.config.slots_per_restore_point >= split.slot.as_u64() {
-            self.get_state(&split.state_root, Some(split.slot))?
-                .ok_or(HotColdDBError::MissingSplitState(
-                    split.state_root,
-                    split.slot,
-                ))
-                .map_err(Into::into)
-        } else {
-            self.load_restore_point_by_index(restore_point_idx)
-        }
+    /// Get the split state, for backtracking to frozen slots not yet covered by the vectors stored
+    /// in the freezer database.
+    pub(crate) fn get_split_state(&self, split: &Split) -> Result<BeaconState<E>, Error> {
+        self.get_state(&split.state_root, Some(split.slot))?
+            .ok_or(HotColdDBError::MissingSplitState(
+                split.state_root,
+                split.slot,
+            ))
+            .map_err(Into::into)
     }
 
-    /// Get a suitable block root for backtracking from `high_restore_point` to the state at `slot`.
-    ///
-    /// Defaults to the block root for `slot`, which *should* be in range.
-    fn get_high_restore_point_block_root(
+    /// Get the root of the block at `slot` (or the most recent block prior to it), which must be
+    /// prior to the split.
+    pub(crate) fn get_frozen_block_root(
         &self,
-        high_restore_point: &BeaconState<E>,
         slot: Slot,
-    ) -> Result<Hash256, HotColdDBError> {
-        high_restore_point
-            .get_block_root(slot)
-            .or_else(|_| high_restore_point.get_oldest_block_root())
-            .copied()
-            .map_err(HotColdDBError::RestorePointBlockHashError)
+        split: &Split,
+    ) -> Result<Hash256, Error> {
+        let (block_root, _) = self
+            .forwards_block_roots_iterator_until(
+                slot,
+                slot,
+                || Ok((self.get_split_state(split)?, split.block_root)),
+                &self.spec,
+            )?
+            .next()
+            .ok_or(HotColdDBError::MissingFrozenBlockRoot(slot))??;
+        Ok(block_root)
     }
 
     /// Load the blocks between `start_slot` and `end_slot` by backtracking from `end_block_hash`.
@@ -1552,6 +1709,45 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         }
     }
 
+    /// Fetch a single data column for a given block from the store.
+    pub fn get_data_column(
+        &self,
+        block_root: &Hash256,
+        column_index: &ColumnIndex,
+    ) -> Result<Option<Arc<DataColumnSidecar<E>>>, Error> {
+        match self.blobs_db.get_bytes(
+            DBColumn::BeaconDataColumn.into(),
+            &get_data_column_key(block_root, column_index),
+        )? {
+            Some(ref data_column_bytes) => Ok(Some(Arc::new(DataColumnSidecar::from_ssz_bytes(
+                data_column_bytes,
+            )?))),
+            None => Ok(None),
+        }
+    }
+
+    /// Fetch the indices of all data columns stored for a given block.
+    pub fn get_data_column_keys(&self, block_root: Hash256) -> Result<Vec<ColumnIndex>, Error> {
+        self.blobs_db
+            .iter_column_from::<Vec<u8>>(
+                DBColumn::BeaconDataColumn,
+                &get_data_column_key(&block_root, &0),
+            )
+            .take_while(|res| {
+                res.as_ref()
+                    .map_or(true, |(key, _)| key.starts_with(block_root.as_bytes()))
+            })
+            .map(|res| {
+                let (key, _) = res?;
+                let index_bytes = key
+                    .get(Hash256::len_bytes()..)
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .ok_or(Error::InvalidKey)?;
+                Ok(ColumnIndex::from_be_bytes(index_bytes))
+            })
+            .collect()
+    }
+
     /// Get a reference to the `ChainSpec` used by the database.
     pub fn get_chain_spec(&self) -> &ChainSpec {
         &self.spec
@@ -1580,12 +1776,13 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         };
     }
 
-    /// Fetch the slot of the most recently stored restore point (if any).
-    pub fn get_latest_restore_point_slot(&self) -> Option<Slot> {
+    /// Fetch the slot of the most recent state on the finest layer of the diff hierarchy which is
+    /// stored in the freezer (if any).
+    pub fn get_latest_cold_state_slot(&self) -> Option<Slot> {
         let split_slot = self.get_split_slot();
         let anchor = self.get_anchor_info();
 
-        // There are no restore points stored if the state upper limit lies in the hot database,
+        // There are no states stored if the state upper limit lies in the hot database,
         // and the lower limit is zero. It hasn't been reached yet, and may never be.
         if anchor.as_ref().map_or(false, |a| {
             a.state_upper_limit >= split_slot && a.state_lower_limit == 0
@@ -1597,10 +1794,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         {
             Some(lower_limit)
         } else {
-            Some(
-                (split_slot - 1) / self.config.slots_per_restore_point
-                    * self.config.slots_per_restore_point,
-            )
+            let finest_modulus = self.hierarchy.finest_modulus();
+            Some((split_slot - 1) / finest_modulus * finest_modulus)
         }
     }
 
@@ -1636,16 +1831,17 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         retain_historic_states: bool,
     ) -> Result<KeyValueStoreOp, Error> {
         let anchor_slot = block.slot();
-        let slots_per_restore_point = self.config.slots_per_restore_point;
+        let slots_per_epoch = E::slots_per_epoch();
 
         let state_upper_limit = if !retain_historic_states {
             STATE_UPPER_LIMIT_NO_RETAIN
-        } else if anchor_slot % slots_per_restore_point == 0 {
+        } else if anchor_slot % slots_per_epoch == 0 {
             anchor_slot
         } else {
-            // Set the `state_upper_limit` to the slot of the *next* restore point.
-            // See `get_state_upper_limit` for rationale.
-            (anchor_slot / slots_per_restore_point + 1) * slots_per_restore_point
+            // Set the `state_upper_limit` to the slot of the checkpoint state at the start of the
+            // *next* epoch, which is stored as a snapshot. See `get_state_upper_limit` for
+            // rationale.
+            (anchor_slot / slots_per_epoch + 1) * slots_per_epoch
         };
         let anchor_info = if state_upper_limit == 0 && anchor_slot == 0 {
             // Genesis archive node: no anchor because we *will* store all states.
@@ -1808,16 +2004,15 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     /// instance.
     pub fn get_historic_state_limits(&self) -> (Slot, Slot) {
         // If checkpoint sync is used then states in the hot DB will always be available, but may
-        // become unavailable as finalisation advances due to the lack of a restore point in the
-        // database. For this reason we take the minimum of the split slot and the
-        // restore-point-aligned `state_upper_limit`, which should be set _ahead_ of the checkpoint
-        // slot during initialisation.
+        // become unavailable as finalisation advances due to the lack of a snapshot in the
+        // database. For this reason we take the minimum of the split slot and the epoch-aligned
+        // `state_upper_limit`, which should be set _ahead_ of the checkpoint block's slot during
+        // initialisation.
         //
-        // E.g. if we start from a checkpoint at slot 2048+1024=3072 with SPRP=2048, then states
-        // with slots 3072-4095 will be available only while they are in the hot database, and this
-        // function will return the current split slot as the upper limit. Once slot 4096 is reached
-        // a new restore point will be created at that slot, making all states from 4096 onwards
-        // permanently available.
+        // E.g. if we start from a checkpoint block at slot 3070 with 32 slots per epoch, then
+        // states with slots 3070-3071 will be available only while they are in the hot database.
+        // Once the state at slot 3072 is migrated to the freezer it is stored as a snapshot from
+        // which all subsequent states are diffed, making them permanently available.
         let split_slot = self.get_split_slot();
         self.anchor_info
             .read_recursive()
@@ -1875,25 +2070,78 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         self.split.read_recursive().as_kv_store_op(SPLIT_KEY)
     }
 
-    /// Load the state root of a restore point.
-    fn load_restore_point_hash(&self, restore_point_index: u64) -> Result<Hash256, Error> {
-        let key = Self::restore_point_key(restore_point_index);
-        self.cold_db
-            .get(&key)?
-            .map(|r: RestorePointHash| r.state_root)
-            .ok_or_else(|| HotColdDBError::MissingRestorePointHash(restore_point_index).into())
+    /// Load a restore point from the legacy (pre-v22) freezer layout, along with its state root.
+    ///
+    /// Returns `None` if no restore point with `restore_point_index` is stored. This is only
+    /// required by the schema migration which converts the freezer to the hierarchical layout.
+    pub fn load_legacy_restore_point(
+        &self,
+        restore_point_index: u64,
+    ) -> Result<Option<(Hash256, BeaconState<E>)>, Error> {
+        let Some(RestorePointHash { state_root }) = self
+            .cold_db
+            .get(&Self::restore_point_key(restore_point_index))?
+        else {
+            return Ok(None);
+        };
+        let partial_state_bytes = self
+            .cold_db
+            .get_bytes(DBColumn::BeaconState.into(), state_root.as_bytes())?
+            .ok_or(HotColdDBError::MissingRestorePoint(state_root))?;
+        let partial_state = PartialBeaconState::from_ssz_bytes(&partial_state_bytes, &self.spec)?;
+        let state = self.load_cold_state_from_partial(partial_state)?;
+        Ok(Some((state_root, state)))
+    }
+
+    /// Delete all restore points stored in the legacy (pre-v22) freezer layout.
+    pub fn delete_legacy_restore_points(&self) -> Result<(), Error> {
+        let mut ops = vec![];
+        for column in [DBColumn::BeaconState, DBColumn::BeaconRestorePoint] {
+            for key in self.cold_db.iter_column_keys::<Vec<u8>>(column) {
+                let key = key?;
+                ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                    column.as_str(),
+                    &key,
+                )));
+            }
+        }
+        self.cold_db.do_atomically(ops)
     }
 
-    /// Store the state root of a restore point.
-    fn store_restore_point_hash(
+    /// Stage a restore point for storage in the legacy (pre-v22) freezer layout.
+    ///
+    /// This is only required by the schema migration which converts the freezer back to restore
+    /// points when downgrading.
+    pub fn store_legacy_restore_point(
         &self,
         restore_point_index: u64,
         state_root: Hash256,
+        state: &BeaconState<E>,
         ops: &mut Vec<KeyValueStoreOp>,
     ) {
-        let value = &RestorePointHash { state_root };
-        let op = value.as_kv_store_op(Self::restore_point_key(restore_point_index));
-        ops.push(op);
+        ops.push(PartialBeaconState::from_state_forgetful(state).as_kv_store_op(state_root));
+        ops.push(
+            RestorePointHash { state_root }
+                .as_kv_store_op(Self::restore_point_key(restore_point_index)),
+        );
+    }
+
+    /// Delete all snapshots and diffs stored in the hierarchical freezer layout.
+    pub fn delete_hdiff_states(&self) -> Result<(), Error> {
+        let mut ops = vec![];
+        for column in [DBColumn::BeaconStateSnapshot, DBColumn::BeaconStateDiff] {
+            for key in self.cold_db.iter_column_keys::<Vec<u8>>(column) {
+                let key = key?;
+                ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                    column.as_str(),
+                    &key,
+                )));
+            }
+        }
+        self.cold_db.do_atomically(ops)?;
+        self.hdiff_buffer_cache.lock().clear();
+        self.historic_state_cache.lock().clear();
+        Ok(())
     }
 
     /// Convert a `restore_point_index` into a database key.
@@ -1928,36 +2176,39 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         self.hot_db.get(state_root)
     }
 
-    /// Verify that a parsed config is valid.
-    fn verify_config(config: &StoreConfig) -> Result<(), HotColdDBError> {
-        Self::verify_slots_per_restore_point(config.slots_per_restore_point)?;
-        Self::verify_epochs_per_blob_prune(config.epochs_per_blob_prune)
+    /// Verify that a parsed config is valid, returning the moduli of its diff hierarchy.
+    fn verify_config(config: &StoreConfig) -> Result<HierarchyModuli, HotColdDBError> {
+        let hierarchy = Self::verify_hierarchy_config(config)?;
+        Self::verify_epochs_per_blob_prune(config.epochs_per_blob_prune)?;
+        Ok(hierarchy)
     }
 
-    /// Check that the restore point frequency is valid.
+    /// Check that the diff hierarchy is valid.
     ///
-    /// Specifically, check that it is:
+    /// In addition to the checks of `HierarchyConfig::validate`, check that the spacing of the
+    /// finest layer is:
     /// (1) A divisor of the number of slots per historical root, and
     /// (2) Divisible by the number of slots per epoch
     ///
+    /// (1) ensures that we have at least one stored state within range of our state root history
+    /// when iterating backwards.
     ///
-    /// (1) ensures that we have at least one restore point within range of our state
-    /// root history when iterating backwards (and allows for more frequent restore points if
-    /// desired).
-    ///
-    /// (2) ensures that restore points align with hot state summaries, making it
-    /// quick to migrate hot to cold.
-    fn verify_slots_per_restore_point(slots_per_restore_point: u64) -> Result<(), HotColdDBError> {
+    /// (2) ensures that stored states align with hot state summaries, making it quick to migrate
+    /// hot to cold.
+    fn verify_hierarchy_config(config: &StoreConfig) -> Result<HierarchyModuli, HotColdDBError> {
+        let hierarchy = config
+            .hierarchy_config
+            .to_moduli()
+            .map_err(|e| HotColdDBError::InvalidHierarchy(format!("{e:?}")))?;
+        let finest_modulus = hierarchy.finest_modulus();
         let slots_per_historical_root = E::SlotsPerHistoricalRoot::to_u64();
         let slots_per_epoch = E::slots_per_epoch();
-        if slots_per_restore_point > 0
-            && slots_per_historical_root % slots_per_restore_point == 0
-            && slots_per_restore_point % slots_per_epoch == 0
+        if slots_per_historical_root % finest_modulus == 0 && finest_modulus % slots_per_epoch == 0
         {
-            Ok(())
+            Ok(hierarchy)
         } else {
-            Err(HotColdDBError::InvalidSlotsPerRestorePoint {
-                slots_per_restore_point,
+            Err(HotColdDBError::InvalidHierarchyFinestLayer {
+                finest_modulus,
                 slots_per_historical_root,
                 slots_per_epoch,
             })
@@ -2285,22 +2536,41 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                 }
             };
 
-            if Some(block_root) != last_pruned_block_root && self.blobs_exist(&block_root)? {
-                trace!(
-                    self.log,
-                    "Pruning blobs of block";
-                    "slot" => slot,
-                    "block_root" => ?block_root,
-                );
+            if Some(block_root) != last_pruned_block_root {
                 last_pruned_block_root = Some(block_root);
-                ops.push(StoreOp::DeleteBlobs(block_root));
+
+                if self.blobs_exist(&block_root)? {
+                    trace!(
+                        self.log,
+                        "Pruning blobs of block";
+                        "slot" => slot,
+                        "block_root" => ?block_root,
+                    );
+                    ops.push(StoreOp::DeleteBlobs(block_root));
+                }
+
+                // Data columns are retained for the same period as blobs.
+                let data_column_indices = self.get_data_column_keys(block_root)?;
+                if !data_column_indices.is_empty() {
+                    trace!(
+                        self.log,
+                        "Pruning data columns of block";
+                        "slot" => slot,
+                        "block_root" => ?block_root,
+                    );
+                    ops.push(StoreOp::DeleteDataColumns(block_root, data_column_indices));
+                }
             }
 
             if slot >= end_slot {
                 break;
             }
         }
-        let blob_lists_pruned = ops.len();
+        let blob_lists_pruned = ops
+            .iter()
+            .filter(|op| matches!(op, StoreOp::DeleteBlobs(_)))
+            .count();
+        let data_column_lists_pruned = ops.len() - blob_lists_pruned;
         let new_blob_info = BlobInfo {
             oldest_blob_slot: Some(end_slot + 1),
             blobs_db: blob_info.blobs_db,
@@ -2313,17 +2583,18 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             self.log,
             "Blob pruning complete";
             "blob_lists_pruned" => blob_lists_pruned,
+            "data_column_lists_pruned" => data_column_lists_pruned,
         );
 
         Ok(())
     }
 
-    /// This function fills in missing block roots between last restore point slot and split
+    /// This function fills in missing block roots between the last cold state slot and split
     /// slot, if any.
     pub fn heal_freezer_block_roots_at_split(&self) -> Result<(), Error> {
         let split = self.get_split_info();
-        let last_restore_point_slot = (split.slot - 1) / self.config.slots_per_restore_point
-            * self.config.slots_per_restore_point;
+        let finest_modulus = self.hierarchy.finest_modulus();
+        let last_cold_state_slot = (split.slot - 1) / finest_modulus * finest_modulus;
 
         // Load split state (which has access to block roots).
         let (_, split_state) = self
@@ -2334,12 +2605,10 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             ))?;
 
         let mut batch = vec![];
-        let mut chunk_writer = ChunkWriter::<BlockRoots, _, _>::new(
-            &self.cold_db,
-            last_restore_point_slot.as_usize(),
-        )?;
+        let mut chunk_writer =
+            ChunkWriter::<BlockRoots, _, _>::new(&self.cold_db, last_cold_state_slot.as_usize())?;
 
-        for slot in (last_restore_point_slot.as_u64()..split.slot.as_u64()).map(Slot::new) {
+        for slot in (last_cold_state_slot.as_u64()..split.slot.as_u64()).map(Slot::new) {
             let block_root = *split_state.get_block_root(slot)?;
             chunk_writer.set(slot.as_usize(), block_root, &mut batch)?;
         }
@@ -2441,6 +2710,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             DBColumn::BeaconState,
             DBColumn::BeaconStateSummary,
             DBColumn::BeaconRestorePoint,
+            DBColumn::BeaconStateSnapshot,
+            DBColumn::BeaconStateDiff,
             DBColumn::BeaconStateRoots,
             DBColumn::BeaconHistoricalRoots,
             DBColumn::BeaconRandaoMixes,
@@ -2467,6 +2738,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             "num_kv" => cold_ops.len(),
         );
         self.cold_db.do_atomically(std::mem::take(&mut cold_ops))?;
+        self.hdiff_buffer_cache.lock().clear();
+        self.historic_state_cache.lock().clear();
 
         // If we just deleted the the genesis state, re-store it using the *current* schema, which
         // may be different from the schema of the genesis state we just deleted.
@@ -2579,6 +2852,9 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
     let mut block_root_writer =
         ChunkWriter::<BlockRoots, _, _>::new(&store.cold_db, new_frozen_block_root_upper_limit)?;
 
+    // Roots of the states to copy in full to the freezer, in slot-descending order.
+    let mut states_to_store = vec![];
+
     // 1. Copy all of the states between the new finalized state and the split slot, from the hot DB
     // to the cold DB. Delete the execution payloads of these now-finalized blocks.
     let state_root_iter = RootsIterator::new(&store, finalized_state);
@@ -2605,7 +2881,7 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
         // Store the block root for this slot in the linear array of frozen block roots.
         block_root_writer.set(slot.as_usize(), block_root, &mut cold_db_ops)?;
 
-        // Do not try to store states if a restore point is yet to be stored, or will never be
+        // Do not try to store states if a snapshot is yet to be stored, or will never be
         // stored (see `STATE_UPPER_LIMIT_NO_RETAIN`). Make an exception for the genesis state
         // which always needs to be copied from the hot DB to the freezer and should not be deleted.
         if slot != 0
@@ -2618,29 +2894,43 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
             continue;
         }
 
-        // Store a pointer from this state root to its slot, so we can later reconstruct states
-        // from their state root alone.
-        let cold_state_summary = ColdStateSummary { slot };
-        let op = cold_state_summary.as_kv_store_op(state_root);
-        cold_db_ops.push(op);
-
-        if slot % store.config.slots_per_restore_point == 0 {
-            let state: BeaconState<E> = get_full_state(&store.hot_db, &state_root, &store.spec)?
-                .ok_or(HotColdDBError::MissingStateToFreeze(state_root))?;
-
-            store.store_cold_state(&state_root, &state, &mut cold_db_ops)?;
-
-            // Commit the batch of cold DB ops whenever a full state is written. Each state stored
-            // may read the linear fields of previous states stored.
-            store
-                .cold_db
-                .do_atomically(std::mem::take(&mut cold_db_ops))?;
+        match store.cold_storage_strategy(slot)? {
+            // Store a pointer from this state root to its slot, so we can later reconstruct states
+            // from their state root alone.
+            StorageStrategy::ReplayFrom(_) => {
+                let cold_state_summary = ColdStateSummary { slot };
+                let op = cold_state_summary.as_kv_store_op(state_root);
+                cold_db_ops.push(op);
+            }
+            StorageStrategy::Snapshot | StorageStrategy::DiffFrom(_) => {
+                states_to_store.push(state_root);
+            }
         }
     }
 
     // Finish writing the block roots and commit the remaining cold DB ops.
     block_root_writer.write(&mut cold_db_ops)?;
-    store.cold_db.do_atomically(cold_db_ops)?;
+    store
+        .cold_db
+        .do_atomically(std::mem::take(&mut cold_db_ops))?;
+
+    // 2. Store the states which lie on the diff hierarchy in slot-ascending order, as each diff is
+    // computed against a previously stored state.
+    for state_root in states_to_store.into_iter().rev() {
+        let state: BeaconState<E> = get_full_state(&store.hot_db, &state_root, &store.spec)?
+            .ok_or(HotColdDBError::MissingStateToFreeze(state_root))?;
+
+        let hdiff_buffer = store.store_cold_state(&state_root, &state, &mut cold_db_ops)?;
+
+        // Commit the batch of cold DB ops whenever a full state is written. Each state stored
+        // may read the linear fields of previous states stored.
+        store
+            .cold_db
+            .do_atomically(std::mem::take(&mut cold_db_ops))?;
+        if let Some(buffer) = hdiff_buffer {
+            store.cache_hdiff_buffer(state.slot(), buffer);
+        }
+    }
 
     // Warning: Critical section.  We have to take care not to put any of the two databases in an
     //          inconsistent state if the OS process dies at any point during the freezing
@@ -2811,7 +3101,7 @@ impl StoreItem for ColdStateSummary {
     }
 }
 
-/// Struct for storing the state root of a restore point in the database.
+/// Struct for storing the state root of a restore point in the legacy (pre-v22) freezer layout.
 #[derive(Debug, Clone, Copy, Default, Encode, Decode)]
 struct RestorePointHash {
     state_root: Hash256,
@@ -2847,3 +3137,8 @@ impl StoreItem for TemporaryFlag {
         Ok(TemporaryFlag)
     }
 }
+
+/// Database key of a state snapshot or diff in the freezer, keyed by slot.
+fn slot_key(slot: Slot) -> [u8; 8] {
+    slot.as_u64().to_be_bytes()
+}
@@ -354,8 +354,8 @@ fn next_historical_root_backtrack_state<E: EthSpec, Hot: ItemStore<E>, Cold: Ite
     store: &HotColdDB<E, Hot, Cold>,
     current_state: &BeaconState<E>,
 ) -> Result<BeaconState<E>, Error> {
-    // For compatibility with the freezer database's restore points, we load a state at
-    // a restore point slot (thus avoiding replaying blocks). In the case where we're
+    // For compatibility with the freezer database's diff hierarchy, we load a state at a slot
+    // on its finest layer (thus avoiding replaying blocks). In the case where we're
     // not frozen, this just means we might not jump back by the maximum amount on
     // our first jump (i.e. at most 1 extra state load).
     let new_state_slot = slot_of_prev_restore_point::<E>(current_state.slot());
@@ -372,7 +372,8 @@ fn next_historical_root_backtrack_state<E: EthSpec, Hot: ItemStore<E>, Cold: Ite
     }
 }
 
-/// Compute the slot of the last guaranteed restore point in the freezer database.
+/// Compute the slot of the last state guaranteed to lie on the finest layer of the freezer
+/// database's diff hierarchy.
 fn slot_of_prev_restore_point<E: EthSpec>(current_slot: Slot) -> Slot {
     let slots_per_historical_root = E::SlotsPerHistoricalRoot::to_u64();
     (current_slot - 1) / slots_per_historical_root * slots_per_historical_root
@@ -71,6 +71,15 @@ impl<E: EthSpec> LevelDB<E> {
     pub fn keys_iter(&self) -> KeyIterator<BytesKey> {
         self.db.keys_iter(self.read_options())
     }
+
+    /// Iterate through every key and value in the database, across all columns.
+    pub fn iter_all(&self) -> RawEntryIter {
+        Box::new(
+            self.db
+                .iter(self.read_options())
+                .map(|(bytes_key, value)| Ok((bytes_key.key, value))),
+        )
+    }
 }
 
 impl<E: EthSpec> KeyValueStore<E> for LevelDB<E> {
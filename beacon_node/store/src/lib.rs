@@ -2,11 +2,12 @@
 //!
 //! Provides the following stores:
 //!
-//! - `HotColdDB`: an on-disk store backed by leveldb. Used in production.
+//! - `HotColdDB`: an on-disk store backed by leveldb or redb. Used in production.
 //! - `MemoryStore`: an in-memory store backed by a hash-map. Used for testing.
 //!
 //! Provides a simple API for storing/retrieving all types that sometimes needs type-hints. See
 //! tests for implementation examples.
+mod beacon_node_backend;
 mod chunk_writer;
 pub mod chunked_iter;
 pub mod chunked_vector;
@@ -15,6 +16,7 @@ pub mod consensus_context;
 pub mod errors;
 mod forwards_iter;
 mod garbage_collection;
+pub mod hdiff;
 pub mod hot_cold_store;
 mod impls;
 mod leveldb_store;
@@ -23,17 +25,21 @@ pub mod metadata;
 pub mod metrics;
 mod partial_beacon_state;
 pub mod reconstruct;
+mod redb_store;
 pub mod state_cache;
 
 pub mod iter;
 
+pub use self::beacon_node_backend::BeaconNodeBackend;
 pub use self::chunk_writer::ChunkWriter;
-pub use self::config::StoreConfig;
+pub use self::config::{DatabaseBackend, StoreConfig};
 pub use self::consensus_context::OnDiskConsensusContext;
 pub use self::hot_cold_store::{HotColdDB, HotStateSummary, Split};
 pub use self::leveldb_store::LevelDB;
 pub use self::memory_store::MemoryStore;
 pub use self::partial_beacon_state::PartialBeaconState;
+#[cfg(feature = "redb")]
+pub use self::redb_store::Redb;
 pub use crate::metadata::BlobInfo;
 pub use errors::Error;
 pub use impls::beacon_state::StorageContainer as BeaconStateStorageContainer;
@@ -44,6 +50,8 @@ use std::sync::Arc;
 use strum::{EnumString, IntoStaticStr};
 pub use types::*;
 
+const DATA_COLUMN_DB_KEY_SIZE: usize = 32 + 8;
+
 pub type ColumnIter<'a, K> = Box<dyn Iterator<Item = Result<(K, Vec<u8>), Error>> + 'a>;
 pub type ColumnKeyIter<'a, K> = Box<dyn Iterator<Item = Result<K, Error>> + 'a>;
 
@@ -143,6 +151,13 @@ pub fn get_key_for_col(column: &str, key: &[u8]) -> Vec<u8> {
     result
 }
 
+/// Key for a data column sidecar: the block root followed by the big-endian column index.
+pub fn get_data_column_key(block_root: &Hash256, column_index: &ColumnIndex) -> Vec<u8> {
+    let mut result = block_root.as_bytes().to_vec();
+    result.extend_from_slice(&column_index.to_be_bytes());
+    result
+}
+
 pub fn get_col_from_key(key: &[u8]) -> Option<String> {
     if key.len() < 3 {
         return None;
@@ -210,11 +225,13 @@ pub enum StoreOp<'a, E: EthSpec> {
     PutBlock(Hash256, Arc<SignedBeaconBlock<E>>),
     PutState(Hash256, &'a BeaconState<E>),
     PutBlobs(Hash256, BlobSidecarList<E>),
+    PutDataColumns(Hash256, DataColumnSidecarList<E>),
     PutStateSummary(Hash256, HotStateSummary),
     PutStateTemporaryFlag(Hash256),
     DeleteStateTemporaryFlag(Hash256),
     DeleteBlock(Hash256),
     DeleteBlobs(Hash256),
+    DeleteDataColumns(Hash256, Vec<ColumnIndex>),
     DeleteState(Hash256, Option<Slot>),
     DeleteExecutionPayload(Hash256),
     KeyValueOp(KeyValueStoreOp),
@@ -230,6 +247,9 @@ pub enum DBColumn {
     BeaconBlock,
     #[strum(serialize = "blb")]
     BeaconBlob,
+    /// For data column sidecars, keyed by block root and column index.
+    #[strum(serialize = "bdc")]
+    BeaconDataColumn,
     /// For full `BeaconState`s in the hot database (finalized or fork-boundary states).
     #[strum(serialize = "ste")]
     BeaconState,
@@ -255,8 +275,16 @@ pub enum DBColumn {
     #[strum(serialize = "pkc")]
     PubkeyCache,
     /// For the table mapping restore point numbers to state roots.
+    ///
+    /// DEPRECATED: only read when migrating from the restore point layout of the freezer database.
     #[strum(serialize = "brp")]
     BeaconRestorePoint,
+    /// For compressed full states in the freezer database, keyed by slot.
+    #[strum(serialize = "bsn")]
+    BeaconStateSnapshot,
+    /// For compressed diffs between states in the freezer database, keyed by slot.
+    #[strum(serialize = "bsd")]
+    BeaconStateDiff,
     #[strum(serialize = "bbr")]
     BeaconBlockRoots,
     #[strum(serialize = "bsr")]
@@ -274,6 +302,9 @@ pub enum DBColumn {
     BeaconHistoricalSummaries,
     #[strum(serialize = "olc")]
     OverflowLRUCache,
+    /// For the best `LightClientUpdate` of each sync committee period, keyed by period.
+    #[strum(serialize = "lcu")]
+    LightClientUpdate,
 }
 
 /// A block from the database, which might have an execution payload or not.
@@ -316,7 +347,11 @@ impl DBColumn {
             | Self::BeaconStateRoots
             | Self::BeaconHistoricalRoots
             | Self::BeaconHistoricalSummaries
-            | Self::BeaconRandaoMixes => 8,
+            | Self::BeaconRandaoMixes
+            | Self::BeaconStateSnapshot
+            | Self::BeaconStateDiff
+            | Self::LightClientUpdate => 8,
+            Self::BeaconDataColumn => DATA_COLUMN_DB_KEY_SIZE,
         }
     }
 }
@@ -396,6 +431,97 @@ mod tests {
         test_impl(store);
     }
 
+    #[cfg(feature = "redb")]
+    #[test]
+    fn redb() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        let store = Redb::open(path).unwrap();
+
+        test_impl(store);
+    }
+
+    #[cfg(feature = "redb")]
+    #[test]
+    fn redb_iter_column() {
+        let dir = tempdir().unwrap();
+        let store = Redb::<MinimalEthSpec>::open(dir.path()).unwrap();
+
+        let keys = (0..4u64).map(|i| i.to_be_bytes()).collect::<Vec<_>>();
+        let ops = keys
+            .iter()
+            .map(|key| {
+                KeyValueStoreOp::PutKeyValue(
+                    get_key_for_col(DBColumn::BeaconStateSnapshot.into(), key),
+                    key.to_vec(),
+                )
+            })
+            // Neighbouring columns should not be included in iteration.
+            .chain([
+                KeyValueStoreOp::PutKeyValue(
+                    get_key_for_col(DBColumn::BeaconStateDiff.into(), &[0; 8]),
+                    vec![],
+                ),
+                KeyValueStoreOp::PutKeyValue(
+                    get_key_for_col(DBColumn::BeaconStateSummary.into(), &[0; 32]),
+                    vec![],
+                ),
+            ])
+            .collect();
+        store.do_atomically(ops).unwrap();
+
+        let entries = store
+            .iter_column::<Vec<u8>>(DBColumn::BeaconStateSnapshot)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            entries,
+            keys.iter()
+                .map(|key| (key.to_vec(), key.to_vec()))
+                .collect::<Vec<_>>()
+        );
+
+        let from_keys = store
+            .iter_column_from::<Vec<u8>>(DBColumn::BeaconStateSnapshot, &keys[2])
+            .map(|res| res.unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(from_keys, vec![keys[2].to_vec(), keys[3].to_vec()]);
+
+        let column_keys = store
+            .iter_column_keys::<Vec<u8>>(DBColumn::BeaconStateSnapshot)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(column_keys.len(), keys.len());
+
+        store.compact().unwrap();
+        assert_eq!(
+            store
+                .iter_column_keys::<Vec<u8>>(DBColumn::BeaconStateSnapshot)
+                .count(),
+            keys.len()
+        );
+
+        // Compaction is skipped rather than failing while an iterator is open.
+        let mut iter = store.iter_column_keys::<Vec<u8>>(DBColumn::BeaconStateSnapshot);
+        store.compact().unwrap();
+        assert!(iter.next().is_some());
+    }
+
+    #[cfg(feature = "redb")]
+    #[test]
+    fn backend_mismatch() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        drop(BeaconNodeBackend::<MinimalEthSpec>::open(DatabaseBackend::LevelDb, path).unwrap());
+
+        assert_eq!(
+            DatabaseBackend::detect(path),
+            Some(DatabaseBackend::LevelDb)
+        );
+        assert!(BeaconNodeBackend::<MinimalEthSpec>::open(DatabaseBackend::Redb, path).is_err());
+        assert!(BeaconNodeBackend::<MinimalEthSpec>::open(DatabaseBackend::LevelDb, path).is_ok());
+    }
+
     #[test]
     fn memorydb() {
         let store = MemoryStore::open();
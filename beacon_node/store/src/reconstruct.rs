@@ -1,4 +1,5 @@
 //! Implementation of historic state reconstruction (given complete block history).
+use crate::hdiff::StorageStrategy;
 use crate::hot_cold_store::{HotColdDB, HotColdDBError};
 use crate::{Error, ItemStore};
 use itertools::{process_results, Itertools};
@@ -7,8 +8,9 @@ use state_processing::{
     per_block_processing, per_slot_processing, BlockSignatureStrategy, ConsensusContext,
     VerifyBlockRoot,
 };
+use std::cmp::min;
 use std::sync::Arc;
-use types::{EthSpec, Hash256};
+use types::{BeaconState, EthSpec, Slot};
 
 impl<E, Hot, Cold> HotColdDB<E, Hot, Cold>
 where
@@ -35,32 +37,79 @@ where
             "start_slot" => anchor.state_lower_limit,
         );
 
-        let slots_per_restore_point = self.config.slots_per_restore_point;
-
-        // Iterate blocks from the state lower limit to the upper limit.
+        // Replay blocks from the state lower limit to the upper limit.
         let lower_limit_slot = anchor.state_lower_limit;
         let split = self.get_split_info();
-        let upper_limit_state = self.get_restore_point(
-            anchor.state_upper_limit.as_u64() / slots_per_restore_point,
-            &split,
-        )?;
-        let upper_limit_slot = upper_limit_state.slot();
+        let upper_limit_slot = min(anchor.state_upper_limit, split.slot);
+
+        // The state to be advanced.
+        let state = self
+            .load_cold_state_by_slot(lower_limit_slot)?
+            .ok_or(HotColdDBError::MissingLowerLimitState(lower_limit_slot))?;
+
+        self.store_cold_states_by_replay(state, upper_limit_slot, |slot| {
+            // Update anchor.
+            let old_anchor = Some(anchor.clone());
+
+            if slot + 1 == upper_limit_slot {
+                // The two limits have met in the middle! We're done!
+                self.compare_and_set_anchor_info_with_write(old_anchor, None)?;
+            } else {
+                // The lower limit has been raised, store it.
+                anchor.state_lower_limit = slot;
+
+                self.compare_and_set_anchor_info_with_write(old_anchor, Some(anchor.clone()))?;
+            }
+
+            if slot % E::slots_per_historical_root() as u64 == 0 {
+                info!(
+                    self.log,
+                    "State reconstruction in progress";
+                    "slot" => slot,
+                    "remaining" => upper_limit_slot - 1 - slot
+                );
+            }
+            Ok(())
+        })?;
+
+        // Check that the split point wasn't mutated during the state reconstruction process.
+        // It shouldn't have been, due to the serialization of requests through the store migrator,
+        // so this is just a paranoid check.
+        let latest_split = self.get_split_info();
+        if split != latest_split {
+            return Err(Error::SplitPointModified(latest_split.slot, split.slot));
+        }
+
+        Ok(())
+    }
 
-        // Use a dummy root, as we never read the block for the upper limit state.
-        let upper_limit_block_root = Hash256::repeat_byte(0xff);
+    /// Replay blocks on top of `state` up to `upper_limit_slot` (exclusive), storing every
+    /// intermediate state in the freezer database.
+    ///
+    /// The cold DB ops are committed at each state stored as a snapshot or diff, and at the final
+    /// slot, after which `on_commit` is called with the slot of the latest committed state.
+    /// Interrupted work can be resumed from that state.
+    pub fn store_cold_states_by_replay(
+        self: &Arc<Self>,
+        mut state: BeaconState<E>,
+        upper_limit_slot: Slot,
+        mut on_commit: impl FnMut(Slot) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let lower_limit_slot = state.slot();
+        if lower_limit_slot + 1 >= upper_limit_slot {
+            return Ok(());
+        }
 
-        let block_root_iter = self.forwards_block_roots_iterator(
+        // All block roots prior to the split are stored in the freezer, so the split state is
+        // never loaded.
+        let split = self.get_split_info();
+        let block_root_iter = self.forwards_block_roots_iterator_until(
             lower_limit_slot,
-            upper_limit_state,
-            upper_limit_block_root,
+            upper_limit_slot - 1,
+            || Ok((self.get_split_state(&split)?, split.block_root)),
             &self.spec,
         )?;
 
-        // The state to be advanced.
-        let mut state = self
-            .load_cold_state_by_slot(lower_limit_slot)?
-            .ok_or(HotColdDBError::MissingLowerLimitState(lower_limit_slot))?;
-
         state.build_caches(&self.spec)?;
 
         process_results(block_root_iter, |iter| -> Result<(), Error> {
@@ -108,24 +157,24 @@ where
                     .or_else(|_| state.update_tree_hash_cache())?;
 
                 // Stage state for storage in freezer DB.
-                self.store_cold_state(&state_root, &state, &mut io_batch)?;
+                let hdiff_buffer = self.store_cold_state(&state_root, &state, &mut io_batch)?;
 
-                // If the slot lies on an epoch boundary, commit the batch and update the anchor.
-                if slot % slots_per_restore_point == 0 || slot + 1 == upper_limit_slot {
-                    info!(
-                        self.log,
-                        "State reconstruction in progress";
-                        "slot" => slot,
-                        "remaining" => upper_limit_slot - 1 - slot
-                    );
+                let is_last_slot = slot + 1 == upper_limit_slot;
 
+                // If the state was stored as a snapshot or diff, commit the batch, as subsequent
+                // diffs may be computed against it.
+                if is_last_slot
+                    || !matches!(
+                        self.cold_storage_strategy(slot)?,
+                        StorageStrategy::ReplayFrom(_)
+                    )
+                {
                     self.cold_db.do_atomically(std::mem::take(&mut io_batch))?;
+                    if let Some(buffer) = hdiff_buffer {
+                        self.cache_hdiff_buffer(slot, buffer);
+                    }
 
-                    // Update anchor.
-                    let old_anchor = Some(anchor.clone());
-
-                    if slot + 1 == upper_limit_slot {
-                        // The two limits have met in the middle! We're done!
+                    if is_last_slot {
                         // Perform one last integrity check on the state reached.
                         let computed_state_root = state.update_tree_hash_cache()?;
                         if computed_state_root != state_root {
@@ -135,34 +184,18 @@ where
                                 computed: computed_state_root,
                             });
                         }
+                    }
 
-                        self.compare_and_set_anchor_info_with_write(old_anchor, None)?;
+                    on_commit(slot)?;
 
+                    if is_last_slot {
                         return Ok(());
-                    } else {
-                        // The lower limit has been raised, store it.
-                        anchor.state_lower_limit = slot;
-
-                        self.compare_and_set_anchor_info_with_write(
-                            old_anchor,
-                            Some(anchor.clone()),
-                        )?;
                     }
                 }
             }
 
             // Should always reach the `upper_limit_slot` and return early above.
             Err(Error::StateReconstructionDidNotComplete)
-        })??;
-
-        // Check that the split point wasn't mutated during the state reconstruction process.
-        // It shouldn't have been, due to the serialization of requests through the store migrator,
-        // so this is just a paranoid check.
-        let latest_split = self.get_split_info();
-        if split != latest_split {
-            return Err(Error::SplitPointModified(latest_split.slot, split.slot));
-        }
-
-        Ok(())
+        })?
     }
 }
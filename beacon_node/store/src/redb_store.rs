@@ -0,0 +1,344 @@
+#![cfg(feature = "redb")]
+use super::*;
+use crate::hot_cold_store::HotColdDBError;
+use crate::leveldb_store::BytesKey;
+use parking_lot::{Mutex, RwLock};
+use redb::{Durability, TableDefinition};
+use std::marker::PhantomData;
+use std::path::Path;
+
+/// Name of the redb database file within a database directory.
+pub const REDB_DATA_FILENAME: &str = "database.redb";
+
+/// All columns are stored in a single table, keyed by the column-prefixed key, which keeps the
+/// key layout (and ordering) identical to LevelDB.
+const TABLE: TableDefinition<'static, &[u8], &[u8]> = TableDefinition::new("lighthouse");
+
+/// A wrapped redb database.
+pub struct Redb<E: EthSpec> {
+    /// The database is only locked for writing during compaction, which requires exclusive access.
+    db: RwLock<redb::Database>,
+    /// A mutex to synchronise sensitive read-write transactions.
+    transaction_mutex: Mutex<()>,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> Redb<E> {
+    /// Open a database in the directory at `path`, creating a new database if one does not
+    /// already exist.
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        std::fs::create_dir_all(path).map_err(|e| Error::DBError {
+            message: format!("Unable to create database directory {path:?}: {e}"),
+        })?;
+        let db = redb::Database::create(path.join(REDB_DATA_FILENAME))?;
+
+        // Create the table up-front so that read transactions never observe it missing.
+        let tx = db.begin_write()?;
+        tx.open_table(TABLE)?;
+        tx.commit()?;
+
+        Ok(Self {
+            db: RwLock::new(db),
+            transaction_mutex: Mutex::new(()),
+            _phantom: PhantomData,
+        })
+    }
+
+    fn put_bytes_with_durability(
+        &self,
+        col: &str,
+        key: &[u8],
+        val: &[u8],
+        durability: Durability,
+    ) -> Result<(), Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter_vec(&metrics::DISK_DB_WRITE_COUNT, &[col]);
+        metrics::inc_counter_vec_by(&metrics::DISK_DB_WRITE_BYTES, &[col], val.len() as u64);
+        let _timer = metrics::start_timer(&metrics::DISK_DB_WRITE_TIMES);
+
+        let mut tx = self.db.read().begin_write()?;
+        tx.set_durability(durability);
+        {
+            let mut table = tx.open_table(TABLE)?;
+            table.insert(column_key.as_slice(), val)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Return a range over all entries with keys greater than or equal to `from`.
+    ///
+    /// The range holds its own read transaction, so it observes a consistent snapshot of the
+    /// database regardless of concurrent writes.
+    fn range_from(
+        &self,
+        from: &[u8],
+    ) -> Result<redb::Range<'static, &'static [u8], &'static [u8]>, Error> {
+        let tx = self.db.read().begin_read()?;
+        let table = tx.open_table(TABLE)?;
+        Ok(table.range(from..)?)
+    }
+
+    /// Iterate through every key and value in the database, across all columns.
+    pub fn iter_all(&self) -> RawEntryIter {
+        Box::new(self.iter_from(&[], true))
+    }
+
+    /// Iterate through the entries with keys greater than or equal to `from`, optionally
+    /// skipping the (potentially large) values.
+    fn iter_from(
+        &self,
+        from: &[u8],
+        with_values: bool,
+    ) -> impl Iterator<Item = Result<(Vec<u8>, Vec<u8>), Error>> {
+        let (range, error) = match self.range_from(from) {
+            Ok(range) => (Some(range), None),
+            Err(e) => (None, Some(Err(e))),
+        };
+        error
+            .into_iter()
+            .chain(range.into_iter().flatten().map(move |res| {
+                let (key, value) = res?;
+                let value = if with_values {
+                    value.value().to_vec()
+                } else {
+                    vec![]
+                };
+                Ok((key.value().to_vec(), value))
+            }))
+    }
+
+    /// Compact the whole database, which is the only granularity of compaction redb supports.
+    ///
+    /// Compaction requires exclusive access, so all reads and writes wait for it to complete. It
+    /// is also impossible while any read transaction (e.g. an iterator) is open, in which case it
+    /// is skipped. Freed pages are reused regardless, so compaction only shrinks the file.
+    fn compact_all(&self) -> Result<(), Error> {
+        match self.db.write().compact() {
+            Ok(_) | Err(redb::CompactionError::TransactionInProgress) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl<E: EthSpec> KeyValueStore<E> for Redb<E> {
+    /// Store some `value` in `column`, indexed with `key`.
+    fn put_bytes(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        self.put_bytes_with_durability(col, key, val, Durability::Eventual)
+    }
+
+    fn put_bytes_sync(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        self.put_bytes_with_durability(col, key, val, Durability::Immediate)
+    }
+
+    /// Committing an empty transaction with immediate durability persists all prior commits.
+    fn sync(&self) -> Result<(), Error> {
+        let mut tx = self.db.read().begin_write()?;
+        tx.set_durability(Durability::Immediate);
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Retrieve some bytes in `column` with `key`.
+    fn get_bytes(&self, col: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter_vec(&metrics::DISK_DB_READ_COUNT, &[col]);
+        let timer = metrics::start_timer(&metrics::DISK_DB_READ_TIMES);
+
+        let tx = self.db.read().begin_read()?;
+        let table = tx.open_table(TABLE)?;
+        let bytes = table
+            .get(column_key.as_slice())?
+            .map(|value| value.value().to_vec());
+
+        if let Some(bytes) = &bytes {
+            metrics::inc_counter_vec_by(&metrics::DISK_DB_READ_BYTES, &[col], bytes.len() as u64);
+            metrics::stop_timer(timer);
+        }
+        Ok(bytes)
+    }
+
+    /// Return `true` if `key` exists in `column`.
+    fn key_exists(&self, col: &str, key: &[u8]) -> Result<bool, Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter_vec(&metrics::DISK_DB_EXISTS_COUNT, &[col]);
+
+        let tx = self.db.read().begin_read()?;
+        let table = tx.open_table(TABLE)?;
+        let exists = table.get(column_key.as_slice())?.is_some();
+        Ok(exists)
+    }
+
+    /// Removes `key` from `column`.
+    fn key_delete(&self, col: &str, key: &[u8]) -> Result<(), Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter_vec(&metrics::DISK_DB_DELETE_COUNT, &[col]);
+
+        let mut tx = self.db.read().begin_write()?;
+        tx.set_durability(Durability::Eventual);
+        {
+            let mut table = tx.open_table(TABLE)?;
+            table.remove(column_key.as_slice())?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// All of the operations are applied in a single write transaction, which is either
+    /// committed in full or not at all.
+    fn do_atomically(&self, ops_batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        let _timer = metrics::start_timer(&metrics::DISK_DB_WRITE_TIMES);
+
+        let mut tx = self.db.read().begin_write()?;
+        tx.set_durability(Durability::Eventual);
+        {
+            let mut table = tx.open_table(TABLE)?;
+            for op in ops_batch {
+                match op {
+                    KeyValueStoreOp::PutKeyValue(key, value) => {
+                        let col = get_col_from_key(&key).unwrap_or("unknown".to_owned());
+                        metrics::inc_counter_vec(&metrics::DISK_DB_WRITE_COUNT, &[&col]);
+                        metrics::inc_counter_vec_by(
+                            &metrics::DISK_DB_WRITE_BYTES,
+                            &[&col],
+                            value.len() as u64,
+                        );
+
+                        table.insert(key.as_slice(), value.as_slice())?;
+                    }
+
+                    KeyValueStoreOp::DeleteKey(key) => {
+                        let col = get_col_from_key(&key).unwrap_or("unknown".to_owned());
+                        metrics::inc_counter_vec(&metrics::DISK_DB_DELETE_COUNT, &[&col]);
+
+                        table.remove(key.as_slice())?;
+                    }
+                }
+            }
+        }
+        // Dropping the transaction without committing aborts it, so an error above leaves the
+        // database untouched.
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn begin_rw_transaction(&self) -> MutexGuard<()> {
+        self.transaction_mutex.lock()
+    }
+
+    fn compact_column(&self, _column: DBColumn) -> Result<(), Error> {
+        self.compact_all()
+    }
+
+    /// Compact the database once, rather than once per column.
+    fn compact(&self) -> Result<(), Error> {
+        self.compact_all()
+    }
+
+    fn iter_column_from<K: Key>(&self, column: DBColumn, from: &[u8]) -> ColumnIter<K> {
+        let start_key = get_key_for_col(column.into(), from);
+        Box::new(
+            self.iter_from(&start_key, true)
+                .take_while(move |res| {
+                    res.as_ref()
+                        .map_or(true, |(key, _)| key.starts_with(column.as_bytes()))
+                })
+                .map(move |res| {
+                    let (key, value) = res?;
+                    Ok((
+                        K::from_bytes(remove_column(column, key)?.as_slice())?,
+                        value,
+                    ))
+                }),
+        )
+    }
+
+    fn iter_raw_entries(&self, column: DBColumn, prefix: &[u8]) -> RawEntryIter {
+        let start_key = get_key_for_col(column.into(), prefix);
+        Box::new(
+            self.iter_from(&start_key, true)
+                .take_while(move |res| {
+                    res.as_ref()
+                        .map_or(true, |(key, _)| key.starts_with(&start_key))
+                })
+                .map(move |res| {
+                    let (key, value) = res?;
+                    Ok((key[column.as_bytes().len()..].to_vec(), value))
+                }),
+        )
+    }
+
+    fn iter_raw_keys(&self, column: DBColumn, prefix: &[u8]) -> RawKeyIter {
+        let start_key = get_key_for_col(column.into(), prefix);
+        Box::new(
+            self.iter_from(&start_key, false)
+                .take_while(move |res| {
+                    res.as_ref()
+                        .map_or(true, |(key, _)| key.starts_with(&start_key))
+                })
+                .map(move |res| {
+                    let (key, _) = res?;
+                    Ok(key[column.as_bytes().len()..].to_vec())
+                }),
+        )
+    }
+
+    /// Iterate through all keys in a particular column.
+    fn iter_column_keys<K: Key>(&self, column: DBColumn) -> ColumnKeyIter<K> {
+        let start_key = get_key_for_col(column.into(), &vec![0; column.key_size()]);
+        Box::new(
+            self.iter_from(&start_key, false)
+                .take_while(move |res| {
+                    res.as_ref()
+                        .map_or(true, |(key, _)| key.starts_with(column.as_bytes()))
+                })
+                .map(move |res| {
+                    let (key, _) = res?;
+                    K::from_bytes(remove_column(column, key)?.as_slice())
+                }),
+        )
+    }
+}
+
+/// Remove the column from `key`, checking that the remainder has the column's key size.
+fn remove_column(column: DBColumn, key: Vec<u8>) -> Result<Vec<u8>, Error> {
+    let bytes_key = BytesKey::from_vec(key);
+    bytes_key
+        .remove_column_variable(column)
+        .map(|subkey| subkey.to_vec())
+        .ok_or_else(|| {
+            HotColdDBError::IterationError {
+                unexpected_key: bytes_key.clone(),
+            }
+            .into()
+        })
+}
+
+impl<E: EthSpec> ItemStore<E> for Redb<E> {}
+
+macro_rules! impl_from_redb_error {
+    ($($error:ty),*) => {
+        $(
+            impl From<$error> for Error {
+                fn from(e: $error) -> Error {
+                    Error::DBError {
+                        message: format!("{:?}", e),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_redb_error!(
+    redb::DatabaseError,
+    redb::TransactionError,
+    redb::TableError,
+    redb::StorageError,
+    redb::CommitError,
+    redb::CompactionError
+);
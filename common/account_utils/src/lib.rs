@@ -18,6 +18,7 @@ use std::thread::sleep;
 use std::time::Duration;
 use zeroize::Zeroize;
 
+pub mod secret_provider;
 pub mod validator_definitions;
 
 pub use eth2_keystore;
@@ -59,6 +60,17 @@ pub fn default_keystore_password_path<P: AsRef<Path>>(
         .join(format!("0x{}", keystore.pubkey()))
 }
 
+/// Returns the "default" path where a keystore may store a `SecretProvider` for its password, for
+/// use when its password is not held in a file in `secrets_dir`.
+pub fn default_keystore_password_provider_path<P: AsRef<Path>>(
+    keystore: &Keystore,
+    secrets_dir: P,
+) -> PathBuf {
+    secrets_dir
+        .as_ref()
+        .join(format!("0x{}.provider.yaml", keystore.pubkey()))
+}
+
 /// Reads a password file into a Zeroize-ing `PlainText` struct, with new-lines removed.
 pub fn read_password<P: AsRef<Path>>(path: P) -> Result<PlainText, io::Error> {
     fs::read(path).map(strip_off_newlines).map(Into::into)
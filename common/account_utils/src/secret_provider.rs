@@ -0,0 +1,432 @@
+//! Provides keystore passwords from sources other than the validator host's filesystem.
+
+use crate::{strip_off_newlines, ZeroizeString};
+use reqwest::Url;
+use serde::de::{DeserializeSeed, IgnoredAny, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::time::Duration;
+use zeroize::{Zeroize, Zeroizing};
+
+/// The environment variable holding the Vault token, if `token_path` is not specified.
+pub const VAULT_TOKEN_ENV_VAR: &str = "VAULT_TOKEN";
+/// The header used to authenticate with Vault.
+const VAULT_TOKEN_HEADER: &str = "X-Vault-Token";
+/// The timeout for a request to Vault.
+const VAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A source from which a secret, such as a keystore password, can be read.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "provider", rename_all = "snake_case")]
+pub enum SecretProvider {
+    /// Read the secret from an environment variable.
+    Env { variable: String },
+    /// Run a command and read the secret from its standard output.
+    ///
+    /// Trailing new-lines are removed from the output.
+    Command {
+        command: PathBuf,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
+    },
+    /// Read the secret from a HashiCorp Vault compatible key-value store.
+    ///
+    /// Both version 1 and version 2 of the key-value secrets engine are supported.
+    Vault {
+        /// The full URL of the secret, e.g. `https://vault:8200/v1/secret/data/validator`.
+        ///
+        /// The URL must use HTTPS, unless it refers to the local host.
+        url: String,
+        /// The field of the secret which holds the password.
+        field: String,
+        /// A file holding the Vault token, such as one written by Vault Agent.
+        ///
+        /// The token is read from `VAULT_TOKEN` if this is omitted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token_path: Option<PathBuf>,
+    },
+}
+
+impl SecretProvider {
+    /// Read the secret from the provider.
+    ///
+    /// ## Notes
+    ///
+    /// This function blocks, so it must not be called from an async context.
+    pub fn read_secret(&self) -> Result<ZeroizeString, String> {
+        match self {
+            SecretProvider::Env { variable } => std::env::var(variable)
+                .map(Into::into)
+                .map_err(|e| format!("Unable to read environment variable {}: {}", variable, e)),
+            SecretProvider::Command { command, args } => {
+                let output = Command::new(command)
+                    .args(args)
+                    .stdin(Stdio::null())
+                    .output()
+                    .map_err(|e| format!("Unable to run {:?}: {}", command, e))?;
+                command_secret(command, output.status, output.stdout)
+            }
+            SecretProvider::Vault {
+                url,
+                field,
+                token_path,
+            } => read_vault_secret(url, field, token_path.as_ref()),
+        }
+    }
+
+    /// Read the secret from the provider, giving up after `timeout`.
+    ///
+    /// A command which has not exited by the `timeout` is killed. The other providers block, so
+    /// they are run on the blocking thread pool and abandoned after the `timeout`.
+    pub async fn read_secret_with_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Result<ZeroizeString, String> {
+        let read = async {
+            match self {
+                SecretProvider::Command { command, args } => {
+                    // The child is killed when the future is dropped, so it does not outlive a
+                    // timeout.
+                    let output = tokio::process::Command::new(command)
+                        .args(args)
+                        .stdin(Stdio::null())
+                        .kill_on_drop(true)
+                        .output()
+                        .await
+                        .map_err(|e| format!("Unable to run {:?}: {}", command, e))?;
+                    command_secret(command, output.status, output.stdout)
+                }
+                provider => {
+                    let provider = provider.clone();
+                    tokio::task::spawn_blocking(move || provider.read_secret())
+                        .await
+                        .map_err(|e| format!("Secret provider task failed: {}", e))?
+                }
+            }
+        };
+        tokio::time::timeout(timeout, read)
+            .await
+            .map_err(|_| format!("Secret provider did not respond within {:?}", timeout))?
+    }
+}
+
+/// Converts the `stdout` of a secret provider `command` which exited with `status` into the
+/// secret. The output is zeroized if it is not a valid secret.
+fn command_secret(
+    command: &Path,
+    status: ExitStatus,
+    stdout: Vec<u8>,
+) -> Result<ZeroizeString, String> {
+    let mut stdout = Zeroizing::new(stdout);
+    if !status.success() {
+        return Err(format!("{:?} failed with {}", command, status));
+    }
+    String::from_utf8(strip_off_newlines(std::mem::take(&mut *stdout)))
+        .map(Into::into)
+        .map_err(|e| {
+            e.into_bytes().zeroize();
+            format!("Output of {:?} is not valid UTF-8", command)
+        })
+}
+
+fn read_vault_secret(
+    url: &str,
+    field: &str,
+    token_path: Option<&PathBuf>,
+) -> Result<ZeroizeString, String> {
+    let url = Url::parse(url).map_err(|e| format!("Invalid Vault URL: {}", e))?;
+    let is_local = match url.host_str() {
+        Some("localhost") => true,
+        Some(host) => host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .map_or(false, |ip| ip.is_loopback()),
+        None => false,
+    };
+    if url.scheme() != "https" && !is_local {
+        return Err(format!(
+            "Vault URL must use HTTPS to avoid sending the token and secret in plaintext: {}",
+            url
+        ));
+    }
+
+    let token = match token_path {
+        Some(path) => crate::read_password_string(path)
+            .map_err(|e| format!("Unable to read Vault token from {:?}: {}", path, e))?,
+        None => std::env::var(VAULT_TOKEN_ENV_VAR)
+            .map(Into::into)
+            .map_err(|e| format!("Unable to read {}: {}", VAULT_TOKEN_ENV_VAR, e))?,
+    };
+
+    let body = reqwest::blocking::Client::builder()
+        .timeout(VAULT_REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Unable to build Vault client: {}", e))?
+        .get(url)
+        .header(VAULT_TOKEN_HEADER, token.as_str())
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.bytes())
+        .map(|bytes| Zeroizing::new(bytes.to_vec()))
+        .map_err(|e| format!("Unable to read secret from Vault: {}", e))?;
+
+    // Only the requested field is decoded, straight into a `ZeroizeString`.
+    VaultField { field, depth: 0 }
+        .deserialize(&mut serde_json::Deserializer::from_slice(&body))
+        .map_err(|e| format!("Unable to parse Vault response: {}", e))?
+        .ok_or_else(|| format!("Vault secret has no string field {}", field))
+}
+
+/// Finds `field` in a Vault response without decoding any other values.
+///
+/// The secret is in the top-level `data` object, which version 2 of the key-value engine nests
+/// within a second `data` object.
+struct VaultField<'a> {
+    field: &'a str,
+    depth: usize,
+}
+
+impl<'de> DeserializeSeed<'de> for VaultField<'_> {
+    type Value = Option<ZeroizeString>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for VaultField<'_> {
+    type Value = Option<ZeroizeString>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a Vault response object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut secret = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if self.depth > 0 && key == self.field {
+                secret = Some(map.next_value::<ZeroizeString>()?);
+            } else if self.depth < 2 && key == "data" {
+                let nested = map.next_value_seed(VaultField {
+                    field: self.field,
+                    depth: self.depth + 1,
+                })?;
+                secret = nested.or(secret);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    const PASSWORD: &str = "cats are the best";
+    const TOKEN: &str = "stub-token";
+
+    /// Serve a single request for a Vault secret, responding with `body` if the request has the
+    /// expected token.
+    fn vault_stub(body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!(
+            "http://{}/v1/secret/data/validator",
+            listener.local_addr().unwrap()
+        );
+
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![];
+            let mut buf = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            let request = String::from_utf8(request).unwrap().to_lowercase();
+
+            let response = if request.contains(&format!("x-vault-token: {}", TOKEN)) {
+                format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                    content-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+            } else {
+                "HTTP/1.1 403 Forbidden\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                    .to_string()
+            };
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+
+        url
+    }
+
+    fn token_file() -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("token");
+        std::fs::write(&path, format!("{}\n", TOKEN)).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn env() {
+        let variable = "LIGHTHOUSE_SECRET_PROVIDER_TEST_PASSWORD".to_string();
+        std::env::set_var(&variable, PASSWORD);
+        let provider = SecretProvider::Env { variable };
+        assert_eq!(provider.read_secret().unwrap().as_str(), PASSWORD);
+
+        let provider = SecretProvider::Env {
+            variable: "LIGHTHOUSE_SECRET_PROVIDER_TEST_MISSING".to_string(),
+        };
+        assert!(provider.read_secret().is_err());
+    }
+
+    #[test]
+    fn command() {
+        let provider = SecretProvider::Command {
+            command: "echo".into(),
+            args: vec![PASSWORD.to_string()],
+        };
+        assert_eq!(provider.read_secret().unwrap().as_str(), PASSWORD);
+    }
+
+    #[test]
+    fn failed_command() {
+        let provider = SecretProvider::Command {
+            command: "false".into(),
+            args: vec![],
+        };
+        assert!(provider.read_secret().is_err());
+    }
+
+    #[tokio::test]
+    async fn command_with_timeout() {
+        let provider = SecretProvider::Command {
+            command: "echo".into(),
+            args: vec![PASSWORD.to_string()],
+        };
+        let secret = provider
+            .read_secret_with_timeout(Duration::from_secs(10))
+            .await
+            .unwrap();
+        assert_eq!(secret.as_str(), PASSWORD);
+    }
+
+    #[tokio::test]
+    async fn command_killed_on_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let pid_path = dir.path().join("pid");
+        let provider = SecretProvider::Command {
+            command: "sh".into(),
+            args: vec![
+                "-c".to_string(),
+                format!("echo $$ > {}; exec sleep 60", pid_path.display()),
+            ],
+        };
+        let error = provider
+            .read_secret_with_timeout(Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert!(error.contains("did not respond"), "{}", error);
+
+        // The child is killed once the read is abandoned.
+        let pid = std::fs::read_to_string(&pid_path).unwrap();
+        let mut alive = true;
+        for _ in 0..50 {
+            alive = Command::new("kill")
+                .args(["-0", pid.trim()])
+                .stderr(Stdio::null())
+                .status()
+                .unwrap()
+                .success();
+            if !alive {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert!(!alive, "command should be killed on timeout");
+    }
+
+    #[test]
+    fn vault_kv_v2() {
+        let (_dir, token_path) = token_file();
+        let provider = SecretProvider::Vault {
+            url: vault_stub(
+                r#"{"lease_duration": 0, "data": {"data": {"other": 1, "password": "cats are the best"}, "metadata": {"version": 2}}}"#,
+            ),
+            field: "password".to_string(),
+            token_path: Some(token_path),
+        };
+        assert_eq!(provider.read_secret().unwrap().as_str(), PASSWORD);
+    }
+
+    #[test]
+    fn vault_kv_v1() {
+        let (_dir, token_path) = token_file();
+        let provider = SecretProvider::Vault {
+            url: vault_stub(r#"{"data": {"password": "cats are the best"}}"#),
+            field: "password".to_string(),
+            token_path: Some(token_path),
+        };
+        assert_eq!(provider.read_secret().unwrap().as_str(), PASSWORD);
+    }
+
+    #[test]
+    fn vault_missing_field() {
+        let (_dir, token_path) = token_file();
+        let provider = SecretProvider::Vault {
+            url: vault_stub(r#"{"data": {"data": {"other": "cats are the best"}}}"#),
+            field: "password".to_string(),
+            token_path: Some(token_path),
+        };
+        assert!(provider.read_secret().is_err());
+    }
+
+    #[test]
+    fn vault_requires_https() {
+        let (_dir, token_path) = token_file();
+        let provider = SecretProvider::Vault {
+            url: "http://vault.example.com:8200/v1/secret/data/validator".to_string(),
+            field: "password".to_string(),
+            token_path: Some(token_path),
+        };
+        assert!(provider.read_secret().err().unwrap().contains("HTTPS"));
+    }
+
+    #[test]
+    fn vault_wrong_token() {
+        let dir = tempfile::tempdir().unwrap();
+        let token_path = dir.path().join("token");
+        std::fs::write(&token_path, "wrong-token").unwrap();
+        let provider = SecretProvider::Vault {
+            url: vault_stub(r#"{"data": {"data": {"password": "cats are the best"}}}"#),
+            field: "password".to_string(),
+            token_path: Some(token_path),
+        };
+        assert!(provider.read_secret().is_err());
+    }
+
+    #[test]
+    fn yaml_round_trip() {
+        let provider = SecretProvider::Vault {
+            url: "http://127.0.0.1:8200/v1/secret/data/validator".to_string(),
+            field: "password".to_string(),
+            token_path: None,
+        };
+        let yaml = serde_yaml::to_string(&provider).unwrap();
+        assert!(yaml.contains("provider: vault"));
+        assert_eq!(
+            serde_yaml::from_str::<SecretProvider>(&yaml).unwrap(),
+            provider
+        );
+    }
+}
@@ -4,7 +4,8 @@
 //! attempt) to load into the `crate::intialized_validators::InitializedValidators` struct.
 
 use crate::{
-    default_keystore_password_path, read_password_string, write_file_via_temporary, ZeroizeString,
+    default_keystore_password_path, default_keystore_password_provider_path, read_password_string,
+    secret_provider::SecretProvider, write_file_via_temporary, ZeroizeString,
 };
 use directory::ensure_dir_exists;
 use eth2_keystore::Keystore;
@@ -62,6 +63,11 @@ pub enum PasswordStorage {
 #[derive(Clone, PartialEq, Serialize, Deserialize, Hash, Eq)]
 pub struct Web3SignerDefinition {
     pub url: String,
+    /// Additional Web3Signer instances which hold the same key.
+    ///
+    /// A signing request which fails on `url` is retried on each of these in turn.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_urls: Vec<String>,
     /// Path to a .pem file.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub root_certificate_path: Option<PathBuf>,
@@ -94,6 +100,11 @@ pub enum SigningDefinition {
         voting_keystore_password_path: Option<PathBuf>,
         #[serde(skip_serializing_if = "Option::is_none")]
         voting_keystore_password: Option<ZeroizeString>,
+        /// Read the password from a source other than the filesystem.
+        ///
+        /// Takes precedence over `voting_keystore_password_path`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        voting_keystore_password_provider: Option<SecretProvider>,
     },
     /// A validator that defers to a Web3Signer HTTP server for signing.
     ///
@@ -113,6 +124,13 @@ impl SigningDefinition {
                 voting_keystore_password: Some(password),
                 ..
             } => Ok(Some(password.clone())),
+            SigningDefinition::LocalKeystore {
+                voting_keystore_password_provider: Some(provider),
+                ..
+            } => provider
+                .read_secret()
+                .map(Option::Some)
+                .map_err(Error::UnableToReadKeystorePassword),
             SigningDefinition::LocalKeystore {
                 voting_keystore_password_path: Some(path),
                 ..
@@ -211,6 +229,7 @@ impl ValidatorDefinition {
                 voting_keystore_path,
                 voting_keystore_password_path,
                 voting_keystore_password,
+                voting_keystore_password_provider: None,
             },
         })
     }
@@ -329,6 +348,33 @@ impl ValidatorDefinitions {
                 ))
                 .filter(|path| path.exists());
 
+                // If there is no password file, the password may be read from a provider instead.
+                let provider_path =
+                    default_keystore_password_provider_path(&keystore, secrets_dir.as_ref());
+                let voting_keystore_password_provider =
+                    if voting_keystore_password_path.is_none() && provider_path.exists() {
+                        let provider_result = File::open(&provider_path)
+                            .map_err(|e| format!("{:?}", e))
+                            .and_then(|file| {
+                                serde_yaml::from_reader::<_, SecretProvider>(file)
+                                    .map_err(|e| format!("{:?}", e))
+                            });
+                        match provider_result {
+                            Ok(provider) => Some(provider),
+                            Err(e) => {
+                                error!(
+                                    log,
+                                    "Unable to read keystore password provider";
+                                    "error" => e,
+                                    "provider" => format!("{:?}", provider_path)
+                                );
+                                return None;
+                            }
+                        }
+                    } else {
+                        None
+                    };
+
                 let voting_public_key = match keystore.public_key() {
                     Some(pubkey) => {
                         if known_pubkeys.contains(&pubkey) {
@@ -361,6 +407,7 @@ impl ValidatorDefinitions {
                         voting_keystore_path,
                         voting_keystore_password_path,
                         voting_keystore_password: None,
+                        voting_keystore_password_provider,
                     },
                 })
             })
@@ -687,4 +734,93 @@ mod tests {
         let def: ValidatorDefinition = serde_yaml::from_str(valid_builder_proposals).unwrap();
         assert_eq!(def.builder_proposals, Some(true));
     }
+
+    #[test]
+    fn password_provider_checks() {
+        let no_provider = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        voting_keystore_path: ""
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(no_provider).unwrap();
+        assert!(matches!(
+            def.signing_definition,
+            SigningDefinition::LocalKeystore {
+                voting_keystore_password_provider: None,
+                ..
+            }
+        ));
+
+        let invalid_provider = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        voting_keystore_path: ""
+        voting_keystore_password_provider:
+          provider: carrier_pigeon
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: Result<ValidatorDefinition, _> = serde_yaml::from_str(invalid_provider);
+        assert!(def.is_err());
+
+        let valid_provider = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        voting_keystore_path: ""
+        voting_keystore_password_provider:
+          provider: command
+          command: /usr/bin/pass
+          args: ["show", "validator"]
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(valid_provider).unwrap();
+        assert!(matches!(
+            def.signing_definition,
+            SigningDefinition::LocalKeystore {
+                voting_keystore_password_provider: Some(SecretProvider::Command { .. }),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn discover_password_provider() {
+        use validator_dir::insecure_keys::generate_deterministic_keystore;
+
+        let validators_dir = tempfile::tempdir().unwrap();
+        let secrets_dir = tempfile::tempdir().unwrap();
+        let log = Logger::root(slog::Discard, slog::o!());
+
+        let (keystore, _) = generate_deterministic_keystore(0).unwrap();
+        let keystore_dir = validators_dir
+            .path()
+            .join(format!("0x{}", keystore.pubkey()));
+        fs::create_dir(&keystore_dir).unwrap();
+        keystore
+            .to_json_writer(File::create(keystore_dir.join(VOTING_KEYSTORE_FILE)).unwrap())
+            .unwrap();
+        fs::write(
+            default_keystore_password_provider_path(&keystore, secrets_dir.path()),
+            "provider: env\nvariable: VALIDATOR_PASSWORD\n",
+        )
+        .unwrap();
+
+        let mut defs = ValidatorDefinitions::default();
+        assert_eq!(
+            defs.discover_local_keystores(validators_dir.path(), secrets_dir.path(), &log)
+                .unwrap(),
+            1
+        );
+        assert!(matches!(
+            &defs.as_slice()[0].signing_definition,
+            SigningDefinition::LocalKeystore {
+                voting_keystore_password_path: None,
+                voting_keystore_password_provider: Some(SecretProvider::Env { variable }),
+                ..
+            } if variable == "VALIDATOR_PASSWORD"
+        ));
+    }
 }
@@ -28,13 +28,17 @@ use reqwest::{
 pub use reqwest::{StatusCode, Url};
 pub use sensitive_url::{SensitiveError, SensitiveUrl};
 use serde::{de::DeserializeOwned, Serialize};
-use ssz::Encode;
+use ssz::{Decode, Encode};
 use std::fmt;
 use std::future::Future;
 use std::path::PathBuf;
 use std::time::Duration;
-use store::fork_versioned_response::ExecutionOptimisticFinalizedForkVersionedResponse;
+use store::fork_versioned_response::{
+    EmptyMetadata, ExecutionOptimisticFinalizedForkVersionedResponse,
+    ExecutionOptimisticFinalizedMetadata,
+};
 
+pub const V0: EndpointVersion = EndpointVersion(0);
 pub const V1: EndpointVersion = EndpointVersion(1);
 pub const V2: EndpointVersion = EndpointVersion(2);
 pub const V3: EndpointVersion = EndpointVersion(3);
@@ -43,6 +47,8 @@ pub const CONSENSUS_VERSION_HEADER: &str = "Eth-Consensus-Version";
 pub const EXECUTION_PAYLOAD_BLINDED_HEADER: &str = "Eth-Execution-Payload-Blinded";
 pub const EXECUTION_PAYLOAD_VALUE_HEADER: &str = "Eth-Execution-Payload-Value";
 pub const CONSENSUS_BLOCK_VALUE_HEADER: &str = "Eth-Consensus-Block-Value";
+pub const EXECUTION_OPTIMISTIC_HEADER: &str = "Eth-Execution-Optimistic";
+pub const FINALIZED_HEADER: &str = "Eth-Finalized";
 
 pub const CONTENT_TYPE_HEADER: &str = "Content-Type";
 pub const SSZ_CONTENT_TYPE_HEADER: &str = "application/octet-stream";
@@ -262,6 +268,79 @@ impl BeaconNodeHttpClient {
         }
     }
 
+    /// Perform a HTTP GET request for an SSZ response, returning `None` on a 404 error.
+    async fn get_ssz_opt<T: Decode, U: IntoUrl>(&self, url: U) -> Result<Option<T>, Error> {
+        match self
+            .get_response(url, |b| b.accept(Accept::Ssz))
+            .await
+            .optional()?
+        {
+            Some(response) => T::from_ssz_bytes(&response.bytes().await?)
+                .map(Some)
+                .map_err(Error::InvalidSsz),
+            None => Ok(None),
+        }
+    }
+
+    /// Perform a HTTP GET request for an SSZ response, returning `None` on a 404 error.
+    ///
+    /// The `execution_optimistic` and `finalized` fields are read from the response headers.
+    async fn get_ssz_opt_with_metadata<T, U: IntoUrl>(
+        &self,
+        url: U,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<T>>, Error>
+    where
+        T: Decode + Serialize + DeserializeOwned,
+    {
+        match self
+            .get_response(url, |b| b.accept(Accept::Ssz))
+            .await
+            .optional()?
+        {
+            Some(response) => ssz_response_with_metadata(response).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Perform a HTTP GET request for an SSZ response which is decoded according to the fork in
+    /// its `Eth-Consensus-Version` header, returning `None` on a 404 error.
+    ///
+    /// The `execution_optimistic` and `finalized` fields are read from the response headers.
+    async fn get_fork_versioned_ssz_opt<T, U: IntoUrl>(
+        &self,
+        url: U,
+        decode: impl FnOnce(&[u8], ForkName) -> Result<T, ssz::DecodeError>,
+    ) -> Result<Option<ExecutionOptimisticFinalizedForkVersionedResponse<T>>, Error> {
+        let Some(response) = self
+            .get_response(url, |b| b.accept(Accept::Ssz))
+            .await
+            .optional()?
+        else {
+            return Ok(None);
+        };
+
+        let fork_name = response
+            .headers()
+            .get(CONSENSUS_VERSION_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<ForkName>().ok())
+            .ok_or_else(|| {
+                Error::InvalidHeaders(format!("missing or invalid {CONSENSUS_VERSION_HEADER}"))
+            })?;
+        let (execution_optimistic, finalized) =
+            execution_optimistic_finalized_from_headers(response.headers())?;
+        let data = decode(&response.bytes().await?, fork_name).map_err(Error::InvalidSsz)?;
+
+        Ok(Some(ExecutionOptimisticFinalizedForkVersionedResponse {
+            version: Some(fork_name),
+            metadata: ExecutionOptimisticFinalizedMetadata {
+                execution_optimistic,
+                finalized,
+            },
+            data,
+        }))
+    }
+
     /// Perform a HTTP GET request using an 'accept' header, returning `None` on a 404 error.
     pub async fn get_bytes_opt_accept_header<U: IntoUrl>(
         &self,
@@ -337,6 +416,30 @@ impl BeaconNodeHttpClient {
         }
     }
 
+    /// Perform a HTTP POST request for an SSZ response, returning `None` on a 404 error.
+    ///
+    /// The `execution_optimistic` and `finalized` fields are read from the response headers.
+    async fn post_with_opt_ssz_response<T: Serialize, U: IntoUrl, R>(
+        &self,
+        url: U,
+        body: &T,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<R>>, Error>
+    where
+        R: Decode + Serialize + DeserializeOwned,
+    {
+        let response = self
+            .client
+            .post(url)
+            .accept(Accept::Ssz)
+            .json(body)
+            .send()
+            .await?;
+        match ok_or_error(response).await.optional()? {
+            Some(response) => ssz_response_with_metadata(response).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
     /// Perform a HTTP POST request with a custom timeout.
     async fn post_with_timeout<T: Serialize, U: IntoUrl>(
         &self,
@@ -453,20 +556,45 @@ impl BeaconNodeHttpClient {
         ok_or_error(response).await
     }
 
+    /// Path for `v1/beacon/genesis`
+    fn get_beacon_genesis_path(&self) -> Result<Url, Error> {
+        let mut path = self.eth_path(V1)?;
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("genesis");
+        Ok(path)
+    }
+
     /// `GET beacon/genesis`
     ///
     /// ## Errors
     ///
     /// May return a `404` if beacon chain genesis has not yet occurred.
     pub async fn get_beacon_genesis(&self) -> Result<GenericResponse<GenesisData>, Error> {
-        let mut path = self.eth_path(V1)?;
+        let path = self.get_beacon_genesis_path()?;
+        self.get(path).await
+    }
+
+    /// `GET beacon/genesis` as SSZ
+    ///
+    /// Returns `Ok(None)` on a 404 error, which occurs if beacon chain genesis has not yet
+    /// occurred.
+    pub async fn get_beacon_genesis_ssz(&self) -> Result<Option<GenesisData>, Error> {
+        let path = self.get_beacon_genesis_path()?;
+        self.get_ssz_opt(path).await
+    }
 
+    /// Path for `v1/beacon/states/{state_id}/root`
+    fn get_beacon_states_root_path(&self, state_id: StateId) -> Result<Url, Error> {
+        let mut path = self.eth_path(V1)?;
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
-            .push("genesis");
-
-        self.get(path).await
+            .push("states")
+            .push(&state_id.to_string())
+            .push("root");
+        Ok(path)
     }
 
     /// `GET beacon/states/{state_id}/root`
@@ -476,65 +604,95 @@ impl BeaconNodeHttpClient {
         &self,
         state_id: StateId,
     ) -> Result<Option<ExecutionOptimisticFinalizedResponse<RootData>>, Error> {
-        let mut path = self.eth_path(V1)?;
-
-        path.path_segments_mut()
-            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
-            .push("beacon")
-            .push("states")
-            .push(&state_id.to_string())
-            .push("root");
-
+        let path = self.get_beacon_states_root_path(state_id)?;
         self.get_opt(path).await
     }
 
-    /// `GET beacon/states/{state_id}/fork`
+    /// `GET beacon/states/{state_id}/root` as SSZ
     ///
     /// Returns `Ok(None)` on a 404 error.
-    pub async fn get_beacon_states_fork(
+    pub async fn get_beacon_states_root_ssz(
         &self,
         state_id: StateId,
-    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Fork>>, Error> {
-        let mut path = self.eth_path(V1)?;
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<RootData>>, Error> {
+        let path = self.get_beacon_states_root_path(state_id)?;
+        self.get_ssz_opt_with_metadata(path).await
+    }
 
+    /// Path for `v1/beacon/states/{state_id}/fork`
+    fn get_beacon_states_fork_path(&self, state_id: StateId) -> Result<Url, Error> {
+        let mut path = self.eth_path(V1)?;
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("states")
             .push(&state_id.to_string())
             .push("fork");
+        Ok(path)
+    }
 
+    /// `GET beacon/states/{state_id}/fork`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_fork(
+        &self,
+        state_id: StateId,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Fork>>, Error> {
+        let path = self.get_beacon_states_fork_path(state_id)?;
         self.get_opt(path).await
     }
 
-    /// `GET beacon/states/{state_id}/finality_checkpoints`
+    /// `GET beacon/states/{state_id}/fork` as SSZ
     ///
     /// Returns `Ok(None)` on a 404 error.
-    pub async fn get_beacon_states_finality_checkpoints(
+    pub async fn get_beacon_states_fork_ssz(
         &self,
         state_id: StateId,
-    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<FinalityCheckpointsData>>, Error> {
-        let mut path = self.eth_path(V1)?;
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Fork>>, Error> {
+        let path = self.get_beacon_states_fork_path(state_id)?;
+        self.get_ssz_opt_with_metadata(path).await
+    }
 
+    /// Path for `v1/beacon/states/{state_id}/finality_checkpoints`
+    fn get_beacon_states_finality_checkpoints_path(&self, state_id: StateId) -> Result<Url, Error> {
+        let mut path = self.eth_path(V1)?;
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("states")
             .push(&state_id.to_string())
             .push("finality_checkpoints");
+        Ok(path)
+    }
 
+    /// `GET beacon/states/{state_id}/finality_checkpoints`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_finality_checkpoints(
+        &self,
+        state_id: StateId,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<FinalityCheckpointsData>>, Error> {
+        let path = self.get_beacon_states_finality_checkpoints_path(state_id)?;
         self.get_opt(path).await
     }
 
-    /// `GET beacon/states/{state_id}/validator_balances?id`
+    /// `GET beacon/states/{state_id}/finality_checkpoints` as SSZ
     ///
     /// Returns `Ok(None)` on a 404 error.
-    pub async fn get_beacon_states_validator_balances(
+    pub async fn get_beacon_states_finality_checkpoints_ssz(
+        &self,
+        state_id: StateId,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<FinalityCheckpointsData>>, Error> {
+        let path = self.get_beacon_states_finality_checkpoints_path(state_id)?;
+        self.get_ssz_opt_with_metadata(path).await
+    }
+
+    /// Path for `v1/beacon/states/{state_id}/validator_balances?id`
+    fn get_beacon_states_validator_balances_path(
         &self,
         state_id: StateId,
         ids: Option<&[ValidatorId]>,
-    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<ValidatorBalanceData>>>, Error>
-    {
+    ) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
@@ -553,27 +711,33 @@ impl BeaconNodeHttpClient {
             path.query_pairs_mut().append_pair("id", &id_string);
         }
 
-        self.get_opt(path).await
+        Ok(path)
     }
 
-    /// TESTING ONLY: This request should fail with a 415 response code.
-    pub async fn post_beacon_states_validator_balances_with_ssz_header(
+    /// `GET beacon/states/{state_id}/validator_balances?id`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_validator_balances(
         &self,
         state_id: StateId,
-        ids: Vec<ValidatorId>,
-    ) -> Result<Response, Error> {
-        let mut path = self.eth_path(V1)?;
-
-        path.path_segments_mut()
-            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
-            .push("beacon")
-            .push("states")
-            .push(&state_id.to_string())
-            .push("validator_balances");
-
-        let request = ValidatorBalancesRequestBody { ids };
+        ids: Option<&[ValidatorId]>,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<ValidatorBalanceData>>>, Error>
+    {
+        let path = self.get_beacon_states_validator_balances_path(state_id, ids)?;
+        self.get_opt(path).await
+    }
 
-        self.post_generic_with_ssz_header(path, &request).await
+    /// `GET beacon/states/{state_id}/validator_balances?id` as SSZ
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_validator_balances_ssz(
+        &self,
+        state_id: StateId,
+        ids: Option<&[ValidatorId]>,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<ValidatorBalanceData>>>, Error>
+    {
+        let path = self.get_beacon_states_validator_balances_path(state_id, ids)?;
+        self.get_ssz_opt_with_metadata(path).await
     }
 
     /// `POST beacon/states/{state_id}/validator_balances`
@@ -585,29 +749,46 @@ impl BeaconNodeHttpClient {
         ids: Vec<ValidatorId>,
     ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<ValidatorBalanceData>>>, Error>
     {
-        let mut path = self.eth_path(V1)?;
-
-        path.path_segments_mut()
-            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
-            .push("beacon")
-            .push("states")
-            .push(&state_id.to_string())
-            .push("validator_balances");
-
+        let path = self.get_beacon_states_validator_balances_path(state_id, None)?;
         let request = ValidatorBalancesRequestBody { ids };
 
         self.post_with_opt_response(path, &request).await
     }
 
-    /// `GET beacon/states/{state_id}/validators?id,status`
+    /// `POST beacon/states/{state_id}/validator_balances` as SSZ
     ///
     /// Returns `Ok(None)` on a 404 error.
-    pub async fn get_beacon_states_validators(
+    pub async fn post_beacon_states_validator_balances_ssz(
+        &self,
+        state_id: StateId,
+        ids: Vec<ValidatorId>,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<ValidatorBalanceData>>>, Error>
+    {
+        let path = self.get_beacon_states_validator_balances_path(state_id, None)?;
+        let request = ValidatorBalancesRequestBody { ids };
+
+        self.post_with_opt_ssz_response(path, &request).await
+    }
+
+    /// TESTING ONLY: This request should fail with a 415 response code.
+    pub async fn post_beacon_states_validator_balances_with_ssz_header(
+        &self,
+        state_id: StateId,
+        ids: Vec<ValidatorId>,
+    ) -> Result<Response, Error> {
+        let path = self.get_beacon_states_validator_balances_path(state_id, None)?;
+        let request = ValidatorBalancesRequestBody { ids };
+
+        self.post_generic_with_ssz_header(path, &request).await
+    }
+
+    /// Path for `v1/beacon/states/{state_id}/validators?id,status`
+    fn get_beacon_states_validators_path(
         &self,
         state_id: StateId,
         ids: Option<&[ValidatorId]>,
         statuses: Option<&[ValidatorStatus]>,
-    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<ValidatorData>>>, Error> {
+    ) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
@@ -635,9 +816,35 @@ impl BeaconNodeHttpClient {
             path.query_pairs_mut().append_pair("status", &status_string);
         }
 
+        Ok(path)
+    }
+
+    /// `GET beacon/states/{state_id}/validators?id,status`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_validators(
+        &self,
+        state_id: StateId,
+        ids: Option<&[ValidatorId]>,
+        statuses: Option<&[ValidatorStatus]>,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<ValidatorData>>>, Error> {
+        let path = self.get_beacon_states_validators_path(state_id, ids, statuses)?;
         self.get_opt(path).await
     }
 
+    /// `GET beacon/states/{state_id}/validators?id,status` as SSZ
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_validators_ssz(
+        &self,
+        state_id: StateId,
+        ids: Option<&[ValidatorId]>,
+        statuses: Option<&[ValidatorStatus]>,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<ValidatorData>>>, Error> {
+        let path = self.get_beacon_states_validators_path(state_id, ids, statuses)?;
+        self.get_ssz_opt_with_metadata(path).await
+    }
+
     /// `POST beacon/states/{state_id}/validators`
     ///
     /// Returns `Ok(None)` on a 404 error.
@@ -647,30 +854,35 @@ impl BeaconNodeHttpClient {
         ids: Option<Vec<ValidatorId>>,
         statuses: Option<Vec<ValidatorStatus>>,
     ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<ValidatorData>>>, Error> {
-        let mut path = self.eth_path(V1)?;
-
-        path.path_segments_mut()
-            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
-            .push("beacon")
-            .push("states")
-            .push(&state_id.to_string())
-            .push("validators");
-
+        let path = self.get_beacon_states_validators_path(state_id, None, None)?;
         let request = ValidatorsRequestBody { ids, statuses };
 
         self.post_with_opt_response(path, &request).await
     }
 
-    /// `GET beacon/states/{state_id}/committees?slot,index,epoch`
+    /// `POST beacon/states/{state_id}/validators` as SSZ
     ///
     /// Returns `Ok(None)` on a 404 error.
-    pub async fn get_beacon_states_committees(
+    pub async fn post_beacon_states_validators_ssz(
+        &self,
+        state_id: StateId,
+        ids: Option<Vec<ValidatorId>>,
+        statuses: Option<Vec<ValidatorStatus>>,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<ValidatorData>>>, Error> {
+        let path = self.get_beacon_states_validators_path(state_id, None, None)?;
+        let request = ValidatorsRequestBody { ids, statuses };
+
+        self.post_with_opt_ssz_response(path, &request).await
+    }
+
+    /// Path for `v1/beacon/states/{state_id}/committees?slot,index,epoch`
+    fn get_beacon_states_committees_path(
         &self,
         state_id: StateId,
         slot: Option<Slot>,
         index: Option<u64>,
         epoch: Option<Epoch>,
-    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<CommitteeData>>>, Error> {
+    ) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
@@ -695,15 +907,43 @@ impl BeaconNodeHttpClient {
                 .append_pair("epoch", &epoch.to_string());
         }
 
+        Ok(path)
+    }
+
+    /// `GET beacon/states/{state_id}/committees?slot,index,epoch`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_committees(
+        &self,
+        state_id: StateId,
+        slot: Option<Slot>,
+        index: Option<u64>,
+        epoch: Option<Epoch>,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<CommitteeData>>>, Error> {
+        let path = self.get_beacon_states_committees_path(state_id, slot, index, epoch)?;
         self.get_opt(path).await
     }
 
-    /// `GET beacon/states/{state_id}/sync_committees?epoch`
-    pub async fn get_beacon_states_sync_committees(
+    /// `GET beacon/states/{state_id}/committees?slot,index,epoch` as SSZ
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_committees_ssz(
         &self,
         state_id: StateId,
+        slot: Option<Slot>,
+        index: Option<u64>,
         epoch: Option<Epoch>,
-    ) -> Result<ExecutionOptimisticFinalizedResponse<SyncCommitteeByValidatorIndices>, Error> {
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<CommitteeData>>>, Error> {
+        let path = self.get_beacon_states_committees_path(state_id, slot, index, epoch)?;
+        self.get_ssz_opt_with_metadata(path).await
+    }
+
+    /// Path for `v1/beacon/states/{state_id}/sync_committees?epoch`
+    fn get_beacon_states_sync_committees_path(
+        &self,
+        state_id: StateId,
+        epoch: Option<Epoch>,
+    ) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
@@ -718,15 +958,38 @@ impl BeaconNodeHttpClient {
                 .append_pair("epoch", &epoch.to_string());
         }
 
+        Ok(path)
+    }
+
+    /// `GET beacon/states/{state_id}/sync_committees?epoch`
+    pub async fn get_beacon_states_sync_committees(
+        &self,
+        state_id: StateId,
+        epoch: Option<Epoch>,
+    ) -> Result<ExecutionOptimisticFinalizedResponse<SyncCommitteeByValidatorIndices>, Error> {
+        let path = self.get_beacon_states_sync_committees_path(state_id, epoch)?;
         self.get(path).await
     }
 
-    /// `GET beacon/states/{state_id}/randao?epoch`
-    pub async fn get_beacon_states_randao(
+    /// `GET beacon/states/{state_id}/sync_committees?epoch` as SSZ
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_sync_committees_ssz(
         &self,
         state_id: StateId,
         epoch: Option<Epoch>,
-    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<RandaoMix>>, Error> {
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<SyncCommitteeByValidatorIndices>>, Error>
+    {
+        let path = self.get_beacon_states_sync_committees_path(state_id, epoch)?;
+        self.get_ssz_opt_with_metadata(path).await
+    }
+
+    /// Path for `v1/beacon/states/{state_id}/randao?epoch`
+    fn get_beacon_states_randao_path(
+        &self,
+        state_id: StateId,
+        epoch: Option<Epoch>,
+    ) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
@@ -741,28 +1004,139 @@ impl BeaconNodeHttpClient {
                 .append_pair("epoch", &epoch.to_string());
         }
 
+        Ok(path)
+    }
+
+    /// `GET beacon/states/{state_id}/randao?epoch`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_randao(
+        &self,
+        state_id: StateId,
+        epoch: Option<Epoch>,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<RandaoMix>>, Error> {
+        let path = self.get_beacon_states_randao_path(state_id, epoch)?;
+        self.get_opt(path).await
+    }
+
+    /// `GET beacon/states/{state_id}/randao?epoch` as SSZ
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_randao_ssz(
+        &self,
+        state_id: StateId,
+        epoch: Option<Epoch>,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<RandaoMix>>, Error> {
+        let path = self.get_beacon_states_randao_path(state_id, epoch)?;
+        self.get_ssz_opt_with_metadata(path).await
+    }
+
+    /// Path for `v0/beacon/{states,blocks}/{id}/proof`, selecting the nodes to prove.
+    fn get_proof_path(
+        &self,
+        object: &str,
+        id: &str,
+        gindices: &[u64],
+        paths: &[SszPath],
+    ) -> Result<Url, Error> {
+        let mut path = self.eth_path(V0)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push(object)
+            .push(id)
+            .push("proof");
+
+        if !gindices.is_empty() {
+            let gindices_string = gindices
+                .iter()
+                .map(|gindex| gindex.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            path.query_pairs_mut()
+                .append_pair("gindices", &gindices_string);
+        }
+        if !paths.is_empty() {
+            let paths_string = paths
+                .iter()
+                .map(|path| path.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            path.query_pairs_mut().append_pair("paths", &paths_string);
+        }
+
+        Ok(path)
+    }
+
+    /// `GET v0/beacon/states/{state_id}/proof?gindices,paths`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_proof(
+        &self,
+        state_id: StateId,
+        gindices: &[u64],
+        paths: &[SszPath],
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Multiproof>>, Error> {
+        let path = self.get_proof_path("states", &state_id.to_string(), gindices, paths)?;
+        self.get_opt(path).await
+    }
+
+    /// `GET v0/beacon/states/{state_id}/proof?gindices,paths`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_proof_ssz(
+        &self,
+        state_id: StateId,
+        gindices: &[u64],
+        paths: &[SszPath],
+    ) -> Result<Option<Multiproof>, Error> {
+        let path = self.get_proof_path("states", &state_id.to_string(), gindices, paths)?;
+        self.get_bytes_opt_accept_header(path, Accept::Ssz, self.timeouts.get_debug_beacon_states)
+            .await?
+            .map(|bytes| Multiproof::from_ssz_bytes(&bytes).map_err(Error::InvalidSsz))
+            .transpose()
+    }
+
+    /// Path for `v1/beacon/states/{state_id}/validators/{validator_id}`
+    fn get_beacon_states_validator_id_path(
+        &self,
+        state_id: StateId,
+        validator_id: &ValidatorId,
+    ) -> Result<Url, Error> {
+        let mut path = self.eth_path(V1)?;
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("states")
+            .push(&state_id.to_string())
+            .push("validators")
+            .push(&validator_id.to_string());
+        Ok(path)
+    }
+
+    /// `GET beacon/states/{state_id}/validators/{validator_id}`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_validator_id(
+        &self,
+        state_id: StateId,
+        validator_id: &ValidatorId,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<ValidatorData>>, Error> {
+        let path = self.get_beacon_states_validator_id_path(state_id, validator_id)?;
         self.get_opt(path).await
     }
 
-    /// `GET beacon/states/{state_id}/validators/{validator_id}`
+    /// `GET beacon/states/{state_id}/validators/{validator_id}` as SSZ
     ///
     /// Returns `Ok(None)` on a 404 error.
-    pub async fn get_beacon_states_validator_id(
+    pub async fn get_beacon_states_validator_id_ssz(
         &self,
         state_id: StateId,
         validator_id: &ValidatorId,
     ) -> Result<Option<ExecutionOptimisticFinalizedResponse<ValidatorData>>, Error> {
-        let mut path = self.eth_path(V1)?;
-
-        path.path_segments_mut()
-            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
-            .push("beacon")
-            .push("states")
-            .push(&state_id.to_string())
-            .push("validators")
-            .push(&validator_id.to_string());
-
-        self.get_opt(path).await
+        let path = self.get_beacon_states_validator_id_path(state_id, validator_id)?;
+        self.get_ssz_opt_with_metadata(path).await
     }
 
     /// `GET beacon/light_client/bootstrap`
@@ -818,14 +1192,35 @@ impl BeaconNodeHttpClient {
         self.get_opt(path).await
     }
 
-    /// `GET beacon/headers?slot,parent_root`
+    /// `GET beacon/light_client/updates?start_period,count`
     ///
     /// Returns `Ok(None)` on a 404 error.
-    pub async fn get_beacon_headers(
+    pub async fn get_beacon_light_client_updates<E: EthSpec>(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Option<Vec<ForkVersionedResponse<LightClientUpdate<E>>>>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("light_client")
+            .push("updates");
+
+        path.query_pairs_mut()
+            .append_pair("start_period", &start_period.to_string())
+            .append_pair("count", &count.to_string());
+
+        self.get_opt(path).await
+    }
+
+    /// Path for `v1/beacon/headers?slot,parent_root`
+    fn get_beacon_headers_path(
         &self,
         slot: Option<Slot>,
         parent_root: Option<Hash256>,
-    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<BlockHeaderData>>>, Error> {
+    ) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
@@ -843,27 +1238,66 @@ impl BeaconNodeHttpClient {
                 .append_pair("parent_root", &format!("{:?}", root));
         }
 
+        Ok(path)
+    }
+
+    /// `GET beacon/headers?slot,parent_root`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_headers(
+        &self,
+        slot: Option<Slot>,
+        parent_root: Option<Hash256>,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<BlockHeaderData>>>, Error> {
+        let path = self.get_beacon_headers_path(slot, parent_root)?;
         self.get_opt(path).await
     }
 
-    /// `GET beacon/headers/{block_id}`
+    /// `GET beacon/headers?slot,parent_root` as SSZ
     ///
     /// Returns `Ok(None)` on a 404 error.
-    pub async fn get_beacon_headers_block_id(
+    pub async fn get_beacon_headers_ssz(
         &self,
-        block_id: BlockId,
-    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<BlockHeaderData>>, Error> {
-        let mut path = self.eth_path(V1)?;
+        slot: Option<Slot>,
+        parent_root: Option<Hash256>,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Vec<BlockHeaderData>>>, Error> {
+        let path = self.get_beacon_headers_path(slot, parent_root)?;
+        self.get_ssz_opt_with_metadata(path).await
+    }
 
+    /// Path for `v1/beacon/headers/{block_id}`
+    fn get_beacon_headers_block_id_path(&self, block_id: BlockId) -> Result<Url, Error> {
+        let mut path = self.eth_path(V1)?;
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("headers")
             .push(&block_id.to_string());
+        Ok(path)
+    }
 
+    /// `GET beacon/headers/{block_id}`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_headers_block_id(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<BlockHeaderData>>, Error> {
+        let path = self.get_beacon_headers_block_id_path(block_id)?;
         self.get_opt(path).await
     }
 
+    /// `GET beacon/headers/{block_id}` as SSZ
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_headers_block_id_ssz(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<BlockHeaderData>>, Error> {
+        let path = self.get_beacon_headers_block_id_path(block_id)?;
+        self.get_ssz_opt_with_metadata(path).await
+    }
+
     /// `POST beacon/blocks`
     ///
     /// Returns `Ok(None)` on a 404 error.
@@ -1135,6 +1569,35 @@ impl BeaconNodeHttpClient {
         Ok(Some(response.json().await?))
     }
 
+    /// `GET v0/beacon/blocks/{block_id}/proof?gindices,paths`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_blocks_proof(
+        &self,
+        block_id: BlockId,
+        gindices: &[u64],
+        paths: &[SszPath],
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<Multiproof>>, Error> {
+        let path = self.get_proof_path("blocks", &block_id.to_string(), gindices, paths)?;
+        self.get_opt(path).await
+    }
+
+    /// `GET v0/beacon/blocks/{block_id}/proof?gindices,paths`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_blocks_proof_ssz(
+        &self,
+        block_id: BlockId,
+        gindices: &[u64],
+        paths: &[SszPath],
+    ) -> Result<Option<Multiproof>, Error> {
+        let path = self.get_proof_path("blocks", &block_id.to_string(), gindices, paths)?;
+        self.get_bytes_opt_accept_header(path, Accept::Ssz, self.timeouts.get_beacon_blocks_ssz)
+            .await?
+            .map(|bytes| Multiproof::from_ssz_bytes(&bytes).map_err(Error::InvalidSsz))
+            .transpose()
+    }
+
     /// `GET v1/beacon/blinded_blocks/{block_id}`
     ///
     /// Returns `Ok(None)` on a 404 error.
@@ -1205,25 +1668,40 @@ impl BeaconNodeHttpClient {
             .transpose()
     }
 
-    /// `GET beacon/blocks/{block_id}/root`
-    ///
-    /// Returns `Ok(None)` on a 404 error.
-    pub async fn get_beacon_blocks_root(
-        &self,
-        block_id: BlockId,
-    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<RootData>>, Error> {
+    /// Path for `v1/beacon/blocks/{block_id}/root`
+    fn get_beacon_blocks_root_path(&self, block_id: BlockId) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
-
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("blocks")
             .push(&block_id.to_string())
             .push("root");
+        Ok(path)
+    }
 
+    /// `GET beacon/blocks/{block_id}/root`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_blocks_root(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<RootData>>, Error> {
+        let path = self.get_beacon_blocks_root_path(block_id)?;
         self.get_opt(path).await
     }
 
+    /// `GET beacon/blocks/{block_id}/root` as SSZ
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_blocks_root_ssz(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<RootData>>, Error> {
+        let path = self.get_beacon_blocks_root_path(block_id)?;
+        self.get_ssz_opt_with_metadata(path).await
+    }
+
     /// `GET v1/beacon/blocks/{block_id}/attestations`
     ///
     /// Returns `Ok(None)` on a 404 error.
@@ -1263,6 +1741,27 @@ impl BeaconNodeHttpClient {
         self.get_opt(path).await
     }
 
+    /// `GET v2/beacon/blocks/{block_id}/attestations` as SSZ
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_blocks_attestations_v2_ssz<E: EthSpec>(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<ExecutionOptimisticFinalizedForkVersionedResponse<Vec<Attestation<E>>>>, Error>
+    {
+        let mut path = self.eth_path(V2)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("blocks")
+            .push(&block_id.to_string())
+            .push("attestations");
+
+        self.get_fork_versioned_ssz_opt(path, attestations_from_ssz_bytes_for_fork)
+            .await
+    }
+
     /// `POST v1/beacon/pool/attestations`
     pub async fn post_beacon_pool_attestations_v1<E: EthSpec>(
         &self,
@@ -1334,12 +1833,12 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
-    /// `GET v2/beacon/pool/attestations?slot,committee_index`
-    pub async fn get_beacon_pool_attestations_v2<E: EthSpec>(
+    /// Path for `v2/beacon/pool/attestations?slot,committee_index`
+    fn get_beacon_pool_attestations_v2_path(
         &self,
         slot: Option<Slot>,
         committee_index: Option<u64>,
-    ) -> Result<ForkVersionedResponse<Vec<Attestation<E>>>, Error> {
+    ) -> Result<Url, Error> {
         let mut path = self.eth_path(V2)?;
 
         path.path_segments_mut()
@@ -1358,9 +1857,37 @@ impl BeaconNodeHttpClient {
                 .append_pair("committee_index", &index.to_string());
         }
 
+        Ok(path)
+    }
+
+    /// `GET v2/beacon/pool/attestations?slot,committee_index`
+    pub async fn get_beacon_pool_attestations_v2<E: EthSpec>(
+        &self,
+        slot: Option<Slot>,
+        committee_index: Option<u64>,
+    ) -> Result<ForkVersionedResponse<Vec<Attestation<E>>>, Error> {
+        let path = self.get_beacon_pool_attestations_v2_path(slot, committee_index)?;
         self.get(path).await
     }
 
+    /// `GET v2/beacon/pool/attestations?slot,committee_index` as SSZ
+    pub async fn get_beacon_pool_attestations_v2_ssz<E: EthSpec>(
+        &self,
+        slot: Option<Slot>,
+        committee_index: Option<u64>,
+    ) -> Result<ForkVersionedResponse<Vec<Attestation<E>>>, Error> {
+        let path = self.get_beacon_pool_attestations_v2_path(slot, committee_index)?;
+        let response = self
+            .get_fork_versioned_ssz_opt(path, attestations_from_ssz_bytes_for_fork)
+            .await?
+            .ok_or(Error::StatusCode(StatusCode::NOT_FOUND))?;
+        Ok(ForkVersionedResponse {
+            version: response.version,
+            metadata: EmptyMetadata {},
+            data: response.data,
+        })
+    }
+
     /// `POST v1/beacon/pool/attester_slashings`
     pub async fn post_beacon_pool_attester_slashings_v1<E: EthSpec>(
         &self,
@@ -1414,21 +1941,41 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
-    /// `GET v2/beacon/pool/attester_slashings`
-    pub async fn get_beacon_pool_attester_slashings_v2<E: EthSpec>(
-        &self,
-    ) -> Result<ForkVersionedResponse<Vec<AttesterSlashing<E>>>, Error> {
+    /// Path for `v2/beacon/pool/attester_slashings`
+    fn get_beacon_pool_attester_slashings_v2_path(&self) -> Result<Url, Error> {
         let mut path = self.eth_path(V2)?;
-
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("pool")
             .push("attester_slashings");
+        Ok(path)
+    }
 
+    /// `GET v2/beacon/pool/attester_slashings`
+    pub async fn get_beacon_pool_attester_slashings_v2<E: EthSpec>(
+        &self,
+    ) -> Result<ForkVersionedResponse<Vec<AttesterSlashing<E>>>, Error> {
+        let path = self.get_beacon_pool_attester_slashings_v2_path()?;
         self.get(path).await
     }
 
+    /// `GET v2/beacon/pool/attester_slashings` as SSZ
+    pub async fn get_beacon_pool_attester_slashings_v2_ssz<E: EthSpec>(
+        &self,
+    ) -> Result<ForkVersionedResponse<Vec<AttesterSlashing<E>>>, Error> {
+        let path = self.get_beacon_pool_attester_slashings_v2_path()?;
+        let response = self
+            .get_fork_versioned_ssz_opt(path, attester_slashings_from_ssz_bytes_for_fork)
+            .await?
+            .ok_or(Error::StatusCode(StatusCode::NOT_FOUND))?;
+        Ok(ForkVersionedResponse {
+            version: response.version,
+            metadata: EmptyMetadata {},
+            data: response.data,
+        })
+    }
+
     /// `POST beacon/pool/proposer_slashings`
     pub async fn post_beacon_pool_proposer_slashings(
         &self,
@@ -1447,21 +1994,35 @@ impl BeaconNodeHttpClient {
         Ok(())
     }
 
-    /// `GET beacon/pool/proposer_slashings`
-    pub async fn get_beacon_pool_proposer_slashings(
-        &self,
-    ) -> Result<GenericResponse<Vec<ProposerSlashing>>, Error> {
+    /// Path for `v1/beacon/pool/proposer_slashings`
+    fn get_beacon_pool_proposer_slashings_path(&self) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
-
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("pool")
             .push("proposer_slashings");
+        Ok(path)
+    }
 
+    /// `GET beacon/pool/proposer_slashings`
+    pub async fn get_beacon_pool_proposer_slashings(
+        &self,
+    ) -> Result<GenericResponse<Vec<ProposerSlashing>>, Error> {
+        let path = self.get_beacon_pool_proposer_slashings_path()?;
         self.get(path).await
     }
 
+    /// `GET beacon/pool/proposer_slashings` as SSZ
+    pub async fn get_beacon_pool_proposer_slashings_ssz(
+        &self,
+    ) -> Result<Vec<ProposerSlashing>, Error> {
+        let path = self.get_beacon_pool_proposer_slashings_path()?;
+        self.get_ssz_opt(path)
+            .await?
+            .ok_or(Error::StatusCode(StatusCode::NOT_FOUND))
+    }
+
     /// `POST beacon/pool/voluntary_exits`
     pub async fn post_beacon_pool_voluntary_exits(
         &self,
@@ -1480,21 +2041,35 @@ impl BeaconNodeHttpClient {
         Ok(())
     }
 
-    /// `GET beacon/pool/voluntary_exits`
-    pub async fn get_beacon_pool_voluntary_exits(
-        &self,
-    ) -> Result<GenericResponse<Vec<SignedVoluntaryExit>>, Error> {
+    /// Path for `v1/beacon/pool/voluntary_exits`
+    fn get_beacon_pool_voluntary_exits_path(&self) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
-
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("pool")
             .push("voluntary_exits");
+        Ok(path)
+    }
 
+    /// `GET beacon/pool/voluntary_exits`
+    pub async fn get_beacon_pool_voluntary_exits(
+        &self,
+    ) -> Result<GenericResponse<Vec<SignedVoluntaryExit>>, Error> {
+        let path = self.get_beacon_pool_voluntary_exits_path()?;
         self.get(path).await
     }
 
+    /// `GET beacon/pool/voluntary_exits` as SSZ
+    pub async fn get_beacon_pool_voluntary_exits_ssz(
+        &self,
+    ) -> Result<Vec<SignedVoluntaryExit>, Error> {
+        let path = self.get_beacon_pool_voluntary_exits_path()?;
+        self.get_ssz_opt(path)
+            .await?
+            .ok_or(Error::StatusCode(StatusCode::NOT_FOUND))
+    }
+
     /// `POST beacon/pool/sync_committees`
     pub async fn post_beacon_pool_sync_committee_signatures(
         &self,
@@ -1513,6 +2088,35 @@ impl BeaconNodeHttpClient {
         Ok(())
     }
 
+    /// Path for `v1/beacon/pool/bls_to_execution_changes`
+    fn get_beacon_pool_bls_to_execution_changes_path(&self) -> Result<Url, Error> {
+        let mut path = self.eth_path(V1)?;
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("pool")
+            .push("bls_to_execution_changes");
+        Ok(path)
+    }
+
+    /// `GET beacon/pool/bls_to_execution_changes`
+    pub async fn get_beacon_pool_bls_to_execution_changes(
+        &self,
+    ) -> Result<GenericResponse<Vec<SignedBlsToExecutionChange>>, Error> {
+        let path = self.get_beacon_pool_bls_to_execution_changes_path()?;
+        self.get(path).await
+    }
+
+    /// `GET beacon/pool/bls_to_execution_changes` as SSZ
+    pub async fn get_beacon_pool_bls_to_execution_changes_ssz(
+        &self,
+    ) -> Result<Vec<SignedBlsToExecutionChange>, Error> {
+        let path = self.get_beacon_pool_bls_to_execution_changes_path()?;
+        self.get_ssz_opt(path)
+            .await?
+            .ok_or(Error::StatusCode(StatusCode::NOT_FOUND))
+    }
+
     /// `POST beacon/pool/bls_to_execution_changes`
     pub async fn post_beacon_pool_bls_to_execution_changes(
         &self,
@@ -1543,56 +2147,82 @@ impl BeaconNodeHttpClient {
             .map(|opt| opt.map(|r| r.data))
     }
 
-    /// `POST beacon/rewards/sync_committee`
+    /// `POST beacon/rewards/sync_committee/{block_id}`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
     pub async fn post_beacon_rewards_sync_committee(
         &self,
-        rewards: &[Option<Vec<lighthouse::SyncCommitteeReward>>],
-    ) -> Result<(), Error> {
+        block_id: BlockId,
+        validators: &[ValidatorId],
+    ) -> Result<
+        Option<ExecutionOptimisticFinalizedResponse<Vec<lighthouse::SyncCommitteeReward>>>,
+        Error,
+    > {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("rewards")
-            .push("sync_committee");
-
-        self.post(path, &rewards).await?;
+            .push("sync_committee")
+            .push(&block_id.to_string());
 
-        Ok(())
+        self.post_with_opt_response(path, &validators).await
     }
 
-    /// `GET beacon/rewards/blocks`
-    pub async fn get_beacon_rewards_blocks(&self, epoch: Epoch) -> Result<(), Error> {
+    /// `GET beacon/rewards/blocks/{block_id}`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_rewards_blocks(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<lighthouse::StandardBlockReward>>, Error>
+    {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("rewards")
-            .push("blocks");
+            .push("blocks")
+            .push(&block_id.to_string());
 
-        path.query_pairs_mut()
-            .append_pair("epoch", &epoch.to_string());
+        self.get_opt(path).await
+    }
 
-        self.get(path).await
+    /// TESTING ONLY: This request should fail with a 406 response code.
+    pub async fn get_beacon_rewards_blocks_with_ssz_accept_header(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Response, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("rewards")
+            .push("blocks")
+            .push(&block_id.to_string());
+
+        self.get_response(path, |b| b.accept(Accept::Ssz)).await
     }
 
-    /// `POST beacon/rewards/attestations`
+    /// `POST beacon/rewards/attestations/{epoch}`
     pub async fn post_beacon_rewards_attestations(
         &self,
-        attestations: &[ValidatorId],
-    ) -> Result<(), Error> {
+        epoch: Epoch,
+        validators: &[ValidatorId],
+    ) -> Result<ExecutionOptimisticResponse<lighthouse::StandardAttestationRewards>, Error> {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("rewards")
-            .push("attestations");
-
-        self.post(path, &attestations).await?;
+            .push("attestations")
+            .push(&epoch.to_string());
 
-        Ok(())
+        self.post_with_response(path, &validators).await
     }
 
     // GET builder/states/{state_id}/expected_withdrawals
@@ -2613,3 +3243,44 @@ pub async fn ok_or_error(response: Response) -> Result<Response, Error> {
         Err(Error::StatusCode(status))
     }
 }
+
+/// Reads the `Eth-Execution-Optimistic` and `Eth-Finalized` headers which carry the metadata of an
+/// SSZ response. An absent header is `None`, an unparseable one is an error.
+fn execution_optimistic_finalized_from_headers(
+    headers: &HeaderMap,
+) -> Result<(Option<bool>, Option<bool>), Error> {
+    let parse = |name: &str| {
+        headers
+            .get(name)
+            .map(|value| {
+                value
+                    .to_str()
+                    .ok()
+                    .and_then(|value| value.parse::<bool>().ok())
+                    .ok_or_else(|| Error::InvalidHeaders(format!("invalid {name}")))
+            })
+            .transpose()
+    };
+    Ok((
+        parse(EXECUTION_OPTIMISTIC_HEADER)?,
+        parse(FINALIZED_HEADER)?,
+    ))
+}
+
+/// Decodes the body of an SSZ response along with the metadata in its headers.
+async fn ssz_response_with_metadata<T>(
+    response: Response,
+) -> Result<ExecutionOptimisticFinalizedResponse<T>, Error>
+where
+    T: Decode + Serialize + DeserializeOwned,
+{
+    let (execution_optimistic, finalized) =
+        execution_optimistic_finalized_from_headers(response.headers())?;
+    let data = T::from_ssz_bytes(&response.bytes().await?).map_err(Error::InvalidSsz)?;
+
+    Ok(ExecutionOptimisticFinalizedResponse {
+        execution_optimistic,
+        finalized,
+        data,
+    })
+}
@@ -4,12 +4,15 @@ mod attestation_performance;
 pub mod attestation_rewards;
 mod block_packing_efficiency;
 mod block_rewards;
+mod simulate_block;
 mod standard_block_rewards;
 mod sync_committee_rewards;
+mod validator_history;
 
 use crate::{
     types::{
-        DepositTreeSnapshot, Epoch, EthSpec, FinalizedExecutionBlock, GenericResponse, ValidatorId,
+        DepositTreeSnapshot, Epoch, EthSpec, FinalizedExecutionBlock, ForkName, GenericResponse,
+        ValidatorId,
     },
     BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, Slot,
 };
@@ -28,8 +31,15 @@ pub use block_packing_efficiency::{
 };
 pub use block_rewards::{AttestationRewards, BlockReward, BlockRewardMeta, BlockRewardsQuery};
 pub use lighthouse_network::{types::SyncState, PeerInfo};
+pub use simulate_block::{
+    SimulateBlockQuery, SimulatedBlock, SimulatedOperation, SimulatedOperationError,
+    SimulatedValidatorChange,
+};
 pub use standard_block_rewards::StandardBlockReward;
 pub use sync_committee_rewards::SyncCommitteeReward;
+pub use validator_history::{
+    ValidatorHistory, ValidatorHistoryQuery, ValidatorHistorySample, MAX_VALIDATOR_HISTORY_EPOCHS,
+};
 
 // Define "legacy" implementations of `Option<T>` which use four bytes for encoding the union
 // selector.
@@ -574,6 +584,65 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `POST lighthouse/analysis/simulate_block?state_id,unsigned,skip_signature_verification,verify_payload`
+    ///
+    /// `block_ssz` is the SSZ encoding of a `SignedBeaconBlock`, or of a `BeaconBlock` if
+    /// `query.unsigned` is set.
+    pub async fn post_lighthouse_analysis_simulate_block(
+        &self,
+        block_ssz: Vec<u8>,
+        fork_name: ForkName,
+        query: &SimulateBlockQuery,
+    ) -> Result<SimulatedBlock, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("analysis")
+            .push("simulate_block");
+
+        path.query_pairs_mut()
+            .append_pair("state_id", &query.state_id)
+            .append_pair("unsigned", &query.unsigned.to_string())
+            .append_pair(
+                "skip_signature_verification",
+                &query.skip_signature_verification.to_string(),
+            )
+            .append_pair("verify_payload", &query.verify_payload.to_string());
+
+        self.post_generic_with_consensus_version_and_ssz_body(path, block_ssz, None, fork_name)
+            .await?
+            .json()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// `GET lighthouse/validators/{validator_id}/history?start_epoch,end_epoch`
+    ///
+    /// At most `MAX_VALIDATOR_HISTORY_EPOCHS` epochs may be requested at once.
+    pub async fn get_lighthouse_validator_history(
+        &self,
+        validator_id: ValidatorId,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+    ) -> Result<GenericResponse<ValidatorHistory>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push(&validator_id.to_string())
+            .push("history");
+
+        path.query_pairs_mut()
+            .append_pair("start_epoch", &start_epoch.to_string())
+            .append_pair("end_epoch", &end_epoch.to_string());
+
+        self.get(path).await
+    }
+
     /// `GET` lighthouse/analysis/attestation_performance/{index}?start_epoch,end_epoch
     pub async fn get_lighthouse_analysis_attestation_performance(
         &self,
@@ -0,0 +1,94 @@
+use crate::lighthouse::BlockReward;
+use serde::{Deserialize, Serialize};
+use types::{Hash256, Slot, Validator};
+
+/// Query parameters for the `/lighthouse/analysis/simulate_block` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SimulateBlockQuery {
+    /// The state to which the block is applied, advanced to the slot of the block if necessary.
+    pub state_id: String,
+    /// Whether the request body is a `BeaconBlock` rather than a `SignedBeaconBlock`.
+    #[serde(default)]
+    pub unsigned: bool,
+    /// Skip verification of all signatures in the block.
+    #[serde(default)]
+    pub skip_signature_verification: bool,
+    /// Verify the block hash of the execution payload and the versioned hashes of its blob
+    /// transactions. The payload is not sent to the execution engine.
+    #[serde(default)]
+    pub verify_payload: bool,
+}
+
+/// A step of block processing which may fail independently of the others.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulatedOperation {
+    /// The block as a whole, reported when it is invalid but no individual step fails.
+    Block,
+    BlockHeader,
+    ProposalSignature,
+    Withdrawals,
+    ExecutionPayload,
+    PayloadHashes,
+    Randao,
+    Eth1Data,
+    ProposerSlashing,
+    AttesterSlashing,
+    Attestation,
+    Deposits,
+    VoluntaryExit,
+    BlsToExecutionChange,
+    WithdrawalRequests,
+    DepositRequests,
+    Consolidations,
+    SyncAggregate,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SimulatedOperationError {
+    pub operation: SimulatedOperation,
+    /// The index of the operation within its list in the block body, if applicable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+    pub reason: String,
+}
+
+/// A validator whose record or balance was modified by the block.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SimulatedValidatorChange {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub index: u64,
+    /// `None` if the validator was added to the registry by the block.
+    pub pre_validator: Option<Validator>,
+    pub post_validator: Validator,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub pre_balance: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub post_balance: u64,
+}
+
+/// The outcome of applying a block to a state without importing it.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SimulatedBlock {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    /// `true` if the block passes `per_block_processing` and payload verification, if requested.
+    ///
+    /// The state root in the block is not considered, see `state_root_matches`.
+    pub valid: bool,
+    pub errors: Vec<SimulatedOperationError>,
+    /// The root of the post-state of the block, or `None` if the block is invalid.
+    pub post_state_root: Option<Hash256>,
+    /// Whether the state root in the block equals `post_state_root`.
+    pub state_root_matches: bool,
+    /// Whether the block hash and versioned hashes of the execution payload are valid, or `None`
+    /// if payload verification was not requested or the block has no execution payload.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload_verified: Option<bool>,
+    /// The proposer reward of the block, or `None` if it could not be computed.
+    pub rewards: Option<BlockReward>,
+    /// Validators modified by the block, excluding changes made by slot processing prior to it.
+    ///
+    /// For an invalid block, these are the changes made by the steps which did not fail.
+    pub changed_validators: Vec<SimulatedValidatorChange>,
+}
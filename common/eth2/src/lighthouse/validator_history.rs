@@ -0,0 +1,36 @@
+use crate::types::ValidatorStatus;
+use serde::{Deserialize, Serialize};
+use types::Epoch;
+
+/// The maximum number of epochs which may be sampled by a single request.
+///
+/// The response is built in full rather than streamed, so this bounds both the number of blocks
+/// the beacon node replays and the size of the response. Longer ranges must be split into several
+/// requests.
+pub const MAX_VALIDATOR_HISTORY_EPOCHS: u64 = 8192;
+
+/// The balances and status of a validator in the state at the first slot of `epoch`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ValidatorHistorySample {
+    pub epoch: Epoch,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub balance: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub effective_balance: u64,
+    pub status: ValidatorStatus,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ValidatorHistory {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub index: u64,
+    /// Samples in ascending order of epoch, omitting epochs prior to the validator's deposit.
+    pub samples: Vec<ValidatorHistorySample>,
+}
+
+/// Query parameters for the `/lighthouse/validators/{validator_id}/history` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ValidatorHistoryQuery {
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+}
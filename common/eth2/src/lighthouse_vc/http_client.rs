@@ -1,6 +1,7 @@
 use super::types::*;
 use crate::Error;
 use account_utils::ZeroizeString;
+use futures::{Stream, StreamExt};
 use reqwest::{
     header::{HeaderMap, HeaderValue},
     IntoUrl,
@@ -305,6 +306,21 @@ impl ValidatorClientHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/beacon/health`
+    pub async fn get_lighthouse_beacon_health(
+        &self,
+    ) -> Result<GenericResponse<BeaconNodesHealthResponse>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon")
+            .push("health");
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/spec`
     pub async fn get_lighthouse_spec<T: Serialize + DeserializeOwned>(
         &self,
@@ -349,6 +365,97 @@ impl ValidatorClientHttpClient {
         self.get_opt(path).await
     }
 
+    /// `GET lighthouse/validators/{validator_pubkey}/duties`
+    pub async fn get_lighthouse_validators_duties(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+    ) -> Result<GenericResponse<Vec<DutyOutcome>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push(&validator_pubkey.to_string())
+            .push("duties");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/events?topics`
+    pub async fn get_lighthouse_events(
+        &self,
+        topics: &[EventTopic],
+    ) -> Result<impl Stream<Item = Result<EventKind, Error>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("events");
+
+        let topic_string = topics
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        path.query_pairs_mut().append_pair("topics", &topic_string);
+
+        Ok(self
+            .get_response(path)
+            .await?
+            .bytes_stream()
+            .map(|next| match next {
+                Ok(bytes) => EventKind::from_sse_bytes(bytes.as_ref()),
+                Err(e) => Err(Error::HttpClient(e.into())),
+            }))
+    }
+
+    /// `GET lighthouse/exit_escrow`
+    pub async fn get_lighthouse_exit_escrow(
+        &self,
+    ) -> Result<GenericResponse<Vec<EscrowedExitData>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("exit_escrow");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/exit_escrow`
+    pub async fn post_lighthouse_exit_escrow(
+        &self,
+        request: &ExitEscrowRequest,
+    ) -> Result<GenericResponse<Vec<EscrowedExitData>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("exit_escrow");
+
+        self.post(path, &request).await
+    }
+
+    /// `POST lighthouse/exit_escrow/export`
+    pub async fn post_lighthouse_exit_escrow_export(
+        &self,
+        request: &ExitEscrowExportRequest,
+    ) -> Result<GenericResponse<Vec<EncryptedExitBundle>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("exit_escrow")
+            .push("export");
+
+        self.post(path, &request).await
+    }
+
     /// `POST lighthouse/validators`
     pub async fn post_lighthouse_validators(
         &self,
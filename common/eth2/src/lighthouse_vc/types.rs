@@ -1,8 +1,12 @@
+use crate::types::query_vec;
+use crate::Error as ServerError;
 use account_utils::ZeroizeString;
 use eth2_keystore::Keystore;
 use graffiti::GraffitiString;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt;
 use std::path::PathBuf;
+use std::str::{from_utf8, FromStr};
 
 pub use crate::lighthouse::Health;
 pub use crate::lighthouse_vc::std_types::*;
@@ -14,6 +18,16 @@ pub struct ValidatorData {
     pub enabled: bool,
     pub description: String,
     pub voting_pubkey: PublicKeyBytes,
+    /// The Web3Signer instances which hold the key of an enabled remote signer validator.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub web3signers: Vec<Web3SignerHealth>,
+}
+
+/// The health of a Web3Signer instance, as last observed by the validator client.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Web3SignerHealth {
+    pub url: String,
+    pub healthy: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -144,6 +158,9 @@ pub struct Web3SignerValidatorRequest {
     pub voting_public_key: PublicKey,
     pub url: String,
     #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fallback_urls: Vec<String>,
+    #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub root_certificate_path: Option<PathBuf>,
     #[serde(default)]
@@ -197,3 +214,337 @@ pub struct SingleExportKeystoresResponse {
 pub struct SetGraffitiRequest {
     pub graffiti: GraffitiString,
 }
+
+/// The health tier of a beacon node used by the validator client, from healthiest to least
+/// healthy. Nodes in a healthier tier are always preferred, regardless of score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthTier {
+    /// Synced to the head, not optimistic and with an online execution layer.
+    Healthy,
+    /// Slightly behind the head, or optimistically synced.
+    Degraded,
+    /// Far behind the head, or with an offline execution layer.
+    Unsynced,
+    /// Offline, incompatible or yet to be queried.
+    Unavailable,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeaconNodeHealthData {
+    /// The position of the beacon node in the configured list of beacon nodes.
+    pub index: usize,
+    pub endpoint: String,
+    pub tier: HealthTier,
+    /// A score out of 100 which orders beacon nodes within the same tier, higher is healthier.
+    pub score: f64,
+    pub head_slot: Option<Slot>,
+    pub sync_distance: Option<Slot>,
+    pub is_optimistic: Option<bool>,
+    pub el_offline: Option<bool>,
+    /// Moving average of the round-trip latency to the beacon node, in milliseconds.
+    pub latency_ms: Option<u64>,
+    /// Moving average of the proportion of requests to the beacon node which failed.
+    pub error_rate: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeaconNodesHealthResponse {
+    /// All configured beacon nodes, ordered from the healthiest (which is tried first) to the
+    /// least healthy.
+    pub beacon_nodes: Vec<BeaconNodeHealthData>,
+}
+
+/// A duty performed by a validator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DutyKind {
+    Attestation,
+    Aggregate,
+    BlockProposal,
+    SyncCommitteeMessage,
+    SyncCommitteeContribution,
+}
+
+/// What happened when the validator client attempted a duty.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DutyOutcome {
+    pub duty: DutyKind,
+    pub slot: Slot,
+    pub signed: bool,
+    pub published: bool,
+    /// Whether the message was included on chain, or `None` if inclusion has not been checked.
+    ///
+    /// Inclusion is only checked for published attestations, block proposals and sync committee
+    /// messages.
+    pub included: Option<bool>,
+    /// The root of the signed block, for block proposals.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_root: Option<Hash256>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExitEscrowRequest {
+    /// The epoch at which the validators may exit.
+    pub epoch: Epoch,
+    /// The compressed secp256k1 public key to which the exits are encrypted when they are signed.
+    #[serde(with = "serde_utils::hex_vec")]
+    pub recipient: Vec<u8>,
+    /// The validators to sign exits for, or all enabled validators if `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pubkeys: Option<Vec<PublicKeyBytes>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExitEscrowExportRequest {
+    /// The compressed secp256k1 public key to which the exits were escrowed.
+    #[serde(with = "serde_utils::hex_vec")]
+    pub recipient: Vec<u8>,
+    /// The validators whose exits are exported, or all exits escrowed to `recipient` if `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pubkeys: Option<Vec<PublicKeyBytes>>,
+}
+
+/// A record of an escrowed exit being exported.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExitHandOut {
+    #[serde(with = "serde_utils::hex_vec")]
+    pub recipient: Vec<u8>,
+    /// The time of the export, in seconds since the UNIX epoch.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EscrowedExitData {
+    pub validator_pubkey: PublicKeyBytes,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    pub epoch: Epoch,
+    /// The compressed secp256k1 public key to which the exit is encrypted.
+    #[serde(with = "serde_utils::hex_vec")]
+    pub recipient: Vec<u8>,
+    /// The time the exit was signed, in seconds since the UNIX epoch.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub created: u64,
+    pub handed_out: Vec<ExitHandOut>,
+    /// Whether the exit is still signed over the domain which the validator client would use
+    /// to sign it today.
+    pub valid: bool,
+}
+
+/// Signed voluntary exits encrypted to a recipient's secp256k1 public key.
+///
+/// The plaintext is a JSON list of `SignedVoluntaryExit`, encrypted with AES-256-GCM under a key
+/// derived with HKDF-SHA256 from an ECDH exchange between `ephemeral_pubkey` and `recipient`.
+/// Escrowed exits are each encrypted to a bundle of their own.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncryptedExitBundle {
+    #[serde(with = "serde_utils::hex_vec")]
+    pub recipient: Vec<u8>,
+    #[serde(with = "serde_utils::hex_vec")]
+    pub ephemeral_pubkey: Vec<u8>,
+    #[serde(with = "serde_utils::hex_vec")]
+    pub nonce: Vec<u8>,
+    #[serde(with = "serde_utils::hex_vec")]
+    pub ciphertext: Vec<u8>,
+}
+
+/// A message signed by the validator client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignedMessageKind {
+    RandaoReveal,
+    Block,
+    Attestation,
+    AggregateAndProof,
+    SyncCommitteeMessage,
+    ContributionAndProof,
+    VoluntaryExit,
+    ValidatorRegistration,
+}
+
+/// Where the execution payload of a block was sourced from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockSource {
+    Builder,
+    Local,
+}
+
+/// The status of a validator with regards to doppelganger protection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DoppelgangerProtectionStatus {
+    /// The validator is waiting to detect other instances of its key on the network.
+    Detecting,
+    /// Doppelganger protection permits the validator to sign.
+    SigningEnabled,
+    /// Another instance of the validator was detected and it will no longer sign.
+    DoppelgangerDetected,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SseDutyScheduled {
+    pub validator_pubkey: PublicKeyBytes,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    pub duty: DutyKind,
+    pub slot: Slot,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SseSignature {
+    pub validator_pubkey: PublicKeyBytes,
+    pub message: SignedMessageKind,
+    /// The slot of the signed message, if it has one.
+    pub slot: Option<Slot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SseBlockProduced {
+    pub validator_pubkey: PublicKeyBytes,
+    pub slot: Slot,
+    pub source: BlockSource,
+    #[serde(with = "serde_utils::u256_dec")]
+    pub execution_payload_value: Uint256,
+    #[serde(with = "serde_utils::u256_dec")]
+    pub consensus_block_value: Uint256,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SseBlockPublished {
+    pub validator_pubkey: PublicKeyBytes,
+    pub slot: Slot,
+    pub block_root: Hash256,
+    pub source: BlockSource,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SseDoppelganger {
+    pub validator_pubkey: PublicKeyBytes,
+    pub status: DoppelgangerProtectionStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SseBeaconNodeFallback {
+    /// The beacon node which served the request.
+    pub beacon_node: String,
+    /// The beacon nodes which failed the request before it was served.
+    pub failed_beacon_nodes: Vec<String>,
+}
+
+/// An event published on `GET lighthouse/events`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum EventKind {
+    DutyScheduled(SseDutyScheduled),
+    Signature(SseSignature),
+    BlockProduced(SseBlockProduced),
+    BlockPublished(SseBlockPublished),
+    Doppelganger(SseDoppelganger),
+    BeaconNodeFallback(SseBeaconNodeFallback),
+}
+
+impl EventKind {
+    pub fn topic(&self) -> EventTopic {
+        match self {
+            EventKind::DutyScheduled(_) => EventTopic::DutyScheduled,
+            EventKind::Signature(_) => EventTopic::Signature,
+            EventKind::BlockProduced(_) => EventTopic::BlockProduced,
+            EventKind::BlockPublished(_) => EventTopic::BlockPublished,
+            EventKind::Doppelganger(_) => EventTopic::Doppelganger,
+            EventKind::BeaconNodeFallback(_) => EventTopic::BeaconNodeFallback,
+        }
+    }
+
+    pub fn from_sse_bytes(message: &[u8]) -> Result<Self, ServerError> {
+        let s = from_utf8(message)
+            .map_err(|e| ServerError::InvalidServerSentEvent(format!("{:?}", e)))?;
+
+        let mut split = s.split('\n');
+        let event = split
+            .next()
+            .ok_or_else(|| {
+                ServerError::InvalidServerSentEvent("Could not parse event tag".to_string())
+            })?
+            .trim_start_matches("event:");
+        let data = split
+            .next()
+            .ok_or_else(|| {
+                ServerError::InvalidServerSentEvent("Could not parse data tag".to_string())
+            })?
+            .trim_start_matches("data:");
+
+        fn parse<T: DeserializeOwned>(topic: EventTopic, data: &str) -> Result<T, ServerError> {
+            serde_json::from_str(data)
+                .map_err(|e| ServerError::InvalidServerSentEvent(format!("{}: {:?}", topic, e)))
+        }
+
+        match EventTopic::from_str(event).map_err(ServerError::InvalidServerSentEvent)? {
+            topic @ EventTopic::DutyScheduled => parse(topic, data).map(EventKind::DutyScheduled),
+            topic @ EventTopic::Signature => parse(topic, data).map(EventKind::Signature),
+            topic @ EventTopic::BlockProduced => parse(topic, data).map(EventKind::BlockProduced),
+            topic @ EventTopic::BlockPublished => parse(topic, data).map(EventKind::BlockPublished),
+            topic @ EventTopic::Doppelganger => parse(topic, data).map(EventKind::Doppelganger),
+            topic @ EventTopic::BeaconNodeFallback => {
+                parse(topic, data).map(EventKind::BeaconNodeFallback)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventQuery {
+    #[serde(deserialize_with = "query_vec")]
+    pub topics: Vec<EventTopic>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventTopic {
+    /// A duty was scheduled for a local validator.
+    DutyScheduled,
+    /// A message was signed by a local validator.
+    Signature,
+    /// A block was produced for a local validator, with a payload from a builder or the local
+    /// execution node.
+    BlockProduced,
+    /// A block proposed by a local validator was published.
+    BlockPublished,
+    /// The doppelganger protection status of a local validator changed.
+    Doppelganger,
+    /// A request was served by a beacon node after others failed it.
+    BeaconNodeFallback,
+}
+
+impl FromStr for EventTopic {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "duty_scheduled" => Ok(EventTopic::DutyScheduled),
+            "signature" => Ok(EventTopic::Signature),
+            "block_produced" => Ok(EventTopic::BlockProduced),
+            "block_published" => Ok(EventTopic::BlockPublished),
+            "doppelganger" => Ok(EventTopic::Doppelganger),
+            "beacon_node_fallback" => Ok(EventTopic::BeaconNodeFallback),
+            _ => Err("event topic cannot be parsed.".to_string()),
+        }
+    }
+}
+
+impl fmt::Display for EventTopic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventTopic::DutyScheduled => write!(f, "duty_scheduled"),
+            EventTopic::Signature => write!(f, "signature"),
+            EventTopic::BlockProduced => write!(f, "block_produced"),
+            EventTopic::BlockPublished => write!(f, "block_published"),
+            EventTopic::Doppelganger => write!(f, "doppelganger"),
+            EventTopic::BeaconNodeFallback => write!(f, "beacon_node_fallback"),
+        }
+    }
+}
@@ -87,7 +87,7 @@ impl std::fmt::Display for EndpointVersion {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
 pub struct GenesisData {
     #[serde(with = "serde_utils::quoted_u64")]
     pub genesis_time: u64,
@@ -263,7 +263,7 @@ impl<'a, T: Serialize> From<&'a T> for GenericResponseRef<'a, T> {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Encode, Decode)]
 pub struct RootData {
     pub root: Hash256,
 }
@@ -274,7 +274,7 @@ impl From<Hash256> for RootData {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
 pub struct FinalityCheckpointsData {
     pub previous_justified: Checkpoint,
     pub current_justified: Checkpoint,
@@ -328,7 +328,7 @@ impl From<ValidatorId> for String {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
 pub struct ValidatorData {
     #[serde(with = "serde_utils::quoted_u64")]
     pub index: u64,
@@ -338,7 +338,7 @@ pub struct ValidatorData {
     pub validator: Validator,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
 pub struct ValidatorBalanceData {
     #[serde(with = "serde_utils::quoted_u64")]
     pub index: u64,
@@ -354,8 +354,12 @@ pub struct ValidatorBalanceData {
 // this proposal:
 //
 // https://hackmd.io/bQxMDRt1RbS1TLno8K4NPg?view
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+//
+// The SSZ encoding is a single byte holding the index of the variant, so new variants must only
+// ever be appended.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Encode, Decode)]
 #[serde(rename_all = "snake_case")]
+#[ssz(enum_behaviour = "tag")]
 pub enum ValidatorStatus {
     PendingInitialized,
     PendingQueued,
@@ -512,7 +516,7 @@ pub struct ValidatorsRequestBody {
     pub statuses: Option<Vec<ValidatorStatus>>,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
 pub struct CommitteeData {
     #[serde(with = "serde_utils::quoted_u64")]
     pub index: u64,
@@ -521,20 +525,21 @@ pub struct CommitteeData {
     pub validators: Vec<u64>,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
 pub struct SyncCommitteeByValidatorIndices {
     #[serde(with = "serde_utils::quoted_u64_vec")]
     pub validators: Vec<u64>,
     pub validator_aggregates: Vec<SyncSubcommittee>,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
 pub struct RandaoMix {
     pub randao: Hash256,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
 #[serde(transparent)]
+#[ssz(struct_behaviour = "transparent")]
 pub struct SyncSubcommittee {
     #[serde(with = "serde_utils::quoted_u64_vec")]
     pub indices: Vec<u64>,
@@ -546,13 +551,13 @@ pub struct HeadersQuery {
     pub parent_root: Option<Hash256>,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
 pub struct BlockHeaderAndSignature {
     pub message: BeaconBlockHeader,
     pub signature: SignatureBytes,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
 pub struct BlockHeaderData {
     pub root: Hash256,
     pub canonical: bool,
@@ -605,6 +610,12 @@ pub struct SyncingData {
     pub sync_distance: Slot,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct LightClientUpdatesQuery {
+    pub start_period: u64,
+    pub count: u64,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct ExpectedWithdrawalsQuery {
     pub proposal_slot: Option<Slot>,
@@ -616,7 +627,7 @@ pub struct QueryVec<T: FromStr> {
     values: Vec<T>,
 }
 
-fn query_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+pub(crate) fn query_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
 where
     D: serde::Deserializer<'de>,
     T: FromStr,
@@ -689,6 +700,17 @@ pub struct BlobIndicesQuery {
     pub indices: Option<Vec<u64>>,
 }
 
+/// Selects the nodes of a Merkle multiproof, by generalized index or by SSZ path, e.g.
+/// `validators.0.effective_balance`.
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProofQuery {
+    #[serde(default, deserialize_with = "option_query_vec")]
+    pub gindices: Option<Vec<u64>>,
+    #[serde(default, deserialize_with = "option_query_vec")]
+    pub paths: Option<Vec<SszPath>>,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct ValidatorIndexData(#[serde(with = "serde_utils::quoted_u64_vec")] pub Vec<u64>);
@@ -1862,6 +1884,46 @@ impl<E: EthSpec> PublishBlockRequest<E> {
     }
 }
 
+/// Decode the SSZ encoding of a list of attestations, all of which belong to `fork_name`.
+///
+/// The beacon node only serves the attestations of a single fork in one response (the one named
+/// in `Eth-Consensus-Version`), so a pool holding both formats at the fork boundary can still be
+/// decoded.
+pub fn attestations_from_ssz_bytes_for_fork<E: EthSpec>(
+    bytes: &[u8],
+    fork_name: ForkName,
+) -> Result<Vec<Attestation<E>>, DecodeError> {
+    if fork_name.electra_enabled() {
+        Ok(Vec::<AttestationElectra<E>>::from_ssz_bytes(bytes)?
+            .into_iter()
+            .map(Attestation::Electra)
+            .collect())
+    } else {
+        Ok(Vec::<AttestationBase<E>>::from_ssz_bytes(bytes)?
+            .into_iter()
+            .map(Attestation::Base)
+            .collect())
+    }
+}
+
+/// Decode the SSZ encoding of a list of attester slashings, all of which belong to `fork_name`.
+pub fn attester_slashings_from_ssz_bytes_for_fork<E: EthSpec>(
+    bytes: &[u8],
+    fork_name: ForkName,
+) -> Result<Vec<AttesterSlashing<E>>, DecodeError> {
+    if fork_name.electra_enabled() {
+        Ok(Vec::<AttesterSlashingElectra<E>>::from_ssz_bytes(bytes)?
+            .into_iter()
+            .map(AttesterSlashing::Electra)
+            .collect())
+    } else {
+        Ok(Vec::<AttesterSlashingBase<E>>::from_ssz_bytes(bytes)?
+            .into_iter()
+            .map(AttesterSlashing::Base)
+            .collect())
+    }
+}
+
 /// Converting from a `SignedBlindedBeaconBlock` into a full `SignedBlockContents`.
 pub fn into_full_block_and_blobs<E: EthSpec>(
     blinded_block: SignedBlindedBeaconBlock<E>,
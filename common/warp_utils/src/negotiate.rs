@@ -0,0 +1,107 @@
+//! Content negotiation for endpoints which may respond with either JSON or SSZ.
+
+use crate::reject::{custom_server_error, not_acceptable};
+use eth2::types::{
+    Accept, ExecutionOptimisticFinalizedResponse, ExecutionOptimisticResponse, GenericResponse,
+};
+use eth2::{
+    CONTENT_TYPE_HEADER, EXECUTION_OPTIMISTIC_HEADER, FINALIZED_HEADER, SSZ_CONTENT_TYPE_HEADER,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use ssz::Encode;
+use types::fork_versioned_response::{EmptyMetadata, ExecutionOptimisticFinalizedMetadata};
+use types::ForkVersionedResponse;
+use warp::{
+    http::StatusCode,
+    reply::{self, Response},
+    Rejection, Reply,
+};
+
+/// The metadata fields of a JSON response envelope, which are sent as headers alongside an SSZ
+/// body.
+pub trait ResponseMetadata {
+    fn execution_optimistic(&self) -> Option<bool> {
+        None
+    }
+
+    fn finalized(&self) -> Option<bool> {
+        None
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> ResponseMetadata for GenericResponse<T> {}
+
+impl<T: Serialize + DeserializeOwned> ResponseMetadata for ExecutionOptimisticResponse<T> {
+    fn execution_optimistic(&self) -> Option<bool> {
+        self.execution_optimistic
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> ResponseMetadata for ExecutionOptimisticFinalizedResponse<T> {
+    fn execution_optimistic(&self) -> Option<bool> {
+        self.execution_optimistic
+    }
+
+    fn finalized(&self) -> Option<bool> {
+        self.finalized
+    }
+}
+
+impl<T> ResponseMetadata for ForkVersionedResponse<T, EmptyMetadata> {}
+
+impl<T> ResponseMetadata for ForkVersionedResponse<T, ExecutionOptimisticFinalizedMetadata> {
+    fn execution_optimistic(&self) -> Option<bool> {
+        self.metadata.execution_optimistic
+    }
+
+    fn finalized(&self) -> Option<bool> {
+        self.metadata.finalized
+    }
+}
+
+/// Respond with the SSZ encoding of `data` if the client requested `application/octet-stream`,
+/// otherwise with `json`.
+///
+/// The SSZ body contains only `data`. The `execution_optimistic` and `finalized` fields of the
+/// JSON envelope are sent in the `Eth-Execution-Optimistic` and `Eth-Finalized` headers instead.
+pub fn ssz_or_json_response<S: Encode, J: Serialize + ResponseMetadata>(
+    accept_header: Option<Accept>,
+    data: &S,
+    json: &J,
+) -> Result<Response, Rejection> {
+    match accept_header {
+        Some(Accept::Ssz) => {
+            let mut builder = warp::http::Response::builder()
+                .status(StatusCode::OK)
+                .header(CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER);
+            if let Some(execution_optimistic) = json.execution_optimistic() {
+                builder = builder.header(
+                    EXECUTION_OPTIMISTIC_HEADER,
+                    execution_optimistic.to_string(),
+                );
+            }
+            if let Some(finalized) = json.finalized() {
+                builder = builder.header(FINALIZED_HEADER, finalized.to_string());
+            }
+            builder
+                .body(data.as_ssz_bytes().into())
+                .map_err(|e| custom_server_error(format!("failed to create response: {}", e)))
+        }
+        _ => Ok(reply::json(json).into_response()),
+    }
+}
+
+/// Respond with `json`, for endpoints which have no standard SSZ encoding.
+///
+/// Requests which only accept `application/octet-stream` are rejected with a 406.
+pub fn json_only_response<J: Serialize>(
+    accept_header: Option<Accept>,
+    json: &J,
+) -> Result<Response, Rejection> {
+    match accept_header {
+        Some(Accept::Ssz) => Err(not_acceptable(
+            "this endpoint only responds with JSON".to_string(),
+        )),
+        _ => Ok(reply::json(json).into_response()),
+    }
+}
@@ -145,6 +145,15 @@ pub fn unsupported_media_type(msg: String) -> warp::reject::Rejection {
     warp::reject::custom(UnsupportedMediaType(msg))
 }
 
+#[derive(Debug)]
+pub struct NotAcceptable(pub String);
+
+impl Reject for NotAcceptable {}
+
+pub fn not_acceptable(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(NotAcceptable(msg))
+}
+
 #[derive(Debug)]
 pub struct IndexedBadRequestErrors {
     pub message: String,
@@ -182,6 +191,9 @@ pub async fn handle_rejection(err: warp::Rejection) -> Result<impl warp::Reply,
     } else if err.find::<crate::reject::UnsupportedMediaType>().is_some() {
         code = StatusCode::UNSUPPORTED_MEDIA_TYPE;
         message = "UNSUPPORTED_MEDIA_TYPE".to_string();
+    } else if let Some(e) = err.find::<crate::reject::NotAcceptable>() {
+        code = StatusCode::NOT_ACCEPTABLE;
+        message = format!("NOT_ACCEPTABLE: {}", e.0);
     } else if let Some(e) = err.find::<crate::reject::CustomDeserializeError>() {
         message = format!("BAD_REQUEST: body deserialize error: {}", e.0);
         code = StatusCode::BAD_REQUEST;
@@ -2557,6 +2557,32 @@ impl<E: EthSpec> BeaconState<E> {
     }
 }
 
+/// The fields of each state variant, excluding caches, are exposed so that proofs of arbitrary nodes
+/// can be computed. Pending mutations of the state's lists must be applied beforehand.
+impl<E: EthSpec> MerkleNode for BeaconState<E> {
+    fn merkle_root(&self) -> Hash256 {
+        match self {
+            BeaconState::Base(state) => state.merkle_root(),
+            BeaconState::Altair(state) => state.merkle_root(),
+            BeaconState::Bellatrix(state) => state.merkle_root(),
+            BeaconState::Capella(state) => state.merkle_root(),
+            BeaconState::Deneb(state) => state.merkle_root(),
+            BeaconState::Electra(state) => state.merkle_root(),
+        }
+    }
+
+    fn merkle_node(&self) -> NodeKind<'_> {
+        match self {
+            BeaconState::Base(state) => state.merkle_node(),
+            BeaconState::Altair(state) => state.merkle_node(),
+            BeaconState::Bellatrix(state) => state.merkle_node(),
+            BeaconState::Capella(state) => state.merkle_node(),
+            BeaconState::Deneb(state) => state.merkle_node(),
+            BeaconState::Electra(state) => state.merkle_node(),
+        }
+    }
+}
+
 impl From<RelativeEpochError> for Error {
     fn from(e: RelativeEpochError) -> Error {
         Error::RelativeEpochError(e)
@@ -426,6 +426,12 @@ impl ChainSpec {
         })
     }
 
+    /// Returns true if `EIP7594_FORK_EPOCH` is set and is not set to `FAR_FUTURE_EPOCH`.
+    pub fn is_peer_das_scheduled(&self) -> bool {
+        self.eip7594_fork_epoch
+            .map_or(false, |eip7594_fork_epoch| eip7594_fork_epoch != self.far_future_epoch)
+    }
+
     /// Returns a full `Fork` struct for a given epoch.
     pub fn fork_at_epoch(&self, epoch: Epoch) -> Fork {
         let current_fork_name = self.fork_name_at_epoch(epoch);
@@ -8,7 +8,7 @@ use crate::{BeaconStateError, BlobsList};
 use bls::Signature;
 use derivative::Derivative;
 use kzg::Kzg;
-use kzg::{Blob as KzgBlob, Cell as KzgCell, Error as KzgError};
+use kzg::{Blob as KzgBlob, CellRef as KzgCellRef, Error as KzgError};
 use kzg::{KzgCommitment, KzgProof};
 use merkle_proof::verify_merkle_proof;
 use rayon::prelude::*;
@@ -141,8 +141,7 @@ impl<E: EthSpec> DataColumnSidecar<E> {
                         .ok_or(DataColumnSidecarError::InconsistentArrayLength(format!(
                             "Missing blob cell at index {col}"
                         )))?;
-                let cell: Vec<u8> = cell.into_inner().into_iter().collect();
-                let cell = Cell::<E>::from(cell);
+                let cell = Cell::<E>::from(cell.to_vec());
 
                 let proof = blob_cell_proofs.get(col).ok_or(
                     DataColumnSidecarError::InconsistentArrayLength(format!(
@@ -206,7 +205,7 @@ impl<E: EthSpec> DataColumnSidecar<E> {
         let blob_cells_and_proofs_vec = (0..num_of_blobs)
             .into_par_iter()
             .map(|row_index| {
-                let mut cells: Vec<KzgCell> = vec![];
+                let mut cells: Vec<KzgCellRef> = vec![];
                 let mut cell_ids: Vec<u64> = vec![];
                 for data_column in data_columns {
                     let cell = data_column.column.get(row_index).ok_or(
@@ -218,15 +217,10 @@ impl<E: EthSpec> DataColumnSidecar<E> {
                     cells.push(ssz_cell_to_crypto_cell::<E>(cell)?);
                     cell_ids.push(data_column.index);
                 }
-                // recover_all_cells does not expect sorted
-                let all_cells = kzg.recover_all_cells(&cell_ids, &cells)?;
-                let blob = kzg.cells_to_blob(&all_cells)?;
-
-                // Note: This function computes all cells and proofs. According to Justin this is okay,
-                // computing a partial set may be more expensive and requires code paths that don't exist.
-                // Computing the blobs cells is technically unnecessary but very cheap. It's done here again
-                // for simplicity.
-                kzg.compute_cells_and_proofs(&blob)
+                // Note: This function recovers all cells and recomputes all proofs. According to
+                // Justin this is okay, computing a partial set may be more expensive and requires
+                // code paths that don't exist. `cell_ids` does not need to be sorted.
+                kzg.recover_cells_and_compute_kzg_proofs(&cell_ids, &cells)
             })
             .collect::<Result<Vec<_>, KzgError>>()?;
 
@@ -240,8 +234,7 @@ impl<E: EthSpec> DataColumnSidecar<E> {
                     .ok_or(KzgError::InconsistentArrayLength(format!(
                         "Missing blob cell at index {col}"
                     )))?;
-                let cell: Vec<u8> = cell.into_inner().into_iter().collect();
-                let cell = Cell::<E>::from(cell);
+                let cell = Cell::<E>::from(cell.to_vec());
 
                 let proof = blob_cell_proofs
                     .get(col)
@@ -387,8 +380,15 @@ impl From<SszError> for DataColumnSidecarError {
     }
 }
 
-/// Converts a cell ssz List object to an array to be used with the kzg
+/// Converts a cell ssz List object to an array reference to be used with the kzg
 /// crypto library.
-fn ssz_cell_to_crypto_cell<E: EthSpec>(cell: &Cell<E>) -> Result<KzgCell, KzgError> {
-    KzgCell::from_bytes(cell.as_ref()).map_err(Into::into)
+pub fn ssz_cell_to_crypto_cell<E: EthSpec>(cell: &Cell<E>) -> Result<KzgCellRef, KzgError> {
+    let cell_bytes: &[u8] = cell.as_ref();
+    cell_bytes.try_into().map_err(|_| {
+        KzgError::InconsistentArrayLength(format!(
+            "Cell has invalid length. Expected {} got {}",
+            kzg::BYTES_PER_CELL,
+            cell_bytes.len()
+        ))
+    })
 }
@@ -1,3 +1,4 @@
+use crate::merkle_multiproof::impl_container_merkle_node;
 use crate::test_utils::TestRandom;
 use crate::{BeaconState, EthSpec, Hash256};
 use compare_fields_derive::CompareFields;
@@ -40,3 +41,5 @@ impl HistoricalSummary {
         }
     }
 }
+
+impl_container_merkle_node!(HistoricalSummary, [block_summary_root, state_summary_root]);
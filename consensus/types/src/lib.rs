@@ -54,6 +54,7 @@ pub mod light_client_bootstrap;
 pub mod light_client_finality_update;
 pub mod light_client_optimistic_update;
 pub mod light_client_update;
+pub mod merkle_multiproof;
 pub mod pending_attestation;
 pub mod pending_balance_deposit;
 pub mod pending_consolidation;
@@ -204,6 +205,9 @@ pub use crate::light_client_update::{
     Error as LightClientError, LightClientUpdate, LightClientUpdateAltair,
     LightClientUpdateCapella, LightClientUpdateDeneb, LightClientUpdateElectra,
 };
+pub use crate::merkle_multiproof::{
+    Error as MultiproofError, MerkleNode, Multiproof, NodeKind, PathElement, SszPath,
+};
 pub use crate::participation_flags::ParticipationFlags;
 pub use crate::payload::{
     AbstractExecPayload, BlindedPayload, BlindedPayloadBellatrix, BlindedPayloadCapella,
@@ -1,22 +1,21 @@
 use super::{EthSpec, FixedVector, Hash256, Slot, SyncAggregate, SyncCommittee};
 use crate::light_client_header::LightClientHeaderElectra;
 use crate::{
-    beacon_state, test_utils::TestRandom, BeaconBlock, BeaconBlockHeader, BeaconState, ChainSpec,
-    ForkName, ForkVersionDeserialize, LightClientHeaderAltair, LightClientHeaderCapella,
-    LightClientHeaderDeneb, SignedBeaconBlock,
+    beacon_state, test_utils::TestRandom, ChainSpec, ForkName, ForkVersionDeserialize,
+    LightClientHeader, LightClientHeaderAltair, LightClientHeaderCapella, LightClientHeaderDeneb,
+    SignedBeaconBlock,
 };
 use derivative::Derivative;
 use safe_arith::ArithError;
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
-use ssz::Decode;
+use ssz::{Decode, Encode};
 use ssz_derive::Decode;
 use ssz_derive::Encode;
 use ssz_types::typenum::{U4, U5, U6};
 use std::sync::Arc;
 use superstruct::superstruct;
 use test_random_derive::TestRandom;
-use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
 
 pub const FINALIZED_ROOT_INDEX: usize = 105;
@@ -35,6 +34,9 @@ pub const CURRENT_SYNC_COMMITTEE_PROOF_LEN: usize = 5;
 pub const NEXT_SYNC_COMMITTEE_PROOF_LEN: usize = 5;
 pub const EXECUTION_PAYLOAD_PROOF_LEN: usize = 4;
 
+/// The maximum number of updates which can be requested with `LightClientUpdatesByRange`.
+pub const MAX_REQUEST_LIGHT_CLIENT_UPDATES: u64 = 128;
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Error {
     SszTypesError(ssz_types::Error),
@@ -152,117 +154,219 @@ impl<E: EthSpec> ForkVersionDeserialize for LightClientUpdate<E> {
 }
 
 impl<E: EthSpec> LightClientUpdate<E> {
+    /// Build an update from the parts cached at block import.
+    ///
+    /// The caller is responsible for checking that `finalized_block` is the block committed to by
+    /// `finality_branch`.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        beacon_state: BeaconState<E>,
-        block: BeaconBlock<E>,
-        attested_state: &mut BeaconState<E>,
+        sync_aggregate: &SyncAggregate<E>,
+        signature_slot: Slot,
+        next_sync_committee: Arc<SyncCommittee<E>>,
+        next_sync_committee_branch: FixedVector<Hash256, NextSyncCommitteeProofLen>,
+        finality_branch: FixedVector<Hash256, FinalizedRootProofLen>,
         attested_block: &SignedBeaconBlock<E>,
         finalized_block: &SignedBeaconBlock<E>,
         chain_spec: &ChainSpec,
     ) -> Result<Self, Error> {
-        let sync_aggregate = block.body().sync_aggregate()?;
         if sync_aggregate.num_set_bits() < chain_spec.min_sync_committee_participants as usize {
             return Err(Error::NotEnoughSyncCommitteeParticipants);
         }
 
-        let signature_period = block.epoch().sync_committee_period(chain_spec)?;
-        // Compute and validate attested header.
-        let mut attested_header = attested_state.latest_block_header().clone();
-        attested_header.state_root = attested_state.update_tree_hash_cache()?;
-        let attested_period = attested_header
-            .slot
+        let signature_period = signature_slot
+            .epoch(E::slots_per_epoch())
+            .sync_committee_period(chain_spec)?;
+        let attested_period = attested_block
+            .slot()
             .epoch(E::slots_per_epoch())
             .sync_committee_period(chain_spec)?;
         if attested_period != signature_period {
             return Err(Error::MismatchingPeriods);
         }
-        // Build finalized header from finalized block
-        let finalized_header = BeaconBlockHeader {
-            slot: finalized_block.slot(),
-            proposer_index: finalized_block.message().proposer_index(),
-            parent_root: finalized_block.parent_root(),
-            state_root: finalized_block.state_root(),
-            body_root: finalized_block.message().body_root(),
-        };
-        if finalized_header.tree_hash_root() != beacon_state.finalized_checkpoint().root {
-            return Err(Error::InvalidFinalizedBlock);
-        }
-        let next_sync_committee_branch =
-            attested_state.compute_merkle_proof(NEXT_SYNC_COMMITTEE_INDEX)?;
-        let finality_branch = attested_state.compute_merkle_proof(FINALIZED_ROOT_INDEX)?;
 
         let light_client_update = match attested_block
             .fork_name(chain_spec)
             .map_err(|_| Error::InconsistentFork)?
         {
             ForkName::Base => return Err(Error::AltairForkNotActive),
-            ForkName::Altair | ForkName::Bellatrix => {
-                let attested_header =
-                    LightClientHeaderAltair::block_to_light_client_header(attested_block)?;
-                let finalized_header =
-                    LightClientHeaderAltair::block_to_light_client_header(finalized_block)?;
-                Self::Altair(LightClientUpdateAltair {
-                    attested_header,
-                    next_sync_committee: attested_state.next_sync_committee()?.clone(),
-                    next_sync_committee_branch: FixedVector::new(next_sync_committee_branch)?,
-                    finalized_header,
-                    finality_branch: FixedVector::new(finality_branch)?,
-                    sync_aggregate: sync_aggregate.clone(),
-                    signature_slot: block.slot(),
-                })
-            }
-            ForkName::Capella => {
-                let attested_header =
-                    LightClientHeaderCapella::block_to_light_client_header(attested_block)?;
-                let finalized_header =
-                    LightClientHeaderCapella::block_to_light_client_header(finalized_block)?;
-                Self::Capella(LightClientUpdateCapella {
-                    attested_header,
-                    next_sync_committee: attested_state.next_sync_committee()?.clone(),
-                    next_sync_committee_branch: FixedVector::new(next_sync_committee_branch)?,
-                    finalized_header,
-                    finality_branch: FixedVector::new(finality_branch)?,
-                    sync_aggregate: sync_aggregate.clone(),
-                    signature_slot: block.slot(),
-                })
-            }
-            ForkName::Deneb => {
-                let attested_header =
-                    LightClientHeaderDeneb::block_to_light_client_header(attested_block)?;
-                let finalized_header =
-                    LightClientHeaderDeneb::block_to_light_client_header(finalized_block)?;
-                Self::Deneb(LightClientUpdateDeneb {
-                    attested_header,
-                    next_sync_committee: attested_state.next_sync_committee()?.clone(),
-                    next_sync_committee_branch: FixedVector::new(next_sync_committee_branch)?,
-                    finalized_header,
-                    finality_branch: FixedVector::new(finality_branch)?,
-                    sync_aggregate: sync_aggregate.clone(),
-                    signature_slot: block.slot(),
-                })
-            }
-            ForkName::Electra => {
-                let attested_header =
-                    LightClientHeaderElectra::block_to_light_client_header(attested_block)?;
-                let finalized_header =
-                    LightClientHeaderElectra::block_to_light_client_header(finalized_block)?;
-                Self::Electra(LightClientUpdateElectra {
-                    attested_header,
-                    next_sync_committee: attested_state.next_sync_committee()?.clone(),
-                    next_sync_committee_branch: FixedVector::new(next_sync_committee_branch)?,
-                    finalized_header,
-                    finality_branch: FixedVector::new(finality_branch)?,
-                    sync_aggregate: sync_aggregate.clone(),
-                    signature_slot: block.slot(),
-                })
-            } // To add a new fork, just append the new fork variant on the latest fork. Forks that
-              // have a distinct execution header will need a new LightClientUdpate variant only
-              // if you need to test or support lightclient usages
+            ForkName::Altair | ForkName::Bellatrix => Self::Altair(LightClientUpdateAltair {
+                attested_header: LightClientHeaderAltair::block_to_light_client_header(
+                    attested_block,
+                )?,
+                next_sync_committee,
+                next_sync_committee_branch,
+                finalized_header: LightClientHeaderAltair::block_to_light_client_header(
+                    finalized_block,
+                )?,
+                finality_branch,
+                sync_aggregate: sync_aggregate.clone(),
+                signature_slot,
+            }),
+            ForkName::Capella => Self::Capella(LightClientUpdateCapella {
+                attested_header: LightClientHeaderCapella::block_to_light_client_header(
+                    attested_block,
+                )?,
+                next_sync_committee,
+                next_sync_committee_branch,
+                finalized_header: LightClientHeaderCapella::block_to_light_client_header(
+                    finalized_block,
+                )?,
+                finality_branch,
+                sync_aggregate: sync_aggregate.clone(),
+                signature_slot,
+            }),
+            ForkName::Deneb => Self::Deneb(LightClientUpdateDeneb {
+                attested_header: LightClientHeaderDeneb::block_to_light_client_header(
+                    attested_block,
+                )?,
+                next_sync_committee,
+                next_sync_committee_branch,
+                finalized_header: LightClientHeaderDeneb::block_to_light_client_header(
+                    finalized_block,
+                )?,
+                finality_branch,
+                sync_aggregate: sync_aggregate.clone(),
+                signature_slot,
+            }),
+            ForkName::Electra => Self::Electra(LightClientUpdateElectra {
+                attested_header: LightClientHeaderElectra::block_to_light_client_header(
+                    attested_block,
+                )?,
+                next_sync_committee,
+                next_sync_committee_branch,
+                finalized_header: LightClientHeaderElectra::block_to_light_client_header(
+                    finalized_block,
+                )?,
+                finality_branch,
+                sync_aggregate: sync_aggregate.clone(),
+                signature_slot,
+            }), // To add a new fork, just append the new fork variant on the latest fork. Forks that
+                // have a distinct execution header will need a new LightClientUdpate variant only
+                // if you need to test or support lightclient usages
         };
 
         Ok(light_client_update)
     }
 
+    pub fn map_with_fork_name<F, R>(&self, func: F) -> R
+    where
+        F: Fn(ForkName) -> R,
+    {
+        match self {
+            Self::Altair(_) => func(ForkName::Altair),
+            Self::Capella(_) => func(ForkName::Capella),
+            Self::Deneb(_) => func(ForkName::Deneb),
+            Self::Electra(_) => func(ForkName::Electra),
+        }
+    }
+
+    pub fn attested_header_slot<'a>(&'a self) -> Slot {
+        map_light_client_update_ref!(&'a _, self.to_ref(), |inner, cons| {
+            cons(inner);
+            inner.attested_header.beacon.slot
+        })
+    }
+
+    pub fn finalized_header_slot<'a>(&'a self) -> Slot {
+        map_light_client_update_ref!(&'a _, self.to_ref(), |inner, cons| {
+            cons(inner);
+            inner.finalized_header.beacon.slot
+        })
+    }
+
+    /// The sync committee period of the attested header, which is the period this update is
+    /// served for.
+    pub fn attested_period(&self, chain_spec: &ChainSpec) -> Result<u64, Error> {
+        Ok(self
+            .attested_header_slot()
+            .epoch(E::slots_per_epoch())
+            .sync_committee_period(chain_spec)?)
+    }
+
+    fn is_sync_committee_update(&self) -> bool {
+        self.next_sync_committee_branch()
+            .iter()
+            .any(|node| !node.is_zero())
+    }
+
+    fn is_finality_update(&self) -> bool {
+        self.finality_branch().iter().any(|node| !node.is_zero())
+    }
+
+    fn has_relevant_sync_committee(&self, chain_spec: &ChainSpec) -> Result<bool, Error> {
+        let signature_period = self
+            .signature_slot()
+            .epoch(E::slots_per_epoch())
+            .sync_committee_period(chain_spec)?;
+        Ok(
+            self.is_sync_committee_update()
+                && self.attested_period(chain_spec)? == signature_period,
+        )
+    }
+
+    fn has_sync_committee_finality(&self, chain_spec: &ChainSpec) -> Result<bool, Error> {
+        let finalized_period = self
+            .finalized_header_slot()
+            .epoch(E::slots_per_epoch())
+            .sync_committee_period(chain_spec)?;
+        Ok(finalized_period == self.attested_period(chain_spec)?)
+    }
+
+    /// Returns `true` if `self` should be preferred over `prev` as the best update of a period.
+    ///
+    /// Implements the spec's `is_better_update`:
+    ///
+    /// ref: https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/light-client/sync-protocol.md#is_better_update
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn is_better_update(&self, prev: &Self, chain_spec: &ChainSpec) -> Result<bool, Error> {
+        // Compare supermajority (> 2/3) sync committee participation.
+        let max_active_participants = self.sync_aggregate().sync_committee_bits.len();
+        let new_active_participants = self.sync_aggregate().num_set_bits();
+        let prev_active_participants = prev.sync_aggregate().num_set_bits();
+        let new_has_supermajority = new_active_participants * 3 >= max_active_participants * 2;
+        let prev_has_supermajority = prev_active_participants * 3 >= max_active_participants * 2;
+        if new_has_supermajority != prev_has_supermajority {
+            return Ok(new_has_supermajority);
+        }
+        if !new_has_supermajority && new_active_participants != prev_active_participants {
+            return Ok(new_active_participants > prev_active_participants);
+        }
+
+        // Compare presence of relevant sync committee.
+        let new_has_relevant_sync_committee = self.has_relevant_sync_committee(chain_spec)?;
+        let prev_has_relevant_sync_committee = prev.has_relevant_sync_committee(chain_spec)?;
+        if new_has_relevant_sync_committee != prev_has_relevant_sync_committee {
+            return Ok(new_has_relevant_sync_committee);
+        }
+
+        // Compare indication of any finality.
+        let new_has_finality = self.is_finality_update();
+        let prev_has_finality = prev.is_finality_update();
+        if new_has_finality != prev_has_finality {
+            return Ok(new_has_finality);
+        }
+
+        // Compare sync committee finality.
+        if new_has_finality {
+            let new_has_sync_committee_finality = self.has_sync_committee_finality(chain_spec)?;
+            let prev_has_sync_committee_finality = prev.has_sync_committee_finality(chain_spec)?;
+            if new_has_sync_committee_finality != prev_has_sync_committee_finality {
+                return Ok(new_has_sync_committee_finality);
+            }
+        }
+
+        // Tiebreaker 1: sync committee participation beyond supermajority.
+        if new_active_participants != prev_active_participants {
+            return Ok(new_active_participants > prev_active_participants);
+        }
+
+        // Tiebreaker 2: prefer older data (fewer changes to best).
+        if self.attested_header_slot() != prev.attested_header_slot() {
+            return Ok(self.attested_header_slot() < prev.attested_header_slot());
+        }
+        Ok(self.signature_slot() < prev.signature_slot())
+    }
+
     pub fn from_ssz_bytes(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
         let update = match fork_name {
             ForkName::Altair | ForkName::Bellatrix => {
@@ -280,16 +384,76 @@ impl<E: EthSpec> LightClientUpdate<E> {
 
         Ok(update)
     }
+
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn ssz_max_len_for_fork(fork_name: ForkName) -> usize {
+        let fixed_size = match fork_name {
+            ForkName::Base => 0,
+            ForkName::Altair | ForkName::Bellatrix => {
+                <LightClientUpdateAltair<E> as Encode>::ssz_fixed_len()
+            }
+            ForkName::Capella => <LightClientUpdateCapella<E> as Encode>::ssz_fixed_len(),
+            ForkName::Deneb => <LightClientUpdateDeneb<E> as Encode>::ssz_fixed_len(),
+            ForkName::Electra => <LightClientUpdateElectra<E> as Encode>::ssz_fixed_len(),
+        };
+        // `2 *` because there are two headers in the update
+        fixed_size + 2 * LightClientHeader::<E>::ssz_max_var_len_for_fork(fork_name)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
     use crate::MainnetEthSpec;
     use ssz_types::typenum::Unsigned;
 
     ssz_tests!(LightClientUpdateDeneb<MainnetEthSpec>);
 
+    fn update_with_participation(
+        rng: &mut XorShiftRng,
+        participants: usize,
+    ) -> LightClientUpdate<MainnetEthSpec> {
+        let mut update = LightClientUpdateAltair::<MainnetEthSpec>::random_for_test(rng);
+        update.attested_header.beacon.slot = Slot::new(100);
+        update.finalized_header.beacon.slot = Slot::new(64);
+        update.signature_slot = Slot::new(101);
+        for i in 0..update.sync_aggregate.sync_committee_bits.len() {
+            update
+                .sync_aggregate
+                .sync_committee_bits
+                .set(i, i < participants)
+                .unwrap();
+        }
+        LightClientUpdate::Altair(update)
+    }
+
+    #[test]
+    fn is_better_update_ordering() {
+        let spec = MainnetEthSpec::default_spec();
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let committee_size = <MainnetEthSpec as EthSpec>::SyncCommitteeSize::to_usize();
+        let supermajority = committee_size * 2 / 3 + 1;
+
+        // Supermajority beats any minority.
+        let minority = update_with_participation(&mut rng, supermajority - 2);
+        let majority = update_with_participation(&mut rng, supermajority);
+        assert!(majority.is_better_update(&minority, &spec).unwrap());
+        assert!(!minority.is_better_update(&majority, &spec).unwrap());
+
+        // With equal participation, a finality update beats one without finality.
+        let mut without_finality = majority.clone();
+        *without_finality.finality_branch_mut() = FixedVector::default();
+        assert!(majority.is_better_update(&without_finality, &spec).unwrap());
+        assert!(!without_finality.is_better_update(&majority, &spec).unwrap());
+
+        // Otherwise older data is preferred.
+        let mut newer = majority.clone();
+        *newer.signature_slot_mut() = Slot::new(102);
+        assert!(majority.is_better_update(&newer, &spec).unwrap());
+        assert!(!majority.is_better_update(&majority, &spec).unwrap());
+    }
+
     #[test]
     fn finalized_root_params() {
         assert!(2usize.pow(FINALIZED_ROOT_PROOF_LEN as u32) <= FINALIZED_ROOT_INDEX);
@@ -0,0 +1,2001 @@
+//! Merkle multiproofs for arbitrary nodes of SSZ objects.
+//!
+//! Nodes are addressed by their generalized index relative to the root of an object, or by a
+//! path of field names and list indices which resolves to a generalized index. See:
+//!
+//! https://github.com/ethereum/consensus-specs/blob/dev/ssz/merkle-proofs.md
+//!
+//! Objects expose their tree through `MerkleNode`. Basic values, signatures and bitfields do not
+//! expose their children, so only their roots can be proven.
+use crate::*;
+use ethereum_hashing::{hash32_concat, ZERO_HASHES};
+use safe_arith::{ArithError, SafeArith};
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use tree_hash::{TreeHash, TreeHashType, BYTES_PER_CHUNK};
+
+/// The path element which addresses the length of a list.
+pub const LENGTH_PATH_ELEMENT: &str = "__len__";
+
+/// The minimum number of chunks of a node for its `ChunkLayers` to be cached by a `LayerCache`.
+pub const MIN_CACHED_CHUNKS: usize = 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// Zero is not a generalized index.
+    InvalidGeneralizedIndex(u64),
+    /// The generalized index lies within a node which does not expose its children, e.g. a
+    /// packed chunk of basic values or a signature.
+    UnreachableGeneralizedIndex(u64),
+    /// The generalized index does not fit in a `u64`.
+    GeneralizedIndexOverflow,
+    /// The tree is deeper than the available zero hashes.
+    TreeTooDeep(usize),
+    UnknownField(String),
+    IndexOutOfBounds {
+        index: u64,
+        limit: usize,
+    },
+    /// The path element cannot be applied to the node it addresses.
+    InvalidPathElement(String),
+    /// The number of leaves or proof nodes does not match the generalized indices.
+    InvalidProofLength {
+        expected: usize,
+        found: usize,
+    },
+    /// The leaves and proof do not hash to the root.
+    MissingRoot,
+    ArithError(ArithError),
+}
+
+impl From<ArithError> for Error {
+    fn from(e: ArithError) -> Self {
+        Error::ArithError(e)
+    }
+}
+
+/// The shape of the Merkle tree of an SSZ object.
+pub enum NodeKind<'a> {
+    /// A node whose children are not exposed.
+    Leaf,
+    /// A container, with its fields in declaration order.
+    Container(Vec<(&'static str, &'a dyn MerkleNode)>),
+    /// A vector or list of composite elements.
+    Composite {
+        elements: Vec<&'a dyn MerkleNode>,
+        limit: usize,
+        is_list: bool,
+    },
+    /// A vector or list of basic elements, packed into chunks.
+    Packed {
+        chunks: Vec<Hash256>,
+        len: usize,
+        elements_per_chunk: usize,
+        limit: usize,
+        is_list: bool,
+    },
+}
+
+/// An SSZ object whose Merkle tree can be traversed to compute proofs.
+pub trait MerkleNode {
+    /// The `tree_hash_root` of `self`.
+    fn merkle_root(&self) -> Hash256;
+
+    /// The children of `self` in its Merkle tree.
+    fn merkle_node(&self) -> NodeKind<'_>;
+}
+
+/// The location of a generalized index relative to the chunks of a node.
+enum Position {
+    Root,
+    Length,
+    /// The root of the subtree of `height` whose leftmost chunk is `start`.
+    Subtree {
+        start: usize,
+        height: usize,
+    },
+    /// A node within the child at chunk `index`.
+    Child {
+        index: usize,
+        gindex: u64,
+    },
+}
+
+impl<'a> NodeKind<'a> {
+    fn length(&self) -> Option<usize> {
+        match self {
+            NodeKind::Composite {
+                elements,
+                is_list: true,
+                ..
+            } => Some(elements.len()),
+            NodeKind::Packed {
+                len, is_list: true, ..
+            } => Some(*len),
+            _ => None,
+        }
+    }
+
+    /// The depth of the tree of chunks, excluding any length mix-in.
+    fn depth(&self) -> Result<usize, Error> {
+        let chunk_limit = match self {
+            NodeKind::Leaf => 1,
+            NodeKind::Container(fields) => fields.len(),
+            NodeKind::Composite { limit, .. } => *limit,
+            NodeKind::Packed {
+                elements_per_chunk,
+                limit,
+                ..
+            } => limit
+                .safe_add(elements_per_chunk.safe_sub(1)?)?
+                .safe_div(*elements_per_chunk)?,
+        };
+        Ok(chunk_limit.next_power_of_two().trailing_zeros() as usize)
+    }
+
+    fn chunk_count(&self) -> usize {
+        match self {
+            NodeKind::Leaf => 0,
+            NodeKind::Container(fields) => fields.len(),
+            NodeKind::Composite { elements, .. } => elements.len(),
+            NodeKind::Packed { chunks, .. } => chunks.len(),
+        }
+    }
+
+    fn chunks(&self) -> Vec<Hash256> {
+        match self {
+            NodeKind::Leaf => vec![],
+            NodeKind::Container(fields) => fields
+                .iter()
+                .map(|(_, field)| field.merkle_root())
+                .collect(),
+            NodeKind::Composite { elements, .. } => elements
+                .iter()
+                .map(|element| element.merkle_root())
+                .collect(),
+            NodeKind::Packed { chunks, .. } => chunks.clone(),
+        }
+    }
+
+    fn child(&self, index: usize) -> Option<&'a dyn MerkleNode> {
+        match self {
+            NodeKind::Container(fields) => fields.get(index).map(|(_, field)| *field),
+            NodeKind::Composite { elements, .. } => elements.get(index).copied(),
+            NodeKind::Leaf | NodeKind::Packed { .. } => None,
+        }
+    }
+
+    fn position(&self, gindex: u64) -> Result<Position, Error> {
+        if gindex == 0 {
+            return Err(Error::InvalidGeneralizedIndex(gindex));
+        }
+        if gindex == 1 {
+            return Ok(Position::Root);
+        }
+        if matches!(self, NodeKind::Leaf) {
+            return Err(Error::UnreachableGeneralizedIndex(gindex));
+        }
+
+        // The chunks of a list are the left child of its root, the length is the right child.
+        let data_gindex = if self.length().is_some() {
+            if gindex == 3 {
+                return Ok(Position::Length);
+            }
+            let below_root = gindex_depth(gindex).safe_sub(1)?;
+            if gindex.safe_shr(below_root as u32)? & 1 == 1 {
+                return Err(Error::UnreachableGeneralizedIndex(gindex));
+            }
+            strip_first_step(gindex)?
+        } else {
+            gindex
+        };
+
+        let depth = self.depth()?;
+        let level = gindex_depth(data_gindex);
+        if level <= depth {
+            let height = depth.safe_sub(level)?;
+            let index = data_gindex.safe_sub(1u64.safe_shl(level as u32)?)?;
+            let start = usize::try_from(index)
+                .map_err(|_| Error::GeneralizedIndexOverflow)?
+                .safe_shl(height as u32)?;
+            Ok(Position::Subtree { start, height })
+        } else {
+            let shift = level.safe_sub(depth)? as u32;
+            let chunk_gindex = data_gindex.safe_shr(shift)?;
+            let index = chunk_gindex.safe_sub(1u64.safe_shl(depth as u32)?)?;
+            let mask = 1u64.safe_shl(shift)?.safe_sub(1)?;
+            Ok(Position::Child {
+                index: usize::try_from(index).map_err(|_| Error::GeneralizedIndexOverflow)?,
+                gindex: 1u64.safe_shl(shift)? | (data_gindex & mask),
+            })
+        }
+    }
+}
+
+/// The depth of `gindex` in its tree, i.e. `floor(log2(gindex))`.
+fn gindex_depth(gindex: u64) -> usize {
+    gindex.checked_ilog2().unwrap_or(0) as usize
+}
+
+/// Re-root `gindex` at the child of the root which contains it.
+fn strip_first_step(gindex: u64) -> Result<u64, Error> {
+    let below_root = gindex_depth(gindex).safe_sub(1)? as u32;
+    let mask = 1u64.safe_shl(below_root)?.safe_sub(1)?;
+    Ok(1u64.safe_shl(below_root)? | (gindex & mask))
+}
+
+/// The generalized index of the node at `inner` within the subtree rooted at `outer`.
+pub fn concat_generalized_indices(outer: u64, inner: u64) -> Result<u64, Error> {
+    if outer == 0 {
+        return Err(Error::InvalidGeneralizedIndex(outer));
+    }
+    if inner == 0 {
+        return Err(Error::InvalidGeneralizedIndex(inner));
+    }
+    let inner_depth = gindex_depth(inner);
+    if gindex_depth(outer).safe_add(inner_depth)? >= u64::BITS as usize {
+        return Err(Error::GeneralizedIndexOverflow);
+    }
+    let mask = 1u64.safe_shl(inner_depth as u32)?.safe_sub(1)?;
+    Ok(outer.safe_shl(inner_depth as u32)? | (inner & mask))
+}
+
+fn zero_hash(height: usize) -> Result<Hash256, Error> {
+    ZERO_HASHES
+        .get(height)
+        .map(|hash| Hash256::from_slice(hash))
+        .ok_or(Error::TreeTooDeep(height))
+}
+
+/// Every layer of the tree of chunks of a node, from the chunks up to the root of the tree
+/// (excluding any length mix-in).
+///
+/// Nodes to the right of the last chunk are zero hashes and are not stored.
+#[derive(Debug, PartialEq)]
+pub struct ChunkLayers {
+    layers: Vec<Vec<Hash256>>,
+}
+
+impl ChunkLayers {
+    fn new(chunks: Vec<Hash256>, depth: usize) -> Result<Self, Error> {
+        let mut layers = Vec::with_capacity(depth.safe_add(1)?);
+        let mut layer = chunks;
+        for height in 0..depth {
+            let parents = layer
+                .chunks(2)
+                .map(|pair| {
+                    let left = pair.first().copied().unwrap_or_default();
+                    let right = match pair.get(1) {
+                        Some(right) => *right,
+                        None => zero_hash(height)?,
+                    };
+                    Ok(Hash256::from(hash32_concat(
+                        left.as_bytes(),
+                        right.as_bytes(),
+                    )))
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+            layers.push(std::mem::replace(&mut layer, parents));
+        }
+        layers.push(layer);
+        Ok(Self { layers })
+    }
+
+    /// The root of the subtree of `height` whose leftmost chunk is at index `start`.
+    fn subtree_root(&self, start: usize, height: usize) -> Result<Hash256, Error> {
+        let layer = self.layers.get(height).ok_or(Error::TreeTooDeep(height))?;
+        match layer.get(start.safe_shr(height as u32)?) {
+            Some(root) => Ok(*root),
+            None => zero_hash(height),
+        }
+    }
+}
+
+/// A cache of the `ChunkLayers` of large nodes, keyed by the root of the node.
+///
+/// Computing the chunks of a large list such as the validator registry requires hashing every
+/// element, so caching its layers allows further proofs of the same list to skip this.
+pub trait LayerCache {
+    fn get(&mut self, root: &Hash256) -> Option<Arc<ChunkLayers>>;
+
+    fn insert(&mut self, root: Hash256, layers: Arc<ChunkLayers>);
+}
+
+/// Caches nothing.
+impl LayerCache for () {
+    fn get(&mut self, _: &Hash256) -> Option<Arc<ChunkLayers>> {
+        None
+    }
+
+    fn insert(&mut self, _: Hash256, _: Arc<ChunkLayers>) {}
+}
+
+impl LayerCache for HashMap<Hash256, Arc<ChunkLayers>> {
+    fn get(&mut self, root: &Hash256) -> Option<Arc<ChunkLayers>> {
+        HashMap::get(self, root).cloned()
+    }
+
+    fn insert(&mut self, root: Hash256, layers: Arc<ChunkLayers>) {
+        HashMap::insert(self, root, layers);
+    }
+}
+
+/// Return the `ChunkLayers` of `node`, from `cache` if it has enough chunks to be cached.
+fn chunk_layers(
+    node: &dyn MerkleNode,
+    kind: &NodeKind,
+    cache: &mut dyn LayerCache,
+) -> Result<Arc<ChunkLayers>, Error> {
+    if kind.chunk_count() < MIN_CACHED_CHUNKS {
+        return ChunkLayers::new(kind.chunks(), kind.depth()?).map(Arc::new);
+    }
+    let root = node.merkle_root();
+    if let Some(layers) = cache.get(&root) {
+        return Ok(layers);
+    }
+    let layers = Arc::new(ChunkLayers::new(kind.chunks(), kind.depth()?)?);
+    cache.insert(root, layers.clone());
+    Ok(layers)
+}
+
+/// Compute the roots of the nodes of `node` at `gindices`.
+///
+/// The chunks of each node on the way are computed at most once, so requesting all nodes of a
+/// multiproof in a single call is much cheaper than requesting them one by one.
+pub fn tree_nodes(node: &dyn MerkleNode, gindices: &[u64]) -> Result<Vec<Hash256>, Error> {
+    tree_nodes_with_cache(node, gindices, &mut ())
+}
+
+/// Compute the roots of the nodes of `node` at `gindices`, reusing the layers of large nodes
+/// from `cache`.
+pub fn tree_nodes_with_cache(
+    node: &dyn MerkleNode,
+    gindices: &[u64],
+    cache: &mut dyn LayerCache,
+) -> Result<Vec<Hash256>, Error> {
+    let kind = node.merkle_node();
+    let mut roots = vec![Hash256::zero(); gindices.len()];
+    let mut layers = None;
+    let mut delegated: BTreeMap<usize, Vec<(usize, u64)>> = BTreeMap::new();
+
+    for (i, &gindex) in gindices.iter().enumerate() {
+        let root = match kind.position(gindex)? {
+            Position::Root => node.merkle_root(),
+            Position::Length => (kind.length().unwrap_or(0) as u64).tree_hash_root(),
+            Position::Subtree { start, height } => {
+                let layers = match layers.as_ref() {
+                    Some(layers) => Arc::clone(layers),
+                    None => Arc::clone(layers.insert(chunk_layers(node, &kind, cache)?)),
+                };
+                layers.subtree_root(start, height)?
+            }
+            Position::Child { index, gindex } => {
+                delegated.entry(index).or_default().push((i, gindex));
+                continue;
+            }
+        };
+        if let Some(slot) = roots.get_mut(i) {
+            *slot = root;
+        }
+    }
+
+    for (index, requests) in delegated {
+        let Some(child) = kind.child(index) else {
+            let gindex = requests
+                .first()
+                .and_then(|(i, _)| gindices.get(*i))
+                .copied()
+                .unwrap_or_default();
+            return Err(Error::UnreachableGeneralizedIndex(gindex));
+        };
+        let child_gindices = requests
+            .iter()
+            .map(|(_, gindex)| *gindex)
+            .collect::<Vec<_>>();
+        let child_roots = tree_nodes_with_cache(child, &child_gindices, cache)?;
+        for ((i, _), root) in requests.iter().zip(child_roots) {
+            if let Some(slot) = roots.get_mut(*i) {
+                *slot = root;
+            }
+        }
+    }
+
+    Ok(roots)
+}
+
+/// An element of a path through an SSZ object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathElement {
+    /// A field of a container.
+    Field(String),
+    /// An element of a list or vector.
+    Index(u64),
+    /// The length of a list.
+    Length,
+}
+
+impl FromStr for PathElement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("empty path element".to_string());
+        }
+        if s == LENGTH_PATH_ELEMENT {
+            Ok(PathElement::Length)
+        } else if s.bytes().all(|b| b.is_ascii_digit()) {
+            u64::from_str(s)
+                .map(PathElement::Index)
+                .map_err(|e| format!("invalid index {s}: {e:?}"))
+        } else {
+            Ok(PathElement::Field(s.to_string()))
+        }
+    }
+}
+
+impl fmt::Display for PathElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PathElement::Field(name) => write!(f, "{name}"),
+            PathElement::Index(index) => write!(f, "{index}"),
+            PathElement::Length => write!(f, "{LENGTH_PATH_ELEMENT}"),
+        }
+    }
+}
+
+/// A path through an SSZ object, written as its elements separated by `.`, e.g.
+/// `validators.5.effective_balance` or `historical_summaries.__len__`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SszPath(pub Vec<PathElement>);
+
+impl FromStr for SszPath {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split('.')
+            .map(PathElement::from_str)
+            .collect::<Result<Vec<_>, _>>()
+            .map(SszPath)
+    }
+}
+
+impl fmt::Display for SszPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, element) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ".")?;
+            }
+            write!(f, "{element}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Resolve `path` to a generalized index relative to the root of `node`.
+///
+/// Indexing into a list of basic values resolves to the chunk which contains the element.
+pub fn generalized_index(node: &dyn MerkleNode, path: &[PathElement]) -> Result<u64, Error> {
+    let Some((element, rest)) = path.split_first() else {
+        return Ok(1);
+    };
+    let kind = node.merkle_node();
+    let depth = kind.depth()?;
+    let is_list = kind.length().is_some();
+
+    let (gindex, child) = match (&kind, element) {
+        (NodeKind::Container(fields), PathElement::Field(name)) => {
+            let index = fields
+                .iter()
+                .position(|(field_name, _)| field_name == name)
+                .ok_or_else(|| Error::UnknownField(name.clone()))?;
+            (chunk_gindex(depth, index as u64)?, kind.child(index))
+        }
+        (NodeKind::Composite { limit, .. }, PathElement::Index(index)) => {
+            if *index >= *limit as u64 {
+                return Err(Error::IndexOutOfBounds {
+                    index: *index,
+                    limit: *limit,
+                });
+            }
+            let child = usize::try_from(*index)
+                .ok()
+                .and_then(|index| kind.child(index));
+            (chunk_gindex(depth, *index)?, child)
+        }
+        (
+            NodeKind::Packed {
+                elements_per_chunk,
+                limit,
+                ..
+            },
+            PathElement::Index(index),
+        ) => {
+            if *index >= *limit as u64 {
+                return Err(Error::IndexOutOfBounds {
+                    index: *index,
+                    limit: *limit,
+                });
+            }
+            let chunk_index = index.safe_div(*elements_per_chunk as u64)?;
+            (chunk_gindex(depth, chunk_index)?, None)
+        }
+        (NodeKind::Composite { .. } | NodeKind::Packed { .. }, PathElement::Length) if is_list => {
+            if let Some(element) = rest.first() {
+                return Err(Error::InvalidPathElement(element.to_string()));
+            }
+            return Ok(3);
+        }
+        _ => return Err(Error::InvalidPathElement(element.to_string())),
+    };
+    let gindex = if is_list {
+        concat_generalized_indices(2, gindex)?
+    } else {
+        gindex
+    };
+
+    if rest.is_empty() {
+        return Ok(gindex);
+    }
+    let child = child.ok_or_else(|| {
+        Error::InvalidPathElement(rest.first().map(ToString::to_string).unwrap_or_default())
+    })?;
+    concat_generalized_indices(gindex, generalized_index(child, rest)?)
+}
+
+fn chunk_gindex(depth: usize, index: u64) -> Result<u64, Error> {
+    Ok(1u64.safe_shl(depth as u32)?.safe_add(index)?)
+}
+
+/// A Merkle multiproof of the nodes at `gindices`.
+///
+/// The proof contains the helper nodes required to compute the root, ordered by descending
+/// generalized index as specified by `get_helper_indices`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct Multiproof {
+    #[serde(with = "serde_utils::quoted_u64_vec")]
+    pub gindices: Vec<u64>,
+    pub leaves: Vec<Hash256>,
+    pub proof: Vec<Hash256>,
+}
+
+impl Multiproof {
+    /// Compute the multiproof of the nodes of `node` at `gindices`.
+    pub fn new(node: &dyn MerkleNode, gindices: Vec<u64>) -> Result<Self, Error> {
+        Self::new_with_cache(node, gindices, &mut ())
+    }
+
+    /// Compute the multiproof of the nodes of `node` at `gindices`, reusing the layers of large
+    /// nodes from `cache`.
+    pub fn new_with_cache(
+        node: &dyn MerkleNode,
+        gindices: Vec<u64>,
+        cache: &mut dyn LayerCache,
+    ) -> Result<Self, Error> {
+        let helper_indices = helper_indices(&gindices)?;
+        let all_indices = gindices
+            .iter()
+            .chain(helper_indices.iter())
+            .copied()
+            .collect::<Vec<_>>();
+        let mut leaves = tree_nodes_with_cache(node, &all_indices, cache)?;
+        let proof = leaves.split_off(gindices.len());
+        Ok(Self {
+            gindices,
+            leaves,
+            proof,
+        })
+    }
+
+    /// Compute the root from the leaves and the proof, i.e. `calculate_multi_merkle_root`.
+    pub fn root(&self) -> Result<Hash256, Error> {
+        if self.leaves.len() != self.gindices.len() {
+            return Err(Error::InvalidProofLength {
+                expected: self.gindices.len(),
+                found: self.leaves.len(),
+            });
+        }
+        let helper_indices = helper_indices(&self.gindices)?;
+        if self.proof.len() != helper_indices.len() {
+            return Err(Error::InvalidProofLength {
+                expected: helper_indices.len(),
+                found: self.proof.len(),
+            });
+        }
+
+        let mut objects = self
+            .gindices
+            .iter()
+            .copied()
+            .zip(self.leaves.iter().copied())
+            .chain(helper_indices.into_iter().zip(self.proof.iter().copied()))
+            .collect::<HashMap<_, _>>();
+        let mut keys = objects.keys().copied().collect::<Vec<_>>();
+        keys.sort_unstable_by(|a, b| b.cmp(a));
+
+        let mut pos = 0;
+        while let Some(&k) = keys.get(pos) {
+            let parent = k / 2;
+            if k > 1 && !objects.contains_key(&parent) {
+                if let (Some(left), Some(right)) = (objects.get(&(k & !1)), objects.get(&(k | 1))) {
+                    let root = Hash256::from(hash32_concat(left.as_bytes(), right.as_bytes()));
+                    objects.insert(parent, root);
+                    keys.push(parent);
+                }
+            }
+            pos.safe_add_assign(1)?;
+        }
+
+        objects.get(&1).copied().ok_or(Error::MissingRoot)
+    }
+
+    /// Returns `true` if the proof hashes to `root`.
+    pub fn verify(&self, root: Hash256) -> Result<bool, Error> {
+        Ok(self.root()? == root)
+    }
+}
+
+/// The generalized indices of the nodes required to prove `gindices`, in descending order.
+pub fn helper_indices(gindices: &[u64]) -> Result<Vec<u64>, Error> {
+    let mut helpers = BTreeSet::new();
+    let mut path = BTreeSet::new();
+    for &gindex in gindices {
+        if gindex == 0 {
+            return Err(Error::InvalidGeneralizedIndex(gindex));
+        }
+        let mut node = gindex;
+        while node > 1 {
+            helpers.insert(node ^ 1);
+            path.insert(node);
+            node /= 2;
+        }
+    }
+    let mut helpers = helpers.difference(&path).copied().collect::<Vec<_>>();
+    helpers.reverse();
+    Ok(helpers)
+}
+
+/// Implement `MerkleNode` for types which are proven as a whole.
+macro_rules! impl_leaf_merkle_node {
+    ($($ty: ty),* $(,)?) => {
+        $(
+            impl MerkleNode for $ty {
+                fn merkle_root(&self) -> Hash256 {
+                    self.tree_hash_root()
+                }
+
+                fn merkle_node(&self) -> NodeKind<'_> {
+                    NodeKind::Leaf
+                }
+            }
+        )*
+    };
+}
+
+/// Implement `MerkleNode` for a container, listing its fields in declaration order.
+macro_rules! impl_container_merkle_node {
+    (<$e: ident> $ty: ty, [$($field: ident),* $(,)?]) => {
+        impl<$e: $crate::EthSpec> $crate::merkle_multiproof::MerkleNode for $ty {
+            fn merkle_root(&self) -> $crate::Hash256 {
+                tree_hash::TreeHash::tree_hash_root(self)
+            }
+
+            fn merkle_node(&self) -> $crate::merkle_multiproof::NodeKind<'_> {
+                $crate::merkle_multiproof::NodeKind::Container(vec![
+                    $((
+                        stringify!($field),
+                        &self.$field as &dyn $crate::merkle_multiproof::MerkleNode,
+                    )),*
+                ])
+            }
+        }
+    };
+    ($ty: ty, [$($field: ident),* $(,)?]) => {
+        impl $crate::merkle_multiproof::MerkleNode for $ty {
+            fn merkle_root(&self) -> $crate::Hash256 {
+                tree_hash::TreeHash::tree_hash_root(self)
+            }
+
+            fn merkle_node(&self) -> $crate::merkle_multiproof::NodeKind<'_> {
+                $crate::merkle_multiproof::NodeKind::Container(vec![
+                    $((
+                        stringify!($field),
+                        &self.$field as &dyn $crate::merkle_multiproof::MerkleNode,
+                    )),*
+                ])
+            }
+        }
+    };
+}
+
+pub(crate) use impl_container_merkle_node;
+
+/// The `NodeKind` of a vector or list with `elements`.
+fn sequence_node<'a, T: MerkleNode + TreeHash + 'a>(
+    elements: impl Iterator<Item = &'a T>,
+    limit: usize,
+    is_list: bool,
+) -> NodeKind<'a> {
+    let elements = elements.collect::<Vec<_>>();
+    if matches!(T::tree_hash_type(), TreeHashType::Basic) {
+        let bytes = elements
+            .iter()
+            .flat_map(|element| element.tree_hash_packed_encoding())
+            .collect::<Vec<_>>();
+        let chunks = bytes
+            .chunks(BYTES_PER_CHUNK)
+            .map(|chunk| {
+                let mut padded = [0; BYTES_PER_CHUNK];
+                padded
+                    .iter_mut()
+                    .zip(chunk)
+                    .for_each(|(dst, src)| *dst = *src);
+                Hash256::from(padded)
+            })
+            .collect();
+        NodeKind::Packed {
+            chunks,
+            len: elements.len(),
+            elements_per_chunk: T::tree_hash_packing_factor(),
+            limit,
+            is_list,
+        }
+    } else {
+        NodeKind::Composite {
+            elements: elements
+                .into_iter()
+                .map(|element| element as &dyn MerkleNode)
+                .collect(),
+            limit,
+            is_list,
+        }
+    }
+}
+
+impl<T: milhouse::Value + Send + Sync + MerkleNode, N: Unsigned> MerkleNode for List<T, N> {
+    fn merkle_root(&self) -> Hash256 {
+        self.tree_hash_root()
+    }
+
+    fn merkle_node(&self) -> NodeKind<'_> {
+        sequence_node(self.iter(), N::to_usize(), true)
+    }
+}
+
+impl<T: milhouse::Value + Send + Sync + MerkleNode, N: Unsigned> MerkleNode for Vector<T, N> {
+    fn merkle_root(&self) -> Hash256 {
+        self.tree_hash_root()
+    }
+
+    fn merkle_node(&self) -> NodeKind<'_> {
+        sequence_node(self.iter(), N::to_usize(), false)
+    }
+}
+
+impl<T: TreeHash + MerkleNode, N: Unsigned> MerkleNode for VariableList<T, N> {
+    fn merkle_root(&self) -> Hash256 {
+        self.tree_hash_root()
+    }
+
+    fn merkle_node(&self) -> NodeKind<'_> {
+        sequence_node(self.iter(), N::to_usize(), true)
+    }
+}
+
+impl<T: TreeHash + MerkleNode, N: Unsigned> MerkleNode for FixedVector<T, N> {
+    fn merkle_root(&self) -> Hash256 {
+        self.tree_hash_root()
+    }
+
+    fn merkle_node(&self) -> NodeKind<'_> {
+        sequence_node(self.iter(), N::to_usize(), false)
+    }
+}
+
+impl<T: MerkleNode> MerkleNode for Arc<T> {
+    fn merkle_root(&self) -> Hash256 {
+        self.as_ref().merkle_root()
+    }
+
+    fn merkle_node(&self) -> NodeKind<'_> {
+        self.as_ref().merkle_node()
+    }
+}
+
+impl<N: Unsigned + Clone> MerkleNode for BitList<N> {
+    fn merkle_root(&self) -> Hash256 {
+        self.tree_hash_root()
+    }
+
+    fn merkle_node(&self) -> NodeKind<'_> {
+        NodeKind::Leaf
+    }
+}
+
+impl<N: Unsigned + Clone> MerkleNode for BitVector<N> {
+    fn merkle_root(&self) -> Hash256 {
+        self.tree_hash_root()
+    }
+
+    fn merkle_node(&self) -> NodeKind<'_> {
+        NodeKind::Leaf
+    }
+}
+
+impl_leaf_merkle_node!(
+    u8,
+    u64,
+    bool,
+    [u8; 4],
+    Hash256,
+    Address,
+    Uint256,
+    Slot,
+    Epoch,
+    ExecutionBlockHash,
+    PublicKeyBytes,
+    Signature,
+    SignatureBytes,
+    AggregateSignature,
+    Graffiti,
+    KzgCommitment,
+    ParticipationFlags,
+);
+
+impl_container_merkle_node!(Fork, [previous_version, current_version, epoch]);
+impl_container_merkle_node!(
+    BeaconBlockHeader,
+    [slot, proposer_index, parent_root, state_root, body_root]
+);
+impl_container_merkle_node!(SignedBeaconBlockHeader, [message, signature]);
+impl_container_merkle_node!(Eth1Data, [deposit_root, deposit_count, block_hash]);
+impl_container_merkle_node!(Checkpoint, [epoch, root]);
+impl_container_merkle_node!(
+    Validator,
+    [
+        pubkey,
+        withdrawal_credentials,
+        effective_balance,
+        slashed,
+        activation_eligibility_epoch,
+        activation_epoch,
+        exit_epoch,
+        withdrawable_epoch,
+    ]
+);
+impl_container_merkle_node!(<E> SyncCommittee<E>, [pubkeys, aggregate_pubkey]);
+impl_container_merkle_node!(<E> SyncAggregate<E>, [sync_committee_bits, sync_committee_signature]);
+impl_container_merkle_node!(Withdrawal, [index, validator_index, address, amount]);
+impl_container_merkle_node!(
+    DepositRequest,
+    [pubkey, withdrawal_credentials, amount, signature, index]
+);
+impl_container_merkle_node!(
+    ExecutionLayerWithdrawalRequest,
+    [source_address, validator_pubkey, amount]
+);
+impl_container_merkle_node!(PendingBalanceDeposit, [index, amount]);
+impl_container_merkle_node!(
+    PendingPartialWithdrawal,
+    [index, amount, withdrawable_epoch]
+);
+impl_container_merkle_node!(PendingConsolidation, [source_index, target_index]);
+impl_container_merkle_node!(
+    <E> PendingAttestation<E>,
+    [aggregation_bits, data, inclusion_delay, proposer_index]
+);
+impl_container_merkle_node!(
+    AttestationData,
+    [slot, index, beacon_block_root, source, target]
+);
+impl_container_merkle_node!(<E> AttestationBase<E>, [aggregation_bits, data, signature]);
+impl_container_merkle_node!(
+    <E> AttestationElectra<E>,
+    [aggregation_bits, data, committee_bits, signature]
+);
+impl_container_merkle_node!(<E> IndexedAttestationBase<E>, [attesting_indices, data, signature]);
+impl_container_merkle_node!(<E> IndexedAttestationElectra<E>, [attesting_indices, data, signature]);
+impl_container_merkle_node!(<E> AttesterSlashingBase<E>, [attestation_1, attestation_2]);
+impl_container_merkle_node!(<E> AttesterSlashingElectra<E>, [attestation_1, attestation_2]);
+impl_container_merkle_node!(ProposerSlashing, [signed_header_1, signed_header_2]);
+impl_container_merkle_node!(Deposit, [proof, data]);
+impl_container_merkle_node!(
+    DepositData,
+    [pubkey, withdrawal_credentials, amount, signature]
+);
+impl_container_merkle_node!(VoluntaryExit, [epoch, validator_index]);
+impl_container_merkle_node!(SignedVoluntaryExit, [message, signature]);
+impl_container_merkle_node!(
+    BlsToExecutionChange,
+    [validator_index, from_bls_pubkey, to_execution_address]
+);
+impl_container_merkle_node!(SignedBlsToExecutionChange, [message, signature]);
+impl_container_merkle_node!(Consolidation, [source_index, target_index, epoch]);
+impl_container_merkle_node!(SignedConsolidation, [message, signature]);
+impl_container_merkle_node!(
+    <E> ExecutionPayloadHeaderBellatrix<E>,
+    [
+        parent_hash,
+        fee_recipient,
+        state_root,
+        receipts_root,
+        logs_bloom,
+        prev_randao,
+        block_number,
+        gas_limit,
+        gas_used,
+        timestamp,
+        extra_data,
+        base_fee_per_gas,
+        block_hash,
+        transactions_root,
+    ]
+);
+impl_container_merkle_node!(
+    <E> ExecutionPayloadHeaderCapella<E>,
+    [
+        parent_hash,
+        fee_recipient,
+        state_root,
+        receipts_root,
+        logs_bloom,
+        prev_randao,
+        block_number,
+        gas_limit,
+        gas_used,
+        timestamp,
+        extra_data,
+        base_fee_per_gas,
+        block_hash,
+        transactions_root,
+        withdrawals_root,
+    ]
+);
+impl_container_merkle_node!(
+    <E> ExecutionPayloadHeaderDeneb<E>,
+    [
+        parent_hash,
+        fee_recipient,
+        state_root,
+        receipts_root,
+        logs_bloom,
+        prev_randao,
+        block_number,
+        gas_limit,
+        gas_used,
+        timestamp,
+        extra_data,
+        base_fee_per_gas,
+        block_hash,
+        transactions_root,
+        withdrawals_root,
+        blob_gas_used,
+        excess_blob_gas,
+    ]
+);
+impl_container_merkle_node!(
+    <E> ExecutionPayloadHeaderElectra<E>,
+    [
+        parent_hash,
+        fee_recipient,
+        state_root,
+        receipts_root,
+        logs_bloom,
+        prev_randao,
+        block_number,
+        gas_limit,
+        gas_used,
+        timestamp,
+        extra_data,
+        base_fee_per_gas,
+        block_hash,
+        transactions_root,
+        withdrawals_root,
+        blob_gas_used,
+        excess_blob_gas,
+        deposit_requests_root,
+        withdrawal_requests_root,
+    ]
+);
+impl_container_merkle_node!(
+    <E> ExecutionPayloadBellatrix<E>,
+    [
+        parent_hash,
+        fee_recipient,
+        state_root,
+        receipts_root,
+        logs_bloom,
+        prev_randao,
+        block_number,
+        gas_limit,
+        gas_used,
+        timestamp,
+        extra_data,
+        base_fee_per_gas,
+        block_hash,
+        transactions,
+    ]
+);
+impl_container_merkle_node!(
+    <E> ExecutionPayloadCapella<E>,
+    [
+        parent_hash,
+        fee_recipient,
+        state_root,
+        receipts_root,
+        logs_bloom,
+        prev_randao,
+        block_number,
+        gas_limit,
+        gas_used,
+        timestamp,
+        extra_data,
+        base_fee_per_gas,
+        block_hash,
+        transactions,
+        withdrawals,
+    ]
+);
+impl_container_merkle_node!(
+    <E> ExecutionPayloadDeneb<E>,
+    [
+        parent_hash,
+        fee_recipient,
+        state_root,
+        receipts_root,
+        logs_bloom,
+        prev_randao,
+        block_number,
+        gas_limit,
+        gas_used,
+        timestamp,
+        extra_data,
+        base_fee_per_gas,
+        block_hash,
+        transactions,
+        withdrawals,
+        blob_gas_used,
+        excess_blob_gas,
+    ]
+);
+impl_container_merkle_node!(
+    <E> ExecutionPayloadElectra<E>,
+    [
+        parent_hash,
+        fee_recipient,
+        state_root,
+        receipts_root,
+        logs_bloom,
+        prev_randao,
+        block_number,
+        gas_limit,
+        gas_used,
+        timestamp,
+        extra_data,
+        base_fee_per_gas,
+        block_hash,
+        transactions,
+        withdrawals,
+        blob_gas_used,
+        excess_blob_gas,
+        deposit_requests,
+        withdrawal_requests,
+    ]
+);
+
+/// Implement `MerkleNode` for full payloads, which hash to the root of the execution payload.
+macro_rules! impl_full_payload_merkle_node {
+    ($($ty: ident),* $(,)?) => {
+        $(
+            impl<E: EthSpec> MerkleNode for $ty<E> {
+                fn merkle_root(&self) -> Hash256 {
+                    self.execution_payload.merkle_root()
+                }
+
+                fn merkle_node(&self) -> NodeKind<'_> {
+                    self.execution_payload.merkle_node()
+                }
+            }
+        )*
+    };
+}
+
+impl_full_payload_merkle_node!(
+    FullPayloadBellatrix,
+    FullPayloadCapella,
+    FullPayloadDeneb,
+    FullPayloadElectra
+);
+
+impl_container_merkle_node!(
+    <E> BeaconBlockBodyBase<E, FullPayload<E>>,
+    [
+        randao_reveal,
+        eth1_data,
+        graffiti,
+        proposer_slashings,
+        attester_slashings,
+        attestations,
+        deposits,
+        voluntary_exits,
+    ]
+);
+impl_container_merkle_node!(
+    <E> BeaconBlockBodyAltair<E, FullPayload<E>>,
+    [
+        randao_reveal,
+        eth1_data,
+        graffiti,
+        proposer_slashings,
+        attester_slashings,
+        attestations,
+        deposits,
+        voluntary_exits,
+        sync_aggregate,
+    ]
+);
+impl_container_merkle_node!(
+    <E> BeaconBlockBodyBellatrix<E, FullPayload<E>>,
+    [
+        randao_reveal,
+        eth1_data,
+        graffiti,
+        proposer_slashings,
+        attester_slashings,
+        attestations,
+        deposits,
+        voluntary_exits,
+        sync_aggregate,
+        execution_payload,
+    ]
+);
+impl_container_merkle_node!(
+    <E> BeaconBlockBodyCapella<E, FullPayload<E>>,
+    [
+        randao_reveal,
+        eth1_data,
+        graffiti,
+        proposer_slashings,
+        attester_slashings,
+        attestations,
+        deposits,
+        voluntary_exits,
+        sync_aggregate,
+        execution_payload,
+        bls_to_execution_changes,
+    ]
+);
+impl_container_merkle_node!(
+    <E> BeaconBlockBodyDeneb<E, FullPayload<E>>,
+    [
+        randao_reveal,
+        eth1_data,
+        graffiti,
+        proposer_slashings,
+        attester_slashings,
+        attestations,
+        deposits,
+        voluntary_exits,
+        sync_aggregate,
+        execution_payload,
+        bls_to_execution_changes,
+        blob_kzg_commitments,
+    ]
+);
+impl_container_merkle_node!(
+    <E> BeaconBlockBodyElectra<E, FullPayload<E>>,
+    [
+        randao_reveal,
+        eth1_data,
+        graffiti,
+        proposer_slashings,
+        attester_slashings,
+        attestations,
+        deposits,
+        voluntary_exits,
+        sync_aggregate,
+        execution_payload,
+        bls_to_execution_changes,
+        blob_kzg_commitments,
+        consolidations,
+    ]
+);
+impl_container_merkle_node!(
+    <E> BeaconBlockBase<E>,
+    [slot, proposer_index, parent_root, state_root, body]
+);
+impl_container_merkle_node!(
+    <E> BeaconBlockAltair<E>,
+    [slot, proposer_index, parent_root, state_root, body]
+);
+impl_container_merkle_node!(
+    <E> BeaconBlockBellatrix<E>,
+    [slot, proposer_index, parent_root, state_root, body]
+);
+impl_container_merkle_node!(
+    <E> BeaconBlockCapella<E>,
+    [slot, proposer_index, parent_root, state_root, body]
+);
+impl_container_merkle_node!(
+    <E> BeaconBlockDeneb<E>,
+    [slot, proposer_index, parent_root, state_root, body]
+);
+impl_container_merkle_node!(
+    <E> BeaconBlockElectra<E>,
+    [slot, proposer_index, parent_root, state_root, body]
+);
+
+impl_container_merkle_node!(
+    <E> BeaconStateBase<E>,
+    [
+        genesis_time,
+        genesis_validators_root,
+        slot,
+        fork,
+        latest_block_header,
+        block_roots,
+        state_roots,
+        historical_roots,
+        eth1_data,
+        eth1_data_votes,
+        eth1_deposit_index,
+        validators,
+        balances,
+        randao_mixes,
+        slashings,
+        previous_epoch_attestations,
+        current_epoch_attestations,
+        justification_bits,
+        previous_justified_checkpoint,
+        current_justified_checkpoint,
+        finalized_checkpoint,
+    ]
+);
+impl_container_merkle_node!(
+    <E> BeaconStateAltair<E>,
+    [
+        genesis_time,
+        genesis_validators_root,
+        slot,
+        fork,
+        latest_block_header,
+        block_roots,
+        state_roots,
+        historical_roots,
+        eth1_data,
+        eth1_data_votes,
+        eth1_deposit_index,
+        validators,
+        balances,
+        randao_mixes,
+        slashings,
+        previous_epoch_participation,
+        current_epoch_participation,
+        justification_bits,
+        previous_justified_checkpoint,
+        current_justified_checkpoint,
+        finalized_checkpoint,
+        inactivity_scores,
+        current_sync_committee,
+        next_sync_committee,
+    ]
+);
+impl_container_merkle_node!(
+    <E> BeaconStateBellatrix<E>,
+    [
+        genesis_time,
+        genesis_validators_root,
+        slot,
+        fork,
+        latest_block_header,
+        block_roots,
+        state_roots,
+        historical_roots,
+        eth1_data,
+        eth1_data_votes,
+        eth1_deposit_index,
+        validators,
+        balances,
+        randao_mixes,
+        slashings,
+        previous_epoch_participation,
+        current_epoch_participation,
+        justification_bits,
+        previous_justified_checkpoint,
+        current_justified_checkpoint,
+        finalized_checkpoint,
+        inactivity_scores,
+        current_sync_committee,
+        next_sync_committee,
+        latest_execution_payload_header,
+    ]
+);
+impl_container_merkle_node!(
+    <E> BeaconStateCapella<E>,
+    [
+        genesis_time,
+        genesis_validators_root,
+        slot,
+        fork,
+        latest_block_header,
+        block_roots,
+        state_roots,
+        historical_roots,
+        eth1_data,
+        eth1_data_votes,
+        eth1_deposit_index,
+        validators,
+        balances,
+        randao_mixes,
+        slashings,
+        previous_epoch_participation,
+        current_epoch_participation,
+        justification_bits,
+        previous_justified_checkpoint,
+        current_justified_checkpoint,
+        finalized_checkpoint,
+        inactivity_scores,
+        current_sync_committee,
+        next_sync_committee,
+        latest_execution_payload_header,
+        next_withdrawal_index,
+        next_withdrawal_validator_index,
+        historical_summaries,
+    ]
+);
+impl_container_merkle_node!(
+    <E> BeaconStateDeneb<E>,
+    [
+        genesis_time,
+        genesis_validators_root,
+        slot,
+        fork,
+        latest_block_header,
+        block_roots,
+        state_roots,
+        historical_roots,
+        eth1_data,
+        eth1_data_votes,
+        eth1_deposit_index,
+        validators,
+        balances,
+        randao_mixes,
+        slashings,
+        previous_epoch_participation,
+        current_epoch_participation,
+        justification_bits,
+        previous_justified_checkpoint,
+        current_justified_checkpoint,
+        finalized_checkpoint,
+        inactivity_scores,
+        current_sync_committee,
+        next_sync_committee,
+        latest_execution_payload_header,
+        next_withdrawal_index,
+        next_withdrawal_validator_index,
+        historical_summaries,
+    ]
+);
+impl_container_merkle_node!(
+    <E> BeaconStateElectra<E>,
+    [
+        genesis_time,
+        genesis_validators_root,
+        slot,
+        fork,
+        latest_block_header,
+        block_roots,
+        state_roots,
+        historical_roots,
+        eth1_data,
+        eth1_data_votes,
+        eth1_deposit_index,
+        validators,
+        balances,
+        randao_mixes,
+        slashings,
+        previous_epoch_participation,
+        current_epoch_participation,
+        justification_bits,
+        previous_justified_checkpoint,
+        current_justified_checkpoint,
+        finalized_checkpoint,
+        inactivity_scores,
+        current_sync_committee,
+        next_sync_committee,
+        latest_execution_payload_header,
+        next_withdrawal_index,
+        next_withdrawal_validator_index,
+        historical_summaries,
+        deposit_requests_start_index,
+        deposit_balance_to_consume,
+        exit_balance_to_consume,
+        earliest_exit_epoch,
+        consolidation_balance_to_consume,
+        earliest_consolidation_epoch,
+        pending_balance_deposits,
+        pending_partial_withdrawals,
+        pending_consolidations,
+    ]
+);
+
+impl<'a, E: EthSpec> MerkleNode for BeaconBlockRef<'a, E> {
+    fn merkle_root(&self) -> Hash256 {
+        self.tree_hash_root()
+    }
+
+    fn merkle_node(&self) -> NodeKind<'_> {
+        match *self {
+            BeaconBlockRef::Base(block) => block.merkle_node(),
+            BeaconBlockRef::Altair(block) => block.merkle_node(),
+            BeaconBlockRef::Bellatrix(block) => block.merkle_node(),
+            BeaconBlockRef::Capella(block) => block.merkle_node(),
+            BeaconBlockRef::Deneb(block) => block.merkle_node(),
+            BeaconBlockRef::Electra(block) => block.merkle_node(),
+        }
+    }
+}
+
+impl<E: EthSpec> MerkleNode for BeaconBlock<E> {
+    fn merkle_root(&self) -> Hash256 {
+        self.tree_hash_root()
+    }
+
+    fn merkle_node(&self) -> NodeKind<'_> {
+        match self {
+            BeaconBlock::Base(block) => block.merkle_node(),
+            BeaconBlock::Altair(block) => block.merkle_node(),
+            BeaconBlock::Bellatrix(block) => block.merkle_node(),
+            BeaconBlock::Capella(block) => block.merkle_node(),
+            BeaconBlock::Deneb(block) => block.merkle_node(),
+            BeaconBlock::Electra(block) => block.merkle_node(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+    use ssz_types::typenum::U4;
+
+    type E = MinimalEthSpec;
+
+    fn path(s: &str) -> Vec<PathElement> {
+        SszPath::from_str(s).unwrap().0
+    }
+
+    /// Prove `gindices` of `node` and check the proof against its `tree_hash_root`.
+    fn prove(node: &dyn MerkleNode, gindices: Vec<u64>) -> Multiproof {
+        let proof = Multiproof::new(node, gindices).unwrap();
+        assert!(proof.verify(node.merkle_root()).unwrap());
+        proof
+    }
+
+    /// Check the declared fields of every container reachable from `node` against the roots
+    /// computed by `TreeHash`, by proving all fields at once.
+    fn check_containers(node: &dyn MerkleNode) {
+        match node.merkle_node() {
+            NodeKind::Container(fields) => {
+                let depth = fields.len().next_power_of_two().trailing_zeros() as usize;
+                let gindices = (0..fields.len() as u64)
+                    .map(|i| chunk_gindex(depth, i).unwrap())
+                    .collect();
+                let proof = prove(node, gindices);
+                for ((_, field), leaf) in fields.iter().zip(&proof.leaves) {
+                    assert_eq!(field.merkle_root(), *leaf);
+                    check_containers(*field);
+                }
+            }
+            NodeKind::Composite { elements, .. } => {
+                if let Some(element) = elements.first() {
+                    check_containers(*element);
+                }
+            }
+            NodeKind::Leaf | NodeKind::Packed { .. } => {}
+        }
+    }
+
+    /// Check that the root of every container reachable from `node`, merkleized from the roots of
+    /// its declared fields, is its `tree_hash_root`.
+    fn check_container_roots(node: &dyn MerkleNode) {
+        match node.merkle_node() {
+            NodeKind::Container(fields) => {
+                let leaves = fields
+                    .iter()
+                    .flat_map(|(_, field)| field.merkle_root().to_fixed_bytes())
+                    .collect::<Vec<_>>();
+                let names = fields.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+                assert_eq!(
+                    tree_hash::merkle_root(&leaves, fields.len()),
+                    node.merkle_root(),
+                    "container with fields {names:?}"
+                );
+                for (_, field) in fields {
+                    check_container_roots(field);
+                }
+            }
+            NodeKind::Composite { elements, .. } => {
+                if let Some(element) = elements.first() {
+                    check_container_roots(*element);
+                }
+            }
+            NodeKind::Leaf | NodeKind::Packed { .. } => {}
+        }
+    }
+
+    /// Check the roots of the containers reachable from the state and block of `fork_name`.
+    fn check_fork_container_roots(fork_name: ForkName) {
+        let state = states()
+            .into_iter()
+            .find(|state| state.fork_name_unchecked() == fork_name)
+            .unwrap();
+        let block = blocks()
+            .into_iter()
+            .find(|block| block.fork_name_unchecked() == fork_name)
+            .unwrap();
+        check_container_roots(&state);
+        check_container_roots(&block);
+    }
+
+    /// Push a random element to each of the lists which is empty, so that the containers they hold
+    /// are checked.
+    macro_rules! fill {
+        ($rng:ident, $($list:expr),+ $(,)?) => {
+            $(
+                if $list.is_empty() {
+                    $list.push(TestRandom::random_for_test(&mut $rng)).unwrap();
+                }
+            )+
+        };
+    }
+
+    /// A state of every fork, with at least one element in each list of containers.
+    fn states() -> Vec<BeaconState<E>> {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+
+        let mut base = BeaconStateBase::<E>::random_for_test(&mut rng);
+        fill!(
+            rng,
+            base.historical_roots,
+            base.eth1_data_votes,
+            base.validators,
+            base.balances,
+            base.previous_epoch_attestations,
+            base.current_epoch_attestations,
+        );
+        let mut altair = BeaconStateAltair::<E>::random_for_test(&mut rng);
+        fill!(
+            rng,
+            altair.historical_roots,
+            altair.eth1_data_votes,
+            altair.validators,
+            altair.balances,
+            altair.previous_epoch_participation,
+            altair.current_epoch_participation,
+            altair.inactivity_scores,
+        );
+        let mut bellatrix = BeaconStateBellatrix::<E>::random_for_test(&mut rng);
+        fill!(
+            rng,
+            bellatrix.historical_roots,
+            bellatrix.eth1_data_votes,
+            bellatrix.validators,
+            bellatrix.balances,
+            bellatrix.previous_epoch_participation,
+            bellatrix.current_epoch_participation,
+            bellatrix.inactivity_scores,
+        );
+        let mut capella = BeaconStateCapella::<E>::random_for_test(&mut rng);
+        fill!(
+            rng,
+            capella.historical_roots,
+            capella.eth1_data_votes,
+            capella.validators,
+            capella.balances,
+            capella.previous_epoch_participation,
+            capella.current_epoch_participation,
+            capella.inactivity_scores,
+            capella.historical_summaries,
+        );
+        let mut deneb = BeaconStateDeneb::<E>::random_for_test(&mut rng);
+        fill!(
+            rng,
+            deneb.historical_roots,
+            deneb.eth1_data_votes,
+            deneb.validators,
+            deneb.balances,
+            deneb.previous_epoch_participation,
+            deneb.current_epoch_participation,
+            deneb.inactivity_scores,
+            deneb.historical_summaries,
+        );
+        let mut electra = BeaconStateElectra::<E>::random_for_test(&mut rng);
+        fill!(
+            rng,
+            electra.historical_roots,
+            electra.eth1_data_votes,
+            electra.validators,
+            electra.balances,
+            electra.previous_epoch_participation,
+            electra.current_epoch_participation,
+            electra.inactivity_scores,
+            electra.historical_summaries,
+            electra.pending_balance_deposits,
+            electra.pending_partial_withdrawals,
+            electra.pending_consolidations,
+        );
+
+        let mut states = vec![
+            BeaconState::Base(base),
+            BeaconState::Altair(altair),
+            BeaconState::Bellatrix(bellatrix),
+            BeaconState::Capella(capella),
+            BeaconState::Deneb(deneb),
+            BeaconState::Electra(electra),
+        ];
+        for state in &mut states {
+            state.apply_pending_mutations().unwrap();
+        }
+        states
+    }
+
+    /// A block of every fork, with at least one element in each list of containers.
+    fn blocks() -> Vec<BeaconBlock<E>> {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+
+        let mut base = BeaconBlockBase::<E>::random_for_test(&mut rng);
+        fill!(
+            rng,
+            base.body.proposer_slashings,
+            base.body.attester_slashings,
+            base.body.attestations,
+            base.body.deposits,
+            base.body.voluntary_exits,
+        );
+        let mut altair = BeaconBlockAltair::<E>::random_for_test(&mut rng);
+        fill!(
+            rng,
+            altair.body.proposer_slashings,
+            altair.body.attester_slashings,
+            altair.body.attestations,
+            altair.body.deposits,
+            altair.body.voluntary_exits,
+        );
+        let mut bellatrix = BeaconBlockBellatrix::<E>::random_for_test(&mut rng);
+        fill!(
+            rng,
+            bellatrix.body.proposer_slashings,
+            bellatrix.body.attester_slashings,
+            bellatrix.body.attestations,
+            bellatrix.body.deposits,
+            bellatrix.body.voluntary_exits,
+            bellatrix
+                .body
+                .execution_payload
+                .execution_payload
+                .transactions,
+        );
+        let mut capella = BeaconBlockCapella::<E>::random_for_test(&mut rng);
+        fill!(
+            rng,
+            capella.body.proposer_slashings,
+            capella.body.attester_slashings,
+            capella.body.attestations,
+            capella.body.deposits,
+            capella.body.voluntary_exits,
+            capella
+                .body
+                .execution_payload
+                .execution_payload
+                .transactions,
+            capella.body.execution_payload.execution_payload.withdrawals,
+            capella.body.bls_to_execution_changes,
+        );
+        let mut deneb = BeaconBlockDeneb::<E>::random_for_test(&mut rng);
+        fill!(
+            rng,
+            deneb.body.proposer_slashings,
+            deneb.body.attester_slashings,
+            deneb.body.attestations,
+            deneb.body.deposits,
+            deneb.body.voluntary_exits,
+            deneb.body.execution_payload.execution_payload.transactions,
+            deneb.body.execution_payload.execution_payload.withdrawals,
+            deneb.body.bls_to_execution_changes,
+            deneb.body.blob_kzg_commitments,
+        );
+        let mut electra = BeaconBlockElectra::<E>::random_for_test(&mut rng);
+        fill!(
+            rng,
+            electra.body.proposer_slashings,
+            electra.body.attester_slashings,
+            electra.body.attestations,
+            electra.body.deposits,
+            electra.body.voluntary_exits,
+            electra
+                .body
+                .execution_payload
+                .execution_payload
+                .transactions,
+            electra.body.execution_payload.execution_payload.withdrawals,
+            electra
+                .body
+                .execution_payload
+                .execution_payload
+                .deposit_requests,
+            electra
+                .body
+                .execution_payload
+                .execution_payload
+                .withdrawal_requests,
+            electra.body.bls_to_execution_changes,
+            electra.body.blob_kzg_commitments,
+            electra.body.consolidations,
+        );
+
+        vec![
+            BeaconBlock::Base(base),
+            BeaconBlock::Altair(altair),
+            BeaconBlock::Bellatrix(bellatrix),
+            BeaconBlock::Capella(capella),
+            BeaconBlock::Deneb(deneb),
+            BeaconBlock::Electra(electra),
+        ]
+    }
+
+    #[test]
+    fn helper_indices_match_spec() {
+        // Proving node 9 of a depth 3 tree requires its sibling and its ancestors' siblings.
+        assert_eq!(helper_indices(&[9]).unwrap(), vec![8, 5, 3]);
+        // Siblings which are both proven are not helpers.
+        assert_eq!(helper_indices(&[8, 9]).unwrap(), vec![5, 3]);
+        assert_eq!(helper_indices(&[1]).unwrap(), Vec::<u64>::new());
+        assert!(helper_indices(&[0]).is_err());
+    }
+
+    #[test]
+    fn concat_generalized_indices_example() {
+        assert_eq!(concat_generalized_indices(2, 3).unwrap(), 5);
+        assert_eq!(concat_generalized_indices(1, 7).unwrap(), 7);
+        assert_eq!(concat_generalized_indices(5, 1).unwrap(), 5);
+        assert!(concat_generalized_indices(1 << 40, 1 << 30).is_err());
+    }
+
+    #[test]
+    fn path_parsing_round_trips() {
+        for s in ["validators.5.effective_balance", "balances.__len__", "slot"] {
+            assert_eq!(SszPath::from_str(s).unwrap().to_string(), s);
+        }
+        assert!(SszPath::from_str("validators..slashed").is_err());
+    }
+
+    #[test]
+    fn validator_field_proof() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let validator = Validator::random_for_test(&mut rng);
+
+        let gindex = generalized_index(&validator, &path("effective_balance")).unwrap();
+        // 8 fields, the effective balance is the third.
+        assert_eq!(gindex, 10);
+        let proof = prove(&validator, vec![gindex]);
+        assert_eq!(
+            proof.leaves,
+            vec![validator.effective_balance.tree_hash_root()]
+        );
+        assert_eq!(proof.proof.len(), 3);
+    }
+
+    #[test]
+    fn packed_list_proofs() {
+        let balances = VariableList::<u64, U4>::new(vec![1, 2, 3]).unwrap();
+
+        // Four balances fit in a single chunk, so the data root is the chunk itself.
+        let gindex = generalized_index(&balances, &path("2")).unwrap();
+        assert_eq!(gindex, 2);
+        let proof = prove(&balances, vec![gindex]);
+        assert_eq!(
+            proof.leaves,
+            vec![Hash256::from_slice(
+                &[1u64, 2, 3, 0]
+                    .iter()
+                    .flat_map(|b| b.to_le_bytes())
+                    .collect::<Vec<_>>()
+            )]
+        );
+
+        let gindex = generalized_index(&balances, &path("__len__")).unwrap();
+        assert_eq!(gindex, 3);
+        let proof = prove(&balances, vec![gindex]);
+        assert_eq!(proof.leaves, vec![3u64.tree_hash_root()]);
+
+        assert_eq!(
+            generalized_index(&balances, &path("4")),
+            Err(Error::IndexOutOfBounds { index: 4, limit: 4 })
+        );
+        assert!(matches!(
+            tree_nodes(&balances, &[4]),
+            Err(Error::UnreachableGeneralizedIndex(4))
+        ));
+    }
+
+    #[test]
+    fn cached_layers_are_reused() {
+        type N = ssz_types::typenum::U8192;
+        let balances = VariableList::<u64, N>::new((0..8000).collect()).unwrap();
+        let gindices = ["0", "4321", "__len__"]
+            .iter()
+            .map(|p| generalized_index(&balances, &path(p)).unwrap())
+            .collect::<Vec<_>>();
+
+        let mut cache = HashMap::<Hash256, Arc<ChunkLayers>>::new();
+        let proof = Multiproof::new_with_cache(&balances, gindices.clone(), &mut cache).unwrap();
+        assert_eq!(proof, prove(&balances, gindices.clone()));
+        assert_eq!(cache.len(), 1);
+        let layers = cache.get(&balances.tree_hash_root()).cloned().unwrap();
+
+        // A further proof of the same list uses the cached layers.
+        let proof = Multiproof::new_with_cache(&balances, gindices, &mut cache).unwrap();
+        assert!(proof.verify(balances.tree_hash_root()).unwrap());
+        assert_eq!(cache.len(), 1);
+        assert!(Arc::ptr_eq(
+            &layers,
+            cache.get(&balances.tree_hash_root()).unwrap()
+        ));
+
+        // Small lists are not cached.
+        let small = VariableList::<u64, N>::new(vec![1, 2, 3]).unwrap();
+        Multiproof::new_with_cache(&small, vec![2], &mut cache).unwrap();
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn state_matches_existing_single_proofs() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let state = BeaconState::Deneb(BeaconStateDeneb::<E>::random_for_test(&mut rng));
+
+        for gindex in [
+            light_client_update::CURRENT_SYNC_COMMITTEE_INDEX,
+            light_client_update::NEXT_SYNC_COMMITTEE_INDEX,
+            light_client_update::FINALIZED_ROOT_INDEX,
+        ] {
+            let branch = state.compute_merkle_proof(gindex).unwrap();
+            let proof = prove(&state, vec![gindex as u64]);
+            assert_eq!(proof.proof, branch);
+        }
+    }
+
+    #[test]
+    fn state_containers_of_every_fork() {
+        for state in states() {
+            check_containers(&state);
+        }
+    }
+
+    #[test]
+    fn state_multiproof() {
+        let state = states().pop().unwrap();
+
+        let paths = [
+            "slot",
+            "finalized_checkpoint.root",
+            "balances.__len__",
+            "validators.0.effective_balance",
+            "balances.0",
+            "historical_summaries.0",
+        ];
+        let gindices = paths
+            .iter()
+            .map(|p| generalized_index(&state, &path(p)).unwrap())
+            .collect::<Vec<_>>();
+        let proof = prove(&state, gindices);
+
+        assert_eq!(proof.leaves[0], state.slot().tree_hash_root());
+        assert_eq!(proof.leaves[1], state.finalized_checkpoint().root);
+        assert_eq!(
+            proof.leaves[2],
+            (state.balances().len() as u64).tree_hash_root()
+        );
+        assert_eq!(
+            proof.leaves[3],
+            state
+                .validators()
+                .get(0)
+                .unwrap()
+                .effective_balance
+                .tree_hash_root()
+        );
+        assert_eq!(
+            proof.leaves[5],
+            state
+                .historical_summaries()
+                .unwrap()
+                .get(0)
+                .unwrap()
+                .tree_hash_root()
+        );
+    }
+
+    #[test]
+    fn block_containers_of_every_fork() {
+        for block in blocks() {
+            check_containers(&block);
+            check_containers(&block.to_ref());
+        }
+    }
+
+    #[test]
+    fn container_roots_base() {
+        check_fork_container_roots(ForkName::Base);
+    }
+
+    #[test]
+    fn container_roots_altair() {
+        check_fork_container_roots(ForkName::Altair);
+    }
+
+    #[test]
+    fn container_roots_bellatrix() {
+        check_fork_container_roots(ForkName::Bellatrix);
+    }
+
+    #[test]
+    fn container_roots_capella() {
+        check_fork_container_roots(ForkName::Capella);
+    }
+
+    #[test]
+    fn container_roots_deneb() {
+        check_fork_container_roots(ForkName::Deneb);
+    }
+
+    #[test]
+    fn container_roots_electra() {
+        check_fork_container_roots(ForkName::Electra);
+    }
+
+    #[test]
+    fn block_multiproof() {
+        let block = blocks().pop().unwrap();
+
+        let payload = &block
+            .body_electra()
+            .unwrap()
+            .execution_payload
+            .execution_payload;
+        let paths = [
+            "body.execution_payload.block_hash",
+            "body.execution_payload.withdrawals.__len__",
+            "body.execution_payload.withdrawals.0.amount",
+            "body.execution_payload.deposit_requests.0",
+        ];
+        let gindices = paths
+            .iter()
+            .map(|p| generalized_index(&block, &path(p)).unwrap())
+            .collect::<Vec<_>>();
+        let proof = prove(&block, gindices);
+
+        assert_eq!(proof.leaves[0], payload.block_hash.tree_hash_root());
+        assert_eq!(
+            proof.leaves[1],
+            (payload.withdrawals.len() as u64).tree_hash_root()
+        );
+        assert_eq!(
+            proof.leaves[2],
+            payload.withdrawals[0].amount.tree_hash_root()
+        );
+        assert_eq!(
+            proof.leaves[3],
+            payload.deposit_requests[0].tree_hash_root()
+        );
+
+        // The same proof is computed through a reference to the block.
+        assert_eq!(
+            Multiproof::new(&block.to_ref(), proof.gindices.clone()).unwrap(),
+            proof
+        );
+    }
+}
@@ -2,6 +2,8 @@ mod kzg_commitment;
 mod kzg_proof;
 mod trusted_setup;
 
+use derivative::Derivative;
+use rust_eth_kzg::{DASContext, UsePrecomp};
 use std::fmt::Debug;
 
 pub use crate::{
@@ -13,10 +15,14 @@ pub use c_kzg::{
     Blob, Bytes32, Bytes48, KzgSettings, BYTES_PER_BLOB, BYTES_PER_COMMITMENT,
     BYTES_PER_FIELD_ELEMENT, BYTES_PER_PROOF, FIELD_ELEMENTS_PER_BLOB,
 };
+pub use rust_eth_kzg::{Cell, CellIndex as CellID, CellRef, TrustedSetup as PeerDASTrustedSetup};
+
 #[derive(Debug)]
 pub enum Error {
     /// An error from the underlying kzg library.
     Kzg(c_kzg::Error),
+    /// An error from the underlying PeerDAS kzg library.
+    PeerDASKZG(rust_eth_kzg::Error),
     /// The kzg verification failed
     KzgVerificationFailed,
     /// Misc indexing error
@@ -29,42 +35,41 @@ impl From<c_kzg::Error> for Error {
     }
 }
 
+/// The number of cells in an extended blob, i.e. the number of data columns.
 pub const CELLS_PER_EXT_BLOB: usize = 128;
 
-// TODO(das): use proper crypto once ckzg merges das branch
-#[allow(dead_code)]
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
-pub struct Cell {
-    bytes: [u8; 2048usize],
-}
+/// The number of cells that hold the original (non-extended) blob data.
+pub const CELLS_PER_BLOB: usize = CELLS_PER_EXT_BLOB / 2;
 
-impl Cell {
-    pub fn from_bytes(b: &[u8]) -> Result<Self, Error> {
-        Ok(Self {
-            bytes: b
-                .try_into()
-                .map_err(|_| Error::Kzg(c_kzg::Error::MismatchLength("".to_owned())))?,
-        })
-    }
-    pub fn into_inner(self) -> [u8; 2048usize] {
-        self.bytes
-    }
-}
+/// The size of a single cell in bytes.
+pub const BYTES_PER_CELL: usize = BYTES_PER_BLOB / CELLS_PER_BLOB;
+
+/// All the cells of an extended blob, along with the kzg proof for each cell.
+pub type CellsAndKzgProofs = ([Cell; CELLS_PER_EXT_BLOB], [KzgProof; CELLS_PER_EXT_BLOB]);
 
 /// A wrapper over a kzg library that holds the trusted setup parameters.
-#[derive(Debug)]
+///
+/// The blob methods are backed by `c-kzg`, while the PeerDAS cell methods are backed by
+/// `rust_eth_kzg`. Both are initialized from the same trusted setup.
+#[derive(Derivative)]
+#[derivative(Debug)]
 pub struct Kzg {
     trusted_setup: KzgSettings,
+    #[derivative(Debug = "ignore")]
+    context: DASContext,
 }
 
 impl Kzg {
     /// Load the kzg trusted setup parameters from a vec of G1 and G2 points.
     pub fn new_from_trusted_setup(trusted_setup: TrustedSetup) -> Result<Self, Error> {
+        let peerdas_trusted_setup = PeerDASTrustedSetup::from(&trusted_setup);
+
         Ok(Self {
             trusted_setup: KzgSettings::load_trusted_setup(
                 &trusted_setup.g1_points(),
                 &trusted_setup.g2_points(),
             )?,
+            context: DASContext::new(&peerdas_trusted_setup, UsePrecomp::No),
         })
     }
 
@@ -166,53 +171,108 @@ impl Kzg {
         .map_err(Into::into)
     }
 
-    /// Computes the cells and associated proofs for a given `blob` at index `index`.
-    #[allow(clippy::type_complexity)]
-    pub fn compute_cells_and_proofs(
+    /// Computes the cells and associated proofs for a given `blob`.
+    ///
+    /// The blob is extended to `CELLS_PER_EXT_BLOB` cells, the first `CELLS_PER_BLOB` of which
+    /// contain the original blob data.
+    pub fn compute_cells_and_proofs(&self, blob: &Blob) -> Result<CellsAndKzgProofs, Error> {
+        let (cells, proofs) = self
+            .context
+            .compute_cells_and_kzg_proofs(blob)
+            .map_err(Error::PeerDASKZG)?;
+
+        Ok((cells, proofs.map(KzgProof)))
+    }
+
+    /// Computes the cells of the extended blob for a given `blob`, without their proofs.
+    pub fn compute_cells(&self, blob: &Blob) -> Result<[Cell; CELLS_PER_EXT_BLOB], Error> {
+        self.compute_cells_and_proofs(blob).map(|(cells, _)| cells)
+    }
+
+    /// Verifies a single cell-proof-commitment triplet, where `column` is the data column index
+    /// of the cell in the extended blob.
+    pub fn verify_cell_proof(
         &self,
-        _blob: &Blob,
-    ) -> Result<
-        (
-            Box<[Cell; CELLS_PER_EXT_BLOB]>,
-            Box<[KzgProof; CELLS_PER_EXT_BLOB]>,
-        ),
-        Error,
-    > {
-        // TODO(das): use proper crypto once ckzg merges das branch
-        let cells = Box::new(core::array::from_fn(|_| Cell { bytes: [0u8; 2048] }));
-        let proofs = Box::new([KzgProof([0u8; BYTES_PER_PROOF]); CELLS_PER_EXT_BLOB]);
-        Ok((cells, proofs))
+        cell: CellRef<'_>,
+        kzg_proof: Bytes48,
+        column: CellID,
+        kzg_commitment: Bytes48,
+    ) -> Result<(), Error> {
+        self.verify_cell_proof_batch(&[cell], &[kzg_proof], vec![column], &[kzg_commitment])
     }
 
     /// Verifies a batch of cell-proof-commitment triplets.
     ///
-    /// Here, `coordinates` correspond to the (row, col) coordinate of the cell in the extended
-    /// blob "matrix". In the 1D extension, row corresponds to the blob index, and col corresponds
-    /// to the data column index.
+    /// Here, `columns` correspond to the data column index of each cell in the extended blob
+    /// "matrix", and `kzg_commitments` contains the commitment of the blob (row) that each cell
+    /// belongs to. All four slices must have the same length.
     pub fn verify_cell_proof_batch(
         &self,
-        _cells: &[Cell],
-        _kzg_proofs: &[Bytes48],
-        _coordinates: &[(u64, u64)],
-        _kzg_commitments: &[Bytes48],
+        cells: &[CellRef<'_>],
+        kzg_proofs: &[Bytes48],
+        columns: Vec<CellID>,
+        kzg_commitments: &[Bytes48],
     ) -> Result<(), Error> {
-        // TODO(das): use proper crypto once ckzg merges das branch
-        Ok(())
+        if cells.len() != kzg_proofs.len()
+            || cells.len() != columns.len()
+            || cells.len() != kzg_commitments.len()
+        {
+            return Err(Error::InconsistentArrayLength(format!(
+                "cells: {}, proofs: {}, columns: {}, commitments: {}",
+                cells.len(),
+                kzg_proofs.len(),
+                columns.len(),
+                kzg_commitments.len()
+            )));
+        }
+
+        let proofs = kzg_proofs.iter().map(|proof| proof.as_ref()).collect();
+        let commitments = kzg_commitments
+            .iter()
+            .map(|commitment| commitment.as_ref())
+            .collect();
+
+        match self
+            .context
+            .verify_cell_kzg_proof_batch(commitments, columns, cells.to_vec(), proofs)
+        {
+            Ok(()) => Ok(()),
+            Err(e) if e.invalid_proof() => Err(Error::KzgVerificationFailed),
+            Err(e) => Err(Error::PeerDASKZG(e)),
+        }
     }
 
-    pub fn cells_to_blob(&self, _cells: &[Cell; CELLS_PER_EXT_BLOB]) -> Result<Blob, Error> {
-        // TODO(das): use proper crypto once ckzg merges das branch
-        Ok(Blob::new([0u8; 131072usize]))
+    /// Recovers the original blob from the full set of cells of an extended blob.
+    ///
+    /// The extension is systematic, so the blob is simply the concatenation of the first
+    /// `CELLS_PER_BLOB` cells.
+    pub fn cells_to_blob(&self, cells: &[Cell; CELLS_PER_EXT_BLOB]) -> Result<Blob, Error> {
+        let mut blob_bytes = [0u8; BYTES_PER_BLOB];
+        for (chunk, cell) in blob_bytes
+            .chunks_exact_mut(BYTES_PER_CELL)
+            .zip(cells.iter().take(CELLS_PER_BLOB))
+        {
+            chunk.copy_from_slice(cell.as_ref());
+        }
+        Ok(Blob::new(blob_bytes))
     }
 
-    pub fn recover_all_cells(
+    /// Recovers all the cells of an extended blob, and recomputes the kzg proofs for each of
+    /// them, given at least `CELLS_PER_BLOB` distinct cells.
+    ///
+    /// `cell_ids` contains the column index of each cell in `cells`, and does not need to be
+    /// sorted.
+    pub fn recover_cells_and_compute_kzg_proofs(
         &self,
-        _cell_ids: &[u64],
-        _cells: &[Cell],
-    ) -> Result<Box<[Cell; CELLS_PER_EXT_BLOB]>, Error> {
-        // TODO(das): use proper crypto once ckzg merges das branch
-        let cells = Box::new(core::array::from_fn(|_| Cell { bytes: [0u8; 2048] }));
-        Ok(cells)
+        cell_ids: &[CellID],
+        cells: &[CellRef<'_>],
+    ) -> Result<CellsAndKzgProofs, Error> {
+        let (cells, proofs) = self
+            .context
+            .recover_cells_and_kzg_proofs(cell_ids.to_vec(), cells.to_vec())
+            .map_err(Error::PeerDASKZG)?;
+
+        Ok((cells, proofs.map(KzgProof)))
     }
 }
 
@@ -1,4 +1,5 @@
 use c_kzg::{BYTES_PER_G1_POINT, BYTES_PER_G2_POINT};
+use rust_eth_kzg::TrustedSetup as PeerDASTrustedSetup;
 use serde::{
     de::{self, Deserializer, Visitor},
     Deserialize, Serialize,
@@ -43,6 +44,29 @@ impl TrustedSetup {
     }
 }
 
+impl From<&TrustedSetup> for PeerDASTrustedSetup {
+    fn from(trusted_setup: &TrustedSetup) -> Self {
+        fn to_hex_strings<const N: usize>(points: &[[u8; N]]) -> Vec<String> {
+            points
+                .iter()
+                .map(|point| format!("0x{}", hex::encode(point)))
+                .collect()
+        }
+
+        let g1_monomial = trusted_setup
+            .g1_monomial_points
+            .iter()
+            .map(|p| p.0)
+            .collect::<Vec<_>>();
+
+        Self {
+            g1_monomial: to_hex_strings(&g1_monomial),
+            g1_lagrange: to_hex_strings(&trusted_setup.g1_points()),
+            g2_monomial: to_hex_strings(&trusted_setup.g2_points()),
+        }
+    }
+}
+
 impl Serialize for G1Point {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -3,6 +3,8 @@ use clap_utils::get_color_style;
 use clap_utils::FLAG_HEADER;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use store::hdiff::HierarchyConfig;
+use store::DatabaseBackend;
 
 use crate::InspectTarget;
 
@@ -22,13 +24,31 @@ pub struct DatabaseManager {
     #[clap(
         long,
         value_name = "SLOT_COUNT",
-        help = "Specifies how often a freezer DB restore point should be stored. \
-                Cannot be changed after initialization. \
-                [default: 2048 (mainnet) or 64 (minimal)]",
+        help = "This flag is deprecated and has no effect.",
+        hide = true,
         display_order = 0
     )]
     pub slots_per_restore_point: Option<u64>,
 
+    #[clap(
+        long,
+        value_name = "EXPONENTS",
+        help = "Specifies the layers of the freezer DB's hierarchy of state snapshots and \
+                diffs, as a comma-separated list of strictly increasing powers of 2. \
+                Cannot be changed after initialization. \
+                [default: 5,9,11,13,16,18,21]",
+        display_order = 0
+    )]
+    pub hierarchy_exponents: Option<HierarchyConfig>,
+
+    #[clap(
+        long,
+        value_name = "DATABASE",
+        help = "Set the database backend of the beacon node databases. [default: leveldb]",
+        display_order = 0
+    )]
+    pub backend: Option<DatabaseBackend>,
+
     #[clap(
         long,
         value_name = "DIR",
@@ -79,6 +99,7 @@ pub enum DatabaseManagerSubcommand {
     PruneBlobs(PruneBlobs),
     PruneStates(PruneStates),
     Compact(Compact),
+    ConvertBackend(ConvertBackend),
 }
 
 #[derive(Parser, Clone, Deserialize, Serialize, Debug)]
@@ -227,3 +248,18 @@ pub struct Compact {
     )]
     pub output_dir: Option<PathBuf>,
 }
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Convert the hot, freezer and blobs databases to another backend in place.",
+    alias = "convert_backend"
+)]
+pub struct ConvertBackend {
+    #[clap(
+        long,
+        value_name = "DATABASE",
+        help = "Database backend to convert to.",
+        display_order = 0
+    )]
+    pub to: DatabaseBackend,
+}
@@ -1,4 +1,5 @@
 pub mod cli;
+use crate::cli::ConvertBackend;
 use crate::cli::DatabaseManager;
 use crate::cli::Migrate;
 use crate::cli::PruneStates;
@@ -6,7 +7,7 @@ use beacon_chain::{
     builder::Witness, eth1_chain::CachingEth1Backend, schema_change::migrate_schema,
     slot_clock::SystemTimeSlotClock,
 };
-use beacon_node::{get_data_dir, get_slots_per_restore_point, ClientConfig};
+use beacon_node::{get_data_dir, ClientConfig};
 use clap::ArgMatches;
 use clap::ValueEnum;
 use cli::{Compact, Inspect};
@@ -15,12 +16,12 @@ use serde::{Deserialize, Serialize};
 use slog::{info, warn, Logger};
 use std::fs;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use store::metadata::STATE_UPPER_LIMIT_NO_RETAIN;
 use store::{
     errors::Error,
     metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION},
-    DBColumn, HotColdDB, KeyValueStore, LevelDB,
+    BeaconNodeBackend, DBColumn, DatabaseBackend, HotColdDB, KeyValueStore, KeyValueStoreOp,
 };
 use strum::{EnumString, EnumVariantNames};
 use types::{BeaconState, EthSpec, Slot};
@@ -40,11 +41,12 @@ fn parse_client_config<E: EthSpec>(
         .blobs_db_path
         .clone_from(&database_manager_config.blobs_dir);
 
-    let (sprp, sprp_explicit) =
-        get_slots_per_restore_point::<E>(database_manager_config.slots_per_restore_point)?;
-
-    client_config.store.slots_per_restore_point = sprp;
-    client_config.store.slots_per_restore_point_set_explicitly = sprp_explicit;
+    if let Some(backend) = database_manager_config.backend {
+        client_config.store.backend = backend;
+    }
+    if let Some(hierarchy_config) = &database_manager_config.hierarchy_exponents {
+        client_config.store.hierarchy_config = hierarchy_config.clone();
+    }
     client_config.store.blob_prune_margin_epochs = database_manager_config.blob_prune_margin_epochs;
 
     Ok(client_config)
@@ -61,7 +63,7 @@ pub fn display_db_version<E: EthSpec>(
     let blobs_path = client_config.get_blobs_db_path();
 
     let mut version = CURRENT_SCHEMA_VERSION;
-    HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -146,16 +148,20 @@ pub fn inspect_db<E: EthSpec>(
     let hot_path = client_config.get_db_path();
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
+    let backend = client_config.store.backend;
 
     let mut total = 0;
     let mut num_keys = 0;
 
     let sub_db = if inspect_config.freezer {
-        LevelDB::<E>::open(&cold_path).map_err(|e| format!("Unable to open freezer DB: {e:?}"))?
+        BeaconNodeBackend::<E>::open(backend, &cold_path)
+            .map_err(|e| format!("Unable to open freezer DB: {e:?}"))?
     } else if inspect_config.blobs_db {
-        LevelDB::<E>::open(&blobs_path).map_err(|e| format!("Unable to open blobs DB: {e:?}"))?
+        BeaconNodeBackend::<E>::open(backend, &blobs_path)
+            .map_err(|e| format!("Unable to open blobs DB: {e:?}"))?
     } else {
-        LevelDB::<E>::open(&hot_path).map_err(|e| format!("Unable to open hot DB: {e:?}"))?
+        BeaconNodeBackend::<E>::open(backend, &hot_path)
+            .map_err(|e| format!("Unable to open hot DB: {e:?}"))?
     };
 
     let skip = inspect_config.skip.unwrap_or(0);
@@ -266,14 +272,21 @@ pub fn compact_db<E: EthSpec>(
     let hot_path = client_config.get_db_path();
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
+    let backend = client_config.store.backend;
     let column = compact_config.column;
 
     let (sub_db, db_name) = if compact_config.freezer {
-        (LevelDB::<E>::open(&cold_path)?, "freezer_db")
+        (
+            BeaconNodeBackend::<E>::open(backend, &cold_path)?,
+            "freezer_db",
+        )
     } else if compact_config.blobs_db {
-        (LevelDB::<E>::open(&blobs_path)?, "blobs_db")
+        (
+            BeaconNodeBackend::<E>::open(backend, &blobs_path)?,
+            "blobs_db",
+        )
     } else {
-        (LevelDB::<E>::open(&hot_path)?, "hot_db")
+        (BeaconNodeBackend::<E>::open(backend, &hot_path)?, "hot_db")
     };
     info!(
         log,
@@ -308,7 +321,7 @@ pub fn migrate_db<E: EthSpec>(
 
     let mut from = CURRENT_SCHEMA_VERSION;
     let to = migrate_config.to;
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -348,7 +361,7 @@ pub fn prune_payloads<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -374,7 +387,7 @@ pub fn prune_blobs<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -411,7 +424,7 @@ pub fn prune_states<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -473,6 +486,120 @@ pub fn prune_states<E: EthSpec>(
     Ok(())
 }
 
+/// Maximum size of a batch of values copied during backend conversion.
+const CONVERT_BATCH_SIZE_BYTES: usize = 64 * 1024 * 1024;
+
+pub struct ConvertBackendConfig {
+    to: DatabaseBackend,
+}
+
+fn parse_convert_backend_config(
+    convert_backend_config: &ConvertBackend,
+) -> Result<ConvertBackendConfig, String> {
+    Ok(ConvertBackendConfig {
+        to: convert_backend_config.to,
+    })
+}
+
+/// Convert the hot, freezer and blobs databases to another backend, in place.
+pub fn convert_backend<E: EthSpec>(
+    convert_config: ConvertBackendConfig,
+    client_config: ClientConfig,
+    log: Logger,
+) -> Result<(), Error> {
+    let to = convert_config.to;
+    for (path, db_name) in [
+        (client_config.get_db_path(), "hot_db"),
+        (client_config.get_freezer_db_path(), "freezer_db"),
+        (client_config.get_blobs_db_path(), "blobs_db"),
+    ] {
+        convert_db_backend::<E>(&path, db_name, to, &log)?;
+    }
+
+    info!(
+        log,
+        "Database conversion complete";
+        "backend" => %to,
+        "info" => format!("start the beacon node with --beacon-node-backend {to}"),
+    );
+    Ok(())
+}
+
+/// Convert the database at `path` to the `to` backend.
+///
+/// The database is copied into a temporary directory alongside `path`, which then replaces the
+/// original. The original is only deleted once the copy is complete and synced to disk, so an
+/// interrupted conversion can be resumed by running it again.
+fn convert_db_backend<E: EthSpec>(
+    path: &Path,
+    db_name: &str,
+    to: DatabaseBackend,
+    log: &Logger,
+) -> Result<(), Error> {
+    let tmp_path = path.with_extension("converting");
+    let old_path = path.with_extension("old");
+    let fs_error = |e: std::io::Error| Error::DBError {
+        message: format!("Filesystem error during conversion of {db_name}: {e}"),
+    };
+
+    // Finish a previous conversion that was interrupted after the copy completed.
+    if !path.exists() && tmp_path.exists() && old_path.exists() {
+        fs::rename(&tmp_path, path).map_err(fs_error)?;
+    }
+    let Some(from) = DatabaseBackend::detect(path) else {
+        warn!(log, "No database found"; "db" => db_name, "path" => ?path);
+        return Ok(());
+    };
+    if from == to {
+        if old_path.exists() {
+            fs::remove_dir_all(&old_path).map_err(fs_error)?;
+        }
+        info!(log, "Database already uses backend"; "db" => db_name, "backend" => %to);
+        return Ok(());
+    }
+
+    // Discard any partial copy from a previous attempt.
+    if tmp_path.exists() {
+        fs::remove_dir_all(&tmp_path).map_err(fs_error)?;
+    }
+
+    info!(
+        log,
+        "Converting database";
+        "db" => db_name,
+        "from" => %from,
+        "to" => %to,
+    );
+    {
+        let source = BeaconNodeBackend::<E>::open(from, path)?;
+        let target = BeaconNodeBackend::<E>::open(to, &tmp_path)?;
+
+        let mut batch = vec![];
+        let mut batch_size = 0;
+        let mut num_keys = 0;
+        for res in source.iter_all() {
+            let (key, value) = res?;
+            batch_size += key.len() + value.len();
+            num_keys += 1;
+            batch.push(KeyValueStoreOp::PutKeyValue(key, value));
+
+            if batch_size >= CONVERT_BATCH_SIZE_BYTES {
+                target.do_atomically(std::mem::take(&mut batch))?;
+                batch_size = 0;
+            }
+        }
+        target.do_atomically(batch)?;
+        target.sync()?;
+
+        info!(log, "Copied database"; "db" => db_name, "num_keys" => num_keys);
+    }
+
+    fs::rename(path, &old_path).map_err(fs_error)?;
+    fs::rename(&tmp_path, path).map_err(fs_error)?;
+    fs::remove_dir_all(&old_path).map_err(fs_error)?;
+    Ok(())
+}
+
 /// Run the database manager, returning an error string if the operation did not succeed.
 pub fn run<E: EthSpec>(
     cli_args: &ArgMatches,
@@ -530,5 +657,101 @@ pub fn run<E: EthSpec>(
             let compact_config = parse_compact_config(compact_config)?;
             compact_db::<E>(compact_config, client_config, log).map_err(format_err)
         }
+        cli::DatabaseManagerSubcommand::ConvertBackend(convert_backend_config) => {
+            let convert_config = parse_convert_backend_config(convert_backend_config)?;
+            convert_backend::<E>(convert_config, client_config, log).map_err(format_err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use store::get_key_for_col;
+    use types::{Hash256, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    fn test_logger() -> Logger {
+        Logger::root(slog::Discard, slog::o!())
+    }
+
+    fn entries(backend: DatabaseBackend, path: &Path) -> Vec<(Vec<u8>, Vec<u8>)> {
+        BeaconNodeBackend::<E>::open(backend, path)
+            .unwrap()
+            .iter_all()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    /// Create a LevelDB database at `path` with entries in several columns, returning them.
+    fn populate(path: &Path) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let db = BeaconNodeBackend::<E>::open(DatabaseBackend::LevelDb, path).unwrap();
+        let ops = (0..16u64)
+            .flat_map(|i| {
+                [DBColumn::BeaconBlock, DBColumn::BeaconStateSummary].map(|column| {
+                    KeyValueStoreOp::PutKeyValue(
+                        get_key_for_col(column.into(), Hash256::from_low_u64_be(i).as_bytes()),
+                        vec![i as u8; 64],
+                    )
+                })
+            })
+            .collect();
+        db.do_atomically(ops).unwrap();
+        drop(db);
+        entries(DatabaseBackend::LevelDb, path)
+    }
+
+    #[test]
+    fn convert_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("chain_db");
+        let log = test_logger();
+        let original = populate(&path);
+        assert_eq!(original.len(), 32);
+
+        convert_db_backend::<E>(&path, "hot_db", DatabaseBackend::Redb, &log).unwrap();
+        assert_eq!(DatabaseBackend::detect(&path), Some(DatabaseBackend::Redb));
+        assert_eq!(entries(DatabaseBackend::Redb, &path), original);
+        assert!(!path.with_extension("converting").exists());
+        assert!(!path.with_extension("old").exists());
+
+        convert_db_backend::<E>(&path, "hot_db", DatabaseBackend::LevelDb, &log).unwrap();
+        assert_eq!(
+            DatabaseBackend::detect(&path),
+            Some(DatabaseBackend::LevelDb)
+        );
+        assert_eq!(entries(DatabaseBackend::LevelDb, &path), original);
+    }
+
+    #[test]
+    fn convert_resumes_after_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("chain_db");
+        let tmp_path = path.with_extension("converting");
+        let old_path = path.with_extension("old");
+        let log = test_logger();
+
+        // Simulate a conversion interrupted after the original was moved aside, but before the
+        // copy was moved into its place.
+        let original = populate(&path);
+        convert_db_backend::<E>(&path, "hot_db", DatabaseBackend::Redb, &log).unwrap();
+        fs::rename(&path, &tmp_path).unwrap();
+        populate(&old_path);
+
+        convert_db_backend::<E>(&path, "hot_db", DatabaseBackend::Redb, &log).unwrap();
+        assert_eq!(DatabaseBackend::detect(&path), Some(DatabaseBackend::Redb));
+        assert_eq!(entries(DatabaseBackend::Redb, &path), original);
+        assert!(!tmp_path.exists());
+        assert!(!old_path.exists());
+    }
+
+    #[test]
+    fn convert_missing_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("chain_db");
+
+        convert_db_backend::<E>(&path, "hot_db", DatabaseBackend::Redb, &test_logger()).unwrap();
+        assert!(!path.exists());
     }
 }
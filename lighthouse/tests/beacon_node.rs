@@ -441,8 +441,7 @@ fn eth1_cache_follow_distance_manual() {
 fn run_bellatrix_execution_endpoints_flag_test(flag: &str) {
     use sensitive_url::SensitiveUrl;
     let urls = vec!["http://sigp.io/no-way:1337", "http://infura.not_real:4242"];
-    // we don't support redundancy for execution-endpoints
-    // only the first provided endpoint is parsed.
+    // the first provided endpoint is the primary, the rest are backups.
 
     let mut endpoint_arg = urls[0].to_string();
     for url in urls.iter().skip(1) {
@@ -477,14 +476,72 @@ fn run_bellatrix_execution_endpoints_flag_test(flag: &str) {
                 config.execution_endpoint.as_ref().unwrap().clone(),
                 SensitiveUrl::parse(&urls[0]).unwrap()
             );
-            // Only the first secret file should be used.
+            assert_eq!(
+                config.backup_execution_endpoints,
+                vec![SensitiveUrl::parse(&urls[1]).unwrap()]
+            );
             assert_eq!(
                 config.secret_file.as_ref().unwrap().clone(),
                 jwts[0].clone()
             );
+            assert_eq!(config.backup_secret_files, vec![jwts[1].clone()]);
+        });
+}
+#[test]
+fn execution_endpoints_share_single_jwt() {
+    use sensitive_url::SensitiveUrl;
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let jwt = dir.path().join("jwt-file");
+    CommandLineTest::new()
+        .flag(
+            "execution-endpoint",
+            Some("http://localhost:8551/,http://localhost:9551/"),
+        )
+        .flag("execution-jwt", jwt.as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| {
+            let config = config.execution_layer.as_ref().unwrap();
+            assert_eq!(
+                config.backup_execution_endpoints,
+                vec![SensitiveUrl::parse("http://localhost:9551/").unwrap()]
+            );
+            assert_eq!(config.secret_file, Some(jwt.clone()));
+            assert!(config.backup_secret_files.is_empty());
+        });
+}
+#[test]
+fn execution_endpoint_ipc_without_jwt() {
+    use sensitive_url::SensitiveUrl;
+    CommandLineTest::new()
+        .flag("execution-endpoint", Some("ipc:///tmp/geth.ipc"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let el_config = config.execution_layer.as_ref().unwrap();
+            let endpoint = SensitiveUrl::parse("ipc:///tmp/geth.ipc").unwrap();
+            assert_eq!(el_config.execution_endpoint, Some(endpoint.clone()));
+            assert_eq!(el_config.secret_file, None);
+            assert_eq!(config.eth1.endpoint, Eth1Endpoint::NoAuth(endpoint));
         });
 }
 #[test]
+#[should_panic]
+fn execution_jwt_count_mismatch() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let jwts = format!(
+        "{},{},{}",
+        dir.path().join("jwt-0").display(),
+        dir.path().join("jwt-1").display(),
+        dir.path().join("jwt-2").display()
+    );
+    CommandLineTest::new()
+        .flag(
+            "execution-endpoint",
+            Some("http://localhost:8551/,http://localhost:9551/"),
+        )
+        .flag("execution-jwt", Some(&jwts))
+        .run_with_zero_port();
+}
+#[test]
 fn run_execution_jwt_secret_key_is_persisted() {
     let jwt_secret_key = "0x3cbc11b0d8fa16f3344eacfd6ff6430b9d30734450e8adcf5400f88d327dcb33";
     CommandLineTest::new()
@@ -585,9 +642,7 @@ fn run_payload_builder_flag_test(flag: &str, builders: &str) {
         .collect();
     run_payload_builder_flag_test_with_config(flag, builders, None, None, |config| {
         let config = config.execution_layer.as_ref().unwrap();
-        // Only first provided endpoint is parsed as we don't support
-        // redundancy.
-        assert_eq!(config.builder_url, all_builders.get(0).cloned());
+        assert_eq!(config.builder_urls, all_builders);
     })
 }
 fn run_payload_builder_flag_test_with_config<F: Fn(&Config)>(
@@ -832,6 +887,13 @@ fn network_subscribe_all_subnets_flag() {
         .with_config(|config| assert!(config.network.subscribe_all_subnets));
 }
 #[test]
+fn network_subscribe_all_data_column_subnets_flag() {
+    CommandLineTest::new()
+        .flag("subscribe-all-data-column-subnets", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.network.subscribe_all_data_column_subnets));
+}
+#[test]
 fn network_import_all_attestations_flag() {
     CommandLineTest::new()
         .flag("import-all-attestations", None)
@@ -1841,43 +1903,89 @@ fn validator_monitor_metrics_threshold_custom() {
 // Tests for Store flags.
 #[test]
 fn slots_per_restore_point_flag() {
+    use beacon_node::beacon_chain::store::hdiff::HierarchyConfig;
+
+    // The flag is deprecated and ignored.
     CommandLineTest::new()
         .flag("slots-per-restore-point", Some("64"))
         .run_with_zero_port()
-        .with_config(|config| assert_eq!(config.store.slots_per_restore_point, 64));
+        .with_config(|config| {
+            assert_eq!(config.store.hierarchy_config, HierarchyConfig::default())
+        });
 }
 #[test]
-fn slots_per_restore_point_update_prev_default() {
-    use beacon_node::beacon_chain::store::config::{
-        DEFAULT_SLOTS_PER_RESTORE_POINT, PREV_DEFAULT_SLOTS_PER_RESTORE_POINT,
-    };
-
+fn beacon_node_backend_default() {
     CommandLineTest::new()
-        .flag("slots-per-restore-point", Some("2048"))
         .run_with_zero_port()
-        .with_config_and_dir(|config, dir| {
-            // Check that 2048 is the previous default.
+        .with_config(|config| assert_eq!(config.store.backend, store::DatabaseBackend::LevelDb));
+}
+#[cfg(feature = "beacon-node-redb")]
+#[test]
+fn beacon_node_backend_redb() {
+    CommandLineTest::new()
+        .flag("beacon-node-backend", Some("redb"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.backend, store::DatabaseBackend::Redb));
+}
+#[cfg(feature = "beacon-node-redb")]
+#[test]
+fn beacon_node_backend_redb_auto_compact_db_default() {
+    CommandLineTest::new()
+        .flag("beacon-node-backend", Some("redb"))
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.store.compact_on_prune));
+}
+#[cfg(feature = "beacon-node-redb")]
+#[test]
+fn beacon_node_backend_redb_auto_compact_db_flag() {
+    CommandLineTest::new()
+        .flag("beacon-node-backend", Some("redb"))
+        .flag("auto-compact-db", Some("true"))
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.store.compact_on_prune));
+}
+#[test]
+#[should_panic]
+fn beacon_node_backend_invalid() {
+    CommandLineTest::new()
+        .flag("beacon-node-backend", Some("rocksdb"))
+        .run_with_zero_port();
+}
+#[test]
+fn hierarchy_exponents_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
             assert_eq!(
-                config.store.slots_per_restore_point,
-                PREV_DEFAULT_SLOTS_PER_RESTORE_POINT
-            );
-
-            // Restart the BN with the same datadir and the new default SPRP. It should
-            // allow this.
-            CommandLineTest::new()
-                .flag("datadir", Some(&dir.path().display().to_string()))
-                .flag("zero-ports", None)
-                .run_with_no_datadir()
-                .with_config(|config| {
-                    // The dumped config will have the new default 8192 value, but the fact that
-                    // the BN started and ran (with the same datadir) means that the override
-                    // was successful.
-                    assert_eq!(
-                        config.store.slots_per_restore_point,
-                        DEFAULT_SLOTS_PER_RESTORE_POINT
-                    );
-                });
-        })
+                config.store.hierarchy_config.exponents,
+                vec![5, 9, 11, 13, 16, 18, 21]
+            )
+        });
+}
+#[test]
+fn hierarchy_exponents_flag() {
+    CommandLineTest::new()
+        .flag("hierarchy-exponents", Some("5,8,12,16"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.store.hierarchy_config.exponents, vec![5, 8, 12, 16])
+        });
+}
+#[test]
+#[should_panic]
+fn hierarchy_exponents_not_increasing() {
+    CommandLineTest::new()
+        .flag("hierarchy-exponents", Some("5,12,8"))
+        .run_with_zero_port();
+}
+#[test]
+fn hdiff_buffer_cache_size_flag() {
+    CommandLineTest::new()
+        .flag("hdiff-buffer-cache-size", Some("16"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.store.hdiff_buffer_cache_size, new_non_zero_usize(16))
+        });
 }
 
 #[test]
@@ -1925,6 +2033,12 @@ fn historic_state_cache_size_default() {
         });
 }
 #[test]
+fn auto_compact_db_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.store.compact_on_prune));
+}
+#[test]
 fn auto_compact_db_flag() {
     CommandLineTest::new()
         .flag("auto-compact-db", Some("false"))
@@ -8,8 +8,13 @@ use std::str::FromStr;
 use tempfile::{tempdir, TempDir};
 use types::*;
 use validator_manager::{
+    bls_to_execution_change::BlsToExecutionChangeConfig,
+    common::ValidatorSelection,
     create_validators::CreateConfig,
+    delete_validators::DeleteConfig,
+    exit_validators::ExitConfig,
     import_validators::ImportConfig,
+    list_validators::ListConfig,
     move_validators::{MoveConfig, PasswordSource, Validators},
 };
 
@@ -105,6 +110,30 @@ impl CommandLineTest<MoveConfig> {
     }
 }
 
+impl CommandLineTest<ListConfig> {
+    fn validators_list() -> Self {
+        Self::default().flag("list", None)
+    }
+}
+
+impl CommandLineTest<DeleteConfig> {
+    fn validators_delete() -> Self {
+        Self::default().flag("delete", None)
+    }
+}
+
+impl CommandLineTest<ExitConfig> {
+    fn validators_exit() -> Self {
+        Self::default().flag("exit", None)
+    }
+}
+
+impl CommandLineTest<BlsToExecutionChangeConfig> {
+    fn bls_to_execution_change() -> Self {
+        Self::default().flag("bls-to-execution-change", None)
+    }
+}
+
 #[test]
 pub fn validator_create_without_output_path() {
     CommandLineTest::validators_create().assert_failed();
@@ -394,3 +423,171 @@ pub fn validator_move_count() {
             assert_eq!(expected, config);
         });
 }
+
+#[test]
+pub fn validator_list_defaults() {
+    CommandLineTest::validators_list()
+        .flag("--vc-token", Some("./token.json"))
+        .assert_success(|config| {
+            let expected = ListConfig {
+                vc_url: SensitiveUrl::parse("http://localhost:5062").unwrap(),
+                vc_token_path: PathBuf::from("./token.json"),
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn validator_list_missing_token() {
+    CommandLineTest::validators_list().assert_failed();
+}
+
+#[test]
+pub fn validator_delete_defaults() {
+    CommandLineTest::validators_delete()
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--validators", Some("all"))
+        .flag("--output-path", Some("./meow"))
+        .assert_success(|config| {
+            let expected = DeleteConfig {
+                vc_url: SensitiveUrl::parse("http://localhost:5062").unwrap(),
+                vc_token_path: PathBuf::from("./token.json"),
+                validators: ValidatorSelection::All,
+                output_path: PathBuf::from("./meow"),
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn validator_delete_specific() {
+    CommandLineTest::validators_delete()
+        .flag("--vc-token", Some("./token.json"))
+        .flag(
+            "--validators",
+            Some(&format!("{},{}", EXAMPLE_PUBKEY_0, EXAMPLE_PUBKEY_1)),
+        )
+        .flag("--output-path", Some("./meow"))
+        .assert_success(|config| {
+            assert_eq!(
+                config.validators,
+                ValidatorSelection::Specific(vec![
+                    PublicKeyBytes::from_str(EXAMPLE_PUBKEY_0).unwrap(),
+                    PublicKeyBytes::from_str(EXAMPLE_PUBKEY_1).unwrap()
+                ])
+            );
+        });
+}
+
+#[test]
+pub fn validator_delete_missing_output_path() {
+    CommandLineTest::validators_delete()
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--validators", Some("all"))
+        .assert_failed();
+}
+
+#[test]
+pub fn validator_exit_to_file() {
+    CommandLineTest::validators_exit()
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--validators", Some("all"))
+        .flag("--output-path", Some("./meow"))
+        .assert_success(|config| {
+            let expected = ExitConfig {
+                vc_url: SensitiveUrl::parse("http://localhost:5062").unwrap(),
+                vc_token_path: PathBuf::from("./token.json"),
+                validators: ValidatorSelection::All,
+                exit_epoch: None,
+                bn_url: None,
+                output_path: Some(PathBuf::from("./meow")),
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn validator_exit_to_beacon_node() {
+    CommandLineTest::validators_exit()
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--validators", Some(EXAMPLE_PUBKEY_0))
+        .flag("--exit-epoch", Some("1024"))
+        .flag("--beacon-node", Some("http://localhost:1001"))
+        .assert_success(|config| {
+            let expected = ExitConfig {
+                vc_url: SensitiveUrl::parse("http://localhost:5062").unwrap(),
+                vc_token_path: PathBuf::from("./token.json"),
+                validators: ValidatorSelection::Specific(vec![PublicKeyBytes::from_str(
+                    EXAMPLE_PUBKEY_0,
+                )
+                .unwrap()]),
+                exit_epoch: Some(Epoch::new(1024)),
+                bn_url: Some(SensitiveUrl::parse("http://localhost:1001").unwrap()),
+                output_path: None,
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn validator_exit_missing_destination() {
+    CommandLineTest::validators_exit()
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--validators", Some("all"))
+        .assert_failed();
+}
+
+#[test]
+pub fn bls_to_execution_change_defaults() {
+    CommandLineTest::bls_to_execution_change()
+        .flag("--output-path", Some("./meow"))
+        .flag("--count", Some("2"))
+        .flag("--execution-address", Some(EXAMPLE_ETH1_ADDRESS))
+        .assert_success(|config| {
+            let expected = BlsToExecutionChangeConfig {
+                output_path: PathBuf::from("./meow"),
+                first_index: 0,
+                count: 2,
+                mnemonic_path: None,
+                stdin_inputs: cfg!(windows) || false,
+                execution_address: Address::from_str(EXAMPLE_ETH1_ADDRESS).unwrap(),
+                bn_url: SensitiveUrl::parse("http://localhost:5052").unwrap(),
+                submit: false,
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn bls_to_execution_change_misc_flags() {
+    CommandLineTest::bls_to_execution_change()
+        .flag("--output-path", Some("./meow"))
+        .flag("--first-index", Some("12"))
+        .flag("--count", Some("9"))
+        .flag("--mnemonic-path", Some("./woof"))
+        .flag("--stdin-inputs", None)
+        .flag("--execution-address", Some(EXAMPLE_ETH1_ADDRESS))
+        .flag("--beacon-node", Some("http://localhost:1001"))
+        .flag("--submit", None)
+        .assert_success(|config| {
+            let expected = BlsToExecutionChangeConfig {
+                output_path: PathBuf::from("./meow"),
+                first_index: 12,
+                count: 9,
+                mnemonic_path: Some(PathBuf::from("./woof")),
+                stdin_inputs: true,
+                execution_address: Address::from_str(EXAMPLE_ETH1_ADDRESS).unwrap(),
+                bn_url: SensitiveUrl::parse("http://localhost:1001").unwrap(),
+                submit: true,
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn bls_to_execution_change_missing_execution_address() {
+    CommandLineTest::bls_to_execution_change()
+        .flag("--output-path", Some("./meow"))
+        .flag("--count", Some("2"))
+        .assert_failed();
+}
@@ -0,0 +1,70 @@
+use super::*;
+use crate::case_result::compare_result;
+use kzg::{Blob as KzgBlob, KzgProof};
+use serde::Deserialize;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KZGComputeCellsAndKZGProofsInput {
+    pub blob: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(bound = "E: EthSpec", deny_unknown_fields)]
+pub struct KZGComputeCellsAndKZGProofs<E: EthSpec> {
+    pub input: KZGComputeCellsAndKZGProofsInput,
+    pub output: Option<(Vec<String>, Vec<String>)>,
+    #[serde(skip)]
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> LoadCase for KZGComputeCellsAndKZGProofs<E> {
+    fn load_from_dir(path: &Path, _fork_name: ForkName) -> Result<Self, Error> {
+        decode::yaml_decode_file(path.join("data.yaml").as_path())
+    }
+}
+
+impl<E: EthSpec> Case for KZGComputeCellsAndKZGProofs<E> {
+    fn is_enabled_for_fork(_fork_name: ForkName) -> bool {
+        false
+    }
+
+    fn is_enabled_for_feature(feature_name: FeatureName) -> bool {
+        feature_name == FeatureName::Eip7594
+    }
+
+    fn result(&self, _case_index: usize, _fork_name: ForkName) -> Result<(), Error> {
+        let kzg = get_kzg()?;
+        let cells_and_proofs = parse_blob::<E>(&self.input.blob).and_then(|blob| {
+            let blob = KzgBlob::from_bytes(blob.as_ref())
+                .map_err(|e| Error::FailedToParseTest(format!("Failed to parse blob: {:?}", e)))?;
+            kzg.compute_cells_and_proofs(&blob)
+                .map(|(cells, proofs)| {
+                    (
+                        cells.iter().map(|cell| cell.to_vec()).collect::<Vec<_>>(),
+                        proofs.to_vec(),
+                    )
+                })
+                .map_err(|e| {
+                    Error::InternalError(format!("Failed to compute cells and kzg proofs: {:?}", e))
+                })
+        });
+
+        let expected = self.output.as_ref().and_then(|(cells, proofs)| {
+            let cells = cells
+                .iter()
+                .map(|s| parse_cell(s))
+                .collect::<Result<Vec<_>, _>>()
+                .ok()?;
+            let proofs = proofs
+                .iter()
+                .map(|s| parse_proof(s))
+                .collect::<Result<Vec<_>, _>>()
+                .ok()?;
+            Some((cells, proofs))
+        });
+
+        compare_result::<(Vec<Vec<u8>>, Vec<KzgProof>), _>(&cells_and_proofs, &expected)
+    }
+}
@@ -0,0 +1,70 @@
+use super::*;
+use crate::case_result::compare_result;
+use kzg::CellRef;
+use serde::Deserialize;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KZGRecoverAllCellsInput {
+    pub cell_ids: Vec<u64>,
+    pub cells: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(bound = "E: EthSpec", deny_unknown_fields)]
+pub struct KZGRecoverAllCells<E: EthSpec> {
+    pub input: KZGRecoverAllCellsInput,
+    pub output: Option<Vec<String>>,
+    #[serde(skip)]
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> LoadCase for KZGRecoverAllCells<E> {
+    fn load_from_dir(path: &Path, _fork_name: ForkName) -> Result<Self, Error> {
+        decode::yaml_decode_file(path.join("data.yaml").as_path())
+    }
+}
+
+impl<E: EthSpec> Case for KZGRecoverAllCells<E> {
+    fn is_enabled_for_fork(_fork_name: ForkName) -> bool {
+        false
+    }
+
+    fn is_enabled_for_feature(feature_name: FeatureName) -> bool {
+        feature_name == FeatureName::Eip7594
+    }
+
+    fn result(&self, _case_index: usize, _fork_name: ForkName) -> Result<(), Error> {
+        let kzg = get_kzg()?;
+        let cells = self
+            .input
+            .cells
+            .iter()
+            .map(|s| parse_cell(s))
+            .collect::<Result<Vec<_>, _>>()
+            .and_then(|cells| {
+                let cell_refs = cells
+                    .iter()
+                    .map(|cell| {
+                        CellRef::try_from(cell.as_slice())
+                            .map_err(|e| Error::FailedToParseTest(format!("Invalid cell: {:?}", e)))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                kzg.recover_cells_and_compute_kzg_proofs(&self.input.cell_ids, &cell_refs)
+                    .map(|(cells, _proofs)| cells.iter().map(|cell| cell.to_vec()).collect())
+                    .map_err(|e| Error::InternalError(format!("Failed to recover cells: {:?}", e)))
+            });
+
+        let expected = self.output.as_ref().and_then(|cells| {
+            cells
+                .iter()
+                .map(|s| parse_cell(s))
+                .collect::<Result<Vec<_>, _>>()
+                .ok()
+        });
+
+        compare_result::<Vec<Vec<u8>>, _>(&cells, &expected)
+    }
+}
@@ -2,7 +2,7 @@ use super::*;
 use crate::case_result::compare_result;
 use beacon_chain::kzg_utils::validate_blob;
 use eth2_network_config::TRUSTED_SETUP_BYTES;
-use kzg::{Error as KzgError, Kzg, KzgCommitment, KzgProof, TrustedSetup};
+use kzg::{Error as KzgError, Kzg, KzgCommitment, KzgProof, TrustedSetup, BYTES_PER_CELL};
 use serde::Deserialize;
 use std::marker::PhantomData;
 use types::Blob;
@@ -45,6 +45,22 @@ pub fn parse_blob<E: EthSpec>(blob: &str) -> Result<Blob<E>, Error> {
         })
 }
 
+pub fn parse_cell(cell: &str) -> Result<Vec<u8>, Error> {
+    hex::decode(strip_0x(cell)?)
+        .map_err(|e| Error::FailedToParseTest(format!("Failed to parse cell: {:?}", e)))
+        .and_then(|bytes| {
+            if bytes.len() == BYTES_PER_CELL {
+                Ok(bytes)
+            } else {
+                Err(Error::FailedToParseTest(format!(
+                    "Cell has invalid length. Expected {} got {}",
+                    BYTES_PER_CELL,
+                    bytes.len()
+                )))
+            }
+        })
+}
+
 fn strip_0x(s: &str) -> Result<&str, Error> {
     s.strip_prefix("0x").ok_or(Error::FailedToParseTest(format!(
         "Hex is missing 0x prefix: {}",
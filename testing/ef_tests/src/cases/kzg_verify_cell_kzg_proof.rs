@@ -0,0 +1,70 @@
+use super::*;
+use crate::case_result::compare_result;
+use kzg::{Bytes48, CellRef, Error as KzgError};
+use serde::Deserialize;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KZGVerifyCellKZGProofInput {
+    pub commitment: String,
+    pub cell_id: u64,
+    pub cell: String,
+    pub proof: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(bound = "E: EthSpec", deny_unknown_fields)]
+pub struct KZGVerifyCellKZGProof<E: EthSpec> {
+    pub input: KZGVerifyCellKZGProofInput,
+    pub output: Option<bool>,
+    #[serde(skip)]
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> LoadCase for KZGVerifyCellKZGProof<E> {
+    fn load_from_dir(path: &Path, _fork_name: ForkName) -> Result<Self, Error> {
+        decode::yaml_decode_file(path.join("data.yaml").as_path())
+    }
+}
+
+impl<E: EthSpec> Case for KZGVerifyCellKZGProof<E> {
+    fn is_enabled_for_fork(_fork_name: ForkName) -> bool {
+        false
+    }
+
+    fn is_enabled_for_feature(feature_name: FeatureName) -> bool {
+        feature_name == FeatureName::Eip7594
+    }
+
+    fn result(&self, _case_index: usize, _fork_name: ForkName) -> Result<(), Error> {
+        let parse_input = |input: &KZGVerifyCellKZGProofInput| -> Result<_, Error> {
+            let commitment = parse_commitment(&input.commitment)?;
+            let cell = parse_cell(&input.cell)?;
+            let proof = parse_proof(&input.proof)?;
+            Ok((commitment, cell, proof))
+        };
+
+        let kzg = get_kzg()?;
+        let result = parse_input(&self.input).and_then(|(commitment, cell, proof)| {
+            let cell = CellRef::try_from(cell.as_slice())
+                .map_err(|e| Error::FailedToParseTest(format!("Invalid cell: {:?}", e)))?;
+
+            match kzg.verify_cell_proof(
+                cell,
+                Bytes48::from(proof),
+                self.input.cell_id,
+                Bytes48::from(commitment),
+            ) {
+                Ok(_) => Ok(true),
+                Err(KzgError::KzgVerificationFailed) => Ok(false),
+                Err(e) => Err(Error::InternalError(format!(
+                    "Failed to validate cell: {:?}",
+                    e
+                ))),
+            }
+        });
+
+        compare_result::<bool, _>(&result, &self.output)
+    }
+}
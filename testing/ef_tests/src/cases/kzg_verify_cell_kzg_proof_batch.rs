@@ -0,0 +1,101 @@
+use super::*;
+use crate::case_result::compare_result;
+use kzg::{Bytes48, CellRef, Error as KzgError};
+use serde::Deserialize;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KZGVerifyCellKZGProofBatchInput {
+    pub row_commitments: Vec<String>,
+    pub row_indices: Vec<usize>,
+    pub column_indices: Vec<u64>,
+    pub cells: Vec<String>,
+    pub proofs: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(bound = "E: EthSpec", deny_unknown_fields)]
+pub struct KZGVerifyCellKZGProofBatch<E: EthSpec> {
+    pub input: KZGVerifyCellKZGProofBatchInput,
+    pub output: Option<bool>,
+    #[serde(skip)]
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> LoadCase for KZGVerifyCellKZGProofBatch<E> {
+    fn load_from_dir(path: &Path, _fork_name: ForkName) -> Result<Self, Error> {
+        decode::yaml_decode_file(path.join("data.yaml").as_path())
+    }
+}
+
+impl<E: EthSpec> Case for KZGVerifyCellKZGProofBatch<E> {
+    fn is_enabled_for_fork(_fork_name: ForkName) -> bool {
+        false
+    }
+
+    fn is_enabled_for_feature(feature_name: FeatureName) -> bool {
+        feature_name == FeatureName::Eip7594
+    }
+
+    fn result(&self, _case_index: usize, _fork_name: ForkName) -> Result<(), Error> {
+        let parse_input = |input: &KZGVerifyCellKZGProofBatchInput| -> Result<_, Error> {
+            let row_commitments = input
+                .row_commitments
+                .iter()
+                .map(|s| parse_commitment(s))
+                .collect::<Result<Vec<_>, _>>()?;
+            // Each cell is verified against the commitment of the row (blob) it belongs to.
+            let commitments = input
+                .row_indices
+                .iter()
+                .map(|&row_index| {
+                    row_commitments
+                        .get(row_index)
+                        .map(|&commitment| Bytes48::from(commitment))
+                        .ok_or_else(|| {
+                            Error::FailedToParseTest(format!("Invalid row index: {}", row_index))
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let cells = input
+                .cells
+                .iter()
+                .map(|s| parse_cell(s))
+                .collect::<Result<Vec<_>, _>>()?;
+            let proofs = input
+                .proofs
+                .iter()
+                .map(|s| parse_proof(s).map(Bytes48::from))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok((commitments, cells, proofs))
+        };
+
+        let kzg = get_kzg()?;
+        let result = parse_input(&self.input).and_then(|(commitments, cells, proofs)| {
+            let cells = cells
+                .iter()
+                .map(|cell| {
+                    CellRef::try_from(cell.as_slice())
+                        .map_err(|e| Error::FailedToParseTest(format!("Invalid cell: {:?}", e)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            match kzg.verify_cell_proof_batch(
+                &cells,
+                &proofs,
+                self.input.column_indices.clone(),
+                &commitments,
+            ) {
+                Ok(_) => Ok(true),
+                Err(KzgError::KzgVerificationFailed) => Ok(false),
+                Err(e) => Err(Error::InternalError(format!(
+                    "Failed to validate cells: {:?}",
+                    e
+                ))),
+            }
+        });
+
+        compare_result::<bool, _>(&result, &self.output)
+    }
+}
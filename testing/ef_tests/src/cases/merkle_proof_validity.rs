@@ -125,7 +125,21 @@ impl<E: EthSpec> LoadCase for KzgInclusionMerkleProofValidity<E> {
 
 impl<E: EthSpec> Case for KzgInclusionMerkleProofValidity<E> {
     fn result(&self, _case_index: usize, _fork_name: ForkName) -> Result<(), Error> {
-        let Ok(proof) = self.block.to_ref().kzg_commitment_merkle_proof(0) else {
+        // The Deneb vectors prove the inclusion of the first commitment, whereas the EIP-7594
+        // vectors prove the inclusion of the whole `blob_kzg_commitments` list.
+        let proof = if self.merkle_proof.branch.len() == E::kzg_commitments_inclusion_proof_depth()
+        {
+            self.block
+                .to_ref()
+                .kzg_commitments_merkle_proof()
+                .map(|proof| proof.to_vec())
+        } else {
+            self.block
+                .to_ref()
+                .kzg_commitment_merkle_proof(0)
+                .map(|proof| proof.to_vec())
+        };
+        let Ok(proof) = proof else {
             return Err(Error::FailedToParseTest(
                 "Could not retrieve merkle proof".to_string(),
             ));
@@ -1,6 +1,7 @@
 use crate::cases::{self, Case, Cases, EpochTransition, LoadCase, Operation};
 use crate::type_name;
 use crate::type_name::TypeName;
+use crate::FeatureName;
 use derivative::Derivative;
 use std::fs::{self, DirEntry};
 use std::marker::PhantomData;
@@ -28,12 +29,24 @@ pub trait Handler {
         Self::Case::is_enabled_for_fork(fork_name)
     }
 
+    fn is_enabled_for_feature(&self, feature_name: FeatureName) -> bool {
+        Self::Case::is_enabled_for_feature(feature_name)
+    }
+
     fn run(&self) {
         for fork_name in ForkName::list_all() {
             if !self.disabled_forks().contains(&fork_name) && self.is_enabled_for_fork(fork_name) {
                 self.run_for_fork(fork_name)
             }
         }
+
+        // Run tests for features that are not yet part of a `ForkName`. These tests live in a
+        // directory named after the feature, e.g. `tests/general/eip7594/kzg`.
+        for feature_name in FeatureName::list_all() {
+            if self.is_enabled_for_feature(feature_name) {
+                self.run_for_feature(feature_name)
+            }
+        }
     }
 
     fn use_rayon() -> bool {
@@ -41,13 +54,19 @@ pub trait Handler {
     }
 
     fn run_for_fork(&self, fork_name: ForkName) {
-        let fork_name_str = fork_name.to_string();
+        self.run_in_dir(&fork_name.to_string(), fork_name)
+    }
+
+    fn run_for_feature(&self, feature_name: FeatureName) {
+        self.run_in_dir(&feature_name.to_string(), feature_name.fork_name())
+    }
 
+    fn run_in_dir(&self, fork_name_str: &str, fork_name: ForkName) {
         let handler_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
             .join("consensus-spec-tests")
             .join("tests")
             .join(Self::config_name())
-            .join(&fork_name_str)
+            .join(fork_name_str)
             .join(Self::runner_name())
             .join(self.handler_name());
 
@@ -289,6 +308,14 @@ where
     fn is_enabled_for_fork(&self, fork_name: ForkName) -> bool {
         self.supported_forks.contains(&fork_name)
     }
+
+    fn is_enabled_for_feature(&self, feature_name: FeatureName) -> bool {
+        // EIP-7594 re-uses the SSZ types of the fork it is built on, so only run its vectors for
+        // types that are also tested against that fork, e.g. `AttestationBase` but not
+        // `AttestationElectra`.
+        feature_name == FeatureName::Eip7594
+            && self.supported_forks.contains(&feature_name.fork_name())
+    }
 }
 
 impl<E> Handler for SszStaticTHCHandler<BeaconState<E>, E>
@@ -308,6 +335,10 @@ where
     fn handler_name(&self) -> String {
         BeaconState::<E>::name().into()
     }
+
+    fn is_enabled_for_feature(&self, feature_name: FeatureName) -> bool {
+        feature_name == FeatureName::Eip7594
+    }
 }
 
 impl<T, E> Handler for SszStaticWithSpecHandler<T, E>
@@ -329,6 +360,10 @@ where
     fn handler_name(&self) -> String {
         T::name().into()
     }
+
+    fn is_enabled_for_feature(&self, feature_name: FeatureName) -> bool {
+        feature_name == FeatureName::Eip7594
+    }
 }
 
 #[derive(Derivative)]
@@ -784,6 +819,106 @@ impl<E: EthSpec> Handler for KZGVerifyKZGProofHandler<E> {
     }
 }
 
+#[derive(Derivative)]
+#[derivative(Default(bound = ""))]
+pub struct KZGComputeCellsHandler<E>(PhantomData<E>);
+
+impl<E: EthSpec> Handler for KZGComputeCellsHandler<E> {
+    type Case = cases::KZGComputeCells<E>;
+
+    fn config_name() -> &'static str {
+        "general"
+    }
+
+    fn runner_name() -> &'static str {
+        "kzg"
+    }
+
+    fn handler_name(&self) -> String {
+        "compute_cells".into()
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Default(bound = ""))]
+pub struct KZGComputeCellsAndKZGProofHandler<E>(PhantomData<E>);
+
+impl<E: EthSpec> Handler for KZGComputeCellsAndKZGProofHandler<E> {
+    type Case = cases::KZGComputeCellsAndKZGProofs<E>;
+
+    fn config_name() -> &'static str {
+        "general"
+    }
+
+    fn runner_name() -> &'static str {
+        "kzg"
+    }
+
+    fn handler_name(&self) -> String {
+        "compute_cells_and_kzg_proofs".into()
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Default(bound = ""))]
+pub struct KZGVerifyCellKZGProofHandler<E>(PhantomData<E>);
+
+impl<E: EthSpec> Handler for KZGVerifyCellKZGProofHandler<E> {
+    type Case = cases::KZGVerifyCellKZGProof<E>;
+
+    fn config_name() -> &'static str {
+        "general"
+    }
+
+    fn runner_name() -> &'static str {
+        "kzg"
+    }
+
+    fn handler_name(&self) -> String {
+        "verify_cell_kzg_proof".into()
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Default(bound = ""))]
+pub struct KZGVerifyCellKZGProofBatchHandler<E>(PhantomData<E>);
+
+impl<E: EthSpec> Handler for KZGVerifyCellKZGProofBatchHandler<E> {
+    type Case = cases::KZGVerifyCellKZGProofBatch<E>;
+
+    fn config_name() -> &'static str {
+        "general"
+    }
+
+    fn runner_name() -> &'static str {
+        "kzg"
+    }
+
+    fn handler_name(&self) -> String {
+        "verify_cell_kzg_proof_batch".into()
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Default(bound = ""))]
+pub struct KZGRecoverAllCellsHandler<E>(PhantomData<E>);
+
+impl<E: EthSpec> Handler for KZGRecoverAllCellsHandler<E> {
+    type Case = cases::KZGRecoverAllCells<E>;
+
+    fn config_name() -> &'static str {
+        "general"
+    }
+
+    fn runner_name() -> &'static str {
+        "kzg"
+    }
+
+    fn handler_name(&self) -> String {
+        "recover_all_cells".into()
+    }
+}
+
 #[derive(Derivative)]
 #[derivative(Default(bound = ""))]
 pub struct MerkleProofValidityHandler<E>(PhantomData<E>);
@@ -835,6 +970,30 @@ impl<E: EthSpec + TypeName> Handler for KzgInclusionMerkleProofValidityHandler<E
         // Enabled in Deneb
         fork_name == ForkName::Deneb
     }
+
+    fn is_enabled_for_feature(&self, feature_name: FeatureName) -> bool {
+        feature_name == FeatureName::Eip7594
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Default(bound = ""))]
+pub struct GetCustodyColumnsHandler<E>(PhantomData<E>);
+
+impl<E: EthSpec + TypeName> Handler for GetCustodyColumnsHandler<E> {
+    type Case = cases::GetCustodyColumns<E>;
+
+    fn config_name() -> &'static str {
+        E::name()
+    }
+
+    fn runner_name() -> &'static str {
+        "networking"
+    }
+
+    fn handler_name(&self) -> String {
+        "get_custody_columns".into()
+    }
 }
 
 #[derive(Derivative)]
@@ -9,6 +9,7 @@ pub use cases::{
 pub use decode::log_file_access;
 pub use error::Error;
 pub use handler::*;
+use std::fmt;
 pub use type_name::TypeName;
 use types::{ChainSpec, EthSpec, ForkName};
 
@@ -24,3 +25,33 @@ mod type_name;
 pub fn testing_spec<E: EthSpec>(fork_name: ForkName) -> ChainSpec {
     fork_name.make_genesis_spec(E::default_spec())
 }
+
+/// A consensus feature that has spec tests but has not yet been scheduled in a fork.
+///
+/// Tests for a feature live in a directory named after the feature rather than a fork, e.g.
+/// `tests/general/eip7594`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureName {
+    Eip7594,
+}
+
+impl FeatureName {
+    pub fn list_all() -> Vec<FeatureName> {
+        vec![FeatureName::Eip7594]
+    }
+
+    /// The fork that the feature's tests are built on top of.
+    pub fn fork_name(&self) -> ForkName {
+        match self {
+            FeatureName::Eip7594 => ForkName::Deneb,
+        }
+    }
+}
+
+impl fmt::Display for FeatureName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FeatureName::Eip7594 => write!(f, "eip7594"),
+        }
+    }
+}
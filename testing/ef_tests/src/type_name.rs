@@ -62,6 +62,8 @@ type_name_generic!(BlobSidecar);
 type_name!(Checkpoint);
 type_name!(Consolidation);
 type_name_generic!(ContributionAndProof);
+type_name!(DataColumnIdentifier);
+type_name_generic!(DataColumnSidecar);
 type_name!(Deposit);
 type_name!(DepositData);
 type_name!(DepositMessage);
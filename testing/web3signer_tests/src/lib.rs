@@ -42,11 +42,12 @@ mod tests {
     use types::{attestation::AttestationBase, *};
     use url::Url;
     use validator_client::{
+        events::ServerSentEventHandler,
         initialized_validators::{
             load_pem_certificate, load_pkcs12_identity, InitializedValidators,
         },
         validator_store::{Error as ValidatorStoreError, ValidatorStore},
-        SlashingDatabase, SLASHING_PROTECTION_FILENAME,
+        SlashingDatabase, SlashingProtection, SLASHING_PROTECTION_FILENAME,
     };
 
     /// If the we are unable to reach the Web3Signer HTTP API within this time out then we will
@@ -360,13 +361,14 @@ mod tests {
 
             let validator_store = ValidatorStore::<_, E>::new(
                 initialized_validators,
-                slashing_protection,
+                slashing_protection.into(),
                 Hash256::repeat_byte(42),
                 spec,
                 None,
                 slot_clock,
                 &config,
                 executor,
+                Arc::new(ServerSentEventHandler::new(log.clone())),
                 log.clone(),
             );
 
@@ -429,6 +431,7 @@ mod tests {
                         voting_keystore_path: signer_rig.keystore_path.clone(),
                         voting_keystore_password_path: None,
                         voting_keystore_password: Some(KEYSTORE_PASSWORD.to_string().into()),
+                        voting_keystore_password_provider: None,
                     },
                 };
                 ValidatorStoreRig::new(
@@ -453,6 +456,38 @@ mod tests {
                     description: String::default(),
                     signing_definition: SigningDefinition::Web3Signer(Web3SignerDefinition {
                         url: signer_rig.url.to_string(),
+                        fallback_urls: vec![],
+                        root_certificate_path: Some(root_certificate_path()),
+                        request_timeout_ms: None,
+                        client_identity_path: Some(client_identity_path()),
+                        client_identity_password: Some(client_identity_password()),
+                    }),
+                };
+                ValidatorStoreRig::new(
+                    vec![validator_definition],
+                    slashing_protection_config,
+                    true,
+                    spec.clone(),
+                )
+                .await
+            };
+
+            // A remote signer whose first Web3Signer instance is unreachable, so that every
+            // signature is obtained from its fallback instance.
+            let fallback_signer_validator_store = {
+                let validator_definition = ValidatorDefinition {
+                    enabled: true,
+                    voting_public_key: validator_pubkey.clone(),
+                    graffiti: None,
+                    suggested_fee_recipient: None,
+                    gas_limit: None,
+                    builder_proposals: None,
+                    builder_boost_factor: None,
+                    prefer_builder_proposals: None,
+                    description: String::default(),
+                    signing_definition: SigningDefinition::Web3Signer(Web3SignerDefinition {
+                        url: format!("https://{}:1", WEB3SIGNER_LISTEN_ADDRESS),
+                        fallback_urls: vec![signer_rig.url.to_string()],
                         root_certificate_path: Some(root_certificate_path()),
                         request_timeout_ms: None,
                         client_identity_path: Some(client_identity_path()),
@@ -470,7 +505,11 @@ mod tests {
 
             Self {
                 _signer_rig: signer_rig,
-                validator_rigs: vec![local_signer_validator_store, remote_signer_validator_store],
+                validator_rigs: vec![
+                    local_signer_validator_store,
+                    remote_signer_validator_store,
+                    fallback_signer_validator_store,
+                ],
                 validator_pubkey: PublicKeyBytes::from(&validator_pubkey),
             }
         }
@@ -387,3 +387,36 @@ fn invalid_prev_surrounds_new_multiple() {
     }
     .run()
 }
+
+#[test]
+fn has_signed_attestation() {
+    let dir = tempfile::tempdir().unwrap();
+    let slashing_db =
+        SlashingDatabase::create(&dir.path().join("slashing_protection.sqlite")).unwrap();
+    let pk = pubkey(0);
+    slashing_db.register_validator(pk).unwrap();
+
+    let attestation = attestation_data_builder(0, 1);
+    let signing_root = signed_att(&attestation).signing_root;
+    let other_root = signed_att(&attestation_data_builder(1, 1)).signing_root;
+    assert_eq!(
+        slashing_db.has_signed_attestation(&pk, Epoch::new(1), signing_root),
+        Ok(false)
+    );
+
+    slashing_db
+        .check_and_insert_attestation(&pk, &attestation, DEFAULT_DOMAIN)
+        .unwrap();
+    assert_eq!(
+        slashing_db.has_signed_attestation(&pk, Epoch::new(1), signing_root),
+        Ok(true)
+    );
+    assert_eq!(
+        slashing_db.has_signed_attestation(&pk, Epoch::new(1), other_root),
+        Ok(false)
+    );
+    assert_eq!(
+        slashing_db.has_signed_attestation(&pk, Epoch::new(2), signing_root),
+        Ok(false)
+    );
+}
@@ -0,0 +1,300 @@
+use crate::interchange::Interchange;
+#[cfg(feature = "postgres")]
+use crate::postgres_database::PostgresSlashingDatabase;
+use crate::{
+    InterchangeError, InterchangeImportOutcome, NotSafe, Safe, SigningRoot, SlashingDatabase,
+};
+use types::{AttestationData, BeaconBlockHeader, Epoch, Hash256, PublicKeyBytes, SignedRoot, Slot};
+
+/// The operations supported by every slashing protection database.
+///
+/// Implementations must check and insert each message atomically, and must prevent concurrent
+/// checks for the same validator from interleaving, so that slashable data is never inserted.
+pub trait SlashingProtection {
+    /// Register a validator with the slashing protection database.
+    ///
+    /// This allows the validator to record their signatures in the database, and check
+    /// for slashings.
+    fn register_validator(&self, validator_pk: PublicKeyBytes) -> Result<(), NotSafe> {
+        self.register_validators(std::iter::once(&validator_pk))
+    }
+
+    /// Register multiple validators with the slashing protection database.
+    fn register_validators<'a>(
+        &self,
+        public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
+    ) -> Result<(), NotSafe>;
+
+    /// Check that all of the given validators are registered.
+    fn check_validator_registrations<'a>(
+        &self,
+        public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
+    ) -> Result<(), NotSafe>;
+
+    /// Check a block proposal for slash safety, and if it is safe, record it in the database.
+    ///
+    /// This is the safe, externally-callable interface for checking block proposals.
+    fn check_and_insert_block_proposal(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        block_header: &BeaconBlockHeader,
+        domain: Hash256,
+    ) -> Result<Safe, NotSafe> {
+        self.check_and_insert_block_signing_root(
+            validator_pubkey,
+            block_header.slot,
+            block_header.signing_root(domain).into(),
+        )
+    }
+
+    /// As for `check_and_insert_block_proposal` but without requiring the whole `BeaconBlockHeader`.
+    fn check_and_insert_block_signing_root(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        slot: Slot,
+        signing_root: SigningRoot,
+    ) -> Result<Safe, NotSafe>;
+
+    /// Check an attestation for slash safety, and if it is safe, record it in the database.
+    ///
+    /// This is the safe, externally-callable interface for checking attestations.
+    fn check_and_insert_attestation(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        attestation: &AttestationData,
+        domain: Hash256,
+    ) -> Result<Safe, NotSafe> {
+        let attestation_signing_root = attestation.signing_root(domain).into();
+        self.check_and_insert_attestation_signing_root(
+            validator_pubkey,
+            attestation.source.epoch,
+            attestation.target.epoch,
+            attestation_signing_root,
+        )
+    }
+
+    /// As for `check_and_insert_attestation` but without requiring the whole `AttestationData`.
+    fn check_and_insert_attestation_signing_root(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        att_source_epoch: Epoch,
+        att_target_epoch: Epoch,
+        att_signing_root: SigningRoot,
+    ) -> Result<Safe, NotSafe>;
+
+    /// Import slashing protection from another client in the interchange format.
+    ///
+    /// This function will atomically import the entire interchange, failing if *any*
+    /// record cannot be imported.
+    fn import_interchange_info(
+        &self,
+        interchange: Interchange,
+        genesis_validators_root: Hash256,
+    ) -> Result<Vec<InterchangeImportOutcome>, InterchangeError>;
+
+    fn export_all_interchange_info(
+        &self,
+        genesis_validators_root: Hash256,
+    ) -> Result<Interchange, InterchangeError> {
+        self.export_interchange_info(genesis_validators_root, None)
+    }
+
+    fn export_interchange_info(
+        &self,
+        genesis_validators_root: Hash256,
+        selected_pubkeys: Option<&[PublicKeyBytes]>,
+    ) -> Result<Interchange, InterchangeError>;
+
+    /// Export slashing protection data for the given keys while also disabling them, atomically.
+    ///
+    /// Keys which are unknown to the database are silently omitted from the result.
+    fn export_and_disable_interchange_info(
+        &self,
+        genesis_validators_root: Hash256,
+        pubkeys: &[PublicKeyBytes],
+    ) -> Result<Interchange, InterchangeError>;
+
+    /// Prune the signed blocks table for the given public keys.
+    fn prune_all_signed_blocks<'a>(
+        &self,
+        public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
+        new_min_slot: Slot,
+    ) -> Result<(), NotSafe>;
+
+    /// Prune the signed attestations table for the given validator keys.
+    fn prune_all_signed_attestations<'a>(
+        &self,
+        public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
+        new_min_target: Epoch,
+    ) -> Result<(), NotSafe>;
+
+    /// Check whether the database records a block proposal with `signing_root` at `slot`.
+    ///
+    /// Records without a signing root, e.g. from a minified interchange, are assumed to match.
+    fn has_signed_block(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        slot: Slot,
+        signing_root: SigningRoot,
+    ) -> Result<bool, NotSafe>;
+
+    /// Check whether the database records an attestation with `signing_root` at `target_epoch`.
+    ///
+    /// Records without a signing root, e.g. from a minified interchange, are assumed to match.
+    fn has_signed_attestation(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        target_epoch: Epoch,
+        signing_root: SigningRoot,
+    ) -> Result<bool, NotSafe>;
+
+    fn num_validator_rows(&self) -> Result<u32, NotSafe>;
+}
+
+/// A slashing protection database using the backend selected at runtime.
+#[derive(Debug, Clone)]
+pub enum SlashingProtectionBackend {
+    Sqlite(SlashingDatabase),
+    #[cfg(feature = "postgres")]
+    Postgres(PostgresSlashingDatabase),
+}
+
+impl From<SlashingDatabase> for SlashingProtectionBackend {
+    fn from(db: SlashingDatabase) -> Self {
+        Self::Sqlite(db)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl From<PostgresSlashingDatabase> for SlashingProtectionBackend {
+    fn from(db: PostgresSlashingDatabase) -> Self {
+        Self::Postgres(db)
+    }
+}
+
+/// Call the same method on the database, regardless of its backend.
+macro_rules! map_backend {
+    ($backend:expr, $db:ident => $body:expr) => {
+        match $backend {
+            SlashingProtectionBackend::Sqlite($db) => $body,
+            #[cfg(feature = "postgres")]
+            SlashingProtectionBackend::Postgres($db) => $body,
+        }
+    };
+}
+
+impl SlashingProtection for SlashingProtectionBackend {
+    fn register_validators<'a>(
+        &self,
+        public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
+    ) -> Result<(), NotSafe> {
+        map_backend!(self, db => db.register_validators(public_keys))
+    }
+
+    fn check_validator_registrations<'a>(
+        &self,
+        public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
+    ) -> Result<(), NotSafe> {
+        map_backend!(self, db => db.check_validator_registrations(public_keys))
+    }
+
+    fn check_and_insert_block_signing_root(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        slot: Slot,
+        signing_root: SigningRoot,
+    ) -> Result<Safe, NotSafe> {
+        map_backend!(self, db => db.check_and_insert_block_signing_root(
+            validator_pubkey,
+            slot,
+            signing_root,
+        ))
+    }
+
+    fn check_and_insert_attestation_signing_root(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        att_source_epoch: Epoch,
+        att_target_epoch: Epoch,
+        att_signing_root: SigningRoot,
+    ) -> Result<Safe, NotSafe> {
+        map_backend!(self, db => db.check_and_insert_attestation_signing_root(
+            validator_pubkey,
+            att_source_epoch,
+            att_target_epoch,
+            att_signing_root,
+        ))
+    }
+
+    fn import_interchange_info(
+        &self,
+        interchange: Interchange,
+        genesis_validators_root: Hash256,
+    ) -> Result<Vec<InterchangeImportOutcome>, InterchangeError> {
+        map_backend!(self, db => db.import_interchange_info(interchange, genesis_validators_root))
+    }
+
+    fn export_interchange_info(
+        &self,
+        genesis_validators_root: Hash256,
+        selected_pubkeys: Option<&[PublicKeyBytes]>,
+    ) -> Result<Interchange, InterchangeError> {
+        map_backend!(self, db => db.export_interchange_info(
+            genesis_validators_root,
+            selected_pubkeys,
+        ))
+    }
+
+    fn export_and_disable_interchange_info(
+        &self,
+        genesis_validators_root: Hash256,
+        pubkeys: &[PublicKeyBytes],
+    ) -> Result<Interchange, InterchangeError> {
+        map_backend!(self, db => db.export_and_disable_interchange_info(
+            genesis_validators_root,
+            pubkeys,
+        ))
+    }
+
+    fn prune_all_signed_blocks<'a>(
+        &self,
+        public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
+        new_min_slot: Slot,
+    ) -> Result<(), NotSafe> {
+        map_backend!(self, db => db.prune_all_signed_blocks(public_keys, new_min_slot))
+    }
+
+    fn prune_all_signed_attestations<'a>(
+        &self,
+        public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
+        new_min_target: Epoch,
+    ) -> Result<(), NotSafe> {
+        map_backend!(self, db => db.prune_all_signed_attestations(public_keys, new_min_target))
+    }
+
+    fn has_signed_block(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        slot: Slot,
+        signing_root: SigningRoot,
+    ) -> Result<bool, NotSafe> {
+        map_backend!(self, db => db.has_signed_block(validator_pubkey, slot, signing_root))
+    }
+
+    fn has_signed_attestation(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        target_epoch: Epoch,
+        signing_root: SigningRoot,
+    ) -> Result<bool, NotSafe> {
+        map_backend!(self, db => db.has_signed_attestation(
+            validator_pubkey,
+            target_epoch,
+            signing_root,
+        ))
+    }
+
+    fn num_validator_rows(&self) -> Result<u32, NotSafe> {
+        map_backend!(self, db => db.num_validator_rows())
+    }
+}
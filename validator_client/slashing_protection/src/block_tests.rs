@@ -122,3 +122,41 @@ fn invalid_unregistered_validator() {
     }
     .run()
 }
+
+#[test]
+fn has_signed_block() {
+    let dir = tempfile::tempdir().unwrap();
+    let slashing_db =
+        SlashingDatabase::create(&dir.path().join("slashing_protection.sqlite")).unwrap();
+    let pk = pubkey(0);
+    slashing_db.register_validator(pk).unwrap();
+
+    let signed = block(1);
+    let signing_root = SignedBlock::from_header(&signed, DEFAULT_DOMAIN).signing_root;
+    let other_root = SignedBlock::from_header(&block(1), DEFAULT_DOMAIN).signing_root;
+    assert_eq!(
+        slashing_db.has_signed_block(&pk, signed.slot, signing_root),
+        Ok(false)
+    );
+
+    slashing_db
+        .check_and_insert_block_proposal(&pk, &signed, DEFAULT_DOMAIN)
+        .unwrap();
+    assert_eq!(
+        slashing_db.has_signed_block(&pk, signed.slot, signing_root),
+        Ok(true)
+    );
+    assert_eq!(
+        slashing_db.has_signed_block(&pk, signed.slot, other_root),
+        Ok(false)
+    );
+
+    // A block without a signing root could be any block.
+    slashing_db
+        .check_and_insert_block_signing_root(&pk, Slot::new(2), SigningRoot::default())
+        .unwrap();
+    assert_eq!(
+        slashing_db.has_signed_block(&pk, Slot::new(2), other_root),
+        Ok(true)
+    );
+}
@@ -1,7 +1,7 @@
 use crate::{
     interchange::{Interchange, SignedAttestation, SignedBlock},
     test_utils::{pubkey, DEFAULT_GENESIS_VALIDATORS_ROOT},
-    SigningRoot, SlashingDatabase,
+    SigningRoot, SlashingDatabase, SlashingProtection,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -69,7 +69,11 @@ impl MultiTestCase {
         let dir = tempdir().unwrap();
         let slashing_db_file = dir.path().join("slashing_protection.sqlite");
         let slashing_db = SlashingDatabase::create(&slashing_db_file).unwrap();
+        self.run_with_db(&slashing_db, minify);
+    }
 
+    /// Run the test against `slashing_db`, which must be empty.
+    pub fn run_with_db(&self, slashing_db: &impl SlashingProtection, minify: bool) {
         for test_case in &self.steps {
             // If the test case is marked as containing slashable data, then the spec allows us to
             // fail to import the file. However, we minify on import and ignore slashable data, so
@@ -1,15 +1,21 @@
 mod attestation_tests;
+mod backend;
 mod block_tests;
 mod extra_interchange_tests;
 pub mod interchange;
 pub mod interchange_test;
 mod parallel_tests;
+#[cfg(feature = "postgres")]
+mod postgres_database;
 mod registration_tests;
 mod signed_attestation;
 mod signed_block;
 mod slashing_database;
 pub mod test_utils;
 
+pub use crate::backend::{SlashingProtection, SlashingProtectionBackend};
+#[cfg(feature = "postgres")]
+pub use crate::postgres_database::PostgresSlashingDatabase;
 pub use crate::signed_attestation::{InvalidAttestation, SignedAttestation};
 pub use crate::signed_block::{InvalidBlock, SignedBlock};
 pub use crate::slashing_database::{
@@ -115,6 +121,13 @@ impl From<SQLError> for NotSafe {
     }
 }
 
+#[cfg(feature = "postgres")]
+impl From<postgres::Error> for NotSafe {
+    fn from(error: postgres::Error) -> NotSafe {
+        NotSafe::SQLError(error.to_string())
+    }
+}
+
 impl From<r2d2::Error> for NotSafe {
     fn from(error: r2d2::Error) -> Self {
         // Use `Display` impl to print "timed out waiting for connection"
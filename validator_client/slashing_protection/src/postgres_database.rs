@@ -0,0 +1,912 @@
+//! A slashing protection database stored in PostgreSQL, which may be shared by several validator
+//! clients (e.g. an active/standby pair).
+//!
+//! The schema mirrors the SQLite database. Rather than locking the entire database for every
+//! signature, each check-and-insert locks the signing validator's row in the `validators` table
+//! with `SELECT ... FOR UPDATE`. Concurrent checks for the same validator are serialised on that
+//! lock (even across processes), while checks for different validators proceed in parallel.
+use crate::interchange::{
+    Interchange, InterchangeData, InterchangeMetadata, SignedAttestation as InterchangeAttestation,
+    SignedBlock as InterchangeBlock,
+};
+use crate::signed_attestation::InvalidAttestation;
+use crate::signed_block::InvalidBlock;
+use crate::slashing_database::{check_interchange_metadata, max_or, ValidatorSummary};
+use crate::{
+    InterchangeError, InterchangeImportOutcome, NotSafe, Safe, SignedAttestation, SignedBlock,
+    SigningRoot, SlashingProtection, SUPPORTED_INTERCHANGE_FORMAT_VERSION,
+};
+use postgres::{NoTls, Row, Transaction};
+use r2d2::PooledConnection;
+use r2d2_postgres::PostgresConnectionManager;
+use sensitive_url::SensitiveUrl;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::{Handle, RuntimeFlavor};
+use types::{Epoch, Hash256, PublicKeyBytes, Slot};
+
+type Pool = r2d2::Pool<PostgresConnectionManager<NoTls>>;
+
+/// Unlike SQLite, PostgreSQL supports concurrent transactions, so several connections are kept
+/// open to allow validators to be checked in parallel.
+pub const POOL_SIZE: u32 = 8;
+pub const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Arbitrary key for the advisory lock which serialises schema creation.
+const SCHEMA_LOCK_ID: i64 = 0x5eed_5afe;
+
+const CREATE_SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS validators (
+        id BIGSERIAL PRIMARY KEY,
+        public_key TEXT NOT NULL UNIQUE,
+        enabled BOOLEAN NOT NULL DEFAULT TRUE
+    );
+    CREATE TABLE IF NOT EXISTS signed_blocks (
+        validator_id BIGINT NOT NULL REFERENCES validators(id),
+        slot BIGINT NOT NULL,
+        signing_root BYTEA NOT NULL,
+        UNIQUE (validator_id, slot)
+    );
+    CREATE TABLE IF NOT EXISTS signed_attestations (
+        validator_id BIGINT NOT NULL REFERENCES validators(id),
+        source_epoch BIGINT NOT NULL,
+        target_epoch BIGINT NOT NULL,
+        signing_root BYTEA NOT NULL,
+        UNIQUE (validator_id, target_epoch)
+    );
+";
+
+#[derive(Debug, Clone)]
+pub struct PostgresSlashingDatabase {
+    conn_pool: Arc<ConnectionPool>,
+}
+
+/// A connection pool which may be dropped from within an async context.
+#[derive(Debug)]
+struct ConnectionPool(Option<Pool>);
+
+impl ConnectionPool {
+    fn get(&self) -> Result<PooledConnection<PostgresConnectionManager<NoTls>>, r2d2::Error> {
+        self.0
+            .as_ref()
+            .expect("pool is only removed when dropped")
+            .get()
+    }
+}
+
+impl Drop for ConnectionPool {
+    fn drop(&mut self) {
+        // Closing the pool's connections blocks.
+        let pool = self.0.take();
+        blocking(|| drop(pool));
+    }
+}
+
+impl PostgresSlashingDatabase {
+    /// Connect to the database at `url`, creating the slashing protection tables if they do not
+    /// already exist.
+    pub fn open_or_create(url: &SensitiveUrl) -> Result<Self, NotSafe> {
+        let db = Self::connect(url)?;
+        db.with_transaction(|txn| {
+            // Prevent clients racing to create the tables.
+            txn.execute("SELECT pg_advisory_xact_lock($1)", &[&SCHEMA_LOCK_ID])?;
+            txn.batch_execute(CREATE_SCHEMA)?;
+            Ok::<_, NotSafe>(())
+        })?;
+        Ok(db)
+    }
+
+    /// Connect to an existing slashing protection database at `url`.
+    ///
+    /// Error if the slashing protection tables have not been created.
+    pub fn open(url: &SensitiveUrl) -> Result<Self, NotSafe> {
+        let db = Self::connect(url)?;
+        let initialized = db.with_transaction(|txn| {
+            let row = txn.query_one(
+                "SELECT to_regclass('validators') IS NOT NULL
+                    AND to_regclass('signed_blocks') IS NOT NULL
+                    AND to_regclass('signed_attestations') IS NOT NULL",
+                &[],
+            )?;
+            Ok::<bool, NotSafe>(row.try_get(0)?)
+        })?;
+        if initialized {
+            Ok(db)
+        } else {
+            Err(NotSafe::SQLError(format!(
+                "slashing protection tables do not exist in {}",
+                url
+            )))
+        }
+    }
+
+    fn connect(url: &SensitiveUrl) -> Result<Self, NotSafe> {
+        let config: postgres::Config = url.full.as_str().parse()?;
+        let manager = PostgresConnectionManager::new(config, NoTls);
+        let conn_pool = blocking(|| {
+            Pool::builder()
+                .max_size(POOL_SIZE)
+                .connection_timeout(CONNECTION_TIMEOUT)
+                .build(manager)
+        })
+        .map_err(|e| NotSafe::SQLError(format!("Unable to connect to database: {:?}", e)))?;
+        Ok(Self {
+            conn_pool: Arc::new(ConnectionPool(Some(conn_pool))),
+        })
+    }
+
+    /// Execute a database transaction as a closure, committing if `f` returns `Ok`.
+    pub fn with_transaction<T, U, F>(&self, f: F) -> Result<T, U>
+    where
+        F: FnOnce(&mut Transaction) -> Result<T, U>,
+        U: From<NotSafe>,
+    {
+        blocking(|| {
+            let mut conn = self.conn_pool.get().map_err(NotSafe::from)?;
+            let mut txn = conn.transaction().map_err(NotSafe::from)?;
+            let value = f(&mut txn)?;
+            txn.commit().map_err(NotSafe::from)?;
+            Ok(value)
+        })
+    }
+
+    fn register_validators_in_txn<'a>(
+        public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
+        txn: &mut Transaction,
+    ) -> Result<(), NotSafe> {
+        let stmt = txn.prepare(
+            "INSERT INTO validators (public_key, enabled) VALUES ($1, TRUE)
+             ON CONFLICT (public_key) DO UPDATE SET enabled = TRUE WHERE NOT validators.enabled",
+        )?;
+        for pubkey in public_keys {
+            txn.execute(&stmt, &[&pubkey.as_hex_string()])?;
+        }
+        Ok(())
+    }
+
+    fn get_validator_id_with_status(
+        txn: &mut Transaction,
+        public_key: &PublicKeyBytes,
+    ) -> Result<Option<(i64, bool)>, NotSafe> {
+        txn.query_opt(
+            "SELECT id, enabled FROM validators WHERE public_key = $1",
+            &[&public_key.as_hex_string()],
+        )?
+        .map(|row| Ok((row.try_get(0)?, row.try_get(1)?)))
+        .transpose()
+    }
+
+    /// Get the ID of an enabled validator without locking its row.
+    fn get_enabled_validator_id(
+        txn: &mut Transaction,
+        public_key: &PublicKeyBytes,
+    ) -> Result<i64, NotSafe> {
+        match Self::get_validator_id_with_status(txn, public_key)? {
+            Some((validator_id, true)) => Ok(validator_id),
+            Some((_, false)) => Err(NotSafe::DisabledValidator(*public_key)),
+            None => Err(NotSafe::UnregisteredValidator(*public_key)),
+        }
+    }
+
+    /// Get the ID of an enabled validator, locking its row until the end of the transaction.
+    ///
+    /// Every mutation of a validator's signing history must take this lock first.
+    fn lock_validator(txn: &mut Transaction, public_key: &PublicKeyBytes) -> Result<i64, NotSafe> {
+        let (validator_id, enabled): (i64, bool) = txn
+            .query_opt(
+                "SELECT id, enabled FROM validators WHERE public_key = $1 FOR UPDATE",
+                &[&public_key.as_hex_string()],
+            )?
+            .map(|row| Ok::<_, NotSafe>((row.try_get(0)?, row.try_get(1)?)))
+            .transpose()?
+            .ok_or(NotSafe::UnregisteredValidator(*public_key))?;
+        if enabled {
+            Ok(validator_id)
+        } else {
+            Err(NotSafe::DisabledValidator(*public_key))
+        }
+    }
+
+    /// Check a block proposal from a locked validator for slash safety.
+    fn check_block_proposal(
+        txn: &mut Transaction,
+        validator_id: i64,
+        slot: Slot,
+        signing_root: SigningRoot,
+    ) -> Result<Safe, NotSafe> {
+        let existing_block = txn
+            .query_opt(
+                "SELECT slot, signing_root
+                 FROM signed_blocks
+                 WHERE validator_id = $1 AND slot = $2",
+                &[&validator_id, &to_i64(slot.as_u64())?],
+            )?
+            .map(|row| signed_block_from_row(&row))
+            .transpose()?;
+
+        if let Some(existing_block) = existing_block {
+            if existing_block.signing_root == signing_root {
+                // Same slot and same hash -> we're re-broadcasting a previously signed block
+                return Ok(Safe::SameData);
+            } else {
+                // Same epoch but not the same hash -> it's a DoubleBlockProposal
+                return Err(NotSafe::InvalidBlock(InvalidBlock::DoubleBlockProposal(
+                    existing_block,
+                )));
+            }
+        }
+
+        let min_slot = txn
+            .query_one(
+                "SELECT MIN(slot) FROM signed_blocks WHERE validator_id = $1",
+                &[&validator_id],
+            )?
+            .try_get::<_, Option<i64>>(0)?
+            .map(|slot| from_i64(slot).map(Slot::new))
+            .transpose()?;
+
+        if let Some(min_slot) = min_slot {
+            if slot <= min_slot {
+                return Err(NotSafe::InvalidBlock(
+                    InvalidBlock::SlotViolatesLowerBound {
+                        block_slot: slot,
+                        bound_slot: min_slot,
+                    },
+                ));
+            }
+        }
+
+        Ok(Safe::Valid)
+    }
+
+    /// Check an attestation from a locked validator for slash safety.
+    fn check_attestation(
+        txn: &mut Transaction,
+        validator_id: i64,
+        att_source_epoch: Epoch,
+        att_target_epoch: Epoch,
+        att_signing_root: SigningRoot,
+    ) -> Result<Safe, NotSafe> {
+        let source = to_i64(att_source_epoch.as_u64())?;
+        let target = to_i64(att_target_epoch.as_u64())?;
+
+        // Check for a double vote. Namely, an existing attestation with the same target epoch,
+        // and a different signing root.
+        let same_target_att = txn
+            .query_opt(
+                "SELECT source_epoch, target_epoch, signing_root
+                 FROM signed_attestations
+                 WHERE validator_id = $1 AND target_epoch = $2",
+                &[&validator_id, &target],
+            )?
+            .map(|row| signed_attestation_from_row(&row))
+            .transpose()?;
+
+        if let Some(existing_attestation) = same_target_att {
+            if existing_attestation.signing_root == att_signing_root {
+                return Ok(Safe::SameData);
+            } else {
+                return Err(NotSafe::InvalidAttestation(InvalidAttestation::DoubleVote(
+                    existing_attestation,
+                )));
+            }
+        }
+
+        // Check that no previous vote is surrounding `attestation`.
+        let surrounding_attestation = txn
+            .query_opt(
+                "SELECT source_epoch, target_epoch, signing_root
+                 FROM signed_attestations
+                 WHERE validator_id = $1 AND source_epoch < $2 AND target_epoch > $3
+                 ORDER BY target_epoch DESC
+                 LIMIT 1",
+                &[&validator_id, &source, &target],
+            )?
+            .map(|row| signed_attestation_from_row(&row))
+            .transpose()?;
+
+        if let Some(prev) = surrounding_attestation {
+            return Err(NotSafe::InvalidAttestation(
+                InvalidAttestation::PrevSurroundsNew { prev },
+            ));
+        }
+
+        // Check that no previous vote is surrounded by `attestation`.
+        let surrounded_attestation = txn
+            .query_opt(
+                "SELECT source_epoch, target_epoch, signing_root
+                 FROM signed_attestations
+                 WHERE validator_id = $1 AND source_epoch > $2 AND target_epoch < $3
+                 ORDER BY target_epoch DESC
+                 LIMIT 1",
+                &[&validator_id, &source, &target],
+            )?
+            .map(|row| signed_attestation_from_row(&row))
+            .transpose()?;
+
+        if let Some(prev) = surrounded_attestation {
+            return Err(NotSafe::InvalidAttestation(
+                InvalidAttestation::NewSurroundsPrev { prev },
+            ));
+        }
+
+        // Check lower bounds, as for the SQLite database.
+        let row = txn.query_one(
+            "SELECT MIN(source_epoch), MIN(target_epoch)
+             FROM signed_attestations
+             WHERE validator_id = $1",
+            &[&validator_id],
+        )?;
+        let min_source = optional_epoch(&row, 0)?;
+        let min_target = optional_epoch(&row, 1)?;
+
+        if let Some(min_source) = min_source {
+            if att_source_epoch < min_source {
+                return Err(NotSafe::InvalidAttestation(
+                    InvalidAttestation::SourceLessThanLowerBound {
+                        source_epoch: att_source_epoch,
+                        bound_epoch: min_source,
+                    },
+                ));
+            }
+        }
+
+        if let Some(min_target) = min_target {
+            if att_target_epoch <= min_target {
+                return Err(NotSafe::InvalidAttestation(
+                    InvalidAttestation::TargetLessThanOrEqLowerBound {
+                        target_epoch: att_target_epoch,
+                        bound_epoch: min_target,
+                    },
+                ));
+            }
+        }
+
+        Ok(Safe::Valid)
+    }
+
+    fn insert_block_proposal(
+        txn: &mut Transaction,
+        validator_id: i64,
+        slot: Slot,
+        signing_root: SigningRoot,
+    ) -> Result<(), NotSafe> {
+        txn.execute(
+            "INSERT INTO signed_blocks (validator_id, slot, signing_root)
+             VALUES ($1, $2, $3)",
+            &[
+                &validator_id,
+                &to_i64(slot.as_u64())?,
+                &signing_root.to_hash256_raw().as_bytes(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn insert_attestation(
+        txn: &mut Transaction,
+        validator_id: i64,
+        att_source_epoch: Epoch,
+        att_target_epoch: Epoch,
+        att_signing_root: SigningRoot,
+    ) -> Result<(), NotSafe> {
+        txn.execute(
+            "INSERT INTO signed_attestations (validator_id, source_epoch, target_epoch, signing_root)
+             VALUES ($1, $2, $3, $4)",
+            &[
+                &validator_id,
+                &to_i64(att_source_epoch.as_u64())?,
+                &to_i64(att_target_epoch.as_u64())?,
+                &att_signing_root.to_hash256_raw().as_bytes(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Import a single interchange record, as for `SlashingDatabase::import_interchange_record`.
+    fn import_interchange_record(
+        record: InterchangeData,
+        txn: &mut Transaction,
+    ) -> Result<ValidatorSummary, NotSafe> {
+        let pubkey = &record.pubkey;
+
+        Self::register_validators_in_txn(std::iter::once(pubkey), txn)?;
+        let validator_id = Self::lock_validator(txn, pubkey)?;
+
+        // Summary of minimum and maximum messages pre-import.
+        let prev_summary = Self::validator_summary(txn, validator_id)?;
+
+        // If the interchange contains any blocks, replace all blocks with a synthetic block at
+        // the new maximum slot.
+        if let Some(max_block) = record.signed_blocks.iter().max_by_key(|b| b.slot) {
+            let new_max_slot = max_or(prev_summary.max_block_slot, max_block.slot);
+
+            txn.execute(
+                "DELETE FROM signed_blocks WHERE validator_id = $1",
+                &[&validator_id],
+            )?;
+            Self::insert_block_proposal(txn, validator_id, new_max_slot, SigningRoot::default())?;
+        }
+
+        // Likewise, replace all attestations with a synthetic attestation with the maximum source
+        // and target.
+        let max_source_attestation = record
+            .signed_attestations
+            .iter()
+            .max_by_key(|att| att.source_epoch);
+        let max_target_attestation = record
+            .signed_attestations
+            .iter()
+            .max_by_key(|att| att.target_epoch);
+
+        if let (Some(max_source_att), Some(max_target_att)) =
+            (max_source_attestation, max_target_attestation)
+        {
+            let source_epoch = max_or(
+                prev_summary.max_attestation_source,
+                max_source_att.source_epoch,
+            );
+            let target_epoch = max_or(
+                prev_summary.max_attestation_target,
+                max_target_att.target_epoch,
+            );
+
+            txn.execute(
+                "DELETE FROM signed_attestations WHERE validator_id = $1",
+                &[&validator_id],
+            )?;
+            Self::insert_attestation(
+                txn,
+                validator_id,
+                source_epoch,
+                target_epoch,
+                SigningRoot::default(),
+            )?;
+        }
+
+        let summary = Self::validator_summary(txn, validator_id)?;
+
+        // Check that the summary is consistent with having added the new data.
+        if summary.check_block_consistency(&prev_summary, !record.signed_blocks.is_empty())
+            && summary.check_attestation_consistency(
+                &prev_summary,
+                !record.signed_attestations.is_empty(),
+            )
+        {
+            Ok(summary)
+        } else {
+            // This should never occur and is indicative of a bug in the import code.
+            Err(NotSafe::ConsistencyError)
+        }
+    }
+
+    fn export_interchange_info_in_txn(
+        genesis_validators_root: Hash256,
+        selected_pubkeys: Option<&[PublicKeyBytes]>,
+        txn: &mut Transaction,
+    ) -> Result<Interchange, InterchangeError> {
+        // Determine the validator IDs and public keys to export data for.
+        let to_export = if let Some(selected_pubkeys) = selected_pubkeys {
+            selected_pubkeys
+                .iter()
+                .map(|pubkey| {
+                    let (id, _) = Self::get_validator_id_with_status(txn, pubkey)?
+                        .ok_or(NotSafe::UnregisteredValidator(*pubkey))?;
+                    Ok((id, *pubkey))
+                })
+                .collect::<Result<Vec<_>, InterchangeError>>()?
+        } else {
+            txn.query("SELECT id, public_key FROM validators ORDER BY id ASC", &[])?
+                .into_iter()
+                .map(|row| {
+                    let validator_id: i64 = row.try_get(0)?;
+                    let pubkey = row
+                        .try_get::<_, String>(1)?
+                        .parse()
+                        .map_err(InterchangeError::InvalidPubkey)?;
+                    Ok((validator_id, pubkey))
+                })
+                .collect::<Result<Vec<_>, InterchangeError>>()?
+        };
+
+        let data = to_export
+            .into_iter()
+            .map(|(validator_id, pubkey)| {
+                let signed_blocks = txn
+                    .query(
+                        "SELECT slot, signing_root
+                         FROM signed_blocks
+                         WHERE validator_id = $1
+                         ORDER BY slot ASC",
+                        &[&validator_id],
+                    )?
+                    .iter()
+                    .map(|row| {
+                        let block = signed_block_from_row(row)?;
+                        Ok(InterchangeBlock {
+                            slot: block.slot,
+                            signing_root: block.signing_root.to_hash256(),
+                        })
+                    })
+                    .collect::<Result<_, NotSafe>>()?;
+                let signed_attestations = txn
+                    .query(
+                        "SELECT source_epoch, target_epoch, signing_root
+                         FROM signed_attestations
+                         WHERE validator_id = $1
+                         ORDER BY source_epoch ASC, target_epoch ASC",
+                        &[&validator_id],
+                    )?
+                    .iter()
+                    .map(|row| {
+                        let attestation = signed_attestation_from_row(row)?;
+                        Ok(InterchangeAttestation {
+                            source_epoch: attestation.source_epoch,
+                            target_epoch: attestation.target_epoch,
+                            signing_root: attestation.signing_root.to_hash256(),
+                        })
+                    })
+                    .collect::<Result<_, NotSafe>>()?;
+                Ok(InterchangeData {
+                    pubkey,
+                    signed_blocks,
+                    signed_attestations,
+                })
+            })
+            .collect::<Result<_, InterchangeError>>()?;
+
+        let metadata = InterchangeMetadata {
+            interchange_format_version: SUPPORTED_INTERCHANGE_FORMAT_VERSION,
+            genesis_validators_root,
+        };
+
+        Ok(Interchange { metadata, data })
+    }
+
+    fn validator_summary(
+        txn: &mut Transaction,
+        validator_id: i64,
+    ) -> Result<ValidatorSummary, NotSafe> {
+        let block_row = txn.query_one(
+            "SELECT MIN(slot), MAX(slot) FROM signed_blocks WHERE validator_id = $1",
+            &[&validator_id],
+        )?;
+        let attestation_row = txn.query_one(
+            "SELECT MIN(source_epoch), MIN(target_epoch), MAX(source_epoch), MAX(target_epoch)
+             FROM signed_attestations
+             WHERE validator_id = $1",
+            &[&validator_id],
+        )?;
+
+        Ok(ValidatorSummary {
+            min_block_slot: optional_slot(&block_row, 0)?,
+            max_block_slot: optional_slot(&block_row, 1)?,
+            min_attestation_source: optional_epoch(&attestation_row, 0)?,
+            min_attestation_target: optional_epoch(&attestation_row, 1)?,
+            max_attestation_source: optional_epoch(&attestation_row, 2)?,
+            max_attestation_target: optional_epoch(&attestation_row, 3)?,
+        })
+    }
+}
+
+impl SlashingProtection for PostgresSlashingDatabase {
+    fn register_validators<'a>(
+        &self,
+        public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
+    ) -> Result<(), NotSafe> {
+        self.with_transaction(|txn| Self::register_validators_in_txn(public_keys, txn))
+    }
+
+    fn check_validator_registrations<'a>(
+        &self,
+        mut public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
+    ) -> Result<(), NotSafe> {
+        self.with_transaction(|txn| {
+            public_keys.try_for_each(|public_key| {
+                match Self::get_validator_id_with_status(txn, public_key)? {
+                    Some((_, true)) => Ok(()),
+                    Some((_, false)) => Err(NotSafe::DisabledValidator(*public_key)),
+                    None => Err(NotSafe::UnregisteredValidator(*public_key)),
+                }
+            })
+        })
+    }
+
+    fn check_and_insert_block_signing_root(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        slot: Slot,
+        signing_root: SigningRoot,
+    ) -> Result<Safe, NotSafe> {
+        self.with_transaction(|txn| {
+            let validator_id = Self::lock_validator(txn, validator_pubkey)?;
+            let safe = Self::check_block_proposal(txn, validator_id, slot, signing_root)?;
+
+            if safe != Safe::SameData {
+                Self::insert_block_proposal(txn, validator_id, slot, signing_root)?;
+            }
+            Ok(safe)
+        })
+    }
+
+    fn check_and_insert_attestation_signing_root(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        att_source_epoch: Epoch,
+        att_target_epoch: Epoch,
+        att_signing_root: SigningRoot,
+    ) -> Result<Safe, NotSafe> {
+        // Although it's not required to avoid slashing, we disallow attestations
+        // which are obviously invalid by virtue of their source epoch exceeding their target.
+        if att_source_epoch > att_target_epoch {
+            return Err(NotSafe::InvalidAttestation(
+                InvalidAttestation::SourceExceedsTarget,
+            ));
+        }
+
+        self.with_transaction(|txn| {
+            let validator_id = Self::lock_validator(txn, validator_pubkey)?;
+            let safe = Self::check_attestation(
+                txn,
+                validator_id,
+                att_source_epoch,
+                att_target_epoch,
+                att_signing_root,
+            )?;
+
+            if safe != Safe::SameData {
+                Self::insert_attestation(
+                    txn,
+                    validator_id,
+                    att_source_epoch,
+                    att_target_epoch,
+                    att_signing_root,
+                )?;
+            }
+            Ok(safe)
+        })
+    }
+
+    fn import_interchange_info(
+        &self,
+        interchange: Interchange,
+        genesis_validators_root: Hash256,
+    ) -> Result<Vec<InterchangeImportOutcome>, InterchangeError> {
+        check_interchange_metadata(&interchange, genesis_validators_root)?;
+
+        // Import the entire batch in a single transaction, which is rolled back if any record
+        // fails to import.
+        self.with_transaction(|txn| {
+            let mut import_outcomes = vec![];
+            let mut commit = true;
+
+            for record in interchange.data {
+                let pubkey = record.pubkey;
+                // Use a savepoint so that the transaction remains usable after a failure, allowing
+                // the outcome of every record to be reported.
+                let mut savepoint = txn.savepoint("import_record")?;
+                match Self::import_interchange_record(record, &mut savepoint) {
+                    Ok(summary) => {
+                        savepoint.commit()?;
+                        import_outcomes.push(InterchangeImportOutcome::Success { pubkey, summary });
+                    }
+                    Err(error) => {
+                        drop(savepoint);
+                        import_outcomes.push(InterchangeImportOutcome::Failure { pubkey, error });
+                        commit = false;
+                    }
+                }
+            }
+
+            if commit {
+                Ok(import_outcomes)
+            } else {
+                Err(InterchangeError::AtomicBatchAborted(import_outcomes))
+            }
+        })
+    }
+
+    fn export_interchange_info(
+        &self,
+        genesis_validators_root: Hash256,
+        selected_pubkeys: Option<&[PublicKeyBytes]>,
+    ) -> Result<Interchange, InterchangeError> {
+        self.with_transaction(|txn| {
+            Self::export_interchange_info_in_txn(genesis_validators_root, selected_pubkeys, txn)
+        })
+    }
+
+    fn export_and_disable_interchange_info(
+        &self,
+        genesis_validators_root: Hash256,
+        pubkeys: &[PublicKeyBytes],
+    ) -> Result<Interchange, InterchangeError> {
+        self.with_transaction(|txn| {
+            let mut known_pubkeys = vec![];
+            for pubkey in pubkeys {
+                // Disabling the validator also locks its row, so that no other client can sign
+                // with it between the export and the end of the transaction.
+                let disabled = txn.execute(
+                    "UPDATE validators SET enabled = FALSE WHERE public_key = $1",
+                    &[&pubkey.as_hex_string()],
+                )?;
+                if disabled > 0 {
+                    known_pubkeys.push(*pubkey);
+                }
+            }
+            Self::export_interchange_info_in_txn(genesis_validators_root, Some(&known_pubkeys), txn)
+        })
+    }
+
+    fn prune_all_signed_blocks<'a>(
+        &self,
+        public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
+        new_min_slot: Slot,
+    ) -> Result<(), NotSafe> {
+        let new_min_slot = to_i64(new_min_slot.as_u64())?;
+        // Prune each validator in its own transaction, so that validators are locked one at a
+        // time and other clients pruning concurrently cannot deadlock with us.
+        for public_key in public_keys {
+            self.with_transaction(|txn| {
+                let validator_id = Self::lock_validator(txn, public_key)?;
+                txn.execute(
+                    "DELETE FROM signed_blocks
+                     WHERE
+                        validator_id = $1 AND
+                        slot < $2 AND
+                        slot < (SELECT MAX(slot)
+                                FROM signed_blocks
+                                WHERE validator_id = $1)",
+                    &[&validator_id, &new_min_slot],
+                )?;
+                Ok::<_, NotSafe>(())
+            })?;
+        }
+        Ok(())
+    }
+
+    fn prune_all_signed_attestations<'a>(
+        &self,
+        public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
+        new_min_target: Epoch,
+    ) -> Result<(), NotSafe> {
+        let new_min_target = to_i64(new_min_target.as_u64())?;
+        // See `prune_all_signed_blocks`.
+        for public_key in public_keys {
+            self.with_transaction(|txn| {
+                let validator_id = Self::lock_validator(txn, public_key)?;
+                // The `MAX(target_epoch)` acts as a guard to prevent accidentally clearing the DB.
+                txn.execute(
+                    "DELETE FROM signed_attestations
+                     WHERE
+                        validator_id = $1 AND
+                        target_epoch < $2 AND
+                        target_epoch < (SELECT MAX(target_epoch)
+                                        FROM signed_attestations
+                                        WHERE validator_id = $1)",
+                    &[&validator_id, &new_min_target],
+                )?;
+                Ok::<_, NotSafe>(())
+            })?;
+        }
+        Ok(())
+    }
+
+    fn has_signed_block(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        slot: Slot,
+        signing_root: SigningRoot,
+    ) -> Result<bool, NotSafe> {
+        self.with_transaction(|txn| {
+            let validator_id = Self::get_enabled_validator_id(txn, validator_pubkey)?;
+            let existing_block = txn
+                .query_opt(
+                    "SELECT slot, signing_root
+                     FROM signed_blocks
+                     WHERE validator_id = $1 AND slot = $2",
+                    &[&validator_id, &to_i64(slot.as_u64())?],
+                )?
+                .map(|row| signed_block_from_row(&row))
+                .transpose()?;
+            Ok(existing_block.is_some_and(|block| {
+                block.signing_root.is_null() || block.signing_root == signing_root
+            }))
+        })
+    }
+
+    fn has_signed_attestation(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        target_epoch: Epoch,
+        signing_root: SigningRoot,
+    ) -> Result<bool, NotSafe> {
+        self.with_transaction(|txn| {
+            let validator_id = Self::get_enabled_validator_id(txn, validator_pubkey)?;
+            let existing_attestation = txn
+                .query_opt(
+                    "SELECT source_epoch, target_epoch, signing_root
+                     FROM signed_attestations
+                     WHERE validator_id = $1 AND target_epoch = $2",
+                    &[&validator_id, &to_i64(target_epoch.as_u64())?],
+                )?
+                .map(|row| signed_attestation_from_row(&row))
+                .transpose()?;
+            Ok(existing_attestation.is_some_and(|attestation| {
+                attestation.signing_root.is_null() || attestation.signing_root == signing_root
+            }))
+        })
+    }
+
+    fn num_validator_rows(&self) -> Result<u32, NotSafe> {
+        self.with_transaction(|txn| {
+            let count: i64 = txn
+                .query_one("SELECT COUNT(*) FROM validators", &[])?
+                .try_get(0)?;
+            u32::try_from(count).map_err(|e| NotSafe::SQLError(e.to_string()))
+        })
+    }
+}
+
+/// Run `f`, which uses the blocking `postgres` client.
+///
+/// The blocking client drives its own async runtime, which cannot be started from within the
+/// VC's runtime without first telling tokio that the current thread will block. This is not
+/// possible on a current-thread runtime, which must not be used with this database.
+fn blocking<T>(f: impl FnOnce() -> T) -> T {
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(f)
+        }
+        _ => f(),
+    }
+}
+
+/// Slots and epochs are stored as `BIGINT`, which is signed.
+fn to_i64(value: u64) -> Result<i64, NotSafe> {
+    i64::try_from(value).map_err(|e| NotSafe::SQLError(format!("{value} out of range: {e}")))
+}
+
+fn from_i64(value: i64) -> Result<u64, NotSafe> {
+    u64::try_from(value).map_err(|e| NotSafe::SQLError(format!("{value} out of range: {e}")))
+}
+
+fn optional_slot(row: &Row, column: usize) -> Result<Option<Slot>, NotSafe> {
+    row.try_get::<_, Option<i64>>(column)?
+        .map(|slot| from_i64(slot).map(Slot::new))
+        .transpose()
+}
+
+fn optional_epoch(row: &Row, column: usize) -> Result<Option<Epoch>, NotSafe> {
+    row.try_get::<_, Option<i64>>(column)?
+        .map(|epoch| from_i64(epoch).map(Epoch::new))
+        .transpose()
+}
+
+/// Safely parse a `SigningRoot` from the given `column` of a `row`.
+fn signing_root_from_row(row: &Row, column: usize) -> Result<SigningRoot, NotSafe> {
+    let bytes: &[u8] = row.try_get(column)?;
+    if bytes.len() == 32 {
+        Ok(SigningRoot::from(Hash256::from_slice(bytes)))
+    } else {
+        Err(NotSafe::SQLError(format!(
+            "Invalid length for Hash256: {}",
+            bytes.len()
+        )))
+    }
+}
+
+/// Parse a row of `(slot, signing_root)`.
+fn signed_block_from_row(row: &Row) -> Result<SignedBlock, NotSafe> {
+    let slot = Slot::new(from_i64(row.try_get(0)?)?);
+    let signing_root = signing_root_from_row(row, 1)?;
+    Ok(SignedBlock::new(slot, signing_root))
+}
+
+/// Parse a row of `(source_epoch, target_epoch, signing_root)`.
+fn signed_attestation_from_row(row: &Row) -> Result<SignedAttestation, NotSafe> {
+    let source = Epoch::new(from_i64(row.try_get(0)?)?);
+    let target = Epoch::new(from_i64(row.try_get(1)?)?);
+    let signing_root = signing_root_from_row(row, 2)?;
+    Ok(SignedAttestation::new(source, target, signing_root))
+}
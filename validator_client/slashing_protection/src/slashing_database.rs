@@ -4,14 +4,17 @@ use crate::interchange::{
 };
 use crate::signed_attestation::InvalidAttestation;
 use crate::signed_block::InvalidBlock;
-use crate::{signing_root_from_row, NotSafe, Safe, SignedAttestation, SignedBlock, SigningRoot};
+use crate::{
+    signing_root_from_row, NotSafe, Safe, SignedAttestation, SignedBlock, SigningRoot,
+    SlashingProtection,
+};
 use filesystem::restrict_file_permissions;
 use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::{params, OptionalExtension, Transaction, TransactionBehavior};
 use std::fs::File;
 use std::path::Path;
 use std::time::Duration;
-use types::{AttestationData, BeaconBlockHeader, Epoch, Hash256, PublicKeyBytes, SignedRoot, Slot};
+use types::{Epoch, Hash256, PublicKeyBytes, Slot};
 
 type Pool = r2d2::Pool<SqliteConnectionManager>;
 
@@ -187,22 +190,6 @@ impl SlashingDatabase {
         Ok(value)
     }
 
-    /// Register a validator with the slashing protection database.
-    ///
-    /// This allows the validator to record their signatures in the database, and check
-    /// for slashings.
-    pub fn register_validator(&self, validator_pk: PublicKeyBytes) -> Result<(), NotSafe> {
-        self.register_validators(std::iter::once(&validator_pk))
-    }
-
-    /// Register multiple validators with the slashing protection database.
-    pub fn register_validators<'a>(
-        &self,
-        public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
-    ) -> Result<(), NotSafe> {
-        self.with_transaction(|txn| self.register_validators_in_txn(public_keys, txn))
-    }
-
     /// Register multiple validators inside the given transaction.
     ///
     /// The caller must commit the transaction for the changes to be persisted.
@@ -242,17 +229,6 @@ impl SlashingDatabase {
         Ok(())
     }
 
-    /// Check that all of the given validators are registered.
-    pub fn check_validator_registrations<'a>(
-        &self,
-        mut public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
-    ) -> Result<(), NotSafe> {
-        let mut conn = self.conn_pool.get()?;
-        let txn = conn.transaction()?;
-        public_keys
-            .try_for_each(|public_key| self.get_validator_id_in_txn(&txn, public_key).map(|_| ()))
-    }
-
     /// List the internal validator ID and public key of every registered validator.
     pub fn list_all_registered_validators(
         &self,
@@ -545,44 +521,6 @@ impl SlashingDatabase {
         Ok(())
     }
 
-    /// Check a block proposal for slash safety, and if it is safe, record it in the database.
-    ///
-    /// The checking and inserting happen atomically and exclusively. We enforce exclusivity
-    /// to prevent concurrent checks and inserts from resulting in slashable data being inserted.
-    ///
-    /// This is the safe, externally-callable interface for checking block proposals.
-    pub fn check_and_insert_block_proposal(
-        &self,
-        validator_pubkey: &PublicKeyBytes,
-        block_header: &BeaconBlockHeader,
-        domain: Hash256,
-    ) -> Result<Safe, NotSafe> {
-        self.check_and_insert_block_signing_root(
-            validator_pubkey,
-            block_header.slot,
-            block_header.signing_root(domain).into(),
-        )
-    }
-
-    /// As for `check_and_insert_block_proposal` but without requiring the whole `BeaconBlockHeader`.
-    pub fn check_and_insert_block_signing_root(
-        &self,
-        validator_pubkey: &PublicKeyBytes,
-        slot: Slot,
-        signing_root: SigningRoot,
-    ) -> Result<Safe, NotSafe> {
-        let mut conn = self.conn_pool.get()?;
-        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
-        let safe = self.check_and_insert_block_signing_root_txn(
-            validator_pubkey,
-            slot,
-            signing_root,
-            &txn,
-        )?;
-        txn.commit()?;
-        Ok(safe)
-    }
-
     /// Transactional variant of `check_and_insert_block_signing_root`.
     pub fn check_and_insert_block_signing_root_txn(
         &self,
@@ -599,48 +537,6 @@ impl SlashingDatabase {
         Ok(safe)
     }
 
-    /// Check an attestation for slash safety, and if it is safe, record it in the database.
-    ///
-    /// The checking and inserting happen atomically and exclusively. We enforce exclusivity
-    /// to prevent concurrent checks and inserts from resulting in slashable data being inserted.
-    ///
-    /// This is the safe, externally-callable interface for checking attestations.
-    pub fn check_and_insert_attestation(
-        &self,
-        validator_pubkey: &PublicKeyBytes,
-        attestation: &AttestationData,
-        domain: Hash256,
-    ) -> Result<Safe, NotSafe> {
-        let attestation_signing_root = attestation.signing_root(domain).into();
-        self.check_and_insert_attestation_signing_root(
-            validator_pubkey,
-            attestation.source.epoch,
-            attestation.target.epoch,
-            attestation_signing_root,
-        )
-    }
-
-    /// As for `check_and_insert_attestation` but without requiring the whole `AttestationData`.
-    pub fn check_and_insert_attestation_signing_root(
-        &self,
-        validator_pubkey: &PublicKeyBytes,
-        att_source_epoch: Epoch,
-        att_target_epoch: Epoch,
-        att_signing_root: SigningRoot,
-    ) -> Result<Safe, NotSafe> {
-        let mut conn = self.conn_pool.get()?;
-        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
-        let safe = self.check_and_insert_attestation_signing_root_txn(
-            validator_pubkey,
-            att_source_epoch,
-            att_target_epoch,
-            att_signing_root,
-            &txn,
-        )?;
-        txn.commit()?;
-        Ok(safe)
-    }
-
     /// Transactional variant of `check_and_insert_attestation_signing_root`.
     fn check_and_insert_attestation_signing_root_txn(
         &self,
@@ -670,56 +566,6 @@ impl SlashingDatabase {
         Ok(safe)
     }
 
-    /// Import slashing protection from another client in the interchange format.
-    ///
-    /// This function will atomically import the entire interchange, failing if *any*
-    /// record cannot be imported.
-    pub fn import_interchange_info(
-        &self,
-        interchange: Interchange,
-        genesis_validators_root: Hash256,
-    ) -> Result<Vec<InterchangeImportOutcome>, InterchangeError> {
-        let version = interchange.metadata.interchange_format_version;
-        if version != SUPPORTED_INTERCHANGE_FORMAT_VERSION {
-            return Err(InterchangeError::UnsupportedVersion(version));
-        }
-
-        if genesis_validators_root != interchange.metadata.genesis_validators_root {
-            return Err(InterchangeError::GenesisValidatorsMismatch {
-                client: genesis_validators_root,
-                interchange_file: interchange.metadata.genesis_validators_root,
-            });
-        }
-
-        // Create a single transaction for the entire batch, which will only be committed if
-        // all records are imported successfully.
-        let mut conn = self.conn_pool.get()?;
-        let txn = conn.transaction()?;
-
-        let mut import_outcomes = vec![];
-        let mut commit = true;
-
-        for record in interchange.data {
-            let pubkey = record.pubkey;
-            match self.import_interchange_record(record, &txn) {
-                Ok(summary) => {
-                    import_outcomes.push(InterchangeImportOutcome::Success { pubkey, summary });
-                }
-                Err(error) => {
-                    import_outcomes.push(InterchangeImportOutcome::Failure { pubkey, error });
-                    commit = false;
-                }
-            }
-        }
-
-        if commit {
-            txn.commit()?;
-            Ok(import_outcomes)
-        } else {
-            Err(InterchangeError::AtomicBatchAborted(import_outcomes))
-        }
-    }
-
     pub fn import_interchange_record(
         &self,
         record: InterchangeData,
@@ -792,23 +638,6 @@ impl SlashingDatabase {
         }
     }
 
-    pub fn export_all_interchange_info(
-        &self,
-        genesis_validators_root: Hash256,
-    ) -> Result<Interchange, InterchangeError> {
-        self.export_interchange_info(genesis_validators_root, None)
-    }
-
-    pub fn export_interchange_info(
-        &self,
-        genesis_validators_root: Hash256,
-        selected_pubkeys: Option<&[PublicKeyBytes]>,
-    ) -> Result<Interchange, InterchangeError> {
-        let mut conn = self.conn_pool.get()?;
-        let txn = &conn.transaction()?;
-        self.export_interchange_info_in_txn(genesis_validators_root, selected_pubkeys, txn)
-    }
-
     pub fn export_interchange_info_in_txn(
         &self,
         genesis_validators_root: Hash256,
@@ -918,19 +747,6 @@ impl SlashingDatabase {
         Ok(())
     }
 
-    /// Prune the signed blocks table for the given public keys.
-    pub fn prune_all_signed_blocks<'a>(
-        &self,
-        mut public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
-        new_min_slot: Slot,
-    ) -> Result<(), NotSafe> {
-        let mut conn = self.conn_pool.get()?;
-        let txn = conn.transaction()?;
-        public_keys.try_for_each(|pubkey| self.prune_signed_blocks(pubkey, new_min_slot, &txn))?;
-        txn.commit()?;
-        Ok(())
-    }
-
     /// Remove all attestations for `public_key` with `target < new_min_target`.
     ///
     /// If the `new_min_target` was plucked out of thin air and doesn't necessarily correspond to
@@ -998,29 +814,6 @@ impl SlashingDatabase {
         Ok(())
     }
 
-    /// Prune the signed attestations table for the given validator keys.
-    pub fn prune_all_signed_attestations<'a>(
-        &self,
-        mut public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
-        new_min_target: Epoch,
-    ) -> Result<(), NotSafe> {
-        let mut conn = self.conn_pool.get()?;
-        let txn = conn.transaction()?;
-        public_keys
-            .try_for_each(|pubkey| self.prune_signed_attestations(pubkey, new_min_target, &txn))?;
-        txn.commit()?;
-        Ok(())
-    }
-
-    pub fn num_validator_rows(&self) -> Result<u32, NotSafe> {
-        let mut conn = self.conn_pool.get()?;
-        let txn = conn.transaction()?;
-        let count = txn
-            .prepare("SELECT COALESCE(COUNT(*), 0) FROM validators")?
-            .query_row(params![], |row| row.get(0))?;
-        Ok(count)
-    }
-
     /// Get a summary of a validator's slashing protection data including minimums and maximums.
     pub fn validator_summary(
         &self,
@@ -1062,6 +855,234 @@ impl SlashingDatabase {
     }
 }
 
+impl SlashingProtection for SlashingDatabase {
+    fn register_validators<'a>(
+        &self,
+        public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
+    ) -> Result<(), NotSafe> {
+        self.with_transaction(|txn| self.register_validators_in_txn(public_keys, txn))
+    }
+
+    fn check_validator_registrations<'a>(
+        &self,
+        mut public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
+    ) -> Result<(), NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+        public_keys
+            .try_for_each(|public_key| self.get_validator_id_in_txn(&txn, public_key).map(|_| ()))
+    }
+
+    fn check_and_insert_block_signing_root(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        slot: Slot,
+        signing_root: SigningRoot,
+    ) -> Result<Safe, NotSafe> {
+        // The checking and inserting happen atomically and exclusively. We enforce exclusivity
+        // to prevent concurrent checks and inserts from resulting in slashable data being inserted.
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
+        let safe = self.check_and_insert_block_signing_root_txn(
+            validator_pubkey,
+            slot,
+            signing_root,
+            &txn,
+        )?;
+        txn.commit()?;
+        Ok(safe)
+    }
+
+    fn check_and_insert_attestation_signing_root(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        att_source_epoch: Epoch,
+        att_target_epoch: Epoch,
+        att_signing_root: SigningRoot,
+    ) -> Result<Safe, NotSafe> {
+        // See `check_and_insert_block_signing_root` regarding exclusivity.
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
+        let safe = self.check_and_insert_attestation_signing_root_txn(
+            validator_pubkey,
+            att_source_epoch,
+            att_target_epoch,
+            att_signing_root,
+            &txn,
+        )?;
+        txn.commit()?;
+        Ok(safe)
+    }
+
+    fn import_interchange_info(
+        &self,
+        interchange: Interchange,
+        genesis_validators_root: Hash256,
+    ) -> Result<Vec<InterchangeImportOutcome>, InterchangeError> {
+        check_interchange_metadata(&interchange, genesis_validators_root)?;
+
+        // Create a single transaction for the entire batch, which will only be committed if
+        // all records are imported successfully.
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+
+        let mut import_outcomes = vec![];
+        let mut commit = true;
+
+        for record in interchange.data {
+            let pubkey = record.pubkey;
+            match self.import_interchange_record(record, &txn) {
+                Ok(summary) => {
+                    import_outcomes.push(InterchangeImportOutcome::Success { pubkey, summary });
+                }
+                Err(error) => {
+                    import_outcomes.push(InterchangeImportOutcome::Failure { pubkey, error });
+                    commit = false;
+                }
+            }
+        }
+
+        if commit {
+            txn.commit()?;
+            Ok(import_outcomes)
+        } else {
+            Err(InterchangeError::AtomicBatchAborted(import_outcomes))
+        }
+    }
+
+    fn export_interchange_info(
+        &self,
+        genesis_validators_root: Hash256,
+        selected_pubkeys: Option<&[PublicKeyBytes]>,
+    ) -> Result<Interchange, InterchangeError> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = &conn.transaction()?;
+        self.export_interchange_info_in_txn(genesis_validators_root, selected_pubkeys, txn)
+    }
+
+    fn export_and_disable_interchange_info(
+        &self,
+        genesis_validators_root: Hash256,
+        pubkeys: &[PublicKeyBytes],
+    ) -> Result<Interchange, InterchangeError> {
+        self.with_transaction(|txn| {
+            let known_pubkeys = pubkeys
+                .iter()
+                .filter_map(|pubkey| {
+                    let validator_id = self.get_validator_id_ignoring_status(txn, pubkey).ok()?;
+
+                    Some(
+                        self.update_validator_status(txn, validator_id, false)
+                            .map(|()| *pubkey),
+                    )
+                })
+                .collect::<Result<Vec<PublicKeyBytes>, _>>()?;
+            self.export_interchange_info_in_txn(genesis_validators_root, Some(&known_pubkeys), txn)
+        })
+    }
+
+    fn prune_all_signed_blocks<'a>(
+        &self,
+        mut public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
+        new_min_slot: Slot,
+    ) -> Result<(), NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+        public_keys.try_for_each(|pubkey| self.prune_signed_blocks(pubkey, new_min_slot, &txn))?;
+        txn.commit()?;
+        Ok(())
+    }
+
+    fn prune_all_signed_attestations<'a>(
+        &self,
+        mut public_keys: impl Iterator<Item = &'a PublicKeyBytes>,
+        new_min_target: Epoch,
+    ) -> Result<(), NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+        public_keys
+            .try_for_each(|pubkey| self.prune_signed_attestations(pubkey, new_min_target, &txn))?;
+        txn.commit()?;
+        Ok(())
+    }
+
+    fn has_signed_block(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        slot: Slot,
+        signing_root: SigningRoot,
+    ) -> Result<bool, NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+        let validator_id = self.get_validator_id_in_txn(&txn, validator_pubkey)?;
+        let existing_block = txn
+            .prepare(
+                "SELECT slot, signing_root
+                 FROM signed_blocks
+                 WHERE validator_id = ?1 AND slot = ?2",
+            )?
+            .query_row(params![validator_id, slot], SignedBlock::from_row)
+            .optional()?;
+        Ok(existing_block.is_some_and(|block| {
+            block.signing_root.is_null() || block.signing_root == signing_root
+        }))
+    }
+
+    fn has_signed_attestation(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        target_epoch: Epoch,
+        signing_root: SigningRoot,
+    ) -> Result<bool, NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+        let validator_id = self.get_validator_id_in_txn(&txn, validator_pubkey)?;
+        let existing_attestation = txn
+            .prepare(
+                "SELECT source_epoch, target_epoch, signing_root
+                 FROM signed_attestations
+                 WHERE validator_id = ?1 AND target_epoch = ?2",
+            )?
+            .query_row(
+                params![validator_id, target_epoch],
+                SignedAttestation::from_row,
+            )
+            .optional()?;
+        Ok(existing_attestation.is_some_and(|attestation| {
+            attestation.signing_root.is_null() || attestation.signing_root == signing_root
+        }))
+    }
+
+    fn num_validator_rows(&self) -> Result<u32, NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+        let count = txn
+            .prepare("SELECT COALESCE(COUNT(*), 0) FROM validators")?
+            .query_row(params![], |row| row.get(0))?;
+        Ok(count)
+    }
+}
+
+/// Check that an interchange file is compatible with this client before importing it.
+pub(crate) fn check_interchange_metadata(
+    interchange: &Interchange,
+    genesis_validators_root: Hash256,
+) -> Result<(), InterchangeError> {
+    let version = interchange.metadata.interchange_format_version;
+    if version != SUPPORTED_INTERCHANGE_FORMAT_VERSION {
+        return Err(InterchangeError::UnsupportedVersion(version));
+    }
+
+    if genesis_validators_root != interchange.metadata.genesis_validators_root {
+        return Err(InterchangeError::GenesisValidatorsMismatch {
+            client: genesis_validators_root,
+            interchange_file: interchange.metadata.genesis_validators_root,
+        });
+    }
+
+    Ok(())
+}
+
 /// Minimum and maximum slots and epochs signed by a validator.
 #[derive(Debug)]
 pub struct ValidatorSummary {
@@ -1074,7 +1095,7 @@ pub struct ValidatorSummary {
 }
 
 impl ValidatorSummary {
-    fn check_block_consistency(&self, prev: &Self, imported_blocks: bool) -> bool {
+    pub(crate) fn check_block_consistency(&self, prev: &Self, imported_blocks: bool) -> bool {
         if imported_blocks {
             // Max block slot should be monotonically increasing and non-null.
             // Minimum should match maximum due to pruning.
@@ -1086,7 +1107,11 @@ impl ValidatorSummary {
         }
     }
 
-    fn check_attestation_consistency(&self, prev: &Self, imported_attestations: bool) -> bool {
+    pub(crate) fn check_attestation_consistency(
+        &self,
+        prev: &Self,
+        imported_attestations: bool,
+    ) -> bool {
         if imported_attestations {
             // Max source and target epochs should be monotically increasing and non-null.
             // Minimums should match maximums due to pruning.
@@ -1105,7 +1130,7 @@ impl ValidatorSummary {
 }
 
 /// Take the maximum of `opt_x` and `y`, returning `y` if `opt_x` is `None`.
-fn max_or<T: Copy + Ord>(opt_x: Option<T>, y: T) -> T {
+pub(crate) fn max_or<T: Copy + Ord>(opt_x: Option<T>, y: T) -> T {
     opt_x.map_or(y, |x| std::cmp::max(x, y))
 }
 
@@ -1166,6 +1191,13 @@ impl From<rusqlite::Error> for InterchangeError {
     }
 }
 
+#[cfg(feature = "postgres")]
+impl From<postgres::Error> for InterchangeError {
+    fn from(error: postgres::Error) -> Self {
+        Self::SQLError(error.to_string())
+    }
+}
+
 impl From<r2d2::Error> for InterchangeError {
     fn from(error: r2d2::Error) -> Self {
         InterchangeError::SQLPoolError(error)
@@ -7,6 +7,12 @@ lazy_static! {
     pub static ref TEST_ROOT_DIR: PathBuf = test_root_dir();
 }
 
+/// Environment variable holding the URL of a PostgreSQL database to run the tests against.
+///
+/// The slashing protection tables in this database are dropped before each test case.
+#[cfg(feature = "postgres")]
+pub const POSTGRES_URL_ENV: &str = "SLASHING_PROTECTION_TEST_POSTGRES_URL";
+
 fn download_tests() {
     let make_output = std::process::Command::new("make")
         .current_dir(std::env::var("CARGO_MANIFEST_DIR").unwrap())
@@ -25,29 +31,51 @@ fn test_root_dir() -> PathBuf {
         .join("tests")
 }
 
-// NOTE: I've combined two tests together to avoid a race-condition which occurs when fighting over
-// which test builds the TEST_ROOT_DIR lazy static.
-#[test]
-fn generated_and_with_minification() {
-    for entry in TEST_ROOT_DIR
+fn generated_test_cases() -> impl Iterator<Item = MultiTestCase> {
+    TEST_ROOT_DIR
         .join("generated")
         .read_dir()
         .unwrap()
         .map(Result::unwrap)
-    {
-        let file = File::open(entry.path()).unwrap();
-        let test_case: MultiTestCase = serde_json::from_reader(&file).unwrap();
+        .map(|entry| {
+            let file = File::open(entry.path()).unwrap();
+            serde_json::from_reader(&file).unwrap()
+        })
+}
+
+/// Connect to the database at `$POSTGRES_URL_ENV`, emptying it of any slashing protection data.
+#[cfg(feature = "postgres")]
+fn empty_postgres_database(url: &str) -> slashing_protection::PostgresSlashingDatabase {
+    let mut client = postgres::Client::connect(url, postgres::NoTls).unwrap();
+    client
+        .batch_execute("DROP TABLE IF EXISTS signed_attestations, signed_blocks, validators")
+        .unwrap();
+    let url = sensitive_url::SensitiveUrl::parse(url).unwrap();
+    slashing_protection::PostgresSlashingDatabase::open_or_create(&url).unwrap()
+}
+
+// NOTE: I've combined two tests together to avoid a race-condition which occurs when fighting over
+// which test builds the TEST_ROOT_DIR lazy static.
+#[test]
+fn generated_and_with_minification() {
+    for test_case in generated_test_cases() {
         test_case.run(false);
     }
 
-    for entry in TEST_ROOT_DIR
-        .join("generated")
-        .read_dir()
-        .unwrap()
-        .map(Result::unwrap)
-    {
-        let file = File::open(entry.path()).unwrap();
-        let test_case: MultiTestCase = serde_json::from_reader(&file).unwrap();
+    for test_case in generated_test_cases() {
         test_case.run(true);
     }
+
+    #[cfg(feature = "postgres")]
+    match std::env::var(POSTGRES_URL_ENV) {
+        Ok(url) => {
+            for minify in [false, true] {
+                for test_case in generated_test_cases() {
+                    let slashing_db = empty_postgres_database(&url);
+                    test_case.run_with_db(&slashing_db, minify);
+                }
+            }
+        }
+        Err(_) => eprintln!("{} not set, skipping PostgreSQL tests", POSTGRES_URL_ENV),
+    }
 }
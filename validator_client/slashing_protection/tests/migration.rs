@@ -1,5 +1,5 @@
 //! Tests for upgrading a previous version of the database to the latest schema.
-use slashing_protection::{NotSafe, SlashingDatabase};
+use slashing_protection::{NotSafe, SlashingDatabase, SlashingProtection};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
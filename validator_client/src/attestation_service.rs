@@ -1,11 +1,13 @@
 use crate::beacon_node_fallback::{ApiTopic, BeaconNodeFallback, RequireSynced};
 use crate::{
     duties_service::{DutiesService, DutyAndProof},
+    duty_history::DutyHistory,
     http_metrics::metrics,
     validator_store::{Error as ValidatorStoreError, ValidatorStore},
     OfflineOnFailure,
 };
 use environment::RuntimeContext;
+use eth2::lighthouse_vc::types::DutyKind;
 use futures::future::join_all;
 use slog::{crit, debug, error, info, trace, warn};
 use slot_clock::SlotClock;
@@ -22,6 +24,7 @@ pub struct AttestationServiceBuilder<T: SlotClock + 'static, E: EthSpec> {
     validator_store: Option<Arc<ValidatorStore<T, E>>>,
     slot_clock: Option<T>,
     beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
+    duty_history: Option<Arc<DutyHistory>>,
     context: Option<RuntimeContext<E>>,
 }
 
@@ -32,6 +35,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
             validator_store: None,
             slot_clock: None,
             beacon_nodes: None,
+            duty_history: None,
             context: None,
         }
     }
@@ -56,6 +60,11 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
         self
     }
 
+    pub fn duty_history(mut self, duty_history: Arc<DutyHistory>) -> Self {
+        self.duty_history = Some(duty_history);
+        self
+    }
+
     pub fn runtime_context(mut self, context: RuntimeContext<E>) -> Self {
         self.context = Some(context);
         self
@@ -76,6 +85,9 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
                 beacon_nodes: self
                     .beacon_nodes
                     .ok_or("Cannot build AttestationService without beacon_nodes")?,
+                duty_history: self
+                    .duty_history
+                    .ok_or("Cannot build AttestationService without duty_history")?,
                 context: self
                     .context
                     .ok_or("Cannot build AttestationService without runtime_context")?,
@@ -90,6 +102,7 @@ pub struct Inner<T, E: EthSpec> {
     validator_store: Arc<ValidatorStore<T, E>>,
     slot_clock: T,
     beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    duty_history: Arc<DutyHistory>,
     context: RuntimeContext<E>,
 }
 
@@ -355,7 +368,18 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                 },
             )
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| {
+                let error = e.to_string();
+                for duty_and_proof in validator_duties {
+                    self.duty_history.record_unsigned(
+                        duty_and_proof.duty.pubkey,
+                        DutyKind::Attestation,
+                        slot,
+                        error.clone(),
+                    );
+                }
+                error
+            })?;
 
         // Create futures to produce signed `Attestation` objects.
         let attestation_data_ref = &attestation_data;
@@ -374,6 +398,12 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                     "duty_index" => duty.committee_index,
                     "attestation_index" => attestation_data.index,
                 );
+                self.duty_history.record_unsigned(
+                    duty.pubkey,
+                    DutyKind::Attestation,
+                    slot,
+                    "Inconsistent validator duties".to_string(),
+                );
                 return None;
             }
 
@@ -395,6 +425,12 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                         "duty" => ?duty,
                         "err" => ?err,
                     );
+                    self.duty_history.record_unsigned(
+                        duty.pubkey,
+                        DutyKind::Attestation,
+                        slot,
+                        format!("Invalid validator duties: {:?}", err),
+                    );
                     return None;
                 }
             };
@@ -409,7 +445,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                 )
                 .await
             {
-                Ok(()) => Some((attestation, duty.validator_index)),
+                Ok(()) => Some((attestation, duty)),
                 Err(ValidatorStoreError::UnknownPubkey(pubkey)) => {
                     // A pubkey can be missing when a validator was recently
                     // removed via the API.
@@ -433,17 +469,27 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                         "committee_index" => committee_index,
                         "slot" => slot.as_u64(),
                     );
+                    self.duty_history.record_unsigned(
+                        duty.pubkey,
+                        DutyKind::Attestation,
+                        slot,
+                        format!("Failed to sign attestation: {:?}", e),
+                    );
                     None
                 }
             }
         });
 
         // Execute all the futures in parallel, collecting any successful results.
-        let (ref attestations, ref validator_indices): (Vec<_>, Vec<_>) = join_all(signing_futures)
+        let (ref attestations, signed_duties): (Vec<_>, Vec<_>) = join_all(signing_futures)
             .await
             .into_iter()
             .flatten()
             .unzip();
+        let validator_indices = signed_duties
+            .iter()
+            .map(|duty| duty.validator_index)
+            .collect::<Vec<_>>();
 
         if attestations.is_empty() {
             warn!(log, "No attestations were published");
@@ -456,7 +502,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             .fork_name_at_slot::<E>(attestation_data.slot);
 
         // Post the attestations to the BN.
-        match self
+        let publish_result = match self
             .beacon_nodes
             .request(
                 RequireSynced::No,
@@ -480,24 +526,39 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             )
             .await
         {
-            Ok(()) => info!(
-                log,
-                "Successfully published attestations";
-                "count" => attestations.len(),
-                "validator_indices" => ?validator_indices,
-                "head_block" => ?attestation_data.beacon_block_root,
-                "committee_index" => attestation_data.index,
-                "slot" => attestation_data.slot.as_u64(),
-                "type" => "unaggregated",
-            ),
-            Err(e) => error!(
-                log,
-                "Unable to publish attestations";
-                "error" => %e,
-                "committee_index" => attestation_data.index,
-                "slot" => slot.as_u64(),
-                "type" => "unaggregated",
-            ),
+            Ok(()) => {
+                info!(
+                    log,
+                    "Successfully published attestations";
+                    "count" => attestations.len(),
+                    "validator_indices" => ?validator_indices,
+                    "head_block" => ?attestation_data.beacon_block_root,
+                    "committee_index" => attestation_data.index,
+                    "slot" => attestation_data.slot.as_u64(),
+                    "type" => "unaggregated",
+                );
+                Ok(())
+            }
+            Err(e) => {
+                error!(
+                    log,
+                    "Unable to publish attestations";
+                    "error" => %e,
+                    "committee_index" => attestation_data.index,
+                    "slot" => slot.as_u64(),
+                    "type" => "unaggregated",
+                );
+                Err(e.to_string())
+            }
+        };
+
+        for duty in signed_duties {
+            self.duty_history.record_signed(
+                duty.pubkey,
+                DutyKind::Attestation,
+                slot,
+                publish_result.clone(),
+            );
         }
 
         Ok(Some(attestation_data))
@@ -581,7 +642,20 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                 },
             )
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| {
+                let error = e.to_string();
+                for duty_and_proof in validator_duties {
+                    if duty_and_proof.selection_proof.is_some() {
+                        self.duty_history.record_unsigned(
+                            duty_and_proof.duty.pubkey,
+                            DutyKind::Aggregate,
+                            attestation_data.slot,
+                            error.clone(),
+                        );
+                    }
+                }
+                error
+            })?;
 
         // Create futures to produce the signed aggregated attestations.
         let signing_futures = validator_duties.iter().map(|duty_and_proof| async move {
@@ -590,6 +664,12 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
 
             if !duty.match_attestation_data::<E>(attestation_data, &self.context.eth2_config.spec) {
                 crit!(log, "Inconsistent validator duties during signing");
+                self.duty_history.record_unsigned(
+                    duty.pubkey,
+                    DutyKind::Aggregate,
+                    attestation_data.slot,
+                    "Inconsistent validator duties".to_string(),
+                );
                 return None;
             }
 
@@ -603,7 +683,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                 )
                 .await
             {
-                Ok(aggregate) => Some(aggregate),
+                Ok(aggregate) => Some((aggregate, duty.pubkey)),
                 Err(ValidatorStoreError::UnknownPubkey(pubkey)) => {
                     // A pubkey can be missing when a validator was recently
                     // removed via the API.
@@ -621,21 +701,28 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                         "error" => ?e,
                         "pubkey" => ?duty.pubkey,
                     );
+                    self.duty_history.record_unsigned(
+                        duty.pubkey,
+                        DutyKind::Aggregate,
+                        attestation_data.slot,
+                        format!("Failed to sign aggregate: {:?}", e),
+                    );
                     None
                 }
             }
         });
 
         // Execute all the futures in parallel, collecting any successful results.
-        let signed_aggregate_and_proofs = join_all(signing_futures)
-            .await
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>();
+        let (signed_aggregate_and_proofs, aggregators): (Vec<_>, Vec<_>) =
+            join_all(signing_futures)
+                .await
+                .into_iter()
+                .flatten()
+                .unzip();
 
         if !signed_aggregate_and_proofs.is_empty() {
             let signed_aggregate_and_proofs_slice = signed_aggregate_and_proofs.as_slice();
-            match self
+            let publish_result = match self
                 .beacon_nodes
                 .first_success(
                     RequireSynced::No,
@@ -677,6 +764,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                             "type" => "aggregated",
                         );
                     }
+                    Ok(())
                 }
                 Err(e) => {
                     for signed_aggregate_and_proof in signed_aggregate_and_proofs {
@@ -691,7 +779,17 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                             "type" => "aggregated",
                         );
                     }
+                    Err(e.to_string())
                 }
+            };
+
+            for aggregator in aggregators {
+                self.duty_history.record_signed(
+                    aggregator,
+                    DutyKind::Aggregate,
+                    attestation_data.slot,
+                    publish_result.clone(),
+                );
             }
         }
 
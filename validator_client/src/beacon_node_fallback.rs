@@ -1,10 +1,15 @@
 //! Allows for a list of `BeaconNodeHttpClient` to appear as a single entity which will exhibits
 //! "fallback" behaviour; it will try a request on all of the nodes until one or none of them
 //! succeed.
+//!
+//! Nodes are tried in order of their health, see `beacon_node_health`.
 
-use crate::check_synced::check_synced;
+use crate::beacon_node_health::HealthMeasurements;
+use crate::check_synced::{check_synced, get_sync_status};
+use crate::events::ServerSentEventHandler;
 use crate::http_metrics::metrics::{inc_counter_vec, ENDPOINT_ERRORS, ENDPOINT_REQUESTS};
 use environment::RuntimeContext;
+use eth2::lighthouse_vc::types::{BeaconNodeHealthData, EventKind, SseBeaconNodeFallback};
 use eth2::BeaconNodeHttpClient;
 use futures::future;
 use serde::{Deserialize, Serialize};
@@ -118,6 +123,7 @@ impl<T> Error<T> {
 }
 
 /// The list of errors encountered whilst attempting to perform a query.
+#[derive(Debug)]
 pub struct Errors<T>(pub Vec<(String, Error<T>)>);
 
 impl<T: Debug> fmt::Display for Errors<T> {
@@ -154,6 +160,7 @@ pub enum CandidateError {
 pub struct CandidateBeaconNode<E> {
     beacon_node: BeaconNodeHttpClient,
     status: RwLock<Result<(), CandidateError>>,
+    health: parking_lot::RwLock<HealthMeasurements>,
     _phantom: PhantomData<E>,
 }
 
@@ -163,10 +170,23 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         Self {
             beacon_node,
             status: RwLock::new(Err(CandidateError::Uninitialized)),
+            health: parking_lot::RwLock::new(HealthMeasurements::default()),
             _phantom: PhantomData,
         }
     }
 
+    /// Returns the health of `self`, which is at position `index` in the configured order.
+    pub fn health(&self, index: usize) -> BeaconNodeHealthData {
+        self.health
+            .read()
+            .health(index, self.beacon_node.to_string())
+    }
+
+    /// Record the outcome of a request to `self`, for its error rate.
+    fn record_request(&self, success: bool) {
+        self.health.write().record_request(success);
+    }
+
     /// Returns the status of `self`.
     ///
     /// If `RequiredSynced::No`, any `NotSynced` node will be ignored and mapped to `Ok(())`.
@@ -179,7 +199,8 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
 
     /// Indicate that `self` is offline.
     pub async fn set_offline(&self) {
-        *self.status.write().await = Err(CandidateError::Offline)
+        *self.status.write().await = Err(CandidateError::Offline);
+        self.health.write().set_available(false);
     }
 
     /// Perform some queries against the node to determine if it is a good candidate, updating
@@ -203,6 +224,15 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
             Ok(())
         };
 
+        {
+            // The status check counts as a request, so that the error rate of a node which is not
+            // being sent requests still recovers.
+            let available = matches!(new_status, Ok(()) | Err(CandidateError::NotSynced));
+            let mut health = self.health.write();
+            health.set_available(available);
+            health.record_request(available);
+        }
+
         // In case of concurrent use, the latest value will always be used. It's possible that a
         // long time out might over-ride a recent successful response, leading to a falsely-offline
         // status. I deem this edge-case acceptable in return for the concurrency benefits of not
@@ -214,6 +244,7 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
 
     /// Checks if the node is reachable.
     async fn is_online(&self, was_offline: bool, log: &Logger) -> Result<(), CandidateError> {
+        let request_instant = Instant::now();
         let result = self
             .beacon_node
             .get_node_version()
@@ -222,6 +253,10 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
 
         match result {
             Ok(version) => {
+                self.health
+                    .write()
+                    .record_latency(request_instant.elapsed());
+
                 if was_offline {
                     info!(
                         log,
@@ -332,7 +367,10 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         log: &Logger,
     ) -> Result<(), CandidateError> {
         if let Some(slot_clock) = slot_clock {
-            check_synced(&self.beacon_node, slot_clock, Some(log)).await
+            let sync_status = get_sync_status(&self.beacon_node, slot_clock, Some(log)).await;
+            let result = sync_status.as_ref().map_err(|e| *e).and_then(check_synced);
+            self.health.write().sync_status = sync_status.ok();
+            result
         } else {
             // Skip this check if we don't supply a slot clock.
             Ok(())
@@ -348,6 +386,7 @@ pub struct BeaconNodeFallback<T, E> {
     slot_clock: Option<T>,
     broadcast_topics: Vec<ApiTopic>,
     spec: ChainSpec,
+    event_handler: Option<Arc<ServerSentEventHandler>>,
     log: Logger,
 }
 
@@ -363,6 +402,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
             slot_clock: None,
             broadcast_topics,
             spec,
+            event_handler: None,
             log,
         }
     }
@@ -376,6 +416,11 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         self.slot_clock = Some(slot_clock);
     }
 
+    /// Used to publish an event whenever a request is served after other candidates failed it.
+    pub fn set_event_handler(&mut self, event_handler: Arc<ServerSentEventHandler>) {
+        self.event_handler = Some(event_handler);
+    }
+
     /// The count of candidates, regardless of their state.
     pub fn num_total(&self) -> usize {
         self.candidates.len()
@@ -414,6 +459,36 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         n
     }
 
+    /// The candidates, ordered from the healthiest to the least healthy.
+    fn ordered_candidates(&self) -> Vec<&CandidateBeaconNode<E>> {
+        let mut candidates = self
+            .candidates
+            .iter()
+            .enumerate()
+            .map(|(index, candidate)| (candidate.health.read().sort_key(index), candidate))
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|(sort_key, _)| *sort_key);
+        candidates
+            .into_iter()
+            .map(|(_, candidate)| candidate)
+            .collect()
+    }
+
+    /// The health of all candidates, ordered from the healthiest to the least healthy.
+    pub fn health(&self) -> Vec<BeaconNodeHealthData> {
+        let mut health = self
+            .candidates
+            .iter()
+            .enumerate()
+            .map(|(index, candidate)| {
+                let sort_key = candidate.health.read().sort_key(index);
+                (sort_key, candidate.health(index))
+            })
+            .collect::<Vec<_>>();
+        health.sort_by_key(|(sort_key, _)| *sort_key);
+        health.into_iter().map(|(_, health)| health).collect()
+    }
+
     /// Loop through ALL candidates in `self.candidates` and update their sync status.
     ///
     /// It is possible for a node to return an unsynced status while continuing to serve
@@ -439,7 +514,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         let futures: Vec<_> = self
             .candidates
             .iter()
-            .map(|candidate| async {
+            .map(|candidate| async move {
                 let beacon_node_id = candidate.beacon_node.to_string();
                 // The `node/version` endpoint is used since I imagine it would
                 // require the least processing in the BN and therefore measure
@@ -453,7 +528,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
                     .await
                     .ok()
                     .map(|_| Instant::now());
-                (beacon_node_id, response_instant)
+                (candidate, beacon_node_id, response_instant)
             })
             .collect();
 
@@ -465,10 +540,16 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         future::join_all(futures)
             .await
             .into_iter()
-            .map(|(beacon_node_id, response_instant)| LatencyMeasurement {
-                beacon_node_id,
-                latency: response_instant
-                    .and_then(|response| response.checked_duration_since(request_instant)),
+            .map(|(candidate, beacon_node_id, response_instant)| {
+                let latency = response_instant
+                    .and_then(|response| response.checked_duration_since(request_instant));
+                if let Some(latency) = latency {
+                    candidate.health.write().record_latency(latency);
+                }
+                LatencyMeasurement {
+                    beacon_node_id,
+                    latency,
+                }
             })
             .collect()
     }
@@ -491,6 +572,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         Err: Debug,
     {
         let mut errors = vec![];
+        let mut failed_beacon_nodes = vec![];
         let mut to_retry = vec![];
         let mut retry_unsynced = vec![];
         let log = &self.log.clone();
@@ -506,8 +588,22 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
                 // There exists a race condition where `func` may be called when the candidate is
                 // actually not ready. We deem this an acceptable inefficiency.
                 match func(&$candidate.beacon_node).await {
-                    Ok(val) => return Ok(val),
+                    Ok(val) => {
+                        $candidate.record_request(true);
+                        if let Some(event_handler) = &self.event_handler {
+                            if !failed_beacon_nodes.is_empty() {
+                                event_handler.register(EventKind::BeaconNodeFallback(
+                                    SseBeaconNodeFallback {
+                                        beacon_node: $candidate.beacon_node.to_string(),
+                                        failed_beacon_nodes,
+                                    },
+                                ));
+                            }
+                        }
+                        return Ok(val);
+                    }
                     Err(e) => {
+                        $candidate.record_request(false);
                         debug!(
                             log,
                             "Request to beacon node failed";
@@ -522,6 +618,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
                         if matches!(offline_on_failure, OfflineOnFailure::Yes) {
                             $candidate.set_offline().await;
                         }
+                        failed_beacon_nodes.push($candidate.beacon_node.to_string());
                         errors.push(($candidate.beacon_node.to_string(), Error::RequestFailed(e)));
                         inc_counter_vec(&ENDPOINT_ERRORS, &[$candidate.beacon_node.as_ref()]);
                     }
@@ -529,10 +626,10 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
             }};
         }
 
-        // First pass: try `func` on all synced and ready candidates.
+        // First pass: try `func` on all synced and ready candidates, healthiest first.
         //
         // This ensures that we always choose a synced node if it is available.
-        for candidate in &self.candidates {
+        for candidate in self.ordered_candidates() {
             match candidate.status(RequireSynced::Yes).await {
                 Err(e @ CandidateError::NotSynced) if require_synced == false => {
                     // This client is unsynced we will try it after trying all synced clients
@@ -615,8 +712,12 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
             // There exists a race condition where `func` may be called when the candidate is
             // actually not ready. We deem this an acceptable inefficiency.
             match func(&candidate.beacon_node).await {
-                Ok(val) => Ok(val),
+                Ok(val) => {
+                    candidate.record_request(true);
+                    Ok(val)
+                }
                 Err(e) => {
+                    candidate.record_request(false);
                     // If we have an error on this function, mark the client as not-ready.
                     //
                     // There exists a race condition where the candidate may have been marked
@@ -635,7 +736,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         //
         // This ensures that we always choose a synced node if it is available.
         let mut first_batch_futures = vec![];
-        for candidate in &self.candidates {
+        for candidate in self.ordered_candidates() {
             match candidate.status(RequireSynced::Yes).await {
                 Ok(_) => {
                     first_batch_futures.push(run_on_candidate(candidate));
@@ -749,8 +850,205 @@ impl ApiTopic {
 #[cfg(test)]
 mod test {
     use super::*;
+    use eth2::lighthouse_vc::types::HealthTier;
+    use eth2::types::{GenericResponse, SyncingData, VersionData};
+    use eth2::Timeouts;
+    use logging::test_logger;
+    use sensitive_url::SensitiveUrl;
+    use slot_clock::TestingSlotClock;
     use std::str::FromStr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use strum::VariantNames;
+    use types::{MainnetEthSpec, Slot};
+    use warp::{http::StatusCode, path::FullPath, Filter, Reply};
+
+    type E = MainnetEthSpec;
+
+    /// The behaviour of a `MockBeaconNode`.
+    #[derive(Clone)]
+    struct MockBeaconNodeState {
+        sync_status: SyncingData,
+        delay: Duration,
+        online: bool,
+    }
+
+    impl Default for MockBeaconNodeState {
+        fn default() -> Self {
+            Self {
+                sync_status: SyncingData {
+                    is_syncing: false,
+                    is_optimistic: false,
+                    el_offline: false,
+                    head_slot: Slot::new(100),
+                    sync_distance: Slot::new(0),
+                },
+                delay: Duration::ZERO,
+                online: true,
+            }
+        }
+    }
+
+    /// Serves the endpoints used to check the status of a beacon node, returning a client for it.
+    fn mock_beacon_node(state: MockBeaconNodeState, spec: &ChainSpec) -> BeaconNodeHttpClient {
+        let config = Config::from_chain_spec::<E>(spec);
+        let routes = warp::get()
+            .and(warp::path::full())
+            .then(move |path: FullPath| {
+                let state = state.clone();
+                let config = config.clone();
+                async move {
+                    sleep(state.delay).await;
+                    if !state.online {
+                        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+                    }
+                    match path.as_str() {
+                        "/eth/v1/node/version" => {
+                            warp::reply::json(&GenericResponse::from(VersionData {
+                                version: "Lighthouse/mock".to_string(),
+                            }))
+                            .into_response()
+                        }
+                        "/eth/v1/node/syncing" => {
+                            warp::reply::json(&GenericResponse::from(state.sync_status))
+                                .into_response()
+                        }
+                        "/eth/v1/config/spec" => {
+                            warp::reply::json(&GenericResponse::from(config)).into_response()
+                        }
+                        _ => StatusCode::NOT_FOUND.into_response(),
+                    }
+                }
+            });
+        let (addr, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+
+        let url = SensitiveUrl::parse(&format!("http://{addr}")).unwrap();
+        BeaconNodeHttpClient::new(url, Timeouts::set_all(Duration::from_secs(5)))
+    }
+
+    async fn mock_fallback(
+        states: Vec<MockBeaconNodeState>,
+    ) -> BeaconNodeFallback<TestingSlotClock, E> {
+        let spec = E::default_spec();
+        let candidates = states
+            .into_iter()
+            .map(|state| CandidateBeaconNode::new(mock_beacon_node(state, &spec)))
+            .collect();
+        let mut fallback = BeaconNodeFallback::new(candidates, vec![], spec, test_logger());
+
+        let slot_clock =
+            TestingSlotClock::new(Slot::new(0), Duration::ZERO, Duration::from_secs(12));
+        slot_clock.set_slot(100);
+        fallback.set_slot_clock(slot_clock);
+
+        fallback.update_all_candidates().await;
+        fallback
+    }
+
+    fn indices(health: &[BeaconNodeHealthData]) -> Vec<usize> {
+        health.iter().map(|node| node.index).collect()
+    }
+
+    #[tokio::test]
+    async fn candidates_ordered_by_health() {
+        let synced = MockBeaconNodeState::default();
+        let fallback = mock_fallback(vec![
+            MockBeaconNodeState {
+                sync_status: SyncingData {
+                    is_syncing: true,
+                    sync_distance: Slot::new(100),
+                    ..synced.sync_status.clone()
+                },
+                ..synced.clone()
+            },
+            MockBeaconNodeState {
+                sync_status: SyncingData {
+                    el_offline: true,
+                    ..synced.sync_status.clone()
+                },
+                ..synced.clone()
+            },
+            MockBeaconNodeState {
+                delay: Duration::from_millis(200),
+                ..synced.clone()
+            },
+            synced.clone(),
+            MockBeaconNodeState {
+                online: false,
+                ..synced.clone()
+            },
+        ])
+        .await;
+
+        let health = fallback.health();
+        assert_eq!(indices(&health), vec![3, 2, 1, 0, 4]);
+        assert_eq!(
+            health.iter().map(|node| node.tier).collect::<Vec<_>>(),
+            vec![
+                HealthTier::Healthy,
+                HealthTier::Healthy,
+                HealthTier::Unsynced,
+                HealthTier::Unsynced,
+                HealthTier::Unavailable
+            ]
+        );
+        assert!(health[1].latency_ms.unwrap() >= 200);
+        assert_eq!(health[4].head_slot, None);
+
+        // Requests are sent to the healthiest node first.
+        let endpoint = fallback
+            .first_success(
+                RequireSynced::No,
+                OfflineOnFailure::Yes,
+                |client| async move { Ok::<_, ()>(client.to_string()) },
+            )
+            .await
+            .unwrap();
+        assert_eq!(endpoint, health[0].endpoint);
+    }
+
+    #[tokio::test]
+    async fn failing_candidate_deprioritised() {
+        let fallback = mock_fallback(vec![MockBeaconNodeState::default(); 2]).await;
+        assert_eq!(indices(&fallback.health()), vec![0, 1]);
+
+        let failing = &fallback.health()[0].endpoint.clone();
+        let failed_requests = &AtomicUsize::new(0);
+        let request = || {
+            fallback.first_success(RequireSynced::Yes, OfflineOnFailure::No, move |client| {
+                let fail = client.to_string() == *failing;
+                if fail {
+                    failed_requests.fetch_add(1, Ordering::Relaxed);
+                }
+                async move {
+                    if fail {
+                        Err(())
+                    } else {
+                        Ok(())
+                    }
+                }
+            })
+        };
+
+        // The first node is tried first until its error rate outweighs the configured order.
+        for _ in 0..3 {
+            request().await.unwrap();
+        }
+        assert_eq!(failed_requests.load(Ordering::Relaxed), 2);
+
+        let health = fallback.health();
+        assert_eq!(indices(&health), vec![1, 0]);
+        assert!(health[1].error_rate > 0.0);
+        assert_eq!(health[0].error_rate, 0.0);
+
+        // Successful status checks restore the first node once it recovers.
+        for _ in 0..20 {
+            fallback.update_all_candidates().await;
+        }
+        let health = fallback.health();
+        assert_eq!(indices(&health), vec![0, 1]);
+        assert!(health[0].error_rate < 0.05);
+    }
 
     #[test]
     fn api_topic_all() {
@@ -0,0 +1,327 @@
+//! Tracks the health of each candidate beacon node, so that `BeaconNodeFallback` can try the
+//! healthiest nodes first.
+//!
+//! Each node is assigned a `HealthTier` from its sync status, which always takes precedence, and
+//! a `score` out of 100 which orders nodes within a tier. The score is reduced by the node's
+//! latency, its recent request error rate and its distance from the head. Nodes with similar
+//! scores retain their configured order, so that small fluctuations in latency do not cause the
+//! VC to flap between nodes.
+//!
+//! Status checks count towards the error rate, so the error rate of a node which is no longer
+//! tried first recovers once it responds to status checks again.
+use crate::check_synced::SYNC_TOLERANCE;
+use eth2::lighthouse_vc::types::{BeaconNodeHealthData, HealthTier};
+use eth2::types::SyncingData;
+use std::cmp::Reverse;
+use std::time::Duration;
+
+/// Nodes which are fewer than this many slots behind the head (and not optimistic, with an online
+/// EL) are `HealthTier::Healthy`. This matches the tolerance used to determine if a node is synced.
+pub const HEALTHY_SYNC_DISTANCE: u64 = SYNC_TOLERANCE;
+/// Nodes which are at most this many slots behind the head (with an online EL) are
+/// `HealthTier::Degraded`.
+pub const DEGRADED_SYNC_DISTANCE: u64 = 32;
+
+/// The maximum score, for a node with no latency, errors or sync distance.
+pub const MAX_SCORE: f64 = 100.0;
+/// Nodes with scores within the same band of this width are considered equally healthy.
+pub const SCORE_BAND_WIDTH: f64 = 5.0;
+/// A node only moves to another band once its score is this far outside its current band, so that
+/// a score close to the edge of a band does not cause the node to flap between bands.
+pub const SCORE_BAND_HYSTERESIS: f64 = 1.0;
+
+/// Points deducted per millisecond of latency.
+const LATENCY_PENALTY_PER_MS: f64 = 0.1;
+const MAX_LATENCY_PENALTY: f64 = 40.0;
+/// Points deducted for a 100% error rate.
+const MAX_ERROR_PENALTY: f64 = 40.0;
+/// Points deducted per slot of sync distance.
+const SYNC_DISTANCE_PENALTY_PER_SLOT: f64 = 1.0;
+const MAX_SYNC_DISTANCE_PENALTY: f64 = 20.0;
+
+/// Weight of each new latency measurement in the moving average.
+const LATENCY_SMOOTHING: f64 = 0.3;
+/// Weight of each new request outcome in the moving average error rate.
+const ERROR_RATE_SMOOTHING: f64 = 0.1;
+
+/// The measurements used to compute the health of a single beacon node.
+#[derive(Debug, Clone, Default)]
+pub struct HealthMeasurements {
+    /// Whether the node was online and compatible at the last status update.
+    pub available: bool,
+    /// The sync status reported by the node at the last status update.
+    pub sync_status: Option<SyncingData>,
+    /// Exponential moving average of the node's round-trip latency.
+    pub latency: Option<Duration>,
+    /// Exponential moving average of the proportion of failed requests.
+    pub error_rate: f64,
+    /// The score band of the node, which is only updated once the score leaves the band by more
+    /// than `SCORE_BAND_HYSTERESIS`.
+    score_band: Option<u64>,
+}
+
+impl HealthMeasurements {
+    pub fn record_latency(&mut self, latency: Duration) {
+        self.latency = Some(match self.latency {
+            Some(previous) => previous
+                .mul_f64(1.0 - LATENCY_SMOOTHING)
+                .saturating_add(latency.mul_f64(LATENCY_SMOOTHING)),
+            None => latency,
+        });
+        self.update_score_band();
+    }
+
+    pub fn record_request(&mut self, success: bool) {
+        let outcome = if success { 0.0 } else { 1.0 };
+        self.error_rate =
+            self.error_rate * (1.0 - ERROR_RATE_SMOOTHING) + outcome * ERROR_RATE_SMOOTHING;
+        self.update_score_band();
+    }
+
+    /// Set whether the node is online and compatible, clearing its sync status if it is not.
+    pub fn set_available(&mut self, available: bool) {
+        self.available = available;
+        if !available {
+            self.sync_status = None;
+        }
+        self.update_score_band();
+    }
+
+    fn update_score_band(&mut self) {
+        let score = self.score();
+        self.score_band = Some(match self.score_band {
+            Some(band)
+                if score >= band as f64 * SCORE_BAND_WIDTH - SCORE_BAND_HYSTERESIS
+                    && score < (band + 1) as f64 * SCORE_BAND_WIDTH + SCORE_BAND_HYSTERESIS =>
+            {
+                band
+            }
+            _ => (score / SCORE_BAND_WIDTH).floor() as u64,
+        });
+    }
+
+    /// Compute the tier of the node, which takes precedence over its score.
+    pub fn tier(&self) -> HealthTier {
+        match &self.sync_status {
+            Some(sync_status) if self.available => {
+                let sync_distance = sync_status.sync_distance.as_u64();
+                if sync_status.el_offline || sync_distance > DEGRADED_SYNC_DISTANCE {
+                    HealthTier::Unsynced
+                } else if sync_distance >= HEALTHY_SYNC_DISTANCE || sync_status.is_optimistic {
+                    HealthTier::Degraded
+                } else {
+                    HealthTier::Healthy
+                }
+            }
+            // The sync status is unknown if the node was not queried with a slot clock.
+            None if self.available => HealthTier::Healthy,
+            _ => HealthTier::Unavailable,
+        }
+    }
+
+    /// Compute the score of the node, between 0 and `MAX_SCORE`.
+    pub fn score(&self) -> f64 {
+        if !self.available {
+            return 0.0;
+        }
+        let latency_penalty = self.latency.map_or(0.0, |latency| {
+            (latency.as_secs_f64() * 1000.0 * LATENCY_PENALTY_PER_MS).min(MAX_LATENCY_PENALTY)
+        });
+        let error_penalty = self.error_rate.clamp(0.0, 1.0) * MAX_ERROR_PENALTY;
+        let sync_distance_penalty = self.sync_status.as_ref().map_or(0.0, |sync_status| {
+            (sync_status.sync_distance.as_u64() as f64 * SYNC_DISTANCE_PENALTY_PER_SLOT)
+                .min(MAX_SYNC_DISTANCE_PENALTY)
+        });
+        (MAX_SCORE - latency_penalty - error_penalty - sync_distance_penalty).max(0.0)
+    }
+
+    /// Summarise the health of the node at `index` (in the configured order).
+    pub fn health(&self, index: usize, endpoint: String) -> BeaconNodeHealthData {
+        BeaconNodeHealthData {
+            index,
+            endpoint,
+            tier: self.tier(),
+            score: self.score(),
+            head_slot: self.sync_status.as_ref().map(|s| s.head_slot),
+            sync_distance: self.sync_status.as_ref().map(|s| s.sync_distance),
+            is_optimistic: self.sync_status.as_ref().map(|s| s.is_optimistic),
+            el_offline: self.sync_status.as_ref().map(|s| s.el_offline),
+            latency_ms: self.latency.map(|latency| latency.as_millis() as u64),
+            error_rate: self.error_rate,
+        }
+    }
+
+    /// The key by which the node at `index` (in the configured order) is ordered, healthiest
+    /// first.
+    ///
+    /// Nodes are ordered by tier, then by score band, then by their configured order.
+    pub fn sort_key(&self, index: usize) -> (HealthTier, Reverse<u64>, usize) {
+        let score_band = self
+            .score_band
+            .unwrap_or_else(|| (self.score() / SCORE_BAND_WIDTH).floor() as u64);
+        (self.tier(), Reverse(score_band), index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::Slot;
+
+    fn synced(sync_distance: u64) -> SyncingData {
+        SyncingData {
+            is_syncing: sync_distance > 0,
+            is_optimistic: false,
+            el_offline: false,
+            head_slot: Slot::new(100),
+            sync_distance: Slot::new(sync_distance),
+        }
+    }
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-6, "{a} != {b}");
+    }
+
+    fn measurements(sync_status: SyncingData) -> HealthMeasurements {
+        HealthMeasurements {
+            available: true,
+            sync_status: Some(sync_status),
+            ..HealthMeasurements::default()
+        }
+    }
+
+    #[test]
+    fn tiers() {
+        assert_eq!(measurements(synced(0)).tier(), HealthTier::Healthy);
+        assert_eq!(
+            measurements(synced(HEALTHY_SYNC_DISTANCE - 1)).tier(),
+            HealthTier::Healthy
+        );
+        assert_eq!(
+            measurements(synced(HEALTHY_SYNC_DISTANCE)).tier(),
+            HealthTier::Degraded
+        );
+        assert_eq!(
+            measurements(SyncingData {
+                is_optimistic: true,
+                ..synced(0)
+            })
+            .tier(),
+            HealthTier::Degraded
+        );
+        assert_eq!(
+            measurements(synced(DEGRADED_SYNC_DISTANCE + 1)).tier(),
+            HealthTier::Unsynced
+        );
+        assert_eq!(
+            measurements(SyncingData {
+                el_offline: true,
+                ..synced(0)
+            })
+            .tier(),
+            HealthTier::Unsynced
+        );
+        assert_eq!(
+            HealthMeasurements::default().tier(),
+            HealthTier::Unavailable
+        );
+    }
+
+    #[test]
+    fn score_penalties() {
+        let mut health = measurements(synced(0));
+        assert_close(health.score(), MAX_SCORE);
+
+        health.record_latency(Duration::from_millis(100));
+        assert_close(health.score(), MAX_SCORE - 10.0);
+
+        // Latency penalties are capped.
+        health.latency = Some(Duration::from_secs(10));
+        assert_close(health.score(), MAX_SCORE - MAX_LATENCY_PENALTY);
+
+        health.latency = None;
+        health.error_rate = 0.5;
+        assert_close(health.score(), MAX_SCORE - MAX_ERROR_PENALTY / 2.0);
+
+        let health = measurements(synced(2));
+        assert_close(health.score(), MAX_SCORE - 2.0);
+
+        assert_eq!(HealthMeasurements::default().score(), 0.0);
+    }
+
+    #[test]
+    fn moving_averages() {
+        let mut health = HealthMeasurements::default();
+        health.record_latency(Duration::from_millis(100));
+        assert_eq!(health.latency, Some(Duration::from_millis(100)));
+        health.record_latency(Duration::from_millis(200));
+        assert_close(health.latency.unwrap().as_secs_f64(), 0.13);
+
+        health.record_request(false);
+        assert_close(health.error_rate, ERROR_RATE_SMOOTHING);
+        for _ in 0..100 {
+            health.record_request(true);
+        }
+        assert!(health.error_rate < 0.001);
+    }
+
+    #[test]
+    fn ordering() {
+        let mut nodes = [
+            // Configured first, but unsynced.
+            measurements(synced(DEGRADED_SYNC_DISTANCE + 1)).sort_key(0),
+            // High latency.
+            {
+                let mut health = measurements(synced(0));
+                health.record_latency(Duration::from_millis(200));
+                health.sort_key(1)
+            },
+            // Slightly higher latency than the next node, but within the same score band.
+            {
+                let mut health = measurements(synced(0));
+                health.record_latency(Duration::from_millis(9));
+                health.sort_key(2)
+            },
+            {
+                let mut health = measurements(synced(0));
+                health.record_latency(Duration::from_millis(1));
+                health.sort_key(3)
+            },
+            HealthMeasurements::default().sort_key(4),
+        ];
+        nodes.sort();
+        assert_eq!(
+            nodes.iter().map(|(_, _, index)| *index).collect::<Vec<_>>(),
+            vec![2, 3, 1, 0, 4]
+        );
+    }
+
+    #[test]
+    fn score_band_hysteresis() {
+        let mut health = measurements(synced(0));
+        health.record_latency(Duration::from_millis(45));
+        assert_close(health.score(), 95.5);
+        let (_, Reverse(band), _) = health.sort_key(0);
+        assert_eq!(band, 19);
+
+        // Just below the edge of the band, the node stays in its band.
+        health.latency = None;
+        health.record_latency(Duration::from_millis(55));
+        assert_close(health.score(), 94.5);
+        assert_eq!(health.sort_key(0).1, Reverse(19));
+
+        // Once the score is clearly outside the band, the node moves.
+        health.latency = None;
+        health.record_latency(Duration::from_millis(65));
+        assert_close(health.score(), 93.5);
+        assert_eq!(health.sort_key(0).1, Reverse(18));
+
+        // And it does not return until the score is clearly within the previous band.
+        health.latency = None;
+        health.record_latency(Duration::from_millis(45));
+        assert_eq!(health.sort_key(0).1, Reverse(18));
+        health.latency = None;
+        health.record_latency(Duration::from_millis(30));
+        assert_eq!(health.sort_key(0).1, Reverse(19));
+    }
+}
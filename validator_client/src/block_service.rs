@@ -6,12 +6,16 @@ use crate::{
     OfflineOnFailure,
 };
 use crate::{
+    duty_history::DutyHistory,
     http_metrics::metrics,
     validator_store::{Error as ValidatorStoreError, ValidatorStore},
 };
 use bls::SignatureBytes;
 use environment::RuntimeContext;
-use eth2::types::{FullBlockContents, PublishBlockRequest};
+use eth2::lighthouse_vc::types::{
+    BlockSource, DutyKind, DutyOutcome, EventKind, SseBlockProduced, SseBlockPublished,
+};
+use eth2::types::{FullBlockContents, ProduceBlockV3Metadata, PublishBlockRequest};
 use eth2::{BeaconNodeHttpClient, StatusCode};
 use slog::{crit, debug, error, info, trace, warn, Logger};
 use slot_clock::SlotClock;
@@ -22,8 +26,8 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc;
 use types::{
-    BlindedBeaconBlock, BlockType, EthSpec, Graffiti, PublicKeyBytes, SignedBlindedBeaconBlock,
-    Slot,
+    BlindedBeaconBlock, BlockType, EthSpec, Graffiti, Hash256, PublicKeyBytes,
+    SignedBlindedBeaconBlock, Slot,
 };
 
 #[derive(Debug)]
@@ -56,6 +60,7 @@ pub struct BlockServiceBuilder<T, E: EthSpec> {
     slot_clock: Option<Arc<T>>,
     beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     proposer_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
+    duty_history: Option<Arc<DutyHistory>>,
     context: Option<RuntimeContext<E>>,
     graffiti: Option<Graffiti>,
     graffiti_file: Option<GraffitiFile>,
@@ -68,6 +73,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
             slot_clock: None,
             beacon_nodes: None,
             proposer_nodes: None,
+            duty_history: None,
             context: None,
             graffiti: None,
             graffiti_file: None,
@@ -94,6 +100,11 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
         self
     }
 
+    pub fn duty_history(mut self, duty_history: Arc<DutyHistory>) -> Self {
+        self.duty_history = Some(duty_history);
+        self
+    }
+
     pub fn runtime_context(mut self, context: RuntimeContext<E>) -> Self {
         self.context = Some(context);
         self
@@ -121,6 +132,9 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
                 beacon_nodes: self
                     .beacon_nodes
                     .ok_or("Cannot build BlockService without beacon_node")?,
+                duty_history: self
+                    .duty_history
+                    .ok_or("Cannot build BlockService without duty_history")?,
                 context: self
                     .context
                     .ok_or("Cannot build BlockService without runtime_context")?,
@@ -213,6 +227,7 @@ pub struct Inner<T, E: EthSpec> {
     slot_clock: Arc<T>,
     beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     proposer_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
+    duty_history: Arc<DutyHistory>,
     context: RuntimeContext<E>,
     graffiti: Option<Graffiti>,
     graffiti_file: Option<GraffitiFile>,
@@ -330,9 +345,20 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
             self.inner.context.executor.spawn(
                 async move {
                     let result = service
+                        .clone()
                         .publish_block(slot, validator_pubkey, builder_boost_factor)
                         .await;
 
+                    // Signed blocks are recorded as they are published.
+                    if let Err(BlockError::Recoverable(e)) = &result {
+                        service.duty_history.record_unsigned(
+                            validator_pubkey,
+                            DutyKind::BlockProposal,
+                            slot,
+                            e.clone(),
+                        );
+                    }
+
                     match result {
                         Ok(_) => {}
                         Err(BlockError::Recoverable(e)) | Err(BlockError::Irrecoverable(e)) => {
@@ -417,7 +443,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         // Try the proposer nodes first, since we've likely gone to efforts to
         // protect them from DoS attacks and they're most likely to successfully
         // publish a block.
-        proposer_fallback
+        let publish_result = proposer_fallback
             .request_proposers_first(
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
@@ -426,7 +452,33 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                         .await
                 },
             )
-            .await?;
+            .await
+            .map_err(|e| e.to_string());
+
+        self.duty_history.record(
+            *validator_pubkey,
+            DutyOutcome {
+                duty: DutyKind::BlockProposal,
+                slot,
+                signed: true,
+                published: publish_result.is_ok(),
+                included: None,
+                block_root: Some(signed_block.canonical_root()),
+                error: publish_result.clone().err(),
+            },
+        );
+
+        // The block has been signed, so it must not be produced again.
+        publish_result.map_err(BlockError::Irrecoverable)?;
+
+        self.validator_store
+            .event_handler()
+            .register(EventKind::BlockPublished(SseBlockPublished {
+                validator_pubkey: *validator_pubkey,
+                slot: signed_block.slot(),
+                block_root: signed_block.canonical_root(),
+                source: signed_block.source(),
+            }));
 
         info!(
             log,
@@ -502,7 +554,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         //
         // Try the proposer nodes last, since it's likely that they don't have a
         // great view of attestations on the network.
-        let unsigned_block = proposer_fallback
+        let (unsigned_block, metadata) = proposer_fallback
             .request_proposers_last(
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
@@ -531,6 +583,16 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
             )
             .await?;
 
+        self.validator_store
+            .event_handler()
+            .register(EventKind::BlockProduced(SseBlockProduced {
+                validator_pubkey,
+                slot,
+                source: unsigned_block.source(),
+                execution_payload_value: metadata.execution_payload_value,
+                consensus_block_value: metadata.consensus_block_value,
+            }));
+
         self_ref
             .sign_and_publish_block(
                 proposer_fallback,
@@ -584,8 +646,8 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         proposer_index: Option<u64>,
         builder_boost_factor: Option<u64>,
         log: &Logger,
-    ) -> Result<UnsignedBlock<E>, BlockError> {
-        let (block_response, _) = beacon_node
+    ) -> Result<(UnsignedBlock<E>, ProduceBlockV3Metadata), BlockError> {
+        let (block_response, metadata) = beacon_node
             .get_validator_blocks_v3::<E>(
                 slot,
                 randao_reveal_ref,
@@ -616,7 +678,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
             ));
         }
 
-        Ok::<_, BlockError>(unsigned_block)
+        Ok::<_, BlockError>((unsigned_block, metadata))
     }
 
     /// Returns the builder boost factor of the given public key.
@@ -662,6 +724,14 @@ impl<E: EthSpec> UnsignedBlock<E> {
             UnsignedBlock::Blinded(block) => block.proposer_index(),
         }
     }
+
+    /// Blocks with a payload from a builder are always blinded.
+    pub fn source(&self) -> BlockSource {
+        match self {
+            UnsignedBlock::Full(_) => BlockSource::Local,
+            UnsignedBlock::Blinded(_) => BlockSource::Builder,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -677,6 +747,12 @@ impl<E: EthSpec> SignedBlock<E> {
             SignedBlock::Blinded(_) => BlockType::Blinded,
         }
     }
+    pub fn source(&self) -> BlockSource {
+        match self {
+            SignedBlock::Full(_) => BlockSource::Local,
+            SignedBlock::Blinded(_) => BlockSource::Builder,
+        }
+    }
     pub fn slot(&self) -> Slot {
         match self {
             SignedBlock::Full(block) => block.signed_block().message().slot(),
@@ -695,6 +771,12 @@ impl<E: EthSpec> SignedBlock<E> {
             SignedBlock::Blinded(block) => block.message().body().attestations_len(),
         }
     }
+    pub fn canonical_root(&self) -> Hash256 {
+        match self {
+            SignedBlock::Full(block) => block.signed_block().canonical_root(),
+            SignedBlock::Blinded(block) => block.canonical_root(),
+        }
+    }
 }
 
 fn handle_block_post_error(err: eth2::Error, slot: Slot, log: &Logger) -> Result<(), BlockError> {
@@ -1,26 +1,44 @@
 use crate::beacon_node_fallback::CandidateError;
+use eth2::types::SyncingData;
 use eth2::BeaconNodeHttpClient;
 use slog::{debug, error, warn, Logger};
 use slot_clock::SlotClock;
 
 /// A distance in slots.
-const SYNC_TOLERANCE: u64 = 4;
+pub const SYNC_TOLERANCE: u64 = 4;
 
 /// Returns
 ///
 ///  `Ok(())`                           if the beacon node is synced and ready for action,
-///  `Err(CandidateError::Offline)`     if the beacon node is unreachable,
 ///  `Err(CandidateError::NotSynced)`   if the beacon node indicates that it is syncing **AND**
 ///                                         it is more than `SYNC_TOLERANCE` behind the highest
-///                                         known slot.
+///                                         known slot, or its execution layer is offline.
 ///
-///  The second condition means the even if the beacon node thinks that it's syncing, we'll still
+///  The first condition means the even if the beacon node thinks that it's syncing, we'll still
 ///  try to use it if it's close enough to the head.
-pub async fn check_synced<T: SlotClock>(
+pub fn check_synced(sync_status: &SyncingData) -> Result<(), CandidateError> {
+    if is_synced(sync_status) {
+        Ok(())
+    } else {
+        Err(CandidateError::NotSynced)
+    }
+}
+
+fn is_synced(sync_status: &SyncingData) -> bool {
+    let bn_is_synced =
+        !sync_status.is_syncing || (sync_status.sync_distance.as_u64() < SYNC_TOLERANCE);
+    bn_is_synced && !sync_status.el_offline
+}
+
+/// Queries the sync status of the beacon node, logging if it is not synced or if its clock
+/// differs from ours.
+///
+/// Returns `Err(CandidateError::Offline)` if the beacon node is unreachable.
+pub async fn get_sync_status<T: SlotClock>(
     beacon_node: &BeaconNodeHttpClient,
     slot_clock: &T,
     log_opt: Option<&Logger>,
-) -> Result<(), CandidateError> {
+) -> Result<SyncingData, CandidateError> {
     let resp = match beacon_node.get_node_syncing().await {
         Ok(resp) => resp,
         Err(e) => {
@@ -36,8 +54,7 @@ pub async fn check_synced<T: SlotClock>(
         }
     };
 
-    let bn_is_synced = !resp.data.is_syncing || (resp.data.sync_distance.as_u64() < SYNC_TOLERANCE);
-    let is_synced = bn_is_synced && !resp.data.el_offline;
+    let is_synced = is_synced(&resp.data);
 
     if let Some(log) = log_opt {
         if !is_synced {
@@ -72,9 +89,5 @@ pub async fn check_synced<T: SlotClock>(
         }
     }
 
-    if is_synced {
-        Ok(())
-    } else {
-        Err(CandidateError::NotSynced)
-    }
+    Ok(resp.data)
 }
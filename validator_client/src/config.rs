@@ -1,4 +1,5 @@
 use crate::beacon_node_fallback::ApiTopic;
+use crate::duty_history::DEFAULT_DUTY_HISTORY_SIZE;
 use crate::graffiti_file::GraffitiFile;
 use crate::{http_api, http_metrics};
 use clap::ArgMatches;
@@ -40,6 +41,9 @@ pub struct Config {
     pub disable_auto_discover: bool,
     /// If true, re-register existing validators in definitions.yml for slashing protection.
     pub init_slashing_protection: bool,
+    /// An optional PostgreSQL database to use for slashing protection instead of the local
+    /// SQLite file, allowing several validator clients to share one signing history.
+    pub slashing_protection_db_url: Option<SensitiveUrl>,
     /// If true, use longer timeouts for requests made to the beacon node.
     pub use_long_timeouts: bool,
     /// Graffiti to be inserted everytime we create a block.
@@ -57,6 +61,9 @@ pub struct Config {
     /// If true, enable functionality that monitors the network for attestations or proposals from
     /// any of the validators managed by this client before starting up.
     pub enable_doppelganger_protection: bool,
+    /// If true, continue to monitor the network for messages from any of the validators managed
+    /// by this client which it did not sign, disabling those validators.
+    pub enable_continuous_doppelganger_protection: bool,
     /// If true, then we publish validator specific metrics (e.g next attestation duty slot)
     /// for all our managed validators.
     /// Note: We publish validator specific metrics for low validator counts without this flag
@@ -77,6 +84,8 @@ pub struct Config {
     pub enable_latency_measurement_service: bool,
     /// Defines the number of validators per `validator/register_validator` request sent to the BN.
     pub validator_registration_batch_size: usize,
+    /// The maximum number of duty outcomes kept for each validator. No history is kept if zero.
+    pub duty_history_size: usize,
     /// Enable slashing protection even while using web3signer keys.
     pub enable_web3signer_slashing_protection: bool,
     /// Specifies the boost factor, a percentage multiplier to apply to the builder's payload value.
@@ -111,6 +120,7 @@ impl Default for Config {
             allow_unsynced_beacon_node: false,
             disable_auto_discover: false,
             init_slashing_protection: false,
+            slashing_protection_db_url: None,
             use_long_timeouts: false,
             graffiti: None,
             graffiti_file: None,
@@ -119,6 +129,7 @@ impl Default for Config {
             http_metrics: <_>::default(),
             monitoring_api: None,
             enable_doppelganger_protection: false,
+            enable_continuous_doppelganger_protection: false,
             enable_high_validator_count_metrics: false,
             beacon_nodes_tls_certs: None,
             builder_proposals: false,
@@ -127,6 +138,7 @@ impl Default for Config {
             broadcast_topics: vec![ApiTopic::Subscriptions],
             enable_latency_measurement_service: true,
             validator_registration_batch_size: 500,
+            duty_history_size: DEFAULT_DUTY_HISTORY_SIZE,
             enable_web3signer_slashing_protection: true,
             builder_boost_factor: None,
             prefer_builder_proposals: false,
@@ -194,6 +206,16 @@ impl Config {
 
         config.disable_auto_discover = cli_args.get_flag("disable-auto-discover");
         config.init_slashing_protection = cli_args.get_flag("init-slashing-protection");
+        config.slashing_protection_db_url = parse_optional(cli_args, "slashing-protection-db-url")?;
+        if config.slashing_protection_db_url.is_some()
+            && cfg!(not(feature = "slashing-protection-postgres"))
+        {
+            return Err(
+                "--slashing-protection-db-url requires Lighthouse to be compiled with the \
+                 `slashing-protection-postgres` feature"
+                    .to_string(),
+            );
+        }
         config.use_long_timeouts = cli_args.get_flag("use-long-timeouts");
 
         if let Some(graffiti_file_path) = cli_args.get_one::<String>("graffiti-file") {
@@ -373,6 +395,10 @@ impl Config {
             config.enable_doppelganger_protection = true;
         }
 
+        if cli_args.get_flag("enable-continuous-doppelganger-protection") {
+            config.enable_continuous_doppelganger_protection = true;
+        }
+
         if cli_args.get_flag("builder-proposals") {
             config.builder_proposals = true;
         }
@@ -430,6 +456,8 @@ impl Config {
             return Err("validator-registration-batch-size cannot be 0".to_string());
         }
 
+        config.duty_history_size = parse_required(cli_args, "duty-history-size")?;
+
         config.enable_web3signer_slashing_protection =
             if cli_args.get_flag("disable-slashing-protection-web3signer") {
                 warn!(
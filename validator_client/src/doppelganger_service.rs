@@ -15,6 +15,18 @@
 //! prevents a stale-mate where all validators will cease to function for a few epochs and then all
 //! start at the same time.
 //!
+//! ## Continuous detection
+//!
+//! Optionally, the service continues to watch for doppelgangers after the initial detection period
+//! by subscribing to the block and attestation events of the beacon node. Every block or
+//! attestation from a local validator must be recorded in the slashing protection database before
+//! it is signed, so any such message without a record was signed by another instance of that
+//! validator. When one is found, that validator alone is disabled and the validator client keeps
+//! running.
+//!
+//! Attestations are attributed to validators using their attester duties. An aggregate which spans
+//! several committees is only checked for the first of them.
+//!
 //! ## Caveat
 //!
 //! Presently doppelganger protection will never advance if the call at the last slot of each epoch
@@ -30,19 +42,27 @@
 //! Doppelganger protection is a best-effort, last-line-of-defence mitigation. Do not rely upon it.
 
 use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
+use crate::duties_service::{DutiesService, DutyAndProof};
+use crate::events::ServerSentEventHandler;
+use crate::http_metrics::metrics;
 use crate::validator_store::ValidatorStore;
 use crate::OfflineOnFailure;
 use environment::RuntimeContext;
-use eth2::types::LivenessResponseData;
+use eth2::lighthouse_vc::types::{
+    DoppelgangerProtectionStatus, EventKind as VcEventKind, SseDoppelganger,
+};
+use eth2::types::{BlockId, EventKind, EventTopic, LivenessResponseData, SseBlock};
+use futures::StreamExt;
 use parking_lot::RwLock;
-use slog::{crit, error, info, Logger};
+use slashing_protection::NotSafe;
+use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
 use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::sync::Arc;
 use task_executor::ShutdownReason;
 use tokio::time::sleep;
-use types::{Epoch, EthSpec, PublicKeyBytes, Slot};
+use types::{Attestation, Epoch, EthSpec, PublicKeyBytes, Slot};
 
 /// A wrapper around `PublicKeyBytes` which encodes information about the status of a validator
 /// pubkey with regards to doppelganger protection.
@@ -270,6 +290,7 @@ async fn beacon_node_liveness<'a, T: 'static + SlotClock, E: EthSpec>(
 
 pub struct DoppelgangerService {
     doppelganger_states: RwLock<HashMap<PublicKeyBytes, DoppelgangerState>>,
+    event_handler: Option<Arc<ServerSentEventHandler>>,
     log: Logger,
 }
 
@@ -277,10 +298,29 @@ impl DoppelgangerService {
     pub fn new(log: Logger) -> Self {
         Self {
             doppelganger_states: <_>::default(),
+            event_handler: None,
             log,
         }
     }
 
+    /// Used to publish an event whenever the status of a validator changes.
+    pub fn set_event_handler(&mut self, event_handler: Arc<ServerSentEventHandler>) {
+        self.event_handler = Some(event_handler);
+    }
+
+    fn register_status_event(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        status: DoppelgangerProtectionStatus,
+    ) {
+        if let Some(event_handler) = &self.event_handler {
+            event_handler.register(VcEventKind::Doppelganger(SseDoppelganger {
+                validator_pubkey,
+                status,
+            }));
+        }
+    }
+
     /// Starts a reoccurring future which will try to keep the doppelganger service updated each
     /// slot.
     pub fn start_update_service<E: EthSpec, T: 'static + SlotClock>(
@@ -365,6 +405,212 @@ impl DoppelgangerService {
         Ok(())
     }
 
+    /// Starts a long-running task which watches the beacon node's event stream for blocks and
+    /// attestations by local validators that are not recorded in the slashing protection database.
+    ///
+    /// Only validators which have completed their initial detection period are watched.
+    pub fn start_continuous_detection_service<E: EthSpec, T: 'static + SlotClock>(
+        service: Arc<Self>,
+        context: RuntimeContext<E>,
+        duties_service: Arc<DutiesService<T, E>>,
+    ) -> Result<(), String> {
+        info!(
+            service.log,
+            "Continuous doppelganger detection service started";
+        );
+
+        context.executor.spawn(
+            async move {
+                loop {
+                    // The HTTP client's timeout ends each subscription after a slot, so only back
+                    // off if the subscription itself fails.
+                    if let Err(e) = service.watch_events(&duties_service).await {
+                        error!(
+                            service.log,
+                            "Unable to subscribe to beacon node events";
+                            "error" => %e,
+                        );
+                        sleep(duties_service.slot_clock.slot_duration()).await;
+                    }
+                }
+            },
+            "continuous_doppelganger_service",
+        );
+        Ok(())
+    }
+
+    /// Check the blocks and attestations from the event stream of a beacon node until the stream
+    /// ends.
+    async fn watch_events<E: EthSpec, T: 'static + SlotClock>(
+        &self,
+        duties_service: &DutiesService<T, E>,
+    ) -> Result<(), String> {
+        let events = duties_service
+            .beacon_nodes
+            .first_success(
+                RequireSynced::No,
+                OfflineOnFailure::Yes,
+                |beacon_node| async move {
+                    beacon_node
+                        .get_events::<E>(&[EventTopic::Block, EventTopic::Attestation])
+                        .await
+                },
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut events = std::pin::pin!(events);
+
+        while let Some(event) = events.next().await {
+            match event {
+                Ok(EventKind::Block(block)) => self.check_block(duties_service, block).await,
+                Ok(EventKind::Attestation(attestation)) => {
+                    self.check_attestation(duties_service, &attestation).await
+                }
+                Ok(_) => (),
+                Err(eth2::Error::HttpClient(e)) => {
+                    debug!(
+                        self.log,
+                        "Beacon node event stream ended";
+                        "error" => %e,
+                    );
+                    break;
+                }
+                Err(e) => {
+                    warn!(
+                        self.log,
+                        "Invalid beacon node event";
+                        "error" => ?e,
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check a block proposed by a local validator.
+    async fn check_block<E: EthSpec, T: 'static + SlotClock>(
+        &self,
+        duties_service: &DutiesService<T, E>,
+        block: SseBlock,
+    ) {
+        let proposers = duties_service.block_proposers(block.slot);
+        if proposers.is_empty() {
+            return;
+        }
+
+        let header = match duties_service
+            .beacon_nodes
+            .first_success(
+                RequireSynced::No,
+                OfflineOnFailure::Yes,
+                |beacon_node| async move {
+                    beacon_node
+                        .get_beacon_headers_block_id(BlockId::Root(block.block))
+                        .await
+                },
+            )
+            .await
+        {
+            Ok(Some(response)) => response.data.header.message,
+            Ok(None) => return,
+            Err(e) => {
+                error!(
+                    self.log,
+                    "Unable to fetch block header for doppelganger detection";
+                    "error" => %e,
+                    "block_root" => ?block.block,
+                );
+                return;
+            }
+        };
+
+        let validator_store = &duties_service.validator_store;
+        for pubkey in proposers {
+            if validator_store.validator_index(&pubkey) != Some(header.proposer_index) {
+                continue;
+            }
+            let unrecorded = validator_store.is_unrecorded_block(pubkey, &header);
+            self.handle_check_result(validator_store, pubkey, unrecorded, metrics::BEACON_BLOCK)
+                .await;
+        }
+    }
+
+    /// Check an attestation which may include local validators.
+    async fn check_attestation<E: EthSpec, T: 'static + SlotClock>(
+        &self,
+        duties_service: &DutiesService<T, E>,
+        attestation: &Attestation<E>,
+    ) {
+        let duties = duties_service.attesters(attestation.data().slot);
+        let validator_store = &duties_service.validator_store;
+        for pubkey in attesting_validators(attestation, &duties) {
+            let unrecorded = validator_store.is_unrecorded_attestation(pubkey, attestation.data());
+            self.handle_check_result(validator_store, pubkey, unrecorded, metrics::ATTESTATIONS)
+                .await;
+        }
+    }
+
+    /// Disable `validator` if it signed a message which isn't in the slashing protection
+    /// database.
+    async fn handle_check_result<E: EthSpec, T: 'static + SlotClock>(
+        &self,
+        validator_store: &ValidatorStore<T, E>,
+        validator: PublicKeyBytes,
+        unrecorded: Result<bool, NotSafe>,
+        message: &str,
+    ) {
+        match unrecorded {
+            Ok(false) => return,
+            Ok(true) => (),
+            // The validator has already been disabled or removed.
+            Err(NotSafe::DisabledValidator(_)) | Err(NotSafe::UnregisteredValidator(_)) => return,
+            Err(e) => {
+                error!(
+                    self.log,
+                    "Unable to check slashing protection for doppelganger detection";
+                    "error" => ?e,
+                    "pubkey" => ?validator,
+                );
+                return;
+            }
+        }
+
+        metrics::inc_counter_vec(&metrics::DOPPELGANGER_DETECTIONS_TOTAL, &[message]);
+        crit!(
+            self.log,
+            "Doppelganger detected";
+            "msg" => "A message signed by this validator was observed on the network, but this \
+                validator client did not sign it. Another validator client is running the same \
+                key. The validator has been disabled to prevent potential slashable offences",
+            "message" => message,
+            "pubkey" => ?validator,
+        );
+
+        // Stop signing before disabling the validator, which may take some time.
+        self.disable_signing(validator);
+        self.register_status_event(
+            validator,
+            DoppelgangerProtectionStatus::DoppelgangerDetected,
+        );
+        if let Err(e) = validator_store.disable_validator(validator).await {
+            crit!(
+                self.log,
+                "Failed to disable validator after detecting a doppelganger";
+                "msg" => "the validator will not sign until the validator client is restarted",
+                "error" => e,
+                "pubkey" => ?validator,
+            );
+        }
+    }
+
+    /// Prevent `validator` from signing any slashable messages until it is registered again.
+    pub fn disable_signing(&self, validator: PublicKeyBytes) {
+        if let Some(state) = self.doppelganger_states.write().get_mut(&validator) {
+            state.remaining_epochs = u64::MAX;
+        }
+    }
+
     /// Returns the current status of the `validator` in the doppelganger protection process.
     pub fn validator_status(&self, validator: PublicKeyBytes) -> DoppelgangerStatus {
         self.doppelganger_states
@@ -425,6 +671,13 @@ impl DoppelgangerService {
 
         self.doppelganger_states.write().insert(validator, state);
 
+        let status = if remaining_epochs == 0 {
+            DoppelgangerProtectionStatus::SigningEnabled
+        } else {
+            DoppelgangerProtectionStatus::Detecting
+        };
+        self.register_status_event(validator, status);
+
         Ok(())
     }
 
@@ -582,7 +835,15 @@ impl DoppelgangerService {
                     offences. Ensure that you are not running a duplicate or overlapping \
                     validator client",
                 "doppelganger_indices" => ?violators
-            )
+            );
+            for index in &violators {
+                if let Some(pubkey) = indices_map.get(index) {
+                    self.register_status_event(
+                        *pubkey,
+                        DoppelgangerProtectionStatus::DoppelgangerDetected,
+                    );
+                }
+            }
         }
 
         // The concept of "epoch satisfaction" is that for some epoch `e` we are *satisfied* that
@@ -599,6 +860,10 @@ impl DoppelgangerService {
             .end_slot(E::slots_per_epoch());
         let previous_epoch_is_satisfied = request_slot >= previous_epoch_satisfaction_slot;
 
+        // Validators which completed detection, for which events are published once the lock on
+        // `self.doppelganger_states` is released.
+        let mut newly_enabled = vec![];
+
         // Iterate through all the previous epoch responses, updating `self.doppelganger_states`.
         //
         // Do not bother iterating through the current epoch responses since they've already been
@@ -671,10 +936,15 @@ impl DoppelgangerService {
                         "msg" => "starting validator",
                         "validator_index" => response.index
                     );
+                    newly_enabled.push(*pubkey);
                 }
             }
         }
 
+        for pubkey in newly_enabled {
+            self.register_status_event(pubkey, DoppelgangerProtectionStatus::SigningEnabled);
+        }
+
         // Attempt to shutdown the validator client if there are any detected duplicate validators.
         if violators_exist {
             shutdown_func();
@@ -684,6 +954,28 @@ impl DoppelgangerService {
     }
 }
 
+/// Returns the validators in `duties` which participated in `attestation`.
+///
+/// For an aggregate spanning several committees only the first committee is considered, since the
+/// offsets of later committees depend on the lengths of committees without local validators.
+fn attesting_validators<E: EthSpec>(
+    attestation: &Attestation<E>,
+    duties: &[DutyAndProof],
+) -> Vec<PublicKeyBytes> {
+    duties
+        .iter()
+        .filter(|duty_and_proof| {
+            let duty = &duty_and_proof.duty;
+            duty.slot == attestation.data().slot
+                && attestation.committee_index() == Some(duty.committee_index)
+                && attestation
+                    .get_aggregation_bit(duty.validator_committee_index as usize)
+                    .unwrap_or(false)
+        })
+        .map(|duty_and_proof| duty_and_proof.duty.pubkey)
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1460,4 +1752,139 @@ mod test {
 
         scenario.assert_all_enabled();
     }
+
+    #[test]
+    fn disable_signing() {
+        let scenario = TestBuilder::default()
+            .build()
+            .register_all_in_doppelganger_protection_if_enabled()
+            .assert_all_enabled();
+
+        let disabled = scenario.validators[1];
+        scenario.doppelganger.disable_signing(disabled);
+
+        for validator in &scenario.validators {
+            let expected = if *validator == disabled {
+                DoppelgangerStatus::SigningDisabled(*validator)
+            } else {
+                DoppelgangerStatus::SigningEnabled(*validator)
+            };
+            assert_eq!(scenario.doppelganger.validator_status(*validator), expected);
+        }
+    }
+
+    mod attesting_validators {
+        use super::*;
+        use types::{
+            AggregateSignature, AttestationBase, AttestationData, AttestationElectra, BitList,
+            BitVector, Checkpoint, Hash256,
+        };
+
+        const SLOT: Slot = Slot::new(3);
+
+        fn duty(pubkey: PublicKeyBytes, committee_index: u64, position: u64) -> DutyAndProof {
+            DutyAndProof::new_without_selection_proof(
+                eth2::types::AttesterData {
+                    pubkey,
+                    validator_index: 0,
+                    committees_at_slot: 4,
+                    committee_index,
+                    committee_length: 8,
+                    validator_committee_index: position,
+                    slot: SLOT,
+                },
+                SLOT,
+            )
+        }
+
+        fn data(index: u64) -> AttestationData {
+            AttestationData {
+                slot: SLOT,
+                index,
+                beacon_block_root: Hash256::zero(),
+                source: Checkpoint::default(),
+                target: Checkpoint::default(),
+            }
+        }
+
+        fn base_attestation(committee_index: u64, positions: &[usize]) -> Attestation<E> {
+            let mut aggregation_bits = BitList::with_capacity(8).unwrap();
+            for position in positions {
+                aggregation_bits.set(*position, true).unwrap();
+            }
+            Attestation::Base(AttestationBase {
+                aggregation_bits,
+                data: data(committee_index),
+                signature: AggregateSignature::empty(),
+            })
+        }
+
+        fn electra_attestation(committee_indices: &[usize], positions: &[usize]) -> Attestation<E> {
+            let mut committee_bits = BitVector::new();
+            for index in committee_indices {
+                committee_bits.set(*index, true).unwrap();
+            }
+            let mut aggregation_bits = BitList::with_capacity(8 * committee_indices.len()).unwrap();
+            for position in positions {
+                aggregation_bits.set(*position, true).unwrap();
+            }
+            Attestation::Electra(AttestationElectra {
+                aggregation_bits,
+                data: data(0),
+                committee_bits,
+                signature: AggregateSignature::empty(),
+            })
+        }
+
+        fn validators() -> Vec<PublicKeyBytes> {
+            let mut rng = XorShiftRng::from_seed([42; 16]);
+            (0..3)
+                .map(|_| PublicKeyBytes::random_for_test(&mut rng))
+                .collect()
+        }
+
+        #[test]
+        fn base() {
+            let validators = validators();
+            let duties = vec![
+                duty(validators[0], 1, 2),
+                duty(validators[1], 1, 5),
+                duty(validators[2], 2, 2),
+            ];
+
+            assert_eq!(
+                attesting_validators(&base_attestation(1, &[2]), &duties),
+                vec![validators[0]]
+            );
+            assert_eq!(
+                attesting_validators(&base_attestation(1, &[2, 5]), &duties),
+                vec![validators[0], validators[1]]
+            );
+            assert_eq!(
+                attesting_validators(&base_attestation(2, &[2, 5]), &duties),
+                vec![validators[2]]
+            );
+            assert!(attesting_validators(&base_attestation(3, &[2]), &duties).is_empty());
+        }
+
+        #[test]
+        fn electra() {
+            let validators = validators();
+            let duties = vec![duty(validators[0], 1, 2), duty(validators[1], 2, 2)];
+
+            assert_eq!(
+                attesting_validators(&electra_attestation(&[1], &[2]), &duties),
+                vec![validators[0]]
+            );
+            assert_eq!(
+                attesting_validators(&electra_attestation(&[2], &[2]), &duties),
+                vec![validators[1]]
+            );
+            // Only the first committee of an aggregate is checked.
+            assert_eq!(
+                attesting_validators(&electra_attestation(&[1, 2], &[2, 10]), &duties),
+                vec![validators[0]]
+            );
+        }
+    }
 }
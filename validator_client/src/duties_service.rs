@@ -16,6 +16,7 @@ use crate::{
     validator_store::{DoppelgangerStatus, Error as ValidatorStoreError, ValidatorStore},
 };
 use environment::RuntimeContext;
+use eth2::lighthouse_vc::types::{DutyKind, EventKind, SseDutyScheduled};
 use eth2::types::{
     AttesterData, BeaconCommitteeSubscription, DutiesResponse, ProposerData, StateId, ValidatorId,
 };
@@ -906,6 +907,7 @@ async fn poll_beacon_attesters_for_epoch<T: SlotClock + 'static, E: EthSpec>(
 
     // Update the duties service with the new `DutyAndProof` messages.
     let mut attesters = duties_service.attesters.write();
+    let mut scheduled = vec![];
     let mut already_warned = Some(());
     let current_slot = duties_service
         .slot_clock
@@ -956,9 +958,20 @@ async fn poll_beacon_attesters_for_epoch<T: SlotClock + 'static, E: EthSpec>(
                 vacant.insert((dependent_root, duty_and_proof));
             }
         }
+        scheduled.push(scheduled_duty_event(
+            duty.pubkey,
+            duty.validator_index,
+            DutyKind::Attestation,
+            duty.slot,
+        ));
     }
     drop(attesters);
 
+    let event_handler = duties_service.validator_store.event_handler();
+    for event in scheduled {
+        event_handler.register(event);
+    }
+
     // Spawn the background task to compute selection proofs.
     let subservice = duties_service.clone();
     duties_service.context.executor.spawn(
@@ -1305,10 +1318,22 @@ async fn poll_beacon_proposers<T: SlotClock + 'static, E: EthSpec>(
                     "num_relevant_duties" => relevant_duties.len(),
                 );
 
-                if let Some((prior_dependent_root, _)) = duties_service
-                    .proposers
-                    .write()
-                    .insert(current_epoch, (dependent_root, relevant_duties))
+                let mut proposers = duties_service.proposers.write();
+
+                // Proposer duties are downloaded every slot, so only publish events for the
+                // duties which were not already known.
+                let new_duties = relevant_duties
+                    .iter()
+                    .filter(|duty| {
+                        proposers
+                            .get(&current_epoch)
+                            .map_or(true, |(_, prior_duties)| !prior_duties.contains(duty))
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                if let Some((prior_dependent_root, _)) =
+                    proposers.insert(current_epoch, (dependent_root, relevant_duties))
                 {
                     if dependent_root != prior_dependent_root {
                         warn!(
@@ -1320,6 +1345,17 @@ async fn poll_beacon_proposers<T: SlotClock + 'static, E: EthSpec>(
                         )
                     }
                 }
+                drop(proposers);
+
+                let event_handler = duties_service.validator_store.event_handler();
+                for duty in new_duties {
+                    event_handler.register(scheduled_duty_event(
+                        duty.pubkey,
+                        duty.validator_index,
+                        DutyKind::BlockProposal,
+                        duty.slot,
+                    ));
+                }
             }
             // Don't return early here, we still want to try and produce blocks using the cached values.
             Err(e) => error!(
@@ -1371,6 +1407,20 @@ async fn poll_beacon_proposers<T: SlotClock + 'static, E: EthSpec>(
     Ok(())
 }
 
+fn scheduled_duty_event(
+    validator_pubkey: PublicKeyBytes,
+    validator_index: u64,
+    duty: DutyKind,
+    slot: Slot,
+) -> EventKind {
+    EventKind::DutyScheduled(SseDutyScheduled {
+        validator_pubkey,
+        validator_index,
+        duty,
+        slot,
+    })
+}
+
 /// Notify the block service if it should produce a block.
 async fn notify_block_production_service<T: SlotClock + 'static, E: EthSpec>(
     current_slot: Slot,
@@ -0,0 +1,642 @@
+//! Records the outcome of each duty attempted by the local validators.
+//!
+//! The services which perform duties record whether each message was signed and published. Once
+//! per epoch, the beacon node is asked whether recently published messages were included on chain
+//! and the history is written to disk. The number of outcomes kept for each validator is bounded,
+//! with the oldest outcomes being dropped first.
+
+use crate::beacon_node_fallback::{OfflineOnFailure, RequireSynced};
+use crate::duties_service::DutiesService;
+use account_utils::write_file_via_temporary;
+use environment::RuntimeContext;
+use eth2::lighthouse_vc::types::{DutyKind, DutyOutcome};
+use eth2::types::{BlockId, ValidatorId};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use slog::{debug, error, info, warn, Logger};
+use slot_clock::SlotClock;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio::time::sleep;
+use types::{Epoch, EthSpec, PublicKeyBytes, Slot};
+
+/// The file in the validator directory which stores the duty history.
+pub const DUTY_HISTORY_FILENAME: &str = "duty_history.json";
+const DUTY_HISTORY_TEMP_FILENAME: &str = ".duty_history.json.tmp";
+
+/// The default number of outcomes kept for each validator, roughly one day of attestations.
+pub const DEFAULT_DUTY_HISTORY_SIZE: usize = 256;
+
+/// The number of new outcomes which can be buffered for slow event subscribers.
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Published messages are only checked for inclusion within this many epochs, since the beacon
+/// node may not keep the states required to check older messages.
+const INCLUSION_CHECK_EPOCHS: u64 = 4;
+
+/// The duty history of a single validator, as stored on disk.
+#[derive(Serialize, Deserialize)]
+struct ValidatorDutyHistory {
+    pubkey: PublicKeyBytes,
+    outcomes: Vec<DutyOutcome>,
+}
+
+pub struct DutyHistory {
+    outcomes: RwLock<HashMap<PublicKeyBytes, VecDeque<DutyOutcome>>>,
+    /// The maximum number of outcomes kept for each validator. No outcomes are kept if zero.
+    max_outcomes: usize,
+    /// The directory to which the history is written, if any.
+    dir: Option<PathBuf>,
+    event_tx: broadcast::Sender<(PublicKeyBytes, DutyOutcome)>,
+    log: Logger,
+}
+
+impl DutyHistory {
+    /// Create an empty history which is never written to disk.
+    pub fn new(max_outcomes: usize, log: Logger) -> Self {
+        let (event_tx, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self {
+            outcomes: <_>::default(),
+            max_outcomes,
+            dir: None,
+            event_tx,
+            log,
+        }
+    }
+
+    /// Load the history stored in `dir`, starting a new history if there is none.
+    ///
+    /// A history which cannot be read is discarded, since it is not required to perform duties.
+    pub fn open(dir: &Path, max_outcomes: usize, log: Logger) -> Self {
+        let mut history = Self::new(max_outcomes, log);
+        history.dir = Some(dir.to_path_buf());
+
+        let path = dir.join(DUTY_HISTORY_FILENAME);
+        if max_outcomes == 0 || !path.exists() {
+            return history;
+        }
+
+        let stored = fs::read(&path)
+            .map_err(|e| format!("Unable to read file: {:?}", e))
+            .and_then(|bytes| {
+                serde_json::from_slice::<Vec<ValidatorDutyHistory>>(&bytes)
+                    .map_err(|e| format!("Unable to parse file: {:?}", e))
+            });
+        match stored {
+            Ok(validators) => {
+                let mut outcomes = history.outcomes.write();
+                for validator in validators {
+                    let skip = validator.outcomes.len().saturating_sub(max_outcomes);
+                    outcomes.insert(
+                        validator.pubkey,
+                        validator.outcomes.into_iter().skip(skip).collect(),
+                    );
+                }
+            }
+            Err(e) => warn!(
+                history.log,
+                "Discarding unreadable duty history";
+                "error" => e,
+                "path" => ?path,
+            ),
+        }
+
+        history
+    }
+
+    /// Record the outcome of a duty, notifying any subscribers.
+    pub fn record(&self, validator_pubkey: PublicKeyBytes, outcome: DutyOutcome) {
+        if self.max_outcomes == 0 {
+            return;
+        }
+
+        {
+            let mut outcomes = self.outcomes.write();
+            let validator_outcomes = outcomes.entry(validator_pubkey).or_default();
+            validator_outcomes.push_back(outcome.clone());
+            while validator_outcomes.len() > self.max_outcomes {
+                validator_outcomes.pop_front();
+            }
+        }
+
+        // An error only indicates that there are no subscribers.
+        let _ = self.event_tx.send((validator_pubkey, outcome));
+    }
+
+    /// Record a duty which failed before its message was signed.
+    pub fn record_unsigned(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        duty: DutyKind,
+        slot: Slot,
+        error: String,
+    ) {
+        self.record(
+            validator_pubkey,
+            DutyOutcome {
+                duty,
+                slot,
+                signed: false,
+                published: false,
+                included: None,
+                block_root: None,
+                error: Some(error),
+            },
+        )
+    }
+
+    /// Record a duty whose message was signed, along with the result of publishing it.
+    pub fn record_signed(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        duty: DutyKind,
+        slot: Slot,
+        publish_result: Result<(), String>,
+    ) {
+        self.record(
+            validator_pubkey,
+            DutyOutcome {
+                duty,
+                slot,
+                signed: true,
+                published: publish_result.is_ok(),
+                included: None,
+                block_root: None,
+                error: publish_result.err(),
+            },
+        )
+    }
+
+    /// Set whether the message published for a duty was included on chain, notifying any
+    /// subscribers.
+    pub fn set_included(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        duty: DutyKind,
+        slot: Slot,
+        included: bool,
+    ) {
+        let outcome = {
+            let mut outcomes = self.outcomes.write();
+            let Some(outcome) = outcomes.get_mut(&validator_pubkey).and_then(|outcomes| {
+                outcomes
+                    .iter_mut()
+                    .rev()
+                    .find(|outcome| outcome.duty == duty && outcome.slot == slot)
+            }) else {
+                return;
+            };
+            outcome.included = Some(included);
+            outcome.clone()
+        };
+
+        let _ = self.event_tx.send((validator_pubkey, outcome));
+    }
+
+    /// Returns the recorded outcomes for a validator, from oldest to newest.
+    pub fn outcomes(&self, validator_pubkey: &PublicKeyBytes) -> Vec<DutyOutcome> {
+        self.outcomes
+            .read()
+            .get(validator_pubkey)
+            .map(|outcomes| outcomes.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Subscribe to new and updated outcomes for all validators.
+    pub fn subscribe(&self) -> broadcast::Receiver<(PublicKeyBytes, DutyOutcome)> {
+        self.event_tx.subscribe()
+    }
+
+    /// Returns the published messages from `start_slot` onwards whose inclusion is unknown.
+    fn pending_inclusion(&self, start_slot: Slot) -> Vec<(PublicKeyBytes, DutyOutcome)> {
+        self.outcomes
+            .read()
+            .iter()
+            .flat_map(|(pubkey, outcomes)| {
+                outcomes
+                    .iter()
+                    .filter(|outcome| {
+                        outcome.published
+                            && outcome.included.is_none()
+                            && outcome.slot >= start_slot
+                    })
+                    .map(|outcome| (*pubkey, outcome.clone()))
+            })
+            .collect()
+    }
+
+    /// Write the history to disk, if it has a directory.
+    pub fn persist(&self) -> Result<(), String> {
+        let Some(dir) = &self.dir else {
+            return Ok(());
+        };
+
+        let stored = self
+            .outcomes
+            .read()
+            .iter()
+            .map(|(pubkey, outcomes)| ValidatorDutyHistory {
+                pubkey: *pubkey,
+                outcomes: outcomes.iter().cloned().collect(),
+            })
+            .collect::<Vec<_>>();
+        let bytes = serde_json::to_vec(&stored)
+            .map_err(|e| format!("Unable to encode duty history: {:?}", e))?;
+
+        write_file_via_temporary(
+            &dir.join(DUTY_HISTORY_FILENAME),
+            &dir.join(DUTY_HISTORY_TEMP_FILENAME),
+            &bytes,
+        )
+        .map_err(|e| format!("Unable to write duty history: {:?}", e))
+    }
+
+    /// Query the beacon node for the inclusion of recently published messages.
+    ///
+    /// Blocks and sync committee messages are checked from the epoch after they were published,
+    /// and attestations two epochs after, once their rewards are known.
+    async fn check_inclusion<T: SlotClock + 'static, E: EthSpec>(
+        &self,
+        duties_service: &DutiesService<T, E>,
+        current_epoch: Epoch,
+    ) {
+        let slots_per_epoch = E::slots_per_epoch();
+        let start_slot = current_epoch
+            .saturating_sub(INCLUSION_CHECK_EPOCHS)
+            .start_slot(slots_per_epoch);
+
+        let mut attesters: HashMap<Epoch, Vec<(PublicKeyBytes, Slot, u64)>> = HashMap::new();
+        let mut sync_committee_members: HashMap<Slot, Vec<(PublicKeyBytes, u64)>> = HashMap::new();
+        for (pubkey, outcome) in self.pending_inclusion(start_slot) {
+            let epoch = outcome.slot.epoch(slots_per_epoch);
+            let Some(validator_index) = duties_service.validator_store.validator_index(&pubkey)
+            else {
+                continue;
+            };
+
+            match outcome.duty {
+                DutyKind::BlockProposal if epoch < current_epoch => {
+                    self.check_block_inclusion(duties_service, pubkey, &outcome)
+                        .await
+                }
+                DutyKind::Attestation if epoch + 1 < current_epoch => attesters
+                    .entry(epoch)
+                    .or_default()
+                    .push((pubkey, outcome.slot, validator_index)),
+                DutyKind::SyncCommitteeMessage if epoch < current_epoch => sync_committee_members
+                    .entry(outcome.slot)
+                    .or_default()
+                    .push((pubkey, validator_index)),
+                _ => (),
+            }
+        }
+
+        for (epoch, attesters) in attesters {
+            self.check_attestation_inclusion(duties_service, epoch, attesters)
+                .await;
+        }
+
+        for (slot, members) in sync_committee_members {
+            self.check_sync_committee_message_inclusion(duties_service, slot, members)
+                .await;
+        }
+    }
+
+    /// A block is included if it is the canonical block at its slot.
+    async fn check_block_inclusion<T: SlotClock + 'static, E: EthSpec>(
+        &self,
+        duties_service: &DutiesService<T, E>,
+        validator_pubkey: PublicKeyBytes,
+        outcome: &DutyOutcome,
+    ) {
+        let slot = outcome.slot;
+        let result = duties_service
+            .beacon_nodes
+            .first_success(
+                RequireSynced::Yes,
+                OfflineOnFailure::Yes,
+                |beacon_node| async move {
+                    beacon_node
+                        .get_beacon_blocks_root(BlockId::Slot(slot))
+                        .await
+                },
+            )
+            .await;
+
+        match result {
+            Ok(response) => {
+                let canonical_root = response.map(|response| response.data.root);
+                let included = canonical_root.is_some() && canonical_root == outcome.block_root;
+                self.set_included(validator_pubkey, DutyKind::BlockProposal, slot, included);
+            }
+            Err(e) => debug!(
+                self.log,
+                "Unable to check block inclusion";
+                "error" => %e,
+                "slot" => slot,
+            ),
+        }
+    }
+
+    /// An attestation is included if the validator was not penalised for both its source and
+    /// target votes.
+    async fn check_attestation_inclusion<T: SlotClock + 'static, E: EthSpec>(
+        &self,
+        duties_service: &DutiesService<T, E>,
+        epoch: Epoch,
+        attesters: Vec<(PublicKeyBytes, Slot, u64)>,
+    ) {
+        let validator_ids = attesters
+            .iter()
+            .map(|(_, _, validator_index)| ValidatorId::Index(*validator_index))
+            .collect::<Vec<_>>();
+        let validator_ids = &validator_ids;
+
+        let result = duties_service
+            .beacon_nodes
+            .first_success(
+                RequireSynced::Yes,
+                OfflineOnFailure::Yes,
+                |beacon_node| async move {
+                    beacon_node
+                        .post_beacon_rewards_attestations(epoch, validator_ids)
+                        .await
+                },
+            )
+            .await;
+
+        match result {
+            Ok(response) => {
+                let rewards = response
+                    .data
+                    .total_rewards
+                    .into_iter()
+                    .map(|rewards| (rewards.validator_index, rewards))
+                    .collect::<HashMap<_, _>>();
+                for (pubkey, slot, validator_index) in attesters {
+                    if let Some(rewards) = rewards.get(&validator_index) {
+                        let included = rewards.source >= 0 || rewards.target >= 0;
+                        self.set_included(pubkey, DutyKind::Attestation, slot, included);
+                    }
+                }
+            }
+            Err(e) => debug!(
+                self.log,
+                "Unable to check attestation inclusion";
+                "error" => %e,
+                "epoch" => epoch,
+            ),
+        }
+    }
+
+    /// A sync committee message is included if the validator was rewarded by the block in the
+    /// following slot.
+    async fn check_sync_committee_message_inclusion<T: SlotClock + 'static, E: EthSpec>(
+        &self,
+        duties_service: &DutiesService<T, E>,
+        slot: Slot,
+        members: Vec<(PublicKeyBytes, u64)>,
+    ) {
+        let validator_ids = members
+            .iter()
+            .map(|(_, validator_index)| ValidatorId::Index(*validator_index))
+            .collect::<Vec<_>>();
+        let validator_ids = &validator_ids;
+
+        let result = duties_service
+            .beacon_nodes
+            .first_success(
+                RequireSynced::Yes,
+                OfflineOnFailure::Yes,
+                |beacon_node| async move {
+                    beacon_node
+                        .post_beacon_rewards_sync_committee(BlockId::Slot(slot + 1), validator_ids)
+                        .await
+                },
+            )
+            .await;
+
+        match result {
+            Ok(response) => {
+                // Without a block in the following slot, no sync committee messages were
+                // included.
+                let rewards = response
+                    .map(|response| response.data)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|reward| (reward.validator_index, reward.reward))
+                    .collect::<HashMap<_, _>>();
+                for (pubkey, validator_index) in members {
+                    let included = rewards.get(&validator_index).is_some_and(|r| *r > 0);
+                    self.set_included(pubkey, DutyKind::SyncCommitteeMessage, slot, included);
+                }
+            }
+            Err(e) => debug!(
+                self.log,
+                "Unable to check sync committee message inclusion";
+                "error" => %e,
+                "slot" => slot,
+            ),
+        }
+    }
+}
+
+/// Starts a service which checks the inclusion of published messages and writes the history to
+/// disk once per epoch.
+pub(crate) fn start_update_service<T: SlotClock + 'static, E: EthSpec>(
+    history: Arc<DutyHistory>,
+    context: RuntimeContext<E>,
+    duties_service: Arc<DutiesService<T, E>>,
+) -> Result<(), String> {
+    if history.max_outcomes == 0 {
+        info!(context.log(), "Duty history disabled");
+        return Ok(());
+    }
+
+    let log = context.log().clone();
+    let executor = context.executor.clone();
+    let slot_clock = duties_service.slot_clock.clone();
+    let slot_duration = slot_clock.slot_duration();
+    let slots_per_epoch = E::slots_per_epoch();
+
+    info!(log, "Duty history service started");
+
+    context.executor.spawn(
+        async move {
+            loop {
+                let Some(duration_to_next_epoch) =
+                    slot_clock.duration_to_next_epoch(slots_per_epoch)
+                else {
+                    error!(log, "Failed to read slot clock");
+                    // If we can't read the slot clock, just wait another slot.
+                    sleep(slot_duration).await;
+                    continue;
+                };
+
+                // Avoid contending with the duties performed at the start of the epoch.
+                sleep(duration_to_next_epoch + slot_duration * 3 / 4).await;
+
+                if let Some(slot) = slot_clock.now() {
+                    history
+                        .check_inclusion(&duties_service, slot.epoch(slots_per_epoch))
+                        .await;
+                }
+
+                let history = history.clone();
+                let log = log.clone();
+                executor.spawn_blocking(
+                    move || {
+                        if let Err(e) = history.persist() {
+                            error!(log, "Failed to persist duty history"; "error" => e);
+                        }
+                    },
+                    "duty_history_persist",
+                );
+            }
+        },
+        "duty_history",
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use logging::test_logger;
+    use tempfile::tempdir;
+
+    fn pubkey(byte: u8) -> PublicKeyBytes {
+        let mut bytes = [0; 48];
+        bytes[0] = byte;
+        PublicKeyBytes::deserialize(&bytes).unwrap()
+    }
+
+    fn record_attestations(history: &DutyHistory, validator: PublicKeyBytes, slots: u64) {
+        for slot in 0..slots {
+            history.record_signed(validator, DutyKind::Attestation, Slot::new(slot), Ok(()));
+        }
+    }
+
+    #[test]
+    fn outcomes_are_bounded() {
+        let history = DutyHistory::new(4, test_logger());
+        let validator = pubkey(1);
+        record_attestations(&history, validator, 10);
+
+        let slots = history
+            .outcomes(&validator)
+            .into_iter()
+            .map(|outcome| outcome.slot.as_u64())
+            .collect::<Vec<_>>();
+        assert_eq!(slots, vec![6, 7, 8, 9]);
+        assert!(history.outcomes(&pubkey(2)).is_empty());
+    }
+
+    #[test]
+    fn disabled_history_records_nothing() {
+        let history = DutyHistory::new(0, test_logger());
+        let validator = pubkey(1);
+        record_attestations(&history, validator, 2);
+        assert!(history.outcomes(&validator).is_empty());
+    }
+
+    #[test]
+    fn unsigned_and_unpublished_outcomes() {
+        let history = DutyHistory::new(4, test_logger());
+        let validator = pubkey(1);
+        history.record_unsigned(
+            validator,
+            DutyKind::BlockProposal,
+            Slot::new(1),
+            "unsigned".into(),
+        );
+        history.record_signed(
+            validator,
+            DutyKind::Aggregate,
+            Slot::new(2),
+            Err("unpublished".into()),
+        );
+
+        let outcomes = history.outcomes(&validator);
+        assert!(!outcomes[0].signed && !outcomes[0].published);
+        assert_eq!(outcomes[0].error.as_deref(), Some("unsigned"));
+        assert!(outcomes[1].signed && !outcomes[1].published);
+        assert_eq!(outcomes[1].error.as_deref(), Some("unpublished"));
+        assert!(history.pending_inclusion(Slot::new(0)).is_empty());
+    }
+
+    #[test]
+    fn set_included() {
+        let history = DutyHistory::new(4, test_logger());
+        let validator = pubkey(1);
+        record_attestations(&history, validator, 3);
+        history.record_signed(validator, DutyKind::Aggregate, Slot::new(1), Ok(()));
+        assert_eq!(history.pending_inclusion(Slot::new(1)).len(), 3);
+
+        let mut events = history.subscribe();
+        history.set_included(validator, DutyKind::Attestation, Slot::new(1), true);
+        history.set_included(validator, DutyKind::Attestation, Slot::new(2), false);
+
+        let included = history
+            .outcomes(&validator)
+            .into_iter()
+            .map(|outcome| (outcome.duty, outcome.included))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            included,
+            vec![
+                (DutyKind::Attestation, None),
+                (DutyKind::Attestation, Some(true)),
+                (DutyKind::Attestation, Some(false)),
+                (DutyKind::Aggregate, None),
+            ]
+        );
+
+        let (event_pubkey, event) = events.try_recv().unwrap();
+        assert_eq!(event_pubkey, validator);
+        assert_eq!(event.slot, Slot::new(1));
+        assert_eq!(event.included, Some(true));
+    }
+
+    #[test]
+    fn persist_and_open() {
+        let dir = tempdir().unwrap();
+        let validators = [pubkey(1), pubkey(2)];
+
+        let history = DutyHistory::open(dir.path(), 8, test_logger());
+        for validator in validators {
+            record_attestations(&history, validator, 6);
+        }
+        history.persist().unwrap();
+
+        // Reopening with a smaller size keeps the most recent outcomes.
+        let reopened = DutyHistory::open(dir.path(), 4, test_logger());
+        for validator in validators {
+            assert_eq!(
+                reopened.outcomes(&validator),
+                history.outcomes(&validator)[2..].to_vec()
+            );
+        }
+    }
+
+    #[test]
+    fn open_discards_unreadable_history() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(DUTY_HISTORY_FILENAME), b"not json").unwrap();
+
+        let history = DutyHistory::open(dir.path(), 4, test_logger());
+        assert!(history.outcomes(&pubkey(1)).is_empty());
+        record_attestations(&history, pubkey(1), 1);
+        history.persist().unwrap();
+        assert_eq!(
+            DutyHistory::open(dir.path(), 4, test_logger())
+                .outcomes(&pubkey(1))
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,74 @@
+//! Publishes Lighthouse-specific events to subscribers of `GET lighthouse/events`.
+
+pub use eth2::lighthouse_vc::types::{EventKind, EventTopic};
+use slog::{trace, Logger};
+use tokio::sync::broadcast::{self, error::SendError, Receiver, Sender};
+
+/// The number of events which can be buffered for slow subscribers to each topic.
+///
+/// Signatures are published for every validator in each slot, so this is larger than the capacity
+/// used by the beacon node.
+const DEFAULT_CHANNEL_CAPACITY: usize = 1024;
+
+pub struct ServerSentEventHandler {
+    duty_scheduled_tx: Sender<EventKind>,
+    signature_tx: Sender<EventKind>,
+    block_produced_tx: Sender<EventKind>,
+    block_published_tx: Sender<EventKind>,
+    doppelganger_tx: Sender<EventKind>,
+    beacon_node_fallback_tx: Sender<EventKind>,
+    log: Logger,
+}
+
+impl ServerSentEventHandler {
+    pub fn new(log: Logger) -> Self {
+        Self::new_with_capacity(log, DEFAULT_CHANNEL_CAPACITY)
+    }
+
+    pub fn new_with_capacity(log: Logger, capacity: usize) -> Self {
+        let (duty_scheduled_tx, _) = broadcast::channel(capacity);
+        let (signature_tx, _) = broadcast::channel(capacity);
+        let (block_produced_tx, _) = broadcast::channel(capacity);
+        let (block_published_tx, _) = broadcast::channel(capacity);
+        let (doppelganger_tx, _) = broadcast::channel(capacity);
+        let (beacon_node_fallback_tx, _) = broadcast::channel(capacity);
+
+        Self {
+            duty_scheduled_tx,
+            signature_tx,
+            block_produced_tx,
+            block_published_tx,
+            doppelganger_tx,
+            beacon_node_fallback_tx,
+            log,
+        }
+    }
+
+    /// Publish `kind` to the subscribers of its topic.
+    pub fn register(&self, kind: EventKind) {
+        let topic = kind.topic();
+        if let Err(SendError(event)) = self.sender(topic).send(kind) {
+            trace!(
+                self.log,
+                "No receivers registered to listen for event";
+                "topic" => %topic,
+                "event" => ?event,
+            );
+        }
+    }
+
+    pub fn subscribe(&self, topic: EventTopic) -> Receiver<EventKind> {
+        self.sender(topic).subscribe()
+    }
+
+    fn sender(&self, topic: EventTopic) -> &Sender<EventKind> {
+        match topic {
+            EventTopic::DutyScheduled => &self.duty_scheduled_tx,
+            EventTopic::Signature => &self.signature_tx,
+            EventTopic::BlockProduced => &self.block_produced_tx,
+            EventTopic::BlockPublished => &self.block_published_tx,
+            EventTopic::Doppelganger => &self.doppelganger_tx,
+            EventTopic::BeaconNodeFallback => &self.beacon_node_fallback_tx,
+        }
+    }
+}
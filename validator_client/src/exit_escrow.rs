@@ -0,0 +1,663 @@
+//! Stores pre-signed voluntary exits until they are handed out to the owners of the validators.
+//!
+//! Exits are signed for a chosen epoch and encrypted to the recipient's secp256k1 public key as
+//! soon as they are signed, so the escrow written to the validator directory never holds a
+//! plaintext exit. Each exit is exported as the bundle it was stored as, and each export is
+//! recorded against the exit. The domain of each exit is stored alongside it so that an exit which
+//! the network would no longer accept (e.g., after a change to the fork schedule) can be detected,
+//! and is checked again before every export.
+//!
+//! The escrow file is still kept readable only by the owner of the validator client, since it
+//! records which validators have exits and to whom they were handed out.
+
+use account_utils::write_file_via_temporary;
+use eth2::lighthouse_vc::types::{EncryptedExitBundle, EscrowedExitData, ExitHandOut};
+use filesystem::restrict_file_permissions;
+use itertools::Itertools;
+use parking_lot::RwLock;
+use rand::{thread_rng, RngCore};
+use ring::{aead, hkdf};
+use serde::{Deserialize, Serialize};
+use slog::{info, warn, Logger};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use types::{Epoch, Hash256, PublicKeyBytes, SignedVoluntaryExit};
+
+/// The file in the validator directory which stores the escrowed exits.
+pub const EXIT_ESCROW_FILENAME: &str = "exit_escrow.json";
+const EXIT_ESCROW_TEMP_FILENAME: &str = ".exit_escrow.json.tmp";
+
+/// Binds keys derived for exit bundles to this purpose.
+const HKDF_INFO: &[u8] = b"lighthouse-exit-escrow-v1";
+
+/// An escrowed exit, as stored on disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EscrowedExit {
+    pubkey: PublicKeyBytes,
+    validator_index: u64,
+    epoch: Epoch,
+    /// The domain over which the exit was signed.
+    domain: Hash256,
+    /// The time the exit was signed, in seconds since the UNIX epoch.
+    created: u64,
+    /// The signed exit, encrypted to `bundle.recipient`.
+    bundle: EncryptedExitBundle,
+    handed_out: Vec<ExitHandOut>,
+}
+
+impl EscrowedExit {
+    /// Whether the exit was signed over the domain given by `current_domain` for its epoch.
+    fn is_valid(&self, current_domain: impl Fn(Epoch) -> Hash256) -> bool {
+        self.domain == current_domain(self.epoch)
+    }
+
+    fn data(&self, valid: bool) -> EscrowedExitData {
+        EscrowedExitData {
+            validator_pubkey: self.pubkey,
+            validator_index: self.validator_index,
+            epoch: self.epoch,
+            recipient: self.bundle.recipient.clone(),
+            created: self.created,
+            handed_out: self.handed_out.clone(),
+            valid,
+        }
+    }
+}
+
+pub struct ExitEscrow {
+    exits: RwLock<HashMap<PublicKeyBytes, EscrowedExit>>,
+    /// The directory to which the exits are written, if any.
+    dir: Option<PathBuf>,
+    log: Logger,
+}
+
+impl ExitEscrow {
+    /// Create an empty escrow which is never written to disk.
+    pub fn new(log: Logger) -> Self {
+        Self {
+            exits: <_>::default(),
+            dir: None,
+            log,
+        }
+    }
+
+    /// Load the exits stored in `dir`, starting an empty escrow if there are none.
+    ///
+    /// Unlike the duty history, an unreadable escrow is an error since it may hold the only record
+    /// of which exits have been handed out.
+    pub fn open(dir: &Path, log: Logger) -> Result<Self, String> {
+        let mut escrow = Self::new(log);
+        escrow.dir = Some(dir.to_path_buf());
+
+        let path = dir.join(EXIT_ESCROW_FILENAME);
+        if path.exists() {
+            // The file is created with restricted permissions, but they are preserved when it is
+            // rewritten, so restrict them in case the file was copied in or modified by hand.
+            restrict_file_permissions(&path)
+                .map_err(|e| format!("Unable to restrict permissions of {:?}: {:?}", path, e))?;
+            let bytes = fs::read(&path)
+                .map_err(|e| format!("Unable to read exit escrow {:?}: {:?}", path, e))?;
+            let exits: Vec<EscrowedExit> = serde_json::from_slice(&bytes)
+                .map_err(|e| format!("Unable to parse exit escrow {:?}: {:?}", path, e))?;
+            *escrow.exits.get_mut() = exits.into_iter().map(|exit| (exit.pubkey, exit)).collect();
+        }
+
+        Ok(escrow)
+    }
+
+    /// Encrypt `signed_exits` to `recipient` and store them, replacing any exits already escrowed
+    /// for the same validators.
+    ///
+    /// Each exit is given with the domain over which it was signed.
+    pub fn insert(
+        &self,
+        recipient: &[u8],
+        signed_exits: Vec<(PublicKeyBytes, SignedVoluntaryExit, Hash256)>,
+    ) -> Result<Vec<EscrowedExitData>, EscrowError> {
+        let recipient = parse_recipient(recipient)?;
+        let created = timestamp_now();
+
+        // Encrypt every exit before any is stored, so that a failure leaves the escrow unchanged.
+        let encrypted = signed_exits
+            .into_iter()
+            .map(|(pubkey, signed_exit, domain)| {
+                let plaintext = serde_json::to_vec(&[&signed_exit]).map_err(|e| {
+                    EscrowError::Encryption(format!("Unable to encode exit: {:?}", e))
+                })?;
+                Ok(EscrowedExit {
+                    pubkey,
+                    validator_index: signed_exit.message.validator_index,
+                    epoch: signed_exit.message.epoch,
+                    domain,
+                    created,
+                    bundle: encrypt_bundle(&recipient, plaintext)?,
+                    handed_out: vec![],
+                })
+            })
+            .collect::<Result<Vec<_>, EscrowError>>()?;
+
+        // Only replace the stored exits once the new ones have been written to disk.
+        let mut exits = self.exits.write();
+        let mut updated = exits.clone();
+        let mut inserted = Vec::with_capacity(encrypted.len());
+
+        for exit in encrypted {
+            let pubkey = exit.pubkey;
+            inserted.push(exit.data(true));
+
+            if let Some(previous) = updated.insert(pubkey, exit) {
+                if !previous.handed_out.is_empty() {
+                    warn!(
+                        self.log,
+                        "Replaced an escrowed exit which was handed out";
+                        "msg" => "the previous exit remains valid and may still be published",
+                        "validator" => ?pubkey,
+                        "previous_epoch" => previous.epoch,
+                    );
+                }
+            }
+        }
+
+        self.persist(&updated).map_err(EscrowError::Persist)?;
+        *exits = updated;
+        Ok(inserted)
+    }
+
+    /// Return the escrowed exits, ordered by validator index.
+    ///
+    /// An exit is valid if it was signed over the domain given by `current_domain` for its epoch.
+    pub fn exits(&self, current_domain: impl Fn(Epoch) -> Hash256) -> Vec<EscrowedExitData> {
+        let mut exits = self
+            .exits
+            .read()
+            .values()
+            .map(|exit| exit.data(exit.is_valid(&current_domain)))
+            .collect::<Vec<_>>();
+        exits.sort_unstable_by_key(|exit| exit.validator_index);
+        exits
+    }
+
+    /// Export the exits of `pubkeys` (or all exits escrowed to `recipient` if `None`), recording
+    /// that they have been handed out. Each exit is returned once, in the bundle it was encrypted
+    /// to when it was escrowed, even if its validator appears more than once in `pubkeys`.
+    ///
+    /// No exit is exported unless all of them are escrowed to `recipient` and still signed over
+    /// the domain given by `current_domain` for their epoch.
+    pub fn export(
+        &self,
+        pubkeys: Option<&[PublicKeyBytes]>,
+        recipient: &[u8],
+        current_domain: impl Fn(Epoch) -> Hash256,
+    ) -> Result<Vec<EncryptedExitBundle>, EscrowError> {
+        let recipient = parse_recipient(recipient)?;
+        let mut exits = self.exits.write();
+
+        let selected = match pubkeys {
+            Some(pubkeys) => pubkeys
+                .iter()
+                .unique()
+                .map(|pubkey| {
+                    let exit = exits.get(pubkey).ok_or(EscrowError::NotEscrowed(*pubkey))?;
+                    if exit.bundle.recipient != recipient {
+                        return Err(EscrowError::WrongRecipient(*pubkey));
+                    }
+                    Ok(exit)
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            None => {
+                let mut selected = exits
+                    .values()
+                    .filter(|exit| exit.bundle.recipient == recipient)
+                    .collect::<Vec<_>>();
+                selected.sort_unstable_by_key(|exit| exit.validator_index);
+                selected
+            }
+        };
+        if selected.is_empty() {
+            return Err(EscrowError::NoExits);
+        }
+        if let Some(exit) = selected.iter().find(|exit| !exit.is_valid(&current_domain)) {
+            return Err(EscrowError::Invalid(exit.pubkey));
+        }
+
+        let bundles = selected
+            .iter()
+            .map(|exit| exit.bundle.clone())
+            .collect::<Vec<_>>();
+        let selected = selected.iter().map(|exit| exit.pubkey).collect::<Vec<_>>();
+
+        // Only record the hand out once it has been written to disk.
+        let hand_out = ExitHandOut {
+            recipient: recipient.clone(),
+            timestamp: timestamp_now(),
+        };
+        let mut updated = exits.clone();
+        for pubkey in &selected {
+            if let Some(exit) = updated.get_mut(pubkey) {
+                exit.handed_out.push(hand_out.clone());
+            }
+        }
+        self.persist(&updated).map_err(EscrowError::Persist)?;
+        *exits = updated;
+
+        info!(
+            self.log,
+            "Exported escrowed exits";
+            "count" => selected.len(),
+            "recipient" => hex::encode(&recipient),
+        );
+
+        Ok(bundles)
+    }
+
+    /// Log a warning for each exit which was not signed over the domain given by `current_domain`.
+    ///
+    /// The domain only changes if the validator client is restarted with a different fork
+    /// schedule, so it is sufficient to warn when the escrow is opened. Exports check the validity
+    /// of each exit again regardless.
+    pub fn check_validity(&self, current_domain: impl Fn(Epoch) -> Hash256) {
+        for exit in self.exits.read().values() {
+            if exit.is_valid(&current_domain) {
+                continue;
+            }
+
+            if exit.handed_out.is_empty() {
+                warn!(
+                    self.log,
+                    "Escrowed exit is invalid";
+                    "msg" => "the exit must be signed again before it is handed out",
+                    "validator" => ?exit.pubkey,
+                    "epoch" => exit.epoch,
+                );
+            } else {
+                warn!(
+                    self.log,
+                    "Handed out exit is invalid";
+                    "msg" => "the exit must be signed again and handed out to its recipients",
+                    "validator" => ?exit.pubkey,
+                    "epoch" => exit.epoch,
+                    "recipients" => exit.handed_out.len(),
+                );
+            }
+        }
+    }
+
+    fn persist(&self, exits: &HashMap<PublicKeyBytes, EscrowedExit>) -> Result<(), String> {
+        let Some(dir) = &self.dir else {
+            return Ok(());
+        };
+
+        let stored = exits.values().collect::<Vec<_>>();
+        let bytes = serde_json::to_vec(&stored)
+            .map_err(|e| format!("Unable to encode exit escrow: {:?}", e))?;
+
+        write_file_via_temporary(
+            &dir.join(EXIT_ESCROW_FILENAME),
+            &dir.join(EXIT_ESCROW_TEMP_FILENAME),
+            &bytes,
+        )
+        .map_err(|e| format!("Unable to write exit escrow: {:?}", e))
+    }
+}
+
+#[derive(Debug)]
+pub enum EscrowError {
+    /// The validator has no escrowed exit.
+    NotEscrowed(PublicKeyBytes),
+    /// There are no escrowed exits to export.
+    NoExits,
+    /// The recipient is not a valid secp256k1 public key.
+    InvalidRecipient(String),
+    /// The validator's exit is escrowed to a different recipient.
+    WrongRecipient(PublicKeyBytes),
+    /// The validator's exit is no longer signed over the current domain.
+    Invalid(PublicKeyBytes),
+    Encryption(String),
+    Persist(String),
+}
+
+/// Parse the compressed or uncompressed secp256k1 public key `recipient`, returning it in
+/// compressed form so that each recipient has a single encoding.
+pub fn parse_recipient(recipient: &[u8]) -> Result<Vec<u8>, EscrowError> {
+    libsecp256k1::PublicKey::parse_slice(recipient, None)
+        .map(|pubkey| pubkey.serialize_compressed().to_vec())
+        .map_err(|e| EscrowError::InvalidRecipient(format!("{:?}", e)))
+}
+
+/// Encrypt `plaintext` to the compressed or uncompressed secp256k1 public key `recipient`.
+pub fn encrypt_bundle(
+    recipient: &[u8],
+    mut plaintext: Vec<u8>,
+) -> Result<EncryptedExitBundle, EscrowError> {
+    let recipient_pubkey = libsecp256k1::PublicKey::parse_slice(recipient, None)
+        .map_err(|e| EscrowError::InvalidRecipient(format!("{:?}", e)))?;
+
+    let ephemeral_secret = libsecp256k1::SecretKey::random(&mut thread_rng());
+    let ephemeral_pubkey =
+        libsecp256k1::PublicKey::from_secret_key(&ephemeral_secret).serialize_compressed();
+
+    let key = bundle_key(&recipient_pubkey, &ephemeral_secret, &ephemeral_pubkey)
+        .map_err(EscrowError::Encryption)?;
+    let mut nonce = [0; aead::NONCE_LEN];
+    thread_rng().fill_bytes(&mut nonce);
+    key.seal_in_place_append_tag(
+        aead::Nonce::assume_unique_for_key(nonce),
+        aead::Aad::from(recipient),
+        &mut plaintext,
+    )
+    .map_err(|_| EscrowError::Encryption("Unable to encrypt exits".to_string()))?;
+
+    Ok(EncryptedExitBundle {
+        recipient: recipient.to_vec(),
+        ephemeral_pubkey: ephemeral_pubkey.to_vec(),
+        nonce: nonce.to_vec(),
+        ciphertext: plaintext,
+    })
+}
+
+/// Decrypt the exits in `bundle` with the recipient's `secret_key`.
+pub fn decrypt_bundle(
+    bundle: &EncryptedExitBundle,
+    secret_key: &libsecp256k1::SecretKey,
+) -> Result<Vec<SignedVoluntaryExit>, String> {
+    let ephemeral_pubkey = libsecp256k1::PublicKey::parse_slice(&bundle.ephemeral_pubkey, None)
+        .map_err(|e| format!("Invalid ephemeral public key: {:?}", e))?;
+    let nonce = aead::Nonce::try_assume_unique_for_key(&bundle.nonce)
+        .map_err(|_| "Invalid nonce".to_string())?;
+
+    let key = bundle_key(&ephemeral_pubkey, secret_key, &bundle.ephemeral_pubkey)?;
+    let mut ciphertext = bundle.ciphertext.clone();
+    let plaintext = key
+        .open_in_place(nonce, aead::Aad::from(&bundle.recipient), &mut ciphertext)
+        .map_err(|_| "Unable to decrypt exits".to_string())?;
+
+    serde_json::from_slice(plaintext).map_err(|e| format!("Unable to parse exits: {:?}", e))
+}
+
+/// Derive the AES-256-GCM key shared between the holders of `secret_key` and the secret key of
+/// `public_key`.
+fn bundle_key(
+    public_key: &libsecp256k1::PublicKey,
+    secret_key: &libsecp256k1::SecretKey,
+    ephemeral_pubkey: &[u8],
+) -> Result<aead::LessSafeKey, String> {
+    let mut shared_point = *public_key;
+    shared_point
+        .tweak_mul_assign(secret_key)
+        .map_err(|e| format!("Unable to derive shared secret: {:?}", e))?;
+
+    let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, ephemeral_pubkey)
+        .extract(&shared_point.serialize_compressed());
+    let info = [HKDF_INFO];
+    let key = prk
+        .expand(&info, &aead::AES_256_GCM)
+        .map_err(|_| "Unable to derive key".to_string())?;
+
+    Ok(aead::LessSafeKey::new(key.into()))
+}
+
+fn timestamp_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use logging::test_logger;
+    use types::{Signature, VoluntaryExit};
+
+    fn signed_exit(validator_index: u64, epoch: u64) -> SignedVoluntaryExit {
+        SignedVoluntaryExit {
+            message: VoluntaryExit {
+                epoch: Epoch::new(epoch),
+                validator_index,
+            },
+            signature: Signature::empty(),
+        }
+    }
+
+    fn recipient() -> (libsecp256k1::SecretKey, Vec<u8>) {
+        let secret_key = libsecp256k1::SecretKey::random(&mut thread_rng());
+        let public_key = libsecp256k1::PublicKey::from_secret_key(&secret_key);
+        (secret_key, public_key.serialize_compressed().to_vec())
+    }
+
+    fn escrow_with_exits(dir: &Path, recipient: &[u8]) -> ExitEscrow {
+        let escrow = ExitEscrow::open(dir, test_logger()).unwrap();
+        escrow
+            .insert(
+                recipient,
+                vec![
+                    (
+                        PublicKeyBytes::empty(),
+                        signed_exit(1, 256),
+                        Hash256::zero(),
+                    ),
+                    (
+                        PublicKeyBytes::deserialize(&[1; 48]).unwrap(),
+                        signed_exit(0, 256),
+                        Hash256::repeat_byte(1),
+                    ),
+                ],
+            )
+            .unwrap();
+        escrow
+    }
+
+    #[test]
+    fn bundle_round_trip() {
+        let (secret_key, recipient) = recipient();
+        let exits = vec![signed_exit(0, 10), signed_exit(1, 10)];
+
+        let bundle = encrypt_bundle(&recipient, serde_json::to_vec(&exits).unwrap()).unwrap();
+        assert_eq!(decrypt_bundle(&bundle, &secret_key).unwrap(), exits);
+
+        let (other_secret_key, _) = self::recipient();
+        assert!(decrypt_bundle(&bundle, &other_secret_key).is_err());
+    }
+
+    #[test]
+    fn invalid_recipient() {
+        assert!(matches!(
+            encrypt_bundle(&[0; 33], vec![]),
+            Err(EscrowError::InvalidRecipient(_))
+        ));
+        assert!(matches!(
+            ExitEscrow::new(test_logger()).insert(
+                &[0; 33],
+                vec![(PublicKeyBytes::empty(), signed_exit(0, 0), Hash256::zero())]
+            ),
+            Err(EscrowError::InvalidRecipient(_))
+        ));
+    }
+
+    #[test]
+    fn export_records_hand_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let (secret_key, recipient) = recipient();
+        let escrow = escrow_with_exits(dir.path(), &recipient);
+
+        let pubkey = PublicKeyBytes::empty();
+        let bundles = escrow
+            .export(Some(&[pubkey]), &recipient, |_| Hash256::zero())
+            .unwrap();
+        assert_eq!(bundles.len(), 1);
+        assert_eq!(
+            decrypt_bundle(&bundles[0], &secret_key).unwrap(),
+            vec![signed_exit(1, 256)]
+        );
+
+        // The hand out is persisted.
+        let escrow = ExitEscrow::open(dir.path(), test_logger()).unwrap();
+        let exits = escrow.exits(|_| Hash256::zero());
+        assert_eq!(exits.len(), 2);
+        assert_eq!(exits[0].validator_index, 0);
+        assert!(exits[0].handed_out.is_empty());
+        assert!(!exits[0].valid);
+        assert_eq!(exits[1].validator_pubkey, pubkey);
+        assert_eq!(exits[1].recipient, recipient);
+        assert_eq!(exits[1].handed_out.len(), 1);
+        assert_eq!(exits[1].handed_out[0].recipient, recipient);
+        assert!(exits[1].valid);
+    }
+
+    #[test]
+    fn export_duplicate_pubkeys() {
+        let dir = tempfile::tempdir().unwrap();
+        let (secret_key, recipient) = recipient();
+        let escrow = escrow_with_exits(dir.path(), &recipient);
+
+        let pubkey = PublicKeyBytes::empty();
+        let bundles = escrow
+            .export(Some(&[pubkey, pubkey]), &recipient, |_| Hash256::zero())
+            .unwrap();
+        assert_eq!(bundles.len(), 1);
+        assert_eq!(
+            decrypt_bundle(&bundles[0], &secret_key).unwrap(),
+            vec![signed_exit(1, 256)]
+        );
+
+        // The hand out is recorded once.
+        let escrow = ExitEscrow::open(dir.path(), test_logger()).unwrap();
+        let exits = escrow.exits(|_| Hash256::zero());
+        assert_eq!(exits[1].validator_pubkey, pubkey);
+        assert_eq!(exits[1].handed_out.len(), 1);
+    }
+
+    #[test]
+    fn failed_write_leaves_escrow_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_, recipient) = recipient();
+        let escrow = escrow_with_exits(dir.path(), &recipient);
+        let before = escrow.exits(|_| Hash256::zero());
+
+        // Remove the directory so that the escrow can no longer be written.
+        fs::remove_dir_all(dir.path()).unwrap();
+
+        let pubkey = PublicKeyBytes::empty();
+        assert!(matches!(
+            escrow.insert(
+                &recipient,
+                vec![(pubkey, signed_exit(1, 512), Hash256::zero())]
+            ),
+            Err(EscrowError::Persist(_))
+        ));
+        assert!(matches!(
+            escrow.export(Some(&[pubkey]), &recipient, |_| Hash256::zero()),
+            Err(EscrowError::Persist(_))
+        ));
+
+        let after = escrow.exits(|_| Hash256::zero());
+        assert_eq!(after.len(), before.len());
+        assert_eq!(after[1].epoch, Epoch::new(256));
+        assert!(after.iter().all(|exit| exit.handed_out.is_empty()));
+    }
+
+    #[test]
+    fn escrow_file_is_encrypted() {
+        let dir = tempfile::tempdir().unwrap();
+        let (secret_key, recipient) = recipient();
+        escrow_with_exits(dir.path(), &recipient);
+
+        let contents = fs::read_to_string(dir.path().join(EXIT_ESCROW_FILENAME)).unwrap();
+        assert!(!contents.contains("signature"));
+
+        let stored: Vec<EscrowedExit> = serde_json::from_str(&contents).unwrap();
+        for exit in stored {
+            let signed_exits = decrypt_bundle(&exit.bundle, &secret_key).unwrap();
+            assert_eq!(
+                signed_exits,
+                vec![signed_exit(exit.validator_index, exit.epoch.as_u64())]
+            );
+        }
+    }
+
+    #[test]
+    fn export_rechecks_validity() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_, recipient) = recipient();
+        let escrow = escrow_with_exits(dir.path(), &recipient);
+
+        let invalid = PublicKeyBytes::deserialize(&[1; 48]).unwrap();
+        assert!(matches!(
+            escrow.export(Some(&[invalid]), &recipient, |_| Hash256::zero()),
+            Err(EscrowError::Invalid(pubkey)) if pubkey == invalid
+        ));
+        assert!(matches!(
+            escrow.export(None, &recipient, |_| Hash256::zero()),
+            Err(EscrowError::Invalid(pubkey)) if pubkey == invalid
+        ));
+
+        // A change of domain invalidates an exit which was valid when it was escrowed.
+        assert!(matches!(
+            escrow.export(Some(&[PublicKeyBytes::empty()]), &recipient, |_| {
+                Hash256::repeat_byte(2)
+            }),
+            Err(EscrowError::Invalid(_))
+        ));
+
+        // Nothing was handed out.
+        assert!(escrow
+            .exits(|_| Hash256::zero())
+            .iter()
+            .all(|exit| exit.handed_out.is_empty()));
+    }
+
+    #[test]
+    fn export_to_other_recipient() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_, recipient) = recipient();
+        let escrow = escrow_with_exits(dir.path(), &recipient);
+        let (_, other_recipient) = self::recipient();
+
+        let pubkey = PublicKeyBytes::empty();
+        assert!(matches!(
+            escrow.export(Some(&[pubkey]), &other_recipient, |_| Hash256::zero()),
+            Err(EscrowError::WrongRecipient(wrong)) if wrong == pubkey
+        ));
+        assert!(matches!(
+            escrow.export(None, &other_recipient, |_| Hash256::zero()),
+            Err(EscrowError::NoExits)
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn escrow_file_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(EXIT_ESCROW_FILENAME);
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+
+        escrow_with_exits(dir.path(), &recipient().1);
+        assert_eq!(mode(&path), 0o600);
+
+        // Permissions which were loosened are restricted again when the escrow is opened.
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        ExitEscrow::open(dir.path(), test_logger()).unwrap();
+        assert_eq!(mode(&path), 0o600);
+    }
+
+    #[test]
+    fn export_unknown_validator() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_, recipient) = recipient();
+        let escrow = escrow_with_exits(dir.path(), &recipient);
+
+        let unknown = PublicKeyBytes::deserialize(&[2; 48]).unwrap();
+        assert!(matches!(
+            escrow.export(Some(&[unknown]), &recipient, |_| Hash256::zero()),
+            Err(EscrowError::NotEscrowed(pubkey)) if pubkey == unknown
+        ));
+        assert!(matches!(
+            ExitEscrow::new(test_logger()).export(None, &recipient, |_| Hash256::zero()),
+            Err(EscrowError::NoExits)
+        ));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::exit_escrow::{parse_recipient, EscrowError, ExitEscrow};
+use crate::validator_store::{DoppelgangerStatus, ValidatorStore};
+use eth2::lighthouse_vc::types::{
+    EncryptedExitBundle, EscrowedExitData, ExitEscrowExportRequest, ExitEscrowRequest,
+};
+use slog::{info, warn, Logger};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use types::{EthSpec, VoluntaryExit};
+
+/// Sign exits for the requested validators and store them in the escrow.
+pub async fn escrow_exits<T: 'static + SlotClock + Clone, E: EthSpec>(
+    request: ExitEscrowRequest,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    exit_escrow: Arc<ExitEscrow>,
+    slot_clock: T,
+    log: Logger,
+) -> Result<Vec<EscrowedExitData>, warp::Rejection> {
+    let current_epoch = slot_clock
+        .now()
+        .map(|slot| slot.epoch(E::slots_per_epoch()))
+        .ok_or_else(|| {
+            warp_utils::reject::custom_server_error("Unable to determine current epoch".to_string())
+        })?;
+    if request.epoch < current_epoch {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "exit epoch {} is prior to the current epoch {}",
+            request.epoch, current_epoch
+        )));
+    }
+    // Check the recipient before signing anything, the exits are encrypted to it once signed.
+    let recipient = parse_recipient(&request.recipient).map_err(escrow_error_rejection)?;
+
+    // Validators are only skipped when escrowing exits for all validators, an explicitly requested
+    // validator which cannot be exited is an error.
+    let (pubkeys, skip_unknown) = match request.pubkeys {
+        Some(pubkeys) => (pubkeys, false),
+        None => (
+            validator_store.voting_pubkeys(DoppelgangerStatus::ignored),
+            true,
+        ),
+    };
+
+    let mut signed_exits = Vec::with_capacity(pubkeys.len());
+    for pubkey in pubkeys {
+        if !validator_store.has_validator(&pubkey) {
+            return Err(warp_utils::reject::custom_not_found(format!(
+                "{} is disabled or not managed by this validator client",
+                pubkey.as_hex_string()
+            )));
+        }
+
+        let Some(validator_index) = validator_store.validator_index(&pubkey) else {
+            if skip_unknown {
+                warn!(
+                    log,
+                    "Not escrowing exit for validator without index";
+                    "validator" => pubkey.as_hex_string(),
+                );
+                continue;
+            }
+            return Err(warp_utils::reject::custom_not_found(format!(
+                "The validator index for {} is not known. The validator client \
+                may still be initializing or the validator has not yet had a \
+                deposit processed.",
+                pubkey.as_hex_string()
+            )));
+        };
+
+        let voluntary_exit = VoluntaryExit {
+            epoch: request.epoch,
+            validator_index,
+        };
+        let signed_exit = validator_store
+            .sign_voluntary_exit(pubkey, voluntary_exit)
+            .await
+            .map_err(|e| {
+                warp_utils::reject::custom_server_error(format!(
+                    "Failed to sign voluntary exit for {}: {:?}",
+                    pubkey.as_hex_string(),
+                    e
+                ))
+            })?;
+        signed_exits.push((
+            pubkey,
+            signed_exit,
+            validator_store.voluntary_exit_domain(request.epoch),
+        ));
+    }
+
+    info!(
+        log,
+        "Escrowing voluntary exits";
+        "count" => signed_exits.len(),
+        "epoch" => request.epoch,
+    );
+
+    exit_escrow
+        .insert(&recipient, signed_exits)
+        .map_err(escrow_error_rejection)
+}
+
+/// Export the requested exits, checking that each is still valid.
+pub fn export_exits<T: 'static + SlotClock + Clone, E: EthSpec>(
+    request: ExitEscrowExportRequest,
+    exit_escrow: &ExitEscrow,
+    validator_store: &ValidatorStore<T, E>,
+) -> Result<Vec<EncryptedExitBundle>, warp::Rejection> {
+    exit_escrow
+        .export(request.pubkeys.as_deref(), &request.recipient, |epoch| {
+            validator_store.voluntary_exit_domain(epoch)
+        })
+        .map_err(escrow_error_rejection)
+}
+
+fn escrow_error_rejection(e: EscrowError) -> warp::Rejection {
+    match e {
+        EscrowError::NotEscrowed(pubkey) => warp_utils::reject::custom_not_found(format!(
+            "no exit is escrowed for {}",
+            pubkey.as_hex_string()
+        )),
+        EscrowError::NoExits => warp_utils::reject::custom_not_found(
+            "no exits are escrowed to the recipient".to_string(),
+        ),
+        EscrowError::InvalidRecipient(e) => {
+            warp_utils::reject::custom_bad_request(format!("invalid recipient public key: {}", e))
+        }
+        EscrowError::WrongRecipient(pubkey) => warp_utils::reject::custom_bad_request(format!(
+            "the exit for {} is escrowed to a different recipient",
+            pubkey.as_hex_string()
+        )),
+        EscrowError::Invalid(pubkey) => warp_utils::reject::custom_bad_request(format!(
+            "the exit for {} is no longer valid and must be escrowed again",
+            pubkey.as_hex_string()
+        )),
+        EscrowError::Encryption(e) | EscrowError::Persist(e) => {
+            warp_utils::reject::custom_server_error(e)
+        }
+    }
+}
@@ -1,6 +1,7 @@
 mod api_secret;
 mod create_signed_voluntary_exit;
 mod create_validator;
+mod exit_escrow;
 mod graffiti;
 mod keystores;
 mod remotekeys;
@@ -10,7 +11,12 @@ pub mod test_utils;
 
 use crate::http_api::graffiti::{delete_graffiti, get_graffiti, set_graffiti};
 
+use crate::beacon_node_fallback::BeaconNodeFallback;
+use crate::duty_history::DutyHistory;
+use crate::exit_escrow::ExitEscrow;
 use crate::http_api::create_signed_voluntary_exit::create_signed_voluntary_exit;
+use crate::http_api::exit_escrow::{escrow_exits, export_exits};
+use crate::signing_method::SigningMethod;
 use crate::{determine_graffiti, GraffitiFile, ValidatorStore};
 use account_utils::{
     mnemonic_from_phrase,
@@ -42,11 +48,14 @@ use std::sync::Arc;
 use sysinfo::{System, SystemExt};
 use system_health::observe_system_health_vc;
 use task_executor::TaskExecutor;
-use tokio_stream::{wrappers::BroadcastStream, StreamExt};
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
+    StreamExt,
+};
 use types::{ChainSpec, ConfigAndPreset, EthSpec};
 use validator_dir::Builder as ValidatorDirBuilder;
 use warp::{sse::Event, Filter};
-use warp_utils::task::blocking_json_task;
+use warp_utils::{query::multi_key_query, task::blocking_json_task};
 
 #[derive(Debug)]
 pub enum Error {
@@ -73,6 +82,9 @@ pub struct Context<T: SlotClock, E: EthSpec> {
     pub task_executor: TaskExecutor,
     pub api_secret: ApiSecret,
     pub validator_store: Option<Arc<ValidatorStore<T, E>>>,
+    pub beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
+    pub duty_history: Option<Arc<DutyHistory>>,
+    pub exit_escrow: Option<Arc<ExitEscrow>>,
     pub validator_dir: Option<PathBuf>,
     pub secrets_dir: Option<PathBuf>,
     pub graffiti_file: Option<GraffitiFile>,
@@ -180,6 +192,35 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    let inner_duty_history = ctx.duty_history.clone();
+    let duty_history_filter = warp::any()
+        .map(move || inner_duty_history.clone())
+        .and_then(|duty_history: Option<_>| async move {
+            duty_history.ok_or_else(|| {
+                warp_utils::reject::custom_not_found("duty history is not initialized.".to_string())
+            })
+        });
+
+    let inner_exit_escrow = ctx.exit_escrow.clone();
+    let exit_escrow_filter = warp::any().map(move || inner_exit_escrow.clone()).and_then(
+        |exit_escrow: Option<_>| async move {
+            exit_escrow.ok_or_else(|| {
+                warp_utils::reject::custom_not_found("exit escrow is not initialized.".to_string())
+            })
+        },
+    );
+
+    let inner_beacon_nodes = ctx.beacon_nodes.clone();
+    let beacon_nodes_filter = warp::any()
+        .map(move || inner_beacon_nodes.clone())
+        .and_then(|beacon_nodes: Option<_>| async move {
+            beacon_nodes.ok_or_else(|| {
+                warp_utils::reject::custom_not_found(
+                    "beacon nodes are not initialized.".to_string(),
+                )
+            })
+        });
+
     let inner_task_executor = ctx.task_executor.clone();
     let task_executor_filter = warp::any().map(move || inner_task_executor.clone());
 
@@ -299,15 +340,21 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
         .and(validator_store_filter.clone())
         .then(|validator_store: Arc<ValidatorStore<T, E>>| {
             blocking_json_task(move || {
-                let validators = validator_store
-                    .initialized_validators()
-                    .read()
+                let initialized_validators_rw_lock = validator_store.initialized_validators();
+                let initialized_validators = initialized_validators_rw_lock.read();
+                let validators = initialized_validators
                     .validator_definitions()
                     .iter()
-                    .map(|def| api_types::ValidatorData {
-                        enabled: def.enabled,
-                        description: def.description.clone(),
-                        voting_pubkey: PublicKeyBytes::from(&def.voting_public_key),
+                    .map(|def| {
+                        let voting_pubkey = PublicKeyBytes::from(&def.voting_public_key);
+                        api_types::ValidatorData {
+                            enabled: def.enabled,
+                            description: def.description.clone(),
+                            web3signers: web3signer_health(
+                                initialized_validators.signing_method(&voting_pubkey),
+                            ),
+                            voting_pubkey,
+                        }
                     })
                     .collect::<Vec<_>>();
 
@@ -324,16 +371,22 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
         .then(
             |validator_pubkey: PublicKey, validator_store: Arc<ValidatorStore<T, E>>| {
                 blocking_json_task(move || {
-                    let validator = validator_store
-                        .initialized_validators()
-                        .read()
+                    let initialized_validators_rw_lock = validator_store.initialized_validators();
+                    let initialized_validators = initialized_validators_rw_lock.read();
+                    let validator = initialized_validators
                         .validator_definitions()
                         .iter()
                         .find(|def| def.voting_public_key == validator_pubkey)
-                        .map(|def| api_types::ValidatorData {
-                            enabled: def.enabled,
-                            description: def.description.clone(),
-                            voting_pubkey: PublicKeyBytes::from(&def.voting_public_key),
+                        .map(|def| {
+                            let voting_pubkey = PublicKeyBytes::from(&def.voting_public_key);
+                            api_types::ValidatorData {
+                                enabled: def.enabled,
+                                description: def.description.clone(),
+                                web3signers: web3signer_health(
+                                    initialized_validators.signing_method(&voting_pubkey),
+                                ),
+                                voting_pubkey,
+                            }
                         })
                         .ok_or_else(|| {
                             warp_utils::reject::custom_not_found(format!(
@@ -347,6 +400,163 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // GET lighthouse/validators/{validator_pubkey}/duties
+    let get_lighthouse_validators_duties = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("duties"))
+        .and(warp::path::end())
+        .and(duty_history_filter.clone())
+        .then(
+            |validator_pubkey: PublicKey, duty_history: Arc<DutyHistory>| {
+                blocking_json_task(move || {
+                    let outcomes = duty_history.outcomes(&PublicKeyBytes::from(&validator_pubkey));
+                    Ok(api_types::GenericResponse::from(outcomes))
+                })
+            },
+        );
+
+    // GET lighthouse/validators/{validator_pubkey}/duties/events
+    let get_lighthouse_validators_duties_events = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("duties"))
+        .and(warp::path("events"))
+        .and(warp::path::end())
+        .and(duty_history_filter)
+        .then(
+            |validator_pubkey: PublicKey, duty_history: Arc<DutyHistory>| async move {
+                let validator_pubkey = PublicKeyBytes::from(&validator_pubkey);
+                // Build a JSON stream of the outcomes for this validator only.
+                let s =
+                    BroadcastStream::new(duty_history.subscribe()).filter_map(
+                        move |msg| match msg {
+                            Ok((pubkey, outcome)) if pubkey == validator_pubkey => {
+                                Some(Event::default().json_data(outcome).map_err(|e| {
+                                    warp_utils::reject::server_sent_event_error(format!("{:?}", e))
+                                }))
+                            }
+                            Ok(_) => None,
+                            Err(e) => Some(Err(warp_utils::reject::server_sent_event_error(
+                                format!("Unable to receive event {}", e),
+                            ))),
+                        },
+                    );
+
+                warp::sse::reply(warp::sse::keep_alive().stream(s))
+            },
+        );
+
+    // GET lighthouse/events?topics
+    let get_lighthouse_events = warp::path("lighthouse")
+        .and(warp::path("events"))
+        .and(warp::path::end())
+        .and(multi_key_query::<api_types::EventQuery>())
+        .and(validator_store_filter.clone())
+        .and_then(
+            |topics_res: Result<api_types::EventQuery, warp::Rejection>,
+             validator_store: Arc<ValidatorStore<T, E>>| async move {
+                let topics = topics_res?;
+                let event_handler = validator_store.event_handler();
+
+                // Subscribe to each topic, merging the events into a single stream.
+                let receivers = topics
+                    .topics
+                    .into_iter()
+                    .map(|topic| {
+                        BroadcastStream::new(event_handler.subscribe(topic))
+                            .map(|msg| match msg {
+                                Ok(data) => Event::default()
+                                    .event(data.topic().to_string())
+                                    .json_data(data)
+                                    .unwrap_or_else(|e| {
+                                        Event::default().comment(format!("error - bad json: {e:?}"))
+                                    }),
+                                // Do not terminate the stream if the channel fills up. Just drop
+                                // some messages and send a comment to the client.
+                                Err(BroadcastStreamRecvError::Lagged(n)) => Event::default()
+                                    .comment(format!("error - dropped {n} messages")),
+                            })
+                            .map(Ok::<_, std::convert::Infallible>)
+                    })
+                    .collect::<Vec<_>>();
+                let s = futures::stream::select_all(receivers);
+
+                Ok::<_, warp::Rejection>(warp::sse::reply(warp::sse::keep_alive().stream(s)))
+            },
+        );
+
+    // GET lighthouse/exit_escrow
+    let get_lighthouse_exit_escrow = warp::path("lighthouse")
+        .and(warp::path("exit_escrow"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(exit_escrow_filter.clone())
+        .then(
+            |validator_store: Arc<ValidatorStore<T, E>>, exit_escrow: Arc<ExitEscrow>| {
+                blocking_json_task(move || {
+                    let exits =
+                        exit_escrow.exits(|epoch| validator_store.voluntary_exit_domain(epoch));
+                    Ok(api_types::GenericResponse::from(exits))
+                })
+            },
+        );
+
+    // POST lighthouse/exit_escrow
+    let post_lighthouse_exit_escrow = warp::path("lighthouse")
+        .and(warp::path("exit_escrow"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .and(exit_escrow_filter.clone())
+        .and(slot_clock_filter.clone())
+        .and(log_filter.clone())
+        .and(task_executor_filter.clone())
+        .then(
+            |body: api_types::ExitEscrowRequest,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             exit_escrow: Arc<ExitEscrow>,
+             slot_clock: T,
+             log,
+             task_executor: TaskExecutor| {
+                blocking_json_task(move || {
+                    if let Some(handle) = task_executor.handle() {
+                        let exits = handle.block_on(escrow_exits(
+                            body,
+                            validator_store,
+                            exit_escrow,
+                            slot_clock,
+                            log,
+                        ))?;
+                        Ok(api_types::GenericResponse::from(exits))
+                    } else {
+                        Err(warp_utils::reject::custom_server_error(
+                            "Lighthouse shutting down".into(),
+                        ))
+                    }
+                })
+            },
+        );
+
+    // POST lighthouse/exit_escrow/export
+    let post_lighthouse_exit_escrow_export = warp::path("lighthouse")
+        .and(warp::path("exit_escrow"))
+        .and(warp::path("export"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .and(exit_escrow_filter)
+        .then(
+            |body: api_types::ExitEscrowExportRequest,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             exit_escrow: Arc<ExitEscrow>| {
+                blocking_json_task(move || {
+                    let bundles = export_exits(body, &exit_escrow, &validator_store)?;
+                    Ok(api_types::GenericResponse::from(bundles))
+                })
+            },
+        );
+
     // GET lighthouse/ui/health
     let get_lighthouse_ui_health = warp::path("lighthouse")
         .and(warp::path("ui"))
@@ -364,6 +574,22 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    // GET lighthouse/beacon/health
+    let get_lighthouse_beacon_health = warp::path("lighthouse")
+        .and(warp::path("beacon"))
+        .and(warp::path("health"))
+        .and(warp::path::end())
+        .and(beacon_nodes_filter)
+        .then(|beacon_nodes: Arc<BeaconNodeFallback<T, E>>| {
+            blocking_json_task(move || {
+                Ok(api_types::GenericResponse::from(
+                    api_types::BeaconNodesHealthResponse {
+                        beacon_nodes: beacon_nodes.health(),
+                    },
+                ))
+            })
+        });
+
     let get_lighthouse_ui_graffiti = warp::path("lighthouse")
         .and(warp::path("ui"))
         .and(warp::path("graffiti"))
@@ -575,6 +801,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         enabled: body.enable,
                         description: validator_def.description,
                         voting_pubkey: keypair.pk.into(),
+                        web3signers: vec![],
                     }))
                 })
             },
@@ -609,6 +836,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                 signing_definition: SigningDefinition::Web3Signer(
                                     Web3SignerDefinition {
                                         url: web3signer.url,
+                                        fallback_urls: web3signer.fallback_urls,
                                         root_certificate_path: web3signer.root_certificate_path,
                                         request_timeout_ms: web3signer.request_timeout_ms,
                                         client_identity_path: web3signer.client_identity_path,
@@ -1251,7 +1479,12 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(get_lighthouse_spec)
                         .or(get_lighthouse_validators)
                         .or(get_lighthouse_validators_pubkey)
+                        .or(get_lighthouse_validators_duties)
+                        .or(get_lighthouse_validators_duties_events)
+                        .or(get_lighthouse_events)
+                        .or(get_lighthouse_exit_escrow)
                         .or(get_lighthouse_ui_health)
+                        .or(get_lighthouse_beacon_health)
                         .or(get_lighthouse_ui_graffiti)
                         .or(get_fee_recipient)
                         .or(get_gas_limit)
@@ -1266,6 +1499,8 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(post_validators_mnemonic)
                         .or(post_validators_web3signer)
                         .or(post_validators_voluntary_exits)
+                        .or(post_lighthouse_exit_escrow)
+                        .or(post_lighthouse_exit_escrow_export)
                         .or(post_fee_recipient)
                         .or(post_gas_limit)
                         .or(post_std_keystores)
@@ -1309,3 +1544,21 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
 
     Ok((listening_socket, server))
 }
+
+/// Returns the health of the Web3Signer instances which hold a validator's key.
+///
+/// Validators which are disabled or use a local keystore have no Web3Signer instances.
+fn web3signer_health(
+    signing_method: Option<Arc<SigningMethod>>,
+) -> Vec<api_types::Web3SignerHealth> {
+    match signing_method.as_deref() {
+        Some(SigningMethod::Web3Signer { signing_urls, .. }) => signing_urls
+            .iter()
+            .map(|signing_url| api_types::Web3SignerHealth {
+                url: signing_url.instance.url().to_string(),
+                healthy: signing_url.instance.is_healthy(),
+            })
+            .collect(),
+        Some(SigningMethod::LocalKeystore { .. }) | None => vec![],
+    }
+}
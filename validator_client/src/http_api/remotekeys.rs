@@ -130,6 +130,7 @@ fn import_single_remotekey<T: SlotClock + 'static, E: EthSpec>(
         description: String::from("Added by remotekey API"),
         signing_definition: SigningDefinition::Web3Signer(Web3SignerDefinition {
             url,
+            fallback_urls: vec![],
             root_certificate_path: None,
             request_timeout_ms: None,
             client_identity_path: None,
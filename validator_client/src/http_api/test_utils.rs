@@ -1,4 +1,7 @@
 use crate::doppelganger_service::DoppelgangerService;
+use crate::duty_history::{DutyHistory, DEFAULT_DUTY_HISTORY_SIZE};
+use crate::events::ServerSentEventHandler;
+use crate::exit_escrow::ExitEscrow;
 use crate::key_cache::{KeyCache, CACHE_FILENAME};
 use crate::{
     http_api::{ApiSecret, Config as HttpConfig, Context},
@@ -108,13 +111,14 @@ impl ApiTester {
 
         let validator_store = Arc::new(ValidatorStore::<_, E>::new(
             initialized_validators,
-            slashing_protection,
+            slashing_protection.into(),
             Hash256::repeat_byte(42),
             spec,
             Some(Arc::new(DoppelgangerService::new(log.clone()))),
             slot_clock.clone(),
             &config,
             test_runtime.task_executor.clone(),
+            Arc::new(ServerSentEventHandler::new(log.clone())),
             log.clone(),
         ));
 
@@ -124,12 +128,17 @@ impl ApiTester {
 
         let initialized_validators = validator_store.initialized_validators();
 
+        let duty_history = Arc::new(DutyHistory::new(DEFAULT_DUTY_HISTORY_SIZE, log.clone()));
+
         let context = Arc::new(Context {
             task_executor: test_runtime.task_executor.clone(),
             api_secret,
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
+            beacon_nodes: None,
+            exit_escrow: Some(Arc::new(ExitEscrow::new(log.clone()))),
+            duty_history: Some(duty_history),
             graffiti_file: None,
             graffiti_flag: Some(Graffiti::default()),
             spec: E::default_spec(),
@@ -514,6 +523,7 @@ impl ApiTester {
                     builder_proposals: None,
                     voting_public_key: kp.pk,
                     url: format!("http://signer_{}.com/", i),
+                    fallback_urls: vec![],
                     root_certificate_path: None,
                     request_timeout_ms: None,
                     client_identity_path: None,
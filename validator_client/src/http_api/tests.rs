@@ -4,6 +4,9 @@
 mod keystores;
 
 use crate::doppelganger_service::DoppelgangerService;
+use crate::duty_history::{DutyHistory, DEFAULT_DUTY_HISTORY_SIZE};
+use crate::events::ServerSentEventHandler;
+use crate::exit_escrow::{decrypt_bundle, ExitEscrow};
 use crate::{
     http_api::{ApiSecret, Config as HttpConfig, Context},
     initialized_validators::InitializedValidators,
@@ -20,6 +23,7 @@ use eth2::{
     Error as ApiError,
 };
 use eth2_keystore::KeystoreBuilder;
+use futures::StreamExt;
 use logging::test_logger;
 use parking_lot::RwLock;
 use sensitive_url::SensitiveUrl;
@@ -44,6 +48,7 @@ struct ApiTester {
     client: ValidatorClientHttpClient,
     initialized_validators: Arc<RwLock<InitializedValidators>>,
     validator_store: Arc<ValidatorStore<TestingSlotClock, E>>,
+    duty_history: Arc<DutyHistory>,
     url: SensitiveUrl,
     slot_clock: TestingSlotClock,
     _validator_dir: TempDir,
@@ -96,13 +101,14 @@ impl ApiTester {
 
         let validator_store = Arc::new(ValidatorStore::<_, E>::new(
             initialized_validators,
-            slashing_protection,
+            slashing_protection.into(),
             Hash256::repeat_byte(42),
             spec.clone(),
             Some(Arc::new(DoppelgangerService::new(log.clone()))),
             slot_clock.clone(),
             &config,
             test_runtime.task_executor.clone(),
+            Arc::new(ServerSentEventHandler::new(log.clone())),
             log.clone(),
         ));
 
@@ -112,12 +118,17 @@ impl ApiTester {
 
         let initialized_validators = validator_store.initialized_validators();
 
+        let duty_history = Arc::new(DutyHistory::new(DEFAULT_DUTY_HISTORY_SIZE, log.clone()));
+
         let context = Arc::new(Context {
             task_executor: test_runtime.task_executor.clone(),
             api_secret,
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
+            beacon_nodes: None,
+            exit_escrow: Some(Arc::new(ExitEscrow::new(log.clone()))),
+            duty_history: Some(duty_history.clone()),
             graffiti_file: None,
             graffiti_flag: Some(Graffiti::default()),
             spec: E::default_spec(),
@@ -153,6 +164,7 @@ impl ApiTester {
             client,
             initialized_validators,
             validator_store,
+            duty_history,
             url,
             slot_clock,
             _validator_dir: validator_dir,
@@ -477,6 +489,7 @@ impl ApiTester {
                     prefer_builder_proposals: None,
                     voting_public_key: kp.pk,
                     url: format!("http://signer_{}.com/", i),
+                    fallback_urls: vec![format!("http://fallback_signer_{}.com/", i)],
                     root_certificate_path: None,
                     request_timeout_ms: None,
                     client_identity_path: None,
@@ -497,6 +510,29 @@ impl ApiTester {
             assert_eq!(self.vals_enabled(), initial_enabled_vals);
         };
 
+        // Instances are assumed to be healthy until they fail a health check.
+        for validator_request in &request {
+            let validator = self
+                .client
+                .get_lighthouse_validators_pubkey(&validator_request.voting_public_key.compress())
+                .await
+                .unwrap()
+                .unwrap()
+                .data;
+            let expected_web3signers = if s.enabled {
+                std::iter::once(&validator_request.url)
+                    .chain(&validator_request.fallback_urls)
+                    .map(|url| Web3SignerHealth {
+                        url: url.clone(),
+                        healthy: true,
+                    })
+                    .collect()
+            } else {
+                vec![]
+            };
+            assert_eq!(validator.web3signers, expected_web3signers);
+        }
+
         self
     }
 
@@ -520,6 +556,97 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_exit_escrow(self) -> Self {
+        let validators = self.client.get_lighthouse_validators().await.unwrap().data;
+        // Only the first validator has an index, so it is the only one which can be exited.
+        let pubkey = validators[0].voting_pubkey;
+        self.initialized_validators.write().set_index(&pubkey, 0);
+
+        let secret_key = libsecp256k1::SecretKey::random(&mut rand::thread_rng());
+        let recipient = libsecp256k1::PublicKey::from_secret_key(&secret_key)
+            .serialize_compressed()
+            .to_vec();
+
+        let epoch = Epoch::new(256);
+        let escrowed = self
+            .client
+            .post_lighthouse_exit_escrow(&ExitEscrowRequest {
+                epoch,
+                recipient: recipient.clone(),
+                pubkeys: None,
+            })
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(escrowed.len(), 1);
+        assert_eq!(escrowed[0].validator_pubkey, pubkey);
+        assert_eq!(escrowed[0].epoch, epoch);
+        assert_eq!(escrowed[0].recipient, recipient);
+        assert!(escrowed[0].valid);
+
+        // An explicitly requested validator without an index is an error.
+        let err = self
+            .client
+            .post_lighthouse_exit_escrow(&ExitEscrowRequest {
+                epoch,
+                recipient: recipient.clone(),
+                pubkeys: Some(vec![validators[1].voting_pubkey]),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 404);
+
+        // Exits cannot be escrowed without a valid recipient.
+        let err = self
+            .client
+            .post_lighthouse_exit_escrow(&ExitEscrowRequest {
+                epoch,
+                recipient: vec![0; 33],
+                pubkeys: None,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 400);
+
+        // Exits can only be exported to the recipient they were escrowed to.
+        let other_recipient = libsecp256k1::PublicKey::from_secret_key(
+            &libsecp256k1::SecretKey::random(&mut rand::thread_rng()),
+        )
+        .serialize_compressed()
+        .to_vec();
+        let err = self
+            .client
+            .post_lighthouse_exit_escrow_export(&ExitEscrowExportRequest {
+                recipient: other_recipient,
+                pubkeys: Some(vec![pubkey]),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 400);
+
+        let bundles = self
+            .client
+            .post_lighthouse_exit_escrow_export(&ExitEscrowExportRequest {
+                recipient: recipient.clone(),
+                pubkeys: None,
+            })
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(bundles.len(), 1);
+        let signed_exits = decrypt_bundle(&bundles[0], &secret_key).unwrap();
+        assert_eq!(signed_exits.len(), 1);
+        assert_eq!(signed_exits[0].message.validator_index, 0);
+        assert_eq!(signed_exits[0].message.epoch, epoch);
+
+        let exits = self.client.get_lighthouse_exit_escrow().await.unwrap().data;
+        assert_eq!(exits.len(), 1);
+        assert_eq!(exits[0].handed_out.len(), 1);
+        assert_eq!(exits[0].handed_out[0].recipient, recipient);
+
+        self
+    }
+
     fn get_current_epoch(&self) -> Epoch {
         self.slot_clock
             .now()
@@ -577,6 +704,87 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_validator_duties(self, index: usize) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        let pubkey = validator.voting_pubkey;
+
+        assert!(self
+            .client
+            .get_lighthouse_validators_duties(&pubkey)
+            .await
+            .unwrap()
+            .data
+            .is_empty());
+
+        self.duty_history
+            .record_signed(pubkey, DutyKind::Attestation, Slot::new(1), Ok(()));
+        self.duty_history.record_unsigned(
+            pubkey,
+            DutyKind::BlockProposal,
+            Slot::new(2),
+            "no beacon node".to_string(),
+        );
+        self.duty_history
+            .set_included(pubkey, DutyKind::Attestation, Slot::new(1), true);
+
+        let outcomes = self
+            .client
+            .get_lighthouse_validators_duties(&pubkey)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(outcomes, self.duty_history.outcomes(&pubkey));
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0].duty, DutyKind::Attestation);
+        assert_eq!(outcomes[0].included, Some(true));
+        assert_eq!(outcomes[1].duty, DutyKind::BlockProposal);
+        assert!(!outcomes[1].signed);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_events(self) -> Self {
+        let mut events = Box::pin(
+            self.client
+                .get_lighthouse_events(&[EventTopic::Doppelganger, EventTopic::BlockPublished])
+                .await
+                .unwrap(),
+        );
+
+        let signature = EventKind::Signature(SseSignature {
+            validator_pubkey: PublicKeyBytes::empty(),
+            message: SignedMessageKind::Attestation,
+            slot: Some(Slot::new(1)),
+        });
+        let doppelganger = EventKind::Doppelganger(SseDoppelganger {
+            validator_pubkey: PublicKeyBytes::empty(),
+            status: DoppelgangerProtectionStatus::DoppelgangerDetected,
+        });
+        let block_published = EventKind::BlockPublished(SseBlockPublished {
+            validator_pubkey: PublicKeyBytes::empty(),
+            slot: Slot::new(2),
+            block_root: Hash256::repeat_byte(1),
+            source: BlockSource::Builder,
+        });
+
+        // Events for topics which were not subscribed to must not be received.
+        let event_handler = self.validator_store.event_handler();
+        event_handler.register(signature);
+        event_handler.register(doppelganger.clone());
+        event_handler.register(block_published.clone());
+
+        for expected in [doppelganger, block_published] {
+            let event = tokio::time::timeout(Duration::from_secs(5), events.next())
+                .await
+                .expect("should receive event")
+                .unwrap()
+                .unwrap();
+            assert_eq!(event, expected);
+        }
+
+        self
+    }
+
     pub async fn set_gas_limit(self, index: usize, gas_limit: u64) -> Self {
         let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
 
@@ -858,6 +1066,37 @@ async fn routes_with_invalid_auth() {
                 .await
         })
         .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .get_lighthouse_validators_duties(&PublicKeyBytes::empty())
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client.get_lighthouse_events(&[EventTopic::Signature]).await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move { client.get_lighthouse_exit_escrow().await })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_exit_escrow(&ExitEscrowRequest {
+                    epoch: Epoch::new(0),
+                    recipient: vec![],
+                    pubkeys: None,
+                })
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_exit_escrow_export(&ExitEscrowExportRequest {
+                    recipient: vec![],
+                    pubkeys: None,
+                })
+                .await
+        })
+        .await
         .test_with_invalid_auth(|client| async move {
             client
                 .post_lighthouse_validators(vec![ValidatorRequest {
@@ -1053,6 +1292,43 @@ async fn validator_enabling() {
         .assert_validators_count(2);
 }
 
+#[tokio::test]
+async fn validator_duties() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .test_get_validator_duties(0)
+        .await
+        .test_get_validator_duties(1)
+        .await;
+}
+
+#[tokio::test]
+async fn exit_escrow() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .test_exit_escrow()
+        .await;
+}
+
+#[tokio::test]
+async fn lighthouse_events() {
+    ApiTester::new().await.test_get_lighthouse_events().await;
+}
+
 #[tokio::test]
 async fn validator_gas_limit() {
     ApiTester::new()
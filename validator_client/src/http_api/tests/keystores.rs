@@ -47,6 +47,7 @@ fn web3signer_validator_with_pubkey(pubkey: PublicKey) -> Web3SignerValidatorReq
         prefer_builder_proposals: None,
         voting_public_key: pubkey,
         url: web3_signer_url(),
+        fallback_urls: vec![],
         root_certificate_path: None,
         request_timeout_ms: None,
         client_identity_path: None,
@@ -98,6 +98,11 @@ lazy_static::lazy_static! {
         "Total count of ValidatorRegistrationData signings",
         &["status"]
     );
+    pub static ref DOPPELGANGER_DETECTIONS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_doppelganger_detections_total",
+        "Total count of messages from local validators which were signed by another client",
+        &["message"]
+    );
     pub static ref DUTIES_SERVICE_TIMES: Result<HistogramVec> = try_create_histogram_vec(
         "vc_duties_service_task_times_seconds",
         "Duration to perform duties service tasks",
@@ -182,6 +187,15 @@ lazy_static::lazy_static! {
         "vc_block_signing_times_seconds",
         "Duration to obtain a signature for a block",
     );
+    pub static ref WEB3SIGNER_HEALTHY: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "vc_web3signer_healthy",
+        "Set to 1 if the Web3Signer instance is healthy, otherwise set to 0",
+        &["web3signer"]
+    );
+    pub static ref WEB3SIGNER_FAILOVERS: Result<IntCounter> = try_create_int_counter(
+        "vc_web3signer_failovers_total",
+        "Count of signing requests retried on another Web3Signer instance",
+    );
 
     pub static ref ATTESTATION_DUTY: Result<IntGaugeVec> = try_create_int_gauge_vec(
         "vc_attestation_duty_slot",
@@ -6,9 +6,10 @@
 //! The `InitializedValidators` struct in this file serves as the source-of-truth of which
 //! validators are managed by this validator client.
 
-use crate::signing_method::SigningMethod;
+use crate::signing_method::{SigningMethod, Web3SignerInstance, Web3SignerUrl};
 use account_utils::{
     read_password, read_password_from_user, read_password_string,
+    secret_provider::SecretProvider,
     validator_definitions::{
         self, SigningDefinition, ValidatorDefinition, ValidatorDefinitions, Web3SignerDefinition,
         CONFIG_FILENAME,
@@ -25,7 +26,7 @@ use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::{self, Read};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 use std::time::Duration;
 use types::graffiti::GraffitiString;
 use types::{Address, Graffiti, Keypair, PublicKey, PublicKeyBytes};
@@ -42,9 +43,20 @@ use crate::Config;
 /// that time is outside the synchronous assumptions of Eth2.
 const DEFAULT_REMOTE_SIGNER_REQUEST_TIMEOUT: Duration = Duration::from_secs(12);
 
+/// Timeout for reading a keystore password from a `SecretProvider`.
+///
+/// Long enough for a provider command or Vault request, short enough that a hung provider does
+/// not stall the validator client indefinitely.
+const SECRET_PROVIDER_TIMEOUT: Duration = Duration::from_secs(30);
+
 // Use TTY instead of stdin to capture passwords from users.
 const USE_STDIN: bool = false;
 
+/// The remote signer instances, keyed by their base URL and the definition whose client they use.
+///
+/// Only weak references are held, so an instance is dropped once no validator uses it.
+type Web3SignerInstances = HashMap<(Url, Web3SignerDefinition), Weak<Web3SignerInstance>>;
+
 pub enum OnDecryptFailure {
     /// If the key cache fails to decrypt, create a new cache.
     CreateNew,
@@ -113,6 +125,8 @@ pub enum Error {
     /// Unable to apply an action to a validator.
     InvalidActionOnValidator,
     UnableToReadValidatorPassword(String),
+    /// There was an error reading a keystore password from a secret provider.
+    UnableToReadPasswordFromProvider(String),
     UnableToReadKeystoreFile(eth2_keystore::Error),
     UnableToSaveKeyCache(key_cache::Error),
     UnableToDecryptKeyCache(key_cache::Error),
@@ -209,6 +223,7 @@ impl InitializedValidator {
         key_cache: &mut KeyCache,
         key_stores: &mut HashMap<PathBuf, Keystore>,
         web3_signer_client_map: &mut Option<HashMap<Web3SignerDefinition, Client>>,
+        web3_signer_instances: &mut Web3SignerInstances,
         config: &Config,
     ) -> Result<Self, Error> {
         if !def.enabled {
@@ -222,6 +237,7 @@ impl InitializedValidator {
                 voting_keystore_path,
                 voting_keystore_password_path,
                 voting_keystore_password,
+                voting_keystore_password_provider,
             } => {
                 use std::collections::hash_map::Entry::*;
                 let voting_keystore = match key_stores.entry(voting_keystore_path.clone()) {
@@ -234,22 +250,43 @@ impl InitializedValidator {
                 } else {
                     let keystore = voting_keystore.clone();
                     let keystore_path = voting_keystore_path.clone();
+                    let provider_password = match (
+                        &voting_keystore_password,
+                        &voting_keystore_password_provider,
+                    ) {
+                        (None, Some(provider)) => {
+                            Some(read_password_from_provider(provider).await?)
+                        }
+                        _ => None,
+                    };
                     // Decoding a local keystore can take several seconds, therefore it's best
                     // to keep if off the core executor. This also has the fortunate effect of
                     // interrupting the potentially long-running task during shut down.
                     let (password, keypair) = tokio::task::spawn_blocking(move || {
                         Result::<_, Error>::Ok(
-                            match (voting_keystore_password_path, voting_keystore_password) {
+                            match (
+                                voting_keystore_password_path,
+                                voting_keystore_password,
+                                provider_password,
+                            ) {
                                 // If the password is supplied, use it and ignore the path
                                 // (if supplied).
-                                (_, Some(password)) => (
+                                (_, Some(password), _) => (
                                     password.as_ref().to_vec().into(),
                                     keystore
                                         .decrypt_keypair(password.as_ref())
                                         .map_err(Error::UnableToDecryptKeystore)?,
                                 ),
+                                // If a provider is supplied, use it and ignore the path
+                                // (if supplied).
+                                (_, None, Some(password)) => {
+                                    let keypair = keystore
+                                        .decrypt_keypair(password.as_ref())
+                                        .map_err(Error::UnableToDecryptKeystore)?;
+                                    (password.as_ref().to_vec().into(), keypair)
+                                }
                                 // If only the path is supplied, use the path.
-                                (Some(path), None) => {
+                                (Some(path), None, None) => {
                                     let password = read_password(path)
                                         .map_err(Error::UnableToReadVotingKeystorePassword)?;
                                     let keypair = keystore
@@ -258,7 +295,7 @@ impl InitializedValidator {
                                     (password, keypair)
                                 }
                                 // If there is no password available, maybe prompt for a password.
-                                (None, None) => {
+                                (None, None, None) => {
                                     let (password, keypair) = unlock_keystore_via_stdin_password(
                                         &keystore,
                                         &keystore_path,
@@ -295,9 +332,6 @@ impl InitializedValidator {
                 }
             }
             SigningDefinition::Web3Signer(web3_signer) => {
-                let signing_url = build_web3_signer_url(&web3_signer.url, &def.voting_public_key)
-                    .map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))?;
-
                 let request_timeout = web3_signer
                     .request_timeout_ms
                     .map(Duration::from_millis)
@@ -316,7 +350,7 @@ impl InitializedValidator {
                                 config.web3_signer_keep_alive_timeout,
                                 config.web3_signer_max_idle_connections,
                             )?;
-                            client_map.insert(web3_signer, client.clone());
+                            client_map.insert(web3_signer.clone(), client.clone());
                             client
                         }
                     }
@@ -332,14 +366,46 @@ impl InitializedValidator {
                         config.web3_signer_keep_alive_timeout,
                         config.web3_signer_max_idle_connections,
                     )?;
-                    new_web3_signer_client_map.insert(web3_signer, client.clone());
+                    new_web3_signer_client_map.insert(web3_signer.clone(), client.clone());
                     *web3_signer_client_map = Some(new_web3_signer_client_map);
                     client
                 };
 
+                // Instances are shared by every validator which uses them with the same client, so
+                // that their health is tracked once. Instances which are no longer used by any
+                // validator are forgotten.
+                web3_signer_instances.retain(|_, instance| instance.strong_count() > 0);
+                let signing_urls = std::iter::once(&web3_signer.url)
+                    .chain(&web3_signer.fallback_urls)
+                    .map(|url| {
+                        let base_url = Url::parse(url)
+                            .map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))?;
+                        let signing_url = build_web3_signer_url(url, &def.voting_public_key)
+                            .map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))?;
+                        let key = (base_url, web3_signer.clone());
+                        let instance = match web3_signer_instances.get(&key).and_then(Weak::upgrade)
+                        {
+                            Some(instance) => instance,
+                            None => {
+                                let instance = Arc::new(
+                                    Web3SignerInstance::new(key.0.clone(), http_client.clone())
+                                        .map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))?,
+                                );
+                                web3_signer_instances.insert(key, Arc::downgrade(&instance));
+                                instance
+                            }
+                        };
+                        Ok(Web3SignerUrl {
+                            signing_url,
+                            instance,
+                        })
+                    })
+                    .collect::<Result<_, Error>>()?;
+
                 SigningMethod::Web3Signer {
-                    signing_url,
+                    signing_urls,
                     http_client,
+                    request_timeout,
                     voting_public_key: def.voting_public_key,
                 }
             }
@@ -432,6 +498,14 @@ fn build_web3_signer_client(
         .map_err(Error::UnableToBuildWeb3SignerClient)
 }
 
+/// Read a keystore password from `provider`, giving up after `SECRET_PROVIDER_TIMEOUT`.
+async fn read_password_from_provider(provider: &SecretProvider) -> Result<ZeroizeString, Error> {
+    provider
+        .read_secret_with_timeout(SECRET_PROVIDER_TIMEOUT)
+        .await
+        .map_err(Error::UnableToReadPasswordFromProvider)
+}
+
 /// Try to unlock `keystore` at `keystore_path` by prompting the user via `stdin`.
 fn unlock_keystore_via_stdin_password(
     keystore: &Keystore,
@@ -445,6 +519,7 @@ fn unlock_keystore_via_stdin_password(
     eprintln!();
     eprintln!(" - voting_keystore_password");
     eprintln!(" - voting_keystore_password_path");
+    eprintln!(" - voting_keystore_password_provider");
     eprintln!();
     eprintln!(
         "You may exit and update {} or enter a password. \
@@ -484,6 +559,8 @@ pub struct InitializedValidators {
     validators: HashMap<PublicKeyBytes, InitializedValidator>,
     /// The clients used for communications with a remote signer.
     web3_signer_client_map: Option<HashMap<Web3SignerDefinition, Client>>,
+    /// The remote signer instances used by validators.
+    web3_signer_instances: Web3SignerInstances,
     /// For logging via `slog`.
     log: Logger,
     config: Config,
@@ -502,6 +579,7 @@ impl InitializedValidators {
             definitions,
             validators: HashMap::default(),
             web3_signer_client_map: None,
+            web3_signer_instances: HashMap::default(),
             config,
             log,
         };
@@ -534,6 +612,14 @@ impl InitializedValidators {
             .map(|v| v.signing_method.clone())
     }
 
+    /// Returns all the remote signer instances used by validators in `self`.
+    pub fn web3_signer_instances(&self) -> Vec<Arc<Web3SignerInstance>> {
+        self.web3_signer_instances
+            .values()
+            .filter_map(Weak::upgrade)
+            .collect()
+    }
+
     /// Add a validator definition to `self`, replacing any disabled definition with the same
     /// voting public key.
     ///
@@ -601,17 +687,24 @@ impl InitializedValidators {
                     voting_keystore_path,
                     voting_keystore_password,
                     voting_keystore_password_path,
-                    ..
+                    voting_keystore_password_provider,
                 } if is_local_keystore => {
-                    let password = match (voting_keystore_password, voting_keystore_password_path) {
-                        (Some(password), _) => Some(password.clone()),
-                        (_, Some(path)) => {
+                    let password = match (
+                        voting_keystore_password,
+                        voting_keystore_password_provider,
+                        voting_keystore_password_path,
+                    ) {
+                        (Some(password), _, _) => Some(password.clone()),
+                        (None, Some(provider), _) => {
+                            Some(read_password_from_provider(provider).await?)
+                        }
+                        (None, None, Some(path)) => {
                             password_path_opt = Some(path.clone());
                             read_password_string(path)
                                 .map(Option::Some)
                                 .map_err(Error::UnableToReadValidatorPassword)?
                         }
-                        (None, None) => None,
+                        (None, None, None) => None,
                     };
                     let keystore = Keystore::from_json_file(voting_keystore_path)
                         .map_err(Error::UnableToReadKeystoreFile)?;
@@ -946,6 +1039,46 @@ impl InitializedValidators {
         Ok(())
     }
 
+    /// Disables the validator with `voting_public_key` and removes it from `self.validators`.
+    ///
+    /// ## Notes
+    ///
+    /// Unlike `set_validator_definition_fields`, no other validators are initialized, so this
+    /// function never awaits and may be called whilst holding a blocking lock. The validator is not
+    /// removed from the key cache, which is reset the next time the validators are updated.
+    ///
+    /// Saves the `ValidatorDefinitions` to file.
+    pub fn disable_validator(&mut self, voting_public_key: &PublicKey) -> Result<(), Error> {
+        if let Some(def) = self
+            .definitions
+            .as_mut_slice()
+            .iter_mut()
+            .find(|def| def.voting_public_key == *voting_public_key)
+        {
+            def.enabled = false;
+        }
+
+        if self
+            .validators
+            .remove(&PublicKeyBytes::from(voting_public_key))
+            .is_some()
+        {
+            info!(
+                self.log,
+                "Disabled validator";
+                "voting_pubkey" => format!("{:?}", voting_public_key)
+            );
+        }
+        set_gauge(
+            &crate::http_metrics::metrics::ENABLED_VALIDATORS_COUNT,
+            self.num_enabled() as i64,
+        );
+
+        self.definitions
+            .save(&self.validators_dir)
+            .map_err(Error::UnableToSaveDefinitions)
+    }
+
     /// Sets the `InitializedValidator` and `ValidatorDefinition` `suggested_fee_recipient` values.
     ///
     /// ## Notes
@@ -1150,10 +1283,17 @@ impl InitializedValidators {
                 SigningDefinition::LocalKeystore {
                     voting_keystore_password_path,
                     voting_keystore_password,
+                    voting_keystore_password_provider,
                     voting_keystore_path,
                 } => {
                     let pw = if let Some(p) = voting_keystore_password {
                         p.as_ref().to_vec().into()
+                    } else if let Some(provider) = voting_keystore_password_provider {
+                        read_password_from_provider(provider)
+                            .await?
+                            .as_ref()
+                            .to_vec()
+                            .into()
                     } else if let Some(path) = voting_keystore_password_path {
                         read_password(path).map_err(Error::UnableToReadVotingKeystorePassword)?
                     } else {
@@ -1248,6 +1388,7 @@ impl InitializedValidators {
                             &mut key_cache,
                             &mut key_stores,
                             &mut None,
+                            &mut HashMap::new(),
                             &self.config,
                         )
                         .await
@@ -1299,6 +1440,7 @@ impl InitializedValidators {
                             &mut key_cache,
                             &mut key_stores,
                             &mut self.web3_signer_client_map,
+                            &mut self.web3_signer_instances,
                             &self.config,
                         )
                         .await
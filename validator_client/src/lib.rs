@@ -1,5 +1,6 @@
 mod attestation_service;
 mod beacon_node_fallback;
+mod beacon_node_health;
 mod block_service;
 mod check_synced;
 mod cli;
@@ -12,9 +13,13 @@ mod notifier;
 mod preparation_service;
 mod signing_method;
 mod sync_committee_service;
+mod web3signer_health;
 
 pub mod config;
 mod doppelganger_service;
+pub mod duty_history;
+pub mod events;
+pub mod exit_escrow;
 pub mod http_api;
 pub mod initialized_validators;
 pub mod validator_store;
@@ -26,7 +31,9 @@ use initialized_validators::InitializedValidators;
 use lighthouse_metrics::set_gauge;
 use monitoring_api::{MonitoringHttpClient, ProcessType};
 use sensitive_url::SensitiveUrl;
-pub use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
+pub use slashing_protection::{
+    SlashingDatabase, SlashingProtection, SlashingProtectionBackend, SLASHING_PROTECTION_FILENAME,
+};
 
 use crate::beacon_node_fallback::{
     start_fallback_updater_service, BeaconNodeFallback, CandidateBeaconNode, OfflineOnFailure,
@@ -40,8 +47,11 @@ use attestation_service::{AttestationService, AttestationServiceBuilder};
 use block_service::{BlockService, BlockServiceBuilder};
 use clap::ArgMatches;
 use duties_service::{sync::SyncDutiesMap, DutiesService};
+use duty_history::DutyHistory;
 use environment::RuntimeContext;
 use eth2::{reqwest::ClientBuilder, types::Graffiti, BeaconNodeHttpClient, StatusCode, Timeouts};
+use events::ServerSentEventHandler;
+use exit_escrow::ExitEscrow;
 use http_api::ApiSecret;
 use notifier::spawn_notifier;
 use parking_lot::RwLock;
@@ -87,6 +97,9 @@ const HTTP_GET_DEPOSIT_SNAPSHOT_QUOTIENT: u32 = 4;
 const HTTP_GET_VALIDATOR_BLOCK_TIMEOUT_QUOTIENT: u32 = 4;
 
 const DOPPELGANGER_SERVICE_NAME: &str = "doppelganger";
+const DUTY_HISTORY_SERVICE_NAME: &str = "duty_history";
+const EVENTS_SERVICE_NAME: &str = "events";
+const EXIT_ESCROW_SERVICE_NAME: &str = "exit_escrow";
 
 #[derive(Clone)]
 pub struct ProductionValidatorClient<E: EthSpec> {
@@ -97,6 +110,8 @@ pub struct ProductionValidatorClient<E: EthSpec> {
     sync_committee_service: SyncCommitteeService<SystemTimeSlotClock, E>,
     doppelganger_service: Option<Arc<DoppelgangerService>>,
     preparation_service: PreparationService<SystemTimeSlotClock, E>,
+    duty_history: Arc<DutyHistory>,
+    exit_escrow: Arc<ExitEscrow>,
     validator_store: Arc<ValidatorStore<SystemTimeSlotClock, E>>,
     slot_clock: SystemTimeSlotClock,
     http_api_listen_addr: Option<SocketAddr>,
@@ -251,23 +266,62 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
         // Create the slashing database if there are no validators, even if
         // `init_slashing_protection` is not supplied. There is no risk in creating a slashing
         // database without any validators in it.
-        let slashing_db_path = config.validator_dir.join(SLASHING_PROTECTION_FILENAME);
-        let slashing_protection = if config.init_slashing_protection || voting_pubkeys.is_empty() {
-            SlashingDatabase::open_or_create(&slashing_db_path).map_err(|e| {
-                format!(
-                    "Failed to open or create slashing protection database: {:?}",
-                    e
-                )
-            })
-        } else {
-            SlashingDatabase::open(&slashing_db_path).map_err(|e| {
-                format!(
-                    "Failed to open slashing protection database: {:?}.\n\
-                     Ensure that `slashing_protection.sqlite` is in {:?} folder",
-                    e, config.validator_dir
-                )
-            })
-        }?;
+        let init_slashing_protection = config.init_slashing_protection || voting_pubkeys.is_empty();
+        let slashing_protection: SlashingProtectionBackend = match &config
+            .slashing_protection_db_url
+        {
+            #[cfg(feature = "slashing-protection-postgres")]
+            Some(db_url) => {
+                use slashing_protection::PostgresSlashingDatabase;
+
+                info!(
+                    log,
+                    "Using PostgreSQL slashing protection database";
+                    "url" => %db_url,
+                );
+                if init_slashing_protection {
+                    PostgresSlashingDatabase::open_or_create(db_url).map_err(|e| {
+                        format!(
+                            "Failed to open or create slashing protection database: {:?}",
+                            e
+                        )
+                    })
+                } else {
+                    PostgresSlashingDatabase::open(db_url).map_err(|e| {
+                        format!(
+                            "Failed to open slashing protection database: {:?}.\n\
+                             Ensure that the database at {} has been initialized",
+                            e, db_url
+                        )
+                    })
+                }?
+                .into()
+            }
+            #[cfg(not(feature = "slashing-protection-postgres"))]
+            Some(_) => {
+                return Err("PostgreSQL slashing protection is not supported by this build".into())
+            }
+            None => {
+                let slashing_db_path = config.validator_dir.join(SLASHING_PROTECTION_FILENAME);
+                if init_slashing_protection {
+                    SlashingDatabase::open_or_create(&slashing_db_path).map_err(|e| {
+                        format!(
+                            "Failed to open or create slashing protection database: {:?}",
+                            e
+                        )
+                    })
+                } else {
+                    SlashingDatabase::open(&slashing_db_path).map_err(|e| {
+                        format!(
+                            "Failed to open slashing protection database: {:?}.\n\
+                             Ensure that `slashing_protection.sqlite` is in {:?} folder",
+                            e, config.validator_dir
+                        )
+                    })
+                }?
+                .into()
+            }
+        };
 
         // Check validator registration with slashing protection, or auto-register all validators.
         if config.init_slashing_protection {
@@ -392,6 +446,13 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
         // Initialize the number of connected, avaliable beacon nodes to 0.
         set_gauge(&http_metrics::metrics::AVAILABLE_BEACON_NODES_COUNT, 0);
 
+        let event_handler = Arc::new(ServerSentEventHandler::new(
+            context
+                .service_context(EVENTS_SERVICE_NAME.into())
+                .log()
+                .clone(),
+        ));
+
         let mut beacon_nodes: BeaconNodeFallback<_, E> = BeaconNodeFallback::new(
             candidates,
             config.broadcast_topics.clone(),
@@ -425,6 +486,8 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
 
         beacon_nodes.set_slot_clock(slot_clock.clone());
         proposer_nodes.set_slot_clock(slot_clock.clone());
+        beacon_nodes.set_event_handler(event_handler.clone());
+        proposer_nodes.set_event_handler(event_handler.clone());
 
         let beacon_nodes = Arc::new(beacon_nodes);
         start_fallback_updater_service(context.clone(), beacon_nodes.clone())?;
@@ -433,12 +496,14 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
         start_fallback_updater_service(context.clone(), proposer_nodes.clone())?;
 
         let doppelganger_service = if config.enable_doppelganger_protection {
-            Some(Arc::new(DoppelgangerService::new(
+            let mut doppelganger_service = DoppelgangerService::new(
                 context
                     .service_context(DOPPELGANGER_SERVICE_NAME.into())
                     .log()
                     .clone(),
-            )))
+            );
+            doppelganger_service.set_event_handler(event_handler.clone());
+            Some(Arc::new(doppelganger_service))
         } else {
             None
         };
@@ -452,6 +517,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             slot_clock.clone(),
             &config,
             context.executor.clone(),
+            event_handler,
             log.clone(),
         ));
 
@@ -492,10 +558,29 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             ctx.shared.write().duties_service = Some(duties_service.clone());
         }
 
+        let duty_history = Arc::new(DutyHistory::open(
+            &config.validator_dir,
+            config.duty_history_size,
+            context
+                .service_context(DUTY_HISTORY_SERVICE_NAME.into())
+                .log()
+                .clone(),
+        ));
+
+        let exit_escrow = Arc::new(ExitEscrow::open(
+            &config.validator_dir,
+            context
+                .service_context(EXIT_ESCROW_SERVICE_NAME.into())
+                .log()
+                .clone(),
+        )?);
+        exit_escrow.check_validity(|epoch| validator_store.voluntary_exit_domain(epoch));
+
         let mut block_service_builder = BlockServiceBuilder::new()
             .slot_clock(slot_clock.clone())
             .validator_store(validator_store.clone())
             .beacon_nodes(beacon_nodes.clone())
+            .duty_history(duty_history.clone())
             .runtime_context(context.service_context("block".into()))
             .graffiti(config.graffiti)
             .graffiti_file(config.graffiti_file.clone());
@@ -512,6 +597,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             .slot_clock(slot_clock.clone())
             .validator_store(validator_store.clone())
             .beacon_nodes(beacon_nodes.clone())
+            .duty_history(duty_history.clone())
             .runtime_context(context.service_context("attestation".into()))
             .build()?;
 
@@ -529,6 +615,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             validator_store.clone(),
             slot_clock.clone(),
             beacon_nodes.clone(),
+            duty_history.clone(),
             context.service_context("sync_committee".into()),
         );
 
@@ -540,6 +627,8 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             sync_committee_service,
             doppelganger_service,
             preparation_service,
+            duty_history,
+            exit_escrow,
             validator_store,
             config,
             slot_clock,
@@ -564,6 +653,9 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
                 task_executor: self.context.executor.clone(),
                 api_secret,
                 validator_store: Some(self.validator_store.clone()),
+                beacon_nodes: Some(self.beacon_nodes.clone()),
+                duty_history: Some(self.duty_history.clone()),
+                exit_escrow: Some(self.exit_escrow.clone()),
                 validator_dir: Some(self.config.validator_dir.clone()),
                 secrets_dir: Some(self.config.secrets_dir.clone()),
                 graffiti_file: self.config.graffiti_file.clone(),
@@ -617,22 +709,45 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             .start_update_service(&self.context.eth2_config.spec)
             .map_err(|e| format!("Unable to start preparation service: {}", e))?;
 
+        duty_history::start_update_service(
+            self.duty_history.clone(),
+            self.context.service_context(DUTY_HISTORY_SERVICE_NAME.into()),
+            self.duties_service.clone(),
+        )
+        .map_err(|e| format!("Unable to start duty history service: {}", e))?;
+
         if let Some(doppelganger_service) = self.doppelganger_service.clone() {
             DoppelgangerService::start_update_service(
-                doppelganger_service,
+                doppelganger_service.clone(),
                 self.context
                     .service_context(DOPPELGANGER_SERVICE_NAME.into()),
                 self.validator_store.clone(),
                 self.duties_service.beacon_nodes.clone(),
                 self.duties_service.slot_clock.clone(),
             )
-            .map_err(|e| format!("Unable to start doppelganger service: {}", e))?
+            .map_err(|e| format!("Unable to start doppelganger service: {}", e))?;
+
+            if self.config.enable_continuous_doppelganger_protection {
+                DoppelgangerService::start_continuous_detection_service(
+                    doppelganger_service,
+                    self.context
+                        .service_context(DOPPELGANGER_SERVICE_NAME.into()),
+                    self.duties_service.clone(),
+                )
+                .map_err(|e| format!("Unable to start continuous doppelganger service: {}", e))?
+            }
         } else {
             info!(log, "Doppelganger protection disabled.")
         }
 
         spawn_notifier(self).map_err(|e| format!("Failed to start notifier: {}", e))?;
 
+        web3signer_health::start_web3signer_health_service(
+            self.context.service_context("web3signer_health".into()),
+            self.duties_service.slot_clock.clone(),
+            self.validator_store.clone(),
+        );
+
         if self.config.enable_latency_measurement_service {
             latency::start_latency_service(
                 self.context.clone(),
@@ -9,7 +9,9 @@ use lockfile::Lockfile;
 use parking_lot::Mutex;
 use reqwest::{header::ACCEPT, Client};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use task_executor::TaskExecutor;
 use types::*;
 use url::Url;
@@ -26,6 +28,7 @@ pub enum Error {
         domain: Domain,
     },
     Web3SignerRequestFailed(String),
+    Web3SignerRequestRefused(String),
     Web3SignerJsonParsingFailed(String),
     ShuttingDown,
     TokioJoin(String),
@@ -33,6 +36,18 @@ pub enum Error {
     GenesisForkVersionRequired,
 }
 
+impl Error {
+    /// Returns `true` if another Web3Signer instance may be asked for the signature after this
+    /// error.
+    ///
+    /// Only transport errors, timeouts and server errors fail over. A request refused by the
+    /// signer (e.g. a `412` from its slashing protection) must not be retried elsewhere, since
+    /// another instance with a separate slashing database could sign the message.
+    fn allows_failover(&self) -> bool {
+        matches!(self, Error::Web3SignerRequestFailed(_))
+    }
+}
+
 /// Enumerates all messages that can be signed by a validator.
 pub enum SignableMessage<'a, E: EthSpec, Payload: AbstractExecPayload<E> = FullPayload<E>> {
     RandaoReveal(Epoch),
@@ -89,12 +104,163 @@ pub enum SigningMethod {
     ///
     /// See: https://docs.web3signer.consensys.net/en/latest/
     Web3Signer {
-        signing_url: Url,
+        /// The Web3Signer instances which hold this key, in order of preference.
+        signing_urls: Vec<Web3SignerUrl>,
         http_client: Client,
+        /// The time allowed to obtain a signature, including any retries on other instances.
+        request_timeout: Duration,
         voting_public_key: PublicKey,
     },
 }
 
+/// A Web3Signer instance, shared between all the validators which use it.
+pub struct Web3SignerInstance {
+    url: Url,
+    upcheck_url: Url,
+    http_client: Client,
+    healthy: AtomicBool,
+}
+
+impl Web3SignerInstance {
+    /// Create an instance at `url`, which is assumed to be healthy until a request fails.
+    pub fn new(url: Url, http_client: Client) -> Result<Self, url::ParseError> {
+        let upcheck_url = url.join("upcheck")?;
+        let instance = Self {
+            url,
+            upcheck_url,
+            http_client,
+            healthy: AtomicBool::new(true),
+        };
+        instance.set_healthy(true);
+        Ok(instance)
+    }
+
+    /// The base URL of the instance.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+
+    pub fn set_healthy(&self, healthy: bool) {
+        self.healthy.store(healthy, Ordering::Relaxed);
+        metrics::set_int_gauge(
+            &metrics::WEB3SIGNER_HEALTHY,
+            &[&self.url.origin().ascii_serialization()],
+            healthy as i64,
+        );
+    }
+
+    /// Query the `upcheck` endpoint of the instance and update its health.
+    pub async fn upcheck(&self) -> bool {
+        let healthy = self
+            .http_client
+            .get(self.upcheck_url.clone())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .is_ok();
+        self.set_healthy(healthy);
+        healthy
+    }
+}
+
+/// The URL at which a Web3Signer instance signs messages for a single validator.
+pub struct Web3SignerUrl {
+    pub signing_url: Url,
+    pub instance: Arc<Web3SignerInstance>,
+}
+
+impl Web3SignerUrl {
+    /// Request a signature, marking the instance as unhealthy if it cannot serve the request.
+    async fn request_signature<E: EthSpec, Payload: AbstractExecPayload<E>>(
+        &self,
+        http_client: &Client,
+        request: &SigningRequest<'_, E, Payload>,
+        timeout: Duration,
+    ) -> Result<Signature, Error> {
+        let response = http_client
+            .post(self.signing_url.clone())
+            .header(ACCEPT, "application/json")
+            .timeout(timeout)
+            .json(request)
+            .send()
+            .await
+            .map_err(|e| {
+                self.instance.set_healthy(false);
+                Error::Web3SignerRequestFailed(e.to_string())
+            })?;
+
+        let status = response.status();
+        if status.is_server_error() {
+            self.instance.set_healthy(false);
+        }
+
+        let response: SigningResponse = response
+            .error_for_status()
+            .map_err(|e| {
+                if status.is_client_error() {
+                    Error::Web3SignerRequestRefused(e.to_string())
+                } else {
+                    Error::Web3SignerRequestFailed(e.to_string())
+                }
+            })?
+            .json()
+            .await
+            .map_err(|e| Error::Web3SignerJsonParsingFailed(e.to_string()))?;
+
+        Ok(response.signature)
+    }
+}
+
+/// Request a signature from each of `signing_urls` in turn until one succeeds, one refuses the
+/// request or `request_timeout` elapses.
+///
+/// The healthy instances are tried first, but the unhealthy ones are still tried rather than miss
+/// the duty. Each attempt is allowed an equal share of the remaining time, so that an instance
+/// which hangs cannot use up the time needed to fail over to the others.
+async fn request_signature_with_failover<E: EthSpec, Payload: AbstractExecPayload<E>>(
+    signing_urls: &[Web3SignerUrl],
+    http_client: &Client,
+    request: &SigningRequest<'_, E, Payload>,
+    request_timeout: Duration,
+) -> Result<Signature, Error> {
+    let deadline = Instant::now() + request_timeout;
+    let (healthy, unhealthy): (Vec<_>, Vec<_>) = signing_urls
+        .iter()
+        .partition(|signing_url| signing_url.instance.is_healthy());
+    let num_instances = signing_urls.len();
+
+    let mut result = Err(Error::Web3SignerRequestFailed(
+        "No Web3Signer instance is available".to_string(),
+    ));
+    for (i, signing_url) in healthy.into_iter().chain(unhealthy).enumerate() {
+        let Some(remaining) = deadline
+            .checked_duration_since(Instant::now())
+            .filter(|remaining| !remaining.is_zero())
+        else {
+            break;
+        };
+
+        if i > 0 {
+            metrics::inc_counter(&metrics::WEB3SIGNER_FAILOVERS);
+        }
+
+        let timeout = remaining / (num_instances - i) as u32;
+        result = signing_url
+            .request_signature(http_client, request, timeout)
+            .await;
+        match &result {
+            Err(e) if e.allows_failover() => (),
+            _ => break,
+        }
+    }
+
+    result
+}
+
 /// The additional information used to construct a signature. Mostly used for protection from replay
 /// attacks.
 pub struct SigningContext {
@@ -183,8 +349,9 @@ impl SigningMethod {
                 Ok(signature)
             }
             SigningMethod::Web3Signer {
-                signing_url,
+                signing_urls,
                 http_client,
+                request_timeout,
                 ..
             } => {
                 let _timer =
@@ -240,22 +407,132 @@ impl SigningMethod {
                     object,
                 };
 
-                // Request a signature from the Web3Signer instance via HTTP(S).
-                let response: SigningResponse = http_client
-                    .post(signing_url.clone())
-                    .header(ACCEPT, "application/json")
-                    .json(&request)
-                    .send()
-                    .await
-                    .map_err(|e| Error::Web3SignerRequestFailed(e.to_string()))?
-                    .error_for_status()
-                    .map_err(|e| Error::Web3SignerRequestFailed(e.to_string()))?
-                    .json()
-                    .await
-                    .map_err(|e| Error::Web3SignerJsonParsingFailed(e.to_string()))?;
-
-                Ok(response.signature)
+                request_signature_with_failover(
+                    signing_urls,
+                    http_client,
+                    &request,
+                    *request_timeout,
+                )
+                .await
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use tokio::time::sleep;
+    use warp::http::StatusCode;
+    use warp::Filter;
+    use web3signer::MessageType;
+
+    type E = MainnetEthSpec;
+
+    /// Start a Web3Signer which responds to every signing request after `delay`.
+    fn mock_web3signer(delay: Duration) -> Url {
+        let routes = warp::post().then(move || async move {
+            sleep(delay).await;
+            warp::reply::json(&serde_json::json!({ "signature": Signature::empty() }))
+        });
+        let (addr, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        Url::parse(&format!("http://{addr}")).unwrap()
+    }
+
+    /// Start a Web3Signer which refuses every signing request with `412 Precondition Failed`, as
+    /// its slashing protection does for a slashable message.
+    fn refusing_web3signer() -> Url {
+        let routes = warp::post().map(|| {
+            warp::reply::with_status(
+                warp::reply::json(&serde_json::json!({ "message": "Slashable message" })),
+                StatusCode::PRECONDITION_FAILED,
+            )
+        });
+        let (addr, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        Url::parse(&format!("http://{addr}")).unwrap()
+    }
+
+    /// Start a Web3Signer which signs every request and counts them in `calls`.
+    fn counting_web3signer(calls: Arc<AtomicUsize>) -> Url {
+        let routes = warp::post().map(move || {
+            calls.fetch_add(1, Ordering::Relaxed);
+            warp::reply::json(&serde_json::json!({ "signature": Signature::empty() }))
+        });
+        let (addr, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        Url::parse(&format!("http://{addr}")).unwrap()
+    }
+
+    fn randao_request() -> SigningRequest<'static, E, FullPayload<E>> {
+        SigningRequest {
+            message_type: MessageType::RandaoReveal,
+            fork_info: None,
+            signing_root: Hash256::zero(),
+            object: Web3SignerObject::RandaoReveal {
+                epoch: Epoch::new(0),
+            },
+        }
+    }
+
+    fn signing_url(url: Url, http_client: &Client) -> Web3SignerUrl {
+        Web3SignerUrl {
+            signing_url: url.join("api/v1/eth2/sign/0x00").unwrap(),
+            instance: Arc::new(Web3SignerInstance::new(url, http_client.clone()).unwrap()),
+        }
+    }
+
+    #[tokio::test]
+    async fn hanging_instance_fails_over() {
+        let http_client = Client::new();
+        let signing_urls = vec![
+            signing_url(mock_web3signer(Duration::from_secs(60)), &http_client),
+            signing_url(mock_web3signer(Duration::ZERO), &http_client),
+        ];
+        let request = randao_request();
+        let request_timeout = Duration::from_secs(4);
+
+        // The hanging instance is given half of the time, leaving the rest for the second.
+        let start = Instant::now();
+        let signature =
+            request_signature_with_failover(&signing_urls, &http_client, &request, request_timeout)
+                .await
+                .unwrap();
+        assert_eq!(signature, Signature::empty());
+        assert!(start.elapsed() >= request_timeout / 2);
+        assert!(start.elapsed() < request_timeout);
+        assert!(!signing_urls[0].instance.is_healthy());
+        assert!(signing_urls[1].instance.is_healthy());
+
+        // The unhealthy instance is tried last.
+        let start = Instant::now();
+        request_signature_with_failover(&signing_urls, &http_client, &request, request_timeout)
+            .await
+            .unwrap();
+        assert!(start.elapsed() < request_timeout / 2);
+    }
+
+    #[tokio::test]
+    async fn refusal_does_not_fail_over() {
+        let http_client = Client::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let signing_urls = vec![
+            signing_url(refusing_web3signer(), &http_client),
+            signing_url(counting_web3signer(calls.clone()), &http_client),
+        ];
+
+        let result = request_signature_with_failover(
+            &signing_urls,
+            &http_client,
+            &randao_request(),
+            Duration::from_secs(4),
+        )
+        .await;
+        assert!(matches!(result, Err(Error::Web3SignerRequestRefused(_))));
+        assert_eq!(calls.load(Ordering::Relaxed), 0);
+        // A refusal is not a fault of the instance.
+        assert!(signing_urls[0].instance.is_healthy());
+    }
+}
@@ -1,10 +1,12 @@
 use crate::beacon_node_fallback::{ApiTopic, BeaconNodeFallback, RequireSynced};
 use crate::{
     duties_service::DutiesService,
+    duty_history::DutyHistory,
     validator_store::{Error as ValidatorStoreError, ValidatorStore},
     OfflineOnFailure,
 };
 use environment::RuntimeContext;
+use eth2::lighthouse_vc::types::DutyKind;
 use eth2::types::BlockId;
 use futures::future::join_all;
 use futures::future::FutureExt;
@@ -47,6 +49,7 @@ pub struct Inner<T: SlotClock + 'static, E: EthSpec> {
     validator_store: Arc<ValidatorStore<T, E>>,
     slot_clock: T,
     beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    duty_history: Arc<DutyHistory>,
     context: RuntimeContext<E>,
     /// Boolean to track whether the service has posted subscriptions to the BN at least once.
     ///
@@ -60,6 +63,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
         validator_store: Arc<ValidatorStore<T, E>>,
         slot_clock: T,
         beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+        duty_history: Arc<DutyHistory>,
         context: RuntimeContext<E>,
     ) -> Self {
         Self {
@@ -68,6 +72,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 validator_store,
                 slot_clock,
                 beacon_nodes,
+                duty_history,
                 context,
                 first_subscription_done: AtomicBool::new(false),
             }),
@@ -265,7 +270,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 )
                 .await
             {
-                Ok(signature) => Some(signature),
+                Ok(signature) => Some((signature, duty.pubkey)),
                 Err(ValidatorStoreError::UnknownPubkey(pubkey)) => {
                     // A pubkey can be missing when a validator was recently
                     // removed via the API.
@@ -286,19 +291,26 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                         "slot" => slot,
                         "error" => ?e,
                     );
+                    self.duty_history.record_unsigned(
+                        duty.pubkey,
+                        DutyKind::SyncCommitteeMessage,
+                        slot,
+                        format!("Failed to sign sync committee signature: {:?}", e),
+                    );
                     None
                 }
             }
         });
 
         // Execute all the futures in parallel, collecting any successful results.
-        let committee_signatures = &join_all(signature_futures)
+        let (ref committee_signatures, signers): (Vec<_>, Vec<_>) = join_all(signature_futures)
             .await
             .into_iter()
             .flatten()
-            .collect::<Vec<_>>();
+            .unzip();
 
-        self.beacon_nodes
+        let publish_result = self
+            .beacon_nodes
             .request(
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
@@ -310,14 +322,25 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 },
             )
             .await
-            .map_err(|e| {
-                error!(
-                    log,
-                    "Unable to publish sync committee messages";
-                    "slot" => slot,
-                    "error" => %e,
-                );
-            })?;
+            .map_err(|e| e.to_string());
+
+        for signer in signers {
+            self.duty_history.record_signed(
+                signer,
+                DutyKind::SyncCommitteeMessage,
+                slot,
+                publish_result.clone(),
+            );
+        }
+
+        publish_result.map_err(|e| {
+            error!(
+                log,
+                "Unable to publish sync committee messages";
+                "slot" => slot,
+                "error" => e,
+            );
+        })?;
 
         info!(
             log,
@@ -369,6 +392,17 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
 
         let log = self.context.log();
 
+        let record_unsigned = |error: String| {
+            for (_, aggregator_pk, _) in &subnet_aggregators {
+                self.duty_history.record_unsigned(
+                    *aggregator_pk,
+                    DutyKind::SyncCommitteeContribution,
+                    slot,
+                    error.clone(),
+                );
+            }
+        };
+
         let contribution = &self
             .beacon_nodes
             .first_success(
@@ -394,7 +428,8 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                     "slot" => slot,
                     "beacon_block_root" => ?beacon_block_root,
                     "error" => %e,
-                )
+                );
+                record_unsigned(e.to_string());
             })?
             .ok_or_else(|| {
                 crit!(
@@ -403,6 +438,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                     "slot" => slot,
                     "beacon_block_root" => ?beacon_block_root,
                 );
+                record_unsigned("No aggregate contribution found".to_string());
             })?
             .data;
 
@@ -419,7 +455,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                     )
                     .await
                 {
-                    Ok(signed_contribution) => Some(signed_contribution),
+                    Ok(signed_contribution) => Some((signed_contribution, aggregator_pk)),
                     Err(ValidatorStoreError::UnknownPubkey(pubkey)) => {
                         // A pubkey can be missing when a validator was recently
                         // removed via the API.
@@ -438,6 +474,12 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                             "slot" => slot,
                             "error" => ?e,
                         );
+                        self.duty_history.record_unsigned(
+                            aggregator_pk,
+                            DutyKind::SyncCommitteeContribution,
+                            slot,
+                            format!("Unable to sign sync committee contribution: {:?}", e),
+                        );
                         None
                     }
                 }
@@ -445,14 +487,15 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
         );
 
         // Execute all the futures in parallel, collecting any successful results.
-        let signed_contributions = &join_all(signature_futures)
+        let (ref signed_contributions, aggregators): (Vec<_>, Vec<_>) = join_all(signature_futures)
             .await
             .into_iter()
             .flatten()
-            .collect::<Vec<_>>();
+            .unzip();
 
         // Publish to the beacon node.
-        self.beacon_nodes
+        let publish_result = self
+            .beacon_nodes
             .first_success(
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
@@ -463,14 +506,25 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 },
             )
             .await
-            .map_err(|e| {
-                error!(
-                    log,
-                    "Unable to publish signed contributions and proofs";
-                    "slot" => slot,
-                    "error" => %e,
-                );
-            })?;
+            .map_err(|e| e.to_string());
+
+        for aggregator in aggregators {
+            self.duty_history.record_signed(
+                aggregator,
+                DutyKind::SyncCommitteeContribution,
+                slot,
+                publish_result.clone(),
+            );
+        }
+
+        publish_result.map_err(|e| {
+            error!(
+                log,
+                "Unable to publish signed contributions and proofs";
+                "slot" => slot,
+                "error" => e,
+            );
+        })?;
 
         info!(
             log,
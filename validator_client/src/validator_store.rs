@@ -1,14 +1,17 @@
 use crate::{
     doppelganger_service::DoppelgangerService,
+    events::ServerSentEventHandler,
     http_metrics::metrics,
     initialized_validators::InitializedValidators,
     signing_method::{Error as SigningError, SignableMessage, SigningContext, SigningMethod},
     Config,
 };
 use account_utils::validator_definitions::{PasswordStorage, ValidatorDefinition};
+use eth2::lighthouse_vc::types::{EventKind, SignedMessageKind, SseSignature};
 use parking_lot::{Mutex, RwLock};
 use slashing_protection::{
-    interchange::Interchange, InterchangeError, NotSafe, Safe, SlashingDatabase,
+    interchange::Interchange, InterchangeError, NotSafe, Safe, SlashingProtection,
+    SlashingProtectionBackend,
 };
 use slog::{crit, error, info, warn, Logger};
 use slot_clock::SlotClock;
@@ -18,12 +21,13 @@ use std::sync::Arc;
 use task_executor::TaskExecutor;
 use types::{
     attestation::Error as AttestationError, graffiti::GraffitiString, AbstractExecPayload, Address,
-    AggregateAndProof, Attestation, BeaconBlock, BlindedPayload, ChainSpec, ContributionAndProof,
-    Domain, Epoch, EthSpec, Fork, ForkName, Graffiti, Hash256, PublicKeyBytes, SelectionProof,
-    Signature, SignedAggregateAndProof, SignedBeaconBlock, SignedContributionAndProof, SignedRoot,
-    SignedValidatorRegistrationData, SignedVoluntaryExit, Slot, SyncAggregatorSelectionData,
-    SyncCommitteeContribution, SyncCommitteeMessage, SyncSelectionProof, SyncSubnetId,
-    ValidatorRegistrationData, VoluntaryExit,
+    AggregateAndProof, Attestation, AttestationData, BeaconBlock, BeaconBlockHeader,
+    BlindedPayload, ChainSpec, ContributionAndProof, Domain, Epoch, EthSpec, Fork, ForkName,
+    Graffiti, Hash256, PublicKeyBytes, SelectionProof, Signature, SignedAggregateAndProof,
+    SignedBeaconBlock, SignedContributionAndProof, SignedRoot, SignedValidatorRegistrationData,
+    SignedVoluntaryExit, Slot, SyncAggregatorSelectionData, SyncCommitteeContribution,
+    SyncCommitteeMessage, SyncSelectionProof, SyncSubnetId, ValidatorRegistrationData,
+    VoluntaryExit,
 };
 
 pub use crate::doppelganger_service::DoppelgangerStatus;
@@ -60,7 +64,7 @@ pub const DEFAULT_GAS_LIMIT: u64 = 30_000_000;
 
 pub struct ValidatorStore<T, E: EthSpec> {
     validators: Arc<RwLock<InitializedValidators>>,
-    slashing_protection: SlashingDatabase,
+    slashing_protection: SlashingProtectionBackend,
     slashing_protection_last_prune: Arc<Mutex<Epoch>>,
     genesis_validators_root: Hash256,
     spec: Arc<ChainSpec>,
@@ -74,6 +78,7 @@ pub struct ValidatorStore<T, E: EthSpec> {
     prefer_builder_proposals: bool,
     builder_boost_factor: Option<u64>,
     task_executor: TaskExecutor,
+    event_handler: Arc<ServerSentEventHandler>,
     _phantom: PhantomData<E>,
 }
 
@@ -83,13 +88,14 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         validators: InitializedValidators,
-        slashing_protection: SlashingDatabase,
+        slashing_protection: SlashingProtectionBackend,
         genesis_validators_root: Hash256,
         spec: ChainSpec,
         doppelganger_service: Option<Arc<DoppelgangerService>>,
         slot_clock: T,
         config: &Config,
         task_executor: TaskExecutor,
+        event_handler: Arc<ServerSentEventHandler>,
         log: Logger,
     ) -> Self {
         Self {
@@ -108,6 +114,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             prefer_builder_proposals: config.prefer_builder_proposals,
             builder_boost_factor: config.builder_boost_factor,
             task_executor,
+            event_handler,
             _phantom: PhantomData,
         }
     }
@@ -135,6 +142,26 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         self.validators.clone()
     }
 
+    /// Returns the handler used to publish events to `GET lighthouse/events`.
+    pub fn event_handler(&self) -> &Arc<ServerSentEventHandler> {
+        &self.event_handler
+    }
+
+    /// Publish an event for a message signed by `validator_pubkey`.
+    fn register_signature(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        message: SignedMessageKind,
+        slot: Option<Slot>,
+    ) {
+        self.event_handler
+            .register(EventKind::Signature(SseSignature {
+                validator_pubkey,
+                message,
+                slot,
+            }));
+    }
+
     /// Indicates if the `voting_public_key` exists in self and is enabled.
     pub fn has_validator(&self, voting_public_key: &PublicKeyBytes) -> bool {
         self.validators
@@ -311,6 +338,91 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             })
     }
 
+    /// Disable a validator so that it performs no further duties, updating the validator
+    /// definitions on disk.
+    pub async fn disable_validator(&self, validator_pubkey: PublicKeyBytes) -> Result<(), String> {
+        let voting_public_key = validator_pubkey
+            .decompress()
+            .map_err(|e| format!("Invalid validator pubkey: {:?}", e))?;
+        let validators = self.validators.clone();
+
+        // The definitions are written to disk, so update them on a blocking thread.
+        let update = move || validators.write().disable_validator(&voting_public_key);
+
+        self.task_executor
+            .spawn_blocking_handle(update, "disable_validator")
+            .ok_or_else(|| "Runtime shutdown".to_string())?
+            .await
+            .map_err(|e| format!("Disable validator task failed: {:?}", e))?
+            .map_err(|e| format!("Unable to disable validator: {:?}", e))
+    }
+
+    /// Returns `true` if `block_header` was proposed by `validator_pubkey` but is not recorded in
+    /// the slashing protection database, meaning that another instance of the validator signed
+    /// it.
+    ///
+    /// Validators without local slashing protection are never reported, since the database has
+    /// no record of their messages.
+    pub fn is_unrecorded_block(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        block_header: &BeaconBlockHeader,
+    ) -> Result<bool, NotSafe> {
+        if !self.requires_local_slashing_protection(validator_pubkey) {
+            return Ok(false);
+        }
+
+        let signing_epoch = block_header.slot.epoch(E::slots_per_epoch());
+        let domain_hash = self
+            .signing_context(Domain::BeaconProposer, signing_epoch)
+            .domain_hash(&self.spec);
+        self.slashing_protection
+            .has_signed_block(
+                &validator_pubkey,
+                block_header.slot,
+                block_header.signing_root(domain_hash).into(),
+            )
+            .map(|signed| !signed)
+    }
+
+    /// Returns `true` if `attestation` was signed by `validator_pubkey` but is not recorded in the
+    /// slashing protection database.
+    ///
+    /// See `Self::is_unrecorded_block`.
+    pub fn is_unrecorded_attestation(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        attestation: &AttestationData,
+    ) -> Result<bool, NotSafe> {
+        if !self.requires_local_slashing_protection(validator_pubkey) {
+            return Ok(false);
+        }
+
+        let signing_epoch = attestation.target.epoch;
+        let domain_hash = self
+            .signing_context(Domain::BeaconAttester, signing_epoch)
+            .domain_hash(&self.spec);
+        self.slashing_protection
+            .has_signed_attestation(
+                &validator_pubkey,
+                signing_epoch,
+                attestation.signing_root(domain_hash).into(),
+            )
+            .map(|signed| !signed)
+    }
+
+    /// Returns `true` if messages signed by `validator_pubkey` are recorded in the local
+    /// slashing protection database.
+    fn requires_local_slashing_protection(&self, validator_pubkey: PublicKeyBytes) -> bool {
+        self.validators
+            .read()
+            .signing_method(&validator_pubkey)
+            .is_some_and(|signing_method| {
+                signing_method
+                    .requires_local_slashing_protection(self.enable_web3signer_slashing_protection)
+            })
+    }
+
     pub fn num_voting_validators(&self) -> usize {
         self.validators.read().num_enabled()
     }
@@ -401,6 +513,8 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             )
             .await?;
 
+        self.register_signature(validator_pubkey, SignedMessageKind::RandaoReveal, None);
+
         Ok(signature)
     }
 
@@ -601,6 +715,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                         &self.task_executor,
                     )
                     .await?;
+                self.register_signature(
+                    validator_pubkey,
+                    SignedMessageKind::Block,
+                    Some(block.slot()),
+                );
                 Ok(SignedBeaconBlock::from_block(block, signature))
             }
             Ok(Safe::SameData) => {
@@ -683,6 +802,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                     .map_err(Error::UnableToSignAttestation)?;
 
                 metrics::inc_counter_vec(&metrics::SIGNED_ATTESTATIONS_TOTAL, &[metrics::SUCCESS]);
+                self.register_signature(
+                    validator_pubkey,
+                    SignedMessageKind::Attestation,
+                    Some(attestation.data().slot),
+                );
 
                 Ok(())
             }
@@ -726,6 +850,12 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         }
     }
 
+    /// Returns the domain over which a voluntary exit for `epoch` is signed.
+    pub fn voluntary_exit_domain(&self, epoch: Epoch) -> Hash256 {
+        self.signing_context(Domain::VoluntaryExit, epoch)
+            .domain_hash(&self.spec)
+    }
+
     pub async fn sign_voluntary_exit(
         &self,
         validator_pubkey: PublicKeyBytes,
@@ -745,6 +875,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .await?;
 
         metrics::inc_counter_vec(&metrics::SIGNED_VOLUNTARY_EXITS_TOTAL, &[metrics::SUCCESS]);
+        self.register_signature(validator_pubkey, SignedMessageKind::VoluntaryExit, None);
 
         Ok(SignedVoluntaryExit {
             message: voluntary_exit,
@@ -774,6 +905,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             &metrics::SIGNED_VALIDATOR_REGISTRATIONS_TOTAL,
             &[metrics::SUCCESS],
         );
+        self.register_signature(
+            validator_registration_data.pubkey,
+            SignedMessageKind::ValidatorRegistration,
+            None,
+        );
 
         Ok(SignedValidatorRegistrationData {
             message: validator_registration_data,
@@ -809,6 +945,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .await?;
 
         metrics::inc_counter_vec(&metrics::SIGNED_AGGREGATES_TOTAL, &[metrics::SUCCESS]);
+        self.register_signature(
+            validator_pubkey,
+            SignedMessageKind::AggregateAndProof,
+            Some(message.aggregate().data().slot),
+        );
 
         Ok(SignedAggregateAndProof::from_aggregate_and_proof(
             message, signature,
@@ -916,6 +1057,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             &metrics::SIGNED_SYNC_COMMITTEE_MESSAGES_TOTAL,
             &[metrics::SUCCESS],
         );
+        self.register_signature(
+            *validator_pubkey,
+            SignedMessageKind::SyncCommitteeMessage,
+            Some(slot),
+        );
 
         Ok(SyncCommitteeMessage {
             slot,
@@ -958,6 +1104,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             &metrics::SIGNED_SYNC_COMMITTEE_CONTRIBUTIONS_TOTAL,
             &[metrics::SUCCESS],
         );
+        self.register_signature(
+            aggregator_pubkey,
+            SignedMessageKind::ContributionAndProof,
+            Some(message.contribution.slot),
+        );
 
         Ok(SignedContributionAndProof { message, signature })
     }
@@ -980,28 +1131,8 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         &self,
         pubkeys: &[PublicKeyBytes],
     ) -> Result<Interchange, InterchangeError> {
-        self.slashing_protection.with_transaction(|txn| {
-            let known_pubkeys = pubkeys
-                .iter()
-                .filter_map(|pubkey| {
-                    let validator_id = self
-                        .slashing_protection
-                        .get_validator_id_ignoring_status(txn, pubkey)
-                        .ok()?;
-
-                    Some(
-                        self.slashing_protection
-                            .update_validator_status(txn, validator_id, false)
-                            .map(|()| *pubkey),
-                    )
-                })
-                .collect::<Result<Vec<PublicKeyBytes>, _>>()?;
-            self.slashing_protection.export_interchange_info_in_txn(
-                self.genesis_validators_root,
-                Some(&known_pubkeys),
-                txn,
-            )
-        })
+        self.slashing_protection
+            .export_and_disable_interchange_info(self.genesis_validators_root, pubkeys)
     }
 
     /// Prune the slashing protection database so that it remains performant.
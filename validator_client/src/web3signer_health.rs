@@ -0,0 +1,67 @@
+use crate::ValidatorStore;
+use environment::RuntimeContext;
+use futures::future::join_all;
+use slog::{info, warn};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use tokio::time::sleep;
+use types::EthSpec;
+
+/// The health checks will run 1/3rd of the way through the slot, after attestations have been
+/// signed.
+pub const SLOT_DELAY_MULTIPLIER: u32 = 1;
+pub const SLOT_DELAY_DENOMINATOR: u32 = 3;
+
+/// Starts a service that checks the health of each Web3Signer instance once per slot.
+///
+/// An instance which fails a check is only used to sign once all healthy instances have failed.
+pub fn start_web3signer_health_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    slot_clock: T,
+    validator_store: Arc<ValidatorStore<T, E>>,
+) {
+    let log = context.log().clone();
+
+    let future = async move {
+        loop {
+            let sleep_time = slot_clock
+                .duration_to_next_slot()
+                .map(|next_slot| {
+                    next_slot
+                        + (slot_clock.slot_duration() / SLOT_DELAY_DENOMINATOR)
+                            * SLOT_DELAY_MULTIPLIER
+                })
+                .unwrap_or_else(|| slot_clock.slot_duration());
+
+            sleep(sleep_time).await;
+
+            let instances = validator_store
+                .initialized_validators()
+                .read()
+                .web3_signer_instances();
+
+            join_all(instances.into_iter().map(|instance| {
+                let log = &log;
+                async move {
+                    let was_healthy = instance.is_healthy();
+                    match (was_healthy, instance.upcheck().await) {
+                        (true, false) => warn!(
+                            log,
+                            "Web3Signer instance is unhealthy";
+                            "url" => %instance.url(),
+                        ),
+                        (false, true) => info!(
+                            log,
+                            "Web3Signer instance is healthy";
+                            "url" => %instance.url(),
+                        ),
+                        _ => (),
+                    }
+                }
+            }))
+            .await;
+        }
+    };
+
+    context.executor.spawn(future, "web3signer_health");
+}
@@ -0,0 +1,441 @@
+use super::common::*;
+use crate::DumpConfig;
+use account_utils::{eth2_keystore::keypair_from_secret, read_mnemonic_from_cli};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use eth2::{
+    types::{StateId, ValidatorData, ValidatorId},
+    BeaconNodeHttpClient, SensitiveUrl, Timeouts,
+};
+use eth2_wallet::{bip39::Seed, recover_validator_secret_from_mnemonic, KeyType};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::time::Duration;
+use types::*;
+
+pub const CMD: &str = "bls-to-execution-change";
+pub const FIRST_INDEX_FLAG: &str = "first-index";
+pub const MNEMONIC_FLAG: &str = "mnemonic-path";
+pub const EXECUTION_ADDRESS_FLAG: &str = "execution-address";
+pub const BEACON_NODE_FLAG: &str = "beacon-node";
+pub const OUTPUT_PATH_FLAG: &str = "output-path";
+pub const SUBMIT_FLAG: &str = "submit";
+
+pub const SIGNED_CHANGES_FILENAME: &str = "signed_bls_to_execution_changes.json";
+
+const BEACON_NODE_HTTP_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Derives the BLS withdrawal keys for a range of validators from a BIP-39 mnemonic \
+                and signs messages which change their withdrawal credentials to an execution \
+                address. The withdrawal credentials of each validator are checked against the \
+                beacon chain before signing. The signed changes are written to a JSON file and \
+                may optionally be published to the beacon node. Publishing a change is \
+                irreversible, the withdrawal credentials of a validator can only be changed once.",
+        )
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .short('h')
+                .help("Prints help information")
+                .action(ArgAction::HelpLong)
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new(OUTPUT_PATH_FLAG)
+                .long(OUTPUT_PATH_FLAG)
+                .value_name("DIRECTORY")
+                .help(
+                    "The path to a directory where the signed changes will be written. The \
+                    directory will be created if it does not exist.",
+                )
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(FIRST_INDEX_FLAG)
+                .long(FIRST_INDEX_FLAG)
+                .value_name("FIRST_INDEX")
+                .help("The first of consecutive key indexes you wish to change.")
+                .action(ArgAction::Set)
+                .required(false)
+                .default_value("0")
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(COUNT_FLAG)
+                .long(COUNT_FLAG)
+                .value_name("VALIDATOR_COUNT")
+                .help("The number of consecutive key indexes to change.")
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(MNEMONIC_FLAG)
+                .long(MNEMONIC_FLAG)
+                .value_name("MNEMONIC_PATH")
+                .help("If present, the mnemonic will be read in from this file.")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(STDIN_INPUTS_FLAG)
+                .action(ArgAction::SetTrue)
+                .hide(cfg!(windows))
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty.")
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new(EXECUTION_ADDRESS_FLAG)
+                .long(EXECUTION_ADDRESS_FLAG)
+                .value_name("ETH1_ADDRESS")
+                .help(
+                    "The execution address which will receive the withdrawals of all the \
+                    validators. This cannot be changed once the changes are published.",
+                )
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(BEACON_NODE_FLAG)
+                .long(BEACON_NODE_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help(
+                    "A HTTP(S) address of a beacon node using the beacon-API. The beacon node \
+                    is used to find the index and withdrawal credentials of each validator.",
+                )
+                .default_value("http://localhost:5052")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(SUBMIT_FLAG)
+                .long(SUBMIT_FLAG)
+                .help(
+                    "If present, the signed changes will be published to the beacon node \
+                    after they have been written to the output file.",
+                )
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0),
+        )
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct BlsToExecutionChangeConfig {
+    pub output_path: PathBuf,
+    pub first_index: u32,
+    pub count: u32,
+    pub mnemonic_path: Option<PathBuf>,
+    pub stdin_inputs: bool,
+    pub execution_address: Address,
+    pub bn_url: SensitiveUrl,
+    pub submit: bool,
+}
+
+impl BlsToExecutionChangeConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        Ok(Self {
+            output_path: clap_utils::parse_required(matches, OUTPUT_PATH_FLAG)?,
+            first_index: clap_utils::parse_required(matches, FIRST_INDEX_FLAG)?,
+            count: clap_utils::parse_required(matches, COUNT_FLAG)?,
+            mnemonic_path: clap_utils::parse_optional(matches, MNEMONIC_FLAG)?,
+            stdin_inputs: cfg!(windows) || matches.get_flag(STDIN_INPUTS_FLAG),
+            execution_address: clap_utils::parse_required(matches, EXECUTION_ADDRESS_FLAG)?,
+            bn_url: clap_utils::parse_required(matches, BEACON_NODE_FLAG)?,
+            submit: matches.get_flag(SUBMIT_FLAG),
+        })
+    }
+}
+
+pub async fn cli_run(
+    matches: &ArgMatches,
+    spec: &ChainSpec,
+    dump_config: DumpConfig,
+) -> Result<(), String> {
+    let config = BlsToExecutionChangeConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        run(config, spec).await
+    }
+}
+
+async fn run(config: BlsToExecutionChangeConfig, spec: &ChainSpec) -> Result<(), String> {
+    let BlsToExecutionChangeConfig {
+        output_path,
+        first_index,
+        count,
+        mnemonic_path,
+        stdin_inputs,
+        execution_address,
+        bn_url,
+        submit,
+    } = config;
+
+    let derivation_indices = derivation_indices(first_index, count)?;
+
+    let signed_changes_path = prepare_output_file(&output_path, SIGNED_CHANGES_FILENAME)?;
+
+    let bn_http_client =
+        BeaconNodeHttpClient::new(bn_url, Timeouts::set_all(BEACON_NODE_HTTP_TIMEOUT));
+    let version = bn_http_client
+        .get_node_version()
+        .await
+        .map_err(|e| format!("Failed to test connection to beacon node: {:?}", e))?
+        .data
+        .version;
+    eprintln!("Connected to beacon node running version {}", version);
+
+    // The changes are signed over the genesis fork, so they are only valid on a single network.
+    let genesis = bn_http_client
+        .get_beacon_genesis()
+        .await
+        .map_err(|e| format!("Failed to get genesis from beacon node: {:?}", e))?
+        .data;
+    if genesis.genesis_fork_version != spec.genesis_fork_version {
+        return Err("Beacon node appears to be on the wrong network".to_string());
+    }
+
+    let mnemonic = read_mnemonic_from_cli(mnemonic_path, stdin_inputs)?;
+    let seed = Seed::new(&mnemonic, "");
+
+    let mut signed_changes = Vec::with_capacity(count as usize);
+    for derivation_index in derivation_indices {
+        let derive = |key_type: KeyType| -> Result<Keypair, String> {
+            let (secret, _) =
+                recover_validator_secret_from_mnemonic(seed.as_bytes(), derivation_index, key_type)
+                    .map_err(|e| format!("Unable to recover validator keys: {:?}", e))?;
+            keypair_from_secret(secret.as_bytes())
+                .map_err(|e| format!("Unable to build keypair: {:?}", e))
+        };
+
+        let voting_keypair = derive(KeyType::Voting)?;
+        let voting_pubkey = PublicKeyBytes::from(&voting_keypair.pk);
+
+        let validator = bn_http_client
+            .get_beacon_states_validator_id(StateId::Head, &ValidatorId::PublicKey(voting_pubkey))
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to get validator {:?} from beacon node: {:?}",
+                    voting_pubkey, e
+                )
+            })?
+            .ok_or_else(|| {
+                format!(
+                    "Validator {:?} at derivation index {} is not known to the beacon node",
+                    voting_pubkey, derivation_index
+                )
+            })?
+            .data;
+
+        let withdrawal_keypair = derive(KeyType::Withdrawal)?;
+        match sign_change(
+            &validator,
+            &withdrawal_keypair,
+            execution_address,
+            genesis.genesis_validators_root,
+            spec,
+        )? {
+            Some(signed_change) => {
+                eprintln!(
+                    "Signed change for validator {:?} (index {}) at derivation index {}",
+                    voting_pubkey, validator.index, derivation_index
+                );
+                signed_changes.push(signed_change);
+            }
+            None => eprintln!(
+                "Validator {:?} (index {}) already has execution withdrawal credentials",
+                voting_pubkey, validator.index
+            ),
+        }
+    }
+
+    if signed_changes.is_empty() {
+        return Err("No validators require a change of withdrawal credentials".to_string());
+    }
+
+    // Write the changes before publishing them, so that they are retained if publishing fails.
+    write_to_json_file(&signed_changes_path, &signed_changes)?;
+    eprintln!(
+        "Wrote {} signed changes to {:?}",
+        signed_changes.len(),
+        signed_changes_path
+    );
+
+    if submit {
+        bn_http_client
+            .post_beacon_pool_bls_to_execution_changes(&signed_changes)
+            .await
+            .map_err(|e| format!("Failed to publish changes to beacon node: {:?}", e))?;
+        eprintln!(
+            "Published {} changes to the beacon node",
+            signed_changes.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Signs a change of the withdrawal credentials of `validator` to `execution_address`.
+///
+/// Returns `Ok(None)` if `validator` no longer has BLS withdrawal credentials, and an error if its
+/// credentials were not derived from `withdrawal_keypair`.
+fn sign_change(
+    validator: &ValidatorData,
+    withdrawal_keypair: &Keypair,
+    execution_address: Address,
+    genesis_validators_root: Hash256,
+    spec: &ChainSpec,
+) -> Result<Option<SignedBlsToExecutionChange>, String> {
+    let withdrawal_credentials = validator.validator.withdrawal_credentials;
+    if withdrawal_credentials.as_bytes()[0] != spec.bls_withdrawal_prefix_byte {
+        return Ok(None);
+    }
+
+    let expected = Hash256::from(WithdrawalCredentials::bls(&withdrawal_keypair.pk, spec));
+    if withdrawal_credentials != expected {
+        return Err(format!(
+            "The withdrawal credentials of validator {} ({:?}) do not match the withdrawal \
+            key derived from the mnemonic ({:?})",
+            validator.index, withdrawal_credentials, expected
+        ));
+    }
+
+    let change = BlsToExecutionChange {
+        validator_index: validator.index,
+        from_bls_pubkey: withdrawal_keypair.pk.compress(),
+        to_execution_address: execution_address,
+    };
+    Ok(Some(change.sign(
+        &withdrawal_keypair.sk,
+        genesis_validators_root,
+        spec,
+    )))
+}
+
+/// Returns the range of `count` derivation indices starting at `first_index`.
+fn derivation_indices(first_index: u32, count: u32) -> Result<Range<u32>, String> {
+    if count == 0 {
+        return Err(format!("--{} cannot be 0", COUNT_FLAG));
+    }
+    let end_index = first_index.checked_add(count).ok_or_else(|| {
+        format!(
+            "--{} plus --{} cannot exceed {}",
+            FIRST_INDEX_FLAG,
+            COUNT_FLAG,
+            u32::MAX
+        )
+    })?;
+    Ok(first_index..end_index)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use eth2::types::ValidatorStatus;
+
+    fn validator_data(index: u64, withdrawal_credentials: Hash256) -> ValidatorData {
+        ValidatorData {
+            index,
+            balance: 32_000_000_000,
+            status: ValidatorStatus::ActiveOngoing,
+            validator: Validator {
+                withdrawal_credentials,
+                ..Validator::default()
+            },
+        }
+    }
+
+    #[test]
+    fn sign_change_for_bls_credentials() {
+        let spec = MainnetEthSpec::default_spec();
+        let keypair = Keypair::random();
+        let address = Address::repeat_byte(0x42);
+        let genesis_validators_root = Hash256::repeat_byte(0x01);
+        let validator = validator_data(7, WithdrawalCredentials::bls(&keypair.pk, &spec).into());
+
+        let signed_change = sign_change(
+            &validator,
+            &keypair,
+            address,
+            genesis_validators_root,
+            &spec,
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(signed_change.message.validator_index, 7);
+        assert_eq!(signed_change.message.to_execution_address, address);
+        assert_eq!(signed_change.message.from_bls_pubkey, keypair.pk.compress());
+        let domain = spec.compute_domain(
+            Domain::BlsToExecutionChange,
+            spec.genesis_fork_version,
+            genesis_validators_root,
+        );
+        assert!(signed_change
+            .signature
+            .verify(&keypair.pk, signed_change.message.signing_root(domain)));
+    }
+
+    #[test]
+    fn skip_execution_credentials() {
+        let spec = MainnetEthSpec::default_spec();
+        let keypair = Keypair::random();
+        let validator = validator_data(
+            7,
+            WithdrawalCredentials::eth1(Address::repeat_byte(0x42), &spec).into(),
+        );
+
+        assert_eq!(
+            sign_change(
+                &validator,
+                &keypair,
+                Address::zero(),
+                Hash256::zero(),
+                &spec
+            ),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn derivation_index_range() {
+        assert_eq!(derivation_indices(10, 3), Ok(10..13));
+        assert_eq!(
+            derivation_indices(u32::MAX - 1, 1),
+            Ok(u32::MAX - 1..u32::MAX)
+        );
+        assert!(derivation_indices(0, 0).is_err());
+        assert!(derivation_indices(u32::MAX, 1).is_err());
+        assert!(derivation_indices(u32::MAX - 1, 2).is_err());
+    }
+
+    #[test]
+    fn reject_mismatched_credentials() {
+        let spec = MainnetEthSpec::default_spec();
+        let validator = validator_data(
+            7,
+            WithdrawalCredentials::bls(&Keypair::random().pk, &spec).into(),
+        );
+
+        assert!(sign_change(
+            &validator,
+            &Keypair::random(),
+            Address::zero(),
+            Hash256::zero(),
+            &spec
+        )
+        .is_err());
+    }
+}
@@ -9,8 +9,10 @@ use eth2::{
     SensitiveUrl,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use tree_hash::TreeHash;
 use types::*;
 
@@ -152,6 +154,63 @@ impl ValidatorSpecification {
     }
 }
 
+/// A selection of validators on a validator client, parsed from either a list of comma-separated
+/// 0x-prefixed validator pubkeys or the keyword "all".
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum ValidatorSelection {
+    All,
+    Specific(Vec<PublicKeyBytes>),
+}
+
+impl FromStr for ValidatorSelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Self::All),
+            pubkeys => pubkeys
+                .split(',')
+                .map(PublicKeyBytes::from_str)
+                .collect::<Result<Vec<_>, _>>()
+                .map(Self::Specific),
+        }
+    }
+}
+
+impl ValidatorSelection {
+    /// Returns the selected pubkeys from the `keystores` present on the VC at `vc_url`.
+    ///
+    /// Returns an error if any specifically selected validator is not present on the VC.
+    pub fn select(
+        self,
+        keystores: &[SingleKeystoreResponse],
+        vc_url: &SensitiveUrl,
+    ) -> Result<Vec<PublicKeyBytes>, String> {
+        match self {
+            Self::All => Ok(keystores.iter().map(|v| v.validating_pubkey).collect()),
+            Self::Specific(request_pubkeys) => {
+                let vc_pubkeys: HashSet<_> =
+                    keystores.iter().map(|v| &v.validating_pubkey).collect();
+                let missing = request_pubkeys
+                    .iter()
+                    .filter(|pubkey| !vc_pubkeys.contains(pubkey))
+                    .collect::<Vec<_>>();
+                if !missing.is_empty() {
+                    for pk in &missing {
+                        eprintln!("{:?} is not present on {:?}", pk, vc_url);
+                    }
+                    return Err(format!(
+                        "{} validators not found on {:?}",
+                        missing.len(),
+                        vc_url
+                    ));
+                }
+                Ok(request_pubkeys)
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct CreateSpec {
     pub mnemonic: String,
@@ -349,6 +408,28 @@ pub async fn vc_http_client<P: AsRef<Path>>(
     Ok((http_client, remote_keystores))
 }
 
+/// Create the directory at `path` if it does not already exist, and return the path of
+/// `file_name` within it.
+///
+/// Returns an error if the file already exists, since it will not be overwritten.
+pub fn prepare_output_file(path: &Path, file_name: &str) -> Result<PathBuf, String> {
+    if !path.exists() {
+        fs::create_dir(path)
+            .map_err(|e| format!("Failed to create {:?} directory: {:?}", path, e))?;
+    } else if !path.is_dir() {
+        return Err(format!("{:?} must be a directory", path));
+    }
+
+    let file_path = path.join(file_name);
+    if file_path.exists() {
+        return Err(format!(
+            "{:?} already exists, refusing to overwrite",
+            file_path
+        ));
+    }
+    Ok(file_path)
+}
+
 /// Write some object to a file as JSON.
 ///
 /// The file must be created new, it must not already exist.
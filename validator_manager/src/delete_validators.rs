@@ -0,0 +1,353 @@
+use super::common::*;
+use crate::DumpConfig;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use eth2::{
+    lighthouse_vc::std_types::{DeleteKeystoreStatus, DeleteKeystoresRequest},
+    SensitiveUrl,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+pub const CMD: &str = "delete";
+pub const VC_URL_FLAG: &str = "vc-url";
+pub const VC_TOKEN_FLAG: &str = "vc-token";
+pub const VALIDATORS_FLAG: &str = "validators";
+pub const OUTPUT_PATH_FLAG: &str = "output-path";
+
+pub const SLASHING_PROTECTION_FILENAME: &str = "slashing_protection.json";
+
+const NO_VALIDATORS_MSG: &str = "No validators selected for deletion";
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Deletes validators from a validator client using the HTTP API. The slashing \
+                protection data for the deleted validators is exported from the validator \
+                client and written to a file, it should be imported into any validator client \
+                which runs these validators in the future. Web3Signer validators cannot be \
+                deleted with this command.",
+        )
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .short('h')
+                .help("Prints help information")
+                .action(ArgAction::HelpLong)
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new(VC_URL_FLAG)
+                .long(VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help("A HTTP(S) address of a validator client using the keymanager-API.")
+                .default_value("http://localhost:5062")
+                .requires(VC_TOKEN_FLAG)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(VC_TOKEN_FLAG)
+                .long(VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The file containing a token required by the validator client.")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(VALIDATORS_FLAG)
+                .long(VALIDATORS_FLAG)
+                .value_name("STRING")
+                .help(
+                    "The validators to be deleted. Either a list of 0x-prefixed \
+                    validator pubkeys or the keyword \"all\".",
+                )
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(OUTPUT_PATH_FLAG)
+                .long(OUTPUT_PATH_FLAG)
+                .value_name("DIRECTORY")
+                .help(
+                    "The path to a directory where the slashing protection interchange file \
+                    will be created. The directory will be created if it does not exist.",
+                )
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct DeleteConfig {
+    pub vc_url: SensitiveUrl,
+    pub vc_token_path: PathBuf,
+    pub validators: ValidatorSelection,
+    pub output_path: PathBuf,
+}
+
+impl DeleteConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        Ok(Self {
+            vc_url: clap_utils::parse_required(matches, VC_URL_FLAG)?,
+            vc_token_path: clap_utils::parse_required(matches, VC_TOKEN_FLAG)?,
+            validators: clap_utils::parse_required(matches, VALIDATORS_FLAG)?,
+            output_path: clap_utils::parse_required(matches, OUTPUT_PATH_FLAG)?,
+        })
+    }
+}
+
+pub async fn cli_run(matches: &ArgMatches, dump_config: DumpConfig) -> Result<(), String> {
+    let config = DeleteConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        run(config).await
+    }
+}
+
+async fn run(config: DeleteConfig) -> Result<(), String> {
+    let DeleteConfig {
+        vc_url,
+        vc_token_path,
+        validators,
+        output_path,
+    } = config;
+
+    // Check the output file before deleting anything, so the slashing protection data is never
+    // lost.
+    let slashing_protection_path = prepare_output_file(&output_path, SLASHING_PROTECTION_FILENAME)?;
+
+    let (http_client, keystores) = vc_http_client(vc_url.clone(), &vc_token_path).await?;
+
+    let pubkeys_to_delete = validators.select(&keystores, &vc_url)?;
+    if pubkeys_to_delete.is_empty() {
+        return Err(NO_VALIDATORS_MSG.to_string());
+    }
+
+    let readonly = keystores
+        .iter()
+        .filter(|v| pubkeys_to_delete.contains(&v.validating_pubkey) && v.readonly == Some(true))
+        .map(|v| v.validating_pubkey)
+        .collect::<Vec<_>>();
+    if !readonly.is_empty() {
+        for pk in &readonly {
+            eprintln!("{:?} is read-only and cannot be deleted", pk);
+        }
+        return Err(format!("{} read-only validators selected", readonly.len()));
+    }
+
+    let count = pubkeys_to_delete.len();
+    eprintln!("Deleting {} validators from the VC", count);
+
+    // Delete all of the validators in a single request so that the slashing protection data is
+    // exported as a single interchange.
+    let request = DeleteKeystoresRequest {
+        pubkeys: pubkeys_to_delete.clone(),
+    };
+    let response = http_client.delete_keystores(&request).await.map_err(|e| {
+        eprintln!(
+            "There was an error deleting the validators and it's unclear if they were \
+            removed or not. The validators may need to be checked with the \"list\" command."
+        );
+        format!("Deleting validators failed with {:?}", e)
+    })?;
+
+    write_to_json_file(&slashing_protection_path, &response.slashing_protection)?;
+
+    if response.data.len() != count {
+        return Err(format!(
+            "VC returned {} statuses for {} deleted validators",
+            response.data.len(),
+            count
+        ));
+    }
+
+    let mut num_failed = 0;
+    for (pubkey, status) in pubkeys_to_delete.iter().zip(&response.data) {
+        match status.status {
+            DeleteKeystoreStatus::Deleted => eprintln!("Deleted validator {:?}", pubkey),
+            DeleteKeystoreStatus::NotActive => {
+                eprintln!(
+                    "Validator {:?} was not active, its slashing protection data was exported",
+                    pubkey
+                )
+            }
+            DeleteKeystoreStatus::NotFound | DeleteKeystoreStatus::Error => {
+                num_failed += 1;
+                eprintln!(
+                    "Failed to delete validator {:?}, the VC returned status {:?} with \
+                    message {:?}",
+                    pubkey, status.status, status.message
+                );
+            }
+        }
+    }
+
+    if num_failed > 0 {
+        return Err(format!("Failed to delete {} validators", num_failed));
+    }
+
+    eprintln!(
+        "Deleted {} validators, the slashing protection data was written to {:?}",
+        count, slashing_protection_path
+    );
+
+    Ok(())
+}
+
+// The tests use crypto and are too slow in debug.
+#[cfg(not(debug_assertions))]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::import_validators::tests::TestBuilder as ImportTestBuilder;
+    use slashing_protection::interchange::Interchange;
+    use std::fs;
+    use tempfile::tempdir;
+    use types::PublicKeyBytes;
+
+    const VC_TOKEN_FILE_NAME: &str = "vc_token.json";
+
+    struct TestResult {
+        result: Result<(), String>,
+        deleted: Vec<PublicKeyBytes>,
+        remaining: Vec<PublicKeyBytes>,
+        slashing_protection: Option<Interchange>,
+    }
+
+    /// Import `count` validators to a VC, then delete the validators at `delete_indices` (or all
+    /// of them if `None`).
+    async fn run_test(count: u32, delete_indices: Option<Vec<usize>>) -> TestResult {
+        let import_test_result = ImportTestBuilder::new()
+            .await
+            .create_validators(count, 0)
+            .await
+            .run_test()
+            .await;
+        assert!(
+            import_test_result.result.is_ok(),
+            "precondition: validators are imported"
+        );
+        let vc = import_test_result.vc;
+
+        let dir = tempdir().unwrap();
+        let vc_token_path = dir.path().join(VC_TOKEN_FILE_NAME);
+        fs::write(&vc_token_path, &vc.api_token).unwrap();
+        let output_path = dir.path().join("output");
+
+        let vc_pubkeys: Vec<_> = vc
+            .client
+            .get_keystores()
+            .await
+            .unwrap()
+            .data
+            .into_iter()
+            .map(|v| v.validating_pubkey)
+            .collect();
+        let (validators, deleted) = match delete_indices {
+            Some(indices) => {
+                let pubkeys: Vec<_> = indices.into_iter().map(|i| vc_pubkeys[i]).collect();
+                (ValidatorSelection::Specific(pubkeys.clone()), pubkeys)
+            }
+            None => (ValidatorSelection::All, vc_pubkeys.clone()),
+        };
+
+        let result = run(DeleteConfig {
+            vc_url: vc.url.clone(),
+            vc_token_path,
+            validators,
+            output_path: output_path.clone(),
+        })
+        .await;
+
+        let remaining = vc
+            .client
+            .get_keystores()
+            .await
+            .unwrap()
+            .data
+            .into_iter()
+            .map(|v| v.validating_pubkey)
+            .collect();
+        let slashing_protection =
+            fs::read_to_string(output_path.join(SLASHING_PROTECTION_FILENAME))
+                .ok()
+                .map(|contents| serde_json::from_str(&contents).unwrap());
+
+        TestResult {
+            result,
+            deleted,
+            remaining,
+            slashing_protection,
+        }
+    }
+
+    impl TestResult {
+        fn assert_ok(self) {
+            assert_eq!(self.result, Ok(()));
+            for pubkey in &self.deleted {
+                assert!(
+                    !self.remaining.contains(pubkey),
+                    "deleted validator should not remain on the VC"
+                );
+            }
+
+            let slashing_protection = self
+                .slashing_protection
+                .expect("slashing protection should be written");
+            let mut exported: Vec<_> = slashing_protection
+                .data
+                .iter()
+                .map(|data| data.pubkey)
+                .collect();
+            let mut deleted = self.deleted;
+            exported.sort_unstable_by_key(PublicKeyBytes::serialize);
+            deleted.sort_unstable_by_key(PublicKeyBytes::serialize);
+            assert_eq!(exported, deleted);
+        }
+    }
+
+    #[tokio::test]
+    async fn delete_one_of_three_validators() {
+        let result = run_test(3, Some(vec![1])).await;
+        assert_eq!(result.remaining.len(), 2);
+        result.assert_ok();
+    }
+
+    #[tokio::test]
+    async fn delete_all_validators() {
+        let result = run_test(3, None).await;
+        assert!(result.remaining.is_empty());
+        result.assert_ok();
+    }
+
+    #[tokio::test]
+    async fn delete_unknown_validator() {
+        let import_test_result = ImportTestBuilder::new()
+            .await
+            .create_validators(1, 0)
+            .await
+            .run_test()
+            .await;
+        let vc = import_test_result.vc;
+
+        let dir = tempdir().unwrap();
+        let vc_token_path = dir.path().join(VC_TOKEN_FILE_NAME);
+        fs::write(&vc_token_path, &vc.api_token).unwrap();
+
+        let result = run(DeleteConfig {
+            vc_url: vc.url.clone(),
+            vc_token_path,
+            validators: ValidatorSelection::Specific(vec![PublicKeyBytes::empty()]),
+            output_path: dir.path().join("output"),
+        })
+        .await;
+
+        assert!(result.unwrap_err().contains("validators not found"));
+        assert_eq!(vc.client.get_keystores().await.unwrap().data.len(), 1);
+    }
+}
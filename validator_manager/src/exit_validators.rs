@@ -0,0 +1,322 @@
+use super::common::*;
+use crate::DumpConfig;
+use clap::{Arg, ArgAction, ArgGroup, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use eth2::{BeaconNodeHttpClient, SensitiveUrl, Timeouts};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use types::{Epoch, SignedVoluntaryExit};
+
+pub const CMD: &str = "exit";
+pub const VC_URL_FLAG: &str = "vc-url";
+pub const VC_TOKEN_FLAG: &str = "vc-token";
+pub const VALIDATORS_FLAG: &str = "validators";
+pub const EXIT_EPOCH_FLAG: &str = "exit-epoch";
+pub const BEACON_NODE_FLAG: &str = "beacon-node";
+pub const OUTPUT_PATH_FLAG: &str = "output-path";
+
+pub const SIGNED_EXITS_FILENAME: &str = "signed_voluntary_exits.json";
+
+const BEACON_NODE_HTTP_TIMEOUT: Duration = Duration::from_secs(2);
+
+const NO_VALIDATORS_MSG: &str = "No validators selected for exit";
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Signs voluntary exits for validators on a validator client using the HTTP API. \
+                The signed exits can be written to a file and/or published to a beacon node. \
+                Publishing a voluntary exit is irreversible, an exited validator can never \
+                become active again.",
+        )
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .short('h')
+                .help("Prints help information")
+                .action(ArgAction::HelpLong)
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new(VC_URL_FLAG)
+                .long(VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help("A HTTP(S) address of a validator client using the keymanager-API.")
+                .default_value("http://localhost:5062")
+                .requires(VC_TOKEN_FLAG)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(VC_TOKEN_FLAG)
+                .long(VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The file containing a token required by the validator client.")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(VALIDATORS_FLAG)
+                .long(VALIDATORS_FLAG)
+                .value_name("STRING")
+                .help(
+                    "The validators to be exited. Either a list of 0x-prefixed \
+                    validator pubkeys or the keyword \"all\".",
+                )
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(EXIT_EPOCH_FLAG)
+                .long(EXIT_EPOCH_FLAG)
+                .value_name("EPOCH")
+                .help(
+                    "The epoch at which the validators may exit. Defaults to the current epoch \
+                    of the validator client.",
+                )
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(BEACON_NODE_FLAG)
+                .long(BEACON_NODE_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help(
+                    "A HTTP(S) address of a beacon node. If this value is supplied then \
+                    the signed voluntary exits will be published to the beacon node.",
+                )
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(OUTPUT_PATH_FLAG)
+                .long(OUTPUT_PATH_FLAG)
+                .value_name("DIRECTORY")
+                .help(
+                    "The path to a directory where the signed voluntary exits will be \
+                    written. The directory will be created if it does not exist.",
+                )
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .group(
+            ArgGroup::new("destination")
+                .args([BEACON_NODE_FLAG, OUTPUT_PATH_FLAG])
+                .multiple(true)
+                .required(true),
+        )
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ExitConfig {
+    pub vc_url: SensitiveUrl,
+    pub vc_token_path: PathBuf,
+    pub validators: ValidatorSelection,
+    pub exit_epoch: Option<Epoch>,
+    pub bn_url: Option<SensitiveUrl>,
+    pub output_path: Option<PathBuf>,
+}
+
+impl ExitConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        Ok(Self {
+            vc_url: clap_utils::parse_required(matches, VC_URL_FLAG)?,
+            vc_token_path: clap_utils::parse_required(matches, VC_TOKEN_FLAG)?,
+            validators: clap_utils::parse_required(matches, VALIDATORS_FLAG)?,
+            exit_epoch: clap_utils::parse_optional(matches, EXIT_EPOCH_FLAG)?,
+            bn_url: clap_utils::parse_optional(matches, BEACON_NODE_FLAG)?,
+            output_path: clap_utils::parse_optional(matches, OUTPUT_PATH_FLAG)?,
+        })
+    }
+}
+
+pub async fn cli_run(matches: &ArgMatches, dump_config: DumpConfig) -> Result<(), String> {
+    let config = ExitConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        run(config).await
+    }
+}
+
+async fn run(config: ExitConfig) -> Result<(), String> {
+    let ExitConfig {
+        vc_url,
+        vc_token_path,
+        validators,
+        exit_epoch,
+        bn_url,
+        output_path,
+    } = config;
+
+    if bn_url.is_none() && output_path.is_none() {
+        return Err(format!(
+            "Must supply either --{BEACON_NODE_FLAG} or --{OUTPUT_PATH_FLAG}."
+        ));
+    }
+
+    let signed_exits_path = output_path
+        .map(|path| prepare_output_file(&path, SIGNED_EXITS_FILENAME))
+        .transpose()?;
+
+    let bn_http_client = if let Some(bn_url) = bn_url {
+        let bn_http_client =
+            BeaconNodeHttpClient::new(bn_url, Timeouts::set_all(BEACON_NODE_HTTP_TIMEOUT));
+        let version = bn_http_client
+            .get_node_version()
+            .await
+            .map_err(|e| format!("Failed to test connection to beacon node: {:?}", e))?
+            .data
+            .version;
+        eprintln!("Connected to beacon node running version {}", version);
+        Some(bn_http_client)
+    } else {
+        None
+    };
+
+    let (http_client, keystores) = vc_http_client(vc_url.clone(), &vc_token_path).await?;
+
+    let pubkeys_to_exit = validators.select(&keystores, &vc_url)?;
+    if pubkeys_to_exit.is_empty() {
+        return Err(NO_VALIDATORS_MSG.to_string());
+    }
+
+    let count = pubkeys_to_exit.len();
+    let mut signed_exits: Vec<SignedVoluntaryExit> = Vec::with_capacity(count);
+    for (i, pubkey) in pubkeys_to_exit.iter().enumerate() {
+        let signed_exit = http_client
+            .post_validator_voluntary_exit(pubkey, exit_epoch)
+            .await
+            .map_err(|e| format!("Failed to sign voluntary exit for {:?}: {:?}", pubkey, e))?
+            .data;
+        eprintln!("Signed voluntary exit {} of {}", i + 1, count);
+        signed_exits.push(signed_exit);
+    }
+
+    // Write the exits before publishing them, so that they are retained if publishing fails.
+    if let Some(signed_exits_path) = &signed_exits_path {
+        write_to_json_file(signed_exits_path, &signed_exits)?;
+    }
+
+    if let Some(bn_http_client) = bn_http_client {
+        let mut num_failed = 0;
+        for (pubkey, signed_exit) in pubkeys_to_exit.iter().zip(&signed_exits) {
+            match bn_http_client
+                .post_beacon_pool_voluntary_exits(signed_exit)
+                .await
+            {
+                Ok(()) => eprintln!(
+                    "Published voluntary exit for validator {:?} (index {})",
+                    pubkey, signed_exit.message.validator_index
+                ),
+                Err(e) => {
+                    num_failed += 1;
+                    eprintln!(
+                        "Failed to publish voluntary exit for validator {:?}: {:?}",
+                        pubkey, e
+                    );
+                }
+            }
+        }
+
+        if num_failed > 0 {
+            return Err(format!(
+                "Failed to publish {} of {} voluntary exits",
+                num_failed, count
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// The tests use crypto and are too slow in debug.
+#[cfg(not(debug_assertions))]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::import_validators::tests::TestBuilder as ImportTestBuilder;
+    use std::fs;
+    use tempfile::tempdir;
+
+    const VC_TOKEN_FILE_NAME: &str = "vc_token.json";
+
+    #[tokio::test]
+    async fn sign_exits_to_file() {
+        let import_test_result = ImportTestBuilder::new()
+            .await
+            .create_validators(3, 0)
+            .await
+            .run_test()
+            .await;
+        assert!(
+            import_test_result.result.is_ok(),
+            "precondition: validators are imported"
+        );
+        let vc = import_test_result.vc;
+
+        let dir = tempdir().unwrap();
+        let vc_token_path = dir.path().join(VC_TOKEN_FILE_NAME);
+        fs::write(&vc_token_path, &vc.api_token).unwrap();
+        let output_path = dir.path().join("output");
+
+        // The VC has no beacon node to learn the validator indices from, so set them manually.
+        let pubkeys: Vec<_> = vc
+            .client
+            .get_keystores()
+            .await
+            .unwrap()
+            .data
+            .into_iter()
+            .map(|v| v.validating_pubkey)
+            .collect();
+        for (index, pubkey) in pubkeys.iter().enumerate() {
+            vc.initialized_validators
+                .write()
+                .set_index(pubkey, index as u64);
+        }
+
+        let exit_epoch = Epoch::new(256);
+        run(ExitConfig {
+            vc_url: vc.url.clone(),
+            vc_token_path,
+            validators: ValidatorSelection::Specific(pubkeys[1..].to_vec()),
+            exit_epoch: Some(exit_epoch),
+            bn_url: None,
+            output_path: Some(output_path.clone()),
+        })
+        .await
+        .unwrap();
+
+        let contents = fs::read_to_string(output_path.join(SIGNED_EXITS_FILENAME)).unwrap();
+        let signed_exits: Vec<SignedVoluntaryExit> = serde_json::from_str(&contents).unwrap();
+        assert_eq!(
+            signed_exits
+                .iter()
+                .map(|exit| exit.message.validator_index)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(signed_exits
+            .iter()
+            .all(|exit| exit.message.epoch == exit_epoch));
+    }
+
+    #[tokio::test]
+    async fn exit_requires_destination() {
+        let dir = tempdir().unwrap();
+        let result = run(ExitConfig {
+            vc_url: SensitiveUrl::parse("http://localhost:5062").unwrap(),
+            vc_token_path: dir.path().join(VC_TOKEN_FILE_NAME),
+            validators: ValidatorSelection::All,
+            exit_epoch: None,
+            bn_url: None,
+            output_path: None,
+        })
+        .await;
+        assert!(result.unwrap_err().contains(BEACON_NODE_FLAG));
+    }
+}
@@ -6,9 +6,13 @@ use serde::Serialize;
 use std::path::PathBuf;
 use types::EthSpec;
 
+pub mod bls_to_execution_change;
 pub mod common;
 pub mod create_validators;
+pub mod delete_validators;
+pub mod exit_validators;
 pub mod import_validators;
+pub mod list_validators;
 pub mod move_validators;
 
 pub const CMD: &str = "validator_manager";
@@ -56,6 +60,10 @@ pub fn cli_app() -> Command {
         .subcommand(create_validators::cli_app())
         .subcommand(import_validators::cli_app())
         .subcommand(move_validators::cli_app())
+        .subcommand(list_validators::cli_app())
+        .subcommand(delete_validators::cli_app())
+        .subcommand(exit_validators::cli_app())
+        .subcommand(bls_to_execution_change::cli_app())
 }
 
 /// Run the account manager, returning an error if the operation did not succeed.
@@ -83,6 +91,18 @@ pub fn run<E: EthSpec>(matches: &ArgMatches, env: Environment<E>) -> Result<(),
                     Some((move_validators::CMD, matches)) => {
                         move_validators::cli_run(matches, dump_config).await
                     }
+                    Some((list_validators::CMD, matches)) => {
+                        list_validators::cli_run(matches, dump_config).await
+                    }
+                    Some((delete_validators::CMD, matches)) => {
+                        delete_validators::cli_run(matches, dump_config).await
+                    }
+                    Some((exit_validators::CMD, matches)) => {
+                        exit_validators::cli_run(matches, dump_config).await
+                    }
+                    Some((bls_to_execution_change::CMD, matches)) => {
+                        bls_to_execution_change::cli_run(matches, &spec, dump_config).await
+                    }
                     Some((unknown, _)) => Err(format!(
                         "{} is not a valid {} command. See --help.",
                         unknown, CMD
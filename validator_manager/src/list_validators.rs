@@ -0,0 +1,149 @@
+use super::common::*;
+use crate::DumpConfig;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use eth2::{lighthouse_vc::std_types::SingleKeystoreResponse, SensitiveUrl};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+pub const CMD: &str = "list";
+pub const VC_URL_FLAG: &str = "vc-url";
+pub const VC_TOKEN_FLAG: &str = "vc-token";
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Lists the validators on a validator client using the HTTP API. The validators \
+                are printed to stdout as JSON.",
+        )
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .short('h')
+                .help("Prints help information")
+                .action(ArgAction::HelpLong)
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new(VC_URL_FLAG)
+                .long(VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help("A HTTP(S) address of a validator client using the keymanager-API.")
+                .default_value("http://localhost:5062")
+                .requires(VC_TOKEN_FLAG)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(VC_TOKEN_FLAG)
+                .long(VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The file containing a token required by the validator client.")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ListConfig {
+    pub vc_url: SensitiveUrl,
+    pub vc_token_path: PathBuf,
+}
+
+impl ListConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        Ok(Self {
+            vc_url: clap_utils::parse_required(matches, VC_URL_FLAG)?,
+            vc_token_path: clap_utils::parse_required(matches, VC_TOKEN_FLAG)?,
+        })
+    }
+}
+
+pub async fn cli_run(matches: &ArgMatches, dump_config: DumpConfig) -> Result<(), String> {
+    let config = ListConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        let validators = run(config).await?;
+        let json = serde_json::to_string_pretty(&validators)
+            .map_err(|e| format!("Failed to serialize validators: {:?}", e))?;
+        println!("{}", json);
+        Ok(())
+    }
+}
+
+async fn run(config: ListConfig) -> Result<Vec<SingleKeystoreResponse>, String> {
+    let ListConfig {
+        vc_url,
+        vc_token_path,
+    } = config;
+
+    let (_http_client, keystores) = vc_http_client(vc_url, &vc_token_path).await?;
+
+    Ok(keystores)
+}
+
+// The tests use crypto and are too slow in debug.
+#[cfg(not(debug_assertions))]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::import_validators::tests::TestBuilder as ImportTestBuilder;
+    use std::fs;
+    use tempfile::tempdir;
+    use types::PublicKeyBytes;
+
+    const VC_TOKEN_FILE_NAME: &str = "vc_token.json";
+
+    async fn run_test(count: u32) {
+        let import_test_result = ImportTestBuilder::new()
+            .await
+            .create_validators(count, 0)
+            .await
+            .run_test()
+            .await;
+        assert!(
+            import_test_result.result.is_ok(),
+            "precondition: validators are imported"
+        );
+        let vc = import_test_result.vc;
+
+        let dir = tempdir().unwrap();
+        let vc_token_path = dir.path().join(VC_TOKEN_FILE_NAME);
+        fs::write(&vc_token_path, &vc.api_token).unwrap();
+
+        let listed = run(ListConfig {
+            vc_url: vc.url.clone(),
+            vc_token_path,
+        })
+        .await
+        .unwrap();
+
+        let mut listed_pubkeys: Vec<_> = listed.iter().map(|v| v.validating_pubkey).collect();
+        let mut vc_pubkeys: Vec<_> = vc
+            .client
+            .get_lighthouse_validators()
+            .await
+            .unwrap()
+            .data
+            .into_iter()
+            .map(|v| v.voting_pubkey)
+            .collect();
+        listed_pubkeys.sort_unstable_by_key(PublicKeyBytes::serialize);
+        vc_pubkeys.sort_unstable_by_key(PublicKeyBytes::serialize);
+
+        assert_eq!(listed_pubkeys.len(), count as usize);
+        assert_eq!(listed_pubkeys, vc_pubkeys);
+    }
+
+    #[tokio::test]
+    async fn list_one_validator() {
+        run_test(1).await;
+    }
+
+    #[tokio::test]
+    async fn list_three_validators() {
+        run_test(3).await;
+    }
+}